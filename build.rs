@@ -0,0 +1,74 @@
+/// ビルド時のメタデータを環境変数として埋め込む
+///
+/// `vidyeet version` で表示する git コミット・ビルド日・ターゲットトリプルを
+/// `VIDYEET_GIT_COMMIT` / `VIDYEET_BUILD_DATE` / `VIDYEET_TARGET` として設定します。
+/// git が使えない環境（ソースアーカイブからのビルドなど）では `unknown` になります。
+/// 再現可能ビルドのため、`SOURCE_DATE_EPOCH` が設定されていればビルド日に使用します。
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    println!("cargo:rustc-env=VIDYEET_GIT_COMMIT={}", git_commit());
+    println!("cargo:rustc-env=VIDYEET_BUILD_DATE={}", build_date());
+    println!(
+        "cargo:rustc-env=VIDYEET_TARGET={}",
+        std::env::var("TARGET").unwrap_or_else(|_| "unknown".to_string())
+    );
+
+    // コミットが変わった場合のみ再実行する（存在しないパスを指定すると毎回再実行になるため確認する）
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    for path in [".git/HEAD", ".git/refs/heads"] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={}", path);
+        }
+    }
+}
+
+/// 短縮コミットハッシュ
+fn git_commit() -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_string())
+        .filter(|commit| !commit.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// ビルド日（UTC、YYYY-MM-DD）
+fn build_date() -> String {
+    let secs = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse::<u64>().ok())
+        .or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_secs())
+        });
+    match secs {
+        Some(secs) => {
+            let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+            format!("{:04}-{:02}-{:02}", year, month, day)
+        }
+        None => "unknown".to_string(),
+    }
+}
+
+/// 1970-01-01からの日数を年月日へ変換する（グレゴリオ暦）
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
@@ -0,0 +1,159 @@
+/// API操作の監査ログ
+///
+/// config.tomlの `[audit] enabled = true` の場合に、状態を変更するAPI呼び出し
+/// （POST/PUT/PATCH/DELETE）をJSONL形式で追記します（追記のみ、書き換え・削除はしない）。
+/// 共有のサービスアカウントでも「誰が何を削除したか」を追えるよう、
+/// エンドポイント・アセットID・ステータスコードに加えて、プロファイル・マスクしたToken ID・OSのユーザー名を記録します。
+///
+/// 設定はプロセス内で最初の記録時に一度だけ読み込むため、
+/// 各コマンドやApiClientに引数として引き回す必要はありません。
+use crate::api::auth::AuthManager;
+use crate::api::error::InfraError;
+use crate::config::UserConfig;
+use crate::config::user::DEFAULT_PROFILE;
+use reqwest::Response;
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// 既定の記録先ファイル名（データディレクトリ配下）
+const AUDIT_FILE_NAME: &str = "audit.jsonl";
+
+/// 記録する（状態を変更する）HTTPメソッド
+const AUDITED_METHODS: &[&str] = &["POST", "PUT", "PATCH", "DELETE"];
+
+/// 監査ログの出力先と記録者（無効な場合はNone）
+static AUDIT_SINK: OnceLock<Option<AuditSink>> = OnceLock::new();
+
+/// 監査ログの出力先と記録者
+#[derive(Debug)]
+struct AuditSink {
+    path: PathBuf,
+    profile: String,
+    token_id: Option<String>,
+    user: Option<String>,
+}
+
+/// 監査ログの1行
+#[derive(Debug, Serialize)]
+struct AuditEntry<'a> {
+    /// 記録日時（Unix timestamp）
+    timestamp: String,
+    method: &'a str,
+    endpoint: &'a str,
+    /// エンドポイントから分かる場合のみ（`/video/v1/assets/{id}...`）
+    asset_id: Option<&'a str>,
+    /// レスポンスを受け取れなかった場合はNone
+    status_code: Option<u16>,
+    /// 通信エラーの内容（レスポンスを受け取れなかった場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    profile: &'a str,
+    /// マスクしたToken ID
+    token_id: Option<&'a str>,
+    /// OSのユーザー名（USER / USERNAME）
+    user: Option<&'a str>,
+}
+
+/// 監査ログを読み込んだ設定から初期化する（無効・設定が読めない場合はNone）
+fn sink() -> Option<&'static AuditSink> {
+    AUDIT_SINK
+        .get_or_init(|| {
+            let config = UserConfig::load().ok()?;
+            if !config.audit.enabled {
+                return None;
+            }
+            let path = match &config.audit.path {
+                Some(path) => PathBuf::from(path),
+                None => UserConfig::data_dir().ok()?.join(AUDIT_FILE_NAME),
+            };
+            let token_id = config.get_auth().ok().map(|auth| {
+                AuthManager::new(auth.token_id.clone(), auth.token_secret.clone())
+                    .get_masked_token_id()
+            });
+            let user = std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .ok();
+            Some(AuditSink {
+                path,
+                profile: DEFAULT_PROFILE.to_string(),
+                token_id,
+                user,
+            })
+        })
+        .as_ref()
+}
+
+/// API呼び出しの結果を監査ログに記録する（GETなど状態を変更しないメソッドは記録しない）
+///
+/// 記録の失敗は警告にとどめ、API呼び出しの結果には影響させません。
+///
+/// # Arguments
+/// * `method` - HTTPメソッド名
+/// * `endpoint` - エンドポイントパス
+/// * `result` - 最終試行の結果
+pub fn record(method: &str, endpoint: &str, result: &Result<Response, InfraError>) {
+    if !AUDITED_METHODS.contains(&method) {
+        return;
+    }
+    let Some(sink) = sink() else {
+        return;
+    };
+
+    let (status_code, error) = match result {
+        Ok(response) => (Some(response.status().as_u16()), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    let entry = AuditEntry {
+        timestamp: chrono::Utc::now().timestamp().to_string(),
+        method,
+        endpoint,
+        asset_id: asset_id_of(endpoint),
+        status_code,
+        error,
+        profile: &sink.profile,
+        token_id: sink.token_id.as_deref(),
+        user: sink.user.as_deref(),
+    };
+
+    if let Err(e) = append(&sink.path, &entry) {
+        eprintln!("Warning: Failed to write audit log: {}", e);
+    }
+}
+
+/// 1行追記する
+fn append(path: &Path, entry: &AuditEntry) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let line = serde_json::to_string(entry)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+/// エンドポイントからアセットIDを取り出す（`/video/v1/assets/{id}` とその配下のみ）
+fn asset_id_of(endpoint: &str) -> Option<&str> {
+    let path = endpoint.split('?').next().unwrap_or(endpoint);
+    path.strip_prefix("/video/v1/assets/")?
+        .split('/')
+        .next()
+        .filter(|id| !id.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_id_of() {
+        assert_eq!(asset_id_of("/video/v1/assets/abc123"), Some("abc123"));
+        assert_eq!(
+            asset_id_of("/video/v1/assets/abc123/playback-ids"),
+            Some("abc123")
+        );
+        assert_eq!(asset_id_of("/video/v1/assets"), None);
+        assert_eq!(asset_id_of("/video/v1/uploads/up1/cancel"), None);
+    }
+}
@@ -7,6 +7,7 @@ use crate::api::error::InfraError;
 use base64::{Engine as _, engine::general_purpose};
 
 /// 認証マネージャー
+#[derive(Clone)]
 pub struct AuthManager {
     token_id: String,
     token_secret: String,
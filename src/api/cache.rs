@@ -0,0 +1,250 @@
+/// list/show用のレスポンスキャッシュ
+///
+/// GETレスポンスをデータディレクトリ配下（`<config_dir>/vidyeet/cache/`）に保存し、
+/// 短時間の連続実行ではネットワークに出ずにキャッシュを返します。
+/// 保存から`max_age`を過ぎたエントリは、ETag（If-None-Match）や
+/// Last-Modified（If-Modified-Since）による条件付きリクエストで再検証し、
+/// 304 Not Modifiedならキャッシュ済みのボディを再利用します。
+///
+/// キャッシュは性能のためのベストエフォートであり、読み書きの失敗は無視されます。
+/// アセットを変更する操作（削除・アップロード）の後は`invalidate_default()`で破棄します。
+use crate::api::error::InfraError;
+use crate::config::{APP_CONFIG, UserConfig};
+use reqwest::header::{CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// キャッシュディレクトリ名（データディレクトリ配下）
+const CACHE_DIR_NAME: &str = "cache";
+
+/// FNV-1a 64bit のオフセット基底
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a 64bit の素数
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// キャッシュエントリ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// レスポンスのETag
+    pub etag: Option<String>,
+    /// レスポンスのLast-Modified
+    pub last_modified: Option<String>,
+    /// 取得（または再検証）した時刻（Unix秒）
+    pub fetched_at: u64,
+    /// レスポンスボディ（JSON）
+    pub body: String,
+}
+
+impl CacheEntry {
+    /// レスポンスヘッダーとボディから新しいエントリを作成
+    pub fn from_response_parts(
+        etag: Option<String>,
+        last_modified: Option<String>,
+        body: String,
+    ) -> Self {
+        Self {
+            etag,
+            last_modified,
+            fetched_at: unix_now(),
+            body,
+        }
+    }
+
+    /// 再検証なしで使用できるか
+    pub fn is_fresh(&self, max_age: Duration) -> bool {
+        unix_now().saturating_sub(self.fetched_at) < max_age.as_secs()
+    }
+
+    /// 再検証済みとして取得時刻を更新
+    pub fn touch(&mut self) {
+        self.fetched_at = unix_now();
+    }
+
+    /// 条件付きリクエストのヘッダーを付与
+    pub fn apply_conditional_headers(&self, mut request: RequestBuilder) -> RequestBuilder {
+        if let Some(etag) = &self.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        request
+    }
+
+    /// キャッシュ済みのボディから200 OKのレスポンスを再構築
+    pub fn to_response(&self) -> Result<Response, InfraError> {
+        let response = http::Response::builder()
+            .status(StatusCode::OK)
+            .header(CONTENT_TYPE, "application/json")
+            .body(self.body.clone())
+            .map_err(|e| InfraError::network(format!("Failed to build cached response: {}", e)))?;
+        Ok(Response::from(response))
+    }
+}
+
+/// レスポンスからETagとLast-Modifiedを取り出す
+pub fn validators(response: &Response) -> (Option<String>, Option<String>) {
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    (header(ETAG), header(LAST_MODIFIED))
+}
+
+/// ディスク上のレスポンスキャッシュ
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    /// キャッシュディレクトリ
+    dir: PathBuf,
+    /// 再検証なしで使用できる期間
+    max_age: Duration,
+}
+
+impl ResponseCache {
+    /// キャッシュディレクトリと有効期間を指定して作成
+    pub fn new(dir: PathBuf, max_age: Duration) -> Self {
+        Self { dir, max_age }
+    }
+
+    /// 既定の場所（データディレクトリ配下）とAPP_CONFIGの有効期間で作成
+    pub fn open_default() -> Option<Self> {
+        UserConfig::data_dir().ok().map(|dir| {
+            Self::new(
+                dir.join(CACHE_DIR_NAME),
+                Duration::from_secs(APP_CONFIG.api.cache_max_age_secs),
+            )
+        })
+    }
+
+    /// 既定の場所のキャッシュをすべて破棄（アセット変更後に呼ぶ）
+    pub fn invalidate_default() {
+        if let Some(cache) = Self::open_default() {
+            cache.clear();
+        }
+    }
+
+    /// 再検証なしで使用できる期間
+    pub fn max_age(&self) -> Duration {
+        self.max_age
+    }
+
+    /// キャッシュキーを生成
+    ///
+    /// 認証情報ごと（アカウントごと）にキャッシュを分離するため、
+    /// 認証ヘッダーとエンドポイントの組からハッシュを計算します。
+    /// ファイル名にはハッシュのみを使用し、認証情報そのものは保存しません。
+    pub fn key(endpoint: &str, auth_header: Option<&str>) -> String {
+        let mut hash = FNV_OFFSET_BASIS;
+        for byte in auth_header
+            .unwrap_or_default()
+            .bytes()
+            .chain(std::iter::once(b'\n'))
+            .chain(endpoint.bytes())
+        {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+        format!("{:016x}", hash)
+    }
+
+    /// エントリを読み込む（存在しない・壊れている場合はNone）
+    pub fn load(&self, key: &str) -> Option<CacheEntry> {
+        let content = fs::read_to_string(self.entry_path(key)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// エントリを保存する（失敗は無視）
+    pub fn store(&self, key: &str, entry: &CacheEntry) {
+        if fs::create_dir_all(&self.dir).is_err() {
+            return;
+        }
+        if let Ok(content) = serde_json::to_string(entry) {
+            let _ = fs::write(self.entry_path(key), content);
+        }
+    }
+
+    /// すべてのエントリを破棄する（失敗は無視）
+    pub fn clear(&self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+
+    /// エントリのファイルパス
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+}
+
+/// 現在時刻（Unix秒）
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_separates_accounts_and_endpoints() {
+        let a = ResponseCache::key("/video/v1/assets", Some("Basic aaa"));
+        let b = ResponseCache::key("/video/v1/assets", Some("Basic bbb"));
+        let c = ResponseCache::key("/video/v1/assets/x", Some("Basic aaa"));
+
+        assert_ne!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a, ResponseCache::key("/video/v1/assets", Some("Basic aaa")));
+        assert!(!a.contains("aaa"));
+    }
+
+    #[test]
+    fn test_store_load_and_clear() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::new(dir.path().join("cache"), Duration::from_secs(10));
+        let key = ResponseCache::key("/video/v1/assets", None);
+
+        assert!(cache.load(&key).is_none());
+
+        let entry = CacheEntry::from_response_parts(
+            Some("\"v1\"".to_string()),
+            None,
+            "{\"data\":[]}".to_string(),
+        );
+        cache.store(&key, &entry);
+
+        let loaded = cache.load(&key).expect("entry should be cached");
+        assert_eq!(loaded.etag.as_deref(), Some("\"v1\""));
+        assert_eq!(loaded.body, "{\"data\":[]}");
+        assert!(loaded.is_fresh(cache.max_age()));
+
+        cache.clear();
+        assert!(cache.load(&key).is_none());
+    }
+
+    #[test]
+    fn test_entry_freshness() {
+        let mut entry = CacheEntry::from_response_parts(None, None, String::new());
+        entry.fetched_at -= 60;
+        assert!(!entry.is_fresh(Duration::from_secs(10)));
+
+        entry.touch();
+        assert!(entry.is_fresh(Duration::from_secs(10)));
+    }
+
+    #[tokio::test]
+    async fn test_cached_response_round_trip() {
+        let entry = CacheEntry::from_response_parts(None, None, "{\"ok\":true}".to_string());
+        let response = entry.to_response().unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), "{\"ok\":true}");
+    }
+}
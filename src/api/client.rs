@@ -1,38 +1,175 @@
 /// HTTPクライアント
 ///
 /// Mux Videoとの通信を担当するHTTPクライアント。
-/// タイムアウト、エラーハンドリング、HTTP Basic認証を含みます。
+/// タイムアウト、エラーハンドリング、HTTP Basic認証、
+/// 一時的な障害に対する自動リトライ、クライアント側レート制限、
+/// `--debug-http` 指定時の通信トレースを含みます。
+use crate::api::audit;
+use crate::api::cache::{self, CacheEntry, ResponseCache};
 use crate::api::error::InfraError;
+use crate::api::pagination::{PageCursor, PageResponse};
+use crate::api::rate_limit::{BandwidthLimiter, RateLimiter};
+use crate::api::retry::RetryPolicy;
+use crate::api::trace;
 use crate::config::APP_CONFIG;
-use reqwest::{Client, Response};
-use std::time::Duration;
+use futures_util::stream::{self, Stream, TryStreamExt};
+use reqwest::{Body, Client, Response, StatusCode};
+use std::time::{Duration, Instant};
 
 /// APIクライアントの結果型
 type ApiResult<T> = Result<T, InfraError>;
 
+#[cfg(not(any(feature = "rustls", feature = "native-tls")))]
+compile_error!("a TLS backend is required: enable the `rustls` (default) or `native-tls` feature");
+
+/// ビルドに使用したTLSバックエンド（両方のfeatureが有効な場合は `native-tls` を優先）
+#[cfg(feature = "native-tls")]
+pub const TLS_BACKEND: &str = "native-tls";
+
+/// ビルドに使用したTLSバックエンド（両方のfeatureが有効な場合は `native-tls` を優先）
+#[cfg(not(feature = "native-tls"))]
+pub const TLS_BACKEND: &str = "rustls";
+
+/// HTTPコネクションのチューニング設定
+///
+/// コネクションの再利用（keep-alive、プール）を適切に設定することで、
+/// 複数チャンクのアップロードやバッチコマンドで毎回のTCP/TLSハンドシェイクを省き、
+/// 高レイテンシ回線でのスループットを改善します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionOptions {
+    /// TCP keep-aliveの間隔（Noneで無効）
+    pub tcp_keepalive: Option<Duration>,
+
+    /// アイドル状態のコネクションをプールに保持する時間（Noneで無期限）
+    pub pool_idle_timeout: Option<Duration>,
+
+    /// ホストごとに保持するアイドルコネクションの最大数
+    pub pool_max_idle_per_host: usize,
+
+    /// HTTP/2 prior knowledge（ALPNネゴシエーションなしでHTTP/2を使用）
+    /// HTTP/2非対応のサーバーでは接続できなくなるため、既定では無効
+    pub http2_prior_knowledge: bool,
+}
+
+impl Default for ConnectionOptions {
+    /// APP_CONFIGの設定値からコネクション設定を構築
+    fn default() -> Self {
+        Self {
+            tcp_keepalive: Some(Duration::from_secs(APP_CONFIG.api.tcp_keepalive_secs)),
+            pool_idle_timeout: Some(Duration::from_secs(APP_CONFIG.api.pool_idle_timeout_secs)),
+            pool_max_idle_per_host: APP_CONFIG.api.pool_max_idle_per_host,
+            http2_prior_knowledge: APP_CONFIG.api.http2_prior_knowledge,
+        }
+    }
+}
+
 /// APIクライアント
+///
+/// クローンしたクライアントはHTTPコネクションプール・レートリミッター・帯域リミッターを共有します。
+/// 複数タスクで並行してAPIを呼ぶ場合はクローンを渡してください。
+#[derive(Clone)]
 pub struct ApiClient {
     client: Client,
     base_url: String,
+    retry_policy: RetryPolicy,
+    rate_limiter: RateLimiter,
+    bandwidth_limiter: Option<BandwidthLimiter>,
+    cache: Option<ResponseCache>,
 }
 
 impl ApiClient {
     /// 新しいAPIクライアントを作成
     ///
+    /// コネクション設定はAPP_CONFIGの既定値（`ConnectionOptions::default()`）を使用します。
+    ///
     /// # Arguments
     /// * `base_url` - APIのベースURL（例: "https://api.mux.com"）
     ///
     /// # Returns
     /// 設定済みのAPIクライアント
     pub fn new(base_url: String) -> ApiResult<Self> {
+        Self::with_connection_options(base_url, ConnectionOptions::default())
+    }
+
+    /// コネクション設定を指定してAPIクライアントを作成
+    ///
+    /// # Arguments
+    /// * `base_url` - APIのベースURL（例: "https://api.mux.com"）
+    /// * `options` - keep-alive、プール、HTTP/2の設定
+    pub fn with_connection_options(
+        base_url: String,
+        options: ConnectionOptions,
+    ) -> ApiResult<Self> {
         let timeout = Duration::from_secs(APP_CONFIG.api.timeout_seconds);
 
-        let client = Client::builder()
+        let mut builder = Client::builder()
             .timeout(timeout)
+            .tcp_keepalive(options.tcp_keepalive)
+            .pool_idle_timeout(options.pool_idle_timeout)
+            .pool_max_idle_per_host(options.pool_max_idle_per_host);
+
+        if options.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+
+        // 既定のrustlsに加えてnative-tlsを指定した場合は、明示したnative-tlsを使う
+        #[cfg(feature = "native-tls")]
+        {
+            builder = builder.use_native_tls();
+        }
+        #[cfg(all(feature = "rustls", not(feature = "native-tls")))]
+        {
+            builder = builder.use_rustls_tls();
+        }
+
+        let client = builder
             .build()
             .map_err(|e| InfraError::network(format!("Failed to create HTTP client: {}", e)))?;
 
-        Ok(Self { client, base_url })
+        Ok(Self {
+            client,
+            base_url,
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: RateLimiter::default(),
+            bandwidth_limiter: None,
+            cache: None,
+        })
+    }
+
+    /// レートリミッターを差し替える
+    ///
+    /// 別々に生成したクライアント間で同じ制限を共有したい場合に、
+    /// 共有の`RateLimiter`（のクローン）を渡します。
+    #[allow(dead_code)]
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// リトライポリシーを差し替える
+    ///
+    /// デフォルトではAPP_CONFIGの設定値（`RetryPolicy::default()`）が使用されます。
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// チャンク送信の帯域を制限する
+    ///
+    /// クローンしたクライアントは同じ制限を共有するため、
+    /// 並行アップロード全体の送信帯域が上限以下に抑えられます。
+    pub fn with_bandwidth_limiter(mut self, bandwidth_limiter: BandwidthLimiter) -> Self {
+        self.bandwidth_limiter = Some(bandwidth_limiter);
+        self
+    }
+
+    /// GETレスポンスのキャッシュを有効にする
+    ///
+    /// キャッシュが有効な間、`get()`は新しいレスポンスをキャッシュから返し、
+    /// 古くなったものは条件付きリクエストで再検証します。
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
     }
 
     /// デフォルトのプロダクション環境クライアントを作成
@@ -46,10 +183,135 @@ impl ApiClient {
     /// * `endpoint` - エンドポイントパス（例: "/video/v1/assets"）
     /// * `auth_header` - HTTP Basic認証ヘッダー（オプション）
     pub async fn get(&self, endpoint: &str, auth_header: Option<&str>) -> ApiResult<Response> {
+        if let Some(cache) = &self.cache {
+            return self.get_with_cache(cache, endpoint, auth_header).await;
+        }
+
+        let url = self.build_url(endpoint);
+
+        self.send_with_retry(
+            || self.build_request(self.client.get(&url), auth_header),
+            endpoint,
+            "GET",
+        )
+        .await
+    }
+
+    /// キャッシュを使用してGETリクエストを送信
+    ///
+    /// 1. 有効期間内のキャッシュがあればネットワークに出ずに返す
+    /// 2. 期限切れのキャッシュがあれば条件付きリクエストを送り、304ならキャッシュを返す
+    /// 3. 200ならボディをキャッシュに保存して返す（それ以外のレスポンスはそのまま返す）
+    async fn get_with_cache(
+        &self,
+        cache: &ResponseCache,
+        endpoint: &str,
+        auth_header: Option<&str>,
+    ) -> ApiResult<Response> {
+        let key = ResponseCache::key(endpoint, auth_header);
+        let cached = cache.load(&key);
+
+        if let Some(entry) = &cached
+            && entry.is_fresh(cache.max_age())
+        {
+            return entry.to_response();
+        }
+
         let url = self.build_url(endpoint);
-        let request = self.build_request(self.client.get(&url), auth_header);
+        let response = self
+            .send_with_retry(
+                || {
+                    let request = self.build_request(self.client.get(&url), auth_header);
+                    match &cached {
+                        Some(entry) => entry.apply_conditional_headers(request),
+                        None => request,
+                    }
+                },
+                endpoint,
+                "GET",
+            )
+            .await?;
+
+        if response.status() == StatusCode::NOT_MODIFIED
+            && let Some(mut entry) = cached
+        {
+            entry.touch();
+            cache.store(&key, &entry);
+            return entry.to_response();
+        }
+
+        if response.status() != StatusCode::OK {
+            return Ok(response);
+        }
 
-        Self::send_with_error_handling(request, endpoint, "GET").await
+        let (etag, last_modified) = cache::validators(&response);
+        let body = response.text().await.map_err(|e| {
+            InfraError::network(format!(
+                "Failed to read response for GET {}: {}",
+                endpoint, e
+            ))
+        })?;
+        let entry = CacheEntry::from_response_parts(etag, last_modified, body);
+        cache.store(&key, &entry);
+        entry.to_response()
+    }
+
+    /// 一覧系エンドポイントの全ページを順に取得し、要素のストリームとして返す
+    ///
+    /// `next_cursor` があればカーソルを、なければ `page` パラメータを進めて
+    /// 最終ページまで取得します。ページは必要になった時点で遅延取得されます。
+    /// 各ページのリクエストにはリトライとレート制限が適用されます。
+    ///
+    /// # Arguments
+    /// * `endpoint` - エンドポイントパス（例: "/video/v1/assets"）
+    /// * `auth_header` - HTTP Basic認証ヘッダー（オプション）
+    pub fn get_paginated<'a, T>(
+        &'a self,
+        endpoint: &'a str,
+        auth_header: Option<&'a str>,
+    ) -> impl Stream<Item = ApiResult<T>> + 'a
+    where
+        T: serde::de::DeserializeOwned + 'a,
+    {
+        stream::try_unfold(Some(PageCursor::first()), move |cursor| async move {
+            let Some(cursor) = cursor else {
+                return ApiResult::Ok(None);
+            };
+
+            let page = self.get_page(endpoint, &cursor, auth_header).await?;
+            Ok(Some(page))
+        })
+        .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
+    /// 一覧系エンドポイントの1ページを取得し、要素と次のページ位置（最終ページならNone）を返す
+    ///
+    /// 途中から再開できるよう、ページ位置を呼び出し側で保持する場合に使います。
+    ///
+    /// # Arguments
+    /// * `endpoint` - エンドポイントパス（例: "/video/v1/assets"）
+    /// * `cursor` - 取得するページの位置
+    /// * `auth_header` - HTTP Basic認証ヘッダー（オプション）
+    pub async fn get_page<T>(
+        &self,
+        endpoint: &str,
+        cursor: &PageCursor,
+        auth_header: Option<&str>,
+    ) -> ApiResult<(Vec<T>, Option<PageCursor>)>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let page_size = APP_CONFIG.api.page_size;
+
+        let response = self
+            .get(&cursor.apply(endpoint, page_size), auth_header)
+            .await?;
+        let response = Self::check_response(response, endpoint).await?;
+        let page: PageResponse<T> = Self::parse_json(response).await?;
+
+        let next = cursor.next(page.next_cursor.as_deref(), page.data.len(), page_size);
+        Ok((page.data, next))
     }
 
     /// POSTリクエストを送信
@@ -65,9 +327,57 @@ impl ApiClient {
         auth_header: Option<&str>,
     ) -> ApiResult<Response> {
         let url = self.build_url(endpoint);
-        let request = self.build_request(self.client.post(&url).json(body), auth_header);
 
-        Self::send_with_error_handling(request, endpoint, "POST").await
+        self.send_with_retry(
+            || self.build_request(self.client.post(&url).json(body), auth_header),
+            endpoint,
+            "POST",
+        )
+        .await
+    }
+
+    /// PATCHリクエストを送信
+    ///
+    /// # Arguments
+    /// * `endpoint` - エンドポイントパス（例: "/video/v1/assets/{ASSET_ID}"）
+    /// * `body` - リクエストボディ（JSON）
+    /// * `auth_header` - HTTP Basic認証ヘッダー（オプション）
+    pub async fn patch<T: serde::Serialize>(
+        &self,
+        endpoint: &str,
+        body: &T,
+        auth_header: Option<&str>,
+    ) -> ApiResult<Response> {
+        let url = self.build_url(endpoint);
+
+        self.send_with_retry(
+            || self.build_request(self.client.patch(&url).json(body), auth_header),
+            endpoint,
+            "PATCH",
+        )
+        .await
+    }
+
+    /// API宛てのPUTリクエストを送信
+    ///
+    /// # Arguments
+    /// * `endpoint` - エンドポイントパス（例: "/video/v1/uploads/{UPLOAD_ID}/cancel"）
+    /// * `body` - リクエストボディ（JSON）
+    /// * `auth_header` - HTTP Basic認証ヘッダー（オプション）
+    pub async fn put_json<T: serde::Serialize>(
+        &self,
+        endpoint: &str,
+        body: &T,
+        auth_header: Option<&str>,
+    ) -> ApiResult<Response> {
+        let url = self.build_url(endpoint);
+
+        self.send_with_retry(
+            || self.build_request(self.client.put(&url).json(body), auth_header),
+            endpoint,
+            "PUT",
+        )
+        .await
     }
 
     /// PUTリクエストを送信（ファイルアップロード用）
@@ -82,26 +392,53 @@ impl ApiClient {
         body: Vec<u8>,
         content_type: &str,
     ) -> Result<Response, InfraError> {
-        let response = self
+        let request = self
             .client
             .put(url)
             .header("Content-Type", content_type)
-            .body(body)
-            .send()
-            .await
-            .map_err(|e| {
-                if e.is_timeout() {
-                    InfraError::Timeout {
-                        operation: format!("PUT {}", url),
-                    }
-                } else if e.is_connect() {
-                    InfraError::network(format!("Connection failed to {}: {}", url, e))
-                } else {
-                    InfraError::network(format!("Request failed: {}", e))
-                }
-            })?;
+            .body(body);
 
-        Ok(response)
+        self.send_with_error_handling(request, url, "PUT").await
+    }
+
+    /// チャンクをPUTで送信（Direct Uploadのチャンクアップロード用）
+    ///
+    /// クライアントのコネクションプールを再利用するため、
+    /// 連続するチャンク送信でハンドシェイクが発生しません。
+    /// 帯域リミッターが設定されている場合は、送信前にチャンクサイズ分の許可を待ちます。
+    ///
+    /// ストリーミングボディは長さが事前に分からないため、
+    /// `content_length`を明示してContent-Lengthヘッダーを付与します
+    /// （チャンク転送エンコーディングにしない）。
+    ///
+    /// # Arguments
+    /// * `url` - 完全なURL（Mux Direct UploadのURL）
+    /// * `body` - チャンクのボディ（`Bytes`またはファイルからのストリーム）
+    /// * `content_length` - チャンクのバイト数
+    /// * `content_type` - Content-Typeヘッダー
+    /// * `content_range` - Content-Rangeヘッダー（例: "bytes 0-1023/4096"）
+    pub async fn put_chunk(
+        &self,
+        url: &str,
+        body: Body,
+        content_length: u64,
+        content_type: &str,
+        content_range: &str,
+    ) -> ApiResult<Response> {
+        if let Some(limiter) = &self.bandwidth_limiter {
+            limiter.acquire(content_length).await;
+        }
+
+        let request = self
+            .client
+            .put(url)
+            .header("Content-Type", content_type)
+            .header("Content-Length", content_length)
+            .header("Content-Range", content_range)
+            .body(body);
+
+        self.send_with_error_handling(request, "direct upload URL", "PUT")
+            .await
     }
 
     /// DELETEリクエストを送信
@@ -111,9 +448,13 @@ impl ApiClient {
     /// * `auth_header` - HTTP Basic認証ヘッダー（オプション）
     pub async fn delete(&self, endpoint: &str, auth_header: Option<&str>) -> ApiResult<Response> {
         let url = self.build_url(endpoint);
-        let request = self.build_request(self.client.delete(&url), auth_header);
 
-        Self::send_with_error_handling(request, endpoint, "DELETE").await
+        self.send_with_retry(
+            || self.build_request(self.client.delete(&url), auth_header),
+            endpoint,
+            "DELETE",
+        )
+        .await
     }
 
     /// URLを構築
@@ -133,13 +474,90 @@ impl ApiClient {
         request
     }
 
+    /// リトライポリシーに従ってリクエストを送信する
+    ///
+    /// ネットワークエラー・タイムアウト・5xxレスポンスの場合、
+    /// 指数バックオフで待機してから同じリクエストを再構築して再送します。
+    /// 最終試行の結果（成功レスポンス、4xx/5xxレスポンス、エラー）はそのまま返します。
+    ///
+    /// # Arguments
+    /// * `build` - 試行ごとにリクエストを構築するクロージャ
+    /// * `endpoint` - エンドポイント名（エラーメッセージ用）
+    /// * `method` - HTTPメソッド名（エラーメッセージ用）
+    async fn send_with_retry<F>(
+        &self,
+        build: F,
+        endpoint: &str,
+        method: &str,
+    ) -> ApiResult<Response>
+    where
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        let max_attempts = self.retry_policy.max_attempts.max(1);
+        let mut attempt = 0;
+
+        loop {
+            // 試行ごとにレート制限の許可を待つ（リトライもリクエスト数に含める）
+            self.rate_limiter.acquire().await;
+            let result = self
+                .send_with_error_handling(build(), endpoint, method)
+                .await;
+            attempt += 1;
+
+            let retry_reason = match &result {
+                Ok(response) if RetryPolicy::is_retryable_status(response.status()) => {
+                    Some(format!("HTTP {}", response.status()))
+                }
+                Err(e) if RetryPolicy::is_retryable_error(e) => Some(e.to_string()),
+                _ => None,
+            };
+
+            let Some(reason) = retry_reason else {
+                audit::record(method, endpoint, &result);
+                return result;
+            };
+            if attempt >= max_attempts {
+                audit::record(method, endpoint, &result);
+                return result;
+            }
+
+            let backoff = self.retry_policy.backoff(attempt - 1);
+            eprintln!(
+                "{} {} failed (attempt {}/{}), retrying in {}ms: {}",
+                method,
+                endpoint,
+                attempt,
+                max_attempts,
+                backoff.as_millis(),
+                reason
+            );
+            tokio::time::sleep(backoff).await;
+        }
+    }
+
     /// リクエストを送信し、エラーハンドリングを行う
+    ///
+    /// トレースが有効な場合は送信前後にリクエスト・レスポンスの内容を出力します。
     async fn send_with_error_handling(
+        &self,
         request: reqwest::RequestBuilder,
         endpoint: &str,
         method: &str,
     ) -> ApiResult<Response> {
-        request.send().await.map_err(|e| {
+        let request = request.build().map_err(|e| {
+            InfraError::network(format!(
+                "Failed to build request for {} {}: {}",
+                method, endpoint, e
+            ))
+        })?;
+
+        trace::trace_request(&request);
+        let url = request.url().clone();
+        let started_at = Instant::now();
+        let result = self.client.execute(request).await;
+        trace::trace_response(method, &url, &result, started_at.elapsed());
+
+        result.map_err(|e| {
             if e.is_timeout() {
                 InfraError::timeout(format!("{} {}", method, endpoint))
             } else if e.is_connect() {
@@ -171,7 +589,7 @@ impl ApiClient {
             .await
             .unwrap_or_else(|_| "Unable to read error response".to_string());
 
-        Err(InfraError::api(endpoint, error_body, Some(status_code)))
+        Err(InfraError::api_response(endpoint, error_body, status_code))
     }
 
     /// JSONレスポンスをデシリアライズ
@@ -193,6 +611,28 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_client_creation_with_connection_options() {
+        let options = ConnectionOptions {
+            tcp_keepalive: None,
+            pool_idle_timeout: None,
+            pool_max_idle_per_host: 1,
+            http2_prior_knowledge: true,
+        };
+        let client = ApiClient::with_connection_options("https://api.mux.com".to_string(), options);
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn test_default_connection_options() {
+        let options = ConnectionOptions::default();
+        assert_eq!(
+            options.tcp_keepalive,
+            Some(Duration::from_secs(APP_CONFIG.api.tcp_keepalive_secs))
+        );
+        assert!(!options.http2_prior_knowledge);
+    }
+
     #[test]
     fn test_production_client() {
         let client = ApiClient::production();
@@ -0,0 +1,132 @@
+/// Mux APIのエンドポイントパス
+///
+/// コマンドごとにパス文字列を組み立てないよう、エンドポイントのパスをここに集約します。
+/// IDはパスの1セグメントとして埋め込むため、`/` などを含む値はパーセントエンコードします。
+use crate::api::pagination::encode_query_value;
+
+/// アセット一覧（GET）・アセット作成（POST）
+pub const ASSETS: &str = "/video/v1/assets";
+
+/// Direct Upload一覧（GET）・Direct Upload作成（POST）
+pub const UPLOADS: &str = "/video/v1/uploads";
+
+/// ライブストリーム一覧
+pub const LIVE_STREAMS: &str = "/video/v1/live-streams";
+
+/// 再生制限一覧（GET）・再生制限作成（POST）
+pub const PLAYBACK_RESTRICTIONS: &str = "/video/v1/playback-restrictions";
+
+/// URL署名キー一覧（GET）・署名キー作成（POST）
+pub const SIGNING_KEYS: &str = "/system/v1/signing-keys";
+
+/// アクセストークンの情報（GET）
+pub const WHOAMI: &str = "/system/v1/whoami";
+
+/// パスの1セグメントとしてIDをエンコードする
+fn segment(id: &str) -> String {
+    encode_query_value(id)
+}
+
+/// アセット（GET・PATCH・DELETE）
+pub fn asset(asset_id: &str) -> String {
+    format!("{}/{}", ASSETS, segment(asset_id))
+}
+
+/// アセットの再生ID一覧（POST）
+pub fn playback_ids(asset_id: &str) -> String {
+    format!("{}/playback-ids", asset(asset_id))
+}
+
+/// アセットの再生ID（GET・DELETE）
+pub fn playback_id(asset_id: &str, playback_id: &str) -> String {
+    format!("{}/{}", playback_ids(asset_id), segment(playback_id))
+}
+
+/// アセットのStatic Rendition一覧（POST）
+pub fn static_renditions(asset_id: &str) -> String {
+    format!("{}/static-renditions", asset(asset_id))
+}
+
+/// アセットのStatic Rendition（DELETE）
+pub fn static_rendition(asset_id: &str, rendition_id: &str) -> String {
+    format!("{}/{}", static_renditions(asset_id), segment(rendition_id))
+}
+
+/// アセットのトラック一覧（POST）
+pub fn tracks(asset_id: &str) -> String {
+    format!("{}/tracks", asset(asset_id))
+}
+
+/// アセットのトラック（DELETE）
+pub fn track(asset_id: &str, track_id: &str) -> String {
+    format!("{}/{}", tracks(asset_id), segment(track_id))
+}
+
+/// オーディオトラックからの字幕の自動生成（POST）
+pub fn generate_subtitles(asset_id: &str, track_id: &str) -> String {
+    format!("{}/generate-subtitles", track(asset_id, track_id))
+}
+
+/// Direct Upload（GET）
+pub fn upload(upload_id: &str) -> String {
+    format!("{}/{}", UPLOADS, segment(upload_id))
+}
+
+/// Direct Uploadの取り消し（PUT）
+pub fn cancel_upload(upload_id: &str) -> String {
+    format!("{}/cancel", upload(upload_id))
+}
+
+/// ライブストリーム（GET・DELETE）
+pub fn live_stream(live_stream_id: &str) -> String {
+    format!("{}/{}", LIVE_STREAMS, segment(live_stream_id))
+}
+
+/// ライブストリームのストリームキーの再発行（POST）
+pub fn reset_stream_key(live_stream_id: &str) -> String {
+    format!("{}/reset-stream-key", live_stream(live_stream_id))
+}
+
+/// 再生制限（GET・DELETE）
+pub fn playback_restriction(restriction_id: &str) -> String {
+    format!("{}/{}", PLAYBACK_RESTRICTIONS, segment(restriction_id))
+}
+
+/// URL署名キー（GET・DELETE）
+pub fn signing_key(key_id: &str) -> String {
+    format!("{}/{}", SIGNING_KEYS, segment(key_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_endpoints() {
+        assert_eq!(asset("abc"), "/video/v1/assets/abc");
+        assert_eq!(
+            playback_id("abc", "pb1"),
+            "/video/v1/assets/abc/playback-ids/pb1"
+        );
+        assert_eq!(
+            static_rendition("abc", "r1"),
+            "/video/v1/assets/abc/static-renditions/r1"
+        );
+        assert_eq!(track("abc", "t1"), "/video/v1/assets/abc/tracks/t1");
+        assert_eq!(
+            generate_subtitles("abc", "t1"),
+            "/video/v1/assets/abc/tracks/t1/generate-subtitles"
+        );
+        assert_eq!(cancel_upload("up1"), "/video/v1/uploads/up1/cancel");
+        assert_eq!(
+            reset_stream_key("ls1"),
+            "/video/v1/live-streams/ls1/reset-stream-key"
+        );
+    }
+
+    #[test]
+    fn test_ids_are_encoded_as_one_segment() {
+        assert_eq!(asset("../uploads"), "/video/v1/assets/..%2Fuploads");
+        assert_eq!(signing_key("a b"), "/system/v1/signing-keys/a%20b");
+    }
+}
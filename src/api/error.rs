@@ -3,6 +3,7 @@
 /// 外部システム（ファイルシステム、ネットワーク、API）との
 /// やり取りで発生するエラーを構造化して定義。
 /// #[from] / #[source] を使って原因連鎖を保持する。
+use crate::api::types::MuxErrorResponse;
 use crate::error_severity::ErrorSeverity;
 use std::io;
 use thiserror::Error;
@@ -19,12 +20,28 @@ pub enum InfraError {
         endpoint: String,
         message: String,
         status_code: Option<u16>,
+        /// Mux APIのエラー種別（`error.type`、レスポンスがMuxのエラー形式の場合のみ）
+        error_type: Option<String>,
+        /// Mux APIのエラーメッセージ（`error.messages`）
+        messages: Vec<String>,
     },
 
     /// タイムアウトエラー
     #[error("operation timed out: {operation}")]
     Timeout { operation: String },
 
+    /// ローカルに保存したデータ（インデックス、キューなど）の読み書きエラー
+    #[error("local storage error: {path} - {message}")]
+    Storage { path: String, message: String },
+
+    /// ユーザー定義フック（config.tomlの`[hooks]`）の実行エラー
+    #[error("hook '{hook}' failed: {message}")]
+    Hook { hook: String, message: String },
+
+    /// ffmpegによる変換（`upload --transcode`）のエラー
+    #[error("transcode failed: {message}")]
+    Transcode { message: String },
+
     /// その他のI/Oエラー
     #[error("I/O error")]
     Io(#[from] io::Error),
@@ -48,6 +65,32 @@ impl InfraError {
             endpoint: endpoint.into(),
             message: message.into(),
             status_code,
+            error_type: None,
+            messages: Vec::new(),
+        }
+    }
+
+    /// 失敗したレスポンスの本文からAPIエラーを作成
+    ///
+    /// 本文がMuxのエラー形式（`{"error": {"type": ..., "messages": [...]}}`）の場合は
+    /// 種別とメッセージを取り出し、メッセージには `messages` を連結したものを使います。
+    /// それ以外の本文はそのままメッセージにします。
+    pub fn api_response(endpoint: impl Into<String>, body: String, status_code: u16) -> Self {
+        let Ok(response) = serde_json::from_str::<MuxErrorResponse>(&body) else {
+            return Self::api(endpoint, body, Some(status_code));
+        };
+        let detail = response.error;
+        let message = if detail.messages.is_empty() {
+            detail.error_type.clone()
+        } else {
+            detail.messages.join("; ")
+        };
+        Self::Api {
+            endpoint: endpoint.into(),
+            message,
+            status_code: Some(status_code),
+            error_type: Some(detail.error_type),
+            messages: detail.messages,
         }
     }
 
@@ -58,21 +101,180 @@ impl InfraError {
         }
     }
 
+    /// ローカルストレージエラーを作成
+    pub fn storage(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Storage {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// フック実行エラーを作成
+    pub fn hook(hook: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Hook {
+            hook: hook.into(),
+            message: message.into(),
+        }
+    }
+
+    /// 変換エラーを作成
+    pub fn transcode(message: impl Into<String>) -> Self {
+        Self::Transcode {
+            message: message.into(),
+        }
+    }
+
     /// エラーの深刻度を返す
     pub fn severity(&self) -> ErrorSeverity {
         ErrorSeverity::SystemError
     }
 
+    /// 機械可読なエラーコードを返す
+    ///
+    /// `--machine` 出力の `error.code` に使われる安定した識別子（変更しない）
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Network { .. } => "NETWORK_ERROR",
+            Self::Api {
+                status_code: Some(401 | 403),
+                ..
+            } => "AUTH_REJECTED",
+            Self::Api {
+                status_code: Some(404),
+                ..
+            } => "NOT_FOUND",
+            Self::Api {
+                status_code: Some(429),
+                ..
+            } => "RATE_LIMITED",
+            Self::Api { .. } => "API_ERROR",
+            Self::Timeout { .. } => "TIMEOUT",
+            Self::Storage { .. } => "STORAGE_ERROR",
+            Self::Hook { .. } => "HOOK_FAILED",
+            Self::Transcode { .. } => "TRANSCODE_FAILED",
+            Self::Io(_) => "IO_ERROR",
+        }
+    }
+
+    /// アセット数の上限などの容量制限、またはレート制限によるエラーか
+    ///
+    /// 判定条件:
+    /// - HTTP 429: レート制限超過（Too Many Requests）
+    /// - HTTP 400/422 かつ `error.type` が `invalid_parameters` かつ
+    ///   メッセージに "limited to" と "assets" を含む: アセット数の上限
+    pub fn is_capacity_limit(&self) -> bool {
+        let Self::Api {
+            status_code,
+            error_type,
+            messages,
+            ..
+        } = self
+        else {
+            return false;
+        };
+        match status_code {
+            Some(429) => true,
+            Some(400 | 422) => {
+                error_type.as_deref() == Some("invalid_parameters")
+                    && messages.iter().any(|message| {
+                        let message = message.to_lowercase();
+                        message.contains("limited to") && message.contains("assets")
+                    })
+            }
+            _ => false,
+        }
+    }
+
+    /// API通信エラーの場合、エンドポイントとHTTPステータスコードを返す
+    pub fn api_details(&self) -> Option<(&str, Option<u16>)> {
+        match self {
+            Self::Api {
+                endpoint,
+                status_code,
+                ..
+            } => Some((endpoint.as_str(), *status_code)),
+            _ => None,
+        }
+    }
+
     /// ユーザー向けのヒントメッセージを返す
-    #[allow(dead_code)]
     pub fn hint(&self) -> Option<&str> {
         match self {
             Self::Network { .. } => Some("Check your internet connection and try again."),
+            Self::Api {
+                status_code: Some(401 | 403),
+                ..
+            } => Some("Check your API credentials and permissions, or run 'vidyeet login' again."),
+            Self::Api {
+                status_code: Some(404),
+                ..
+            } => Some(
+                "The requested resource was not found. Check the asset ID with 'vidyeet list'.",
+            ),
+            Self::Api {
+                status_code: Some(429),
+                ..
+            } => Some("Too many requests were sent to Mux. Wait a moment and try again."),
+            Self::Api {
+                status_code: Some(500..=599),
+                ..
+            } => Some("Mux is temporarily unavailable. Try again later."),
             Self::Api { .. } => Some("Check your API credentials and permissions."),
             Self::Timeout { .. } => {
                 Some("The operation took too long. Try again or check your connection.")
             }
+            Self::Storage { .. } => Some(
+                "Local data could not be read or written. Check permissions, or delete the file to rebuild it.",
+            ),
+            Self::Hook { .. } => Some(
+                "Check the command in the [hooks] section of config.toml and run it manually to see its output.",
+            ),
+            Self::Transcode { .. } => Some(
+                "Check that ffmpeg is installed, and run ffmpeg manually with the --transcode arguments to see its output.",
+            ),
             Self::Io(_) => Some("An I/O error occurred. Check file permissions and disk space."),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_response_parses_mux_error() {
+        let body = r#"{"error":{"type":"invalid_parameters","messages":["Free plan is limited to 10 assets."]}}"#;
+        let error = InfraError::api_response("/video/v1/uploads", body.to_string(), 400);
+        match &error {
+            InfraError::Api {
+                message,
+                error_type,
+                messages,
+                ..
+            } => {
+                assert_eq!(message, "Free plan is limited to 10 assets.");
+                assert_eq!(error_type.as_deref(), Some("invalid_parameters"));
+                assert_eq!(messages.len(), 1);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert!(error.is_capacity_limit());
+    }
+
+    #[test]
+    fn test_api_response_keeps_other_bodies() {
+        let error = InfraError::api_response("/video/v1/uploads", "Bad Gateway".to_string(), 502);
+        assert_eq!(
+            error.to_string(),
+            "API error: /video/v1/uploads - Bad Gateway"
+        );
+        assert!(!error.is_capacity_limit());
+
+        let body = r#"{"error":{"type":"invalid_parameters","messages":["title is too long"]}}"#;
+        assert!(
+            !InfraError::api_response("/video/v1/uploads", body.to_string(), 400)
+                .is_capacity_limit()
+        );
+        assert!(InfraError::api("/video/v1/uploads", "slow down", Some(429)).is_capacity_limit());
+    }
+}
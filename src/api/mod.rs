@@ -2,7 +2,19 @@
 ///
 /// api.videoとの通信を担当するモジュール。
 /// 認証、動画アップロード、動画管理機能を提供します。
+pub mod audit;
 pub mod auth;
+pub mod cache;
 pub mod client;
+pub mod endpoints;
 pub mod error;
+pub mod mux;
+pub mod pagination;
+pub mod pause;
+pub mod poll;
+pub mod rate_limit;
+pub mod retry;
+pub mod signing;
+pub mod thumbnail;
+pub mod trace;
 pub mod types;
@@ -0,0 +1,394 @@
+/// Mux APIの型付きクライアント
+///
+/// エンドポイントごとのリクエストとレスポンスを型付きのメソッドとして`ApiClient`に追加します。
+/// パスは`endpoints`、リクエスト・レスポンスの型は`types`に定義しており、
+/// コマンドはエンドポイントの文字列やJSONを組み立てずにこれらのメソッドを呼び出します。
+/// 失敗したレスポンスは`InfraError::Api`（Muxのエラー種別・メッセージ付き）として返します。
+use crate::api::client::ApiClient;
+use crate::api::endpoints;
+use crate::api::error::InfraError;
+use crate::api::pagination::PageResponse;
+use crate::api::types::{
+    AssetData, AssetPatch, CreateTrackRequest, CreateUploadRequest, DataResponse, DirectUploadData,
+    GenerateSubtitlesRequest, LiveStream, PlaybackId, PlaybackIdRequest, PlaybackRestriction,
+    PlaybackRestrictionRequest, ReferrerRestriction, SigningKey, StaticRendition,
+    StaticRenditionRequest, TokenInfo, Track,
+};
+use futures_util::stream::Stream;
+use reqwest::Response;
+use serde::Serialize;
+use serde::de::{DeserializeOwned, IgnoredAny};
+
+/// APIクライアントの結果型
+type ApiResult<T> = Result<T, InfraError>;
+
+impl ApiClient {
+    /// GETで単一のリソース（`data`の中身）を取得
+    ///
+    /// # Arguments
+    /// * `endpoint` - エンドポイントパス（`endpoints`で構築したもの）
+    /// * `auth_header` - HTTP Basic認証ヘッダー
+    pub async fn get_data<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        auth_header: &str,
+    ) -> ApiResult<T> {
+        let response = self.get(endpoint, Some(auth_header)).await?;
+        Self::into_data(response, endpoint).await
+    }
+
+    /// POSTでリソースを作成し、作成されたリソース（`data`の中身）を返す
+    pub async fn post_data<B: Serialize, T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        body: &B,
+        auth_header: &str,
+    ) -> ApiResult<T> {
+        let response = self.post(endpoint, body, Some(auth_header)).await?;
+        Self::into_data(response, endpoint).await
+    }
+
+    /// PATCHでリソースを更新し、更新後のリソース（`data`の中身）を返す
+    pub async fn patch_data<B: Serialize, T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        body: &B,
+        auth_header: &str,
+    ) -> ApiResult<T> {
+        let response = self.patch(endpoint, body, Some(auth_header)).await?;
+        Self::into_data(response, endpoint).await
+    }
+
+    /// DELETEでリソースを削除する（レスポンスボディは読まない）
+    pub async fn delete_resource(&self, endpoint: &str, auth_header: &str) -> ApiResult<()> {
+        let response = self.delete(endpoint, Some(auth_header)).await?;
+        Self::check_response(response, endpoint).await?;
+        Ok(())
+    }
+
+    /// レスポンスをチェックし、`data`の中身をデシリアライズ
+    async fn into_data<T: DeserializeOwned>(response: Response, endpoint: &str) -> ApiResult<T> {
+        let response = Self::check_response(response, endpoint).await?;
+        let body: DataResponse<T> = Self::parse_json(response).await?;
+        Ok(body.data)
+    }
+
+    // ---- アセット ----
+
+    /// アセットを取得
+    pub async fn get_asset(&self, asset_id: &str, auth_header: &str) -> ApiResult<AssetData> {
+        self.get_data(&endpoints::asset(asset_id), auth_header)
+            .await
+    }
+
+    /// URL取り込みでアセットを作成
+    ///
+    /// # Arguments
+    /// * `request` - 作成リクエスト（`inputs` や `playback_policies` などを含むJSON）
+    pub async fn create_asset<B: Serialize>(
+        &self,
+        request: &B,
+        auth_header: &str,
+    ) -> ApiResult<AssetData> {
+        self.post_data(endpoints::ASSETS, request, auth_header)
+            .await
+    }
+
+    /// 全アセットを新しい順に取得（全ページを遅延取得するストリーム）
+    pub fn list_assets<'a>(
+        &'a self,
+        auth_header: &'a str,
+    ) -> impl Stream<Item = ApiResult<AssetData>> + 'a {
+        self.get_paginated(endpoints::ASSETS, Some(auth_header))
+    }
+
+    /// アセットの総数を取得（`limit=1` の1リクエストのみで、全ページは取得しない）
+    ///
+    /// 一覧レスポンスの `total_row_count` を返します。APIが総数を返さない場合はNoneです。
+    pub async fn count_assets(&self, auth_header: &str) -> ApiResult<Option<usize>> {
+        let endpoint = format!("{}?limit=1", endpoints::ASSETS);
+        let response = self.get(&endpoint, Some(auth_header)).await?;
+        let response = Self::check_response(response, &endpoint).await?;
+        let page: PageResponse<IgnoredAny> = Self::parse_json(response).await?;
+        Ok(page.total_row_count)
+    }
+
+    /// アセットのメタデータ・passthroughを更新
+    pub async fn patch_asset(
+        &self,
+        asset_id: &str,
+        patch: &AssetPatch,
+        auth_header: &str,
+    ) -> ApiResult<AssetData> {
+        self.patch_data(&endpoints::asset(asset_id), patch, auth_header)
+            .await
+    }
+
+    /// アセットを削除
+    pub async fn delete_asset(&self, asset_id: &str, auth_header: &str) -> ApiResult<()> {
+        self.delete_resource(&endpoints::asset(asset_id), auth_header)
+            .await
+    }
+
+    // ---- Direct Upload ----
+
+    /// Direct Uploadを作成
+    pub async fn create_upload(
+        &self,
+        request: &CreateUploadRequest,
+        auth_header: &str,
+    ) -> ApiResult<DirectUploadData> {
+        self.post_data(endpoints::UPLOADS, request, auth_header)
+            .await
+    }
+
+    /// Direct Uploadを取得
+    pub async fn get_upload(
+        &self,
+        upload_id: &str,
+        auth_header: &str,
+    ) -> ApiResult<DirectUploadData> {
+        self.get_data(&endpoints::upload(upload_id), auth_header)
+            .await
+    }
+
+    /// 全Direct Uploadを取得（全ページを遅延取得するストリーム）
+    pub fn list_uploads<'a>(
+        &'a self,
+        auth_header: &'a str,
+    ) -> impl Stream<Item = ApiResult<DirectUploadData>> + 'a {
+        self.get_paginated(endpoints::UPLOADS, Some(auth_header))
+    }
+
+    /// 送信待ち（`waiting`）のDirect Uploadを取り消す
+    pub async fn cancel_upload(
+        &self,
+        upload_id: &str,
+        auth_header: &str,
+    ) -> ApiResult<DirectUploadData> {
+        let endpoint = endpoints::cancel_upload(upload_id);
+        let response = self
+            .put_json(&endpoint, &serde_json::json!({}), Some(auth_header))
+            .await?;
+        Self::into_data(response, &endpoint).await
+    }
+
+    // ---- 再生ID ----
+
+    /// アセットに再生IDを追加
+    ///
+    /// # Arguments
+    /// * `policy` - 再生ポリシー（public, signed）
+    pub async fn create_playback_id(
+        &self,
+        asset_id: &str,
+        policy: &str,
+        auth_header: &str,
+    ) -> ApiResult<PlaybackId> {
+        let request = PlaybackIdRequest {
+            policy: policy.to_string(),
+        };
+        self.post_data(&endpoints::playback_ids(asset_id), &request, auth_header)
+            .await
+    }
+
+    /// アセットの再生IDを削除
+    pub async fn delete_playback_id(
+        &self,
+        asset_id: &str,
+        playback_id: &str,
+        auth_header: &str,
+    ) -> ApiResult<()> {
+        self.delete_resource(&endpoints::playback_id(asset_id, playback_id), auth_header)
+            .await
+    }
+
+    // ---- Static Rendition ----
+
+    /// アセットにStatic Rendition（MP4）を追加
+    ///
+    /// # Arguments
+    /// * `resolution` - 解像度（highest, 1080p, audio-onlyなど）
+    pub async fn create_static_rendition(
+        &self,
+        asset_id: &str,
+        resolution: &str,
+        auth_header: &str,
+    ) -> ApiResult<StaticRendition> {
+        let request = StaticRenditionRequest {
+            resolution: resolution.to_string(),
+        };
+        self.post_data(
+            &endpoints::static_renditions(asset_id),
+            &request,
+            auth_header,
+        )
+        .await
+    }
+
+    /// アセットのStatic Renditionを削除
+    pub async fn delete_static_rendition(
+        &self,
+        asset_id: &str,
+        rendition_id: &str,
+        auth_header: &str,
+    ) -> ApiResult<()> {
+        self.delete_resource(
+            &endpoints::static_rendition(asset_id, rendition_id),
+            auth_header,
+        )
+        .await
+    }
+
+    // ---- トラック ----
+
+    /// アセットにトラック（字幕・音声）を追加
+    pub async fn create_track(
+        &self,
+        asset_id: &str,
+        request: &CreateTrackRequest,
+        auth_header: &str,
+    ) -> ApiResult<Track> {
+        self.post_data(&endpoints::tracks(asset_id), request, auth_header)
+            .await
+    }
+
+    /// オーディオトラックから字幕トラックを自動生成（生成を開始した字幕トラックを返す）
+    pub async fn generate_subtitles(
+        &self,
+        asset_id: &str,
+        track_id: &str,
+        request: &GenerateSubtitlesRequest,
+        auth_header: &str,
+    ) -> ApiResult<Vec<Track>> {
+        self.post_data(
+            &endpoints::generate_subtitles(asset_id, track_id),
+            request,
+            auth_header,
+        )
+        .await
+    }
+
+    /// アセットのトラックを削除
+    pub async fn delete_track(
+        &self,
+        asset_id: &str,
+        track_id: &str,
+        auth_header: &str,
+    ) -> ApiResult<()> {
+        self.delete_resource(&endpoints::track(asset_id, track_id), auth_header)
+            .await
+    }
+
+    // ---- ライブストリーム ----
+
+    /// 全ライブストリームを取得（全ページを遅延取得するストリーム）
+    pub fn list_live_streams<'a>(
+        &'a self,
+        auth_header: &'a str,
+    ) -> impl Stream<Item = ApiResult<LiveStream>> + 'a {
+        self.get_paginated(endpoints::LIVE_STREAMS, Some(auth_header))
+    }
+
+    /// ライブストリームを取得
+    pub async fn get_live_stream(
+        &self,
+        live_stream_id: &str,
+        auth_header: &str,
+    ) -> ApiResult<LiveStream> {
+        self.get_data(&endpoints::live_stream(live_stream_id), auth_header)
+            .await
+    }
+
+    /// ライブストリームのストリームキーを再発行し、新しいキーを含むライブストリームを返す
+    pub async fn reset_stream_key(
+        &self,
+        live_stream_id: &str,
+        auth_header: &str,
+    ) -> ApiResult<LiveStream> {
+        self.post_data(
+            &endpoints::reset_stream_key(live_stream_id),
+            &serde_json::json!({}),
+            auth_header,
+        )
+        .await
+    }
+
+    // ---- 再生制限 ----
+
+    /// 再生制限を作成
+    pub async fn create_playback_restriction(
+        &self,
+        referrer: &ReferrerRestriction,
+        auth_header: &str,
+    ) -> ApiResult<PlaybackRestriction> {
+        let request = PlaybackRestrictionRequest {
+            referrer: referrer.clone(),
+        };
+        self.post_data(endpoints::PLAYBACK_RESTRICTIONS, &request, auth_header)
+            .await
+    }
+
+    /// 全再生制限を取得（全ページを遅延取得するストリーム）
+    pub fn list_playback_restrictions<'a>(
+        &'a self,
+        auth_header: &'a str,
+    ) -> impl Stream<Item = ApiResult<PlaybackRestriction>> + 'a {
+        self.get_paginated(endpoints::PLAYBACK_RESTRICTIONS, Some(auth_header))
+    }
+
+    /// 再生制限を取得
+    pub async fn get_playback_restriction(
+        &self,
+        restriction_id: &str,
+        auth_header: &str,
+    ) -> ApiResult<PlaybackRestriction> {
+        self.get_data(
+            &endpoints::playback_restriction(restriction_id),
+            auth_header,
+        )
+        .await
+    }
+
+    /// 再生制限を削除
+    pub async fn delete_playback_restriction(
+        &self,
+        restriction_id: &str,
+        auth_header: &str,
+    ) -> ApiResult<()> {
+        self.delete_resource(
+            &endpoints::playback_restriction(restriction_id),
+            auth_header,
+        )
+        .await
+    }
+
+    // ---- URL署名キー ----
+
+    /// 全URL署名キーを取得（秘密鍵は含まれない）
+    pub fn list_signing_keys<'a>(
+        &'a self,
+        auth_header: &'a str,
+    ) -> impl Stream<Item = ApiResult<SigningKey>> + 'a {
+        self.get_paginated(endpoints::SIGNING_KEYS, Some(auth_header))
+    }
+
+    /// URL署名キーを作成（返されるキーにのみ秘密鍵が含まれる）
+    pub async fn create_signing_key(&self, auth_header: &str) -> ApiResult<SigningKey> {
+        self.post_data(endpoints::SIGNING_KEYS, &serde_json::json!({}), auth_header)
+            .await
+    }
+
+    /// URL署名キーを削除
+    pub async fn delete_signing_key(&self, key_id: &str, auth_header: &str) -> ApiResult<()> {
+        self.delete_resource(&endpoints::signing_key(key_id), auth_header)
+            .await
+    }
+
+    // ---- アクセストークン ----
+
+    /// 認証に使っているアクセストークンの情報（属する環境）を取得
+    pub async fn get_token_info(&self, auth_header: &str) -> ApiResult<TokenInfo> {
+        self.get_data(endpoints::WHOAMI, auth_header).await
+    }
+}
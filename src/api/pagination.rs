@@ -0,0 +1,131 @@
+/// 一覧系エンドポイントのページネーション
+///
+/// Mux APIの一覧レスポンスは `next_cursor` によるカーソル方式と、
+/// `page` パラメータによるページ番号方式の両方をサポートしています。
+/// レスポンスに `next_cursor` があればカーソルを優先し、
+/// なければページ番号を進めて、1ページ分に満たない応答が返るまで取得を続けます。
+use serde::{Deserialize, Serialize};
+
+/// 一覧レスポンスの共通形式
+#[derive(Debug, Deserialize)]
+pub struct PageResponse<T> {
+    pub data: Vec<T>,
+
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+
+    /// 一覧全体の件数（APIが返す場合のみ）
+    #[serde(default)]
+    pub total_row_count: Option<usize>,
+}
+
+/// 次に取得するページの位置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PageCursor {
+    /// ページ番号（1始まり）
+    Page(u32),
+    /// `next_cursor` で返されたカーソル
+    Cursor(String),
+}
+
+impl PageCursor {
+    /// 最初のページ
+    pub fn first() -> Self {
+        Self::Page(1)
+    }
+
+    /// エンドポイントにページ指定のクエリパラメータを付与
+    ///
+    /// # Arguments
+    /// * `endpoint` - エンドポイントパス（既存のクエリパラメータを含んでもよい）
+    /// * `page_size` - 1ページあたりの件数（`limit`）
+    pub fn apply(&self, endpoint: &str, page_size: usize) -> String {
+        let separator = if endpoint.contains('?') { '&' } else { '?' };
+        let position = match self {
+            Self::Page(page) => format!("page={}", page),
+            Self::Cursor(cursor) => format!("cursor={}", encode_query_value(cursor)),
+        };
+        format!("{}{}limit={}&{}", endpoint, separator, page_size, position)
+    }
+
+    /// 取得したページから次のページ位置を決定（最終ページならNone）
+    ///
+    /// # Arguments
+    /// * `next_cursor` - レスポンスの `next_cursor`
+    /// * `items_in_page` - 取得したページの件数
+    /// * `page_size` - 1ページあたりの件数（`limit`）
+    pub fn next(
+        &self,
+        next_cursor: Option<&str>,
+        items_in_page: usize,
+        page_size: usize,
+    ) -> Option<Self> {
+        if let Some(cursor) = next_cursor.filter(|c| !c.is_empty()) {
+            return Some(Self::Cursor(cursor.to_string()));
+        }
+
+        // 1ページ分に満たなければ最終ページ
+        if items_in_page == 0 || items_in_page < page_size {
+            return None;
+        }
+
+        match self {
+            Self::Page(page) => Some(Self::Page(page + 1)),
+            // カーソル方式で next_cursor が返らなければ終端
+            Self::Cursor(_) => None,
+        }
+    }
+}
+
+/// クエリパラメータの値をパーセントエンコード（RFC 3986の非予約文字以外）
+pub(crate) fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_page_and_cursor() {
+        assert_eq!(
+            PageCursor::first().apply("/video/v1/assets", 100),
+            "/video/v1/assets?limit=100&page=1"
+        );
+        assert_eq!(
+            PageCursor::Cursor("abc=/+".to_string()).apply("/video/v1/assets?live=false", 25),
+            "/video/v1/assets?live=false&limit=25&cursor=abc%3D%2F%2B"
+        );
+    }
+
+    #[test]
+    fn test_next_prefers_cursor() {
+        let next = PageCursor::first().next(Some("cursor_abc"), 3, 100);
+        assert_eq!(next, Some(PageCursor::Cursor("cursor_abc".to_string())));
+    }
+
+    #[test]
+    fn test_next_falls_back_to_page_number() {
+        assert_eq!(
+            PageCursor::Page(2).next(None, 100, 100),
+            Some(PageCursor::Page(3))
+        );
+        // 1ページ分に満たなければ終端
+        assert_eq!(PageCursor::Page(2).next(None, 99, 100), None);
+        assert_eq!(PageCursor::Page(1).next(Some(""), 0, 100), None);
+        // カーソル方式でnext_cursorが無ければ終端
+        assert_eq!(
+            PageCursor::Cursor("x".to_string()).next(None, 100, 100),
+            None
+        );
+    }
+}
@@ -0,0 +1,69 @@
+/// チャンク転送の一時停止
+///
+/// 対話モードでの `p`（一時停止）/ `r`（再開）の操作を、チャンクアップロードのループへ伝えます。
+/// 一時停止中は送信中のチャンクを送り終えた後、次のチャンクの送信前で再開を待ちます。
+/// 並行アップロード中のすべてのファイルが同じ状態に従います。
+///
+/// 状態はプロセス全体で1つのため、各コマンドやApiClientに引数として引き回す必要はありません。
+use std::sync::OnceLock;
+use tokio::sync::watch;
+
+/// 一時停止中か（trueで一時停止）
+static PAUSED: OnceLock<watch::Sender<bool>> = OnceLock::new();
+
+fn state() -> &'static watch::Sender<bool> {
+    PAUSED.get_or_init(|| watch::Sender::new(false))
+}
+
+/// 一時停止する
+///
+/// # 戻り値
+/// 状態が変わった場合はtrue（すでに一時停止中の場合はfalse）
+pub fn pause() -> bool {
+    state().send_if_modified(|paused| !std::mem::replace(paused, true))
+}
+
+/// 再開する
+///
+/// # 戻り値
+/// 状態が変わった場合はtrue（一時停止中でなかった場合はfalse）
+pub fn resume() -> bool {
+    state().send_if_modified(|paused| std::mem::replace(paused, false))
+}
+
+/// 一時停止中か
+pub fn is_paused() -> bool {
+    *state().borrow()
+}
+
+/// 再開されるまで待機する（一時停止中でなければすぐに戻る）
+pub async fn wait_until_resumed() {
+    let mut receiver = state().subscribe();
+    // 送信側は静的に保持しているため、チャネルが閉じることはない
+    let _ = receiver.wait_for(|paused| !*paused).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_pause_and_resume() {
+        assert!(pause());
+        assert!(!pause());
+        assert!(is_paused());
+
+        let waiter = tokio::spawn(wait_until_resumed());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        assert!(resume());
+        assert!(!resume());
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("waiter should finish after resume")
+            .unwrap();
+        assert!(!is_paused());
+    }
+}
@@ -0,0 +1,145 @@
+/// 状態待ちポーリングの間隔
+///
+/// アセットの作成・準備完了を待つポーリングの間隔を、初回は短く、以降は
+/// 指数的に延ばして上限で頭打ちにします（`poll_interval_secs` → `poll_max_interval_secs`）。
+/// 複数のポーリングが同じ間隔で揃わないよう、間隔にはジッター（ランダムな短縮）を加えます。
+///
+/// サーバーが `Retry-After` ヘッダーで待機時間を示した場合は、その時間より早くは再取得しません。
+use crate::config::APP_CONFIG;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// ポーリング間隔のバックオフ
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PollBackoff {
+    /// 初回の間隔（ミリ秒）
+    pub initial_ms: u64,
+
+    /// 間隔の上限（ミリ秒）
+    pub max_ms: u64,
+
+    /// ジッターの割合（0.0〜1.0、間隔をこの割合まで短縮する）
+    pub jitter_ratio: f64,
+}
+
+impl Default for PollBackoff {
+    /// APP_CONFIGの設定値から構築
+    fn default() -> Self {
+        Self {
+            initial_ms: APP_CONFIG.upload.poll_interval_secs * 1000,
+            max_ms: APP_CONFIG.upload.poll_max_interval_secs * 1000,
+            jitter_ratio: APP_CONFIG.upload.poll_jitter_ratio,
+        }
+    }
+}
+
+impl PollBackoff {
+    /// n回目（0始まり）のポーリング後の間隔（ジッターなし）
+    ///
+    /// `initial_ms * 2^attempt` を `max_ms` で頭打ちにします。
+    pub fn interval(&self, attempt: u32) -> Duration {
+        let multiplier = 2_u64.saturating_pow(attempt);
+        let interval_ms = self.initial_ms.saturating_mul(multiplier).min(self.max_ms);
+        Duration::from_millis(interval_ms)
+    }
+
+    /// n回目（0始まり）のポーリング後の間隔（ジッターあり）
+    ///
+    /// # 引数
+    /// * `random` - 0.0以上1.0未満の乱数（1.0に近いほど間隔を短縮する）
+    pub fn jittered(&self, attempt: u32, random: f64) -> Duration {
+        let ratio = self.jitter_ratio.clamp(0.0, 1.0) * random.clamp(0.0, 1.0);
+        self.interval(attempt).mul_f64(1.0 - ratio)
+    }
+
+    /// 次のポーリングまでの間隔
+    ///
+    /// サーバーの示した待機時間（`hint`）がある場合は、それより短くしません。
+    pub fn next_delay(&self, attempt: u32, hint: Option<Duration>) -> Duration {
+        let delay = self.jittered(attempt, random_unit());
+        hint.map_or(delay, |hint| delay.max(hint))
+    }
+}
+
+/// `Retry-After` ヘッダーの待機時間（秒数の形式のみ対応）
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// 0.0以上1.0未満の乱数
+///
+/// ジッター用途のため暗号論的な強度は不要で、標準ライブラリの
+/// ハッシュのランダムな鍵から生成します（乱数クレートに依存しない）。
+fn random_unit() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1_u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn backoff() -> PollBackoff {
+        PollBackoff {
+            initial_ms: 2000,
+            max_ms: 30_000,
+            jitter_ratio: 0.2,
+        }
+    }
+
+    #[test]
+    fn test_interval_is_exponential_and_capped() {
+        let backoff = backoff();
+        assert_eq!(backoff.interval(0), Duration::from_secs(2));
+        assert_eq!(backoff.interval(1), Duration::from_secs(4));
+        assert_eq!(backoff.interval(3), Duration::from_secs(16));
+        assert_eq!(backoff.interval(4), Duration::from_secs(30)); // 上限
+        assert_eq!(backoff.interval(64), Duration::from_secs(30)); // オーバーフローしない
+    }
+
+    #[test]
+    fn test_jitter_only_shortens_interval() {
+        let backoff = backoff();
+        assert_eq!(backoff.jittered(0, 0.0), Duration::from_secs(2));
+        assert_eq!(backoff.jittered(0, 0.5), Duration::from_millis(1800));
+        for _ in 0..100 {
+            let delay = backoff.next_delay(4, None);
+            assert!(delay > Duration::from_secs(24) && delay <= Duration::from_secs(30));
+        }
+    }
+
+    #[test]
+    fn test_hint_is_a_lower_bound() {
+        let backoff = backoff();
+        assert_eq!(
+            backoff.next_delay(0, Some(Duration::from_secs(60))),
+            Duration::from_secs(60)
+        );
+        assert!(backoff.next_delay(4, Some(Duration::from_secs(1))) > Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static(" 15 "));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(15)));
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2026 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), None);
+    }
+}
@@ -0,0 +1,300 @@
+/// APIリクエストのクライアント側レート制限
+///
+/// トークンバケット方式で1秒あたりのリクエスト数を制限します。
+/// `RateLimiter`はクローンしても内部状態（バケット）を共有するため、
+/// 複数の非同期タスクで同じApiClientを使う場合でも全体として制限が守られます。
+/// バッチ処理（一括削除、複数アップロードのポーリングなど）で
+/// Mux側のレート制限（HTTP 429）に当たることを未然に防ぐのが目的です。
+///
+/// 同じトークンバケットで送信バイト数を制限する`BandwidthLimiter`も提供します。
+/// 複数ファイルの並行アップロードで回線を使い切らないよう、全体の帯域を上限で抑えます。
+use crate::config::APP_CONFIG;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// トークンバケットの状態
+#[derive(Debug)]
+struct TokenBucket {
+    /// 現在のトークン数
+    tokens: f64,
+    /// 最後に補充した時刻
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// 経過時間に応じてトークンを補充する（上限はburst）
+    fn refill(&mut self, now: Instant, requests_per_second: f64, burst: f64) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * requests_per_second).min(burst);
+        self.last_refill = now;
+    }
+
+    /// トークンを1つ取得する
+    ///
+    /// # 戻り値
+    /// - `Ok(())`: 取得成功
+    /// - `Err(Duration)`: トークン不足、次のトークンが補充されるまでの待機時間
+    fn try_acquire(
+        &mut self,
+        now: Instant,
+        requests_per_second: f64,
+        burst: f64,
+    ) -> Result<(), Duration> {
+        self.refill(now, requests_per_second, burst);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / requests_per_second,
+            ))
+        }
+    }
+
+    /// `amount`個のトークンを借り入れる（残量が負になることを許容）
+    ///
+    /// # 戻り値
+    /// - `Ok(())`: 取得成功（残量が負になった場合は次回の取得が返済まで待たされる）
+    /// - `Err(Duration)`: 借りが残っている、残量が0に戻るまでの待機時間
+    fn try_borrow(
+        &mut self,
+        now: Instant,
+        amount: f64,
+        rate_per_second: f64,
+        burst: f64,
+    ) -> Result<(), Duration> {
+        self.refill(now, rate_per_second, burst);
+        if self.tokens >= 0.0 {
+            self.tokens -= amount;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(-self.tokens / rate_per_second))
+        }
+    }
+}
+
+/// トークンバケット方式のレートリミッター
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    /// 1秒あたりの許可リクエスト数（0以下で無制限）
+    requests_per_second: f64,
+    /// バースト許容量（バケットの容量）
+    burst: f64,
+    /// 共有バケット
+    bucket: Arc<Mutex<TokenBucket>>,
+}
+
+impl Default for RateLimiter {
+    /// APP_CONFIGの設定値からリミッターを構築
+    fn default() -> Self {
+        Self::new(
+            APP_CONFIG.api.requests_per_second,
+            APP_CONFIG.api.rate_limit_burst,
+        )
+    }
+}
+
+impl RateLimiter {
+    /// 新しいレートリミッターを作成
+    ///
+    /// # 引数
+    /// * `requests_per_second` - 1秒あたりの許可リクエスト数（0以下で無制限）
+    /// * `burst` - 連続して即時送信できる最大リクエスト数（最小1）
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        let burst = f64::from(burst.max(1));
+        Self {
+            requests_per_second,
+            burst,
+            bucket: Arc::new(Mutex::new(TokenBucket {
+                tokens: burst,
+                last_refill: Instant::now(),
+            })),
+        }
+    }
+
+    /// レート制限が有効か
+    pub fn is_enabled(&self) -> bool {
+        self.requests_per_second > 0.0
+    }
+
+    /// リクエスト送信の許可を待つ
+    ///
+    /// トークンが利用可能になるまで非同期に待機します。
+    /// ロックは待機中に保持しないため、他タスクの進行を妨げません。
+    pub async fn acquire(&self) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        loop {
+            let wait = {
+                let mut bucket = self
+                    .bucket
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                match bucket.try_acquire(Instant::now(), self.requests_per_second, self.burst) {
+                    Ok(()) => return,
+                    Err(wait) => wait,
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// トークンバケット方式の帯域リミッター（バイト/秒）
+///
+/// `RateLimiter`と同様にクローン間でバケットを共有するため、
+/// 並行アップロード全体の送信帯域をまとめて制限できます。
+/// チャンクはバケット容量（1秒分）より大きいことがあるため、
+/// 残量が0以上なら送信を許可して不足分を「借り」とし、次の送信を借りの返済まで待たせます。
+#[derive(Debug, Clone)]
+pub struct BandwidthLimiter {
+    /// 1秒あたりの許可バイト数
+    bytes_per_second: f64,
+    /// 共有バケット（トークン = バイト）
+    bucket: Arc<Mutex<TokenBucket>>,
+}
+
+impl BandwidthLimiter {
+    /// 新しい帯域リミッターを作成
+    ///
+    /// # 引数
+    /// * `bytes_per_second` - 1秒あたりの許可バイト数（最小1）
+    pub fn new(bytes_per_second: u64) -> Self {
+        let bytes_per_second = bytes_per_second.max(1) as f64;
+        Self {
+            bytes_per_second,
+            bucket: Arc::new(Mutex::new(TokenBucket {
+                tokens: bytes_per_second,
+                last_refill: Instant::now(),
+            })),
+        }
+    }
+
+    /// 1秒あたりの許可バイト数
+    pub fn bytes_per_second(&self) -> u64 {
+        self.bytes_per_second as u64
+    }
+
+    /// `bytes`バイトの送信許可を待つ
+    ///
+    /// ロックは待機中に保持しないため、他タスクの進行を妨げません。
+    pub async fn acquire(&self, bytes: u64) {
+        loop {
+            let wait = {
+                let mut bucket = self
+                    .bucket
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                match bucket.try_borrow(
+                    Instant::now(),
+                    bytes as f64,
+                    self.bytes_per_second,
+                    self.bytes_per_second,
+                ) {
+                    Ok(()) => return,
+                    Err(wait) => wait,
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_allows_burst_then_waits() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket {
+            tokens: 2.0,
+            last_refill: start,
+        };
+
+        assert!(bucket.try_acquire(start, 2.0, 2.0).is_ok());
+        assert!(bucket.try_acquire(start, 2.0, 2.0).is_ok());
+
+        // バースト分を使い切ると、次のトークンまで0.5秒（2 req/s）待機
+        let wait = bucket.try_acquire(start, 2.0, 2.0).unwrap_err();
+        assert_eq!(wait, Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_bucket_refills_over_time_up_to_burst() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket {
+            tokens: 0.0,
+            last_refill: start,
+        };
+
+        // 10秒経過しても上限（burst=3）までしか補充されない
+        bucket.refill(start + Duration::from_secs(10), 1.0, 3.0);
+        assert_eq!(bucket.tokens, 3.0);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_limiter_does_not_wait() {
+        let limiter = RateLimiter::new(0.0, 1);
+        assert!(!limiter.is_enabled());
+        for _ in 0..100 {
+            limiter.acquire().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cloned_limiter_shares_bucket() {
+        let limiter = RateLimiter::new(1.0, 1);
+        let cloned = limiter.clone();
+
+        limiter.acquire().await;
+
+        // クローン側から見てもトークンは消費済み
+        let mut bucket = cloned.bucket.lock().unwrap();
+        assert!(bucket.try_acquire(Instant::now(), 1.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_bucket_borrow_waits_until_debt_is_repaid() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket {
+            tokens: 100.0,
+            last_refill: start,
+        };
+
+        // 容量より大きい要求も残量が0以上なら即時許可される
+        assert!(bucket.try_borrow(start, 300.0, 100.0, 100.0).is_ok());
+        assert_eq!(bucket.tokens, -200.0);
+
+        // 借り（200バイト）を100バイト/秒で返済するまで2秒待機
+        let wait = bucket.try_borrow(start, 1.0, 100.0, 100.0).unwrap_err();
+        assert_eq!(wait, Duration::from_secs(2));
+
+        assert!(
+            bucket
+                .try_borrow(start + Duration::from_secs(2), 1.0, 100.0, 100.0)
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cloned_bandwidth_limiter_shares_bucket() {
+        let limiter = BandwidthLimiter::new(1_000);
+        let cloned = limiter.clone();
+
+        limiter.acquire(5_000).await;
+
+        // クローン側から見ても借りが残っている
+        let mut bucket = cloned.bucket.lock().unwrap();
+        assert!(
+            bucket
+                .try_borrow(Instant::now(), 1.0, 1_000.0, 1_000.0)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,120 @@
+/// APIリクエストのリトライポリシー
+///
+/// 一時的な障害（ネットワークエラー、タイムアウト、5xxレスポンス）に対して
+/// 指数バックオフで自動リトライするための方針を定義します。
+/// ApiClientのGET/POST/DELETEに一元的に適用されます。
+use crate::api::error::InfraError;
+use crate::config::APP_CONFIG;
+use reqwest::StatusCode;
+use std::time::Duration;
+
+/// リトライポリシー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// 最大試行回数（初回を含む、1ならリトライなし）
+    pub max_attempts: u32,
+
+    /// 指数バックオフの基準時間（ミリ秒）
+    pub backoff_base_ms: u64,
+
+    /// バックオフの上限（ミリ秒）
+    pub backoff_max_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    /// APP_CONFIGの設定値からポリシーを構築
+    fn default() -> Self {
+        Self {
+            max_attempts: APP_CONFIG.api.max_attempts,
+            backoff_base_ms: APP_CONFIG.api.retry_backoff_base_ms,
+            backoff_max_ms: APP_CONFIG.api.retry_backoff_max_ms,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// リトライを行わないポリシー
+    pub fn no_retry() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// n回目の失敗後（0始まり）の待機時間を計算
+    ///
+    /// `backoff_base_ms * 2^attempt` を `backoff_max_ms` で頭打ちにします。
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let multiplier = 2_u64.saturating_pow(attempt);
+        let backoff_ms = self
+            .backoff_base_ms
+            .saturating_mul(multiplier)
+            .min(self.backoff_max_ms);
+        Duration::from_millis(backoff_ms)
+    }
+
+    /// レスポンスのステータスがリトライ対象か判定（5xx）
+    pub fn is_retryable_status(status: StatusCode) -> bool {
+        status.is_server_error()
+    }
+
+    /// エラーがリトライ対象か判定（ネットワークエラー、タイムアウト）
+    pub fn is_retryable_error(error: &InfraError) -> bool {
+        matches!(
+            error,
+            InfraError::Network { .. } | InfraError::Timeout { .. }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_uses_app_config() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.max_attempts, APP_CONFIG.api.max_attempts);
+        assert_eq!(policy.backoff_base_ms, APP_CONFIG.api.retry_backoff_base_ms);
+    }
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            backoff_base_ms: 500,
+            backoff_max_ms: 3000,
+        };
+
+        assert_eq!(policy.backoff(0), Duration::from_millis(500));
+        assert_eq!(policy.backoff(1), Duration::from_millis(1000));
+        assert_eq!(policy.backoff(2), Duration::from_millis(2000));
+        assert_eq!(policy.backoff(3), Duration::from_millis(3000)); // 上限
+        assert_eq!(policy.backoff(40), Duration::from_millis(3000)); // オーバーフローしない
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        assert!(RetryPolicy::is_retryable_status(
+            StatusCode::INTERNAL_SERVER_ERROR
+        ));
+        assert!(RetryPolicy::is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(!RetryPolicy::is_retryable_status(StatusCode::NOT_FOUND));
+        assert!(!RetryPolicy::is_retryable_status(StatusCode::OK));
+    }
+
+    #[test]
+    fn test_retryable_error() {
+        assert!(RetryPolicy::is_retryable_error(&InfraError::network(
+            "reset"
+        )));
+        assert!(RetryPolicy::is_retryable_error(&InfraError::timeout(
+            "GET /video/v1/assets"
+        )));
+        assert!(!RetryPolicy::is_retryable_error(&InfraError::api(
+            "/video/v1/assets",
+            "bad request",
+            Some(400)
+        )));
+    }
+}
@@ -0,0 +1,98 @@
+/// サムネイル画像のURL
+///
+/// Muxのサムネイル（`https://image.mux.com/{playback_id}/thumbnail.jpg`）は、
+/// `time`（切り出す位置、秒）・`width`・`height` のクエリで切り出す位置と大きさを指定できます。
+/// 冒頭の黒画面やタイトルを避けるため、再生時間の一定の割合（`APP_CONFIG.thumbnail.time_ratio`）の位置を切り出し、
+/// 再生時間が分からない場合は位置を指定せずMuxの既定に任せます。
+/// 署名付きURLでは、これらのパラメータをクエリではなくトークンに含めます（`UrlSigner::sign_url`）。
+use crate::api::signing::Audience;
+use crate::config::APP_CONFIG;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// サムネイル画像の大きさ（`--thumbnail-width` / `--thumbnail-height`）
+///
+/// 片方のみ指定した場合は、もう片方を縦横比に合わせてMuxが決めます。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ThumbnailSize {
+    /// 幅（ピクセル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    /// 高さ（ピクセル）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+}
+
+/// サムネイルを切り出す位置（秒、小数点以下2桁）
+///
+/// 再生時間が分からない（0以下を含む）場合はNone
+pub fn thumbnail_time(duration_secs: Option<f64>) -> Option<f64> {
+    let duration = duration_secs.filter(|d| d.is_finite() && *d > 0.0)?;
+    Some((duration * APP_CONFIG.thumbnail.time_ratio * 100.0).round() / 100.0)
+}
+
+/// 再生IDのサムネイル画像のURL
+///
+/// # 引数
+/// * `playback_id` - 再生ID
+/// * `duration_secs` - 動画の再生時間（秒）。Noneの場合は切り出す位置を指定しない
+/// * `size` - 画像の大きさ
+pub fn thumbnail_url(playback_id: &str, duration_secs: Option<f64>, size: ThumbnailSize) -> String {
+    let mut params = Vec::new();
+    if let Some(time) = thumbnail_time(duration_secs) {
+        params.push(format!("time={}", time));
+    }
+    if let Some(width) = size.width {
+        params.push(format!("width={}", width));
+    }
+    if let Some(height) = size.height {
+        params.push(format!("height={}", height));
+    }
+
+    let url = Audience::Thumbnail.url(playback_id);
+    if params.is_empty() {
+        url
+    } else {
+        format!("{}?{}", url, params.join("&"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumbnail_url() {
+        assert_eq!(
+            thumbnail_url("play_1", Some(130.0), ThumbnailSize::default()),
+            "https://image.mux.com/play_1/thumbnail.jpg?time=13"
+        );
+        assert_eq!(
+            thumbnail_url(
+                "play_1",
+                Some(42.345),
+                ThumbnailSize {
+                    width: Some(640),
+                    height: None,
+                }
+            ),
+            "https://image.mux.com/play_1/thumbnail.jpg?time=4.23&width=640"
+        );
+        // 再生時間が分からない場合は位置を指定しない
+        assert_eq!(
+            thumbnail_url(
+                "play_1",
+                None,
+                ThumbnailSize {
+                    width: Some(640),
+                    height: Some(360),
+                }
+            ),
+            "https://image.mux.com/play_1/thumbnail.jpg?width=640&height=360"
+        );
+        assert_eq!(
+            thumbnail_url("play_1", Some(0.0), ThumbnailSize::default()),
+            "https://image.mux.com/play_1/thumbnail.jpg"
+        );
+    }
+}
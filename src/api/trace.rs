@@ -0,0 +1,252 @@
+/// HTTP通信のデバッグトレース
+///
+/// `--debug-http` 指定時に、すべてのAPI呼び出しについて
+/// メソッド・URL・ステータス・レイテンシ・ヘッダー（機密情報はマスク）をstderrへ出力します。
+/// `--debug-http=body` ではテキスト系（JSONなど）のリクエストボディも出力します。
+/// バイナリ（動画チャンク）のボディは出力しません。
+///
+/// トレースレベルはプロセス全体で1つ（CLI起動時に一度だけ設定）のため、
+/// 各コマンドやApiClientに引数として引き回す必要はありません。
+use crate::config::APP_CONFIG;
+use reqwest::header::{CONTENT_TYPE, HeaderMap};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
+
+/// マスク対象のヘッダー名（小文字）
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+];
+
+/// マスク対象のクエリパラメータ名に含まれる語（小文字）
+/// Direct UploadのURLは署名付きのため、署名やトークンを含む値を伏せる
+const SENSITIVE_QUERY_KEYWORDS: &[&str] = &["signature", "token", "key", "credential", "secret"];
+
+/// マスク後の表示値
+const REDACTED: &str = "[REDACTED]";
+
+/// 現在のトレースレベル（TraceLevelの判別値）
+static TRACE_LEVEL: AtomicU8 = AtomicU8::new(TraceLevel::Off as u8);
+
+/// トレースの詳細度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum TraceLevel {
+    /// トレースしない
+    Off = 0,
+    /// メソッド・URL・ステータス・レイテンシ・ヘッダー
+    Headers = 1,
+    /// Headersに加えてテキスト系リクエストボディ
+    Bodies = 2,
+}
+
+/// トレースレベルを設定する
+pub fn set_level(level: TraceLevel) {
+    TRACE_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// 現在のトレースレベルを取得する
+pub fn level() -> TraceLevel {
+    match TRACE_LEVEL.load(Ordering::Relaxed) {
+        1 => TraceLevel::Headers,
+        2 => TraceLevel::Bodies,
+        _ => TraceLevel::Off,
+    }
+}
+
+/// トレースが有効か
+pub fn is_enabled() -> bool {
+    level() > TraceLevel::Off
+}
+
+/// 送信するリクエストをトレース出力する
+pub fn trace_request(request: &reqwest::Request) {
+    if !is_enabled() {
+        return;
+    }
+
+    eprintln!(
+        "[http] --> {} {}",
+        request.method(),
+        redact_url(request.url())
+    );
+    trace_headers(request.headers());
+
+    if level() >= TraceLevel::Bodies
+        && let Some(bytes) = request.body().and_then(|b| b.as_bytes())
+    {
+        if is_textual(request.headers()) {
+            eprintln!("[http]     body: {}", truncate_body(bytes));
+        } else {
+            eprintln!("[http]     body: <{} bytes, binary omitted>", bytes.len());
+        }
+    }
+}
+
+/// 受信したレスポンス（または送信エラー）をトレース出力する
+pub fn trace_response(
+    method: &str,
+    url: &reqwest::Url,
+    result: &Result<reqwest::Response, reqwest::Error>,
+    elapsed: Duration,
+) {
+    if !is_enabled() {
+        return;
+    }
+
+    match result {
+        Ok(response) => {
+            eprintln!(
+                "[http] <-- {} {} {} ({} ms)",
+                response.status(),
+                method,
+                redact_url(url),
+                elapsed.as_millis()
+            );
+            trace_headers(response.headers());
+        }
+        Err(e) => {
+            eprintln!(
+                "[http] <-- ERROR {} {} ({} ms): {}",
+                method,
+                redact_url(url),
+                elapsed.as_millis(),
+                e
+            );
+        }
+    }
+}
+
+/// ヘッダーを1行ずつ出力する（機密ヘッダーはマスク）
+fn trace_headers(headers: &HeaderMap) {
+    for (name, value) in headers {
+        eprintln!(
+            "[http]     {}: {}",
+            name,
+            redact_header(name.as_str(), value.to_str().unwrap_or("<non-ascii>"))
+        );
+    }
+}
+
+/// 機密ヘッダーの値をマスクする
+fn redact_header(name: &str, value: &str) -> String {
+    if SENSITIVE_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+        // 認証スキーム（Basicなど）は残してデバッグしやすくする
+        match value.split_once(' ') {
+            Some((scheme, _)) => format!("{} {}", scheme, REDACTED),
+            None => REDACTED.to_string(),
+        }
+    } else {
+        value.to_string()
+    }
+}
+
+/// URLのクエリに含まれる署名・トークン類をマスクする
+fn redact_url(url: &reqwest::Url) -> String {
+    if url.query().is_none() {
+        return url.to_string();
+    }
+
+    let mut redacted = url.clone();
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| {
+            let lower = k.to_ascii_lowercase();
+            let value = if SENSITIVE_QUERY_KEYWORDS.iter().any(|kw| lower.contains(kw)) {
+                REDACTED.to_string()
+            } else {
+                v.into_owned()
+            };
+            (k.into_owned(), value)
+        })
+        .collect();
+    redacted.query_pairs_mut().clear().extend_pairs(pairs);
+    redacted.to_string()
+}
+
+/// Content-Typeがテキスト系（JSON・テキスト・フォーム）か判定
+fn is_textual(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|ct| {
+            let ct = ct.to_ascii_lowercase();
+            ct.starts_with("application/json")
+                || ct.starts_with("text/")
+                || ct.starts_with("application/x-www-form-urlencoded")
+        })
+        .unwrap_or(false)
+}
+
+/// ボディを最大長で切り詰めて文字列化する
+fn truncate_body(bytes: &[u8]) -> String {
+    let max = APP_CONFIG.api.debug_body_max_bytes;
+    let text = String::from_utf8_lossy(&bytes[..bytes.len().min(max)]);
+    if bytes.len() > max {
+        format!("{}... ({} bytes total)", text, bytes.len())
+    } else {
+        text.into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_redact_authorization_header_keeps_scheme() {
+        assert_eq!(
+            redact_header("Authorization", "Basic dXNlcjpwYXNz"),
+            "Basic [REDACTED]"
+        );
+        assert_eq!(redact_header("cookie", "session=abc"), "[REDACTED]");
+        assert_eq!(
+            redact_header("content-type", "application/json"),
+            "application/json"
+        );
+    }
+
+    #[test]
+    fn test_redact_url_masks_signed_query() {
+        let url = reqwest::Url::parse(
+            "https://storage.googleapis.com/upload?upload_id=abc&X-Goog-Signature=deadbeef",
+        )
+        .unwrap();
+        let redacted = redact_url(&url);
+
+        assert!(redacted.contains("upload_id=abc"));
+        assert!(!redacted.contains("deadbeef"));
+        assert!(redacted.contains("X-Goog-Signature="));
+    }
+
+    #[test]
+    fn test_redact_url_without_query_is_unchanged() {
+        let url = reqwest::Url::parse("https://api.mux.com/video/v1/assets").unwrap();
+        assert_eq!(redact_url(&url), "https://api.mux.com/video/v1/assets");
+    }
+
+    #[test]
+    fn test_is_textual() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        assert!(is_textual(&headers));
+
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("video/mp4"));
+        assert!(!is_textual(&headers));
+
+        assert!(!is_textual(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn test_truncate_body() {
+        let max = APP_CONFIG.api.debug_body_max_bytes;
+        let long = vec![b'a'; max + 10];
+        let truncated = truncate_body(&long);
+        assert!(truncated.ends_with(&format!("... ({} bytes total)", max + 10)));
+
+        assert_eq!(truncate_body(b"{\"ok\":true}"), "{\"ok\":true}");
+    }
+}
@@ -1,8 +1,146 @@
 /// API通信用の型定義
 ///
 /// Mux Video APIのレスポンスをデシリアライズするための構造体を定義します。
+use crate::domain::passthrough::{self, Passthrough};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// Mux APIが文字列で返すステータスを列挙型として定義する
+///
+/// 未知の値は `Unknown` に元の文字列のまま保持し、シリアライズ時もそのまま出力します。
+/// JSON Schemaでは文字列として扱います。
+macro_rules! api_status {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $value:literal,)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)+
+            /// 未知のステータス（Mux APIの値をそのまま保持）
+            Unknown(String),
+        }
+
+        impl $name {
+            /// Mux APIでの値
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)+
+                    Self::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => Self::$variant,)+
+                    other => Self::Unknown(other.to_string()),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                Ok(Self::from(value.as_str()))
+            }
+        }
+
+        impl JsonSchema for $name {
+            fn inline_schema() -> bool {
+                true
+            }
+
+            fn schema_name() -> std::borrow::Cow<'static, str> {
+                stringify!($name).into()
+            }
+
+            fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+                String::json_schema(generator)
+            }
+        }
+    };
+}
+
+api_status! {
+    /// アセットのステータス
+    AssetStatus {
+        /// 処理中
+        Preparing => "preparing",
+        /// 再生可能
+        Ready => "ready",
+        /// 処理に失敗
+        Errored => "errored",
+    }
+}
+
+api_status! {
+    /// Direct Uploadのステータス
+    UploadStatus {
+        /// ファイルの送信待ち
+        Waiting => "waiting",
+        /// アセット作成済み
+        AssetCreated => "asset_created",
+        /// 失敗
+        Errored => "errored",
+        /// 取り消し済み
+        Cancelled => "cancelled",
+        /// 有効期限切れ
+        TimedOut => "timed_out",
+    }
+}
+
+api_status! {
+    /// Static Renditionのステータス
+    RenditionStatus {
+        /// 生成中
+        Preparing => "preparing",
+        /// 生成済み
+        Ready => "ready",
+        /// 生成に失敗
+        Errored => "errored",
+        /// 生成されなかった（元の動画が指定した解像度より小さいなど）
+        Skipped => "skipped",
+        /// 削除済み
+        Deleted => "deleted",
+    }
+}
+
+api_status! {
+    /// ライブストリームのステータス
+    LiveStreamStatus {
+        /// 配信待ち
+        Idle => "idle",
+        /// 配信中
+        Active => "active",
+        /// 無効化済み
+        Disabled => "disabled",
+    }
+}
+
+impl RenditionStatus {
+    /// 生成に失敗した（今後readyにならない）ステータスか
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Self::Errored | Self::Skipped)
+    }
+}
+
 /// Mux APIエラーレスポンス
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MuxErrorResponse {
@@ -16,6 +154,12 @@ pub struct MuxErrorDetail {
     pub messages: Vec<String>,
 }
 
+/// 単一のリソースを `data` で包んだレスポンス（Mux APIの共通形式）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataResponse<T> {
+    pub data: T,
+}
+
 /// Direct Uploadレスポンス
 ///
 /// POST /video/v1/uploads のレスポンス型
@@ -30,12 +174,14 @@ pub struct DirectUploadData {
     pub id: String,
 
     /// アップロード有効期限（秒）
+    #[serde(default)]
     pub timeout: u64,
 
     /// アップロードステータス
-    pub status: String,
+    pub status: UploadStatus,
 
     /// 新規アセット設定
+    #[serde(default)]
     pub new_asset_settings: NewAssetSettings,
 
     /// 作成されたアセットID（asset_created状態の場合のみ）
@@ -59,8 +205,9 @@ pub struct DirectUploadData {
     pub test: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NewAssetSettings {
+    #[serde(default)]
     pub playback_policies: Vec<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -70,7 +217,7 @@ pub struct NewAssetSettings {
     pub meta: Option<AssetMeta>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AssetMeta {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
@@ -97,13 +244,13 @@ pub struct AssetResponse {
     pub data: AssetData,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AssetData {
     /// アセットID
     pub id: String,
 
     /// ステータス
-    pub status: String,
+    pub status: AssetStatus,
 
     /// 再生ID
     #[serde(default)]
@@ -160,6 +307,10 @@ pub struct AssetData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub passthrough: Option<String>,
 
+    /// メタデータ（タイトルなど）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<AssetMeta>,
+
     /// MP4サポート（deprecated）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mp4_support: Option<String>,
@@ -169,13 +320,13 @@ pub struct AssetData {
     pub static_renditions: Option<StaticRenditionsWrapper>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PlaybackId {
     pub id: String,
     pub policy: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Track {
     /// トラックタイプ（video, audioなど）
     #[serde(rename = "type")]
@@ -185,6 +336,10 @@ pub struct Track {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 
+    /// ステータス（preparing, ready, errored。自動生成中の字幕トラックはpreparing）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+
     /// トラック時間（秒）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
@@ -208,9 +363,21 @@ pub struct Track {
     /// 最大チャンネルレイアウト（audioトラックのみ、例: stereo）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_channel_layout: Option<String>,
+
+    /// テキストトラックの種類（textトラックのみ、例: subtitles）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_type: Option<String>,
+
+    /// 言語コード（text・audioトラックのみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language_code: Option<String>,
+
+    /// トラック名（text・audioトラックのみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct StaticRendition {
     /// Rendition ID
     pub id: String,
@@ -220,7 +387,7 @@ pub struct StaticRendition {
     pub rendition_type: String,
 
     /// ステータス（preparing, ready, errored, skipped, deleted）
-    pub status: String,
+    pub status: RenditionStatus,
 
     /// 解像度（highest, 1080p, 720pなど）
     pub resolution: String,
@@ -230,14 +397,59 @@ pub struct StaticRendition {
 
     /// ファイル拡張子（例: "mp4", "m4a"）
     pub ext: String,
+
+    /// ファイルサイズ（bytes、ready状態のみ。Mux APIは文字列で返す）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filesize: Option<String>,
+}
+
+impl StaticRendition {
+    /// ファイルサイズ（bytes）
+    pub fn file_size(&self) -> Option<u64> {
+        self.filesize.as_deref().and_then(|size| size.parse().ok())
+    }
 }
 
 /// Static Renditionsラッパー（Mux APIの実際の構造）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct StaticRenditionsWrapper {
     pub files: Vec<StaticRendition>,
 }
 
+/// 再生制限（Playback Restriction）
+///
+/// 署名付き再生IDのトークンに `playback_restriction_id` として含めると、
+/// 許可したリファラー（ドメイン）以外からの再生を拒否します。
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PlaybackRestriction {
+    /// 再生制限ID
+    pub id: String,
+
+    /// 作成日時（Unix timestamp）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+
+    /// 更新日時（Unix timestamp）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+
+    /// リファラーによる制限
+    #[serde(default)]
+    pub referrer: ReferrerRestriction,
+}
+
+/// リファラー（ドメイン）による再生制限
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ReferrerRestriction {
+    /// 再生を許可するドメイン（`*.example.com` のワイルドカード、すべてを許可する `*` を含む）
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+
+    /// リファラーのないリクエスト（ネイティブアプリなど）からの再生を許可するか
+    #[serde(default)]
+    pub allow_no_referrer: bool,
+}
+
 /// アセット一覧レスポンス
 ///
 /// GET /video/v1/assets のレスポンス型
@@ -249,13 +461,250 @@ pub struct AssetsListResponse {
     pub next_cursor: Option<String>,
 }
 
+/// ライブストリーム
+///
+/// GET /video/v1/live-streams の要素・GET /video/v1/live-streams/{LIVE_STREAM_ID} の `data`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LiveStream {
+    /// ライブストリームID
+    pub id: String,
+
+    /// ステータス（idle, active, disabled）
+    pub status: LiveStreamStatus,
+
+    /// ストリームキー（RTMPで配信する際の秘密の値）
+    pub stream_key: String,
+
+    /// 再生ID
+    #[serde(default)]
+    pub playback_ids: Vec<PlaybackId>,
+
+    /// 作成日時（Unix timestamp）
+    pub created_at: String,
+
+    /// 配信中の録画アセットID（active状態のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_asset_id: Option<String>,
+
+    /// 過去の配信の録画アセットID
+    #[serde(default)]
+    pub recent_asset_ids: Vec<String>,
+
+    /// レイテンシモード（standard, reduced, low）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_mode: Option<String>,
+}
+
+/// URL署名キー
+///
+/// 秘密鍵（`private_key`）は作成時のレスポンスにのみ含まれます。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigningKey {
+    /// 署名キーID
+    pub id: String,
+
+    /// 作成日時（Unix timestamp）
+    pub created_at: String,
+
+    /// Base64エンコードされたPEM形式の秘密鍵（作成時のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+}
+
+/// アクセストークンの情報
+///
+/// トークンが属する環境（Production・Developmentなど）を返します。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenInfo {
+    /// 環境ID
+    #[serde(default)]
+    pub environment_id: Option<String>,
+
+    /// 環境名（例: "Production"）
+    #[serde(default)]
+    pub environment_name: Option<String>,
+}
+
+/// Direct Upload作成リクエスト
+///
+/// POST /video/v1/uploads のリクエスト型
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateUploadRequest {
+    pub new_asset_settings: NewAssetSettingsRequest,
+}
+
+/// Direct Uploadで作成するアセットの設定
+#[derive(Debug, Clone, Serialize)]
+pub struct NewAssetSettingsRequest {
+    pub playback_policies: Vec<String>,
+
+    pub video_quality: String,
+
+    pub max_resolution_tier: String,
+
+    /// アセット作成時に生成するStatic Rendition（生成しない場合は送らない）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub static_renditions: Vec<StaticRenditionRequest>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<AssetMeta>,
+}
+
+/// 再生制限の作成リクエスト
+///
+/// POST /video/v1/playback-restrictions のリクエスト型
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaybackRestrictionRequest {
+    pub referrer: ReferrerRestriction,
+}
+
+/// Static Renditionの作成リクエスト
+///
+/// POST /video/v1/assets/{ASSET_ID}/static-renditions のリクエスト型
+#[derive(Debug, Clone, Serialize)]
+pub struct StaticRenditionRequest {
+    /// 解像度（highest, 1080p, audio-onlyなど）
+    pub resolution: String,
+}
+
+/// アップロード時に生成するStatic Rendition（`upload --mp4`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum Mp4Rendition {
+    /// 元の動画に最も近い解像度のMP4
+    #[default]
+    #[serde(rename = "highest")]
+    Highest,
+    /// 1080pのMP4
+    #[serde(rename = "1080p")]
+    P1080,
+    /// 720pのMP4
+    #[serde(rename = "720p")]
+    P720,
+    /// 音声のみのM4A
+    #[serde(rename = "audio-only")]
+    AudioOnly,
+    /// Static Renditionを生成しない
+    #[serde(rename = "none")]
+    None,
+}
+
+impl Mp4Rendition {
+    /// 指定できる値（`--mp4`・エラーメッセージ用）
+    pub const NAMES: &'static [&'static str] = &["highest", "1080p", "720p", "audio-only", "none"];
+
+    /// `highest` / `1080p` / `720p` / `audio-only` / `none` を解析する
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "highest" => Some(Self::Highest),
+            "1080p" => Some(Self::P1080),
+            "720p" => Some(Self::P720),
+            "audio-only" | "audio_only" => Some(Self::AudioOnly),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
+
+    /// Mux APIでの解像度（`static_renditions[].resolution` の値）。`none` の場合はNone
+    pub fn resolution(self) -> Option<&'static str> {
+        match self {
+            Self::Highest => Some("highest"),
+            Self::P1080 => Some("1080p"),
+            Self::P720 => Some("720p"),
+            Self::AudioOnly => Some("audio-only"),
+            Self::None => None,
+        }
+    }
+
+    /// 生成されるファイル名（MP4のURLの末尾）。`none` の場合はNone
+    ///
+    /// 音声のみの場合はMP4ではなく `audio.m4a` になります。
+    pub fn file_name(self) -> Option<&'static str> {
+        match self {
+            Self::Highest => Some("highest.mp4"),
+            Self::P1080 => Some("1080p.mp4"),
+            Self::P720 => Some("720p.mp4"),
+            Self::AudioOnly => Some("audio.m4a"),
+            Self::None => None,
+        }
+    }
+}
+
+/// 再生IDの作成リクエスト
+///
+/// POST /video/v1/assets/{ASSET_ID}/playback-ids のリクエスト型
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaybackIdRequest {
+    /// 再生ポリシー（public, signed）
+    pub policy: String,
+}
+
+/// アセットの更新リクエスト
+///
+/// PATCH /video/v1/assets/{ASSET_ID} のリクエスト型。指定したフィールドのみ書き換えます
+/// （`meta` は部分更新されず、丸ごと置き換えられます）。
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AssetPatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<AssetMeta>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passthrough: Option<String>,
+}
+
+/// トラックの追加リクエスト
+///
+/// POST /video/v1/assets/{ASSET_ID}/tracks のリクエスト型（字幕・音声トラックの追加）
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateTrackRequest {
+    /// トラックのファイルのURL（Muxから取得できる必要がある）
+    pub url: String,
+
+    /// トラックタイプ（text, audio）
+    #[serde(rename = "type")]
+    pub track_type: String,
+
+    /// テキストトラックの種類（subtitles）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_type: Option<String>,
+
+    /// 言語コード（BCP 47、例: "ja", "en-US"）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language_code: Option<String>,
+
+    /// プレイヤーに表示するトラック名
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// 聴覚障害者向けの字幕（CC）か
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub closed_captions: Option<bool>,
+}
+
+/// 字幕の自動生成リクエスト
+///
+/// POST /video/v1/assets/{ASSET_ID}/tracks/{TRACK_ID}/generate-subtitles のリクエスト型
+/// （オーディオトラックの音声から字幕トラックを生成する）
+#[derive(Debug, Clone, Serialize)]
+pub struct GenerateSubtitlesRequest {
+    /// 生成する字幕トラック
+    pub generated_subtitles: Vec<GeneratedSubtitles>,
+}
+
+/// 自動生成する字幕トラックの設定
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedSubtitles {
+    /// 音声の言語コード（BCP 47、例: "en"）
+    pub language_code: String,
+
+    /// プレイヤーに表示するトラック名（省略時はMuxが言語から決める）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
 impl AssetResponse {
     /// 再生URLを構築（HLS形式）
     pub fn get_playback_url(&self) -> Option<String> {
-        self.data
-            .playback_ids
-            .first()
-            .map(|playback_id| format!("https://stream.mux.com/{}.m3u8", playback_id.id))
+        self.data.get_playback_url()
     }
 
     /// MP4再生URLを構築
@@ -267,7 +716,54 @@ impl AssetResponse {
     }
 }
 
+/// アップロード時に記録するチェックサムのpassthrough接頭辞（例: "sha256:9f86d0..."）
+pub const CHECKSUM_PASSTHROUGH_PREFIX: &str = passthrough::CHECKSUM_PREFIX;
+
 impl AssetData {
+    /// 再生URLを構築（HLS形式）
+    pub fn get_playback_url(&self) -> Option<String> {
+        self.playback_ids
+            .first()
+            .map(|playback_id| format!("https://stream.mux.com/{}.m3u8", playback_id.id))
+    }
+
+    /// タイトル（meta.title）を取得
+    pub fn title(&self) -> Option<&str> {
+        self.meta.as_ref().and_then(|m| m.title.as_deref())
+    }
+
+    /// 外部ID（meta.external_id）を取得
+    pub fn external_id(&self) -> Option<&str> {
+        self.meta.as_ref().and_then(|m| m.external_id.as_deref())
+    }
+
+    /// アップロード時に記録した元ファイルのSHA-256（passthroughから取得）
+    pub fn sha256(&self) -> Option<String> {
+        Passthrough::parse(self.passthrough.as_deref()).and_then(|p| p.sha256().map(str::to_string))
+    }
+
+    /// タグ（passthroughから取得。vidyeet以外が書き込んだ値の場合は空）
+    pub fn tags(&self) -> Vec<String> {
+        Passthrough::parse(self.passthrough.as_deref())
+            .map(|p| p.tags())
+            .unwrap_or_default()
+    }
+
+    /// Static Renditionsの合計サイズ（bytes）
+    ///
+    /// サイズの分かる（ready状態の）レンディションがない場合はNone。
+    /// 元の動画・HLS用のセグメントのサイズはAPIから取得できないため含みません。
+    pub fn rendition_storage_bytes(&self) -> Option<u64> {
+        let sizes: Vec<u64> = self
+            .static_renditions
+            .as_ref()?
+            .files
+            .iter()
+            .filter_map(StaticRendition::file_size)
+            .collect();
+        (!sizes.is_empty()).then(|| sizes.iter().sum())
+    }
+
     /// MP4再生URLを構築
     ///
     /// static_renditionsから最初のready状態のMP4を探し、
@@ -281,7 +777,7 @@ impl AssetData {
             && let Some(rendition) = wrapper
                 .files
                 .iter()
-                .find(|r| r.status == "ready" && r.ext == "mp4")
+                .find(|r| r.status == RenditionStatus::Ready && r.ext == "mp4")
         {
             return Some(format!(
                 "https://stream.mux.com/{}/{}",
@@ -301,6 +797,21 @@ impl AssetData {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_status_enums_keep_unknown_values() {
+        let status: AssetStatus = serde_json::from_str("\"ready\"").unwrap();
+        assert_eq!(status, AssetStatus::Ready);
+
+        let status: RenditionStatus = serde_json::from_str("\"archived\"").unwrap();
+        assert_eq!(status, RenditionStatus::Unknown("archived".to_string()));
+        assert!(!status.is_failed());
+        assert_eq!(serde_json::to_string(&status).unwrap(), "\"archived\"");
+
+        assert_eq!(UploadStatus::from("timed_out"), UploadStatus::TimedOut);
+        assert_eq!(UploadStatus::AssetCreated.to_string(), "asset_created");
+        assert!(RenditionStatus::Skipped.is_failed());
+    }
+
     #[test]
     fn test_direct_upload_response_deserialization() {
         let json = r#"{
@@ -324,7 +835,7 @@ mod tests {
 
         assert_eq!(response.data.id, "upload_abc123");
         assert_eq!(response.data.timeout, 3600);
-        assert_eq!(response.data.status, "waiting");
+        assert_eq!(response.data.status, UploadStatus::Waiting);
         assert!(!response.data.id.is_empty() && response.data.url.is_some());
     }
 
@@ -333,7 +844,7 @@ mod tests {
         let response = AssetResponse {
             data: AssetData {
                 id: "asset_123".to_string(),
-                status: "ready".to_string(),
+                status: AssetStatus::Ready,
                 playback_ids: vec![PlaybackId {
                     id: "playback_xyz".to_string(),
                     policy: "public".to_string(),
@@ -351,6 +862,7 @@ mod tests {
                 master_access: None,
                 encoding_tier: None,
                 passthrough: None,
+                meta: None,
                 mp4_support: None,
                 static_renditions: None,
             },
@@ -367,7 +879,7 @@ mod tests {
         let response_with_mp4 = AssetResponse {
             data: AssetData {
                 id: "asset_456".to_string(),
-                status: "ready".to_string(),
+                status: AssetStatus::Ready,
                 playback_ids: vec![PlaybackId {
                     id: "playback_abc".to_string(),
                     policy: "public".to_string(),
@@ -385,15 +897,17 @@ mod tests {
                 master_access: None,
                 encoding_tier: None,
                 passthrough: None,
+                meta: None,
                 mp4_support: None,
                 static_renditions: Some(StaticRenditionsWrapper {
                     files: vec![StaticRendition {
                         id: "rendition_123".to_string(),
                         rendition_type: "standard".to_string(),
-                        status: "ready".to_string(),
+                        status: RenditionStatus::Ready,
                         resolution: "highest".to_string(),
                         name: "highest.mp4".to_string(),
                         ext: "mp4".to_string(),
+                        filesize: Some("1048576".to_string()),
                     }],
                 }),
             },
@@ -405,12 +919,23 @@ mod tests {
             mp4_url.unwrap(),
             "https://stream.mux.com/playback_abc/highest.mp4"
         );
+        let rendition = &response_with_mp4
+            .data
+            .static_renditions
+            .as_ref()
+            .unwrap()
+            .files[0];
+        assert_eq!(rendition.file_size(), Some(1_048_576));
+        assert_eq!(
+            response_with_mp4.data.rendition_storage_bytes(),
+            Some(1_048_576)
+        );
 
         // MP4 renditionがない場合
         let response_without_mp4 = AssetResponse {
             data: AssetData {
                 id: "asset_789".to_string(),
-                status: "ready".to_string(),
+                status: AssetStatus::Ready,
                 playback_ids: vec![PlaybackId {
                     id: "playback_def".to_string(),
                     policy: "public".to_string(),
@@ -428,6 +953,7 @@ mod tests {
                 master_access: None,
                 encoding_tier: None,
                 passthrough: None,
+                meta: None,
                 mp4_support: None,
                 static_renditions: None,
             },
@@ -439,6 +965,7 @@ mod tests {
             mp4_url.unwrap(),
             "https://stream.mux.com/playback_def/highest.mp4"
         );
+        assert_eq!(response_without_mp4.data.rendition_storage_bytes(), None);
     }
 
     #[test]
@@ -461,4 +988,107 @@ mod tests {
         assert_eq!(response.data[0].id, "asset_1");
         assert!(response.next_cursor.is_some());
     }
+
+    #[test]
+    fn test_live_stream_deserialization() {
+        let json = r#"{
+            "data": {
+                "id": "ls_1",
+                "status": "idle",
+                "stream_key": "secret-key",
+                "playback_ids": [{"id": "play_1", "policy": "public"}],
+                "created_at": "1609869152",
+                "latency_mode": "standard"
+            }
+        }"#;
+
+        let response: DataResponse<LiveStream> =
+            serde_json::from_str(json).expect("Failed to parse");
+
+        assert_eq!(response.data.status, LiveStreamStatus::Idle);
+        assert_eq!(response.data.stream_key, "secret-key");
+        assert!(response.data.recent_asset_ids.is_empty());
+    }
+
+    #[test]
+    fn test_request_bodies_omit_unset_fields() {
+        let patch = AssetPatch {
+            passthrough: Some("vidyeet".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&patch).unwrap(),
+            serde_json::json!({ "passthrough": "vidyeet" })
+        );
+
+        let track = CreateTrackRequest {
+            url: "https://example.com/ja.vtt".to_string(),
+            track_type: "text".to_string(),
+            text_type: Some("subtitles".to_string()),
+            language_code: Some("ja".to_string()),
+            name: None,
+            closed_captions: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&track).unwrap(),
+            serde_json::json!({
+                "url": "https://example.com/ja.vtt",
+                "type": "text",
+                "text_type": "subtitles",
+                "language_code": "ja"
+            })
+        );
+
+        let generate = GenerateSubtitlesRequest {
+            generated_subtitles: vec![GeneratedSubtitles {
+                language_code: "en".to_string(),
+                name: None,
+            }],
+        };
+        assert_eq!(
+            serde_json::to_value(&generate).unwrap(),
+            serde_json::json!({ "generated_subtitles": [{ "language_code": "en" }] })
+        );
+
+        // --mp4 none ではStatic Renditionを送らない
+        let settings = NewAssetSettingsRequest {
+            playback_policies: vec!["public".to_string()],
+            video_quality: "basic".to_string(),
+            max_resolution_tier: "2160p".to_string(),
+            static_renditions: Vec::new(),
+            meta: None,
+        };
+        assert!(
+            serde_json::to_value(&settings)
+                .unwrap()
+                .get("static_renditions")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_mp4_rendition_names() {
+        for name in Mp4Rendition::NAMES {
+            let rendition = Mp4Rendition::parse(name).unwrap();
+            assert_eq!(serde_json::to_value(rendition).unwrap(), *name);
+            assert_eq!(rendition.resolution().unwrap_or("none"), *name);
+        }
+        assert_eq!(
+            Mp4Rendition::parse("Audio_Only"),
+            Some(Mp4Rendition::AudioOnly)
+        );
+        assert_eq!(Mp4Rendition::AudioOnly.file_name(), Some("audio.m4a"));
+        assert_eq!(Mp4Rendition::P1080.file_name(), Some("1080p.mp4"));
+        assert_eq!(Mp4Rendition::None.file_name(), None);
+        assert_eq!(Mp4Rendition::parse("480p"), None);
+    }
+
+    #[test]
+    fn test_upload_list_item_without_settings() {
+        // 一覧の要素は作成時のレスポンスより項目が少ない場合がある
+        let json = r#"{"id": "upload_1", "status": "timed_out"}"#;
+        let upload: DirectUploadData = serde_json::from_str(json).expect("Failed to parse");
+        assert_eq!(upload.status, UploadStatus::TimedOut);
+        assert!(upload.new_asset_settings.playback_policies.is_empty());
+    }
 }
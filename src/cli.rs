@@ -1,40 +1,223 @@
-use crate::commands;
+use crate::presentation::browser;
+use crate::presentation::clipboard;
+use crate::presentation::i18n::{Msg, tf};
 use crate::presentation::input;
+use crate::presentation::notify;
 use crate::presentation::output;
 use crate::presentation::progress;
+use crate::presentation::schema;
+use crate::presentation::snippet::{self, SnippetFormat};
+use crate::presentation::style;
 use anyhow::{Context, Result, bail};
+use std::time::Instant;
+use vidyeet::api::signing::Audience;
+use vidyeet::api::thumbnail::ThumbnailSize;
+use vidyeet::api::trace::{self, TraceLevel};
+use vidyeet::api::types::Mp4Rendition;
+use vidyeet::commands;
+use vidyeet::commands::CommandResult;
+use vidyeet::commands::batch_upload::BatchOptions;
+use vidyeet::commands::captions::CaptionOptions;
+use vidyeet::commands::result::{
+    BatchDetails, BatchResult, CaptionAction, CopyTarget, GroupBy, TagAction, WaitUntil,
+};
+use vidyeet::commands::retry::RetryTarget;
+use vidyeet::commands::upload::{ConfirmDelete, UploadOptions};
+use vidyeet::config::user::{CapacityPolicy, PlaybackPolicy};
+use vidyeet::config::{APP_CONFIG, UserConfig};
+use vidyeet::hooks::{self, HookEvent};
+use vidyeet::storage::history::{History, NewHistoryEntry, REDACTED};
+use vidyeet::storage::manifest::UploadManifest;
+use vidyeet::webhook;
 
-/// CLI引数を解析し、適切なコマンドにディスパッチする
-pub async fn parse_args(args: &[String]) -> Result<()> {
-    if args.len() < 2 {
-        output::print_usage();
-        return Ok(());
-    }
+/// グローバルフラグ `--debug-http` を引数から取り除き、HTTPトレースを有効化する
+///
+/// 位置に依存せず、どこに指定しても有効です（`--` 以降は位置引数として扱うため取り除きません）。
+/// - `--debug-http`: メソッド・URL・ステータス・レイテンシ・ヘッダーを出力
+/// - `--debug-http=body`: 上記に加えてテキスト系のリクエストボディを出力
+///
+/// # Returns
+/// フラグを除いた残りの引数
+pub fn apply_debug_http_flag(args: Vec<String>) -> Vec<String> {
+    let (level, remaining) = take_debug_http_flag(args);
+    trace::set_level(level);
+    remaining
+}
 
-    // グローバルフラグ --machine のチェック
-    let (machine_output, command_start_index) = if args.len() > 1 && args[1] == "--machine" {
-        (true, 2)
-    } else {
-        (false, 1)
-    };
+/// `--debug-http` / `--debug-http=body` を引数から取り除き、指定されたトレースレベルを返す
+fn take_debug_http_flag(args: Vec<String>) -> (TraceLevel, Vec<String>) {
+    let mut level = TraceLevel::Off;
+    let mut after_terminator = false;
+    let remaining = args
+        .into_iter()
+        .filter(|arg| {
+            if after_terminator {
+                return true;
+            }
+            let flag_level = match arg.as_str() {
+                "--" => {
+                    after_terminator = true;
+                    return true;
+                }
+                "--debug-http" => TraceLevel::Headers,
+                "--debug-http=body" => TraceLevel::Bodies,
+                _ => return true,
+            };
+            level = level.max(flag_level);
+            false
+        })
+        .collect();
+
+    (level, remaining)
+}
+
+/// グローバルフラグ `--machine` を引数から取り除く
+///
+/// `--debug-http` と同様に位置に依存せず、どこに指定しても有効です
+/// （`--` 以降は位置引数として扱うため取り除きません）。
+///
+/// # Returns
+/// `--machine` が指定されたかと、フラグを除いた残りの引数
+pub fn take_machine_flag(args: Vec<String>) -> (bool, Vec<String>) {
+    take_global_flag(args, "--machine")
+}
+
+/// グローバルフラグ `--no-color` を引数から取り除く
+///
+/// `--machine` と同様に位置に依存せず、どこに指定しても有効です。
+///
+/// # Returns
+/// `--no-color` が指定されたかと、フラグを除いた残りの引数
+pub fn take_no_color_flag(args: Vec<String>) -> (bool, Vec<String>) {
+    take_global_flag(args, "--no-color")
+}
+
+/// グローバルフラグ `--bytes` を引数から取り除く
+///
+/// `--machine` と同様に位置に依存せず、どこに指定しても有効です。
+///
+/// # Returns
+/// `--bytes` が指定されたかと、フラグを除いた残りの引数
+pub fn take_bytes_flag(args: Vec<String>) -> (bool, Vec<String>) {
+    take_global_flag(args, "--bytes")
+}
 
-    if args.len() < command_start_index + 1 {
-        output::print_usage();
-        return Ok(());
+/// グローバルフラグ `--iso-duration` を引数から取り除く
+///
+/// `--machine` と同様に位置に依存せず、どこに指定しても有効です。
+///
+/// # Returns
+/// `--iso-duration` が指定されたかと、フラグを除いた残りの引数
+pub fn take_iso_duration_flag(args: Vec<String>) -> (bool, Vec<String>) {
+    take_global_flag(args, "--iso-duration")
+}
+
+/// グローバルフラグ `--query <expr>`（または `--query=<expr>`）を引数から取り除く
+///
+/// `--machine` と同様に位置に依存せず、どこに指定しても有効です。
+/// 値がない場合は空の式として返します（式の検証で失敗させるため）。
+///
+/// # Returns
+/// `--query` の式と、フラグを除いた残りの引数
+pub fn take_query_flag(args: Vec<String>) -> (Option<String>, Vec<String>) {
+    let mut query = None;
+    let mut remaining = Vec::with_capacity(args.len());
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            remaining.push(arg);
+            remaining.extend(args.by_ref());
+            break;
+        }
+        if arg == "--query" {
+            query = Some(args.next().unwrap_or_default());
+        } else if let Some(expr) = arg.strip_prefix("--query=") {
+            query = Some(expr.to_string());
+        } else {
+            remaining.push(arg);
+        }
     }
 
-    let command = &args[command_start_index];
+    (query, remaining)
+}
+
+/// 値を取らないグローバルフラグを引数から取り除く（`--` 以降は取り除かない）
+fn take_global_flag(args: Vec<String>, flag: &str) -> (bool, Vec<String>) {
+    let mut found = false;
+    let mut after_terminator = false;
+    let remaining = args
+        .into_iter()
+        .filter(|arg| {
+            if after_terminator {
+                return true;
+            }
+            if arg == "--" {
+                after_terminator = true;
+                return true;
+            }
+            if arg == flag {
+                found = true;
+                return false;
+            }
+            true
+        })
+        .collect();
+
+    (found, remaining)
+}
+
+/// CLI引数を解析し、適切なコマンドにディスパッチする
+///
+/// フラグはコマンドの前後どこに指定しても構いません
+/// （例: `vidyeet upload video.mp4 --progress`、`vidyeet --progress upload video.mp4`）。
+/// 最初の位置引数をコマンドとして扱います。
+/// コマンドなしで `--version` を指定した場合は `version` コマンドとして扱います。
+///
+/// # Returns
+/// 終了コード（エラー以外で0以外になるのは一括処理の一部・全件失敗のみ）
+pub async fn parse_args(args: &[String], machine_output: bool) -> Result<i32> {
+    // プログラム名を除いた引数から、コマンドとそれ以外の引数を分離
+    let rest = args.get(1..).unwrap_or_default();
+    let (command, args): (&str, Vec<String>) = match split_command(rest) {
+        Some(split) => split,
+        None if has_flag(rest, "--version") => ("version", Vec::new()),
+        None => {
+            output::print_usage();
+            return Ok(0);
+        }
+    };
+
+    // --plain（upload / show）: 結果の値だけをstdoutへ出力する
+    let mut plain_output = false;
+    // --output markdown|html（upload / show）: 共有用スニペットをstdoutへ出力する
+    let mut snippet_format = None;
 
-    let result = match command.as_str() {
+    let result = match command {
         "login" => {
-            // --stdin フラグをチェック
-            let use_stdin =
-                args.get(command_start_index + 1).map(|s| s.as_str()) == Some("--stdin");
+            let token_file = flag_value(&args, "--token-file");
+            let token_id = flag_value(&args, "--token-id");
+            let token_secret = flag_value(&args, "--token-secret");
+            let use_stdin = has_flag(&args, "--stdin");
 
-            let credentials = if use_stdin {
-                input::read_credentials_from_stdin()?
-            } else {
-                input::read_credentials_interactive()?
+            let sources = [
+                use_stdin,
+                token_file.is_some(),
+                token_id.is_some() || token_secret.is_some(),
+            ];
+            if sources.iter().filter(|source| **source).count() > 1 {
+                bail!("Use only one of --stdin, --token-file, or --token-id/--token-secret");
+            }
+
+            let credentials = match (token_file, token_id, token_secret) {
+                (Some(path), _, _) => {
+                    input::read_credentials_from_file(std::path::Path::new(path))?
+                }
+                (None, Some(id), Some(secret)) => input::credentials_from_flags(id, secret)?,
+                (None, Some(_), None) | (None, None, Some(_)) => {
+                    bail!("--token-id and --token-secret must be specified together")
+                }
+                (None, None, None) if use_stdin => input::read_credentials_from_stdin()?,
+                (None, None, None) => input::read_credentials_interactive()?,
             };
 
             commands::login::execute(credentials)
@@ -47,21 +230,58 @@ pub async fn parse_args(args: &[String]) -> Result<()> {
         "status" => commands::status::execute()
             .await
             .context("Status command failed")?,
-        "list" => commands::list::execute(machine_output)
+        "list" => {
+            let use_cache = !has_flag(&args, "--no-cache");
+            let offline = has_flag(&args, "--offline");
+            let title_only = has_flag(&args, "--title-only");
+            let tag = flag_value(&args, "--tag");
+            output::init_relative_time(has_flag(&args, "--relative-time"));
+            let group_by = flag_value(&args, "--group-by")
+                .map(parse_group_by)
+                .transpose()?;
+            let expires = parse_expires_flag(&args)?;
+
+            let mut result = commands::list::execute(
+                machine_output,
+                use_cache,
+                offline,
+                title_only,
+                tag,
+                group_by,
+            )
             .await
-            .context("List command failed")?,
+            .context("List command failed")?;
+            commands::playback::sign_result(&mut result, expires)?;
+            result
+        }
         "show" => {
-            let asset_id = args
-                .get(command_start_index + 1)
-                .context("Please specify an asset ID for show command")?;
+            let asset_id =
+                positional(&args, 0).context("Please specify an asset ID for show command")?;
+
+            let use_cache = !has_flag(&args, "--no-cache");
+            plain_output = has_flag(&args, "--plain") || has_flag(&args, "--url-only");
+            ensure_plain_allowed(plain_output, machine_output)?;
+            snippet_format = flag_value(&args, "--output")
+                .map(parse_snippet_format)
+                .transpose()?;
+            ensure_snippet_allowed(snippet_format, plain_output, machine_output)?;
+            output::init_relative_time(has_flag(&args, "--relative-time"));
+            let expires = parse_expires_flag(&args)?;
+            let thumbnail_size = parse_thumbnail_size_flags(&args)?;
 
-            commands::show::execute(asset_id)
+            let copy = copy_flag(&args);
+
+            let mut result = commands::show::execute(asset_id, use_cache, thumbnail_size)
                 .await
-                .context("Show command failed")?
+                .context("Show command failed")?;
+            commands::playback::sign_result(&mut result, expires)?;
+            if let Some(target) = copy {
+                clipboard::copy_result_url(&mut result, target);
+            }
+            result
         }
         "delete" => {
-            let asset_id = args
-                .get(command_start_index + 1)
+            let asset_id = positional(&args, 0)
                 .context("Please specify an asset ID for delete command")?
                 .trim();
 
@@ -70,14 +290,14 @@ pub async fn parse_args(args: &[String]) -> Result<()> {
             }
 
             // --force フラグをチェック
-            let force = args.get(command_start_index + 2).map(|s| s.as_str()) == Some("--force");
+            let force = has_flag(&args, "--force");
 
             // force フラグがない場合は確認プロンプトを表示
             if !force && !machine_output {
                 let confirmed = input::confirm_delete(asset_id)?;
                 if !confirmed {
                     // キャンセルされた場合は正常終了
-                    return Ok(());
+                    return Ok(0);
                 }
             }
 
@@ -86,46 +306,577 @@ pub async fn parse_args(args: &[String]) -> Result<()> {
                 .context("Delete command failed")?
         }
         "upload" => {
-            let file_path = args
-                .get(command_start_index + 1)
-                .context("Please specify a file path for upload command")?
-                .trim(); // 先頭・末尾の空白削除
+            let upload_args = parse_upload_args(&args)?;
+            plain_output = upload_args.plain;
+            ensure_plain_allowed(plain_output, machine_output)?;
+            snippet_format = upload_args.snippet;
+            ensure_snippet_allowed(snippet_format, plain_output, machine_output)?;
+            let file_paths = upload_args.file_paths.clone();
+            let manifest_path = upload_args.manifest_path.clone();
+            let upload_options = upload_args.options.upload.clone();
+            let expires = upload_args.expires_secs;
+            let copy = upload_args.copy;
+            let resuming = upload_args.resume_upload_id.is_some();
 
-            if file_path.is_empty() {
-                bail!("File path cannot be empty");
+            let started = Instant::now();
+            let mut outcome = run_upload(upload_args, machine_output).await;
+            if let Ok(result) = &mut outcome {
+                // アップロードは完了しているため、署名の失敗は警告にとどめる
+                if let Err(e) = commands::playback::sign_result(result, expires) {
+                    eprintln!("Warning: Failed to sign playback URLs: {:#}", e);
+                }
+                if let Some(target) = copy {
+                    clipboard::copy_result_url(result, target);
+                }
             }
+            // 待機の再開に失敗した場合、retry で同じファイルを送り直すと重複するため記録しない
+            if !resuming {
+                commands::retry::record_upload_outcome(&file_paths, upload_options, &outcome);
+            }
+            run_upload_hooks(&file_paths, &outcome).await;
+            notify::notify_upload_finished(&outcome, started.elapsed());
+            send_upload_webhook(&file_paths, &outcome).await;
+            let result = outcome?;
 
-            // --progress フラグをチェック
-            let show_progress =
-                args.get(command_start_index + 2).map(|s| s.as_str()) == Some("--progress");
+            if let Some(manifest_path) = &manifest_path {
+                write_manifest(manifest_path, &result)?;
+            }
 
-            // 進捗通知チャネルを作成
-            let (progress_tx, progress_rx) = tokio::sync::mpsc::channel(32);
+            result
+        }
+        "queue" => match positional(&args, 0) {
+            Some("add") => {
+                let file_path = positional(&args, 1)
+                    .context("Please specify a file path for queue add command")?
+                    .trim();
 
-            // アップロード処理を別タスクで開始
-            let upload_handle = tokio::spawn({
-                let file_path = file_path.to_string();
-                async move { commands::upload::execute(&file_path, Some(progress_tx)).await }
-            });
+                if file_path.is_empty() {
+                    bail!("File path cannot be empty");
+                }
 
-            // 進捗受信ループ（プレゼンテーション層に委譲）
-            let progress_handle = tokio::spawn(async move {
-                progress::handle_upload_progress(progress_rx, machine_output, show_progress).await
-            });
+                commands::queue::add(file_path)
+                    .await
+                    .context("Queue add command failed")?
+            }
+            Some("run") => commands::queue::run()
+                .await
+                .context("Queue run command failed")?,
+            _ => bail!("Please specify a queue subcommand: 'queue add <file>' or 'queue run'"),
+        },
+        "retry" => {
+            let target = match (has_flag(&args, "--all"), positional(&args, 0)) {
+                (true, Some(_)) => bail!("Specify either --all or a failed upload ID, not both"),
+                (true, None) => Some(RetryTarget::All),
+                (false, Some(id)) => {
+                    Some(RetryTarget::Id(id.trim().parse().with_context(|| {
+                        format!("Invalid failed upload ID: '{}' (expected a number)", id)
+                    })?))
+                }
+                (false, None) => None,
+            };
 
-            // 両方のタスクの完了を待機
-            let upload_result = upload_handle
+            let mut result = match target {
+                Some(target) => commands::retry::run(target).await,
+                None => commands::retry::list().await,
+            }
+            .context("Retry command failed")?;
+            if let Err(e) = commands::playback::sign_result(&mut result, None) {
+                eprintln!("Warning: Failed to sign playback URLs: {:#}", e);
+            }
+            result
+        }
+        "schema" => {
+            let schema = match positional(&args, 0) {
+                Some(name) => schema::schema_for_name(name).with_context(|| {
+                    format!(
+                        "Unknown schema: '{}'. Available: {}",
+                        name,
+                        schema::SCHEMA_NAMES.join(", ")
+                    )
+                })?,
+                None => schema::all_schemas(),
+            };
+
+            // スキーマ自体がJSONのため、--machine の有無に関わらずstdoutへ出力する
+            if machine_output {
+                output::print_machine_json(schema)?;
+            } else {
+                println!("{}", serde_json::to_string_pretty(&schema)?);
+            }
+            return Ok(0);
+        }
+        "ping" => {
+            let count = match flag_value(&args, "--count") {
+                Some(value) => parse_ping_count(value)?,
+                None => APP_CONFIG.ping.default_count,
+            };
+
+            commands::ping::execute(count)
+                .await
+                .context("Ping command failed")?
+        }
+        "doctor" => commands::doctor::execute()
+            .await
+            .context("Doctor command failed")?,
+        "rename" => {
+            let asset_id =
+                positional(&args, 0).context("Please specify an asset ID for rename command")?;
+            let title =
+                positional(&args, 1).context("Please specify a new title for rename command")?;
+
+            commands::rename::execute(asset_id, title)
+                .await
+                .context("Rename command failed")?
+        }
+        "tag" => {
+            let action = match positional(&args, 0) {
+                Some("add") => TagAction::Add,
+                Some("remove") => TagAction::Remove,
+                Some("list") => TagAction::List,
+                _ => bail!(
+                    "Please specify a tag subcommand: 'tag add <asset_id> <tag>', 'tag remove <asset_id> <tag>' or 'tag list <asset_id>'"
+                ),
+            };
+            let asset_id =
+                positional(&args, 1).context("Please specify an asset ID for tag command")?;
+            let tag = match action {
+                TagAction::List => None,
+                _ => Some(positional(&args, 2).context("Please specify a tag")?),
+            };
+
+            commands::tag::execute(action, asset_id, tag)
+                .await
+                .context("Tag command failed")?
+        }
+        "renditions" => {
+            let action = positional(&args, 0);
+            if !matches!(action, Some("create" | "delete")) {
+                bail!(
+                    "Please specify a renditions subcommand: 'renditions create <asset_id> --resolution <resolution>' or 'renditions delete <asset_id> --resolution <resolution>'"
+                );
+            }
+            let asset_id = positional(&args, 1)
+                .context("Please specify an asset ID for renditions command")?
+                .trim();
+            if asset_id.is_empty() {
+                bail!("Asset ID cannot be empty");
+            }
+            let resolution = flag_value(&args, "--resolution").with_context(|| {
+                format!(
+                    "Please specify --resolution (one of: {})",
+                    commands::renditions::RESOLUTIONS.join(", ")
+                )
+            })?;
+
+            plain_output = has_flag(&args, "--plain");
+            ensure_plain_allowed(plain_output, machine_output)?;
+            if plain_output && action == Some("delete") {
+                bail!("--plain is only supported by 'renditions create'");
+            }
+
+            if action == Some("create") {
+                let wait = !has_flag(&args, "--no-wait");
+                if wait && !machine_output {
+                    eprintln!("{}", tf(Msg::RenditionWaiting, &[&resolution]));
+                }
+                commands::renditions::create(asset_id, resolution, wait)
+                    .await
+                    .context("Renditions create command failed")?
+            } else {
+                commands::renditions::delete(asset_id, resolution)
+                    .await
+                    .context("Renditions delete command failed")?
+            }
+        }
+        "captions" => {
+            let action = match positional(&args, 0) {
+                Some("add") => CaptionAction::Add,
+                Some("generate") => CaptionAction::Generate,
+                _ => bail!(
+                    "Please specify a captions subcommand: 'captions add <asset_id> <url> --language <code>' or 'captions generate <asset_id> --language <code>'"
+                ),
+            };
+            let asset_id = positional(&args, 1)
+                .context("Please specify an asset ID for captions command")?
+                .trim();
+            if asset_id.is_empty() {
+                bail!("Asset ID cannot be empty");
+            }
+            let language = flag_value(&args, "--language").context(
+                "Please specify --language with a BCP 47 language code (e.g. en, ja, pt-BR)",
+            )?;
+            let options = CaptionOptions {
+                language: language.to_string(),
+                name: flag_value(&args, "--name")
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string),
+                closed_captions: has_flag(&args, "--closed-captions"),
+            };
+
+            match action {
+                CaptionAction::Add => {
+                    let url = positional(&args, 2).context(
+                        "Please specify the URL of a WebVTT or SRT file: 'captions add <asset_id> <url>'",
+                    )?;
+                    commands::captions::add(asset_id, url, &options)
+                        .await
+                        .context("Captions add command failed")?
+                }
+                CaptionAction::Generate => {
+                    if options.closed_captions {
+                        bail!("--closed-captions is only supported by 'captions add'");
+                    }
+                    commands::captions::generate(asset_id, &options)
+                        .await
+                        .context("Captions generate command failed")?
+                }
+            }
+        }
+        "playback" => {
+            if positional(&args, 0) != Some("revoke") {
+                bail!(
+                    "Please specify a playback subcommand: 'playback revoke <asset_id> <playback_id> [--replace]'"
+                );
+            }
+            let asset_id = positional(&args, 1)
+                .context("Please specify an asset ID: 'playback revoke <asset_id> <playback_id>'")?
+                .trim();
+            let playback_id = positional(&args, 2)
+                .context(
+                    "Please specify the playback ID to revoke: 'playback revoke <asset_id> <playback_id>'",
+                )?
+                .trim();
+            if asset_id.is_empty() || playback_id.is_empty() {
+                bail!("Asset ID and playback ID cannot be empty");
+            }
+            let replace = has_flag(&args, "--replace");
+            plain_output = has_flag(&args, "--plain");
+            ensure_plain_allowed(plain_output, machine_output)?;
+            if plain_output && !replace {
+                bail!("--plain requires --replace (it prints the new playback ID's HLS URL)");
+            }
+
+            // force フラグがない場合は確認プロンプトを表示
+            if !has_flag(&args, "--force") && !machine_output {
+                let confirmed = input::confirm_revoke(asset_id, playback_id, replace)?;
+                if !confirmed {
+                    // キャンセルされた場合は正常終了
+                    return Ok(0);
+                }
+            }
+
+            commands::playback_ids::revoke(asset_id, playback_id, replace)
                 .await
-                .context("Upload task panicked")?
-                .context("Upload command failed")?;
+                .context("Playback revoke command failed")?
+        }
+        "live" => {
+            if positional(&args, 0) != Some("reset-key") {
+                bail!("Please specify a live subcommand: 'live reset-key <stream_id> [--plain]'");
+            }
+            let live_stream_id = positional(&args, 1)
+                .context("Please specify a live stream ID: 'live reset-key <stream_id>'")?
+                .trim();
+            if live_stream_id.is_empty() {
+                bail!("Live stream ID cannot be empty");
+            }
+            plain_output = has_flag(&args, "--plain");
+            ensure_plain_allowed(plain_output, machine_output)?;
 
-            progress_handle
+            commands::live::reset_key(live_stream_id)
                 .await
-                .context("Progress handler panicked")?
-                .context("Progress handler failed")?;
+                .context("Live reset-key command failed")?
+        }
+        "wait" => {
+            let id = positional(&args, 0)
+                .context("Please specify an asset ID or upload ID for wait command")?
+                .trim();
+            if id.is_empty() {
+                bail!("Asset ID cannot be empty");
+            }
+            let until = flag_value(&args, "--until")
+                .map(parse_wait_until)
+                .transpose()?
+                .unwrap_or_default();
+            let timeout = flag_value(&args, "--timeout")
+                .map(parse_wait_timeout)
+                .transpose()?;
+            let show_progress = has_flag(&args, "--progress");
+            if !show_progress && !machine_output {
+                eprintln!("{}", tf(Msg::WaitWaiting, &[&id, &until.name()]));
+            }
+
+            let mut result = run_wait(id, until, timeout, machine_output, show_progress).await?;
+            commands::playback::sign_result(&mut result, None)?;
+            result
+        }
+        "export" => {
+            let output = flag_value(&args, "--output")
+                .filter(|path| !path.is_empty())
+                .context("Please specify an output file: 'export --output <file.json|file.csv>'")?;
+            let restart = has_flag(&args, "--restart");
 
-            upload_result
+            commands::export::execute(std::path::Path::new(output), restart)
+                .await
+                .context("Export command failed")?
+        }
+        "import" => {
+            let path = positional(&args, 0)
+                .context("Please specify an export or manifest file: 'import <file.json>'")?;
+
+            commands::import::execute(std::path::Path::new(path))
+                .await
+                .context("Import command failed")?
         }
+        "sync" => {
+            let dir =
+                positional(&args, 0).context("Please specify a directory to sync: 'sync <dir>'")?;
+            let delete = has_flag(&args, "--delete");
+            let dry_run = has_flag(&args, "--dry-run");
+            let force = has_flag(&args, "--force");
+            let options = parse_sync_options(&args)?;
+
+            let plan = commands::sync::plan(std::path::Path::new(dir), delete)
+                .await
+                .context("Sync command failed")?;
+            if dry_run {
+                plan.into_dry_run()
+            } else {
+                // 削除対象がある場合は確認プロンプトを表示（--force・機械可読出力時は省略）
+                if !plan.to_delete.is_empty()
+                    && !force
+                    && !machine_output
+                    && !input::confirm_sync_delete(&plan.to_delete)?
+                {
+                    return Ok(0);
+                }
+                commands::sync::execute(plan, options)
+                    .await
+                    .context("Sync command failed")?
+            }
+        }
+        "backup" => {
+            let output = flag_value(&args, "--output")
+                .filter(|path| !path.is_empty())
+                .context("Please specify an output directory: 'backup --output <dir>'")?;
+            let concurrency = match flag_value(&args, "--concurrency") {
+                Some(value) => parse_concurrency(value)?,
+                None => APP_CONFIG.backup.concurrency,
+            };
+
+            commands::backup::execute(std::path::Path::new(output), concurrency)
+                .await
+                .context("Backup command failed")?
+        }
+        "gc" => {
+            let options = commands::gc::GcOptions {
+                errored: has_flag(&args, "--errored"),
+                timed_out_uploads: has_flag(&args, "--timed-out-uploads"),
+                older_than_secs: flag_value(&args, "--older-than")
+                    .map(parse_age)
+                    .transpose()?,
+            };
+            let dry_run = has_flag(&args, "--dry-run");
+            let force = has_flag(&args, "--force");
+
+            let plan = commands::gc::plan(options)
+                .await
+                .context("Gc command failed")?;
+            if dry_run || plan.is_empty() {
+                plan.into_dry_run()
+            } else {
+                // 確認プロンプトを表示（--force・機械可読出力時は省略）
+                if !force && !machine_output && !input::confirm_gc(&plan)? {
+                    return Ok(0);
+                }
+                commands::gc::execute(plan)
+                    .await
+                    .context("Gc command failed")?
+            }
+        }
+        "prune" => {
+            let keep = flag_value(&args, "--keep")
+                .context("Please specify how many assets to keep: 'prune --keep <n>'")
+                .and_then(parse_keep)?;
+            let dry_run = has_flag(&args, "--dry-run");
+            let force = has_flag(&args, "--force");
+
+            let plan = commands::prune::plan(keep)
+                .await
+                .context("Prune command failed")?;
+            if dry_run || plan.to_delete.is_empty() {
+                plan.into_dry_run()
+            } else {
+                // 確認プロンプトを表示（--force・機械可読出力時は省略）
+                if !force && !machine_output && !input::confirm_prune(&plan)? {
+                    return Ok(0);
+                }
+                commands::prune::execute(plan)
+                    .await
+                    .context("Prune command failed")?
+            }
+        }
+        "history" => match positional(&args, 0) {
+            Some("rerun") => {
+                let id = positional(&args, 1)
+                    .context("Please specify a history ID: 'history rerun <id>'")?;
+                let id: u64 = id
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Invalid history ID: '{}' (expected a number)", id))?;
+                return rerun_history(id, machine_output).await;
+            }
+            Some(other) => bail!(
+                "Unknown history subcommand: '{}'. Use 'history [--limit <n>]' or 'history rerun <id>'",
+                other
+            ),
+            None => {
+                let limit = match flag_value(&args, "--limit") {
+                    Some(value) => parse_history_limit(value)?,
+                    None => APP_CONFIG.history.default_limit,
+                };
+
+                commands::history::execute(limit)
+                    .await
+                    .context("History command failed")?
+            }
+        },
+        "sign" => {
+            let playback_id = positional(&args, 0)
+                .context("Please specify a playback ID for sign command")?
+                .trim();
+
+            if playback_id.is_empty() {
+                bail!("Playback ID cannot be empty");
+            }
+
+            let audience = flag_value(&args, "--type")
+                .map(parse_sign_type)
+                .transpose()?
+                .unwrap_or_default();
+            let expires = parse_expires_flag(&args)?;
+            let restriction = flag_value(&args, "--restriction").map(str::trim);
+            if restriction == Some("") {
+                bail!("Playback restriction ID cannot be empty");
+            }
+            plain_output = has_flag(&args, "--plain");
+            ensure_plain_allowed(plain_output, machine_output)?;
+
+            commands::sign::execute(playback_id, audience, expires, restriction)
+                .await
+                .context("Sign command failed")?
+        }
+        "storyboard" => {
+            let asset_id = positional(&args, 0)
+                .context("Please specify an asset ID for storyboard command")?
+                .trim();
+            if asset_id.is_empty() {
+                bail!("Asset ID cannot be empty");
+            }
+            let download_dir = match flag_value(&args, "--download").map(str::trim) {
+                Some("") => bail!("--download requires a directory"),
+                Some(dir) => Some(std::path::PathBuf::from(dir)),
+                None => None,
+            };
+            let expires = parse_expires_flag(&args)?;
+            plain_output = has_flag(&args, "--plain");
+            ensure_plain_allowed(plain_output, machine_output)?;
+
+            commands::storyboard::execute(asset_id, download_dir.as_deref(), expires)
+                .await
+                .context("Storyboard command failed")?
+        }
+        "serve" => {
+            let asset_id = positional(&args, 0)
+                .context("Please specify an asset ID for serve command")?
+                .trim();
+            if asset_id.is_empty() {
+                bail!("Asset ID cannot be empty");
+            }
+            let port = flag_value(&args, "--port")
+                .map(parse_port)
+                .transpose()?
+                .unwrap_or(APP_CONFIG.serve.default_port);
+            let expires = parse_expires_flag(&args)?;
+            plain_output = has_flag(&args, "--plain");
+            ensure_plain_allowed(plain_output, machine_output)?;
+
+            let server = commands::serve::start(asset_id, port, expires)
+                .await
+                .context("Serve command failed")?;
+            if !machine_output {
+                output::print_serve_started(server.info(), plain_output);
+            }
+            if !has_flag(&args, "--no-open")
+                && let Err(e) = browser::open(&server.info().url)
+                && !machine_output
+            {
+                let message = tf(Msg::ServeOpenFailed, &[&e]);
+                eprintln!("{}", style::warning(&tf(Msg::Warning, &[&message])));
+            }
+
+            server
+                .run(async {
+                    let _ = tokio::signal::ctrl_c().await;
+                })
+                .await
+                .context("Serve command failed")?
+        }
+        "restrictions" => match positional(&args, 0) {
+            Some("create") => {
+                let domains = flag_values(&args, "--domain");
+                commands::restrictions::create(&domains, has_flag(&args, "--allow-no-referrer"))
+                    .await
+                    .context("Restrictions create command failed")?
+            }
+            Some("list") => commands::restrictions::list()
+                .await
+                .context("Restrictions list command failed")?,
+            Some("delete") => {
+                let restriction_id = positional(&args, 1)
+                    .context("Please specify a playback restriction ID for restrictions delete")?
+                    .trim();
+
+                if restriction_id.is_empty() {
+                    bail!("Playback restriction ID cannot be empty");
+                }
+
+                commands::restrictions::delete(restriction_id)
+                    .await
+                    .context("Restrictions delete command failed")?
+            }
+            Some("attach") => {
+                let asset_id = positional(&args, 1)
+                    .context("Please specify an asset ID: 'restrictions attach <asset_id> <restriction_id>'")?
+                    .trim();
+                let restriction_id = positional(&args, 2)
+                    .context("Please specify a playback restriction ID: 'restrictions attach <asset_id> <restriction_id>'")?
+                    .trim();
+
+                if asset_id.is_empty() || restriction_id.is_empty() {
+                    bail!("Asset ID and playback restriction ID cannot be empty");
+                }
+
+                commands::restrictions::attach(asset_id, restriction_id)
+                    .await
+                    .context("Restrictions attach command failed")?
+            }
+            _ => bail!(
+                "Please specify a restrictions subcommand: 'restrictions create --domain <domain>', 'restrictions list', 'restrictions delete <id>' or 'restrictions attach <asset_id> <id>'"
+            ),
+        },
+        "stats" => {
+            let use_cache = !has_flag(&args, "--no-cache");
+            commands::stats::execute(use_cache)
+                .await
+                .context("Stats command failed")?
+        }
+        "whoami" => commands::whoami::execute()
+            .await
+            .context("Whoami command failed")?,
+        "version" => commands::version::execute()
+            .await
+            .context("Version command failed")?,
         "help" => commands::help::execute()
             .await
             .context("Help command failed")?,
@@ -136,7 +887,1514 @@ pub async fn parse_args(args: &[String]) -> Result<()> {
     };
 
     // コマンド結果を出力（プレゼンテーション層に委譲）
-    output::output_result(&result, machine_output)?;
+    if let Some(format) = snippet_format {
+        snippet::output_snippet(&result, format)?;
+    } else if plain_output {
+        output::output_plain(&result)?;
+    } else {
+        output::output_result(&result, machine_output)?;
+    }
+
+    // 一括処理で失敗した項目がある場合は0以外の終了コード
+    Ok(result.exit_code())
+}
+
+/// 値を取るフラグ（直後の引数はフラグの値として扱う）
+///
+/// 位置引数の判定（コマンド名の分離・履歴の記録）と各コマンドのフラグの値の取得（`upload` の解析を含む）は、
+/// すべてこの表を使う [`scan_args`] を通します。
+const VALUE_FLAGS: &[&str] = &[
+    "--manifest",
+    "--concurrency",
+    "--limit-rate",
+    "--count",
+    "--token-file",
+    "--token-id",
+    "--token-secret",
+    "--tag",
+    "--output",
+    "--older-than",
+    "--keep",
+    "--on-full",
+    "--policy",
+    "--force-format",
+    "--limit",
+    "--expires",
+    "--type",
+    "--restriction",
+    "--domain",
+    "--resolution",
+    "--until",
+    "--timeout",
+    "--group-by",
+    "--transcode",
+    "--resume-wait",
+    "--thumbnail-width",
+    "--thumbnail-height",
+    "--download",
+    "--mp4",
+    "--language",
+    "--name",
+    "--port",
+];
+
+/// 履歴に記録するコマンド（状態を変更するもの）
+const HISTORY_COMMANDS: &[&str] = &[
+    "login",
+    "logout",
+    "upload",
+    "delete",
+    "rename",
+    "tag",
+    "import",
+    "sync",
+    "gc",
+    "prune",
+    "queue",
+    "retry",
+    "restrictions",
+    "renditions",
+    "captions",
+    "playback",
+    "live",
+];
+
+/// 履歴では値を伏せ字にするフラグ
+const SECRET_FLAGS: &[&str] = &["--token-secret"];
+
+/// 引数からコマンド（最初の位置引数）とそれ以外の引数を分離する
+fn split_command(args: &[String]) -> Option<(&str, Vec<String>)> {
+    let command_index = *positional_indices(args).first()?;
+    Some((
+        &args[command_index],
+        args.iter()
+            .enumerate()
+            .filter(|(i, _)| *i != command_index)
+            .map(|(_, arg)| arg.clone())
+            .collect(),
+    ))
+}
+
+/// 状態を変更するコマンドの実行を操作履歴に記録する
+///
+/// 秘密情報（`--token-secret` の値）は伏せ字にします。
+/// 記録の失敗は警告にとどめ、コマンドの結果には影響させません。
+///
+/// # 引数
+/// * `args` - プログラム名を含む引数（グローバルフラグは除去済み）
+/// * `outcome` - コマンドの結果（終了コードまたはエラー）
+pub fn record_history(args: &[String], outcome: &Result<i32>) {
+    let Some((command, command_args)) = split_command(args.get(1..).unwrap_or_default()) else {
+        return;
+    };
+    if !HISTORY_COMMANDS.contains(&command) {
+        return;
+    }
+
+    let (exit_code, error) = match outcome {
+        Ok(exit_code) => (*exit_code, None),
+        Err(e) => (1, Some(format!("{:#}", e))),
+    };
+    let entry = NewHistoryEntry {
+        command: command.to_string(),
+        args: redact_secrets(command_args),
+        exit_code,
+        error,
+    };
+
+    let appended = History::open_default()
+        .and_then(|history| history.append(entry, chrono::Utc::now().timestamp().to_string()));
+    if let Err(e) = appended {
+        eprintln!("Warning: Failed to record command history: {}", e);
+    }
+}
+
+/// 秘密情報を取るフラグの値を伏せ字にする
+fn redact_secrets(args: Vec<String>) -> Vec<String> {
+    let mut redact_next = false;
+    args.into_iter()
+        .map(|arg| {
+            let redacted = if redact_next {
+                REDACTED.to_string()
+            } else {
+                arg.clone()
+            };
+            redact_next = SECRET_FLAGS.contains(&arg.as_str());
+            redacted
+        })
+        .collect()
+}
+
+/// 履歴のコマンドを同じ引数で再実行する（再実行も履歴に記録する）
+async fn rerun_history(id: u64, machine_output: bool) -> Result<i32> {
+    let entry = History::open_default()
+        .and_then(|history| history.find(id))
+        .context("Failed to load command history")?
+        .with_context(|| {
+            format!(
+                "No history entry with ID {}. Run 'vidyeet history' to list them.",
+                id
+            )
+        })?;
+    if entry.is_redacted() {
+        bail!(
+            "History entry #{} contains redacted secrets and cannot be re-run",
+            id
+        );
+    }
+
+    // 先頭はプログラム名（parse_args は読み飛ばす）
+    let args: Vec<String> = ["vidyeet".to_string(), entry.command.clone()]
+        .into_iter()
+        .chain(entry.args.iter().cloned())
+        .collect();
+    if !machine_output {
+        eprintln!(
+            "{}",
+            style::hint(&tf(Msg::HistoryRerunning, &[&id, &args[1..].join(" ")]))
+        );
+    }
+
+    let outcome = Box::pin(parse_args(&args, machine_output)).await;
+    record_history(&args, &outcome);
+    outcome
+}
+
+/// 引数を分類したもの（[`scan_args`] の結果）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScannedArg<'a> {
+    /// 位置引数（引数のインデックス）
+    Positional(usize),
+    /// 値を取らないフラグ
+    Flag(&'a str),
+    /// 値を取るフラグ（`VALUE_FLAGS`・`--copy`）とその値（値がない場合はNone）
+    Value(&'a str, Option<&'a str>),
+}
+
+/// 引数をフラグ・フラグの値・位置引数に分類する
+///
+/// 値を取るかは `VALUE_FLAGS` で判定し、直後の引数を値として扱います。
+/// `--copy` は値を省略できるため、URLの種類として解析できる場合のみ値として扱います。
+/// `--` 以降の引数は `-` で始まっていても位置引数として扱います。
+fn scan_args(args: &[String]) -> Vec<ScannedArg<'_>> {
+    let mut scanned = Vec::new();
+    let mut iter = args.iter().enumerate().peekable();
+    while let Some((index, arg)) = iter.next() {
+        let arg = arg.as_str();
+        if arg == "--" {
+            scanned.extend(iter.map(|(index, _)| ScannedArg::Positional(index)));
+            break;
+        }
+        if VALUE_FLAGS.contains(&arg) {
+            let value = iter.next().map(|(_, value)| value.as_str());
+            scanned.push(ScannedArg::Value(arg, value));
+        } else if arg == "--copy" {
+            let value = iter
+                .next_if(|(_, value)| CopyTarget::parse(value).is_some())
+                .map(|(_, value)| value.as_str());
+            scanned.push(ScannedArg::Value(arg, value));
+        } else if arg.starts_with("--") {
+            scanned.push(ScannedArg::Flag(arg));
+        } else {
+            scanned.push(ScannedArg::Positional(index));
+        }
+    }
+    scanned
+}
+
+/// 位置引数（フラグとフラグの値以外）のインデックスを列挙する
+fn positional_indices(args: &[String]) -> Vec<usize> {
+    scan_args(args)
+        .into_iter()
+        .filter_map(|arg| match arg {
+            ScannedArg::Positional(index) => Some(index),
+            _ => None,
+        })
+        .collect()
+}
+
+/// n番目（0始まり）の位置引数を取得する
+fn positional(args: &[String], n: usize) -> Option<&str> {
+    positional_indices(args)
+        .get(n)
+        .map(|&index| args[index].as_str())
+}
+
+/// 引数にフラグが含まれるか（`--` 以降・フラグの値は対象外）
+fn has_flag(args: &[String], flag: &str) -> bool {
+    scan_args(args).into_iter().any(|arg| match arg {
+        ScannedArg::Flag(name) | ScannedArg::Value(name, _) => name == flag,
+        ScannedArg::Positional(_) => false,
+    })
+}
+
+/// 値を取るフラグの値をすべて取得する（`--` 以降は対象外、値がない場合は空文字列）
+fn flag_values_of<'a>(args: &'a [String], flag: &str) -> Vec<&'a str> {
+    debug_assert!(
+        VALUE_FLAGS.contains(&flag) || flag == "--copy",
+        "{} must be listed in VALUE_FLAGS",
+        flag
+    );
+    scan_args(args)
+        .into_iter()
+        .filter_map(|arg| match arg {
+            ScannedArg::Value(name, value) if name == flag => Some(value.unwrap_or("")),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 値を取るフラグの値を取得する（`--` 以降は対象外）
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    flag_values_of(args, flag).into_iter().next()
+}
+
+/// `--copy [hls|mp4|thumbnail]` を解析する（指定がなければNone、値を省略した場合はHLS URL）
+fn copy_flag(args: &[String]) -> Option<CopyTarget> {
+    let value = flag_value(args, "--copy")?;
+    Some(CopyTarget::parse(value).unwrap_or_default())
+}
+
+/// 複数回指定できるフラグの値をすべて取得する（`--` 以降は対象外）
+fn flag_values(args: &[String], flag: &str) -> Vec<String> {
+    flag_values_of(args, flag)
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+}
+
+/// `sync` のアップロードの同時実行数・帯域上限・上限時の動作（`--concurrency` / `--limit-rate` / `--on-full`）を解析する
+fn parse_sync_options(args: &[String]) -> Result<BatchOptions> {
+    let mut options = BatchOptions::default();
+    if let Some(value) = flag_value(args, "--concurrency") {
+        options.concurrency = parse_concurrency(value)?;
+    }
+    if let Some(value) = flag_value(args, "--limit-rate") {
+        options.bytes_per_second = Some(parse_byte_rate(value)?);
+    }
+    if let Some(value) = flag_value(args, "--on-full") {
+        options.upload.on_full = Some(parse_on_full(value)?);
+    }
+    Ok(options)
+}
+
+/// `--concurrency` の値を検証する（1以上。上限への丸めは各コマンドで行う）
+fn parse_concurrency(value: &str) -> Result<usize> {
+    value
+        .parse::<usize>()
+        .ok()
+        .filter(|n| *n > 0)
+        .with_context(|| {
+            format!(
+                "Invalid --concurrency value: '{}' (expected 1 or more)",
+                value
+            )
+        })
+}
+
+/// 経過時間の指定（例: "90d", "12h", "2w", "3600"）を秒に変換する（`gc --older-than`）
+fn parse_age(value: &str) -> Result<u64> {
+    parse_duration_secs(value)
+        .with_context(|| format!("Invalid --older-than value: '{}' (e.g. 90d, 12h)", value))
+}
+
+/// `show` のサムネイル画像の大きさ（`--thumbnail-width` / `--thumbnail-height`）を解析する
+fn parse_thumbnail_size_flags(args: &[String]) -> Result<ThumbnailSize> {
+    let dimension = |flag: &str| {
+        flag_value(args, flag)
+            .map(|value| parse_thumbnail_dimension(flag, value))
+            .transpose()
+    };
+    Ok(ThumbnailSize {
+        width: dimension("--thumbnail-width")?,
+        height: dimension("--thumbnail-height")?,
+    })
+}
+
+/// `--thumbnail-width` / `--thumbnail-height` の値（1〜`APP_CONFIG.thumbnail.max_dimension` ピクセル）を解析する
+fn parse_thumbnail_dimension(flag: &str, value: &str) -> Result<u32> {
+    let max = APP_CONFIG.thumbnail.max_dimension;
+    value
+        .trim()
+        .parse::<u32>()
+        .ok()
+        .filter(|pixels| (1..=max).contains(pixels))
+        .with_context(|| {
+            format!(
+                "Invalid {} value: '{}' (expected 1-{} pixels)",
+                flag, value, max
+            )
+        })
+}
+
+/// 署名付きURLの有効期間（`--expires`、例: "1h", "30m"）を解析する（1秒以上）
+fn parse_expires_flag(args: &[String]) -> Result<Option<u64>> {
+    flag_value(args, "--expires").map(parse_expires).transpose()
+}
+
+/// `--port` の値をポート番号に変換する（0はOSが空いているポートを選ぶ）
+fn parse_port(value: &str) -> Result<u16> {
+    value
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid --port value: '{}' (0-65535)", value))
+}
+
+/// `--expires` の値を秒に変換する（1秒以上）
+fn parse_expires(value: &str) -> Result<u64> {
+    parse_duration_secs(value)
+        .filter(|secs| *secs > 0)
+        .with_context(|| format!("Invalid --expires value: '{}' (e.g. 1h, 30m)", value))
+}
+
+/// 期間の指定（例: "90d", "12h", "2w", "3600"）を秒に変換する
+///
+/// 接尾辞は s（秒）・m（分）・h（時間）・d（日）・w（週）で、省略時は秒として扱います。
+fn parse_duration_secs(value: &str) -> Option<u64> {
+    let trimmed = value.trim();
+    let (number, multiplier) = match trimmed.chars().last().map(|c| c.to_ascii_lowercase()) {
+        Some('s') => (&trimmed[..trimmed.len() - 1], 1),
+        Some('m') => (&trimmed[..trimmed.len() - 1], 60),
+        Some('h') => (&trimmed[..trimmed.len() - 1], 60 * 60),
+        Some('d') => (&trimmed[..trimmed.len() - 1], 24 * 60 * 60),
+        Some('w') => (&trimmed[..trimmed.len() - 1], 7 * 24 * 60 * 60),
+        _ => (trimmed, 1),
+    };
+
+    number
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+}
+
+/// `prune --keep` の値を検証する（1以上。すべて削除する用途には使わせない）
+fn parse_keep(value: &str) -> Result<usize> {
+    value
+        .parse::<usize>()
+        .ok()
+        .filter(|n| *n > 0)
+        .with_context(|| format!("Invalid --keep value: '{}' (expected 1 or more)", value))
+}
+
+/// `history --limit` の値を検証する（1以上）
+fn parse_history_limit(value: &str) -> Result<usize> {
+    value
+        .parse::<usize>()
+        .ok()
+        .filter(|n| *n > 0)
+        .with_context(|| format!("Invalid --limit value: '{}' (expected 1 or more)", value))
+}
+
+/// `--on-full` の値（ask / auto_delete / fail）を解析する
+fn parse_on_full(value: &str) -> Result<CapacityPolicy> {
+    CapacityPolicy::parse(value).with_context(|| {
+        format!(
+            "Invalid --on-full value: '{}' (expected one of: {})",
+            value,
+            CapacityPolicy::NAMES.join(", ")
+        )
+    })
+}
+
+/// `upload --policy` の値（public / signed）を解析する
+fn parse_playback_policy(value: &str) -> Result<PlaybackPolicy> {
+    PlaybackPolicy::parse(value).with_context(|| {
+        format!(
+            "Invalid --policy value: '{}' (expected one of: {})",
+            value,
+            PlaybackPolicy::NAMES.join(", ")
+        )
+    })
+}
+
+/// `upload --mp4` の値（highest / 1080p / 720p / audio-only / none）を解析する
+fn parse_mp4_rendition(value: &str) -> Result<Mp4Rendition> {
+    Mp4Rendition::parse(value).with_context(|| {
+        format!(
+            "Invalid --mp4 value: '{}' (expected one of: {})",
+            value,
+            Mp4Rendition::NAMES.join(", ")
+        )
+    })
+}
+
+/// `sign --type` の値（video / thumbnail / storyboard / gif）を解析する
+fn parse_sign_type(value: &str) -> Result<Audience> {
+    Audience::parse(value).with_context(|| {
+        format!(
+            "Invalid --type value: '{}' (expected one of: {})",
+            value,
+            Audience::NAMES.join(", ")
+        )
+    })
+}
+
+/// `wait --until` の値を解析する
+fn parse_wait_until(value: &str) -> Result<WaitUntil> {
+    WaitUntil::parse(value).with_context(|| {
+        format!(
+            "Invalid --until value: '{}' (expected one of: {})",
+            value,
+            WaitUntil::NAMES.join(", ")
+        )
+    })
+}
+
+/// `list --group-by` の値を解析する
+fn parse_group_by(value: &str) -> Result<GroupBy> {
+    GroupBy::parse(value).with_context(|| {
+        format!(
+            "Invalid --group-by value: '{}' (expected one of: {})",
+            value,
+            GroupBy::NAMES.join(", ")
+        )
+    })
+}
 
+/// `wait --timeout` の値（例: "600", "10m"）を秒に変換する（1秒以上）
+fn parse_wait_timeout(value: &str) -> Result<u64> {
+    parse_duration_secs(value)
+        .filter(|secs| *secs > 0)
+        .with_context(|| format!("Invalid --timeout value: '{}' (e.g. 600, 10m)", value))
+}
+
+/// `ping --count` の値を検証する
+fn parse_ping_count(value: &str) -> Result<u32> {
+    value
+        .parse::<u32>()
+        .ok()
+        .filter(|n| (1..=APP_CONFIG.ping.max_count).contains(n))
+        .with_context(|| {
+            format!(
+                "Invalid --count value: '{}' (expected 1 to {})",
+                value, APP_CONFIG.ping.max_count
+            )
+        })
+}
+
+/// `--plain` と `--machine` の併用を拒否する（どちらもstdoutの形式を決めるため）
+fn ensure_plain_allowed(plain_output: bool, machine_output: bool) -> Result<()> {
+    if plain_output && machine_output {
+        bail!("--plain cannot be combined with --machine");
+    }
+    Ok(())
+}
+
+/// `--output markdown|html`（`upload` / `show`）の形式を解析する
+fn parse_snippet_format(value: &str) -> Result<SnippetFormat> {
+    SnippetFormat::parse(value).with_context(|| {
+        format!(
+            "Invalid --output format: '{}' (expected {})",
+            value,
+            SnippetFormat::NAMES.join(" or ")
+        )
+    })
+}
+
+/// `--output markdown|html` と `--plain` / `--machine` の併用を拒否する（いずれもstdoutの形式を決めるため）
+fn ensure_snippet_allowed(
+    snippet_format: Option<SnippetFormat>,
+    plain_output: bool,
+    machine_output: bool,
+) -> Result<()> {
+    if snippet_format.is_some() {
+        if plain_output {
+            bail!("--output cannot be combined with --plain");
+        }
+        if machine_output {
+            bail!("--output cannot be combined with --machine");
+        }
+    }
     Ok(())
 }
+
+/// uploadコマンドを実行する（単一ファイル・複数ファイルを振り分け）
+async fn run_upload(upload_args: UploadArgs, machine_output: bool) -> Result<CommandResult> {
+    let show_progress = upload_args.show_progress;
+
+    // --resume-wait: 送信済みのアップロードのアセット作成を待つ
+    if let Some(upload_id) = upload_args.resume_upload_id {
+        let file_path = upload_args.file_paths.into_iter().next();
+        let (progress_tx, progress_rx) = vidyeet::progress_channel();
+        let options = upload_args.options.upload;
+
+        let wait_handle = tokio::spawn(async move {
+            commands::upload::resume_wait(
+                &upload_id,
+                file_path.as_deref(),
+                options,
+                Some(progress_tx),
+            )
+            .await
+        });
+        let progress_handle = tokio::spawn(async move {
+            progress::handle_upload_progress(progress_rx, machine_output, show_progress).await
+        });
+
+        let result = wait_handle
+            .await
+            .context("Upload task panicked")?
+            .context("Upload command failed")?;
+
+        progress_handle
+            .await
+            .context("Progress handler panicked")?
+            .context("Progress handler failed")?;
+
+        return Ok(result);
+    }
+
+    // 複数ファイルまたはディレクトリ指定時は並行アップロード
+    let is_batch = upload_args.file_paths.len() > 1
+        || std::path::Path::new(&upload_args.file_paths[0]).is_dir();
+
+    if is_batch {
+        if upload_args.options.upload.dry_run {
+            bail!("--dry-run can only be used with a single file");
+        }
+
+        let user_config = UserConfig::load()
+            .context("Failed to load user configuration. Please check your config.toml file.")?;
+        let merged_options = upload_args
+            .options
+            .upload
+            .clone()
+            .with_user_defaults(&user_config);
+        let file_paths = commands::batch_upload::expand_paths(
+            &upload_args.file_paths,
+            &merged_options.supported_formats(),
+        )?;
+
+        // 進捗通知チャネルを作成（全ファイルの進捗を多重化）
+        let (progress_tx, progress_rx) = tokio::sync::mpsc::unbounded_channel();
+        let _pause_controls = input::PauseControls::start(!machine_output);
+
+        let upload_handle = tokio::spawn(async move {
+            commands::batch_upload::execute(&file_paths, upload_args.options, Some(progress_tx))
+                .await
+        });
+
+        let progress_handle = tokio::spawn(async move {
+            progress::handle_batch_upload_progress(progress_rx, machine_output, show_progress).await
+        });
+
+        let upload_result = upload_handle
+            .await
+            .context("Upload task panicked")?
+            .context("Upload command failed")?;
+
+        progress_handle
+            .await
+            .context("Progress handler panicked")?
+            .context("Progress handler failed")?;
+
+        Ok(upload_result)
+    } else {
+        let file_path = upload_args.file_paths[0].clone();
+        let mut upload_options = upload_args.options.upload;
+
+        // 上限に達した場合、capacity_policy が ask なら表示した最も古いアセットの削除を確認する
+        // （--force は重複の確認のみを省略し、削除は確認する。--machine では確認できないため fail と同じ）
+        if !machine_output {
+            upload_options.confirm_delete =
+                Some(ConfirmDelete::new(input::confirm_capacity_delete));
+        }
+
+        run_single_upload(&file_path, upload_options, machine_output, show_progress).await
+    }
+}
+
+/// 1ファイルをアップロードし、進捗を表示する
+async fn run_single_upload(
+    file_path: &str,
+    upload_options: UploadOptions,
+    machine_output: bool,
+    show_progress: bool,
+) -> Result<CommandResult> {
+    let file_path = file_path.to_string();
+
+    // 進捗通知チャネルを作成
+    let (progress_tx, progress_rx) = vidyeet::progress_channel();
+    // アップロード中のみ受け付ける（上限到達時の確認プロンプトの間は、入力を回答として受け取る）
+    let _pause_controls = input::PauseControls::start(!machine_output && !upload_options.dry_run);
+
+    // アップロード処理を別タスクで開始
+    let upload_handle = tokio::spawn(async move {
+        commands::upload::execute(&file_path, upload_options, Some(progress_tx)).await
+    });
+
+    // 進捗受信ループ（プレゼンテーション層に委譲）
+    let progress_handle = tokio::spawn(async move {
+        progress::handle_upload_progress(progress_rx, machine_output, show_progress).await
+    });
+
+    // 両方のタスクの完了を待機
+    let upload_result = upload_handle
+        .await
+        .context("Upload task panicked")?
+        .context("Upload command failed")?;
+
+    progress_handle
+        .await
+        .context("Progress handler panicked")?
+        .context("Progress handler failed")?;
+
+    Ok(upload_result)
+}
+
+/// アセットが指定した状態になるまで待機し、進捗を表示する
+async fn run_wait(
+    id: &str,
+    until: WaitUntil,
+    timeout_secs: Option<u64>,
+    machine_output: bool,
+    show_progress: bool,
+) -> Result<CommandResult> {
+    let id = id.to_string();
+    let (progress_tx, progress_rx) = vidyeet::progress_channel();
+
+    let wait_handle = tokio::spawn(async move {
+        commands::wait::execute(&id, until, timeout_secs, Some(progress_tx)).await
+    });
+    let progress_handle = tokio::spawn(async move {
+        progress::handle_upload_progress(progress_rx, machine_output, show_progress).await
+    });
+
+    let wait_result = wait_handle
+        .await
+        .context("Wait task panicked")?
+        .context("Wait command failed")?;
+
+    progress_handle
+        .await
+        .context("Progress handler panicked")?
+        .context("Progress handler failed")?;
+
+    Ok(wait_result)
+}
+
+/// フックに渡す環境変数（キーは`VIDYEET_`を除いた名前）
+type HookEnv = Vec<(&'static str, String)>;
+
+/// アップロード結果に応じてconfig.tomlの`[hooks]`を実行する
+///
+/// フックの失敗は警告にとどめ、アップロード結果（終了コード・出力）には影響させない。
+/// 設定ファイルが読めない場合はフックなしとして扱う。
+async fn run_upload_hooks(file_paths: &[String], outcome: &Result<CommandResult>) {
+    let hooks = match UserConfig::load() {
+        Ok(config) => config.hooks,
+        Err(_) => return,
+    };
+
+    let single_file_path = match file_paths {
+        [path] => Some(path.clone()),
+        _ => None,
+    };
+
+    let mut runs: Vec<(HookEvent, serde_json::Value, HookEnv)> = Vec::new();
+    match outcome {
+        Ok(result @ CommandResult::Upload(r)) => {
+            let mut env = vec![
+                ("COMMAND", "upload".to_string()),
+                ("ASSET_ID", r.asset_id.clone()),
+                ("FILE_PATH", r.file_path.clone()),
+            ];
+            let optional = [
+                ("PLAYBACK_ID", &r.playback_id),
+                ("HLS_URL", &r.hls_url),
+                ("MP4_URL", &r.mp4_url),
+            ];
+            env.extend(
+                optional
+                    .into_iter()
+                    .filter_map(|(key, value)| value.clone().map(|v| (key, v))),
+            );
+            runs.push((HookEvent::UploadSuccess, output::machine_json(result), env));
+        }
+        Ok(
+            result @ CommandResult::Batch(BatchResult {
+                details: BatchDetails::Upload(r),
+                ..
+            }),
+        ) => {
+            let env = vec![
+                ("COMMAND", "batch_upload".to_string()),
+                ("UPLOADED_COUNT", r.uploaded.len().to_string()),
+                ("FAILED_COUNT", r.failed.len().to_string()),
+            ];
+            let payload = output::machine_json(result);
+            if !r.uploaded.is_empty() {
+                runs.push((HookEvent::UploadSuccess, payload.clone(), env.clone()));
+            }
+            if !r.failed.is_empty() {
+                runs.push((HookEvent::UploadFailure, payload, env));
+            }
+        }
+        // --dry-run の見積もりはアップロードしていないためフックを実行しない
+        Ok(_) => {}
+        Err(e) => {
+            let message = format!("{:#}", e);
+            let payload = serde_json::json!({
+                "success": false,
+                "command": "upload",
+                "file_paths": file_paths,
+                "error": {
+                    "message": message
+                }
+            });
+            let mut env = vec![("COMMAND", "upload".to_string()), ("ERROR", message)];
+            if let Some(path) = &single_file_path {
+                env.push(("FILE_PATH", path.clone()));
+            }
+            runs.push((HookEvent::UploadFailure, payload, env));
+        }
+    }
+
+    for (event, payload, env) in runs {
+        if let Some(command) = event.command(&hooks)
+            && let Err(e) = hooks::run(event, command, &payload, &env).await
+        {
+            eprintln!("Warning: {}", e);
+        }
+    }
+}
+
+/// アップロード結果をconfig.tomlの`[notifications]`のWebhookへ送信する
+///
+/// 送信の失敗は警告にとどめ、アップロード結果（終了コード・出力）には影響させない。
+/// 見積もりのみ（`--dry-run`）の場合は送信しない。
+async fn send_upload_webhook(file_paths: &[String], outcome: &Result<CommandResult>) {
+    let Some(url) = UserConfig::load()
+        .ok()
+        .and_then(|config| config.notifications.webhook_url)
+    else {
+        return;
+    };
+
+    let message = match outcome {
+        Ok(CommandResult::Upload(r)) => webhook::upload_succeeded_message(r),
+        Ok(CommandResult::Batch(BatchResult {
+            details: BatchDetails::Upload(r),
+            ..
+        })) => webhook::batch_upload_message(r),
+        Ok(_) => return,
+        Err(e) => webhook::upload_failed_message(file_paths, &e.root_cause().to_string()),
+    };
+
+    if let Err(e) = webhook::send(&url, &message).await {
+        eprintln!("{}", style::warning(&tf(Msg::Warning, &[&e])));
+    }
+}
+
+/// アップロード結果のマニフェストを書き出す（`upload --manifest`）
+///
+/// 見積もりのみ（`--dry-run`）の場合はアップロードしていないため書き出しません。
+fn write_manifest(path: &std::path::Path, result: &CommandResult) -> Result<()> {
+    let manifest = match result {
+        CommandResult::Upload(r) => UploadManifest::new(std::slice::from_ref(r.as_ref()), &[]),
+        CommandResult::Batch(BatchResult {
+            details: BatchDetails::Upload(r),
+            ..
+        }) => UploadManifest::new(&r.uploaded, &r.failed),
+        _ => return Ok(()),
+    };
+
+    manifest.write(path).with_context(|| {
+        format!(
+            "Failed to write manifest to '{}' (the upload itself succeeded)",
+            path.display()
+        )
+    })
+}
+
+/// uploadコマンドの引数
+#[derive(Debug)]
+struct UploadArgs {
+    /// アップロード対象のファイル・ディレクトリ（1つ以上）
+    file_paths: Vec<String>,
+    /// 進捗表示フラグ
+    show_progress: bool,
+    /// 複数ファイル時の同時実行数・帯域上限
+    options: BatchOptions,
+    /// アップロード結果のマニフェストの書き出し先
+    manifest_path: Option<std::path::PathBuf>,
+    /// HLS URLだけをstdoutへ出力する（`--plain` / `--url-only`）
+    plain: bool,
+    /// 署名付きURLの有効期間（秒、`--expires`）
+    expires_secs: Option<u64>,
+    /// アセット作成の待機を再開するDirect UploadのID（`--resume-wait`）
+    resume_upload_id: Option<String>,
+    /// クリップボードにコピーするURL（`--copy`）
+    copy: Option<CopyTarget>,
+    /// 共有用スニペットの形式（`--output markdown|html`）
+    snippet: Option<SnippetFormat>,
+}
+
+/// uploadコマンドの引数を解析する
+///
+/// `upload <file>... [--progress] [--probe] [--dry-run] [--force] [--manifest <path>]
+///  [--concurrency <n>] [--limit-rate <rate>] [--policy <policy>] [--force-format <format>]
+///  [--plain] [--expires <duration>] [--resume-wait <upload_id>] [--copy [hls|mp4|thumbnail]]
+///  [--output markdown|html]`
+fn parse_upload_args(args: &[String]) -> Result<UploadArgs> {
+    let mut file_paths = Vec::new();
+    let mut resume_upload_id = None;
+    let mut show_progress = false;
+    let mut options = BatchOptions::default();
+    let mut manifest_path = None;
+    let mut plain = false;
+    let mut expires_secs = None;
+    let mut copy = None;
+    let mut snippet = None;
+
+    for arg in scan_args(args) {
+        match arg {
+            ScannedArg::Positional(index) => push_file_path(&mut file_paths, &args[index])?,
+            ScannedArg::Flag("--progress") => show_progress = true,
+            ScannedArg::Flag("--probe") => options.upload.probe = true,
+            ScannedArg::Flag("--dry-run") => options.upload.dry_run = true,
+            ScannedArg::Flag("--force") => options.upload.force = true,
+            ScannedArg::Flag("--wait-ready") => options.upload.wait_ready = true,
+            ScannedArg::Flag("--plain" | "--url-only") => plain = true,
+            // 値は省略でき、省略した場合はHLS URLをコピーする
+            ScannedArg::Value("--copy", value) => {
+                copy = Some(value.and_then(CopyTarget::parse).unwrap_or_default());
+            }
+            ScannedArg::Value("--output", value) => {
+                let value = value.context("--output requires a format (markdown or html)")?;
+                snippet = Some(parse_snippet_format(value)?);
+            }
+            ScannedArg::Value("--on-full", value) => {
+                let value =
+                    value.context("--on-full requires a policy (ask, auto_delete or fail)")?;
+                options.upload.on_full = Some(parse_on_full(value)?);
+            }
+            ScannedArg::Value("--force-format", value) => {
+                let value = value.context("--force-format requires a format (e.g. mp4)")?;
+                options.upload.force_format = Some(value.to_string());
+            }
+            ScannedArg::Value("--policy", value) => {
+                let value =
+                    value.context("--policy requires a playback policy (public or signed)")?;
+                options.upload.policy = Some(parse_playback_policy(value)?);
+            }
+            ScannedArg::Value("--transcode", value) => {
+                let value = value.context(
+                    "--transcode requires ffmpeg arguments (e.g. --transcode \"-c:v libx264 -crf 20\")",
+                )?;
+                // 起動前に引数を解釈できるか確認する（値はそのまま保存し、retryでも同じ変換を行う）
+                vidyeet::media::transcode::split_args(value)
+                    .map_err(|e| anyhow::anyhow!("Invalid --transcode value: {}", e))?;
+                options.upload.transcode = Some(value.to_string());
+            }
+            ScannedArg::Value("--mp4", value) => {
+                let value = value.with_context(|| {
+                    format!(
+                        "--mp4 requires a rendition ({})",
+                        Mp4Rendition::NAMES.join(", ")
+                    )
+                })?;
+                options.upload.mp4 = parse_mp4_rendition(value)?;
+            }
+            ScannedArg::Value(flag @ ("--thumbnail-width" | "--thumbnail-height"), value) => {
+                let value =
+                    value.with_context(|| format!("{} requires a number of pixels", flag))?;
+                let pixels = Some(parse_thumbnail_dimension(flag, value)?);
+                if flag == "--thumbnail-width" {
+                    options.upload.thumbnail.width = pixels;
+                } else {
+                    options.upload.thumbnail.height = pixels;
+                }
+            }
+            ScannedArg::Value("--manifest", value) => {
+                let path = value.context("--manifest requires a file path")?;
+                manifest_path = Some(std::path::PathBuf::from(path));
+            }
+            ScannedArg::Value("--concurrency", value) => {
+                let value = value.context("--concurrency requires a number")?;
+                options.concurrency = parse_concurrency(value)?;
+            }
+            ScannedArg::Value("--limit-rate", value) => {
+                let value = value.context("--limit-rate requires a rate")?;
+                options.bytes_per_second = Some(parse_byte_rate(value)?);
+            }
+            ScannedArg::Value("--expires", value) => {
+                let value = value.context("--expires requires a duration")?;
+                expires_secs = Some(parse_expires(value)?);
+            }
+            ScannedArg::Value("--resume-wait", value) => {
+                let value = value
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .context("--resume-wait requires a Direct Upload ID")?;
+                resume_upload_id = Some(value.to_string());
+            }
+            ScannedArg::Flag(flag) | ScannedArg::Value(flag, _) => {
+                bail!(
+                    "Unknown option for upload: '{}' (use '-- {}' for a file whose name starts with '--')",
+                    flag,
+                    flag
+                );
+            }
+        }
+    }
+
+    if resume_upload_id.is_some() {
+        // 待機の再開は送信済みの1ファイル分（ファイルは省略可）
+        if file_paths.len() > 1 {
+            bail!("--resume-wait accepts at most one file (the file that was uploaded)");
+        }
+        if options.upload.dry_run {
+            bail!("--resume-wait cannot be used with --dry-run");
+        }
+    } else if file_paths.is_empty() {
+        bail!("Please specify a file path for upload command");
+    }
+    if plain && options.upload.dry_run {
+        bail!("--plain cannot be used with --dry-run (nothing is uploaded)");
+    }
+    if snippet.is_some() && options.upload.dry_run {
+        bail!("--output cannot be used with --dry-run (nothing is uploaded)");
+    }
+    if copy.is_some() {
+        if options.upload.dry_run {
+            bail!("--copy cannot be used with --dry-run (nothing is uploaded)");
+        }
+        if file_paths.len() > 1
+            || file_paths
+                .first()
+                .is_some_and(|path| std::path::Path::new(path).is_dir())
+        {
+            bail!("--copy requires a single file (only one URL can be copied)");
+        }
+    }
+    if options.upload.transcode.is_some() {
+        if options.upload.dry_run {
+            bail!(
+                "--transcode cannot be used with --dry-run (the output size is unknown until transcoded)"
+            );
+        }
+        if resume_upload_id.is_some() {
+            bail!("--transcode cannot be used with --resume-wait");
+        }
+        if options.upload.force_format.is_some() {
+            bail!("--transcode cannot be used with --force-format (the output is always MP4)");
+        }
+    }
+
+    Ok(UploadArgs {
+        file_paths,
+        show_progress,
+        options,
+        manifest_path,
+        plain,
+        expires_secs,
+        resume_upload_id,
+        copy,
+        snippet,
+    })
+}
+
+/// アップロード対象のファイルパスを追加する（前後の空白は削除）
+fn push_file_path(file_paths: &mut Vec<String>, path: &str) -> Result<()> {
+    let path = path.trim();
+    if path.is_empty() {
+        bail!("File path cannot be empty");
+    }
+    file_paths.push(path.to_string());
+    Ok(())
+}
+
+/// 帯域指定（例: "500K", "10M", "1G", "65536"）をバイト/秒に変換する
+///
+/// 接尾辞は1024の累乗（K=KiB, M=MiB, G=GiB）で、大文字小文字は区別しません。
+fn parse_byte_rate(value: &str) -> Result<u64> {
+    let trimmed = value.trim();
+    let (number, multiplier) = match trimmed.chars().last().map(|c| c.to_ascii_uppercase()) {
+        Some('K') => (&trimmed[..trimmed.len() - 1], 1024),
+        Some('M') => (&trimmed[..trimmed.len() - 1], 1024 * 1024),
+        Some('G') => (&trimmed[..trimmed.len() - 1], 1024 * 1024 * 1024),
+        _ => (trimmed, 1),
+    };
+
+    number
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .filter(|bytes| *bytes > 0)
+        .with_context(|| format!("Invalid --limit-rate value: '{}' (e.g. 500K, 10M)", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_upload_args_single_file() {
+        let parsed =
+            parse_upload_args(&strings(&["video.mp4", "--progress", "--dry-run"])).unwrap();
+        assert_eq!(parsed.file_paths, ["video.mp4"]);
+        assert!(parsed.show_progress);
+        assert!(!parsed.options.upload.probe);
+        assert!(parsed.options.upload.dry_run);
+        assert_eq!(parsed.options.bytes_per_second, None);
+    }
+
+    #[test]
+    fn test_parse_upload_args_resume_wait() {
+        let parsed = parse_upload_args(&strings(&["--resume-wait", "up_123"])).unwrap();
+        assert_eq!(parsed.resume_upload_id.as_deref(), Some("up_123"));
+        assert!(parsed.file_paths.is_empty());
+
+        let parsed =
+            parse_upload_args(&strings(&["video.mp4", "--resume-wait", "up_123"])).unwrap();
+        assert_eq!(parsed.file_paths, ["video.mp4"]);
+
+        assert!(parse_upload_args(&strings(&["--resume-wait"])).is_err());
+        assert!(parse_upload_args(&strings(&["a.mp4", "b.mp4", "--resume-wait", "up_1"])).is_err());
+        assert!(parse_upload_args(&strings(&["--resume-wait", "up_1", "--dry-run"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_upload_args_batch_options() {
+        let parsed = parse_upload_args(&strings(&[
+            "a.mp4",
+            "b.mp4",
+            "--concurrency",
+            "4",
+            "--limit-rate",
+            "10M",
+            "--probe",
+            "--force",
+            "--manifest",
+            "uploads.json",
+            "--on-full",
+            "fail",
+        ]))
+        .unwrap();
+        assert!(parsed.options.upload.probe);
+        assert!(!parsed.options.upload.dry_run);
+        assert!(parsed.options.upload.force);
+        assert_eq!(
+            parsed.manifest_path.as_deref(),
+            Some(std::path::Path::new("uploads.json"))
+        );
+        assert_eq!(parsed.file_paths, ["a.mp4", "b.mp4"]);
+        assert!(!parsed.show_progress);
+        assert_eq!(parsed.options.concurrency, 4);
+        assert_eq!(parsed.options.bytes_per_second, Some(10 * 1024 * 1024));
+        assert_eq!(parsed.options.upload.on_full, Some(CapacityPolicy::Fail));
+        assert_eq!(parsed.options.upload.policy, None);
+    }
+
+    #[test]
+    fn test_parse_upload_args_force_format() {
+        let parsed =
+            parse_upload_args(&strings(&["capture.tmp", "--force-format", "mp4"])).unwrap();
+        assert_eq!(parsed.options.upload.force_format.as_deref(), Some("mp4"));
+        assert!(parse_upload_args(&strings(&["capture.tmp", "--force-format"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_upload_args_transcode() {
+        let parsed = parse_upload_args(&strings(&[
+            "screen.mkv",
+            "--transcode",
+            "-c:v libx264 -crf 20",
+        ]))
+        .unwrap();
+        assert_eq!(parsed.file_paths, ["screen.mkv"]);
+        assert_eq!(
+            parsed.options.upload.transcode.as_deref(),
+            Some("-c:v libx264 -crf 20")
+        );
+
+        assert!(parse_upload_args(&strings(&["a.mkv", "--transcode"])).is_err());
+        assert!(parse_upload_args(&strings(&["a.mkv", "--transcode", "-vf 'scale"])).is_err());
+        assert!(
+            parse_upload_args(&strings(&["a.mkv", "--transcode", "-c copy", "--dry-run"])).is_err()
+        );
+        assert!(
+            parse_upload_args(&strings(&[
+                "a.mkv",
+                "--transcode",
+                "-c copy",
+                "--force-format",
+                "mp4"
+            ]))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_thumbnail_size() {
+        let parsed = parse_upload_args(&strings(&[
+            "a.mp4",
+            "--thumbnail-width",
+            "640",
+            "--thumbnail-height",
+            "360",
+        ]))
+        .unwrap();
+        assert_eq!(parsed.file_paths, ["a.mp4"]);
+        assert_eq!(parsed.options.upload.thumbnail.width, Some(640));
+        assert_eq!(parsed.options.upload.thumbnail.height, Some(360));
+
+        let size =
+            parse_thumbnail_size_flags(&strings(&["show", "a1", "--thumbnail-width", "320"]))
+                .unwrap();
+        assert_eq!(size.width, Some(320));
+        assert_eq!(size.height, None);
+
+        for value in ["0", "-1", "wide", "100000"] {
+            assert!(parse_thumbnail_dimension("--thumbnail-width", value).is_err());
+        }
+        assert!(parse_upload_args(&strings(&["a.mp4", "--thumbnail-height"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_upload_args_policy() {
+        let parsed = parse_upload_args(&strings(&["a.mp4", "--policy", "Signed"])).unwrap();
+        assert_eq!(parsed.options.upload.policy, Some(PlaybackPolicy::Signed));
+    }
+
+    #[test]
+    fn test_parse_upload_args_rejects_invalid_values() {
+        assert!(parse_upload_args(&strings(&[])).is_err());
+        assert!(parse_upload_args(&strings(&["a.mp4", "--concurrency", "0"])).is_err());
+        assert!(parse_upload_args(&strings(&["a.mp4", "--concurrency"])).is_err());
+        assert!(parse_upload_args(&strings(&["a.mp4", "--limit-rate", "fast"])).is_err());
+        assert!(parse_upload_args(&strings(&["a.mp4", "--manifest"])).is_err());
+        assert!(parse_upload_args(&strings(&["a.mp4", "--on-full", "delete"])).is_err());
+        assert!(parse_upload_args(&strings(&["a.mp4", "--policy", "private"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_upload_mp4_rendition() {
+        let parsed = parse_upload_args(&strings(&["a.mp4"])).unwrap();
+        assert_eq!(parsed.options.upload.mp4, Mp4Rendition::Highest);
+
+        let parsed = parse_upload_args(&strings(&["a.mp4", "--mp4", "Audio-Only"])).unwrap();
+        assert_eq!(parsed.options.upload.mp4, Mp4Rendition::AudioOnly);
+
+        let parsed = parse_upload_args(&strings(&["a.mp4", "--mp4", "none"])).unwrap();
+        assert_eq!(parsed.options.upload.mp4, Mp4Rendition::None);
+
+        assert!(parse_upload_args(&strings(&["a.mp4", "--mp4", "4k"])).is_err());
+        assert!(parse_upload_args(&strings(&["a.mp4", "--mp4"])).is_err());
+        assert!(parse_upload_args(&strings(&["a.mp4", "--policy"])).is_err());
+        assert!(parse_upload_args(&strings(&["a.mp4", "--progres"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_upload_args_plain() {
+        assert!(
+            parse_upload_args(&strings(&["a.mp4", "--plain"]))
+                .unwrap()
+                .plain
+        );
+        assert!(
+            parse_upload_args(&strings(&["--url-only", "a.mp4"]))
+                .unwrap()
+                .plain
+        );
+        assert!(!parse_upload_args(&strings(&["a.mp4"])).unwrap().plain);
+        assert!(parse_upload_args(&strings(&["a.mp4", "--plain", "--dry-run"])).is_err());
+
+        assert!(ensure_plain_allowed(true, true).is_err());
+        assert!(ensure_plain_allowed(true, false).is_ok());
+    }
+
+    #[test]
+    fn test_parse_upload_args_copy() {
+        let parsed = parse_upload_args(&strings(&["a.mp4", "--copy"])).unwrap();
+        assert_eq!(parsed.copy, Some(CopyTarget::Hls));
+        let parsed = parse_upload_args(&strings(&["--copy", "thumbnail", "a.mp4"])).unwrap();
+        assert_eq!(parsed.copy, Some(CopyTarget::Thumbnail));
+        assert_eq!(parsed.file_paths, ["a.mp4"]);
+        // URLの種類でない値はファイルパスとして扱う
+        let parsed = parse_upload_args(&strings(&["--copy", "a.mp4"])).unwrap();
+        assert_eq!(parsed.copy, Some(CopyTarget::Hls));
+        assert_eq!(parsed.file_paths, ["a.mp4"]);
+        assert!(
+            parse_upload_args(&strings(&["a.mp4"]))
+                .unwrap()
+                .copy
+                .is_none()
+        );
+
+        assert!(parse_upload_args(&strings(&["a.mp4", "b.mp4", "--copy"])).is_err());
+        assert!(parse_upload_args(&strings(&["a.mp4", "--copy", "--dry-run"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_upload_args_output() {
+        let parsed = parse_upload_args(&strings(&["a.mp4", "--output", "markdown"])).unwrap();
+        assert_eq!(parsed.snippet, Some(SnippetFormat::Markdown));
+        assert!(
+            parse_upload_args(&strings(&["a.mp4"]))
+                .unwrap()
+                .snippet
+                .is_none()
+        );
+        assert!(parse_upload_args(&strings(&["a.mp4", "--output", "json"])).is_err());
+        assert!(parse_upload_args(&strings(&["a.mp4", "--output"])).is_err());
+        assert!(parse_upload_args(&strings(&["a.mp4", "--output", "html", "--dry-run"])).is_err());
+
+        let html = Some(SnippetFormat::Html);
+        assert!(ensure_snippet_allowed(html, false, false).is_ok());
+        assert!(ensure_snippet_allowed(html, true, false).is_err());
+        assert!(ensure_snippet_allowed(html, false, true).is_err());
+        assert!(ensure_snippet_allowed(None, true, false).is_ok());
+    }
+
+    #[test]
+    fn test_copy_flag() {
+        let args = strings(&["--copy", "asset123"]);
+        assert_eq!(copy_flag(&args), Some(CopyTarget::Hls));
+        assert_eq!(positional(&args, 0), Some("asset123"));
+
+        let args = strings(&["--copy", "MP4", "asset123"]);
+        assert_eq!(copy_flag(&args), Some(CopyTarget::Mp4));
+        assert_eq!(positional(&args, 0), Some("asset123"));
+
+        assert_eq!(copy_flag(&strings(&["asset123"])), None);
+        assert_eq!(copy_flag(&strings(&["--", "--copy"])), None);
+    }
+
+    #[test]
+    fn test_parse_upload_args_after_terminator() {
+        let parsed = parse_upload_args(&strings(&["--progress", "--", "--odd.mp4"])).unwrap();
+        assert_eq!(parsed.file_paths, ["--odd.mp4"]);
+        assert!(parsed.show_progress);
+    }
+
+    #[test]
+    fn test_take_debug_http_flag_stops_at_terminator() {
+        let (level, args) = take_debug_http_flag(strings(&[
+            "vidyeet",
+            "upload",
+            "--debug-http=body",
+            "--",
+            "--debug-http",
+        ]));
+        assert_eq!(level, TraceLevel::Bodies);
+        assert_eq!(args, strings(&["vidyeet", "upload", "--", "--debug-http"]));
+
+        let (level, args) = take_debug_http_flag(strings(&["vidyeet", "--", "--debug-http"]));
+        assert_eq!(level, TraceLevel::Off);
+        assert_eq!(args, strings(&["vidyeet", "--", "--debug-http"]));
+    }
+
+    #[test]
+    fn test_take_machine_flag_anywhere() {
+        let (machine, args) = take_machine_flag(strings(&[
+            "vidyeet",
+            "upload",
+            "a.mp4",
+            "--machine",
+            "--progress",
+        ]));
+        assert!(machine);
+        assert_eq!(args, strings(&["vidyeet", "upload", "a.mp4", "--progress"]));
+
+        // `--` 以降はファイル名として残す
+        let (machine, args) = take_machine_flag(strings(&["vidyeet", "upload", "--", "--machine"]));
+        assert!(!machine);
+        assert_eq!(args, strings(&["vidyeet", "upload", "--", "--machine"]));
+    }
+
+    #[test]
+    fn test_take_no_color_flag() {
+        let (no_color, args) = take_no_color_flag(strings(&["vidyeet", "list", "--no-color"]));
+        assert!(no_color);
+        assert_eq!(args, strings(&["vidyeet", "list"]));
+
+        let (no_color, args) =
+            take_no_color_flag(strings(&["vidyeet", "upload", "--", "--no-color"]));
+        assert!(!no_color);
+        assert_eq!(args, strings(&["vidyeet", "upload", "--", "--no-color"]));
+    }
+
+    #[test]
+    fn test_take_bytes_flag() {
+        let (raw_bytes, args) = take_bytes_flag(strings(&["vidyeet", "--bytes", "list"]));
+        assert!(raw_bytes);
+        assert_eq!(args, strings(&["vidyeet", "list"]));
+    }
+
+    #[test]
+    fn test_take_query_flag() {
+        let (query, args) = take_query_flag(strings(&[
+            "vidyeet",
+            "--query",
+            ".videos[].asset_id",
+            "list",
+        ]));
+        assert_eq!(query.as_deref(), Some(".videos[].asset_id"));
+        assert_eq!(args, strings(&["vidyeet", "list"]));
+
+        let (query, args) =
+            take_query_flag(strings(&["vidyeet", "show", "a1", "--query=.hls_url"]));
+        assert_eq!(query.as_deref(), Some(".hls_url"));
+        assert_eq!(args, strings(&["vidyeet", "show", "a1"]));
+
+        // 値がない場合は空の式（検証で失敗させる）
+        let (query, _) = take_query_flag(strings(&["vidyeet", "list", "--query"]));
+        assert_eq!(query.as_deref(), Some(""));
+
+        let (query, args) = take_query_flag(strings(&["vidyeet", "upload", "--", "--query"]));
+        assert!(query.is_none());
+        assert_eq!(args, strings(&["vidyeet", "upload", "--", "--query"]));
+    }
+
+    #[test]
+    fn test_positional_skips_flags_and_values() {
+        let args = strings(&["--progress", "--manifest", "out.json", "upload", "a.mp4"]);
+        assert_eq!(positional(&args, 0), Some("upload"));
+        assert_eq!(positional(&args, 1), Some("a.mp4"));
+        assert_eq!(positional(&args, 2), None);
+
+        let args = strings(&["--resume-wait", "5", "upload", "x.mp4"]);
+        assert_eq!(positional(&args, 0), Some("upload"));
+        assert_eq!(positional(&args, 1), Some("x.mp4"));
+
+        let args = strings(&["--force", "asset123"]);
+        assert_eq!(positional(&args, 0), Some("asset123"));
+        assert!(has_flag(&args, "--force"));
+        assert!(!has_flag(&strings(&["--", "--force"]), "--force"));
+    }
+
+    #[test]
+    fn test_policy_value_is_not_a_command() {
+        let args = strings(&["--policy", "signed", "upload", "x.mp4"]);
+        assert_eq!(positional(&args, 0), Some("upload"));
+        assert_eq!(positional(&args, 1), Some("x.mp4"));
+        let (command, rest) = split_command(&args).unwrap();
+        assert_eq!(command, "upload");
+        assert_eq!(rest, strings(&["--policy", "signed", "x.mp4"]));
+    }
+
+    #[test]
+    fn test_scan_args_reads_values_from_value_flags() {
+        let args = strings(&[
+            "--force-format",
+            "mp4",
+            "upload",
+            "--copy",
+            "clip.bin",
+            "--manifest",
+            "--progress",
+            "--",
+            "--policy",
+        ]);
+        assert_eq!(
+            scan_args(&args),
+            [
+                ScannedArg::Value("--force-format", Some("mp4")),
+                ScannedArg::Positional(2),
+                ScannedArg::Value("--copy", None),
+                ScannedArg::Positional(4),
+                ScannedArg::Value("--manifest", Some("--progress")),
+                ScannedArg::Positional(8),
+            ]
+        );
+        assert_eq!(flag_value(&args, "--policy"), None);
+        assert_eq!(copy_flag(&args), Some(CopyTarget::Hls));
+        assert_eq!(
+            scan_args(&strings(&["--expires"])),
+            [ScannedArg::Value("--expires", None)]
+        );
+
+        // upload の値もフラグの表から読むため、コマンドの前に置いたフラグの値と同じに解析される
+        let parsed = parse_upload_args(&strings(&["--force-format", "mp4", "clip.bin"])).unwrap();
+        assert_eq!(parsed.file_paths, ["clip.bin"]);
+        assert_eq!(parsed.options.upload.force_format.as_deref(), Some("mp4"));
+    }
+
+    #[test]
+    fn test_flag_values_collects_repeated_flags() {
+        let args = strings(&[
+            "create",
+            "--domain",
+            "example.com",
+            "--allow-no-referrer",
+            "--domain",
+            "*.example.com",
+        ]);
+        assert_eq!(
+            flag_values(&args, "--domain"),
+            ["example.com", "*.example.com"]
+        );
+        assert_eq!(positional(&args, 0), Some("create"));
+        assert_eq!(positional(&args, 1), None);
+        assert!(flag_values(&strings(&["--", "--domain", "a.com"]), "--domain").is_empty());
+    }
+
+    #[test]
+    fn test_ping_count() {
+        let args = strings(&["--count", "3"]);
+        assert_eq!(flag_value(&args, "--count"), Some("3"));
+        assert_eq!(parse_ping_count("3").unwrap(), 3);
+        assert!(parse_ping_count("0").is_err());
+        assert!(parse_ping_count("").is_err());
+        assert!(parse_ping_count(&(APP_CONFIG.ping.max_count + 1).to_string()).is_err());
+        assert_eq!(flag_value(&strings(&["--", "--count"]), "--count"), None);
+    }
+
+    #[test]
+    fn test_parse_byte_rate() {
+        assert_eq!(parse_byte_rate("65536").unwrap(), 65536);
+        assert_eq!(parse_byte_rate("500k").unwrap(), 500 * 1024);
+        assert_eq!(parse_byte_rate("1G").unwrap(), 1024 * 1024 * 1024);
+        assert!(parse_byte_rate("0").is_err());
+        assert!(parse_byte_rate("M").is_err());
+    }
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("3600").unwrap(), 3600);
+        assert_eq!(parse_age("90d").unwrap(), 90 * 86_400);
+        assert_eq!(parse_age("2W").unwrap(), 14 * 86_400);
+        assert_eq!(parse_age("30m").unwrap(), 1800);
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("-1d").is_err());
+    }
+
+    #[test]
+    fn test_parse_expires() {
+        assert_eq!(parse_expires("1h").unwrap(), 3600);
+        assert_eq!(parse_expires("90").unwrap(), 90);
+        assert!(parse_expires("0").is_err());
+        assert!(parse_expires("soon").is_err());
+    }
+
+    #[test]
+    fn test_parse_port() {
+        assert_eq!(parse_port("8080").unwrap(), 8080);
+        assert_eq!(parse_port(" 0 ").unwrap(), 0);
+        assert!(parse_port("65536").is_err());
+        assert!(parse_port("-1").is_err());
+    }
+
+    #[test]
+    fn test_parse_keep() {
+        assert_eq!(parse_keep("20").unwrap(), 20);
+        assert!(parse_keep("0").is_err());
+        assert!(parse_keep("ten").is_err());
+    }
+
+    #[test]
+    fn test_redact_secrets() {
+        let args = strings(&["--token-id", "id123", "--token-secret", "s3cr3t"]);
+        assert_eq!(
+            redact_secrets(args),
+            ["--token-id", "id123", "--token-secret", REDACTED]
+        );
+        assert!(parse_history_limit("0").is_err());
+    }
+}
@@ -0,0 +1,419 @@
+use crate::api::types::{AssetData, AssetStatus, RenditionStatus};
+/// バックアップコマンド
+///
+/// ready状態のMP4レンディションを持つ全アセットについて、MP4を出力先ディレクトリの
+/// `<asset_id>/<ファイル名>` にダウンロードします。
+/// 既にあるファイルは、Mux APIが返すファイルサイズと前回のダウンロード記録（SHA-256）に
+/// 一致すればダウンロードしません。ダウンロードは同時実行数を制限して並行に行います。
+use crate::commands::connect;
+use crate::commands::result::{
+    BackupFailure, BackupFile, BackupResult, BatchDetails, BatchResult, CommandResult,
+};
+use crate::commands::upload;
+use crate::config::APP_CONFIG;
+use crate::storage::backup::{BackupRecord, BackupState};
+use anyhow::{Context, Result, bail};
+use futures_util::{StreamExt, TryStreamExt};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// ダウンロードするファイル
+#[derive(Debug, Clone, PartialEq)]
+struct BackupTarget {
+    /// アセットID
+    asset_id: String,
+    /// ダウンロード元のURL
+    url: String,
+    /// 出力先からの相対パス（`<asset_id>/<ファイル名>`）
+    relative_path: String,
+    /// Mux APIが返したファイルサイズ（bytes）
+    expected_size: Option<u64>,
+}
+
+/// 1ファイルのダウンロード結果
+enum Outcome {
+    Downloaded(BackupRecord),
+    UpToDate(u64),
+    Failed(String),
+}
+
+/// バックアップコマンドを実行する
+///
+/// # 引数
+/// * `output_dir` - 出力先ディレクトリ（なければ作成する）
+/// * `concurrency` - 同時ダウンロード数（1〜`max_concurrency`に丸める）
+///
+/// # 戻り値
+/// 成功・失敗を示すResult<CommandResult>。個々のファイルの失敗は結果に含める
+pub async fn execute(output_dir: &Path, concurrency: usize) -> Result<CommandResult> {
+    let started_at = Instant::now();
+    if output_dir.is_file() {
+        bail!("Output path is a file: {}", output_dir.display());
+    }
+
+    // 認証マネージャーとAPIクライアントを初期化（最新のレンディションを得るためキャッシュは使わない）
+    let (client, auth_manager) = connect()?;
+
+    let auth_header = auth_manager.get_auth_header();
+    let assets: Vec<AssetData> = client
+        .list_assets(&auth_header)
+        .try_collect()
+        .await
+        .context("Failed to fetch assets list")?;
+    let (targets, signed_only) = collect_targets(&assets);
+
+    std::fs::create_dir_all(output_dir).with_context(|| {
+        format!(
+            "Failed to create output directory: {}",
+            output_dir.display()
+        )
+    })?;
+    let mut state = BackupState::load(output_dir).context("Failed to load backup state")?;
+
+    // 再生URL（stream.mux.com）は認証不要のため、APIクライアントとは別のクライアントを使う
+    let http = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(APP_CONFIG.backup.connect_timeout_secs))
+        .build()
+        .context("Failed to create HTTP client")?;
+    let concurrency = concurrency.clamp(1, APP_CONFIG.backup.max_concurrency);
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+
+    let mut tasks = JoinSet::new();
+    for (index, target) in targets.iter().enumerate() {
+        let http = http.clone();
+        let semaphore = Arc::clone(&semaphore);
+        let target = target.clone();
+        let path = output_dir.join(&target.relative_path);
+        let record = state.get(&target.relative_path).cloned();
+
+        tasks.spawn(async move {
+            // セマフォはクローズしないため取得に失敗することはない
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .expect("backup semaphore is never closed");
+            (
+                index,
+                backup_file(&http, &target, &path, record.as_ref()).await,
+            )
+        });
+    }
+
+    // 完了順に受け取り、結果はアセットの順に並べ直す
+    let mut outcomes: Vec<Option<Outcome>> = targets.iter().map(|_| None).collect();
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok((index, outcome)) = joined {
+            outcomes[index] = Some(outcome);
+        }
+    }
+
+    let mut result = BackupResult {
+        output_dir: output_dir.display().to_string(),
+        downloaded: Vec::new(),
+        up_to_date: Vec::new(),
+        signed_only,
+        failed: Vec::new(),
+        downloaded_bytes: 0,
+    };
+    for (target, outcome) in targets.iter().zip(outcomes) {
+        let path = output_dir.join(&target.relative_path).display().to_string();
+        let file = |size| BackupFile {
+            asset_id: target.asset_id.clone(),
+            path: path.clone(),
+            size,
+        };
+        match outcome {
+            Some(Outcome::Downloaded(record)) => {
+                result.downloaded_bytes += record.size;
+                result.downloaded.push(file(record.size));
+                state.record(&target.relative_path, record);
+            }
+            Some(Outcome::UpToDate(size)) => result.up_to_date.push(file(size)),
+            Some(Outcome::Failed(error)) => result.failed.push(BackupFailure {
+                asset_id: target.asset_id.clone(),
+                path,
+                error,
+            }),
+            None => result.failed.push(BackupFailure {
+                asset_id: target.asset_id.clone(),
+                path,
+                error: "Download task terminated unexpectedly".to_string(),
+            }),
+        }
+    }
+
+    if !result.downloaded.is_empty() {
+        state
+            .save(output_dir)
+            .context("Failed to save backup state")?;
+    }
+
+    Ok(CommandResult::Batch(BatchResult::new(
+        BatchDetails::Backup(result),
+        started_at.elapsed(),
+    )))
+}
+
+/// ダウンロードするファイルと、公開の再生IDがないため対象外のアセットを取り出す
+///
+/// ready状態のアセットの、ready状態のMP4レンディションが対象です。
+fn collect_targets(assets: &[AssetData]) -> (Vec<BackupTarget>, Vec<String>) {
+    let mut targets = Vec::new();
+    let mut signed_only = Vec::new();
+
+    for asset in assets
+        .iter()
+        .filter(|asset| asset.status == AssetStatus::Ready)
+    {
+        let renditions: Vec<_> = asset
+            .static_renditions
+            .iter()
+            .flat_map(|wrapper| &wrapper.files)
+            .filter(|r| r.status == RenditionStatus::Ready && r.ext == "mp4")
+            .collect();
+        if renditions.is_empty() {
+            continue;
+        }
+
+        let Some(playback_id) = asset.playback_ids.iter().find(|p| p.policy == "public") else {
+            signed_only.push(asset.id.clone());
+            continue;
+        };
+        targets.extend(renditions.into_iter().map(|rendition| BackupTarget {
+            asset_id: asset.id.clone(),
+            url: format!(
+                "https://stream.mux.com/{}/{}",
+                playback_id.id, rendition.name
+            ),
+            relative_path: format!("{}/{}", asset.id, rendition.name),
+            expected_size: rendition.file_size(),
+        }));
+    }
+
+    (targets, signed_only)
+}
+
+/// 既にあるファイルを確認し、一致しなければダウンロードする
+async fn backup_file(
+    http: &reqwest::Client,
+    target: &BackupTarget,
+    path: &Path,
+    record: Option<&BackupRecord>,
+) -> Outcome {
+    match is_up_to_date(path, target.expected_size, record).await {
+        Ok(Some(size)) => return Outcome::UpToDate(size),
+        Ok(None) => {}
+        Err(e) => return Outcome::Failed(format!("{:#}", e)),
+    }
+
+    match download(http, target, path).await {
+        Ok(record) => Outcome::Downloaded(record),
+        Err(e) => Outcome::Failed(format!("{:#}", e)),
+    }
+}
+
+/// 既にあるファイルがダウンロード済みのものと一致するか（一致する場合はサイズを返す）
+///
+/// Mux APIのファイルサイズと前回の記録（サイズ・SHA-256）のうち、分かるものをすべて照合します。
+/// どちらもない場合は確認できないため、ダウンロードし直します。
+async fn is_up_to_date(
+    path: &Path,
+    expected_size: Option<u64>,
+    record: Option<&BackupRecord>,
+) -> Result<Option<u64>> {
+    let Ok(metadata) = tokio::fs::metadata(path).await else {
+        return Ok(None);
+    };
+    let size = metadata.len();
+
+    if !size_matches(size, expected_size, record) {
+        return Ok(None);
+    }
+    if let Some(record) = record {
+        let sha256 = upload::sha256_file(&path.to_string_lossy()).await?;
+        if sha256 != record.sha256 {
+            return Ok(None);
+        }
+    }
+    Ok(Some(size))
+}
+
+/// ファイルサイズが照合できる値とすべて一致するか（照合できる値がない場合はfalse）
+fn size_matches(size: u64, expected_size: Option<u64>, record: Option<&BackupRecord>) -> bool {
+    let known: Vec<u64> = expected_size
+        .into_iter()
+        .chain(record.map(|record| record.size))
+        .collect();
+    !known.is_empty() && known.iter().all(|known| *known == size)
+}
+
+/// 一時ファイル（`.part`）にダウンロードしてから置き換える
+async fn download(
+    http: &reqwest::Client,
+    target: &BackupTarget,
+    path: &Path,
+) -> Result<BackupRecord> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    let response = http
+        .get(&target.url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to download {}", target.url))?;
+    let status = response.status();
+    if !status.is_success() {
+        bail!(
+            "Failed to download {}: HTTP {}",
+            target.url,
+            status.as_u16()
+        );
+    }
+
+    let part_path = part_path(path);
+    let mut file = tokio::fs::File::create(&part_path)
+        .await
+        .with_context(|| format!("Failed to create {}", part_path.display()))?;
+    let mut digest = Sha256::new();
+    let mut size = 0u64;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.with_context(|| format!("Download of {} was interrupted", target.url))?;
+        digest.update(&chunk);
+        size += chunk.len() as u64;
+        file.write_all(&chunk)
+            .await
+            .with_context(|| format!("Failed to write {}", part_path.display()))?;
+    }
+    file.flush().await?;
+    drop(file);
+
+    if let Some(expected) = target.expected_size
+        && expected != size
+    {
+        let _ = tokio::fs::remove_file(&part_path).await;
+        bail!(
+            "Downloaded size of {} ({} bytes) does not match the expected size ({} bytes)",
+            target.url,
+            size,
+            expected
+        );
+    }
+    tokio::fs::rename(&part_path, path)
+        .await
+        .with_context(|| format!("Failed to move {} into place", part_path.display()))?;
+
+    Ok(BackupRecord {
+        asset_id: target.asset_id.clone(),
+        size,
+        sha256: format!("{:x}", digest.finalize()),
+        downloaded_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+    })
+}
+
+/// ダウンロード中の一時ファイルのパス
+fn part_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".part");
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn asset(policy: &str, mp4_status: &str) -> AssetData {
+        serde_json::from_value(json!({
+            "id": "asset1",
+            "status": "ready",
+            "created_at": "1700000000",
+            "playback_ids": [{ "id": "play1", "policy": policy }],
+            "static_renditions": { "files": [
+                {
+                    "id": "r1", "type": "standard", "status": mp4_status,
+                    "resolution": "highest", "name": "highest.mp4", "ext": "mp4",
+                    "filesize": "2048"
+                },
+                {
+                    "id": "r2", "type": "standard", "status": "ready",
+                    "resolution": "audio-only", "name": "audio.m4a", "ext": "m4a"
+                }
+            ]}
+        }))
+        .unwrap()
+    }
+
+    fn record(size: u64) -> BackupRecord {
+        BackupRecord {
+            asset_id: "asset1".to_string(),
+            size,
+            sha256: "00".to_string(),
+            downloaded_at: "2024-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_collect_targets() {
+        let (targets, signed_only) = collect_targets(&[asset("public", "ready")]);
+        assert_eq!(
+            targets,
+            [BackupTarget {
+                asset_id: "asset1".to_string(),
+                url: "https://stream.mux.com/play1/highest.mp4".to_string(),
+                relative_path: "asset1/highest.mp4".to_string(),
+                expected_size: Some(2048),
+            }]
+        );
+        assert!(signed_only.is_empty());
+
+        let (targets, signed_only) = collect_targets(&[asset("signed", "ready")]);
+        assert!(targets.is_empty());
+        assert_eq!(signed_only, ["asset1"]);
+
+        let (targets, signed_only) = collect_targets(&[asset("public", "preparing")]);
+        assert!(targets.is_empty() && signed_only.is_empty());
+    }
+
+    #[test]
+    fn test_size_matches() {
+        assert!(size_matches(10, Some(10), None));
+        assert!(size_matches(10, None, Some(&record(10))));
+        assert!(!size_matches(10, Some(10), Some(&record(9))));
+        assert!(!size_matches(10, Some(11), None));
+        assert!(!size_matches(10, None, None));
+    }
+
+    #[tokio::test]
+    async fn test_is_up_to_date_checks_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("highest.mp4");
+        assert_eq!(is_up_to_date(&path, Some(3), None).await.unwrap(), None);
+
+        std::fs::write(&path, b"abc").unwrap();
+        assert_eq!(is_up_to_date(&path, Some(3), None).await.unwrap(), Some(3));
+
+        let mut recorded = record(3);
+        assert_eq!(
+            is_up_to_date(&path, Some(3), Some(&recorded))
+                .await
+                .unwrap(),
+            None
+        );
+        recorded.sha256 =
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_string();
+        assert_eq!(
+            is_up_to_date(&path, None, Some(&recorded)).await.unwrap(),
+            Some(3)
+        );
+        assert_eq!(part_path(&path), dir.path().join("highest.mp4.part"));
+    }
+}
@@ -0,0 +1,355 @@
+use crate::api::auth::AuthManager;
+use crate::api::client::ApiClient;
+use crate::api::rate_limit::BandwidthLimiter;
+use crate::api::retry::RetryPolicy;
+/// 複数ファイルアップロードコマンド
+///
+/// 複数のファイル（またはディレクトリ内の動画ファイル）を並行してアップロードします。
+/// - 同時実行数はセマフォで制限し、APIクライアント（コネクションプール・レートリミッター）は全タスクで共有
+/// - ネットワーク断などの一時的な失敗はファイル単位で再試行し、他のファイルには影響させない
+/// - 帯域上限の指定時は、全タスク合計の送信帯域を`BandwidthLimiter`で制限
+/// - 各ファイルの進捗はファイル番号を付けて1本のチャネルに多重化
+use crate::commands::connect_with;
+use crate::commands::result::{
+    BatchDetails, BatchResult, BatchUploadFailure, BatchUploadResult, CommandResult, UploadResult,
+};
+use crate::commands::upload::{self, UploadOptions};
+use crate::config::{APP_CONFIG, UserConfig};
+use crate::domain::progress::{BatchUploadProgress, ProgressSender, UploadJob};
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{Semaphore, mpsc};
+use tokio::task::JoinSet;
+
+/// 複数ファイルアップロードのオプション
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    /// 同時実行数（1〜`max_batch_concurrency`に丸める）
+    pub concurrency: usize,
+    /// 全体の送信帯域の上限（バイト/秒、Noneで無制限）
+    pub bytes_per_second: Option<u64>,
+    /// 各ファイルのアップロードオプション
+    pub upload: UploadOptions,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: APP_CONFIG.upload.batch_concurrency,
+            bytes_per_second: None,
+            upload: UploadOptions::default(),
+        }
+    }
+}
+
+/// 複数ファイルアップロードコマンドを実行する
+///
+/// # 引数
+/// * `file_paths` - アップロード対象のファイルパス（`expand_paths`で展開済み）
+/// * `options` - 同時実行数・帯域上限
+/// * `progress_tx` - 多重化した進捗通知用チャネルの送信側（オプション）
+pub async fn execute(
+    file_paths: &[String],
+    options: BatchOptions,
+    progress_tx: Option<mpsc::UnboundedSender<BatchUploadProgress>>,
+) -> Result<CommandResult> {
+    let started_at = Instant::now();
+
+    // ユーザー設定を読み込み
+    let user_config = UserConfig::load()
+        .context("Failed to load user configuration. Please check your config.toml file.")?;
+
+    // 認証マネージャーとAPIクライアントを初期化（全タスクで共有）
+    let (client, auth_manager) = connect_with(&user_config)?;
+    let result = upload_files(
+        &user_config,
+        client,
+        &auth_manager,
+        file_paths,
+        options,
+        progress_tx,
+    )
+    .await?;
+
+    Ok(CommandResult::Batch(BatchResult::new(
+        BatchDetails::Upload(result),
+        started_at.elapsed(),
+    )))
+}
+
+/// アップロード前フックを実行してから複数ファイルを並行アップロードし、ローカルインデックスに記録する
+///
+/// `sync` からも利用します。
+pub(crate) async fn upload_files(
+    user_config: &UserConfig,
+    mut client: ApiClient,
+    auth_manager: &AuthManager,
+    file_paths: &[String],
+    options: BatchOptions,
+    progress_tx: Option<mpsc::UnboundedSender<BatchUploadProgress>>,
+) -> Result<BatchUploadResult> {
+    // 帯域の上限は全タスクで共有する
+    if let Some(bytes_per_second) = options.bytes_per_second {
+        client = client.with_bandwidth_limiter(BandwidthLimiter::new(bytes_per_second));
+    }
+
+    // アップロード前フック（ファイルごとに順に実行し、失敗したファイルはアップロードしない）
+    let mut prepared_paths = Vec::with_capacity(file_paths.len());
+    let mut hook_failures = Vec::new();
+    for file_path in file_paths {
+        match upload::run_before_upload_hook(&user_config.hooks, file_path).await {
+            Ok(path) => prepared_paths.push(path),
+            Err(e) => hook_failures.push(BatchUploadFailure {
+                file_path: file_path.clone(),
+                error: format!("{:#}", e),
+                attempts: 0,
+            }),
+        }
+    }
+
+    let upload_options = options.upload.with_user_defaults(user_config);
+    let mut result = run(
+        &client,
+        auth_manager,
+        &prepared_paths,
+        options.concurrency,
+        upload_options,
+        progress_tx,
+    )
+    .await;
+    result.failed.extend(hook_failures);
+
+    for uploaded in &result.uploaded {
+        upload::record_upload(uploaded);
+    }
+
+    Ok(result)
+}
+
+/// 認証済みクライアントで複数ファイルを並行アップロードする
+///
+/// 個々のファイルの失敗は結果の`failed`に記録し、全体としては失敗させません。
+///
+/// # 引数
+/// * `client` - APIクライアント（クローンして各タスクで共有）
+/// * `auth_manager` - 認証マネージャー
+/// * `file_paths` - アップロード対象のファイルパス
+/// * `concurrency` - 同時実行数
+/// * `options` - 各ファイルのアップロードオプション
+/// * `progress_tx` - 多重化した進捗通知用チャネルの送信側（オプション）
+pub async fn run(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    file_paths: &[String],
+    concurrency: usize,
+    options: UploadOptions,
+    progress_tx: Option<mpsc::UnboundedSender<BatchUploadProgress>>,
+) -> BatchUploadResult {
+    let concurrency = concurrency.clamp(1, APP_CONFIG.upload.max_batch_concurrency);
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let total_files = file_paths.len();
+
+    let mut tasks = JoinSet::new();
+    for (index, file_path) in file_paths.iter().enumerate() {
+        let client = client.clone();
+        let auth_manager = auth_manager.clone();
+        let semaphore = Arc::clone(&semaphore);
+        let progress_tx = progress_tx.clone();
+        let file_path = file_path.clone();
+        let options = options.clone();
+
+        tasks.spawn(async move {
+            // セマフォはクローズしないため取得に失敗することはない
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .expect("batch upload semaphore is never closed");
+
+            let file_tx = progress_tx.map(|batch_tx| {
+                file_progress_sender(batch_tx, total_files, file_path.clone())
+                    .for_job(UploadJob::new(index + 1, &file_path))
+            });
+            let outcome =
+                upload_with_retry(&client, &auth_manager, &file_path, &options, file_tx).await;
+            (index, outcome)
+        });
+    }
+
+    // 完了順に受け取り、結果は指定順に並べ直す
+    let mut outcomes: Vec<Option<Result<UploadResult, BatchUploadFailure>>> =
+        vec![None; total_files];
+    while let Some(joined) = tasks.join_next().await {
+        // パニックしたタスクはファイルを特定できないため、未完了（None）のまま失敗として記録する
+        if let Ok((index, outcome)) = joined {
+            outcomes[index] = Some(outcome);
+        }
+    }
+
+    let mut uploaded = Vec::new();
+    let mut failed = Vec::new();
+    for (file_path, outcome) in file_paths.iter().zip(outcomes) {
+        match outcome {
+            Some(Ok(result)) => uploaded.push(result),
+            Some(Err(failure)) => failed.push(failure),
+            None => failed.push(BatchUploadFailure {
+                file_path: file_path.clone(),
+                error: "Upload task terminated unexpectedly".to_string(),
+                attempts: 0,
+            }),
+        }
+    }
+
+    BatchUploadResult {
+        uploaded,
+        failed,
+        concurrency,
+    }
+}
+
+/// 1ファイル分の進捗に、ファイル情報を付けて多重化チャネルへ送る送信側を作る
+///
+/// ファイル番号とラベルは [`ProgressSender::for_job`] で付けたジョブから取ります。
+/// 転送用のタスクやチャネルを挟まず送信時にそのまま多重化するため、
+/// ファイル内の順序を保ったまま取りこぼしなく届きます。
+fn file_progress_sender(
+    batch_tx: mpsc::UnboundedSender<BatchUploadProgress>,
+    total_files: usize,
+    source_path: String,
+) -> ProgressSender {
+    ProgressSender::from_fn(move |progress| {
+        let _ = batch_tx.send(BatchUploadProgress::new(
+            progress,
+            total_files,
+            source_path.clone(),
+        ));
+    })
+}
+
+/// 一時的な失敗を指数バックオフで再試行しながら1ファイルをアップロードする
+async fn upload_with_retry(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    file_path: &str,
+    options: &UploadOptions,
+    progress_tx: Option<ProgressSender>,
+) -> Result<UploadResult, BatchUploadFailure> {
+    let max_attempts = APP_CONFIG.upload.batch_max_attempts.max(1);
+    let retry_policy = RetryPolicy::default();
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+        match upload::run(
+            client,
+            auth_manager,
+            file_path,
+            options,
+            progress_tx.clone(),
+        )
+        .await
+        {
+            Ok(result) => return Ok(result),
+            Err(e) if attempts < max_attempts && upload::is_transient_failure(&e) => {
+                tokio::time::sleep(retry_policy.backoff(attempts - 1)).await;
+            }
+            Err(e) => {
+                return Err(BatchUploadFailure {
+                    file_path: file_path.to_string(),
+                    error: format!("{:#}", e),
+                    attempts,
+                });
+            }
+        }
+    }
+}
+
+/// アップロード対象のパスを展開する
+///
+/// ディレクトリは直下の対応フォーマットのファイル（名前順）に展開します。
+/// サブディレクトリは辿りません。ファイルはそのまま残し、検証はアップロード時に行います。
+///
+/// # 引数
+/// * `paths` - 指定されたファイル・ディレクトリのパス
+/// * `supported_formats` - ディレクトリから展開する形式（`UploadOptions::supported_formats`）
+///
+/// # エラー
+/// パスが存在しない場合、またはディレクトリに対応フォーマットのファイルがない場合
+pub fn expand_paths(paths: &[String], supported_formats: &[&str]) -> Result<Vec<String>> {
+    let mut expanded = Vec::new();
+
+    for path in paths {
+        let dir = Path::new(path);
+        if !dir.is_dir() {
+            expanded.push(path.clone());
+            continue;
+        }
+
+        let mut files: Vec<String> = std::fs::read_dir(dir)
+            .with_context(|| format!("Failed to read directory: {}", path))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.is_file() && is_supported_video(p, supported_formats))
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect();
+
+        if files.is_empty() {
+            bail!(
+                "No supported video files found in directory: {} (supported: {})",
+                path,
+                supported_formats.join(", ")
+            );
+        }
+
+        files.sort();
+        expanded.extend(files);
+    }
+
+    Ok(expanded)
+}
+
+/// 拡張子が対応フォーマットか判定（大文字小文字は区別しない）
+fn is_supported_video(path: &Path, supported_formats: &[&str]) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|ext| supported_formats.contains(&ext.to_ascii_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMATS: &[&str] = APP_CONFIG.upload.supported_formats;
+
+    #[test]
+    fn test_expand_paths_lists_supported_files_in_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["b.mp4", "a.MOV", "notes.txt", "c"] {
+            std::fs::write(dir.path().join(name), b"").unwrap();
+        }
+        std::fs::create_dir(dir.path().join("nested.mp4")).unwrap();
+
+        let expanded = expand_paths(&[dir.path().to_string_lossy().into_owned()], FORMATS).unwrap();
+        let names: Vec<_> = expanded
+            .iter()
+            .map(|p| Path::new(p).file_name().unwrap().to_str().unwrap())
+            .collect();
+
+        assert_eq!(names, ["a.MOV", "b.mp4"]);
+    }
+
+    #[test]
+    fn test_expand_paths_keeps_files_as_given() {
+        let paths = vec!["clip2.mp4".to_string(), "clip1.mp4".to_string()];
+        assert_eq!(expand_paths(&paths, FORMATS).unwrap(), paths);
+    }
+
+    #[test]
+    fn test_expand_paths_rejects_directory_without_videos() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("readme.txt"), b"").unwrap();
+
+        assert!(expand_paths(&[dir.path().to_string_lossy().into_owned()], FORMATS).is_err());
+    }
+}
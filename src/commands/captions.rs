@@ -0,0 +1,177 @@
+/// 字幕（captions）コマンド
+///
+/// アセットに字幕トラックを追加します。
+/// - `captions add`: WebVTT・SRTのファイルのURLから字幕トラックを追加
+/// - `captions generate`: オーディオトラックの音声から字幕トラックを自動生成
+///
+/// どちらも `--language` の言語コード（BCP 47）を送る前に検証し、`--name` でプレイヤーに表示するトラック名を指定できます。
+use crate::api::cache::ResponseCache;
+use crate::api::types::{
+    AssetStatus, CreateTrackRequest, GenerateSubtitlesRequest, GeneratedSubtitles, Track,
+};
+use crate::commands::connect;
+use crate::commands::result::{CaptionAction, CaptionsResult, CommandResult};
+use crate::domain::language::normalize_language_code;
+use anyhow::{Context, Result, bail};
+
+/// 字幕トラックの設定
+#[derive(Debug, Clone, Default)]
+pub struct CaptionOptions {
+    /// 言語コード（`--language`、BCP 47）
+    pub language: String,
+    /// プレイヤーに表示するトラック名（`--name`）。Noneの場合はMuxが言語から決める
+    pub name: Option<String>,
+    /// 聴覚障害者向けの字幕（CC）として追加するか（`--closed-captions`、`add` のみ）
+    pub closed_captions: bool,
+}
+
+/// 字幕ファイルのURLから字幕トラックを追加する
+///
+/// # 引数
+/// * `asset_id` - 対象のアセットID
+/// * `url` - 字幕ファイル（WebVTT・SRT）のURL（Muxから取得できる必要がある）
+/// * `options` - 字幕トラックの設定
+///
+/// # エラー
+/// 言語コードの形式が不正な場合、URLがhttp(s)でない場合、トラックの追加に失敗した場合
+pub async fn add(asset_id: &str, url: &str, options: &CaptionOptions) -> Result<CommandResult> {
+    let language = normalize_language_code(&options.language)?;
+    let url = url.trim();
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        bail!(
+            "Caption file must be an http(s) URL that Mux can fetch: {}",
+            url
+        );
+    }
+    let (client, auth_manager) = connect()?;
+
+    let request = CreateTrackRequest {
+        url: url.to_string(),
+        track_type: "text".to_string(),
+        text_type: Some("subtitles".to_string()),
+        language_code: Some(language),
+        name: options.name.clone(),
+        closed_captions: options.closed_captions.then_some(true),
+    };
+    let track = client
+        .create_track(asset_id, &request, &auth_manager.get_auth_header())
+        .await
+        .context(format!("Failed to add caption track to asset {}", asset_id))?;
+
+    // showのキャッシュに追加前のトラックが残らないようにする
+    ResponseCache::invalidate_default();
+
+    Ok(CommandResult::Captions(CaptionsResult {
+        asset_id: asset_id.to_string(),
+        action: CaptionAction::Add,
+        source_track_id: None,
+        tracks: vec![track],
+    }))
+}
+
+/// オーディオトラックの音声から字幕トラックを自動生成する
+///
+/// 生成元のオーディオトラックは、言語コードが `--language` と一致するものを優先し、
+/// なければ最初のオーディオトラックを使います。字幕の生成は非同期に行われ、
+/// 生成中のトラックは `show` で `preparing` と表示されます。
+///
+/// # 引数
+/// * `asset_id` - 対象のアセットID
+/// * `options` - 字幕トラックの設定（音声の言語を `language` に指定する）
+///
+/// # エラー
+/// 言語コードの形式が不正な場合、アセットが `ready` でない、オーディオトラックがない、生成の開始に失敗した場合
+pub async fn generate(asset_id: &str, options: &CaptionOptions) -> Result<CommandResult> {
+    let language = normalize_language_code(&options.language)?;
+    let (client, auth_manager) = connect()?;
+    let auth_header = auth_manager.get_auth_header();
+
+    let asset = client
+        .get_asset(asset_id, &auth_header)
+        .await
+        .context("Failed to fetch asset details")?;
+
+    // オーディオトラックはアセットの処理が終わるまで確定しない
+    if asset.status != AssetStatus::Ready {
+        bail!(
+            "Asset {} is not ready (status: {}); captions can be generated once it is ready. Run 'vidyeet wait {}' first.",
+            asset_id,
+            asset.status,
+            asset_id
+        );
+    }
+    let tracks = asset.tracks.as_deref().unwrap_or_default();
+    let Some(audio_track_id) = audio_track(tracks, &language).and_then(|t| t.id.clone()) else {
+        bail!(
+            "Asset {} has no audio track to generate captions from",
+            asset_id
+        );
+    };
+
+    let request = GenerateSubtitlesRequest {
+        generated_subtitles: vec![GeneratedSubtitles {
+            language_code: language,
+            name: options.name.clone(),
+        }],
+    };
+    let generated = client
+        .generate_subtitles(asset_id, &audio_track_id, &request, &auth_header)
+        .await
+        .context(format!(
+            "Failed to generate captions for asset {}",
+            asset_id
+        ))?;
+
+    // showのキャッシュに生成前のトラックが残らないようにする
+    ResponseCache::invalidate_default();
+
+    Ok(CommandResult::Captions(CaptionsResult {
+        asset_id: asset.id.clone(),
+        action: CaptionAction::Generate,
+        source_track_id: Some(audio_track_id),
+        tracks: generated,
+    }))
+}
+
+/// 字幕の生成元にするオーディオトラック
+///
+/// 言語コードが一致するオーディオトラックを優先し、なければ最初のオーディオトラックを返す。
+fn audio_track<'a>(tracks: &'a [Track], language: &str) -> Option<&'a Track> {
+    let mut audio = tracks.iter().filter(|t| t.track_type == "audio");
+    audio
+        .clone()
+        .find(|t| t.language_code.as_deref() == Some(language))
+        .or_else(|| audio.next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(id: &str, track_type: &str, language_code: Option<&str>) -> Track {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "type": track_type,
+            "language_code": language_code,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_audio_track_prefers_matching_language() {
+        let tracks = vec![
+            track("video_1", "video", None),
+            track("audio_ja", "audio", Some("ja")),
+            track("audio_en", "audio", Some("en")),
+        ];
+        assert_eq!(
+            audio_track(&tracks, "en").and_then(|t| t.id.as_deref()),
+            Some("audio_en")
+        );
+        assert_eq!(
+            audio_track(&tracks, "fr").and_then(|t| t.id.as_deref()),
+            Some("audio_ja")
+        );
+        assert!(audio_track(&tracks[..1], "en").is_none());
+    }
+}
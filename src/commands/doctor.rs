@@ -0,0 +1,337 @@
+/// 診断コマンド
+///
+/// 設定ファイル・api.mux.com / stream.mux.com への到達性（DNS・HTTPS）・認証情報・
+/// 時刻のずれ・データディレクトリの空き容量を順に確認し、チェックごとの結果と対処方法を返します。
+/// 1つのチェックが失敗しても残りのチェックは続行します（前提が満たせないものはスキップ）。
+use crate::api::auth::AuthManager;
+use crate::commands::result::{CheckStatus, CommandResult, DoctorCheck, DoctorResult};
+use crate::config::user::UserConfig;
+use crate::config::{APP_CONFIG, BYTES_PER_MB};
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use reqwest::header::DATE;
+use std::path::Path;
+use std::time::Duration;
+
+/// 診断コマンドを実行
+///
+/// # Returns
+/// 成功時はOk(CommandResult)。チェックの失敗はエラーではなく結果に含める
+pub async fn execute() -> anyhow::Result<CommandResult> {
+    let mut checks = Vec::new();
+
+    let config = match UserConfig::config_path() {
+        Ok(path) => {
+            let (check, config) = check_config(&path);
+            checks.push(check);
+            config
+        }
+        Err(e) => {
+            checks.push(fail("config", e.to_string(), e.hint()));
+            None
+        }
+    };
+    let timeout = Duration::from_secs(APP_CONFIG.doctor.timeout_secs);
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to create HTTP client: {}", e))?;
+
+    let mut server_date = None;
+    let mut api_reachable = false;
+    for (prefix, endpoint) in [
+        ("api", APP_CONFIG.api.endpoint),
+        ("stream", APP_CONFIG.doctor.stream_endpoint),
+    ] {
+        let host = Url::parse(endpoint)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| endpoint.to_string());
+
+        let dns = check_dns(&format!("{}_dns", prefix), &host, timeout).await;
+        let resolved = dns.status == CheckStatus::Pass;
+        checks.push(dns);
+
+        let tls_name = format!("{}_tls", prefix);
+        if !resolved {
+            checks.push(skip(&tls_name, format!("{} could not be resolved", host)));
+            continue;
+        }
+        let (tls, date) = check_tls(&client, &tls_name, endpoint).await;
+        if prefix == "api" {
+            api_reachable = tls.status == CheckStatus::Pass;
+            server_date = date;
+        }
+        checks.push(tls);
+    }
+
+    // APIに接続できない場合は、リトライで待たされるだけのため認証情報の確認を省く
+    checks.push(if api_reachable {
+        check_credentials(config.as_ref()).await
+    } else {
+        skip("credentials", "api.mux.com is not reachable")
+    });
+
+    checks.push(match server_date {
+        Some(server) => check_clock((Utc::now() - server).num_seconds()),
+        None => skip(
+            "clock",
+            "The server time could not be obtained from api.mux.com",
+        ),
+    });
+    checks.push(check_disk());
+
+    Ok(CommandResult::Doctor(DoctorResult { checks }))
+}
+
+/// 設定ファイルの存在・読み込み・パーミッション
+fn check_config(path: &Path) -> (DoctorCheck, Option<UserConfig>) {
+    if !path.exists() {
+        return (
+            fail(
+                "config",
+                format!("Config file not found: {}", path.display()),
+                Some("Run 'vidyeet login' to create the config file."),
+            ),
+            None,
+        );
+    }
+
+    let config = match UserConfig::load() {
+        Ok(config) => config,
+        Err(e) => return (fail("config", e.to_string(), e.hint()), None),
+    };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(metadata) = std::fs::metadata(path) {
+            let mode = metadata.permissions().mode();
+            if !permissions_are_private(mode) {
+                return (
+                    warn(
+                        "config",
+                        format!(
+                            "{} is readable by other users (mode {:o})",
+                            path.display(),
+                            mode & 0o777
+                        ),
+                        Some(&format!(
+                            "The file contains your credentials. Run 'chmod 600 {}'.",
+                            path.display()
+                        )),
+                    ),
+                    Some(config),
+                );
+            }
+        }
+    }
+
+    (pass("config", path.display().to_string()), Some(config))
+}
+
+/// グループ・その他のユーザーに権限がないか
+#[cfg(any(unix, test))]
+fn permissions_are_private(mode: u32) -> bool {
+    mode & 0o077 == 0
+}
+
+/// 認証情報が有効か（GET /video/v1/assets）
+async fn check_credentials(config: Option<&UserConfig>) -> DoctorCheck {
+    let Some(config) = config else {
+        return skip("credentials", "The config file could not be loaded");
+    };
+    let Ok(auth) = config.get_auth() else {
+        return fail(
+            "credentials",
+            "Not logged in",
+            Some("Run 'vidyeet login' to authenticate."),
+        );
+    };
+
+    let auth_manager = AuthManager::new(auth.token_id.clone(), auth.token_secret.clone());
+    match auth_manager.test_credentials().await {
+        Ok(()) => pass(
+            "credentials",
+            format!("Token {} is valid", auth_manager.get_masked_token_id()),
+        ),
+        Err(e) => fail("credentials", e.to_string(), e.hint()),
+    }
+}
+
+/// ホスト名を解決できるか
+async fn check_dns(name: &str, host: &str, timeout: Duration) -> DoctorCheck {
+    let hint = Some("Check your network connection and DNS settings.");
+    match tokio::time::timeout(timeout, tokio::net::lookup_host((host, 443))).await {
+        Ok(Ok(mut addrs)) => match addrs.next() {
+            Some(addr) => pass(name, format!("{} resolved to {}", host, addr.ip())),
+            None => fail(name, format!("{} has no addresses", host), hint),
+        },
+        Ok(Err(e)) => fail(name, format!("Failed to resolve {}: {}", host, e), hint),
+        Err(_) => fail(
+            name,
+            format!("Resolving {} timed out after {}s", host, timeout.as_secs()),
+            hint,
+        ),
+    }
+}
+
+/// HTTPS（TLS）で接続できるか
+///
+/// ステータスコードに関わらず、レスポンスが返れば成功とします。
+/// レスポンスの`Date`ヘッダーを時刻のずれの確認に使います。
+async fn check_tls(
+    client: &reqwest::Client,
+    name: &str,
+    endpoint: &str,
+) -> (DoctorCheck, Option<DateTime<Utc>>) {
+    match client.get(endpoint).send().await {
+        Ok(response) => {
+            let date = response
+                .headers()
+                .get(DATE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+                .map(|date| date.with_timezone(&Utc));
+            let check = pass(
+                name,
+                format!(
+                    "Connected to {} over HTTPS ({:?})",
+                    endpoint,
+                    response.version()
+                ),
+            );
+            (check, date)
+        }
+        Err(e) => {
+            let hint = if e.is_timeout() {
+                "Check your firewall or proxy settings (HTTPS_PROXY)."
+            } else {
+                "Check your firewall, proxy settings (HTTPS_PROXY) and that the system certificate store is up to date."
+            };
+            (
+                fail(
+                    name,
+                    format!("Failed to connect to {}: {}", endpoint, e),
+                    Some(hint),
+                ),
+                None,
+            )
+        }
+    }
+}
+
+/// 時刻のずれ（ローカル - サーバー、秒）
+fn check_clock(skew_secs: i64) -> DoctorCheck {
+    let message = format!("Local clock differs from api.mux.com by {}s", skew_secs);
+    let hint = Some("Synchronize your system clock (NTP).");
+    let skew = skew_secs.abs();
+    if skew >= APP_CONFIG.doctor.clock_skew_fail_secs {
+        fail("clock", message, hint)
+    } else if skew >= APP_CONFIG.doctor.clock_skew_warn_secs {
+        warn("clock", message, hint)
+    } else {
+        pass("clock", message)
+    }
+}
+
+/// データディレクトリの空き容量
+fn check_disk() -> DoctorCheck {
+    let dir = match UserConfig::data_dir() {
+        Ok(dir) => dir,
+        Err(e) => return fail("disk", e.to_string(), e.hint()),
+    };
+    match fs2::available_space(&dir) {
+        Ok(free) => disk_check(&dir, free),
+        Err(e) => warn(
+            "disk",
+            format!("Failed to get free space of {}: {}", dir.display(), e),
+            None,
+        ),
+    }
+}
+
+/// 空き容量からチェック結果を作る
+fn disk_check(dir: &Path, free_bytes: u64) -> DoctorCheck {
+    let message = format!(
+        "{:.0} MB free in {}",
+        free_bytes as f64 / BYTES_PER_MB,
+        dir.display()
+    );
+    if free_bytes < APP_CONFIG.doctor.min_free_disk_bytes {
+        warn(
+            "disk",
+            message,
+            Some("Free up disk space; the response cache and upload queue are stored here."),
+        )
+    } else {
+        pass("disk", message)
+    }
+}
+
+fn check(name: &str, status: CheckStatus, message: String, hint: Option<&str>) -> DoctorCheck {
+    DoctorCheck {
+        name: name.to_string(),
+        status,
+        message,
+        hint: hint.map(str::to_string),
+    }
+}
+
+fn pass(name: &str, message: impl Into<String>) -> DoctorCheck {
+    check(name, CheckStatus::Pass, message.into(), None)
+}
+
+fn warn(name: &str, message: impl Into<String>, hint: Option<&str>) -> DoctorCheck {
+    check(name, CheckStatus::Warn, message.into(), hint)
+}
+
+fn fail(name: &str, message: impl Into<String>, hint: Option<&str>) -> DoctorCheck {
+    check(name, CheckStatus::Fail, message.into(), hint)
+}
+
+fn skip(name: &str, message: impl Into<String>) -> DoctorCheck {
+    check(name, CheckStatus::Skip, message.into(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_clock_thresholds() {
+        assert_eq!(check_clock(2).status, CheckStatus::Pass);
+        assert_eq!(check_clock(-45).status, CheckStatus::Warn);
+        assert_eq!(check_clock(600).status, CheckStatus::Fail);
+        assert!(check_clock(600).hint.is_some());
+    }
+
+    #[test]
+    fn test_disk_check() {
+        let dir = Path::new("/tmp/vidyeet");
+        let check = disk_check(dir, 10 * 1_048_576);
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert!(check.message.starts_with("10 MB free"));
+        assert_eq!(disk_check(dir, u64::MAX / 2).status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn test_permissions_are_private() {
+        assert!(permissions_are_private(0o100600));
+        assert!(!permissions_are_private(0o100644));
+        assert!(!permissions_are_private(0o100660));
+    }
+
+    #[test]
+    fn test_doctor_exit_code() {
+        let result = DoctorResult {
+            checks: vec![pass("config", "ok"), warn("clock", "skew", None)],
+        };
+        assert!(result.passed());
+        assert_eq!(CommandResult::Doctor(result.clone()).exit_code(), 0);
+
+        let mut failed = result;
+        failed.checks.push(fail("disk", "full", None));
+        assert_eq!(CommandResult::Doctor(failed).exit_code(), 1);
+    }
+}
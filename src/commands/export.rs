@@ -0,0 +1,76 @@
+use crate::api::endpoints;
+use crate::api::types::AssetData;
+/// エクスポートコマンド
+///
+/// 全アセットの完全なデータ（`AssetData`）をページ単位で取得し、JSONまたはCSVに書き出します。
+/// 取得済みのページはチェックポイントに保存するため、大量のアセットの取得が途中で
+/// 中断しても、同じ出力先で再実行すると続きから取得します。
+use crate::commands::connect;
+use crate::commands::result::{CommandResult, ExportResult};
+use crate::storage::export::{AssetExport, ExportCheckpoint, ExportFormat};
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+
+/// エクスポートコマンドを実行する
+///
+/// # 引数
+/// * `output` - 出力先（拡張子 `.json` / `.csv` で形式を決める）
+/// * `restart` - チェックポイントを破棄して最初から取得するか（`--restart`）
+///
+/// # 戻り値
+/// 成功・失敗を示すResult<CommandResult>
+pub async fn execute(output: &Path, restart: bool) -> Result<CommandResult> {
+    let Some(format) = ExportFormat::from_path(output) else {
+        bail!(
+            "Unsupported export format: {}. Use a .json or .csv file name.",
+            output.display()
+        );
+    };
+
+    // 認証マネージャーとAPIクライアントを初期化（完全なデータを得るためキャッシュは使わない）
+    let (client, auth_manager) = connect()?;
+
+    let checkpoint_path = ExportCheckpoint::path_for(output);
+    if restart {
+        ExportCheckpoint::remove(&checkpoint_path);
+    }
+    let mut checkpoint = ExportCheckpoint::load(&checkpoint_path)
+        .context("Failed to load export checkpoint")?
+        .unwrap_or_else(ExportCheckpoint::start);
+    let resumed_assets = checkpoint.assets.len();
+
+    let auth_header = auth_manager.get_auth_header();
+    loop {
+        let (assets, next) = client
+            .get_page::<AssetData>(endpoints::ASSETS, &checkpoint.next, Some(&auth_header))
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to fetch assets ({} fetched so far). Re-run the same command to resume.",
+                    checkpoint.assets.len()
+                )
+            })?;
+        checkpoint.assets.extend(assets);
+
+        let Some(next) = next else {
+            break;
+        };
+        checkpoint.next = next;
+        checkpoint
+            .save(&checkpoint_path)
+            .context("Failed to save export checkpoint")?;
+    }
+
+    let export = AssetExport::new(checkpoint.assets);
+    export
+        .write(output, format)
+        .with_context(|| format!("Failed to write export file: {}", output.display()))?;
+    ExportCheckpoint::remove(&checkpoint_path);
+
+    Ok(CommandResult::Export(ExportResult {
+        output_path: output.display().to_string(),
+        format,
+        asset_count: export.total_count,
+        resumed_assets,
+    }))
+}
@@ -0,0 +1,220 @@
+/// 掃除（gc）コマンド
+///
+/// エラー状態（`errored`）のアセットと、ファイルを受け取らないまま残っている
+/// Direct Upload（`waiting`）を探し、アセットは削除、Direct Uploadは取り消します。
+/// `plan` で対象を集め、確認プロンプトの後に `execute` で実行します。
+use crate::api::types::{AssetData, AssetStatus, DirectUploadData, UploadStatus};
+use crate::commands::connect;
+use crate::commands::delete;
+use crate::commands::result::{CommandResult, GcAsset, GcFailure, GcResult};
+use crate::storage::asset_index::AssetIndex;
+use anyhow::{Context, Result};
+use futures_util::TryStreamExt;
+
+/// 掃除の対象の選択
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GcOptions {
+    /// エラー状態のアセットを対象にする（`--errored`）
+    pub errored: bool,
+    /// ファイルを受け取っていないDirect Uploadを対象にする（`--timed-out-uploads`）
+    pub timed_out_uploads: bool,
+    /// 指定した秒数より前に作成されたアセットのみ対象にする（`--older-than`）
+    pub older_than_secs: Option<u64>,
+}
+
+impl GcOptions {
+    /// 対象の指定がない場合は両方を対象にする
+    fn normalized(mut self) -> Self {
+        if !self.errored && !self.timed_out_uploads {
+            self.errored = true;
+            self.timed_out_uploads = true;
+        }
+        self
+    }
+}
+
+/// 掃除の計画（`plan` で作成し、`execute` で実行する）
+#[derive(Debug, Clone)]
+pub struct GcPlan {
+    /// 削除するエラー状態のアセット
+    pub errored_assets: Vec<GcAsset>,
+    /// 取り消すDirect UploadのID
+    pub stale_uploads: Vec<String>,
+}
+
+impl GcPlan {
+    /// 対象がないか
+    pub fn is_empty(&self) -> bool {
+        self.errored_assets.is_empty() && self.stale_uploads.is_empty()
+    }
+
+    /// 実行せずに計画のみを結果にする（`--dry-run`）
+    pub fn into_dry_run(self) -> CommandResult {
+        CommandResult::Gc(self.into_result(true))
+    }
+
+    fn into_result(self, dry_run: bool) -> GcResult {
+        GcResult {
+            dry_run,
+            errored_assets: self.errored_assets,
+            stale_uploads: self.stale_uploads,
+            deleted_assets: Vec::new(),
+            cancelled_uploads: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
+
+/// 掃除の対象を集める（削除・取り消しは行わない）
+///
+/// # 引数
+/// * `options` - 対象の選択
+pub async fn plan(options: GcOptions) -> Result<GcPlan> {
+    let options = options.normalized();
+    let (client, auth_manager) = connect()?;
+    let auth_header = auth_manager.get_auth_header();
+
+    let errored_assets = if options.errored {
+        let assets: Vec<AssetData> = client
+            .list_assets(&auth_header)
+            .try_collect()
+            .await
+            .context("Failed to fetch assets list")?;
+        select_errored(
+            &assets,
+            options.older_than_secs,
+            chrono::Utc::now().timestamp(),
+        )
+    } else {
+        Vec::new()
+    };
+
+    let stale_uploads = if options.timed_out_uploads {
+        let uploads: Vec<DirectUploadData> = client
+            .list_uploads(&auth_header)
+            .try_collect()
+            .await
+            .context("Failed to fetch direct uploads")?;
+        uploads
+            .into_iter()
+            .filter(|upload| upload.status == UploadStatus::Waiting)
+            .map(|upload| upload.id)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    Ok(GcPlan {
+        errored_assets,
+        stale_uploads,
+    })
+}
+
+/// 掃除の計画を実行する
+///
+/// 個々の削除・取り消しの失敗は結果の`failed`に記録し、残りは続行します。
+pub async fn execute(plan: GcPlan) -> Result<CommandResult> {
+    let (client, auth_manager) = connect()?;
+    let auth_header = auth_manager.get_auth_header();
+    let mut result = plan.into_result(false);
+
+    for asset in &result.errored_assets {
+        match delete::run(&client, &auth_manager, &asset.asset_id).await {
+            Ok(_) => {
+                AssetIndex::update_default(|index| index.remove(&asset.asset_id));
+                result.deleted_assets.push(asset.asset_id.clone());
+            }
+            Err(e) => result.failed.push(GcFailure {
+                target: asset.asset_id.clone(),
+                error: format!("{:#}", e),
+            }),
+        }
+    }
+
+    for upload_id in &result.stale_uploads {
+        let cancelled = client
+            .cancel_upload(upload_id, &auth_header)
+            .await
+            .with_context(|| format!("Failed to cancel direct upload {}", upload_id));
+        match cancelled {
+            Ok(_) => result.cancelled_uploads.push(upload_id.clone()),
+            Err(e) => result.failed.push(GcFailure {
+                target: upload_id.clone(),
+                error: format!("{:#}", e),
+            }),
+        }
+    }
+
+    Ok(CommandResult::Gc(result))
+}
+
+/// エラー状態のアセットを選ぶ（`older_than_secs` 指定時は、それより前に作成されたもののみ）
+///
+/// 作成日時を読めないアセットは、経過時間を判定できないため対象にしません。
+fn select_errored(assets: &[AssetData], older_than_secs: Option<u64>, now: i64) -> Vec<GcAsset> {
+    assets
+        .iter()
+        .filter(|asset| asset.status == AssetStatus::Errored)
+        .filter(|asset| match older_than_secs {
+            Some(secs) => asset
+                .created_at
+                .parse::<i64>()
+                .is_ok_and(|created_at| now - created_at >= secs as i64),
+            None => true,
+        })
+        .map(|asset| GcAsset {
+            asset_id: asset.id.clone(),
+            title: asset.title().map(str::to_string),
+            created_at: asset.created_at.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn asset(id: &str, status: &str, created_at: &str) -> AssetData {
+        serde_json::from_value(json!({
+            "id": id,
+            "status": status,
+            "created_at": created_at,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_select_errored() {
+        let day = 86_400;
+        let now = 100 * day;
+        let assets = [
+            asset("ready", "ready", "0"),
+            asset("old", "errored", &(now - 91 * day).to_string()),
+            asset("new", "errored", &(now - day).to_string()),
+        ];
+
+        let all: Vec<_> = select_errored(&assets, None, now)
+            .into_iter()
+            .map(|a| a.asset_id)
+            .collect();
+        assert_eq!(all, ["old", "new"]);
+
+        let old = select_errored(&assets, Some(90 * day as u64), now);
+        assert_eq!(old.len(), 1);
+        assert_eq!(old[0].asset_id, "old");
+    }
+
+    #[test]
+    fn test_options_default_to_both() {
+        let options = GcOptions::default().normalized();
+        assert!(options.errored && options.timed_out_uploads);
+
+        let options = GcOptions {
+            errored: true,
+            ..GcOptions::default()
+        }
+        .normalized();
+        assert!(!options.timed_out_uploads);
+    }
+}
@@ -0,0 +1,25 @@
+/// 操作履歴（history）コマンド
+///
+/// 状態を変更したコマンドの履歴（`history.jsonl`）を新しい順に返します。
+/// 記録と再実行（`history rerun <id>`）は引数を扱うCLI側で行います。
+use crate::commands::result::{CommandResult, HistoryResult};
+use crate::storage::history::History;
+use anyhow::{Context, Result};
+
+/// 新しいものから `limit` 件の履歴を返す
+///
+/// # 引数
+/// * `limit` - 表示する件数
+pub async fn execute(limit: usize) -> Result<CommandResult> {
+    let history = History::open_default().context("Failed to open command history")?;
+    let all = history
+        .entries()
+        .context("Failed to load command history")?;
+    let total_count = all.len();
+    let entries = all.into_iter().rev().take(limit).collect();
+
+    Ok(CommandResult::History(HistoryResult {
+        entries,
+        total_count,
+    }))
+}
@@ -0,0 +1,328 @@
+use crate::api::cache::ResponseCache;
+use crate::api::client::ApiClient;
+use crate::api::types::{AssetData, AssetStatus, CHECKSUM_PASSTHROUGH_PREFIX, RenditionStatus};
+/// インポートコマンド
+///
+/// `export` のJSONファイル、または `upload --manifest` のマニフェストを読み込み、
+/// 記録されたアセットをURL取り込み（`POST /video/v1/assets`）で作り直します。
+/// 公開の再生IDとready状態のMP4がある場合はそのURLから取り込むため、別の環境にも複製できます。
+/// それ以外は同じ環境内でのみ有効な `mux://assets/<id>` から取り込みます。
+use crate::commands::connect;
+use crate::commands::result::{CommandResult, ImportFailure, ImportResult, ImportedAsset};
+use crate::config::APP_CONFIG;
+use crate::storage::asset_index::{AssetIndex, IndexedAsset};
+use crate::storage::export::{AssetExport, EXPORT_VERSION};
+use crate::storage::manifest::{MANIFEST_VERSION, ManifestEntry, UploadManifest};
+use anyhow::{Context, Result, bail};
+use serde_json::{Value, json};
+use std::path::Path;
+
+/// 作り直すアセット
+#[derive(Debug, Clone, PartialEq)]
+struct ImportCandidate {
+    /// 元のアセットID
+    old_asset_id: String,
+    /// タイトル
+    title: Option<String>,
+    /// 作成できない理由（ready状態でないなど）
+    skip_reason: Option<String>,
+    /// 取り込み元のURL
+    input_url: String,
+    /// アセット作成リクエストの本文
+    request_body: Value,
+}
+
+/// インポートコマンドを実行する
+///
+/// # 引数
+/// * `path` - エクスポートファイル（JSON）またはアップロードマニフェストのパス
+///
+/// # 戻り値
+/// 成功・失敗を示すResult<CommandResult>。個々のアセットの失敗は結果に含める
+pub async fn execute(path: &Path) -> Result<CommandResult> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read import file: {}", path.display()))?;
+    let candidates = parse_candidates(&content)
+        .with_context(|| format!("Failed to parse import file: {}", path.display()))?;
+
+    // 認証マネージャーとAPIクライアントを初期化
+    let (client, auth_manager) = connect()?;
+    let auth_header = auth_manager.get_auth_header();
+
+    let mut imported = Vec::new();
+    let mut failed = Vec::new();
+    for candidate in candidates {
+        if let Some(reason) = candidate.skip_reason {
+            failed.push(ImportFailure {
+                old_asset_id: candidate.old_asset_id,
+                error: reason,
+            });
+            continue;
+        }
+
+        match create_asset(&client, &auth_header, &candidate.request_body).await {
+            Ok(asset) => {
+                AssetIndex::update_default(|index| index.upsert(IndexedAsset::from_asset(&asset)));
+                imported.push(ImportedAsset {
+                    old_asset_id: candidate.old_asset_id,
+                    new_asset_id: asset.id,
+                    input_url: candidate.input_url,
+                    title: candidate.title,
+                });
+            }
+            Err(e) => failed.push(ImportFailure {
+                old_asset_id: candidate.old_asset_id,
+                error: format!("{:#}", e),
+            }),
+        }
+    }
+
+    // 作成したアセットがlistのキャッシュに反映されるよう破棄
+    if !imported.is_empty() {
+        ResponseCache::invalidate_default();
+    }
+
+    Ok(CommandResult::Import(ImportResult {
+        source_path: path.display().to_string(),
+        imported,
+        failed,
+    }))
+}
+
+/// URL取り込みでアセットを作成する
+async fn create_asset(client: &ApiClient, auth_header: &str, body: &Value) -> Result<AssetData> {
+    client
+        .create_asset(body, auth_header)
+        .await
+        .context("Failed to create asset")
+}
+
+/// エクスポートファイルまたはマニフェストから作り直すアセットを取り出す
+///
+/// `assets` を含む場合はエクスポート、`uploads` を含む場合はマニフェストとして読み込みます。
+fn parse_candidates(content: &str) -> Result<Vec<ImportCandidate>> {
+    let value: Value = serde_json::from_str(content).context(
+        "The file is not valid JSON. Use a JSON export ('export --output <file.json>') or an upload manifest.",
+    )?;
+
+    if value.get("assets").is_some() {
+        let export: AssetExport = serde_json::from_value(value)?;
+        if export.version > EXPORT_VERSION {
+            bail!(
+                "Unsupported export version {} (this version of vidyeet supports up to {})",
+                export.version,
+                EXPORT_VERSION
+            );
+        }
+        Ok(export.assets.iter().map(from_asset).collect())
+    } else if value.get("uploads").is_some() {
+        let manifest: UploadManifest = serde_json::from_value(value)?;
+        if manifest.version > MANIFEST_VERSION {
+            bail!(
+                "Unsupported manifest version {} (this version of vidyeet supports up to {})",
+                manifest.version,
+                MANIFEST_VERSION
+            );
+        }
+        Ok(manifest.uploads.iter().map(from_manifest_entry).collect())
+    } else {
+        bail!("The file is neither an export (no 'assets') nor an upload manifest (no 'uploads')")
+    }
+}
+
+/// エクスポートしたアセットから作成リクエストを組み立てる
+///
+/// 再生ポリシー・画質・MP4レンディション・meta・passthrough（チェックサムとタグ）を引き継ぎます。
+fn from_asset(asset: &AssetData) -> ImportCandidate {
+    let mut policies: Vec<&str> = Vec::new();
+    for playback_id in &asset.playback_ids {
+        if !policies.contains(&playback_id.policy.as_str()) {
+            policies.push(&playback_id.policy);
+        }
+    }
+    if policies.is_empty() {
+        policies.push("public");
+    }
+
+    let mp4_ready = asset.static_renditions.as_ref().is_some_and(|wrapper| {
+        wrapper
+            .files
+            .iter()
+            .any(|r| r.status == RenditionStatus::Ready && r.ext == "mp4")
+    });
+    let input_url = match asset.playback_ids.iter().find(|p| p.policy == "public") {
+        Some(_) if mp4_ready => asset
+            .get_mp4_playback_url()
+            .unwrap_or_else(|| mux_url(&asset.id)),
+        _ => mux_url(&asset.id),
+    };
+
+    let mut body = json!({
+        "inputs": [{ "url": input_url }],
+        "playback_policies": policies,
+    });
+    if let Some(video_quality) = &asset.video_quality {
+        body["video_quality"] = json!(video_quality);
+    }
+    if let Some(wrapper) = &asset.static_renditions {
+        let mut resolutions: Vec<&str> = Vec::new();
+        for rendition in &wrapper.files {
+            if rendition.status != RenditionStatus::Deleted
+                && !resolutions.contains(&rendition.resolution.as_str())
+            {
+                resolutions.push(&rendition.resolution);
+            }
+        }
+        if !resolutions.is_empty() {
+            body["static_renditions"] = resolutions
+                .iter()
+                .map(|resolution| json!({ "resolution": resolution }))
+                .collect();
+        }
+    }
+    if let Some(meta) = &asset.meta {
+        body["meta"] = json!(meta);
+    }
+    if let Some(passthrough) = asset.passthrough.as_deref().filter(|p| !p.is_empty()) {
+        body["passthrough"] = json!(passthrough);
+    }
+
+    let skip_reason = (asset.status != AssetStatus::Ready)
+        .then(|| format!("Asset is not ready (status: {})", asset.status));
+
+    ImportCandidate {
+        old_asset_id: asset.id.clone(),
+        title: asset.title().map(str::to_string),
+        skip_reason,
+        input_url,
+        request_body: body,
+    }
+}
+
+/// マニフェストの項目から作成リクエストを組み立てる（アップロード時と同じ設定）
+fn from_manifest_entry(entry: &ManifestEntry) -> ImportCandidate {
+    let input_url = entry
+        .mp4_url
+        .clone()
+        .unwrap_or_else(|| mux_url(&entry.asset_id));
+    let body = json!({
+        "inputs": [{ "url": input_url }],
+        "playback_policies": ["public"],
+        "video_quality": APP_CONFIG.upload.video_quality,
+        "static_renditions": [{ "resolution": "highest" }],
+        "passthrough": format!("{}{}", CHECKSUM_PASSTHROUGH_PREFIX, entry.sha256),
+    });
+
+    ImportCandidate {
+        old_asset_id: entry.asset_id.clone(),
+        title: None,
+        skip_reason: None,
+        input_url,
+        request_body: body,
+    }
+}
+
+/// 同じ環境内のアセットを参照する取り込みURL
+fn mux_url(asset_id: &str) -> String {
+    format!("mux://assets/{}", asset_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(policy: &str, mp4_status: &str) -> Value {
+        json!({
+            "id": "old1",
+            "status": "ready",
+            "created_at": "1700000000",
+            "playback_ids": [{ "id": "play1", "policy": policy }],
+            "video_quality": "basic",
+            "meta": { "title": "Intro" },
+            "passthrough": "{\"tags\":[\"demo\"]}",
+            "static_renditions": { "files": [{
+                "id": "r1", "type": "standard", "status": mp4_status,
+                "resolution": "highest", "name": "highest.mp4", "ext": "mp4"
+            }]}
+        })
+    }
+
+    fn export(assets: Vec<Value>) -> String {
+        json!({
+            "version": 1,
+            "exported_at": "2024-01-01T00:00:00Z",
+            "total_count": assets.len(),
+            "assets": assets
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_parse_export_prefers_public_mp4() {
+        let candidates = parse_candidates(&export(vec![asset("public", "ready")])).unwrap();
+        let candidate = &candidates[0];
+        assert_eq!(
+            candidate.input_url,
+            "https://stream.mux.com/play1/highest.mp4"
+        );
+        assert_eq!(candidate.title.as_deref(), Some("Intro"));
+        assert_eq!(candidate.skip_reason, None);
+
+        let body = &candidate.request_body;
+        assert_eq!(body["playback_policies"], json!(["public"]));
+        assert_eq!(body["video_quality"], "basic");
+        assert_eq!(
+            body["static_renditions"],
+            json!([{ "resolution": "highest" }])
+        );
+        assert_eq!(body["meta"]["title"], "Intro");
+        assert_eq!(body["passthrough"], "{\"tags\":[\"demo\"]}");
+    }
+
+    #[test]
+    fn test_parse_export_falls_back_to_mux_url() {
+        let signed = parse_candidates(&export(vec![asset("signed", "ready")])).unwrap();
+        assert_eq!(signed[0].input_url, "mux://assets/old1");
+        assert_eq!(
+            signed[0].request_body["playback_policies"],
+            json!(["signed"])
+        );
+
+        let preparing = parse_candidates(&export(vec![asset("public", "preparing")])).unwrap();
+        assert_eq!(preparing[0].input_url, "mux://assets/old1");
+
+        let mut errored = asset("public", "ready");
+        errored["status"] = json!("errored");
+        let candidates = parse_candidates(&export(vec![errored])).unwrap();
+        assert!(candidates[0].skip_reason.is_some());
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = json!({
+            "version": 1,
+            "generated_at": "2024-01-01T00:00:00Z",
+            "uploads": [{
+                "source_path": "intro.mp4",
+                "file_size": 1,
+                "sha256": "abc",
+                "asset_id": "old1",
+                "playback_id": "play1",
+                "hls_url": "https://stream.mux.com/play1.m3u8",
+                "mp4_url": null,
+                "uploaded_at": "2024-01-01T00:00:00Z"
+            }]
+        });
+        let candidates = parse_candidates(&manifest.to_string()).unwrap();
+        assert_eq!(candidates[0].input_url, "mux://assets/old1");
+        assert_eq!(candidates[0].request_body["passthrough"], "sha256:abc");
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_files() {
+        assert!(parse_candidates("not json").is_err());
+        assert!(parse_candidates("{\"data\": []}").is_err());
+        let future = export(Vec::new()).replace("\"version\":1", "\"version\":99");
+        assert!(parse_candidates(&future).is_err());
+    }
+}
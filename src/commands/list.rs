@@ -1,9 +1,13 @@
 use crate::api::auth::AuthManager;
+use crate::api::cache::ResponseCache;
 use crate::api::client::ApiClient;
-use crate::api::types::AssetsListResponse;
-use crate::commands::result::{CommandResult, ListResult, VideoInfo};
-use crate::config::{APP_CONFIG, UserConfig};
-use anyhow::{Context, Result};
+use crate::api::types::AssetData;
+use crate::commands::connect;
+use crate::commands::result::{CommandResult, GroupBy, ListResult, VideoInfo};
+use crate::config::UserConfig;
+use crate::storage::asset_index::{AssetIndex, IndexedAsset};
+use anyhow::{Context, Result, bail};
+use futures_util::TryStreamExt;
 
 /// リストコマンドを実行する
 ///
@@ -11,6 +15,11 @@ use anyhow::{Context, Result};
 ///
 /// # 引数
 /// * `machine_output` - 機械可読出力フラグ（trueの場合、完全なAPIレスポンスを含む）
+/// * `use_cache` - レスポンスキャッシュを使用するか（`--no-cache`でfalse）
+/// * `offline` - APIに接続せず、ローカルインデックスから一覧を表示するか（`--offline`）
+/// * `title_only` - タイトルのあるアセットのみを表示するか（`--title-only`）
+/// * `tag` - 指定したタグの付いたアセットのみを表示する（`--tag`）
+/// * `group_by` - 作成日・作成月・ステータスでグループに分ける（`--group-by`）
 ///
 /// # 戻り値
 /// 成功・失敗を示すResult<CommandResult>
@@ -18,88 +27,196 @@ use anyhow::{Context, Result};
 /// # エラー
 /// アプリケーション層としてanyhow::Resultを返し、
 /// 設定・認証・インフラ層のエラーを集約します。
-pub async fn execute(machine_output: bool) -> Result<CommandResult> {
-    // ユーザー設定を読み込み
-    let user_config = UserConfig::load()
-        .context("Failed to load user configuration. Please check your config.toml file.")?;
+pub async fn execute(
+    machine_output: bool,
+    use_cache: bool,
+    offline: bool,
+    title_only: bool,
+    tag: Option<&str>,
+    group_by: Option<GroupBy>,
+) -> Result<CommandResult> {
+    let mut result = if offline {
+        execute_offline()?
+    } else {
+        execute_online(machine_output, use_cache).await?
+    };
 
-    // 認証情報を取得
-    let auth = user_config
-        .get_auth()
-        .context("Authentication credentials not found. Please run 'vidyeet login' first.")?;
+    // ローカルインデックスは全件で更新済みのため、絞り込みは最後におこなう
+    if title_only {
+        result.retain_titled();
+    }
+    if let Some(tag) = tag {
+        result.retain_tagged(tag);
+    }
 
+    // 日付はユーザー設定のタイムゾーンで判定（作成日時の表示と同じ）
+    if let Some(group_by) = group_by {
+        let offset_seconds = UserConfig::load()
+            .map(|config| config.timezone_offset_seconds)
+            .unwrap_or_default();
+        result.group(group_by, offset_seconds);
+    }
+
+    Ok(CommandResult::List(result))
+}
+
+/// Mux APIから一覧を取得し、ローカルインデックスを更新する
+async fn execute_online(machine_output: bool, use_cache: bool) -> Result<ListResult> {
     // 認証マネージャーとAPIクライアントを初期化
-    let auth_manager = AuthManager::new(auth.token_id.clone(), auth.token_secret.clone());
-    let client = ApiClient::new(APP_CONFIG.api.endpoint.to_string())
-        .context("Failed to create API client")?;
+    let (mut client, auth_manager) = connect()?;
+    if use_cache && let Some(cache) = ResponseCache::open_default() {
+        client = client.with_cache(cache);
+    }
+
+    // ローカルインデックス更新のため常に完全データを取得し、機械向け出力でなければ破棄
+    let mut result = run(&client, &auth_manager, true).await?;
+    if let Some(raw_assets) = &result.raw_assets {
+        AssetIndex::update_default(|index| {
+            index.replace_all(raw_assets.iter().map(IndexedAsset::from_asset).collect())
+        });
+    }
+    if !machine_output {
+        result.raw_assets = None;
+    }
+
+    Ok(result)
+}
+
+/// ローカルインデックスから一覧を構築する（ネットワーク・認証不要）
+fn execute_offline() -> Result<ListResult> {
+    let index = AssetIndex::load_default().context("Failed to load local asset index")?;
 
+    if index.synced_at().is_none() && index.assets().is_empty() {
+        bail!("No local asset index found. Run 'vidyeet list' while online to build it.");
+    }
+
+    let videos: Vec<VideoInfo> = index
+        .assets()
+        .iter()
+        .cloned()
+        .map(VideoInfo::from)
+        .collect();
+    let total_count = videos.len();
+
+    Ok(ListResult {
+        videos,
+        total_count,
+        raw_assets: None,
+        synced_at: index.synced_at().map(str::to_string),
+        group_by: None,
+        groups: Vec::new(),
+        warnings: Vec::new(),
+    })
+}
+
+impl From<IndexedAsset> for VideoInfo {
+    fn from(asset: IndexedAsset) -> Self {
+        Self {
+            asset_id: asset.asset_id,
+            title: asset.title,
+            external_id: asset.external_id,
+            tags: asset.tags,
+            status: asset.status,
+            playback_id: asset.playback_id,
+            playback_policy: asset.playback_policy,
+            hls_url: asset.hls_url,
+            mp4_url: asset.mp4_url,
+            duration: asset.duration,
+            created_at: asset.created_at,
+            aspect_ratio: asset.aspect_ratio,
+            storage_bytes: None,
+        }
+    }
+}
+
+/// 認証済みクライアントで動画一覧を取得する
+///
+/// 設定ファイルを読まないため、ライブラリ（`vidyeet::Client`）からも利用できます。
+///
+/// # 引数
+/// * `client` - APIクライアント
+/// * `auth_manager` - 認証マネージャー
+/// * `include_raw` - trueの場合、完全なAPIレスポンス（`raw_assets`）を含める
+pub async fn run(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    include_raw: bool,
+) -> Result<ListResult> {
     // アセット一覧を取得
-    let assets = fetch_all_assets(&client, &auth_manager)
+    let assets = fetch_all_assets(client, auth_manager)
         .await
         .context("Failed to fetch assets list")?;
 
     // 機械向け出力用に完全データをクローン（必要な場合のみ）
-    let raw_assets = if machine_output {
-        Some(assets.data.clone())
+    let raw_assets = if include_raw {
+        Some(assets.clone())
     } else {
         None
     };
 
     // 動画情報のリストを構築
     let videos: Vec<VideoInfo> = assets
-        .data
         .into_iter()
         .map(|asset| {
             let playback_id = asset.playback_ids.first().map(|p| p.id.clone());
+            let playback_policy = asset.playback_ids.first().map(|p| p.policy.clone());
             let hls_url = playback_id
                 .as_ref()
                 .map(|id| format!("https://stream.mux.com/{}.m3u8", id));
             // AssetDataのget_mp4_playback_url()を使用して統一的にMP4 URLを取得
             let mp4_url = asset.get_mp4_playback_url();
+            let storage_bytes = asset.rendition_storage_bytes();
 
             VideoInfo {
+                title: asset.title().map(str::to_string),
+                external_id: asset.external_id().map(str::to_string),
+                tags: asset.tags(),
                 asset_id: asset.id,
-                status: asset.status,
+                status: asset.status.to_string(),
                 playback_id,
+                playback_policy,
                 hls_url,
                 mp4_url,
                 duration: asset.duration,
                 created_at: asset.created_at,
                 aspect_ratio: asset.aspect_ratio,
+                storage_bytes,
             }
         })
         .collect();
 
     let total_count = videos.len();
 
-    Ok(CommandResult::List(ListResult {
+    Ok(ListResult {
         videos,
         total_count,
         raw_assets,
-    }))
+        synced_at: None,
+        group_by: None,
+        groups: Vec::new(),
+        warnings: Vec::new(),
+    })
 }
 
-/// Mux APIからアセット一覧を取得
+/// Mux APIからアセット一覧を全ページ取得
 ///
 /// # 引数
 /// * `client` - APIクライアント
 /// * `auth_manager` - 認証マネージャー
 ///
 /// # 戻り値
-/// アセット一覧のレスポンス
+/// 全アセットのリスト
 async fn fetch_all_assets(
     client: &ApiClient,
     auth_manager: &AuthManager,
-) -> Result<AssetsListResponse> {
+) -> Result<Vec<AssetData>> {
     let auth_header = auth_manager.get_auth_header();
 
-    let response = client
-        .get("/video/v1/assets?limit=100", Some(&auth_header))
+    let assets = client
+        .list_assets(&auth_header)
+        .try_collect()
         .await
         .context("Failed to fetch assets list")?;
 
-    let response = ApiClient::check_response(response, "/video/v1/assets").await?;
-    let assets_list: AssetsListResponse = ApiClient::parse_json(response).await?;
-
-    Ok(assets_list)
+    Ok(assets)
 }
@@ -0,0 +1,34 @@
+/// ライブストリーム（live）コマンド
+///
+/// ライブストリームのストリームキーを再発行します（`live reset-key`）。
+/// 再発行すると古いキーではすぐに配信できなくなるため、漏れたキーを素早く無効にできます。
+/// 新しいキーは秘密の値のため、人間向けの出力では伏せ字にし、`--plain` / `--machine` でのみそのまま出力します。
+use crate::commands::connect;
+use crate::commands::result::{CommandResult, LiveResetKeyResult};
+use anyhow::{Context, Result};
+
+/// ストリームキーを再発行する
+///
+/// # 引数
+/// * `live_stream_id` - 対象のライブストリームID
+///
+/// # エラー
+/// ライブストリームが存在しない場合、再発行に失敗した場合
+pub async fn reset_key(live_stream_id: &str) -> Result<CommandResult> {
+    // 認証マネージャーとAPIクライアントを初期化
+    let (client, auth_manager) = connect()?;
+
+    let live_stream = client
+        .reset_stream_key(live_stream_id, &auth_manager.get_auth_header())
+        .await
+        .context(format!(
+            "Failed to reset the stream key of live stream {}",
+            live_stream_id
+        ))?;
+
+    Ok(CommandResult::LiveResetKey(LiveResetKeyResult {
+        live_stream_id: live_stream.id,
+        status: live_stream.status.to_string(),
+        stream_key: live_stream.stream_key,
+    }))
+}
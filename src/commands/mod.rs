@@ -1,12 +1,72 @@
+pub mod backup;
+pub mod batch_upload;
+pub mod captions;
 pub mod delete;
+pub mod doctor;
+pub mod export;
+pub mod gc;
 pub mod help;
+pub mod history;
+pub mod import;
 pub mod list;
+pub mod live;
 pub mod login;
 pub mod logout;
+pub mod ping;
+pub mod playback;
+pub mod playback_ids;
+pub mod prune;
+pub mod queue;
+pub mod rename;
+pub mod renditions;
+pub mod restrictions;
 pub mod result;
+pub mod retry;
+pub mod serve;
 pub mod show;
+pub mod sign;
+pub mod stats;
 pub mod status;
+pub mod storyboard;
+pub mod sync;
+pub mod tag;
 pub mod upload;
+pub mod version;
+pub mod wait;
+pub mod whoami;
 
 #[allow(unused_imports)]
 pub use result::CommandResult;
+
+use crate::api::auth::AuthManager;
+use crate::api::client::ApiClient;
+use crate::config::{APP_CONFIG, UserConfig};
+use anyhow::{Context, Result};
+
+/// 設定を読み込み、APIクライアントと認証マネージャーを用意する
+///
+/// レスポンスキャッシュは設定しないため、キャッシュを使うコマンドは戻り値に `with_cache` を適用します。
+pub(crate) fn connect() -> Result<(ApiClient, AuthManager)> {
+    // ユーザー設定を読み込み
+    let user_config = UserConfig::load()
+        .context("Failed to load user configuration. Please check your config.toml file.")?;
+
+    connect_with(&user_config)
+}
+
+/// 読み込み済みのユーザー設定から、APIクライアントと認証マネージャーを用意する
+///
+/// 認証情報以外の設定も使うコマンド用です。
+pub(crate) fn connect_with(user_config: &UserConfig) -> Result<(ApiClient, AuthManager)> {
+    // 認証情報を取得
+    let auth = user_config
+        .get_auth()
+        .context("Authentication credentials not found. Please run 'vidyeet login' first.")?;
+
+    // 認証マネージャーとAPIクライアントを初期化
+    let auth_manager = AuthManager::new(auth.token_id.clone(), auth.token_secret.clone());
+    let client = ApiClient::new(APP_CONFIG.api.endpoint.to_string())
+        .context("Failed to create API client")?;
+
+    Ok((client, auth_manager))
+}
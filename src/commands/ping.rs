@@ -0,0 +1,77 @@
+/// 疎通確認コマンド
+///
+/// 軽量な認証付きリクエスト（`GET /video/v1/assets?limit=1`）を指定回数送り、
+/// レイテンシの最小・平均・最大と失敗したリクエストを返します。
+/// 大きなアップロードの前に「Muxが遅いのか、自分の回線が遅いのか」を切り分けるためのものです。
+/// 計測を歪めないよう、リトライ・キャッシュは使用しません。
+use crate::api::auth::AuthManager;
+use crate::api::client::ApiClient;
+use crate::api::retry::RetryPolicy;
+use crate::commands::result::{CommandResult, PingAttempt, PingResult};
+use crate::config::APP_CONFIG;
+use crate::config::user::UserConfig;
+use anyhow::{Context, Result};
+use reqwest::Url;
+use std::time::{Duration, Instant};
+
+/// 疎通確認コマンドを実行
+///
+/// # Arguments
+/// * `count` - リクエスト回数（1〜`APP_CONFIG.ping.max_count`）
+///
+/// # Returns
+/// 成功時はOk(CommandResult)。個々のリクエストの失敗はエラーではなく結果に含める
+pub async fn execute(count: u32) -> Result<CommandResult> {
+    let config = UserConfig::load().context("Failed to load configuration file")?;
+    let auth = config
+        .get_auth()
+        .context("Failed to retrieve authentication credentials")?;
+    let auth_header =
+        AuthManager::new(auth.token_id.clone(), auth.token_secret.clone()).get_auth_header();
+
+    let client = ApiClient::production()?.with_retry_policy(RetryPolicy::no_retry());
+    let endpoint = APP_CONFIG.ping.endpoint;
+    let interval = Duration::from_millis(APP_CONFIG.ping.interval_ms);
+
+    let mut attempts = Vec::with_capacity(count as usize);
+    for seq in 1..=count {
+        if seq > 1 {
+            tokio::time::sleep(interval).await;
+        }
+
+        let started = Instant::now();
+        let result = client.get(endpoint, Some(&auth_header)).await;
+        let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+        attempts.push(match result {
+            Ok(response) if response.status().is_success() => PingAttempt {
+                seq,
+                latency_ms,
+                status_code: Some(response.status().as_u16()),
+                error: None,
+            },
+            Ok(response) => PingAttempt {
+                seq,
+                latency_ms,
+                status_code: Some(response.status().as_u16()),
+                error: Some(format!("HTTP {}", response.status())),
+            },
+            Err(e) => PingAttempt {
+                seq,
+                latency_ms,
+                status_code: None,
+                error: Some(e.to_string()),
+            },
+        });
+    }
+
+    let host = Url::parse(APP_CONFIG.api.endpoint)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| APP_CONFIG.api.endpoint.to_string());
+    Ok(CommandResult::Ping(PingResult::from_attempts(
+        host,
+        format!("GET {}", endpoint),
+        attempts,
+    )))
+}
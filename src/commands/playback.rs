@@ -0,0 +1,268 @@
+/// 署名付き再生URL（show / list / upload 共通）
+///
+/// 再生ポリシーが `signed` のアセットはトークンなしのURLでは再生できないため、
+/// config.tomlの `[signing]` の署名キーでトークンを付けたURLに置き換えます。
+/// 署名キーが未設定の場合は、再生できないURLを出力しないようURLを空にし、結果に警告を加えます。
+/// `show` / `upload` のサムネイル画像のURLにも、画像用のトークンを付けます。
+/// `show` では、サムネイル・ストーリーボード・GIFの署名付きURLも加えます。
+use crate::api::signing::{self, Audience, UrlSigner};
+use crate::commands::result::{
+    BatchDetails, BatchResult, CommandResult, SignedImageUrls, UploadResult, VideoInfo,
+};
+use crate::config::{APP_CONFIG, UserConfig};
+use crate::domain::warning::{Warning, WarningKind};
+use anyhow::{Context, Result};
+
+/// 署名付きURLが必要な再生ポリシー
+pub const SIGNED_POLICY: &str = "signed";
+
+/// 結果に含まれる1件分の再生URL
+struct PlaybackUrls<'a> {
+    playback_id: Option<&'a str>,
+    policy: Option<&'a str>,
+    hls_url: &'a mut Option<String>,
+    mp4_url: &'a mut Option<String>,
+    /// サムネイル画像のURL（結果に含まれる場合のみ）
+    thumbnail_url: Option<&'a mut Option<String>>,
+}
+
+impl<'a> PlaybackUrls<'a> {
+    fn of_upload(upload: &'a mut UploadResult) -> Self {
+        Self {
+            playback_id: upload.playback_id.as_deref(),
+            policy: upload.playback_policy.as_deref(),
+            hls_url: &mut upload.hls_url,
+            mp4_url: &mut upload.mp4_url,
+            thumbnail_url: Some(&mut upload.thumbnail_url),
+        }
+    }
+
+    fn of_video(video: &'a mut VideoInfo) -> Self {
+        Self {
+            playback_id: video.playback_id.as_deref(),
+            policy: video.playback_policy.as_deref(),
+            hls_url: &mut video.hls_url,
+            mp4_url: &mut video.mp4_url,
+            thumbnail_url: None,
+        }
+    }
+
+    fn is_signed(&self) -> bool {
+        self.policy == Some(SIGNED_POLICY)
+    }
+}
+
+/// 結果に含まれる再生ポリシーが `signed` のURLに署名する
+///
+/// 対象は `show` / `list` / `upload`（複数ファイル・`retry` を含む）の結果で、
+/// それ以外の結果と、署名が必要なアセットを含まない結果は何もしません（設定も読みません）。
+///
+/// # 引数
+/// * `result` - コマンドの結果
+/// * `expires_secs` - 署名付きURLの有効期間（秒、`--expires`）。
+///   Noneの場合は `APP_CONFIG.signing.default_expires_secs`
+///
+/// # エラー
+/// config.tomlの `[signing]` が不正な場合（片方のみの設定、読み込めない秘密鍵）
+pub fn sign_result(result: &mut CommandResult, expires_secs: Option<u64>) -> Result<()> {
+    let targets: Vec<PlaybackUrls> = playback_urls(result)
+        .into_iter()
+        .filter(PlaybackUrls::is_signed)
+        .collect();
+    if targets.is_empty() {
+        return Ok(());
+    }
+
+    let user_config = UserConfig::load()
+        .context("Failed to load user configuration. Please check your config.toml file.")?;
+    let signer = UrlSigner::from_config(&user_config.signing)
+        .context("Invalid [signing] settings in config.toml")?;
+
+    let count = targets.len();
+    let expires_at =
+        signing::expires_at(expires_secs.unwrap_or(APP_CONFIG.signing.default_expires_secs));
+    apply(targets, signer.as_ref(), expires_at);
+
+    if let Some(signer) = &signer
+        && let CommandResult::Show(show) = result
+        && let Some(playback_id) = show.playback_ids.first()
+        && playback_id.policy == SIGNED_POLICY
+    {
+        show.signed_image_urls = Some(image_urls(signer, &playback_id.id.clone(), expires_at));
+    }
+
+    if signer.is_none() {
+        warn_unsigned(result, count);
+    }
+
+    Ok(())
+}
+
+/// 署名キーがなく再生URLを出力できなかったことを結果の警告に加える
+///
+/// 複数ファイルのアップロードでは、該当するファイルの結果にそれぞれ加えます。
+fn warn_unsigned(result: &mut CommandResult, count: usize) {
+    let warning = |count: usize| {
+        Warning::new(
+            WarningKind::SignedUrlsUnavailable,
+            format!(
+                "{} asset(s) have a signed playback policy; set key_id and private_key under [signing] in config.toml to print signed URLs",
+                count
+            ),
+        )
+    };
+    let signed_uploads = |uploaded: &mut Vec<UploadResult>| {
+        for upload in uploaded
+            .iter_mut()
+            .filter(|u| u.playback_policy.as_deref() == Some(SIGNED_POLICY))
+        {
+            upload.warnings.push(warning(1));
+        }
+    };
+
+    match result {
+        CommandResult::Show(show) => show.warnings.push(warning(count)),
+        CommandResult::List(list) => list.warnings.push(warning(count)),
+        CommandResult::Upload(upload) => upload.warnings.push(warning(count)),
+        CommandResult::Wait(wait) => wait.warnings.push(warning(count)),
+        CommandResult::Batch(BatchResult {
+            details: BatchDetails::Upload(batch),
+            ..
+        }) => signed_uploads(&mut batch.uploaded),
+        CommandResult::Retry(retry) => signed_uploads(&mut retry.uploaded),
+        _ => {}
+    }
+}
+
+/// 結果に含まれる再生URLを集める
+fn playback_urls(result: &mut CommandResult) -> Vec<PlaybackUrls<'_>> {
+    match result {
+        CommandResult::Show(show) => {
+            let show = &mut **show;
+            let first = show.playback_ids.first();
+            vec![PlaybackUrls {
+                playback_id: first.map(|p| p.id.as_str()),
+                policy: first.map(|p| p.policy.as_str()),
+                hls_url: &mut show.hls_url,
+                mp4_url: &mut show.mp4_url,
+                thumbnail_url: Some(&mut show.thumbnail_url),
+            }]
+        }
+        CommandResult::List(list) => list.videos.iter_mut().map(PlaybackUrls::of_video).collect(),
+        CommandResult::Upload(upload) => vec![PlaybackUrls::of_upload(upload)],
+        CommandResult::Wait(wait) => vec![PlaybackUrls {
+            playback_id: wait.playback_id.as_deref(),
+            policy: wait.playback_policy.as_deref(),
+            hls_url: &mut wait.hls_url,
+            mp4_url: &mut wait.mp4_url,
+            thumbnail_url: None,
+        }],
+        CommandResult::Batch(BatchResult {
+            details: BatchDetails::Upload(batch),
+            ..
+        }) => batch
+            .uploaded
+            .iter_mut()
+            .map(PlaybackUrls::of_upload)
+            .collect(),
+        CommandResult::Retry(retry) => retry
+            .uploaded
+            .iter_mut()
+            .map(PlaybackUrls::of_upload)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// URLにトークンを付ける（署名キーがない場合は再生できないURLを取り除く）
+fn apply(targets: Vec<PlaybackUrls>, signer: Option<&UrlSigner>, expires_at: i64) {
+    for target in targets {
+        match (signer, target.playback_id) {
+            (Some(signer), Some(playback_id)) => {
+                for url in [target.hls_url, target.mp4_url].into_iter().flatten() {
+                    *url = signer.sign_url(url, playback_id, Audience::Video, expires_at);
+                }
+                if let Some(Some(url)) = target.thumbnail_url {
+                    *url = signer.sign_url(url, playback_id, Audience::Thumbnail, expires_at);
+                }
+            }
+            _ => {
+                *target.hls_url = None;
+                *target.mp4_url = None;
+                if let Some(url) = target.thumbnail_url {
+                    *url = None;
+                }
+            }
+        }
+    }
+}
+
+/// 再生IDの画像（サムネイル・ストーリーボード・GIF）の署名付きURL
+fn image_urls(signer: &UrlSigner, playback_id: &str, expires_at: i64) -> SignedImageUrls {
+    let sign = |audience: Audience| {
+        signer.sign_url(
+            &audience.url(playback_id),
+            playback_id,
+            audience,
+            expires_at,
+        )
+    };
+    SignedImageUrls {
+        thumbnail_url: sign(Audience::Thumbnail),
+        storyboard_url: sign(Audience::Storyboard),
+        gif_url: sign(Audience::Gif),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::result::ListResult;
+
+    fn video(id: &str, policy: &str) -> VideoInfo {
+        VideoInfo {
+            asset_id: id.to_string(),
+            title: None,
+            external_id: None,
+            tags: Vec::new(),
+            status: "ready".to_string(),
+            playback_id: Some(format!("play_{}", id)),
+            playback_policy: Some(policy.to_string()),
+            hls_url: Some(format!("https://stream.mux.com/play_{}.m3u8", id)),
+            mp4_url: None,
+            duration: None,
+            created_at: "1700000000".to_string(),
+            aspect_ratio: None,
+            storage_bytes: None,
+        }
+    }
+
+    #[test]
+    fn test_without_signer_drops_only_signed_urls() {
+        let mut result = CommandResult::List(ListResult {
+            videos: vec![video("a", "public"), video("b", SIGNED_POLICY)],
+            total_count: 2,
+            raw_assets: None,
+            synced_at: None,
+            group_by: None,
+            groups: Vec::new(),
+            warnings: Vec::new(),
+        });
+
+        let targets: Vec<PlaybackUrls> = playback_urls(&mut result)
+            .into_iter()
+            .filter(PlaybackUrls::is_signed)
+            .collect();
+        assert_eq!(targets.len(), 1);
+        apply(targets, None, 1_700_000_000);
+        warn_unsigned(&mut result, 1);
+
+        let CommandResult::List(list) = result else {
+            unreachable!()
+        };
+        assert!(list.videos[0].hls_url.is_some());
+        assert!(list.videos[1].hls_url.is_none());
+        assert_eq!(list.warnings.len(), 1);
+        assert_eq!(list.warnings[0].kind, WarningKind::SignedUrlsUnavailable);
+    }
+}
@@ -0,0 +1,176 @@
+/// 再生ID管理（playback）コマンド
+///
+/// アセットを残したまま、再生IDを1つ削除します（`playback revoke`）。
+/// 公開してしまったURLを無効にする用途を想定しており、`--replace` を指定すると
+/// 削除する前に同じポリシーの再生IDを追加するため、アセットが再生できない時間は生じません。
+use crate::api::cache::ResponseCache;
+use crate::api::signing::{self, Audience, UrlSigner};
+use crate::api::types::PlaybackId;
+use crate::commands::connect;
+use crate::commands::playback::SIGNED_POLICY;
+use crate::commands::result::{CommandResult, PlaybackRevokeResult};
+use crate::config::{APP_CONFIG, UserConfig};
+use crate::domain::warning::{Warning, WarningKind};
+use crate::storage::asset_index::{AssetIndex, IndexedAsset};
+use anyhow::{Context, Result, bail};
+
+/// 再生IDを削除する
+///
+/// 再生IDがアセットのものか確認してから削除します。
+///
+/// # 引数
+/// * `asset_id` - 対象のアセットID
+/// * `playback_id` - 削除する再生ID
+/// * `replace` - 削除する前に同じポリシーの再生IDを追加するか（`--replace`）
+///
+/// # エラー
+/// 再生IDがアセットにない場合、再生IDの追加・削除に失敗した場合
+pub async fn revoke(asset_id: &str, playback_id: &str, replace: bool) -> Result<CommandResult> {
+    let (client, auth_manager) = connect()?;
+    let auth_header = auth_manager.get_auth_header();
+
+    let mut asset = client
+        .get_asset(asset_id, &auth_header)
+        .await
+        .context("Failed to fetch asset details")?;
+    let revoked = find_playback_id(&asset.playback_ids, asset_id, playback_id)?.clone();
+
+    // 先に追加し、削除に失敗しても再生できる再生IDが残るようにする
+    let replacement = if replace {
+        let created = client
+            .create_playback_id(asset_id, &revoked.policy, &auth_header)
+            .await
+            .context(format!("Failed to add playback ID to asset {}", asset_id))?;
+        asset.playback_ids.push(created.clone());
+        Some(created)
+    } else {
+        None
+    };
+
+    client
+        .delete_playback_id(asset_id, &revoked.id, &auth_header)
+        .await
+        .context(format!(
+            "Failed to delete playback ID {} of asset {}",
+            revoked.id, asset_id
+        ))?;
+    asset.playback_ids.retain(|p| p.id != revoked.id);
+
+    // showのキャッシュと `list --offline` のインデックスに古い再生IDが残らないようにする
+    ResponseCache::invalidate_default();
+    AssetIndex::update_default(|index| index.upsert(IndexedAsset::from_asset(&asset)));
+
+    let mut warnings = Vec::new();
+    let hls_url = match &replacement {
+        Some(created) => hls_url(created, &mut warnings)?,
+        None => None,
+    };
+    if asset.playback_ids.is_empty() {
+        warnings.push(Warning::new(
+            WarningKind::NoPlaybackIds,
+            format!(
+                "Asset {} has no playback IDs left and cannot be played; use --replace to add a new one",
+                asset_id
+            ),
+        ));
+    }
+
+    Ok(CommandResult::PlaybackRevoke(PlaybackRevokeResult {
+        asset_id: asset.id.clone(),
+        revoked,
+        replacement,
+        hls_url,
+        remaining_playback_ids: asset.playback_ids,
+        warnings,
+    }))
+}
+
+/// 追加した再生IDのHLS URL
+///
+/// 再生ポリシーが `signed` の場合は `[signing]` の署名キーで署名し、署名キーがなければNoneにして警告を加えます。
+fn hls_url(playback_id: &PlaybackId, warnings: &mut Vec<Warning>) -> Result<Option<String>> {
+    let url = format!("https://stream.mux.com/{}.m3u8", playback_id.id);
+    if playback_id.policy != SIGNED_POLICY {
+        return Ok(Some(url));
+    }
+
+    let user_config = UserConfig::load()
+        .context("Failed to load user configuration. Please check your config.toml file.")?;
+    let Some(signer) = UrlSigner::from_config(&user_config.signing)
+        .context("Invalid [signing] settings in config.toml")?
+    else {
+        warnings.push(Warning::new(
+            WarningKind::SignedUrlsUnavailable,
+            "The new playback ID has a signed playback policy; set key_id and private_key under [signing] in config.toml to print its signed URL",
+        ));
+        return Ok(None);
+    };
+    let expires_at = signing::expires_at(APP_CONFIG.signing.default_expires_secs);
+    Ok(Some(signer.sign_url(
+        &url,
+        &playback_id.id,
+        Audience::Video,
+        expires_at,
+    )))
+}
+
+/// アセットの再生IDから指定したIDを探す
+///
+/// # エラー
+/// 見つからない場合（アセットの再生IDを添える）
+fn find_playback_id<'a>(
+    playback_ids: &'a [PlaybackId],
+    asset_id: &str,
+    playback_id: &str,
+) -> Result<&'a PlaybackId> {
+    if let Some(found) = playback_ids.iter().find(|p| p.id == playback_id) {
+        return Ok(found);
+    }
+    if playback_ids.is_empty() {
+        bail!("Asset {} has no playback IDs", asset_id);
+    }
+    let ids: Vec<String> = playback_ids
+        .iter()
+        .map(|p| format!("{} ({})", p.id, p.policy))
+        .collect();
+    bail!(
+        "Playback ID {} does not belong to asset {}. Its playback IDs: {}",
+        playback_id,
+        asset_id,
+        ids.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn playback_id(id: &str, policy: &str) -> PlaybackId {
+        PlaybackId {
+            id: id.to_string(),
+            policy: policy.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_find_playback_id() {
+        let ids = vec![
+            playback_id("play_public", "public"),
+            playback_id("play_signed", "signed"),
+        ];
+        let found = find_playback_id(&ids, "asset_1", "play_signed").unwrap();
+        assert_eq!(found.policy, "signed");
+
+        let err = find_playback_id(&ids, "asset_1", "play_other").unwrap_err();
+        let message = err.to_string();
+        assert!(
+            message.contains("does not belong to asset asset_1"),
+            "{}",
+            message
+        );
+        assert!(message.contains("play_public (public), play_signed (signed)"));
+
+        let err = find_playback_id(&[], "asset_1", "play_public").unwrap_err();
+        assert_eq!(err.to_string(), "Asset asset_1 has no playback IDs");
+    }
+}
@@ -0,0 +1,151 @@
+/// 保持数による削除（prune）コマンド
+///
+/// 作成日時の新しい順に `--keep` 件のアセットを残し、それより古いアセットを削除します。
+/// 削除の順序は容量超過時の自動削除（`upload`）と同じです。
+/// `plan` で対象を集め、確認プロンプトの後に `execute` で実行します。
+use crate::api::types::AssetData;
+use crate::commands::connect;
+use crate::commands::delete;
+use crate::commands::result::{
+    BatchDetails, BatchResult, CommandResult, PruneFailure, PruneResult, PrunedAsset,
+};
+use crate::commands::upload;
+use crate::storage::asset_index::AssetIndex;
+use anyhow::{Context, Result};
+use futures_util::TryStreamExt;
+use std::time::{Duration, Instant};
+
+/// 削除の計画（`plan` で作成し、`execute` で実行する）
+#[derive(Debug, Clone)]
+pub struct PrunePlan {
+    /// 残すアセットの数
+    pub keep: usize,
+    /// アセットの総数
+    pub total_assets: usize,
+    /// 削除するアセット（古い順）
+    pub to_delete: Vec<PrunedAsset>,
+    /// 計画の作成にかかった時間（結果の所要時間に含める）
+    pub elapsed: Duration,
+}
+
+impl PrunePlan {
+    /// 実行せずに計画のみを結果にする（`--dry-run`）
+    pub fn into_dry_run(self) -> CommandResult {
+        let elapsed = self.elapsed;
+        CommandResult::Batch(BatchResult::new(
+            BatchDetails::Prune(self.into_result(true)),
+            elapsed,
+        ))
+    }
+
+    fn into_result(self, dry_run: bool) -> PruneResult {
+        PruneResult {
+            keep: self.keep,
+            dry_run,
+            total_assets: self.total_assets,
+            to_delete: self.to_delete,
+            deleted: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
+
+/// 削除の対象を集める（削除は行わない）
+///
+/// # 引数
+/// * `keep` - 残すアセットの数（新しいものから数える）
+pub async fn plan(keep: usize) -> Result<PrunePlan> {
+    let started_at = Instant::now();
+    let (client, auth_manager) = connect()?;
+    let auth_header = auth_manager.get_auth_header();
+
+    let assets: Vec<AssetData> = client
+        .list_assets(&auth_header)
+        .try_collect()
+        .await
+        .context("Failed to fetch assets list")?;
+
+    Ok(PrunePlan {
+        keep,
+        total_assets: assets.len(),
+        to_delete: select_excess(assets, keep),
+        elapsed: started_at.elapsed(),
+    })
+}
+
+/// 削除の計画を実行する
+///
+/// 個々の削除の失敗は結果の`failed`に記録し、残りは続行します。
+pub async fn execute(plan: PrunePlan) -> Result<CommandResult> {
+    let started_at = Instant::now();
+    let (client, auth_manager) = connect()?;
+    let plan_elapsed = plan.elapsed;
+    let mut result = plan.into_result(false);
+
+    for asset in &result.to_delete {
+        match delete::run(&client, &auth_manager, &asset.asset_id).await {
+            Ok(_) => {
+                AssetIndex::update_default(|index| index.remove(&asset.asset_id));
+                result.deleted.push(asset.asset_id.clone());
+            }
+            Err(e) => result.failed.push(PruneFailure {
+                asset_id: asset.asset_id.clone(),
+                error: format!("{:#}", e),
+            }),
+        }
+    }
+
+    Ok(CommandResult::Batch(BatchResult::new(
+        BatchDetails::Prune(result),
+        plan_elapsed + started_at.elapsed(),
+    )))
+}
+
+/// 新しいものから `keep` 件を残し、それより古いアセットを古い順に選ぶ
+fn select_excess(mut assets: Vec<AssetData>, keep: usize) -> Vec<PrunedAsset> {
+    upload::sort_oldest_first(&mut assets);
+    let excess = assets.len().saturating_sub(keep);
+    assets
+        .iter()
+        .take(excess)
+        .map(|asset| PrunedAsset {
+            asset_id: asset.id.clone(),
+            title: asset.title().map(str::to_string),
+            created_at: asset.created_at.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn asset(id: &str, created_at: &str) -> AssetData {
+        serde_json::from_value(json!({
+            "id": id,
+            "status": "ready",
+            "created_at": created_at,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_select_excess_keeps_newest() {
+        // Mux APIと同じく新しい順に並んでいる
+        let assets = vec![
+            asset("newest", "1700000300"),
+            asset("middle", "1700000200"),
+            asset("older", "1700000100"),
+            asset("oldest", "1700000000"),
+        ];
+
+        let ids: Vec<_> = select_excess(assets.clone(), 2)
+            .into_iter()
+            .map(|a| a.asset_id)
+            .collect();
+        assert_eq!(ids, ["oldest", "older"]);
+
+        assert!(select_excess(assets, 10).is_empty());
+    }
+}
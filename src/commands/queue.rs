@@ -0,0 +1,101 @@
+/// キューコマンド
+///
+/// アップロードをローカルのキューに登録し（`queue add`）、
+/// ネットワークが使えるときにまとめて実行します（`queue run`）。
+/// ネットワーク断などの一時的な失敗はキューに残り、次回の実行で自動的に再試行されます。
+use crate::commands::connect_with;
+use crate::commands::result::{CommandResult, QueueAddResult, QueueFailure, QueueRunResult};
+use crate::commands::upload::{self, UploadOptions};
+use crate::config::UserConfig;
+use crate::domain::validator;
+use crate::storage::upload_queue::UploadQueue;
+use anyhow::{Context, Result};
+
+/// アップロードをキューに登録する
+///
+/// 実行時まで問題に気付けない状況を避けるため、登録時点でファイルを検証します。
+///
+/// # 引数
+/// * `file_path` - アップロード対象の動画ファイルのパス
+pub async fn add(file_path: &str) -> Result<CommandResult> {
+    let user_config = UserConfig::load()
+        .context("Failed to load user configuration. Please check your config.toml file.")?;
+    let options = UploadOptions::default().with_user_defaults(&user_config);
+    let validation = validator::validate_upload_file(file_path, &options.supported_formats())
+        .context("File validation failed")?;
+
+    // 実行時のカレントディレクトリに依存しないよう絶対パスで保存
+    let absolute_path = std::fs::canonicalize(&validation.path)
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or(validation.path);
+
+    let mut queue = UploadQueue::load_default().context("Failed to load upload queue")?;
+    let item = queue.add(absolute_path, chrono::Utc::now().timestamp().to_string());
+    queue.save().context("Failed to save upload queue")?;
+
+    Ok(CommandResult::QueueAdd(QueueAddResult {
+        id: item.id,
+        file_path: item.file_path,
+        pending_count: queue.pending().len(),
+    }))
+}
+
+/// キューに登録されたアップロードを登録順に実行する
+///
+/// - 成功した項目はキューから除去
+/// - 一時的な失敗（ネットワークエラー、タイムアウト、5xx）の場合は項目をキューに残し、
+///   以降の項目も実行せずに終了（次回の `queue run` で再試行）
+/// - それ以外の失敗（ファイル削除済み、検証エラーなど）は再試行しても成功しないため、
+///   キューから除去して結果に含める
+pub async fn run() -> Result<CommandResult> {
+    // ユーザー設定を読み込み
+    let user_config = UserConfig::load()
+        .context("Failed to load user configuration. Please check your config.toml file.")?;
+
+    // 認証マネージャーとAPIクライアントを初期化（全項目で共有）
+    let (client, auth_manager) = connect_with(&user_config)?;
+
+    let options = UploadOptions::default().with_user_defaults(&user_config);
+
+    let mut queue = UploadQueue::load_default().context("Failed to load upload queue")?;
+    let mut uploaded = Vec::new();
+    let mut failed = Vec::new();
+
+    for item in queue.pending().to_vec() {
+        let outcome = match upload::run_before_upload_hook(&user_config.hooks, &item.file_path)
+            .await
+        {
+            Ok(file_path) => upload::run(&client, &auth_manager, &file_path, &options, None).await,
+            Err(e) => Err(e),
+        };
+        match outcome {
+            Ok(result) => {
+                upload::record_upload(&result);
+                queue.remove(item.id);
+                uploaded.push(result);
+            }
+            Err(e) if upload::is_transient_failure(&e) => {
+                queue.record_failure(item.id, format!("{:#}", e));
+                queue.save().context("Failed to save upload queue")?;
+                break;
+            }
+            Err(e) => {
+                queue.remove(item.id);
+                failed.push(QueueFailure {
+                    id: item.id,
+                    file_path: item.file_path,
+                    error: format!("{:#}", e),
+                });
+            }
+        }
+
+        // 途中で中断されても完了済みの項目が再実行されないよう、1件ごとに保存
+        queue.save().context("Failed to save upload queue")?;
+    }
+
+    Ok(CommandResult::QueueRun(QueueRunResult {
+        uploaded,
+        failed,
+        remaining: queue.pending().to_vec(),
+    }))
+}
@@ -0,0 +1,133 @@
+use crate::api::auth::AuthManager;
+use crate::api::cache::ResponseCache;
+use crate::api::client::ApiClient;
+use crate::api::types::{AssetData, AssetMeta, AssetPatch};
+/// リネームコマンド
+///
+/// アセットのタイトル（`meta.title`）を変更します。
+/// 変更前のタイトルを表示できるよう、現在のアセットを取得してから
+/// `PATCH /video/v1/assets/{ASSET_ID}` でタイトルのみを書き換えます。
+use crate::commands::connect;
+use crate::commands::result::{CommandResult, RenameResult};
+use crate::config::APP_CONFIG;
+use crate::storage::asset_index::{AssetIndex, IndexedAsset};
+use anyhow::{Context, Result, bail};
+
+/// リネームコマンドを実行する
+///
+/// # 引数
+/// * `asset_id` - 対象のアセットID
+/// * `title` - 新しいタイトル
+///
+/// # 戻り値
+/// 成功・失敗を示すResult<CommandResult>
+pub async fn execute(asset_id: &str, title: &str) -> Result<CommandResult> {
+    // 認証マネージャーとAPIクライアントを初期化
+    let (client, auth_manager) = connect()?;
+
+    let (result, updated) = rename_asset(&client, &auth_manager, asset_id, title).await?;
+
+    // ローカルインデックスのタイトルも更新
+    AssetIndex::update_default(|index| index.upsert(IndexedAsset::from_asset(&updated)));
+
+    Ok(CommandResult::Rename(result))
+}
+
+/// 認証済みクライアントでアセットのタイトルを変更する
+///
+/// 設定ファイルを読まないため、ライブラリ（`vidyeet::Client`）からも利用できます。
+///
+/// # 引数
+/// * `client` - APIクライアント
+/// * `auth_manager` - 認証マネージャー
+/// * `asset_id` - 対象のアセットID
+/// * `title` - 新しいタイトル
+pub async fn run(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    asset_id: &str,
+    title: &str,
+) -> Result<RenameResult> {
+    rename_asset(client, auth_manager, asset_id, title)
+        .await
+        .map(|(result, _)| result)
+}
+
+/// タイトルを検証し、変更前のタイトルを取得してから書き換える
+async fn rename_asset(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    asset_id: &str,
+    title: &str,
+) -> Result<(RenameResult, AssetData)> {
+    let title = validate_title(title)?;
+    let auth_header = auth_manager.get_auth_header();
+
+    let current = client
+        .get_asset(asset_id, &auth_header)
+        .await
+        .context("Failed to fetch asset details")?;
+    let old_title = current.title().map(str::to_string);
+
+    // metaは丸ごと置き換えられるため、タイトル以外（creator_id・external_id）は現在の値を送る
+    let meta = match current.meta {
+        Some(meta) => AssetMeta {
+            title: Some(title.clone()),
+            ..meta
+        },
+        None => AssetMeta {
+            title: Some(title.clone()),
+            creator_id: None,
+            external_id: None,
+        },
+    };
+    let patch = AssetPatch {
+        meta: Some(meta),
+        ..Default::default()
+    };
+
+    let updated = client
+        .patch_asset(asset_id, &patch, &auth_header)
+        .await
+        .context(format!("Failed to update title of asset {}", asset_id))?;
+
+    // list/showのキャッシュに古いタイトルが残らないよう破棄
+    ResponseCache::invalidate_default();
+
+    Ok((
+        RenameResult {
+            asset_id: asset_id.to_string(),
+            old_title,
+            new_title: title,
+        },
+        updated,
+    ))
+}
+
+/// 新しいタイトルを検証する（前後の空白は除去）
+fn validate_title(title: &str) -> Result<String> {
+    let title = title.trim();
+    if title.is_empty() {
+        bail!("Title cannot be empty");
+    }
+    let max = APP_CONFIG.metadata.max_title_chars;
+    if title.chars().count() > max {
+        bail!("Title is too long (max {} characters)", max);
+    }
+    Ok(title.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_title() {
+        assert_eq!(validate_title("  Product demo ").unwrap(), "Product demo");
+        assert!(validate_title("   ").is_err());
+
+        let max = APP_CONFIG.metadata.max_title_chars;
+        assert!(validate_title(&"あ".repeat(max)).is_ok());
+        assert!(validate_title(&"a".repeat(max + 1)).is_err());
+    }
+}
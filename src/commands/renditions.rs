@@ -0,0 +1,316 @@
+/// Static Rendition（MP4）管理（renditions）コマンド
+///
+/// 既存のアセットに解像度を指定してStatic Rendition（MP4）を追加・削除します
+/// （`/video/v1/assets/{ASSET_ID}/static-renditions`）。
+/// MP4なしでアップロードしたアセットにも、後からダウンロード用のMP4を用意できます。
+/// 追加時は、アップロードのアセット作成待ちと同じ間隔（指数バックオフ）・上限でアセットをポーリングし、
+/// MP4が `ready` になるまで待機します（`--no-wait` で待機しない）。
+use crate::api::auth::AuthManager;
+use crate::api::cache::ResponseCache;
+use crate::api::client::ApiClient;
+use crate::api::endpoints;
+use crate::api::signing::{self, Audience, UrlSigner};
+use crate::api::types::{AssetData, AssetResponse, RenditionStatus, StaticRendition};
+use crate::commands::connect;
+use crate::commands::playback::SIGNED_POLICY;
+use crate::commands::result::{CommandResult, RenditionAction, RenditionsResult};
+use crate::commands::wait::Poller;
+use crate::config::{APP_CONFIG, UserConfig};
+use crate::domain::warning::{Warning, WarningKind};
+use anyhow::{Context, Result, bail};
+
+/// 指定できる解像度（Mux APIの `resolution`）
+pub const RESOLUTIONS: &[&str] = &[
+    "highest",
+    "audio-only",
+    "2160p",
+    "1440p",
+    "1080p",
+    "720p",
+    "540p",
+    "480p",
+    "360p",
+    "270p",
+];
+
+/// Static Renditionを追加する
+///
+/// 同じ解像度のStatic Renditionが既にある場合は追加せず、そのまま返します（`changed: false`）。
+///
+/// # 引数
+/// * `asset_id` - 対象のアセットID
+/// * `resolution` - 解像度（`--resolution`）
+/// * `wait` - MP4が `ready` になるまで待機するか（`--no-wait` でfalse）
+pub async fn create(asset_id: &str, resolution: &str, wait: bool) -> Result<CommandResult> {
+    let resolution = validate_resolution(resolution)?;
+    let (client, auth_manager) = connect()?;
+    let asset = fetch_asset(&client, &auth_manager, asset_id).await?;
+
+    let (rendition, changed) = match find_rendition(&asset, &resolution) {
+        Some(existing) => (existing.clone(), false),
+        None => {
+            let auth_header = auth_manager.get_auth_header();
+            let created = client
+                .create_static_rendition(asset_id, &resolution, &auth_header)
+                .await
+                .context(format!(
+                    "Failed to create {} static rendition for asset {}",
+                    resolution, asset_id
+                ))?;
+
+            // showのキャッシュに古いStatic Renditionの一覧が残らないよう破棄
+            ResponseCache::invalidate_default();
+            (created, true)
+        }
+    };
+
+    let rendition = if wait {
+        wait_until_ready(&client, &auth_manager, asset_id, &rendition.id).await?
+    } else {
+        rendition
+    };
+    let mp4_url = mp4_url(&asset, &rendition)?;
+
+    let mut warnings = Vec::new();
+    if rendition.status != RenditionStatus::Ready {
+        warnings.push(Warning::new(
+            WarningKind::Mp4Generating,
+            format!(
+                "The {} MP4 is still being generated; run 'vidyeet show {}' later to get its URL",
+                rendition.resolution, asset_id
+            ),
+        ));
+    }
+
+    Ok(CommandResult::Renditions(RenditionsResult {
+        asset_id: asset_id.to_string(),
+        action: RenditionAction::Create,
+        changed,
+        rendition,
+        mp4_url,
+        warnings,
+    }))
+}
+
+/// Static Renditionを削除する
+///
+/// # 引数
+/// * `asset_id` - 対象のアセットID
+/// * `resolution` - 削除する解像度（`--resolution`）
+pub async fn delete(asset_id: &str, resolution: &str) -> Result<CommandResult> {
+    let resolution = validate_resolution(resolution)?;
+    let (client, auth_manager) = connect()?;
+    let asset = fetch_asset(&client, &auth_manager, asset_id).await?;
+
+    let Some(rendition) = find_rendition(&asset, &resolution).cloned() else {
+        bail!(
+            "Asset {} has no {} static rendition. Run 'vidyeet show {}' to see its renditions.",
+            asset_id,
+            resolution,
+            asset_id
+        );
+    };
+
+    let auth_header = auth_manager.get_auth_header();
+    client
+        .delete_static_rendition(asset_id, &rendition.id, &auth_header)
+        .await
+        .context(format!(
+            "Failed to delete {} static rendition of asset {}",
+            resolution, asset_id
+        ))?;
+
+    ResponseCache::invalidate_default();
+
+    Ok(CommandResult::Renditions(RenditionsResult {
+        asset_id: asset_id.to_string(),
+        action: RenditionAction::Delete,
+        changed: true,
+        rendition,
+        mp4_url: None,
+        warnings: Vec::new(),
+    }))
+}
+
+/// Static Renditionが `ready` になるまでアセットをポーリングする
+///
+/// 間隔と上限はアップロードのアセット作成待ち（`APP_CONFIG.upload`）と同じです。
+async fn wait_until_ready(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    asset_id: &str,
+    rendition_id: &str,
+) -> Result<StaticRendition> {
+    let auth_header = auth_manager.get_auth_header();
+    let endpoint = endpoints::asset(asset_id);
+    let mut poller = Poller::new(APP_CONFIG.upload.max_wait_secs);
+
+    loop {
+        // レート制限中はNoneのため次のポーリングを待つ
+        if let Some(response) = poller
+            .get(client, &auth_header, &endpoint)
+            .await
+            .context("Failed to fetch asset details")?
+        {
+            let asset: AssetResponse = ApiClient::parse_json(response).await?;
+            let rendition = asset
+                .data
+                .static_renditions
+                .iter()
+                .flat_map(|wrapper| &wrapper.files)
+                .find(|r| r.id == rendition_id)
+                .with_context(|| {
+                    format!(
+                        "Static rendition {} was removed from asset {}",
+                        rendition_id, asset_id
+                    )
+                })?;
+
+            match &rendition.status {
+                RenditionStatus::Ready => return Ok(rendition.clone()),
+                status if status.is_failed() => bail!(
+                    "Static rendition {} of asset {} was {} by Mux (the source may be smaller than the requested resolution)",
+                    rendition.resolution,
+                    asset_id,
+                    status
+                ),
+                // まだ生成中
+                _ => {}
+            }
+        }
+
+        // 待機してから再取得
+        if !poller.sleep().await {
+            break;
+        }
+    }
+
+    bail!(
+        "Static rendition was not ready after {} seconds. Run 'vidyeet show {}' later to check it.",
+        APP_CONFIG.upload.max_wait_secs,
+        asset_id
+    )
+}
+
+/// アセットを取得する
+async fn fetch_asset(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    asset_id: &str,
+) -> Result<AssetData> {
+    let auth_header = auth_manager.get_auth_header();
+    client
+        .get_asset(asset_id, &auth_header)
+        .await
+        .context("Failed to fetch asset details")
+}
+
+/// 指定した解像度のStatic Renditionを探す（削除済み・生成に失敗したものは除く）
+fn find_rendition<'a>(asset: &'a AssetData, resolution: &str) -> Option<&'a StaticRendition> {
+    asset
+        .static_renditions
+        .iter()
+        .flat_map(|wrapper| &wrapper.files)
+        .find(|r| {
+            r.resolution == resolution
+                && r.status != RenditionStatus::Deleted
+                && !r.status.is_failed()
+        })
+}
+
+/// `ready` のStatic RenditionのURL（再生IDがない場合はNone）
+///
+/// 再生ポリシーが `signed` の場合は `[signing]` の署名キーで署名し、署名キーがなければNoneにします。
+fn mp4_url(asset: &AssetData, rendition: &StaticRendition) -> Result<Option<String>> {
+    let Some(playback_id) = asset.playback_ids.first() else {
+        return Ok(None);
+    };
+    if rendition.status != RenditionStatus::Ready {
+        return Ok(None);
+    }
+    let url = format!(
+        "https://stream.mux.com/{}/{}",
+        playback_id.id, rendition.name
+    );
+    if playback_id.policy != SIGNED_POLICY {
+        return Ok(Some(url));
+    }
+
+    let user_config = UserConfig::load()
+        .context("Failed to load user configuration. Please check your config.toml file.")?;
+    let signer = UrlSigner::from_config(&user_config.signing)
+        .context("Invalid [signing] settings in config.toml")?;
+    let expires_at = signing::expires_at(APP_CONFIG.signing.default_expires_secs);
+    Ok(signer.map(|signer| signer.sign_url(&url, &playback_id.id, Audience::Video, expires_at)))
+}
+
+/// 解像度を検証する（大文字・小文字は区別しない）
+fn validate_resolution(resolution: &str) -> Result<String> {
+    let resolution = resolution.trim().to_ascii_lowercase();
+    if !RESOLUTIONS.contains(&resolution.as_str()) {
+        bail!(
+            "Invalid resolution: '{}' (expected one of: {})",
+            resolution,
+            RESOLUTIONS.join(", ")
+        );
+    }
+    Ok(resolution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::StaticRenditionsWrapper;
+
+    fn rendition(id: &str, resolution: &str, status: &str) -> StaticRendition {
+        StaticRendition {
+            id: id.to_string(),
+            rendition_type: "standard".to_string(),
+            status: RenditionStatus::from(status),
+            resolution: resolution.to_string(),
+            name: format!("{}.mp4", resolution),
+            ext: "mp4".to_string(),
+            filesize: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_resolution() {
+        assert_eq!(validate_resolution(" 720P ").unwrap(), "720p");
+        assert_eq!(validate_resolution("highest").unwrap(), "highest");
+        assert!(validate_resolution("800p").is_err());
+        assert!(validate_resolution("").is_err());
+    }
+
+    #[test]
+    fn test_find_rendition_skips_failed_and_deleted() {
+        let asset: AssetData = serde_json::from_value(serde_json::json!({
+            "id": "asset_1",
+            "status": "ready",
+            "created_at": "1700000000",
+            "playback_ids": [{ "id": "play_1", "policy": "public" }]
+        }))
+        .unwrap();
+        let asset = AssetData {
+            static_renditions: Some(StaticRenditionsWrapper {
+                files: vec![
+                    rendition("r1", "720p", "errored"),
+                    rendition("r2", "720p", "preparing"),
+                    rendition("r3", "1080p", "deleted"),
+                ],
+            }),
+            ..asset
+        };
+
+        assert_eq!(find_rendition(&asset, "720p").unwrap().id, "r2");
+        assert!(find_rendition(&asset, "1080p").is_none());
+        assert_eq!(
+            mp4_url(&asset, &rendition("r4", "480p", "ready")).unwrap(),
+            Some("https://stream.mux.com/play_1/480p.mp4".to_string())
+        );
+        assert_eq!(
+            mp4_url(&asset, &rendition("r2", "720p", "preparing")).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,184 @@
+/// 再生制限（restrictions）コマンド
+///
+/// 再生を許可するリファラー（ドメイン）を定めた再生制限（Playback Restriction）を
+/// 作成・一覧・削除し（`/video/v1/playback-restrictions`）、アセットに再生制限用の
+/// 署名付き再生IDを追加します（`restrictions attach`）。
+/// 再生制限は署名付き再生IDのトークンに含めることで適用されるため、
+/// URLの署名時に `sign --restriction <id>` または config.tomlの
+/// `[signing] playback_restriction_id` で指定します。
+use crate::api::cache::ResponseCache;
+use crate::api::types::{PlaybackRestriction, ReferrerRestriction};
+use crate::commands::connect;
+use crate::commands::playback::SIGNED_POLICY;
+use crate::commands::result::{
+    CommandResult, RestrictionsAttachResult, RestrictionsCreateResult, RestrictionsDeleteResult,
+    RestrictionsListResult,
+};
+use crate::config::APP_CONFIG;
+use anyhow::{Context, Result, bail};
+use futures_util::TryStreamExt;
+
+/// 再生制限を作成する
+///
+/// # 引数
+/// * `domains` - 再生を許可するドメイン（`--domain`、複数指定可）
+/// * `allow_no_referrer` - リファラーのないリクエストからの再生を許可するか（`--allow-no-referrer`）
+pub async fn create(domains: &[String], allow_no_referrer: bool) -> Result<CommandResult> {
+    let referrer = ReferrerRestriction {
+        allowed_domains: validate_domains(domains)?,
+        allow_no_referrer,
+    };
+    let (client, auth_manager) = connect()?;
+    let auth_header = auth_manager.get_auth_header();
+
+    let restriction = client
+        .create_playback_restriction(&referrer, &auth_header)
+        .await
+        .context("Failed to create playback restriction")?;
+
+    Ok(CommandResult::RestrictionsCreate(
+        RestrictionsCreateResult { restriction },
+    ))
+}
+
+/// 再生制限の一覧を取得する
+pub async fn list() -> Result<CommandResult> {
+    let (client, auth_manager) = connect()?;
+    let auth_header = auth_manager.get_auth_header();
+
+    let restrictions: Vec<PlaybackRestriction> = client
+        .list_playback_restrictions(&auth_header)
+        .try_collect()
+        .await
+        .context("Failed to fetch playback restrictions")?;
+
+    Ok(CommandResult::RestrictionsList(RestrictionsListResult {
+        total_count: restrictions.len(),
+        restrictions,
+    }))
+}
+
+/// 再生制限を削除する
+///
+/// # 引数
+/// * `restriction_id` - 削除する再生制限のID
+pub async fn delete(restriction_id: &str) -> Result<CommandResult> {
+    let (client, auth_manager) = connect()?;
+    let auth_header = auth_manager.get_auth_header();
+
+    client
+        .delete_playback_restriction(restriction_id, &auth_header)
+        .await
+        .context(format!(
+            "Failed to delete playback restriction {}",
+            restriction_id
+        ))?;
+
+    Ok(CommandResult::RestrictionsDelete(
+        RestrictionsDeleteResult {
+            restriction_id: restriction_id.to_string(),
+        },
+    ))
+}
+
+/// アセットに再生制限用の署名付き再生IDを追加する
+///
+/// 再生制限は署名付き再生IDにのみ適用できるため、ポリシー `signed` の再生IDを追加します。
+/// 指定ミスで制限のない再生IDを作らないよう、先に再生制限が存在することを確認します。
+///
+/// # 引数
+/// * `asset_id` - 対象のアセットID
+/// * `restriction_id` - 適用する再生制限のID
+pub async fn attach(asset_id: &str, restriction_id: &str) -> Result<CommandResult> {
+    let (client, auth_manager) = connect()?;
+    let auth_header = auth_manager.get_auth_header();
+
+    let restriction = client
+        .get_playback_restriction(restriction_id, &auth_header)
+        .await
+        .context(format!(
+            "Failed to fetch playback restriction {}",
+            restriction_id
+        ))?;
+
+    let created = client
+        .create_playback_id(asset_id, SIGNED_POLICY, &auth_header)
+        .await
+        .context(format!("Failed to add playback ID to asset {}", asset_id))?;
+
+    // showのキャッシュに古い再生IDの一覧が残らないよう破棄
+    ResponseCache::invalidate_default();
+
+    Ok(CommandResult::RestrictionsAttach(
+        RestrictionsAttachResult {
+            asset_id: asset_id.to_string(),
+            playback_id: created.id,
+            policy: created.policy,
+            restriction_id: restriction.id,
+            allowed_domains: restriction.referrer.allowed_domains,
+        },
+    ))
+}
+
+/// 許可するドメインを検証する（前後の空白を除いて小文字にそろえ、重複を除く）
+///
+/// ドメインのみを受け付け、スキーム（`https://`）やパスを含む値はエラーにします。
+/// ワイルドカードは先頭の `*.`（サブドメイン）と、すべてを許可する `*` のみです。
+fn validate_domains(domains: &[String]) -> Result<Vec<String>> {
+    let mut validated: Vec<String> = Vec::new();
+    for domain in domains {
+        let domain = domain.trim().to_ascii_lowercase();
+        if domain.is_empty() {
+            bail!("Domain cannot be empty");
+        }
+        let host = domain.strip_prefix("*.").unwrap_or(&domain);
+        let valid = domain == "*"
+            || (!host.is_empty()
+                && host.split('.').all(|label| {
+                    !label.is_empty()
+                        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                }));
+        if !valid {
+            bail!(
+                "Invalid domain: '{}' (expected a domain such as 'example.com' or '*.example.com', without scheme or path)",
+                domain
+            );
+        }
+        if !validated.contains(&domain) {
+            validated.push(domain);
+        }
+    }
+
+    if validated.is_empty() {
+        bail!("Please specify at least one domain with --domain");
+    }
+    let max = APP_CONFIG.signing.max_allowed_domains;
+    if validated.len() > max {
+        bail!("Too many domains (max {})", max);
+    }
+    Ok(validated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn domains(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_validate_domains() {
+        assert_eq!(
+            validate_domains(&domains(&[" Example.com ", "*.example.com", "example.com"])).unwrap(),
+            ["example.com", "*.example.com"]
+        );
+        assert_eq!(validate_domains(&domains(&["*"])).unwrap(), ["*"]);
+
+        assert!(validate_domains(&[]).is_err());
+        assert!(validate_domains(&domains(&["  "])).is_err());
+        assert!(validate_domains(&domains(&["https://example.com"])).is_err());
+        assert!(validate_domains(&domains(&["example.com/videos"])).is_err());
+        assert!(validate_domains(&domains(&["*.", "a..b"])).is_err());
+    }
+}
@@ -2,7 +2,13 @@
 ///
 /// 各コマンドはこの型を返し、プレゼンテーション層（main.rs/cli.rs）で
 /// 人間向けと機械向けの出力フォーマットを決定する。
+use crate::config::user::CapacityPolicy;
+use crate::domain::warning::Warning;
+use crate::error_severity::ErrorSeverity;
+use crate::storage::export::ExportFormat;
+use schemars::JsonSchema;
 use serde::Serialize;
+use std::collections::BTreeMap;
 
 /// コマンド実行結果の統一型
 #[derive(Debug, Clone, Serialize)]
@@ -10,50 +16,436 @@ use serde::Serialize;
 pub enum CommandResult {
     Login(LoginResult),
     Logout(LogoutResult),
-    Upload(UploadResult),
+    Upload(Box<UploadResult>),
+    UploadEstimate(Box<UploadEstimateResult>),
     Status(StatusResult),
     List(ListResult),
     Show(Box<ShowResult>),
     Delete(DeleteResult),
+    Rename(RenameResult),
+    Tag(TagResult),
+    Renditions(RenditionsResult),
+    Captions(CaptionsResult),
+    Wait(WaitResult),
+    Export(ExportResult),
+    Import(ImportResult),
+    Gc(GcResult),
+    QueueAdd(QueueAddResult),
+    QueueRun(QueueRunResult),
+    Retry(RetryResult),
+    RetryList(RetryListResult),
+    History(HistoryResult),
+    Sign(SignResult),
+    Storyboard(StoryboardResult),
+    Serve(ServeResult),
+    RestrictionsCreate(RestrictionsCreateResult),
+    RestrictionsList(RestrictionsListResult),
+    RestrictionsDelete(RestrictionsDeleteResult),
+    RestrictionsAttach(RestrictionsAttachResult),
+    PlaybackRevoke(PlaybackRevokeResult),
+    LiveResetKey(LiveResetKeyResult),
+    Batch(BatchResult),
+    Version(VersionResult),
+    Doctor(DoctorResult),
+    Ping(PingResult),
+    Whoami(WhoamiResult),
+    Stats(StatsResult),
     Help,
 }
 
-/// ログインコマンドの結果
+impl CommandResult {
+    /// コマンド結果に対応する終了コード
+    ///
+    /// 一括処理・インポート・同期・バックアップ・掃除・再実行・疎通確認で失敗した項目がある場合（一部失敗は4、全件失敗は1）と、
+    /// 診断で失敗したチェックがある場合（1）のみ0以外を返します。
+    pub fn exit_code(&self) -> i32 {
+        let status = match self {
+            Self::Batch(r) => r.status(),
+            Self::Import(r) => r.status(),
+            Self::Gc(r) => r.status(),
+            Self::QueueRun(r) => r.status(),
+            Self::Retry(r) => r.status(),
+            Self::Ping(r) => r.status(),
+            Self::Doctor(r) if !r.passed() => return 1,
+            _ => return 0,
+        };
+        status.exit_code()
+    }
+
+    /// 結果に含まれる警告
+    ///
+    /// 複数ファイルのアップロード（`Batch` / `Retry`）の警告は
+    /// 各ファイルの結果（`uploaded[].warnings`）に含まれるため、ここでは返しません。
+    pub fn warnings(&self) -> &[Warning] {
+        match self {
+            Self::Upload(r) => &r.warnings,
+            Self::UploadEstimate(r) => &r.warnings,
+            Self::List(r) => &r.warnings,
+            Self::Show(r) => &r.warnings,
+            Self::Renditions(r) => &r.warnings,
+            Self::Wait(r) => &r.warnings,
+            Self::PlaybackRevoke(r) => &r.warnings,
+            Self::Status(r) => &r.warnings,
+            _ => &[],
+        }
+    }
+}
+
+/// 一括処理全体の結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkStatus {
+    /// すべての項目が成功（項目がない場合を含む）
+    Succeeded,
+    /// 一部の項目のみ成功
+    PartialFailure,
+    /// すべての項目が失敗
+    Failed,
+}
+
+impl BulkStatus {
+    /// 成功・失敗の件数から判定する
+    pub fn from_counts(succeeded: usize, failed: usize) -> Self {
+        match (succeeded, failed) {
+            (_, 0) => Self::Succeeded,
+            (0, _) => Self::Failed,
+            _ => Self::PartialFailure,
+        }
+    }
+
+    /// 対応する終了コード
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Succeeded => 0,
+            Self::PartialFailure => ErrorSeverity::PartialFailure.exit_code(),
+            Self::Failed => ErrorSeverity::UserError.exit_code(),
+        }
+    }
+}
+
+/// 一括処理の項目ごとの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemStatus {
+    /// アップロード成功
+    Uploaded,
+    /// 失敗
+    Failed,
+    /// 一時的な失敗のためキューに残った（`queue run`のみ）
+    Queued,
+    /// アセットを削除した（`sync --delete` / `prune`）
+    Deleted,
+    /// ダウンロードした（`backup`）
+    Downloaded,
+    /// 処理済みのため何もしなかった（`sync` のアップロード済みファイル、`backup` の最新のファイル）
+    Unchanged,
+    /// 処理できないため対象外にした（`backup` の公開の再生IDがないアセット）
+    Skipped,
+    /// 実行予定（`--dry-run`）
+    Planned,
+}
+
+/// 一括処理の1項目の結果（機械可読出力の`items`）
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ItemOutcome {
+    /// ファイルパス（ファイルを扱う項目のみ。`backup` は保存先のパス）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    /// 項目の状態
+    pub status: ItemStatus,
+    /// アセットID（アップロードに成功した項目と、アセットを扱う項目）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_id: Option<String>,
+    /// エラーメッセージ（失敗時のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ItemOutcome {
+    fn uploaded(result: &UploadResult) -> Self {
+        Self::file(&result.file_path, ItemStatus::Uploaded, None).with_asset(&result.asset_id)
+    }
+
+    /// ファイルを扱う項目
+    fn file(file_path: &str, status: ItemStatus, error: Option<&str>) -> Self {
+        Self {
+            file_path: Some(file_path.to_string()),
+            status,
+            asset_id: None,
+            error: error.map(str::to_string),
+        }
+    }
+
+    /// アセットを扱う項目
+    fn asset(asset_id: &str, status: ItemStatus, error: Option<&str>) -> Self {
+        Self {
+            file_path: None,
+            status,
+            asset_id: Some(asset_id.to_string()),
+            error: error.map(str::to_string),
+        }
+    }
+
+    fn with_asset(mut self, asset_id: &str) -> Self {
+        self.asset_id = Some(asset_id.to_string());
+        self
+    }
+}
+
+/// 一括処理の結果（複数ファイルのアップロード・`prune`・`sync`・`backup`）
+///
+/// 項目ごとの結果・成功と失敗の件数・所要時間を共通の形で持ち、
+/// 処理ごとの結果は `details` に持ちます。
 #[derive(Debug, Clone, Serialize)]
+pub struct BatchResult {
+    /// 処理ごとの結果
+    #[serde(flatten)]
+    pub details: BatchDetails,
+    /// 項目ごとの結果
+    pub items: Vec<ItemOutcome>,
+    /// 成功した項目の数（処理済みのため何もしなかった項目を含む）
+    pub succeeded: usize,
+    /// 失敗した項目の数
+    pub failed: usize,
+    /// 所要時間（秒）
+    pub duration: f64,
+}
+
+impl BatchResult {
+    /// 処理ごとの結果から項目と件数を集計する
+    ///
+    /// # 引数
+    /// * `details` - 処理ごとの結果
+    /// * `duration` - 所要時間
+    pub fn new(details: BatchDetails, duration: std::time::Duration) -> Self {
+        let (succeeded, failed) = details.counts();
+        Self {
+            items: details.items(),
+            details,
+            succeeded,
+            failed,
+            duration: duration.as_secs_f64(),
+        }
+    }
+
+    /// 全体の結果（`--dry-run` の場合は常に成功）
+    pub fn status(&self) -> BulkStatus {
+        BulkStatus::from_counts(self.succeeded, self.failed)
+    }
+}
+
+/// 一括処理ごとの結果
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum BatchDetails {
+    /// 複数ファイルのアップロード
+    Upload(BatchUploadResult),
+    /// 保持数による削除（`prune`）
+    Prune(PruneResult),
+    /// ディレクトリの同期（`sync`）
+    Sync(SyncResult),
+    /// バックアップ（`backup`）
+    Backup(BackupResult),
+}
+
+impl BatchDetails {
+    /// 成功・失敗した項目の数
+    fn counts(&self) -> (usize, usize) {
+        match self {
+            Self::Upload(r) => (r.uploaded.len(), r.failed.len()),
+            Self::Prune(r) => (r.deleted.len(), r.failed.len()),
+            Self::Sync(r) => (r.uploaded.len() + r.deleted.len(), r.failed.len()),
+            // 最新のファイルもバックアップ済みとして数える
+            Self::Backup(r) => (r.downloaded.len() + r.up_to_date.len(), r.failed.len()),
+        }
+    }
+
+    /// 項目ごとの結果
+    fn items(&self) -> Vec<ItemOutcome> {
+        match self {
+            Self::Upload(r) => r.items(),
+            Self::Prune(r) if r.dry_run => r
+                .to_delete
+                .iter()
+                .map(|a| ItemOutcome::asset(&a.asset_id, ItemStatus::Planned, None))
+                .collect(),
+            Self::Prune(r) => {
+                let deleted = r
+                    .deleted
+                    .iter()
+                    .map(|id| ItemOutcome::asset(id, ItemStatus::Deleted, None));
+                let failed = r
+                    .failed
+                    .iter()
+                    .map(|f| ItemOutcome::asset(&f.asset_id, ItemStatus::Failed, Some(&f.error)));
+                deleted.chain(failed).collect()
+            }
+            Self::Sync(r) => {
+                let unchanged = r.unchanged.iter().map(|f| {
+                    ItemOutcome::file(&f.file_path, ItemStatus::Unchanged, None)
+                        .with_asset(&f.asset_id)
+                });
+                let planned: Vec<ItemOutcome> = if r.dry_run {
+                    let uploads = r
+                        .to_upload
+                        .iter()
+                        .map(|path| ItemOutcome::file(path, ItemStatus::Planned, None));
+                    let deletes = r
+                        .to_delete
+                        .iter()
+                        .map(|a| ItemOutcome::asset(&a.asset_id, ItemStatus::Planned, None));
+                    uploads.chain(deletes).collect()
+                } else {
+                    Vec::new()
+                };
+                let uploaded = r.uploaded.iter().map(ItemOutcome::uploaded);
+                let deleted = r
+                    .deleted
+                    .iter()
+                    .map(|id| ItemOutcome::asset(id, ItemStatus::Deleted, None));
+                let failed = r.failed.iter().map(|f| match f.action {
+                    SyncAction::Upload => {
+                        ItemOutcome::file(&f.target, ItemStatus::Failed, Some(&f.error))
+                    }
+                    SyncAction::Delete => {
+                        ItemOutcome::asset(&f.target, ItemStatus::Failed, Some(&f.error))
+                    }
+                });
+                unchanged
+                    .chain(planned)
+                    .chain(uploaded)
+                    .chain(deleted)
+                    .chain(failed)
+                    .collect()
+            }
+            Self::Backup(r) => {
+                let downloaded = r.downloaded.iter().map(|f| {
+                    ItemOutcome::file(&f.path, ItemStatus::Downloaded, None).with_asset(&f.asset_id)
+                });
+                let up_to_date = r.up_to_date.iter().map(|f| {
+                    ItemOutcome::file(&f.path, ItemStatus::Unchanged, None).with_asset(&f.asset_id)
+                });
+                let signed_only = r
+                    .signed_only
+                    .iter()
+                    .map(|id| ItemOutcome::asset(id, ItemStatus::Skipped, None));
+                let failed = r.failed.iter().map(|f| {
+                    ItemOutcome::file(&f.path, ItemStatus::Failed, Some(&f.error))
+                        .with_asset(&f.asset_id)
+                });
+                downloaded
+                    .chain(up_to_date)
+                    .chain(signed_only)
+                    .chain(failed)
+                    .collect()
+            }
+        }
+    }
+}
+
+/// ログインコマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct LoginResult {
     /// 既にログイン済みだったか（上書き更新の場合true）
     pub was_logged_in: bool,
 }
 
 /// ログアウトコマンドの結果
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct LogoutResult {
     /// ログイン状態だったか
     pub was_logged_in: bool,
 }
 
 /// ステータスコマンドの結果
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct StatusResult {
     /// 認証が通っているか
     pub is_authenticated: bool,
     /// マスキングされたToken ID（認証情報がある場合）
     pub token_id: Option<String>,
+    /// トークンが属する環境の名前（認証済みで取得できた場合）
+    pub environment: Option<String>,
+    /// 現在のアセット数（認証済みで取得できた場合）
+    pub asset_count: Option<usize>,
+    /// プランのアセット数上限（config.tomlの`asset_limit`）
+    pub asset_limit: Option<usize>,
+    /// 上限まであと何件アップロードできるか（アセット数と上限が分かる場合）
+    pub remaining_capacity: Option<usize>,
+    /// 上限に達したときの動作（config.tomlの`capacity_policy`）
+    pub capacity_policy: CapacityPolicy,
+    /// 警告（アセット数・環境を取得できなかった場合）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+impl StatusResult {
+    /// 認証が通らなかった（または認証情報がない）場合の結果を作る
+    pub fn unauthenticated(
+        token_id: Option<String>,
+        asset_limit: Option<usize>,
+        capacity_policy: CapacityPolicy,
+    ) -> Self {
+        Self {
+            is_authenticated: false,
+            token_id,
+            environment: None,
+            asset_count: None,
+            asset_limit,
+            remaining_capacity: None,
+            capacity_policy,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// アセット数と上限から残りの容量を計算して結果を作る
+    pub fn authenticated(
+        token_id: String,
+        environment: Option<String>,
+        asset_count: Option<usize>,
+        asset_limit: Option<usize>,
+        capacity_policy: CapacityPolicy,
+        warnings: Vec<Warning>,
+    ) -> Self {
+        let remaining_capacity = asset_count
+            .zip(asset_limit)
+            .map(|(count, limit)| limit.saturating_sub(count));
+        Self {
+            is_authenticated: true,
+            token_id: Some(token_id),
+            environment,
+            asset_count,
+            asset_limit,
+            remaining_capacity,
+            capacity_policy,
+            warnings,
+        }
+    }
+
+    /// 次のアップロードで上限に達するか（上限ちょうどの場合を含む）
+    pub fn is_at_capacity(&self) -> bool {
+        self.remaining_capacity == Some(0)
+    }
 }
 
 /// アップロードコマンドの結果
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct UploadResult {
     /// アセットID
     pub asset_id: String,
     /// 再生ID（HLS/MP4のURL構築に使用）
     pub playback_id: Option<String>,
+    /// 再生IDのポリシー（public, signed）。signedの場合、URLは署名付き（`[signing]` 設定時のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub playback_policy: Option<String>,
     /// HLS再生URL（すぐに利用可能）
     pub hls_url: Option<String>,
-    /// MP4再生URL（生成完了時のみ）
+    /// MP4再生URL（生成中の場合は `--mp4` で選んだファイル名の予測URL、`--mp4 none` の場合はNone）
     pub mp4_url: Option<String>,
-    /// MP4のステータス（ready, generating）
+    /// MP4のステータス（ready, generating, disabled）
     pub mp4_status: Mp4Status,
+    /// サムネイル画像のURL（再生時間の10%の位置。再生時間が分からない場合はMuxの既定の位置）
+    pub thumbnail_url: Option<String>,
     /// ファイルパス
     pub file_path: String,
     /// ファイルサイズ（bytes）
@@ -62,20 +454,123 @@ pub struct UploadResult {
     pub file_format: String,
     /// 削除した古い動画の数
     pub deleted_old_videos: usize,
+    /// 容量確保のために削除した古いアセット（削除した場合のみ）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deleted_assets: Vec<DeletedAsset>,
+    /// メディア解析結果（`--probe`指定時かつffprobeが利用できた場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media: Option<crate::media::probe::MediaProbe>,
+    /// 警告（メディア解析の結果、MP4の生成中、古いアセットの自動削除など）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+    /// 元ファイルのSHA-256（アセットのpassthroughにも記録）
+    pub sha256: String,
+    /// アップロード完了日時（RFC 3339、UTC）
+    pub uploaded_at: String,
+    /// 段階ごとの所要時間
+    pub timings: UploadTimings,
+    /// Muxが処理した最終的なメディア情報（アセットが `ready` の場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub final_media: Option<FinalMedia>,
+    /// クリップボードにコピーしたURL（`--copy` でコピーできた場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copied: Option<CopiedUrl>,
+}
+
+/// アセットが `ready` になった時点のメディア情報
+///
+/// アップロード直後に `show` を呼ばなくても、Muxが処理した結果を確認できるようにします。
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct FinalMedia {
+    /// 再生時間（秒）
+    pub duration: Option<f64>,
+    /// 解像度ティア（1080p, 720pなど）
+    pub resolution_tier: Option<String>,
+    /// アスペクト比（例: "16:9"）
+    pub aspect_ratio: Option<String>,
+    /// トラックの要約
+    pub tracks: Vec<TrackSummary>,
+}
+
+/// トラックの要約
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct TrackSummary {
+    /// トラックの種類（video, audioなど）
+    #[serde(rename = "type")]
+    pub track_type: String,
+    /// 幅（videoトラックのみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    /// 高さ（videoトラックのみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// フレームレート（videoトラックのみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frame_rate: Option<f64>,
+    /// チャンネルレイアウト（audioトラックのみ、例: stereo）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_layout: Option<String>,
+}
+
+/// アップロードの段階ごとの所要時間（秒、ミリ秒単位に丸める）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, JsonSchema)]
+pub struct UploadTimings {
+    /// ファイル検証・メディア解析
+    pub validate_secs: f64,
+    /// Direct Uploadの作成（容量確保のための削除を含む）
+    pub create_upload_secs: f64,
+    /// ファイルの転送
+    pub upload_secs: f64,
+    /// アセット作成の待機（`--wait-ready` の場合は `ready` になるまで）
+    pub wait_secs: f64,
+    /// 全体
+    pub total_secs: f64,
+    /// 実効転送速度（Mbps）
+    pub throughput_mbps: f64,
+}
+
+/// 容量確保のために削除したアセット
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct DeletedAsset {
+    /// アセットID
+    pub asset_id: String,
+    /// タイトル
+    pub title: Option<String>,
+    /// 作成日時（Unix timestamp）
+    pub created_at: String,
+}
+
+/// アップロードの見積もり結果（`upload --dry-run`）
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct UploadEstimateResult {
+    /// ファイルパス
+    pub file_path: String,
+    /// ファイル形式（拡張子）
+    pub file_format: String,
+    /// 所要時間・エンコード量の見積もり
+    pub estimate: crate::domain::estimate::UploadEstimate,
+    /// メディア解析結果（`--probe`指定時かつffprobeが利用できた場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media: Option<crate::media::probe::MediaProbe>,
+    /// アップロード前の警告（メディア解析時のみ）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
 }
 
 /// MP4の生成ステータス
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Mp4Status {
     /// すぐに利用可能
     Ready,
     /// バックグラウンドで生成中
     Generating,
+    /// 生成しない（`upload --mp4 none`）
+    Disabled,
 }
 
 /// リストコマンドの結果
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct ListResult {
     /// 動画リスト（人間向け簡略版）
     pub videos: Vec<VideoInfo>,
@@ -84,10 +579,172 @@ pub struct ListResult {
     /// 完全なAPIレスポンスデータ（機械向け、--machineフラグ時のみ）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub raw_assets: Option<Vec<crate::api::types::AssetData>>,
+    /// ローカルインデックスの最終同期日時（Unix timestamp、--offline時のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub synced_at: Option<String>,
+    /// グループ化の単位（`--group-by` 指定時のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_by: Option<GroupBy>,
+    /// グループ（`--group-by` 指定時のみ、`videos` の順で最初に現れた順）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<VideoGroup>,
+    /// 警告（署名キーがなく署名付き再生URLを出力できない場合など）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+/// 一覧のグループ化の単位（`list --group-by`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    /// 作成日（`timezone_offset_seconds` のタイムゾーン）
+    Day,
+    /// 作成月（`timezone_offset_seconds` のタイムゾーン）
+    Month,
+    /// ステータス
+    Status,
+}
+
+impl GroupBy {
+    /// 指定できる値（`--group-by`・エラーメッセージ用）
+    pub const NAMES: &'static [&'static str] = &["day", "month", "status"];
+
+    /// `day` / `month` / `status` を解析する
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "day" => Some(Self::Day),
+            "month" => Some(Self::Month),
+            "status" => Some(Self::Status),
+            _ => None,
+        }
+    }
+
+    /// `--group-by` に指定する名前
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Month => "month",
+            Self::Status => "status",
+        }
+    }
+
+    /// 動画の属するグループのキー（作成日時を解析できない場合は `unknown`）
+    fn key(self, video: &VideoInfo, offset_seconds: i32) -> String {
+        let pattern = match self {
+            Self::Day => "%Y-%m-%d",
+            Self::Month => "%Y-%m",
+            Self::Status => return video.status.clone(),
+        };
+        crate::domain::formatter::format_date(&video.created_at, offset_seconds, pattern)
+            .unwrap_or_else(|| UNKNOWN_GROUP.to_string())
+    }
+}
+
+/// 一覧の集計（`list` のフッター・機械可読出力の `summary`）
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct ListSummary {
+    /// ステータスごとの動画数
+    pub status_counts: BTreeMap<String, usize>,
+    /// 動画時間の合計（秒、時間が不明な動画は含まない）
+    pub total_duration: f64,
+    /// Static Renditionsの合計サイズ（bytes、サイズの分かる動画がない場合はNone）
+    ///
+    /// 元の動画・HLS用のセグメントは含まないため、実際の保存容量の目安です。
+    pub approx_storage_bytes: Option<u64>,
+}
+
+/// 作成日時を解析できない動画のグループのキー
+pub const UNKNOWN_GROUP: &str = "unknown";
+
+/// 一覧のグループ（`list --group-by`）
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct VideoGroup {
+    /// グループのキー（例: `"2025-11-30"`・`"2025-11"`・`"ready"`）
+    pub key: String,
+    /// グループの動画数
+    pub count: usize,
+    /// グループの動画時間の合計（秒、時間が不明な動画は含まない）
+    pub total_duration: f64,
+    /// グループのアセットID（`videos` の順）
+    pub asset_ids: Vec<String>,
+}
+
+impl ListResult {
+    /// 一覧の動画を集計する（絞り込み後の `videos` が対象）
+    pub fn summary(&self) -> ListSummary {
+        let mut status_counts = BTreeMap::new();
+        for video in &self.videos {
+            *status_counts.entry(video.status.clone()).or_insert(0) += 1;
+        }
+        let sizes: Vec<u64> = self
+            .videos
+            .iter()
+            .filter_map(|video| video.storage_bytes)
+            .collect();
+
+        ListSummary {
+            status_counts,
+            total_duration: self.videos.iter().filter_map(|video| video.duration).sum(),
+            approx_storage_bytes: (!sizes.is_empty()).then(|| sizes.iter().sum()),
+        }
+    }
+
+    /// 動画をグループに分ける（`--group-by`）
+    ///
+    /// グループは `videos` の順で最初に現れた順に並びます（一覧は作成日時の新しい順のため、
+    /// `day` / `month` は新しい日付から並びます）。
+    ///
+    /// # 引数
+    /// * `group_by` - グループ化の単位
+    /// * `offset_seconds` - 日付の判定に使うタイムゾーンオフセット（秒）
+    pub fn group(&mut self, group_by: GroupBy, offset_seconds: i32) {
+        let mut groups: Vec<VideoGroup> = Vec::new();
+        for video in &self.videos {
+            let key = group_by.key(video, offset_seconds);
+            let index = match groups.iter().position(|group| group.key == key) {
+                Some(index) => index,
+                None => {
+                    groups.push(VideoGroup {
+                        key,
+                        count: 0,
+                        total_duration: 0.0,
+                        asset_ids: Vec::new(),
+                    });
+                    groups.len() - 1
+                }
+            };
+            let group = &mut groups[index];
+            group.count += 1;
+            group.total_duration += video.duration.unwrap_or(0.0);
+            group.asset_ids.push(video.asset_id.clone());
+        }
+
+        self.group_by = Some(group_by);
+        self.groups = groups;
+    }
+
+    /// タイトルのあるアセットのみを残す（`--title-only`）
+    pub fn retain_titled(&mut self) {
+        self.videos.retain(|video| video.title.is_some());
+        if let Some(raw_assets) = &mut self.raw_assets {
+            raw_assets.retain(|asset| asset.title().is_some());
+        }
+        self.total_count = self.videos.len();
+    }
+
+    /// 指定したタグの付いたアセットのみを残す（`--tag`）
+    pub fn retain_tagged(&mut self, tag: &str) {
+        let tag = tag.trim().to_lowercase();
+        self.videos.retain(|video| video.tags.contains(&tag));
+        if let Some(raw_assets) = &mut self.raw_assets {
+            raw_assets.retain(|asset| asset.tags().contains(&tag));
+        }
+        self.total_count = self.videos.len();
+    }
 }
 
 /// アセット詳細表示コマンドの結果
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct ShowResult {
     /// アセットID
     pub asset_id: String,
@@ -107,31 +764,992 @@ pub struct ShowResult {
     pub hls_url: Option<String>,
     /// MP4再生URL
     pub mp4_url: Option<String>,
+    /// サムネイル画像のURL（再生時間の10%の位置、`--thumbnail-width` / `--thumbnail-height` の大きさ）
+    pub thumbnail_url: Option<String>,
     /// 動画トラック情報
     pub tracks: Option<Vec<crate::api::types::Track>>,
     /// Static Renditions（MP4など）
     pub static_renditions: Option<crate::api::types::StaticRenditionsWrapper>,
+    /// 画像の署名付きURL（再生ポリシーがsignedで、`[signing]` を設定した場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_image_urls: Option<SignedImageUrls>,
     /// 完全なAPIレスポンスデータ（機械向け、--machineフラグ時のみ）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub raw_asset: Option<crate::api::types::AssetData>,
+    /// 警告（署名キーがなく署名付き再生URLを出力できない場合など）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+    /// クリップボードにコピーしたURL（`--copy` でコピーできた場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copied: Option<CopiedUrl>,
+}
+
+impl ShowResult {
+    /// サムネイル画像のURL（署名付きの場合は `signed_image_urls` のURL）
+    pub fn thumbnail(&self) -> Option<&str> {
+        self.thumbnail_url.as_deref().or(self
+            .signed_image_urls
+            .as_ref()
+            .map(|images| images.thumbnail_url.as_str()))
+    }
+
+    /// アセットのタイトル（`meta.title`、完全データがない場合はNone）
+    pub fn title(&self) -> Option<&str> {
+        self.raw_asset.as_ref().and_then(|asset| asset.title())
+    }
+}
+
+/// クリップボードにコピーするURL（`upload` / `show` の `--copy`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CopyTarget {
+    /// HLS再生URL（既定）
+    #[default]
+    Hls,
+    /// MP4再生URL
+    Mp4,
+    /// サムネイル画像のURL
+    Thumbnail,
+}
+
+impl CopyTarget {
+    /// 指定できる値（`--copy`・エラーメッセージ用）
+    pub const NAMES: &'static [&'static str] = &["hls", "mp4", "thumbnail"];
+
+    /// `hls` / `mp4` / `thumbnail` を解析する
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "hls" => Some(Self::Hls),
+            "mp4" => Some(Self::Mp4),
+            "thumbnail" => Some(Self::Thumbnail),
+            _ => None,
+        }
+    }
+
+    /// `--copy` に指定する名前
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Hls => "hls",
+            Self::Mp4 => "mp4",
+            Self::Thumbnail => "thumbnail",
+        }
+    }
+}
+
+/// クリップボードにコピーしたURL
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct CopiedUrl {
+    /// コピーしたURLの種類
+    pub target: CopyTarget,
+    /// コピーしたURL（署名付きの場合は署名後のURL）
+    pub url: String,
+}
+
+/// 署名付きURL発行コマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SignResult {
+    /// 再生ID
+    pub playback_id: String,
+    /// URLの種類（video, thumbnail, storyboard, gif）
+    pub url_type: crate::api::signing::Audience,
+    /// 署名付きURL
+    pub url: String,
+    /// 有効期限（Unix timestamp）
+    pub expires_at: String,
+    /// トークンに含めた再生制限のID（`--restriction` または config.tomlの `[signing]`）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub playback_restriction_id: Option<String>,
+}
+
+/// ストーリーボード（`storyboard`）の結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct StoryboardResult {
+    /// アセットID
+    pub asset_id: String,
+    /// 再生ID
+    pub playback_id: String,
+    /// 再生IDのポリシー（public, signed）
+    pub playback_policy: String,
+    /// WebVTT（各時刻のプレビューがスプライト画像のどの範囲かを `#xywh=` で示す）のURL
+    pub vtt_url: String,
+    /// スプライト画像（プレビューを格子状に並べた1枚の画像）のURL
+    pub sprite_url: String,
+    /// 署名付きURLの有効期限（Unix timestamp、再生ポリシーがsignedの場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    /// ダウンロードしたファイル（`--download` 指定時のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download: Option<StoryboardDownload>,
+}
+
+/// `storyboard --download` で保存したファイル
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct StoryboardDownload {
+    /// 保存したVTTのパス（スプライト画像の参照は同じディレクトリのファイル名に書き換え済み）
+    pub vtt_path: String,
+    /// 保存したスプライト画像のパス
+    pub sprite_path: String,
+    /// スプライト画像のサイズ（bytes）
+    pub sprite_bytes: u64,
+    /// VTTのプレビューの数（キューの数）
+    pub tiles: usize,
+}
+
+/// 再生制限作成（`restrictions create`）の結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RestrictionsCreateResult {
+    /// 作成した再生制限
+    pub restriction: crate::api::types::PlaybackRestriction,
+}
+
+/// 再生制限一覧（`restrictions list`）の結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RestrictionsListResult {
+    /// 再生制限
+    pub restrictions: Vec<crate::api::types::PlaybackRestriction>,
+    /// 再生制限の総数
+    pub total_count: usize,
+}
+
+/// 再生制限削除（`restrictions delete`）の結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RestrictionsDeleteResult {
+    /// 削除した再生制限のID
+    pub restriction_id: String,
+}
+
+/// 再生制限用の再生ID追加（`restrictions attach`）の結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RestrictionsAttachResult {
+    /// 対象のアセットID
+    pub asset_id: String,
+    /// 追加した再生ID
+    pub playback_id: String,
+    /// 追加した再生IDのポリシー（常に `signed`）
+    pub policy: String,
+    /// 適用する再生制限のID（URLの署名時に `sign --restriction` で指定する）
+    pub restriction_id: String,
+    /// 再生制限で許可されているドメイン
+    pub allowed_domains: Vec<String>,
+}
+
+/// 再生IDの削除（`playback revoke`）の結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PlaybackRevokeResult {
+    /// 対象のアセットID
+    pub asset_id: String,
+    /// 削除した再生ID
+    pub revoked: crate::api::types::PlaybackId,
+    /// 代わりに追加した同じポリシーの再生ID（`--replace` 指定時のみ）
+    pub replacement: Option<crate::api::types::PlaybackId>,
+    /// 追加した再生IDのHLS URL（`signed` の場合は署名付き、署名キーがなければnull）
+    pub hls_url: Option<String>,
+    /// 削除後にアセットに残っている再生ID
+    pub remaining_playback_ids: Vec<crate::api::types::PlaybackId>,
+    /// 警告（再生IDが残っていない場合など）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+/// ローカルのプレビュープレーヤー（`serve`）の結果（サーバーを停止した後に出力）
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ServeResult {
+    /// アセットID
+    pub asset_id: String,
+    /// プレビューページのURL（例: `http://127.0.0.1:49152/`）
+    pub url: String,
+    /// 再生に使った再生ID
+    pub playback_id: String,
+    /// 再生ポリシー（public, signed）
+    pub playback_policy: String,
+    /// ページが再生するHLS URL（`signed` の場合は署名付き）
+    pub hls_url: String,
+    /// 署名付きURLの有効期限（Unix timestamp、`signed` の場合のみ）
+    pub expires_at: Option<String>,
+    /// ページを配信した回数
+    pub page_views: u64,
+}
+
+/// ストリームキーの再発行（`live reset-key`）の結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct LiveResetKeyResult {
+    /// ライブストリームID
+    pub live_stream_id: String,
+    /// ライブストリームのステータス（idle, active, disabled）
+    pub status: String,
+    /// 新しいストリームキー（人間向けの出力では伏せ字）
+    pub stream_key: String,
+}
+
+/// 再生IDの画像の署名付きURL（image.mux.com）
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SignedImageUrls {
+    /// サムネイル画像（`aud: t`）
+    pub thumbnail_url: String,
+    /// ストーリーボード（WebVTT、`aud: s`）
+    pub storyboard_url: String,
+    /// アニメーションGIF（`aud: g`）
+    pub gif_url: String,
 }
 
 /// 削除コマンドの結果
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct DeleteResult {
     /// 削除されたアセットID
     pub asset_id: String,
 }
 
+/// リネームコマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RenameResult {
+    /// アセットID
+    pub asset_id: String,
+    /// 変更前のタイトル（未設定の場合はnull）
+    pub old_title: Option<String>,
+    /// 変更後のタイトル
+    pub new_title: String,
+}
+
+/// タグコマンドの操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TagAction {
+    /// タグを追加（`tag add`）
+    Add,
+    /// タグを削除（`tag remove`）
+    Remove,
+    /// タグを表示（`tag list`）
+    List,
+}
+
+/// タグコマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct TagResult {
+    /// アセットID
+    pub asset_id: String,
+    /// 実行した操作
+    pub action: TagAction,
+    /// 追加・削除したタグ（正規化後。`list` の場合はnull）
+    pub tag: Option<String>,
+    /// タグが変更されたか（既にある・もともとない場合はfalse）
+    pub changed: bool,
+    /// 操作後のタグ
+    pub tags: Vec<String>,
+}
+
+/// Static Rendition管理コマンドの操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RenditionAction {
+    /// Static Renditionを追加（`renditions create`）
+    Create,
+    /// Static Renditionを削除（`renditions delete`）
+    Delete,
+}
+
+/// Static Rendition管理コマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RenditionsResult {
+    /// アセットID
+    pub asset_id: String,
+    /// 実行した操作
+    pub action: RenditionAction,
+    /// 追加・削除したか（同じ解像度が既にあり追加しなかった場合はfalse）
+    pub changed: bool,
+    /// 対象のStatic Rendition（追加時は待機後の状態、削除時は削除前の状態）
+    pub rendition: crate::api::types::StaticRendition,
+    /// MP4のURL（`ready` の場合のみ。`signed` の再生IDは署名付き、署名キーがなければnull）
+    pub mp4_url: Option<String>,
+    /// 警告（`--no-wait` でMP4がまだ生成中の場合など）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+/// 字幕コマンドの操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptionAction {
+    /// 字幕ファイルのURLから追加（`captions add`）
+    Add,
+    /// オーディオトラックから自動生成（`captions generate`）
+    Generate,
+}
+
+/// 字幕コマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CaptionsResult {
+    /// アセットID
+    pub asset_id: String,
+    /// 実行した操作
+    pub action: CaptionAction,
+    /// 字幕の生成元のオーディオトラックID（`generate` のみ）
+    pub source_track_id: Option<String>,
+    /// 追加した字幕トラック（`generate` では生成中の `preparing`）
+    pub tracks: Vec<crate::api::types::Track>,
+}
+
+/// 待機コマンドで待つ状態（`--until`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WaitUntil {
+    /// アセットが作成された（Direct UploadのIDを指定した場合のみ待機が発生）
+    Created,
+    /// アセットが `ready`（HLSで再生可能）
+    #[default]
+    Ready,
+    /// `ready` のMP4（Static Rendition）がある
+    Mp4Ready,
+}
+
+impl WaitUntil {
+    /// 指定できる値（`--until`・エラーメッセージ用）
+    pub const NAMES: &'static [&'static str] = &["created", "ready", "mp4-ready"];
+
+    /// `created` / `ready` / `mp4-ready` を解析する（`mp4_ready` も受け付ける）
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "created" => Some(Self::Created),
+            "ready" => Some(Self::Ready),
+            "mp4-ready" => Some(Self::Mp4Ready),
+            _ => None,
+        }
+    }
+
+    /// `--until` に指定する名前
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Ready => "ready",
+            Self::Mp4Ready => "mp4-ready",
+        }
+    }
+}
+
+/// 待機コマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct WaitResult {
+    /// アセットID
+    pub asset_id: String,
+    /// 指定したDirect UploadのID（アセットIDを指定した場合はnull）
+    pub upload_id: Option<String>,
+    /// 待機した状態
+    pub until: WaitUntil,
+    /// 待機後のアセットのステータス
+    pub status: String,
+    /// 待機にかかった時間（秒）
+    pub elapsed_secs: u64,
+    /// 再生ID
+    pub playback_id: Option<String>,
+    /// 再生ポリシー（`public` / `signed`）
+    pub playback_policy: Option<String>,
+    /// HLSのURL（`signed` の再生IDは署名付き、署名キーがなければnull）
+    pub hls_url: Option<String>,
+    /// MP4のURL（`--until mp4-ready` の場合のみ。`signed` の再生IDは署名付き、署名キーがなければnull）
+    pub mp4_url: Option<String>,
+    /// 警告（署名キーがなく再生URLを出力できない場合など）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+/// エクスポートコマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ExportResult {
+    /// 出力先のパス
+    pub output_path: String,
+    /// 出力形式
+    pub format: ExportFormat,
+    /// 書き出したアセット数
+    pub asset_count: usize,
+    /// チェックポイントから再開した場合の、前回までに取得済みのアセット数（再開しなかった場合は0）
+    pub resumed_assets: usize,
+}
+
+/// インポートコマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ImportResult {
+    /// 読み込んだファイルのパス
+    pub source_path: String,
+    /// 作成したアセット（ファイル内の順）
+    pub imported: Vec<ImportedAsset>,
+    /// 作成できなかったアセット
+    pub failed: Vec<ImportFailure>,
+}
+
+impl ImportResult {
+    /// 全体の結果
+    pub fn status(&self) -> BulkStatus {
+        BulkStatus::from_counts(self.imported.len(), self.failed.len())
+    }
+
+    /// 元のアセットIDから新しいアセットIDへの対応
+    pub fn id_map(&self) -> BTreeMap<String, String> {
+        self.imported
+            .iter()
+            .map(|asset| (asset.old_asset_id.clone(), asset.new_asset_id.clone()))
+            .collect()
+    }
+}
+
+/// インポートで作成したアセット
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ImportedAsset {
+    /// 元のアセットID
+    pub old_asset_id: String,
+    /// 作成したアセットID
+    pub new_asset_id: String,
+    /// 取り込み元のURL（`mux://assets/<id>` または公開MP4のURL）
+    pub input_url: String,
+    /// タイトル（元のアセットに設定されていた場合）
+    pub title: Option<String>,
+}
+
+/// インポートで作成できなかったアセット
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ImportFailure {
+    /// 元のアセットID
+    pub old_asset_id: String,
+    /// エラーメッセージ
+    pub error: String,
+}
+
+/// 同期コマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SyncResult {
+    /// 同期したディレクトリ
+    pub directory: String,
+    /// 計画のみで、アップロード・削除を行わなかったか（`--dry-run`）
+    pub dry_run: bool,
+    /// アップロード済みのため何もしないファイル
+    pub unchanged: Vec<SyncedFile>,
+    /// アップロードする（した）ファイル
+    pub to_upload: Vec<String>,
+    /// 削除する（した）アセット（`--delete` 指定時のみ）
+    pub to_delete: Vec<SyncRemoteAsset>,
+    /// アップロードに成功したファイル
+    pub uploaded: Vec<UploadResult>,
+    /// 削除したアセットID
+    pub deleted: Vec<String>,
+    /// 失敗したアップロード・削除
+    pub failed: Vec<SyncFailure>,
+}
+
+/// 同期でアセットと一致したローカルファイル
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct SyncedFile {
+    /// ファイルパス
+    pub file_path: String,
+    /// 一致したアセットID
+    pub asset_id: String,
+}
+
+/// 同期で削除するアセット（対応するローカルファイルがないもの）
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct SyncRemoteAsset {
+    /// アセットID
+    pub asset_id: String,
+    /// タイトル
+    pub title: Option<String>,
+}
+
+/// 同期の操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncAction {
+    /// ファイルのアップロード
+    Upload,
+    /// アセットの削除
+    Delete,
+}
+
+/// 同期で失敗した操作
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SyncFailure {
+    /// 操作
+    pub action: SyncAction,
+    /// 対象（アップロードはファイルパス、削除はアセットID）
+    pub target: String,
+    /// エラーメッセージ
+    pub error: String,
+}
+
+/// バックアップコマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BackupResult {
+    /// 出力先ディレクトリ
+    pub output_dir: String,
+    /// ダウンロードしたファイル
+    pub downloaded: Vec<BackupFile>,
+    /// サイズ・チェックサムが一致したためダウンロードしなかったファイル
+    pub up_to_date: Vec<BackupFile>,
+    /// ready状態のMP4はあるが、公開の再生IDがないためダウンロードできなかったアセットID
+    pub signed_only: Vec<String>,
+    /// ダウンロードに失敗したファイル
+    pub failed: Vec<BackupFailure>,
+    /// ダウンロードした合計サイズ（bytes）
+    pub downloaded_bytes: u64,
+}
+
+/// バックアップしたファイル
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BackupFile {
+    /// アセットID
+    pub asset_id: String,
+    /// 保存先のパス
+    pub path: String,
+    /// ファイルサイズ（bytes）
+    pub size: u64,
+}
+
+/// バックアップで失敗したファイル
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BackupFailure {
+    /// アセットID
+    pub asset_id: String,
+    /// 保存先のパス
+    pub path: String,
+    /// エラーメッセージ
+    pub error: String,
+}
+
+/// 掃除（gc）コマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct GcResult {
+    /// 計画のみで、削除・取り消しを行わなかったか（`--dry-run`）
+    pub dry_run: bool,
+    /// 削除する（した）エラー状態のアセット
+    pub errored_assets: Vec<GcAsset>,
+    /// 取り消す（した）、ファイルを受け取っていないDirect UploadのID
+    pub stale_uploads: Vec<String>,
+    /// 削除したアセットID
+    pub deleted_assets: Vec<String>,
+    /// 取り消したDirect UploadのID
+    pub cancelled_uploads: Vec<String>,
+    /// 失敗した削除・取り消し
+    pub failed: Vec<GcFailure>,
+}
+
+impl GcResult {
+    /// 全体の結果（`--dry-run` の場合は常に成功）
+    pub fn status(&self) -> BulkStatus {
+        BulkStatus::from_counts(
+            self.deleted_assets.len() + self.cancelled_uploads.len(),
+            self.failed.len(),
+        )
+    }
+}
+
+/// 掃除の対象のアセット
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct GcAsset {
+    /// アセットID
+    pub asset_id: String,
+    /// タイトル
+    pub title: Option<String>,
+    /// 作成日時（Unix timestamp）
+    pub created_at: String,
+}
+
+/// 掃除で失敗した削除・取り消し
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct GcFailure {
+    /// 対象（アセットIDまたはDirect UploadのID）
+    pub target: String,
+    /// エラーメッセージ
+    pub error: String,
+}
+
+/// 保持数による削除（prune）コマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PruneResult {
+    /// 残すアセットの数（`--keep`）
+    pub keep: usize,
+    /// 計画のみで、削除を行わなかったか（`--dry-run`）
+    pub dry_run: bool,
+    /// 実行前のアセットの総数
+    pub total_assets: usize,
+    /// 削除する（した）アセット（古い順）
+    pub to_delete: Vec<PrunedAsset>,
+    /// 削除したアセットID
+    pub deleted: Vec<String>,
+    /// 削除に失敗したアセット
+    pub failed: Vec<PruneFailure>,
+}
+
+/// 保持数を超えたため削除するアセット
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct PrunedAsset {
+    /// アセットID
+    pub asset_id: String,
+    /// タイトル
+    pub title: Option<String>,
+    /// 作成日時（Unix timestamp）
+    pub created_at: String,
+}
+
+/// 削除に失敗したアセット
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PruneFailure {
+    /// アセットID
+    pub asset_id: String,
+    /// エラーメッセージ
+    pub error: String,
+}
+
+/// 操作履歴コマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct HistoryResult {
+    /// 履歴（新しい順、`--limit` 件まで）
+    pub entries: Vec<crate::storage::history::HistoryEntry>,
+    /// 記録されている履歴の総数
+    pub total_count: usize,
+}
+
+/// キュー登録コマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct QueueAddResult {
+    /// キュー内のID
+    pub id: u64,
+    /// 登録したファイルの絶対パス
+    pub file_path: String,
+    /// 登録後の未実行件数
+    pub pending_count: usize,
+}
+
+/// キュー実行コマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct QueueRunResult {
+    /// アップロードに成功した項目
+    pub uploaded: Vec<UploadResult>,
+    /// 再試行しない失敗（キューから除去済み）
+    pub failed: Vec<QueueFailure>,
+    /// 一時的な失敗（ネットワーク断など）でキューに残った項目
+    pub remaining: Vec<crate::storage::upload_queue::QueuedUpload>,
+}
+
+impl QueueRunResult {
+    /// 全体の結果（キューに残った項目も失敗として数える）
+    pub fn status(&self) -> BulkStatus {
+        BulkStatus::from_counts(
+            self.uploaded.len(),
+            self.failed.len() + self.remaining.len(),
+        )
+    }
+
+    /// 項目ごとの結果（成功・失敗・キューに残った項目の順）
+    pub fn items(&self) -> Vec<ItemOutcome> {
+        let uploaded = self.uploaded.iter().map(ItemOutcome::uploaded);
+        let failed = self
+            .failed
+            .iter()
+            .map(|f| ItemOutcome::file(&f.file_path, ItemStatus::Failed, Some(&f.error)));
+        let remaining = self
+            .remaining
+            .iter()
+            .map(|q| ItemOutcome::file(&q.file_path, ItemStatus::Queued, q.last_error.as_deref()));
+        uploaded.chain(failed).chain(remaining).collect()
+    }
+}
+
+/// キュー実行で失敗した項目
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct QueueFailure {
+    /// キュー内のID
+    pub id: u64,
+    /// ファイルパス
+    pub file_path: String,
+    /// エラーメッセージ
+    pub error: String,
+}
+
+/// 失敗したアップロードの再実行（`retry --all` / `retry <id>`）の結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RetryResult {
+    /// アップロードに成功した項目（記録から除去済み）
+    pub uploaded: Vec<UploadResult>,
+    /// 再び失敗した項目（記録に残り、エラーを更新）
+    pub failed: Vec<RetryFailure>,
+    /// 実行後に記録に残っている件数
+    pub remaining_count: usize,
+}
+
+impl RetryResult {
+    /// 全体の結果
+    pub fn status(&self) -> BulkStatus {
+        BulkStatus::from_counts(self.uploaded.len(), self.failed.len())
+    }
+
+    /// 項目ごとの結果（成功・失敗の順）
+    pub fn items(&self) -> Vec<ItemOutcome> {
+        let uploaded = self.uploaded.iter().map(ItemOutcome::uploaded);
+        let failed = self
+            .failed
+            .iter()
+            .map(|f| ItemOutcome::file(&f.file_path, ItemStatus::Failed, Some(&f.error)));
+        uploaded.chain(failed).collect()
+    }
+}
+
+/// 再実行で再び失敗した項目
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RetryFailure {
+    /// 記録内のID
+    pub id: u64,
+    /// ファイルパス
+    pub file_path: String,
+    /// エラーメッセージ
+    pub error: String,
+}
+
+/// 失敗したアップロードの一覧（引数なしの `retry`）の結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RetryListResult {
+    /// 記録されている失敗（最初に失敗した順）
+    pub entries: Vec<crate::storage::failure_log::FailedUpload>,
+}
+
+/// 複数ファイルアップロードの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BatchUploadResult {
+    /// アップロードに成功した項目（指定順）
+    pub uploaded: Vec<UploadResult>,
+    /// 再試行しても成功しなかった項目（指定順）
+    pub failed: Vec<BatchUploadFailure>,
+    /// 同時実行数
+    pub concurrency: usize,
+}
+
+impl BatchUploadResult {
+    /// 項目ごとの結果（成功・失敗の順）
+    pub fn items(&self) -> Vec<ItemOutcome> {
+        let uploaded = self.uploaded.iter().map(ItemOutcome::uploaded);
+        let failed = self
+            .failed
+            .iter()
+            .map(|f| ItemOutcome::file(&f.file_path, ItemStatus::Failed, Some(&f.error)));
+        uploaded.chain(failed).collect()
+    }
+}
+
+/// 複数ファイルアップロードで失敗した項目
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BatchUploadFailure {
+    /// ファイルパス
+    pub file_path: String,
+    /// 最後に発生したエラーメッセージ
+    pub error: String,
+    /// 試行回数
+    pub attempts: u32,
+}
+
+/// バージョンコマンドの結果（ビルド時のメタデータ）
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct VersionResult {
+    /// バージョン（semver）
+    pub version: String,
+    /// ビルド元の git コミット（不明な場合は "unknown"）
+    pub git_commit: String,
+    /// ビルド日（UTC、YYYY-MM-DD）
+    pub build_date: String,
+    /// ターゲットトリプル
+    pub target: String,
+    /// 組み込まれたAPIエンドポイント
+    pub api_endpoint: String,
+    /// TLSバックエンド（"rustls" または "native-tls"）
+    pub tls_backend: String,
+}
+
+/// whoamiコマンドの結果（使用中の認証情報と設定）
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct WhoamiResult {
+    /// 使用中のプロファイル名
+    pub profile: String,
+    /// 認証情報が保存されているか（有効性は確認しない）
+    pub is_logged_in: bool,
+    /// マスクしたToken ID（未ログインの場合はnull）
+    pub token_id: Option<String>,
+    /// APIエンドポイント
+    pub api_endpoint: String,
+    /// 使用中の設定ファイルのパス
+    pub config_path: String,
+}
+
+/// 統計コマンドの結果（すべてのアセットの集計）
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct StatsResult {
+    /// アセットの総数
+    pub total_count: usize,
+    /// ステータスごとのアセット数
+    pub by_status: BTreeMap<String, usize>,
+    /// 品質（video_quality）ごとのアセット数（不明な場合は `unknown`）
+    pub by_quality: BTreeMap<String, usize>,
+    /// 解像度ティアごとのアセット数（不明な場合は `unknown`）
+    pub by_resolution: BTreeMap<String, usize>,
+    /// 再生時間の合計（秒、時間が不明なアセットは含まない）
+    pub total_duration: f64,
+    /// 再生時間の平均（秒、時間の分かるアセットがない場合はnull）
+    pub average_duration: Option<f64>,
+    /// 週ごとのアップロード数（古い順、アップロードのあった週のみ）
+    pub uploads_per_week: Vec<WeeklyUploads>,
+    /// 最も古いアセット（アセットがない場合はnull）
+    pub oldest: Option<StatsAsset>,
+    /// 最も新しいアセット（アセットがない場合はnull）
+    pub newest: Option<StatsAsset>,
+}
+
+/// 週ごとのアップロード数
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct WeeklyUploads {
+    /// ISO 8601の週（例: "2025-W48"、`timezone_offset_seconds` のタイムゾーン）
+    pub week: String,
+    /// その週に作成されたアセット数
+    pub count: usize,
+}
+
+/// 統計で示すアセット（最も古い・新しいアセット）
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct StatsAsset {
+    /// アセットID
+    pub asset_id: String,
+    /// タイトル（meta.title）
+    pub title: Option<String>,
+    /// 作成日時（Unix timestamp）
+    pub created_at: String,
+}
+
+/// 疎通確認コマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PingResult {
+    /// 接続先のホスト
+    pub host: String,
+    /// 計測に使ったリクエスト（例: "GET /video/v1/assets?limit=1"）
+    pub request: String,
+    /// 成功したリクエスト数
+    pub succeeded: usize,
+    /// 失敗したリクエスト数
+    pub failed: usize,
+    /// 成功したリクエストの最小レイテンシ（ミリ秒、成功がない場合はnull）
+    pub min_ms: Option<f64>,
+    /// 成功したリクエストの平均レイテンシ（ミリ秒）
+    pub avg_ms: Option<f64>,
+    /// 成功したリクエストの最大レイテンシ（ミリ秒）
+    pub max_ms: Option<f64>,
+    /// 各リクエストの結果（送信順）
+    pub attempts: Vec<PingAttempt>,
+}
+
+impl PingResult {
+    /// 各リクエストの結果から集計する
+    pub fn from_attempts(host: String, request: String, attempts: Vec<PingAttempt>) -> Self {
+        let latencies: Vec<f64> = attempts
+            .iter()
+            .filter(|attempt| attempt.error.is_none())
+            .map(|attempt| attempt.latency_ms)
+            .collect();
+        let succeeded = latencies.len();
+        let (min_ms, avg_ms, max_ms) = if latencies.is_empty() {
+            (None, None, None)
+        } else {
+            (
+                latencies.iter().copied().reduce(f64::min),
+                Some(latencies.iter().sum::<f64>() / succeeded as f64),
+                latencies.iter().copied().reduce(f64::max),
+            )
+        };
+
+        Self {
+            host,
+            request,
+            succeeded,
+            failed: attempts.len() - succeeded,
+            min_ms,
+            avg_ms,
+            max_ms,
+            attempts,
+        }
+    }
+
+    /// 全体の結果（失敗したリクエストがあれば一部失敗・全件失敗）
+    pub fn status(&self) -> BulkStatus {
+        BulkStatus::from_counts(self.succeeded, self.failed)
+    }
+}
+
+/// 疎通確認の個々のリクエストの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PingAttempt {
+    /// 送信順の番号（1始まり）
+    pub seq: u32,
+    /// レイテンシ（ミリ秒、レスポンスヘッダー受信まで。失敗時はエラーまでの時間）
+    pub latency_ms: f64,
+    /// HTTPステータスコード（レスポンスがない場合はnull）
+    pub status_code: Option<u16>,
+    /// エラーメッセージ（成功時は省略）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 診断コマンドの結果
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DoctorResult {
+    /// 実行したチェック（実行順）
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorResult {
+    /// 失敗したチェックがないか（警告・スキップは成功扱い）
+    pub fn passed(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|check| check.status != CheckStatus::Fail)
+    }
+}
+
+/// 診断の個々のチェック
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DoctorCheck {
+    /// チェックの識別子（config, api_dns, api_tls, stream_dns, stream_tls, credentials, clock, disk）
+    pub name: String,
+    /// 結果
+    pub status: CheckStatus,
+    /// 結果の詳細
+    pub message: String,
+    /// 対処方法（警告・失敗時のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// 診断チェックの結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// 問題なし
+    Pass,
+    /// 動作はするが注意が必要
+    Warn,
+    /// 問題あり
+    Fail,
+    /// 前提となるチェックが失敗したため未実行
+    Skip,
+}
+
 /// 動画情報
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct VideoInfo {
     /// アセットID
     pub asset_id: String,
+    /// タイトル（meta.title）
+    pub title: Option<String>,
+    /// 外部ID（meta.external_id）
+    pub external_id: Option<String>,
+    /// タグ（passthroughに記録したもの）
+    pub tags: Vec<String>,
     /// ステータス (preparing, ready, errored)
     pub status: String,
     /// 再生ID
     pub playback_id: Option<String>,
+    /// 再生IDのポリシー（public, signed）。signedの場合、URLは署名付き（`[signing]` 設定時のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub playback_policy: Option<String>,
     /// HLS再生URL
     pub hls_url: Option<String>,
     /// MP4再生URL
@@ -142,4 +1760,304 @@ pub struct VideoInfo {
     pub created_at: String,
     /// アスペクト比
     pub aspect_ratio: Option<String>,
+    /// Static Renditionsの合計サイズ（bytes、サイズの分かるレンディションがある場合のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_bytes: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_retain_titled() {
+        let video = |id: &str, title: Option<&str>| VideoInfo {
+            asset_id: id.to_string(),
+            title: title.map(str::to_string),
+            external_id: None,
+            tags: vec![id.to_string()],
+            status: "ready".to_string(),
+            playback_id: None,
+            playback_policy: None,
+            hls_url: None,
+            mp4_url: None,
+            duration: None,
+            created_at: "0".to_string(),
+            aspect_ratio: None,
+            storage_bytes: None,
+        };
+        let mut result = ListResult {
+            videos: vec![video("a", Some("Intro")), video("b", None)],
+            total_count: 2,
+            raw_assets: None,
+            synced_at: None,
+            group_by: None,
+            groups: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        result.retain_titled();
+        assert_eq!(result.total_count, 1);
+        assert_eq!(result.videos[0].asset_id, "a");
+
+        result.retain_tagged("B");
+        assert_eq!(result.total_count, 0);
+    }
+
+    #[test]
+    fn test_list_group() {
+        let video = |id: &str, created_at: &str, duration: Option<f64>| VideoInfo {
+            asset_id: id.to_string(),
+            title: None,
+            external_id: None,
+            tags: Vec::new(),
+            status: "ready".to_string(),
+            playback_id: None,
+            playback_policy: None,
+            hls_url: None,
+            mp4_url: None,
+            duration,
+            created_at: created_at.to_string(),
+            aspect_ratio: None,
+            storage_bytes: None,
+        };
+        // 1764434950 = 2025-11-29 16:49:10 UTC、1761955200 = 2025-11-01 00:00:00 UTC
+        let mut result = ListResult {
+            videos: vec![
+                video("a", "1764434950", Some(60.0)),
+                video("b", "1761955200", Some(30.5)),
+                video("c", "1764434950", None),
+                video("d", "bogus", Some(1.0)),
+            ],
+            total_count: 4,
+            raw_assets: None,
+            synced_at: None,
+            group_by: None,
+            groups: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        result.group(GroupBy::Day, 0);
+        assert_eq!(result.group_by, Some(GroupBy::Day));
+        let keys: Vec<&str> = result.groups.iter().map(|g| g.key.as_str()).collect();
+        assert_eq!(keys, ["2025-11-29", "2025-11-01", UNKNOWN_GROUP]);
+        assert_eq!(result.groups[0].count, 2);
+        assert_eq!(result.groups[0].asset_ids, ["a", "c"]);
+        assert_eq!(result.groups[0].total_duration, 60.0);
+
+        // JST（UTC+9）では 2025-11-29 16:49 UTC が翌日になる
+        result.group(GroupBy::Day, 32400);
+        assert_eq!(result.groups[0].key, "2025-11-30");
+
+        result.group(GroupBy::Month, 0);
+        assert_eq!(result.groups.len(), 2);
+        assert_eq!(result.groups[0].count, 3);
+        assert_eq!(result.groups[0].total_duration, 90.5);
+
+        result.group(GroupBy::Status, 0);
+        assert_eq!(result.groups.len(), 1);
+        assert_eq!(result.groups[0].key, "ready");
+
+        assert_eq!(GroupBy::parse(" Month "), Some(GroupBy::Month));
+        assert_eq!(GroupBy::parse("week"), None);
+    }
+
+    #[test]
+    fn test_list_summary() {
+        let video =
+            |id: &str, status: &str, duration: Option<f64>, storage: Option<u64>| VideoInfo {
+                asset_id: id.to_string(),
+                title: None,
+                external_id: None,
+                tags: Vec::new(),
+                status: status.to_string(),
+                playback_id: None,
+                playback_policy: None,
+                hls_url: None,
+                mp4_url: None,
+                duration,
+                created_at: "0".to_string(),
+                aspect_ratio: None,
+                storage_bytes: storage,
+            };
+        let mut result = ListResult {
+            videos: vec![
+                video("a", "ready", Some(60.0), Some(1000)),
+                video("b", "ready", Some(30.5), None),
+                video("c", "errored", None, Some(24)),
+            ],
+            total_count: 3,
+            raw_assets: None,
+            synced_at: None,
+            group_by: None,
+            groups: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        let summary = result.summary();
+        assert_eq!(summary.status_counts["ready"], 2);
+        assert_eq!(summary.status_counts["errored"], 1);
+        assert_eq!(summary.total_duration, 90.5);
+        assert_eq!(summary.approx_storage_bytes, Some(1024));
+
+        result.videos.retain(|video| video.storage_bytes.is_none());
+        assert_eq!(result.summary().approx_storage_bytes, None);
+    }
+
+    #[test]
+    fn test_bulk_status_from_counts() {
+        assert_eq!(BulkStatus::from_counts(2, 0), BulkStatus::Succeeded);
+        assert_eq!(BulkStatus::from_counts(0, 0), BulkStatus::Succeeded);
+        assert_eq!(BulkStatus::from_counts(1, 1), BulkStatus::PartialFailure);
+        assert_eq!(BulkStatus::from_counts(0, 2), BulkStatus::Failed);
+    }
+
+    #[test]
+    fn test_batch_upload_exit_code() {
+        let result = CommandResult::Batch(BatchResult::new(
+            BatchDetails::Upload(BatchUploadResult {
+                uploaded: Vec::new(),
+                failed: vec![BatchUploadFailure {
+                    file_path: "a.mp4".to_string(),
+                    error: "network".to_string(),
+                    attempts: 3,
+                }],
+                concurrency: 1,
+            }),
+            std::time::Duration::from_secs(2),
+        ));
+        assert_eq!(result.exit_code(), 1);
+
+        let CommandResult::Batch(batch) = &result else {
+            unreachable!()
+        };
+        assert_eq!((batch.succeeded, batch.failed), (0, 1));
+        assert_eq!(batch.duration, 2.0);
+        assert_eq!(batch.items.len(), 1);
+        assert_eq!(batch.items[0].status, ItemStatus::Failed);
+        assert_eq!(batch.items[0].error.as_deref(), Some("network"));
+
+        assert_eq!(BulkStatus::PartialFailure.exit_code(), 4);
+        assert_eq!(CommandResult::Help.exit_code(), 0);
+    }
+
+    #[test]
+    fn test_batch_result_items() {
+        let prune = BatchResult::new(
+            BatchDetails::Prune(PruneResult {
+                keep: 1,
+                dry_run: false,
+                total_assets: 3,
+                to_delete: Vec::new(),
+                deleted: vec!["a".to_string()],
+                failed: vec![PruneFailure {
+                    asset_id: "b".to_string(),
+                    error: "not found".to_string(),
+                }],
+            }),
+            std::time::Duration::ZERO,
+        );
+        assert_eq!(prune.status(), BulkStatus::PartialFailure);
+        assert_eq!(prune.items[0].status, ItemStatus::Deleted);
+        assert_eq!(prune.items[0].asset_id.as_deref(), Some("a"));
+        assert!(prune.items[0].file_path.is_none());
+        assert_eq!(prune.items[1].status, ItemStatus::Failed);
+
+        // 最新のファイルは成功として数え、署名付きのみのアセットは数えない
+        let file = |asset_id: &str| BackupFile {
+            asset_id: asset_id.to_string(),
+            path: format!("backup/{}.mp4", asset_id),
+            size: 1,
+        };
+        let backup = BatchResult::new(
+            BatchDetails::Backup(BackupResult {
+                output_dir: "backup".to_string(),
+                downloaded: vec![file("a")],
+                up_to_date: vec![file("b")],
+                signed_only: vec!["c".to_string()],
+                failed: Vec::new(),
+                downloaded_bytes: 1,
+            }),
+            std::time::Duration::ZERO,
+        );
+        assert_eq!((backup.succeeded, backup.failed), (2, 0));
+        let statuses: Vec<ItemStatus> = backup.items.iter().map(|item| item.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                ItemStatus::Downloaded,
+                ItemStatus::Unchanged,
+                ItemStatus::Skipped
+            ]
+        );
+        assert_eq!(backup.items[0].file_path.as_deref(), Some("backup/a.mp4"));
+    }
+
+    #[test]
+    fn test_status_remaining_capacity() {
+        let status = StatusResult::authenticated(
+            "abc***xyz".to_string(),
+            None,
+            Some(7),
+            Some(10),
+            CapacityPolicy::Ask,
+            Vec::new(),
+        );
+        assert_eq!(status.remaining_capacity, Some(3));
+        assert!(!status.is_at_capacity());
+
+        let status = StatusResult::authenticated(
+            "abc***xyz".to_string(),
+            None,
+            Some(12),
+            Some(10),
+            CapacityPolicy::Ask,
+            Vec::new(),
+        );
+        assert_eq!(status.remaining_capacity, Some(0));
+        assert!(status.is_at_capacity());
+
+        let status = StatusResult::authenticated(
+            "abc***xyz".to_string(),
+            None,
+            Some(7),
+            None,
+            CapacityPolicy::Ask,
+            Vec::new(),
+        );
+        assert_eq!(status.remaining_capacity, None);
+    }
+
+    #[test]
+    fn test_ping_result_statistics() {
+        let attempt = |seq, latency_ms, error: Option<&str>| PingAttempt {
+            seq,
+            latency_ms,
+            status_code: error.is_none().then_some(200),
+            error: error.map(str::to_string),
+        };
+        let result = PingResult::from_attempts(
+            "api.mux.com".to_string(),
+            "GET /video/v1/assets?limit=1".to_string(),
+            vec![
+                attempt(1, 120.0, None),
+                attempt(2, 5000.0, Some("timeout")),
+                attempt(3, 80.0, None),
+            ],
+        );
+        assert_eq!(result.succeeded, 2);
+        assert_eq!(result.failed, 1);
+        assert_eq!(result.min_ms, Some(80.0));
+        assert_eq!(result.avg_ms, Some(100.0));
+        assert_eq!(result.max_ms, Some(120.0));
+        assert_eq!(CommandResult::Ping(result).exit_code(), 4);
+
+        let result = PingResult::from_attempts(
+            "api.mux.com".to_string(),
+            "GET /".to_string(),
+            vec![attempt(1, 10.0, Some("dns error"))],
+        );
+        assert_eq!(result.avg_ms, None);
+        assert_eq!(result.status(), BulkStatus::Failed);
+    }
 }
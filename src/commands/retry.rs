@@ -0,0 +1,179 @@
+/// 再実行（retry）コマンド
+///
+/// `upload` で失敗したファイルを失敗の記録（`failures.json`）から読み込み、
+/// 失敗したときと同じオプションでアップロードし直します。
+/// 成功した項目は記録から除去し、再び失敗した項目はエラーを更新して記録に残します。
+use crate::commands::connect_with;
+use crate::commands::result::{
+    BatchDetails, BatchResult, CommandResult, RetryFailure, RetryListResult, RetryResult,
+};
+use crate::commands::upload::{self, UploadOptions};
+use crate::config::UserConfig;
+use crate::storage::failure_log::{FailedUpload, FailureLog};
+use anyhow::{Context, Result, bail};
+
+/// 再実行する対象
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryTarget {
+    /// 記録されているすべての失敗（`--all`）
+    All,
+    /// 指定したIDの失敗
+    Id(u64),
+}
+
+/// 記録されている失敗を一覧する
+pub async fn list() -> Result<CommandResult> {
+    let log = FailureLog::load_default().context("Failed to load failed uploads")?;
+    Ok(CommandResult::RetryList(RetryListResult {
+        entries: log.entries().to_vec(),
+    }))
+}
+
+/// 記録されている失敗を再実行する
+///
+/// 項目ごとに記録を保存するため、途中で中断されても成功した項目は再実行されません。
+///
+/// # 引数
+/// * `target` - 再実行する対象
+pub async fn run(target: RetryTarget) -> Result<CommandResult> {
+    let mut log = FailureLog::load_default().context("Failed to load failed uploads")?;
+    let targets: Vec<FailedUpload> = match target {
+        RetryTarget::All => log.entries().to_vec(),
+        RetryTarget::Id(id) => match log.find(id) {
+            Some(entry) => vec![entry.clone()],
+            None => bail!(
+                "No failed upload with ID {}. Run 'vidyeet retry' to list them.",
+                id
+            ),
+        },
+    };
+
+    let mut uploaded = Vec::new();
+    let mut failed = Vec::new();
+
+    if !targets.is_empty() {
+        // ユーザー設定を読み込み
+        let user_config = UserConfig::load()
+            .context("Failed to load user configuration. Please check your config.toml file.")?;
+
+        // 認証マネージャーとAPIクライアントを初期化（全項目で共有）
+        let (client, auth_manager) = connect_with(&user_config)?;
+
+        for entry in targets {
+            let options = entry.options.clone().with_user_defaults(&user_config);
+            let outcome =
+                match upload::run_before_upload_hook(&user_config.hooks, &entry.file_path).await {
+                    Ok(file_path) => {
+                        upload::run(&client, &auth_manager, &file_path, &options, None).await
+                    }
+                    Err(e) => Err(e),
+                };
+            match outcome {
+                Ok(result) => {
+                    upload::record_upload(&result);
+                    log.remove(entry.id);
+                    uploaded.push(result);
+                }
+                Err(e) => {
+                    let error = format!("{:#}", e);
+                    log.record(
+                        &entry.file_path,
+                        entry.options,
+                        error.clone(),
+                        chrono::Utc::now().timestamp().to_string(),
+                    );
+                    failed.push(RetryFailure {
+                        id: entry.id,
+                        file_path: entry.file_path,
+                        error,
+                    });
+                }
+            }
+
+            log.save().context("Failed to save failed uploads")?;
+        }
+    }
+
+    Ok(CommandResult::Retry(RetryResult {
+        uploaded,
+        failed,
+        remaining_count: log.entries().len(),
+    }))
+}
+
+/// `upload` の結果を失敗の記録に反映する
+///
+/// 失敗したファイルを記録し、成功したファイルの記録を取り除きます。
+/// 見積もりのみ（`--dry-run`）の場合と、複数ファイル指定で全体が失敗した場合
+/// （ディレクトリ指定などで対象のファイルを特定できない）は記録しません。
+/// 記録の失敗は警告にとどめ、アップロード結果には影響させません。
+///
+/// # 引数
+/// * `file_paths` - `upload` に指定したパス
+/// * `options` - `upload` に指定したオプション（ユーザー設定とのマージ前）
+/// * `outcome` - `upload` の結果
+pub fn record_upload_outcome(
+    file_paths: &[String],
+    options: UploadOptions,
+    outcome: &Result<CommandResult>,
+) {
+    if options.dry_run {
+        return;
+    }
+
+    let mut failures: Vec<(&str, String)> = Vec::new();
+    let mut succeeded: Vec<&str> = Vec::new();
+    match outcome {
+        Ok(CommandResult::Upload(r)) => succeeded.push(&r.file_path),
+        Ok(CommandResult::Batch(BatchResult {
+            details: BatchDetails::Upload(r),
+            ..
+        })) => {
+            succeeded.extend(r.uploaded.iter().map(|u| u.file_path.as_str()));
+            failures.extend(
+                r.failed
+                    .iter()
+                    .map(|f| (f.file_path.as_str(), f.error.clone())),
+            );
+        }
+        Ok(_) => return,
+        Err(e) => match file_paths {
+            [path] => failures.push((path, format!("{:#}", e))),
+            _ => return,
+        },
+    }
+
+    let mut log = match FailureLog::load_default() {
+        Ok(log) => log,
+        Err(e) => {
+            eprintln!("Warning: Failed to load failed uploads: {}", e);
+            return;
+        }
+    };
+
+    let mut changed = false;
+    for path in succeeded {
+        changed |= log.remove_path(&absolute_path(path));
+    }
+    let failed_at = chrono::Utc::now().timestamp().to_string();
+    for (path, error) in failures {
+        log.record(
+            &absolute_path(path),
+            options.clone(),
+            error,
+            failed_at.clone(),
+        );
+        changed = true;
+    }
+
+    if changed && let Err(e) = log.save() {
+        eprintln!("Warning: Failed to save failed uploads: {}", e);
+    }
+}
+
+/// 実行時のカレントディレクトリに依存しないよう絶対パスにする（解決できない場合はそのまま）
+fn absolute_path(path: &str) -> String {
+    std::fs::canonicalize(path)
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| path.to_string())
+}
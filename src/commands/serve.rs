@@ -0,0 +1,409 @@
+use crate::api::signing::{self, Audience, UrlSigner};
+use crate::api::thumbnail::{self, ThumbnailSize};
+use crate::api::types::{AssetStatus, PlaybackId};
+/// ローカルのプレビュープレーヤー（serve）コマンド
+///
+/// アセットのHLSをhls.jsで再生するHTMLページを、ローカルの小さなHTTPサーバー（`127.0.0.1`）で配信します。
+/// エンコード結果をブラウザですぐに確認する用途を想定しており、配信するのはこのページのみです
+/// （動画そのものはページからMuxへ直接取得します）。
+/// 再生ポリシーが `signed` の場合は、`[signing]` の署名キーで再生URLとポスター画像にトークンを付けます。
+use crate::commands::connect_with;
+use crate::commands::playback::SIGNED_POLICY;
+use crate::commands::result::{CommandResult, ServeResult};
+use crate::config::{APP_CONFIG, UserConfig};
+use crate::domain::formatter::escape_html;
+use anyhow::{Context, Result, bail};
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// プレビューページのHTML（`__NAME__` を置き換える）
+const PAGE_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>__TITLE__ - vidyeet preview</title>
+<style>
+  body { margin: 0; background: #111; color: #ddd; font: 14px/1.5 system-ui, sans-serif; }
+  main { max-width: 1280px; margin: 0 auto; padding: 16px; }
+  video { width: 100%; max-height: 80vh; background: #000; }
+  code { color: #9cf; }
+  #error { color: #f88; }
+</style>
+</head>
+<body>
+<main>
+<video id="player" controls playsinline poster="__POSTER__"></video>
+<p>__TITLE__ &middot; <code>__ASSET_ID__</code> &middot; <span id="level"></span></p>
+<p id="error"></p>
+</main>
+<script src="__HLS_JS__"></script>
+<script>
+const source = __SOURCE__;
+const video = document.getElementById("player");
+const error = document.getElementById("error");
+if (window.Hls && Hls.isSupported()) {
+  const hls = new Hls();
+  hls.on(Hls.Events.LEVEL_SWITCHED, (_, data) => {
+    const level = hls.levels[data.level];
+    document.getElementById("level").textContent =
+      level.width + "x" + level.height + " @ " + Math.round(level.bitrate / 1000) + " kbps";
+  });
+  hls.on(Hls.Events.ERROR, (_, data) => {
+    if (data.fatal) error.textContent = "Playback error: " + data.details;
+  });
+  hls.loadSource(source);
+  hls.attachMedia(video);
+} else if (video.canPlayType("application/vnd.apple.mpegurl")) {
+  video.src = source;
+} else {
+  error.textContent = "This browser cannot play HLS (hls.js could not be loaded).";
+}
+</script>
+</body>
+</html>
+"#;
+
+/// 起動したプレビューサーバー（`run` を呼ぶまでリクエストは処理しない）
+pub struct PreviewServer {
+    listener: TcpListener,
+    page: Arc<str>,
+    result: ServeResult,
+}
+
+/// アセットのプレビューページを用意し、サーバーを待ち受け状態にする
+///
+/// 再生IDは `public` を優先し、なければ `signed` の再生IDを署名して使います。
+///
+/// # 引数
+/// * `asset_id` - 対象のアセットID
+/// * `port` - 待ち受けるポート（`--port`、0の場合はOSが選ぶ）
+/// * `expires_secs` - 署名付きURLの有効期間（秒、`--expires`）。Noneの場合は `APP_CONFIG.signing.default_expires_secs`
+///
+/// # エラー
+/// アセットが `ready` でない、再生IDがない、`signed` なのに署名キーがない、ポートを使用できない場合
+pub async fn start(asset_id: &str, port: u16, expires_secs: Option<u64>) -> Result<PreviewServer> {
+    // ユーザー設定を読み込み
+    let user_config = UserConfig::load()
+        .context("Failed to load user configuration. Please check your config.toml file.")?;
+
+    // 認証マネージャーとAPIクライアントを初期化（状態を確認するためキャッシュは使わない）
+    let (client, auth_manager) = connect_with(&user_config)?;
+
+    let asset = client
+        .get_asset(asset_id, &auth_manager.get_auth_header())
+        .await
+        .context("Failed to fetch asset details")?;
+
+    if asset.status != AssetStatus::Ready {
+        bail!(
+            "Asset {} is not ready (status: {}); it can be previewed once it is ready. Run 'vidyeet wait {}' first.",
+            asset_id,
+            asset.status,
+            asset_id
+        );
+    }
+    let Some(playback_id) = preview_playback_id(&asset.playback_ids) else {
+        bail!("Asset {} has no playback ID to preview", asset_id);
+    };
+
+    let mut hls_url = Audience::Video.url(&playback_id.id);
+    let mut poster_url =
+        thumbnail::thumbnail_url(&playback_id.id, asset.duration, ThumbnailSize::default());
+    let mut expires_at = None;
+    if playback_id.policy == SIGNED_POLICY {
+        let Some(signer) = UrlSigner::from_config(&user_config.signing)
+            .context("Invalid [signing] settings in config.toml")?
+        else {
+            bail!(
+                "Asset {} has a signed playback policy. Set key_id and private_key under [signing] in config.toml.",
+                asset_id
+            );
+        };
+        let at =
+            signing::expires_at(expires_secs.unwrap_or(APP_CONFIG.signing.default_expires_secs));
+        hls_url = signer.sign_url(&hls_url, &playback_id.id, Audience::Video, at);
+        poster_url = signer.sign_url(&poster_url, &playback_id.id, Audience::Thumbnail, at);
+        expires_at = Some(at.to_string());
+    }
+
+    let listener = TcpListener::bind((APP_CONFIG.serve.bind_address, port))
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to listen on {}:{}",
+                APP_CONFIG.serve.bind_address, port
+            )
+        })?;
+    let address = listener
+        .local_addr()
+        .context("Failed to get the listening address")?;
+
+    let title = asset.title().unwrap_or(&asset.id).to_string();
+    let page = render_page(&title, &asset.id, &hls_url, &poster_url);
+
+    Ok(PreviewServer {
+        listener,
+        page: page.into(),
+        result: ServeResult {
+            asset_id: asset.id.clone(),
+            url: format!("http://{}/", address),
+            playback_id: playback_id.id.clone(),
+            playback_policy: playback_id.policy.clone(),
+            hls_url,
+            expires_at,
+            page_views: 0,
+        },
+    })
+}
+
+impl PreviewServer {
+    /// 起動時点の結果（プレビューページのURLなど。`page_views` は0）
+    pub fn info(&self) -> &ServeResult {
+        &self.result
+    }
+
+    /// `shutdown` が完了するまでリクエストを処理する
+    ///
+    /// 接続ごとにタスクを起動し、1つのリクエストに応答して接続を閉じます。
+    /// 接続の受け付け・応答の失敗はサーバーを止めずに無視します。
+    pub async fn run(self, shutdown: impl Future<Output = ()>) -> Result<CommandResult> {
+        let page_views = Arc::new(AtomicU64::new(0));
+        tokio::pin!(shutdown);
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                accepted = self.listener.accept() => {
+                    let Ok((stream, _)) = accepted else {
+                        continue;
+                    };
+                    let page = Arc::clone(&self.page);
+                    let page_views = Arc::clone(&page_views);
+                    tokio::spawn(async move {
+                        if let Ok(Route::Page) = handle_connection(stream, &page).await {
+                            page_views.fetch_add(1, Ordering::Relaxed);
+                        }
+                    });
+                }
+            }
+        }
+
+        Ok(CommandResult::Serve(ServeResult {
+            page_views: page_views.load(Ordering::Relaxed),
+            ..self.result
+        }))
+    }
+}
+
+/// プレビューに使う再生ID（`public` を優先し、なければ最初の再生ID）
+fn preview_playback_id(playback_ids: &[PlaybackId]) -> Option<&PlaybackId> {
+    playback_ids
+        .iter()
+        .find(|p| p.policy != SIGNED_POLICY)
+        .or_else(|| playback_ids.first())
+}
+
+/// プレビューページのHTMLを組み立てる
+///
+/// タイトルなどはHTMLとしてエスケープし、再生URLはJavaScriptの文字列リテラル（JSON）として埋め込みます。
+fn render_page(title: &str, asset_id: &str, hls_url: &str, poster_url: &str) -> String {
+    // `</script>` でスクリプトが閉じられないようにする
+    let source = serde_json::Value::from(hls_url)
+        .to_string()
+        .replace("</", "<\\/");
+    PAGE_TEMPLATE
+        .replace("__TITLE__", &escape_html(title))
+        .replace("__ASSET_ID__", &escape_html(asset_id))
+        .replace("__POSTER__", &escape_html(poster_url))
+        .replace("__HLS_JS__", &escape_html(APP_CONFIG.serve.hls_js_url))
+        .replace("__SOURCE__", &source)
+}
+
+/// リクエストへの応答の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Route {
+    /// プレビューページ（200）
+    Page,
+    /// ブラウザが自動で要求するファビコン（204、ログを汚さないため）
+    Favicon,
+    /// それ以外のパス（404）
+    NotFound,
+    /// GET・HEAD以外のメソッド（405）
+    MethodNotAllowed,
+    /// リクエスト行を解釈できない（400）
+    BadRequest,
+}
+
+impl Route {
+    /// ステータス行
+    fn status_line(self) -> &'static str {
+        match self {
+            Self::Page => "200 OK",
+            Self::Favicon => "204 No Content",
+            Self::NotFound => "404 Not Found",
+            Self::MethodNotAllowed => "405 Method Not Allowed",
+            Self::BadRequest => "400 Bad Request",
+        }
+    }
+}
+
+/// リクエストヘッダーの先頭行（`GET /path HTTP/1.1`）から応答を決める
+fn route(request_line: &str) -> Route {
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Route::BadRequest;
+    };
+    if !version.starts_with("HTTP/1.") {
+        return Route::BadRequest;
+    }
+    if method != "GET" && method != "HEAD" {
+        return Route::MethodNotAllowed;
+    }
+    let path = target.split(['?', '#']).next().unwrap_or_default();
+    match path {
+        "/" | "/index.html" => Route::Page,
+        "/favicon.ico" => Route::Favicon,
+        _ => Route::NotFound,
+    }
+}
+
+/// 1つの接続のリクエストを読み、応答して閉じる
+///
+/// # 戻り値
+/// 応答した種類（受信・送信に失敗した場合やタイムアウトした場合はエラー）
+async fn handle_connection(mut stream: TcpStream, page: &str) -> std::io::Result<Route> {
+    let timeout = Duration::from_secs(APP_CONFIG.serve.request_timeout_secs);
+    let head = tokio::time::timeout(timeout, read_request_head(&mut stream))
+        .await
+        .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))??;
+
+    let request_line = head.lines().next().unwrap_or_default();
+    let route = route(request_line);
+    let head_only = request_line.starts_with("HEAD ");
+    let (content_type, body) = match route {
+        Route::Page => ("text/html; charset=utf-8", page),
+        Route::Favicon => ("text/plain; charset=utf-8", ""),
+        _ => ("text/plain; charset=utf-8", route.status_line()),
+    };
+
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        route.status_line(),
+        content_type,
+        body.len()
+    )
+    .into_bytes();
+    if !head_only {
+        response.extend_from_slice(body.as_bytes());
+    }
+    tokio::time::timeout(timeout, stream.write_all(&response))
+        .await
+        .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))??;
+    let _ = stream.shutdown().await;
+    Ok(route)
+}
+
+/// リクエストヘッダー（空行まで）を読む
+///
+/// ボディは使わないため読みません。`APP_CONFIG.serve.max_request_bytes` を超える場合はエラー。
+async fn read_request_head(stream: &mut TcpStream) -> std::io::Result<String> {
+    let max = APP_CONFIG.serve.max_request_bytes;
+    let mut head = Vec::with_capacity(1024);
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        if head.len() > max {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "request header too large",
+            ));
+        }
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route() {
+        assert_eq!(route("GET / HTTP/1.1"), Route::Page);
+        assert_eq!(route("HEAD /index.html?t=1 HTTP/1.1"), Route::Page);
+        assert_eq!(route("GET /favicon.ico HTTP/1.1"), Route::Favicon);
+        assert_eq!(route("GET /../etc/passwd HTTP/1.1"), Route::NotFound);
+        assert_eq!(route("POST / HTTP/1.1"), Route::MethodNotAllowed);
+        assert_eq!(route("GET /"), Route::BadRequest);
+        assert_eq!(route(""), Route::BadRequest);
+    }
+
+    #[test]
+    fn test_render_page_escapes_values() {
+        let page = render_page(
+            "<b>Tom & \"Jerry\"</b>",
+            "asset_1",
+            "https://stream.mux.com/play_1.m3u8?token=a</script>",
+            "https://image.mux.com/play_1/thumbnail.jpg?time=1&width=640",
+        );
+        assert!(page.contains("&lt;b&gt;Tom &amp; &quot;Jerry&quot;&lt;/b&gt; - vidyeet preview"));
+        assert!(page.contains(
+            "poster=\"https://image.mux.com/play_1/thumbnail.jpg?time=1&amp;width=640\""
+        ));
+        assert!(
+            page.contains(
+                "const source = \"https://stream.mux.com/play_1.m3u8?token=a<\\/script>\";"
+            )
+        );
+        assert!(!page.contains("__"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_connection_serves_page() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            handle_connection(stream, "<p>preview</p>").await.unwrap()
+        });
+
+        let mut client = TcpStream::connect(address).await.unwrap();
+        client
+            .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+
+        assert_eq!(server.await.unwrap(), Route::Page);
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+        assert!(response.contains("Content-Type: text/html; charset=utf-8\r\n"));
+        assert!(response.contains("Content-Length: 14\r\n"));
+        assert!(response.ends_with("\r\n\r\n<p>preview</p>"));
+    }
+
+    #[test]
+    fn test_preview_playback_id_prefers_public() {
+        let ids = vec![
+            PlaybackId {
+                id: "play_signed".to_string(),
+                policy: "signed".to_string(),
+            },
+            PlaybackId {
+                id: "play_public".to_string(),
+                policy: "public".to_string(),
+            },
+        ];
+        assert_eq!(preview_playback_id(&ids).unwrap().id, "play_public");
+        assert_eq!(preview_playback_id(&ids[..1]).unwrap().id, "play_signed");
+        assert!(preview_playback_id(&[]).is_none());
+    }
+}
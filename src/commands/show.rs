@@ -1,8 +1,10 @@
 use crate::api::auth::AuthManager;
+use crate::api::cache::ResponseCache;
 use crate::api::client::ApiClient;
+use crate::api::thumbnail::{self, ThumbnailSize};
 use crate::api::types::AssetResponse;
+use crate::commands::connect;
 use crate::commands::result::{CommandResult, ShowResult};
-use crate::config::{APP_CONFIG, UserConfig};
 use anyhow::{Context, Result};
 
 /// アセット詳細を表示するコマンドを実行する
@@ -11,6 +13,8 @@ use anyhow::{Context, Result};
 ///
 /// # 引数
 /// * `asset_id` - 取得するアセットのID
+/// * `use_cache` - レスポンスキャッシュを使用するか（`--no-cache`でfalse）
+/// * `thumbnail_size` - サムネイル画像のURLの大きさ（`--thumbnail-width` / `--thumbnail-height`）
 ///
 /// # 戻り値
 /// 成功・失敗を示すResult<CommandResult>
@@ -18,30 +22,46 @@ use anyhow::{Context, Result};
 /// # エラー
 /// アプリケーション層としてanyhow::Resultを返し、
 /// 設定・認証・インフラ層のエラーを集約します。
-pub async fn execute(asset_id: &str) -> Result<CommandResult> {
-    // ユーザー設定を読み込み
-    let user_config = UserConfig::load()
-        .context("Failed to load user configuration. Please check your config.toml file.")?;
+pub async fn execute(
+    asset_id: &str,
+    use_cache: bool,
+    thumbnail_size: ThumbnailSize,
+) -> Result<CommandResult> {
+    // 認証マネージャーとAPIクライアントを初期化
+    let (mut client, auth_manager) = connect()?;
+    if use_cache && let Some(cache) = ResponseCache::open_default() {
+        client = client.with_cache(cache);
+    }
 
-    // 認証情報を取得
-    let auth = user_config
-        .get_auth()
-        .context("Authentication credentials not found. Please run 'vidyeet login' first.")?;
+    let result = run(&client, &auth_manager, asset_id, thumbnail_size).await?;
 
-    // 認証マネージャーとAPIクライアントを初期化
-    let auth_manager = AuthManager::new(auth.token_id.clone(), auth.token_secret.clone());
-    let client = ApiClient::new(APP_CONFIG.api.endpoint.to_string())
-        .context("Failed to create API client")?;
+    Ok(CommandResult::Show(Box::new(result)))
+}
 
+/// 認証済みクライアントでアセット詳細を取得する
+///
+/// 設定ファイルを読まないため、ライブラリ（`vidyeet::Client`）からも利用できます。
+///
+/// # 引数
+/// * `client` - APIクライアント
+/// * `auth_manager` - 認証マネージャー
+/// * `asset_id` - 取得するアセットのID
+/// * `thumbnail_size` - サムネイル画像のURLの大きさ
+pub async fn run(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    asset_id: &str,
+    thumbnail_size: ThumbnailSize,
+) -> Result<ShowResult> {
     // アセット詳細を取得
-    let asset = fetch_asset(&client, &auth_manager, asset_id)
+    let asset = fetch_asset(client, auth_manager, asset_id)
         .await
         .context("Failed to fetch asset details")?;
 
     // ShowResultを構築
     let result = ShowResult {
         asset_id: asset.data.id.clone(),
-        status: asset.data.status.clone(),
+        status: asset.data.status.to_string(),
         duration: asset.data.duration,
         aspect_ratio: asset.data.aspect_ratio.clone(),
         video_quality: asset.data.video_quality.clone(),
@@ -49,12 +69,18 @@ pub async fn execute(asset_id: &str) -> Result<CommandResult> {
         playback_ids: asset.data.playback_ids.clone(),
         hls_url: asset.get_playback_url(),
         mp4_url: asset.get_mp4_playback_url(),
+        thumbnail_url: asset.data.playback_ids.first().map(|playback_id| {
+            thumbnail::thumbnail_url(&playback_id.id, asset.data.duration, thumbnail_size)
+        }),
         tracks: asset.data.tracks.clone(),
         static_renditions: asset.data.static_renditions.clone(),
+        signed_image_urls: None,
         raw_asset: Some(asset.data),
+        warnings: Vec::new(),
+        copied: None,
     };
 
-    Ok(CommandResult::Show(Box::new(result)))
+    Ok(result)
 }
 
 /// Mux APIからアセット詳細を取得
@@ -72,15 +98,10 @@ async fn fetch_asset(
     asset_id: &str,
 ) -> Result<AssetResponse> {
     let auth_header = auth_manager.get_auth_header();
-    let endpoint = format!("/video/v1/assets/{}", asset_id);
-
-    let response = client
-        .get(&endpoint, Some(&auth_header))
+    let data = client
+        .get_asset(asset_id, &auth_header)
         .await
         .context("Failed to fetch asset details")?;
 
-    let response = ApiClient::check_response(response, &endpoint).await?;
-    let asset_response: AssetResponse = ApiClient::parse_json(response).await?;
-
-    Ok(asset_response)
+    Ok(AssetResponse { data })
 }
@@ -0,0 +1,57 @@
+/// 署名付きURL発行（sign）コマンド
+///
+/// 再生ポリシーが `signed` の再生IDについて、config.tomlの `[signing]` の署名キーで
+/// 動画（HLS）・サムネイル・ストーリーボード・GIFの署名付きURLを発行します。
+/// 署名はローカルで行うため、APIには接続しません。
+use crate::api::signing::{self, Audience, UrlSigner};
+use crate::commands::result::{CommandResult, SignResult};
+use crate::config::{APP_CONFIG, UserConfig};
+use anyhow::{Context, Result, bail};
+
+/// 署名付きURLを発行する
+///
+/// # 引数
+/// * `playback_id` - 再生ID
+/// * `audience` - URLの種類（`--type`）
+/// * `expires_secs` - 有効期間（秒、`--expires`）。Noneの場合は `APP_CONFIG.signing.default_expires_secs`
+/// * `playback_restriction_id` - トークンに含める再生制限のID（`--restriction`）。
+///   Noneの場合はconfig.tomlの `[signing] playback_restriction_id`
+pub async fn execute(
+    playback_id: &str,
+    audience: Audience,
+    expires_secs: Option<u64>,
+    playback_restriction_id: Option<&str>,
+) -> Result<CommandResult> {
+    // ユーザー設定を読み込み
+    let user_config = UserConfig::load()
+        .context("Failed to load user configuration. Please check your config.toml file.")?;
+
+    let Some(signer) = UrlSigner::from_config(&user_config.signing)
+        .context("Invalid [signing] settings in config.toml")?
+    else {
+        bail!(
+            "No signing key is configured. Set key_id and private_key under [signing] in config.toml."
+        );
+    };
+    let restriction = playback_restriction_id
+        .map(str::to_string)
+        .or_else(|| user_config.signing.playback_restriction_id.clone());
+    let signer = signer.with_playback_restriction(restriction.as_deref());
+
+    let expires_at =
+        signing::expires_at(expires_secs.unwrap_or(APP_CONFIG.signing.default_expires_secs));
+    let url = signer.sign_url(
+        &audience.url(playback_id),
+        playback_id,
+        audience,
+        expires_at,
+    );
+
+    Ok(CommandResult::Sign(SignResult {
+        playback_id: playback_id.to_string(),
+        url_type: audience,
+        url,
+        expires_at: expires_at.to_string(),
+        playback_restriction_id: restriction,
+    }))
+}
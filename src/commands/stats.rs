@@ -0,0 +1,184 @@
+use crate::api::cache::ResponseCache;
+use crate::api::types::AssetData;
+/// 統計（stats）コマンド
+///
+/// すべてのアセットを取得し、ステータス・品質・解像度ごとの件数、再生時間の合計と平均、
+/// 週ごとのアップロード数、最も古い・新しいアセットを集計します。
+/// ライブラリ全体の状態を一目で把握するためのもので、アセットは変更しません。
+use crate::commands::connect_with;
+use crate::commands::result::{CommandResult, StatsAsset, StatsResult, WeeklyUploads};
+use crate::config::UserConfig;
+use crate::domain::formatter;
+use anyhow::{Context, Result};
+use futures_util::TryStreamExt;
+use std::collections::BTreeMap;
+
+/// 品質・解像度が不明なアセットの集計キー
+pub const UNKNOWN: &str = "unknown";
+
+/// 週の書式（ISO 8601の週番号、例: "2025-W48"）
+const WEEK_PATTERN: &str = "%G-W%V";
+
+/// statsコマンドを実行する
+///
+/// # 引数
+/// * `use_cache` - レスポンスキャッシュを使用するか（`--no-cache`でfalse）
+pub async fn execute(use_cache: bool) -> Result<CommandResult> {
+    // ユーザー設定を読み込み
+    let user_config = UserConfig::load()
+        .context("Failed to load user configuration. Please check your config.toml file.")?;
+
+    // 認証マネージャーとAPIクライアントを初期化
+    let (mut client, auth_manager) = connect_with(&user_config)?;
+    if use_cache && let Some(cache) = ResponseCache::open_default() {
+        client = client.with_cache(cache);
+    }
+
+    let assets: Vec<AssetData> = client
+        .list_assets(&auth_manager.get_auth_header())
+        .try_collect()
+        .await
+        .context("Failed to fetch assets list")?;
+
+    // 週は作成日時の表示と同じタイムゾーンで判定
+    Ok(CommandResult::Stats(aggregate(
+        &assets,
+        user_config.timezone_offset_seconds,
+    )))
+}
+
+/// アセットを集計する
+///
+/// # 引数
+/// * `assets` - すべてのアセット
+/// * `offset_seconds` - 週の判定に使うタイムゾーンオフセット（秒）
+pub fn aggregate(assets: &[AssetData], offset_seconds: i32) -> StatsResult {
+    let mut by_status = BTreeMap::new();
+    let mut by_quality = BTreeMap::new();
+    let mut by_resolution = BTreeMap::new();
+    let mut weeks: BTreeMap<String, usize> = BTreeMap::new();
+
+    for asset in assets {
+        *by_status.entry(asset.status.to_string()).or_insert(0) += 1;
+
+        let quality = asset.video_quality.as_deref().unwrap_or(UNKNOWN);
+        *by_quality.entry(quality.to_string()).or_insert(0) += 1;
+
+        let resolution = asset
+            .resolution_tier
+            .as_deref()
+            .or(asset.max_resolution_tier.as_deref())
+            .unwrap_or(UNKNOWN);
+        *by_resolution.entry(resolution.to_string()).or_insert(0) += 1;
+
+        if let Some(week) = formatter::format_date(&asset.created_at, offset_seconds, WEEK_PATTERN)
+        {
+            *weeks.entry(week).or_insert(0) += 1;
+        }
+    }
+
+    let durations: Vec<f64> = assets.iter().filter_map(|asset| asset.duration).collect();
+    let total_duration: f64 = durations.iter().sum();
+    let average_duration = (!durations.is_empty()).then(|| total_duration / durations.len() as f64);
+
+    // 作成日時を解析できないアセットは最古・最新の判定から除く
+    let dated = || {
+        assets
+            .iter()
+            .filter_map(|asset| Some((asset.created_at.parse::<i64>().ok()?, asset)))
+    };
+    let oldest = dated().min_by_key(|(created_at, _)| *created_at);
+    let newest = dated().max_by_key(|(created_at, _)| *created_at);
+
+    StatsResult {
+        total_count: assets.len(),
+        by_status,
+        by_quality,
+        by_resolution,
+        total_duration,
+        average_duration,
+        uploads_per_week: weeks
+            .into_iter()
+            .map(|(week, count)| WeeklyUploads { week, count })
+            .collect(),
+        oldest: oldest.map(|(_, asset)| StatsAsset::from(asset)),
+        newest: newest.map(|(_, asset)| StatsAsset::from(asset)),
+    }
+}
+
+impl From<&AssetData> for StatsAsset {
+    fn from(asset: &AssetData) -> Self {
+        Self {
+            asset_id: asset.id.clone(),
+            title: asset.title().map(str::to_string),
+            created_at: asset.created_at.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(id: &str, status: &str, created_at: &str, duration: Option<f64>) -> AssetData {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "status": status,
+            "created_at": created_at,
+            "duration": duration,
+            "video_quality": "basic",
+            "resolution_tier": "1080p"
+        }))
+        .expect("valid asset")
+    }
+
+    #[test]
+    fn test_aggregate() {
+        // 1764434950 = 2025-11-29（土）16:49:10 UTC、1764000000 = 2025-11-24（月）16:00:00 UTC
+        let mut assets = vec![
+            asset("a", "ready", "1764434950", Some(60.0)),
+            asset("b", "ready", "1764000000", Some(30.0)),
+            asset("c", "errored", "1761955200", None),
+        ];
+        assets[2].video_quality = None;
+        assets[2].resolution_tier = None;
+
+        let stats = aggregate(&assets, 0);
+        assert_eq!(stats.total_count, 3);
+        assert_eq!(stats.by_status["ready"], 2);
+        assert_eq!(stats.by_status["errored"], 1);
+        assert_eq!(stats.by_quality["basic"], 2);
+        assert_eq!(stats.by_quality[UNKNOWN], 1);
+        assert_eq!(stats.by_resolution["1080p"], 2);
+        assert_eq!(stats.total_duration, 90.0);
+        assert_eq!(stats.average_duration, Some(45.0));
+        assert_eq!(
+            stats.uploads_per_week,
+            [
+                WeeklyUploads {
+                    week: "2025-W44".to_string(),
+                    count: 1
+                },
+                WeeklyUploads {
+                    week: "2025-W48".to_string(),
+                    count: 2
+                },
+            ]
+        );
+        assert_eq!(stats.oldest.unwrap().asset_id, "c");
+        assert_eq!(stats.newest.unwrap().asset_id, "a");
+
+        // JST（UTC+9）では 2025-11-30（日）になるが、同じ週に数える
+        let stats = aggregate(&assets, 32400);
+        assert_eq!(stats.uploads_per_week[1].count, 2);
+    }
+
+    #[test]
+    fn test_aggregate_empty() {
+        let stats = aggregate(&[], 0);
+        assert_eq!(stats.total_count, 0);
+        assert_eq!(stats.average_duration, None);
+        assert!(stats.uploads_per_week.is_empty());
+        assert!(stats.oldest.is_none());
+    }
+}
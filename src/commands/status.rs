@@ -1,9 +1,13 @@
 /// ステータスコマンド
 ///
-/// 現在の認証情報でMux Video APIにアクセスできるか（ログイン状態か）を確認します。
-use crate::api::auth::AuthManager;
+/// 現在の認証情報でMux Video APIにアクセスできるか（ログイン状態か）を確認し、
+/// 認証済みの場合はトークンが属する環境・アセット数と、プランの上限（config.tomlの`asset_limit`）までの残りを返します。
+/// アセット数は一覧APIを `limit=1` で1回だけ呼び出し、レスポンスの総数から求めます（全ページは取得しません）。
+use crate::api::client::ApiClient;
+use crate::commands::connect_with;
 use crate::commands::result::{CommandResult, StatusResult};
 use crate::config::user::UserConfig;
+use crate::domain::warning::{Warning, WarningKind};
 use anyhow::{Context, Result};
 
 /// ステータスコマンドを実行
@@ -16,25 +20,73 @@ pub async fn execute() -> Result<CommandResult> {
 
     // 認証情報の存在を確認
     if !config.has_auth() {
-        return Ok(CommandResult::Status(StatusResult {
-            is_authenticated: false,
-            token_id: None,
-        }));
+        return Ok(CommandResult::Status(StatusResult::unauthenticated(
+            None,
+            config.asset_limit,
+            config.capacity_policy,
+        )));
     }
 
-    // 認証情報を取得
-    let auth = config
-        .get_auth()
-        .context("Failed to retrieve authentication credentials")?;
-
-    // 認証マネージャーを作成
-    let auth_manager = AuthManager::new(auth.token_id.clone(), auth.token_secret.clone());
+    // レスポンスキャッシュは使わない（次のアップロードで上限に達するかを現在の値で判断するため）
+    let (client, auth_manager) = connect_with(&config)?;
 
     // 認証情報をテスト
-    let is_authenticated = auth_manager.test_credentials().await.is_ok();
+    if auth_manager.test_credentials().await.is_err() {
+        return Ok(CommandResult::Status(StatusResult::unauthenticated(
+            Some(auth_manager.get_masked_token_id()),
+            config.asset_limit,
+            config.capacity_policy,
+        )));
+    }
+
+    // 環境とアセット数（取得できなくても認証状態の表示は続け、警告で知らせる）
+    let auth_header = auth_manager.get_auth_header();
+    let mut warnings = Vec::new();
+    let environment = fetch_environment(&client, &auth_header, &mut warnings).await;
+    let asset_count = fetch_asset_count(&client, &auth_header, &mut warnings).await;
+
+    Ok(CommandResult::Status(StatusResult::authenticated(
+        auth_manager.get_masked_token_id(),
+        environment,
+        asset_count,
+        config.asset_limit,
+        config.capacity_policy,
+        warnings,
+    )))
+}
+
+/// トークンが属する環境の名前を取得（名前がなければID）
+async fn fetch_environment(
+    client: &ApiClient,
+    auth_header: &str,
+    warnings: &mut Vec<Warning>,
+) -> Option<String> {
+    match client.get_token_info(auth_header).await {
+        Ok(info) => info.environment_name.or(info.environment_id),
+        Err(e) => {
+            warnings.push(Warning::new(
+                WarningKind::EnvironmentUnavailable,
+                format!("Could not get the environment of the access token: {}", e),
+            ));
+            None
+        }
+    }
+}
 
-    Ok(CommandResult::Status(StatusResult {
-        is_authenticated,
-        token_id: Some(auth_manager.get_masked_token_id()),
-    }))
+/// アセット数を取得（一覧APIを `limit=1` で1回だけ呼び出す）
+async fn fetch_asset_count(
+    client: &ApiClient,
+    auth_header: &str,
+    warnings: &mut Vec<Warning>,
+) -> Option<usize> {
+    let message = match client.count_assets(auth_header).await {
+        Ok(Some(count)) => return Some(count),
+        Ok(None) => "The asset list response did not include a total count".to_string(),
+        Err(e) => format!("Could not count assets: {}", e),
+    };
+    warnings.push(Warning::new(
+        WarningKind::AssetCountUnavailable,
+        format!("{}; remaining capacity is unknown", message),
+    ));
+    None
 }
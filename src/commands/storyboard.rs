@@ -0,0 +1,215 @@
+use crate::api::error::InfraError;
+use crate::api::signing::{self, Audience, UrlSigner};
+use crate::api::types::AssetStatus;
+/// ストーリーボード（storyboard）コマンド
+///
+/// カスタムプレーヤーのシークバーのプレビューに使う、アセットのストーリーボード
+/// （WebVTTの `storyboard.vtt` と、プレビューを格子状に並べたスプライト画像 `storyboard.jpg`）のURLを表示します。
+/// `--download` を指定すると両方をディレクトリに保存し、VTTが参照するスプライト画像のURLを
+/// 保存したファイル名に書き換えて、そのまま自前でホストできるようにします。
+/// 再生ポリシーが `signed` の場合は、`[signing]` の署名キーでストーリーボード用のトークンを付けます。
+use crate::commands::connect_with;
+use crate::commands::playback::SIGNED_POLICY;
+use crate::commands::result::{CommandResult, StoryboardDownload, StoryboardResult};
+use crate::config::{APP_CONFIG, UserConfig};
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use std::time::Duration;
+
+/// ストーリーボードのURLを表示する（`--download` 指定時は保存する）
+///
+/// # 引数
+/// * `asset_id` - 対象のアセットID
+/// * `download_dir` - 保存先のディレクトリ（`--download`、なければ作成する）。Noneの場合はURLのみ
+/// * `expires_secs` - 署名付きURLの有効期間（秒、`--expires`）。Noneの場合は `APP_CONFIG.signing.default_expires_secs`
+///
+/// # エラー
+/// アセットが `ready` でない、再生IDがない、`signed` なのに署名キーがない、ダウンロードに失敗した場合
+pub async fn execute(
+    asset_id: &str,
+    download_dir: Option<&Path>,
+    expires_secs: Option<u64>,
+) -> Result<CommandResult> {
+    if let Some(dir) = download_dir
+        && dir.is_file()
+    {
+        bail!("Download path is a file: {}", dir.display());
+    }
+
+    // ユーザー設定を読み込み
+    let user_config = UserConfig::load()
+        .context("Failed to load user configuration. Please check your config.toml file.")?;
+
+    // 認証マネージャーとAPIクライアントを初期化（状態を確認するためキャッシュは使わない）
+    let (client, auth_manager) = connect_with(&user_config)?;
+
+    let asset = client
+        .get_asset(asset_id, &auth_manager.get_auth_header())
+        .await
+        .context("Failed to fetch asset details")?;
+
+    // ストーリーボードはアセットの処理が終わるまで生成されない
+    if asset.status != AssetStatus::Ready {
+        bail!(
+            "Asset {} is not ready (status: {}); storyboards are available once it is ready. Run 'vidyeet wait {}' first.",
+            asset_id,
+            asset.status,
+            asset_id
+        );
+    }
+    let Some(playback_id) = asset.playback_ids.first() else {
+        bail!(
+            "Asset {} has no playback ID; storyboards are served per playback ID",
+            asset_id
+        );
+    };
+
+    let mut vtt_url = Audience::Storyboard.url(&playback_id.id);
+    let mut sprite_url = sprite_url(&playback_id.id);
+    let mut expires_at = None;
+    if playback_id.policy == SIGNED_POLICY {
+        let Some(signer) = UrlSigner::from_config(&user_config.signing)
+            .context("Invalid [signing] settings in config.toml")?
+        else {
+            bail!(
+                "Asset {} has a signed playback policy. Set key_id and private_key under [signing] in config.toml.",
+                asset_id
+            );
+        };
+        let at =
+            signing::expires_at(expires_secs.unwrap_or(APP_CONFIG.signing.default_expires_secs));
+        vtt_url = signer.sign_url(&vtt_url, &playback_id.id, Audience::Storyboard, at);
+        sprite_url = signer.sign_url(&sprite_url, &playback_id.id, Audience::Storyboard, at);
+        expires_at = Some(at.to_string());
+    }
+
+    let download = match download_dir {
+        Some(dir) => Some(download(dir, &vtt_url, &sprite_url).await?),
+        None => None,
+    };
+
+    Ok(CommandResult::Storyboard(StoryboardResult {
+        asset_id: asset.id.clone(),
+        playback_id: playback_id.id.clone(),
+        playback_policy: playback_id.policy.clone(),
+        vtt_url,
+        sprite_url,
+        expires_at,
+        download,
+    }))
+}
+
+/// 再生IDのストーリーボードのスプライト画像の署名前のURL
+fn sprite_url(playback_id: &str) -> String {
+    format!("https://image.mux.com/{}/storyboard.jpg", playback_id)
+}
+
+/// VTTとスプライト画像をディレクトリに保存する
+///
+/// VTTはスプライト画像を絶対URL（署名付きの場合はトークン付き）で参照するため、
+/// 保存したスプライト画像のファイル名に書き換えてから保存します。
+async fn download(dir: &Path, vtt_url: &str, sprite_url: &str) -> Result<StoryboardDownload> {
+    let http = reqwest::Client::builder()
+        .timeout(Duration::from_secs(
+            APP_CONFIG.storyboard.download_timeout_secs,
+        ))
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    let vtt = fetch(&http, vtt_url)
+        .await
+        .context("Failed to download storyboard VTT")?;
+    let vtt = String::from_utf8(vtt.to_vec()).context("Storyboard VTT is not valid UTF-8")?;
+    let (vtt, tiles) = localize_vtt(&vtt, APP_CONFIG.storyboard.sprite_file_name);
+    let sprite = fetch(&http, sprite_url)
+        .await
+        .context("Failed to download storyboard image")?;
+
+    tokio::fs::create_dir_all(dir)
+        .await
+        .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+    let vtt_path = dir.join(APP_CONFIG.storyboard.vtt_file_name);
+    let sprite_path = dir.join(APP_CONFIG.storyboard.sprite_file_name);
+    tokio::fs::write(&vtt_path, vtt)
+        .await
+        .with_context(|| format!("Failed to write {}", vtt_path.display()))?;
+    tokio::fs::write(&sprite_path, &sprite)
+        .await
+        .with_context(|| format!("Failed to write {}", sprite_path.display()))?;
+
+    Ok(StoryboardDownload {
+        vtt_path: vtt_path.display().to_string(),
+        sprite_path: sprite_path.display().to_string(),
+        sprite_bytes: sprite.len() as u64,
+        tiles,
+    })
+}
+
+/// URLの内容を取得する
+///
+/// # エラー
+/// 通信に失敗した場合、または2xx以外の場合は`InfraError::Network`を返す
+async fn fetch(http: &reqwest::Client, url: &str) -> Result<bytes::Bytes, InfraError> {
+    let response = http
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| InfraError::network(format!("request failed: {}", e)))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(InfraError::network(format!("HTTP {}", status.as_u16())));
+    }
+    response
+        .bytes()
+        .await
+        .map_err(|e| InfraError::network(format!("failed to read response: {}", e)))
+}
+
+/// VTTのキューが参照するスプライト画像のURLを、保存したファイル名に書き換える
+///
+/// キューの本文は `<スプライト画像のURL>#xywh=<x>,<y>,<幅>,<高さ>` の形式のため、
+/// `#xywh=` より前を置き換えます。
+///
+/// # 戻り値
+/// 書き換えたVTTと、書き換えたキューの数
+fn localize_vtt(vtt: &str, sprite_file_name: &str) -> (String, usize) {
+    let mut tiles = 0;
+    let mut localized: String = vtt
+        .lines()
+        .map(|line| match line.find("#xywh=") {
+            Some(index) => {
+                tiles += 1;
+                format!("{}{}", sprite_file_name, &line[index..])
+            }
+            None => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    localized.push('\n');
+    (localized, tiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_localize_vtt_rewrites_sprite_references() {
+        let vtt = "WEBVTT\n\n\
+            00:00:00.000 --> 00:00:05.000\n\
+            https://image.mux.com/play_1/storyboard.jpg?token=abc#xywh=0,0,256,144\n\n\
+            00:00:05.000 --> 00:00:10.000\n\
+            https://image.mux.com/play_1/storyboard.jpg?token=abc#xywh=256,0,256,144\n";
+
+        let (localized, tiles) = localize_vtt(vtt, "storyboard.jpg");
+        assert_eq!(tiles, 2);
+        assert_eq!(
+            localized,
+            "WEBVTT\n\n\
+            00:00:00.000 --> 00:00:05.000\n\
+            storyboard.jpg#xywh=0,0,256,144\n\n\
+            00:00:05.000 --> 00:00:10.000\n\
+            storyboard.jpg#xywh=256,0,256,144\n"
+        );
+    }
+}
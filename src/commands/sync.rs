@@ -0,0 +1,332 @@
+use crate::api::types::AssetData;
+use crate::commands::batch_upload::{self, BatchOptions};
+/// 同期コマンド
+///
+/// ローカルディレクトリ直下の動画ファイルとMux上のアセットを比較し、
+/// アップロードされていないファイルをアップロードします。
+/// `--delete` 指定時は、対応するローカルファイルがないアセットを削除します。
+/// 比較はアップロード時にpassthroughへ記録したSHA-256で行い、
+/// チェックサムのないアセットはファイル名（ローカルインデックスの元ファイル名・タイトル）で照合します。
+use crate::commands::connect_with;
+use crate::commands::delete;
+use crate::commands::result::{
+    BatchDetails, BatchResult, CommandResult, SyncAction, SyncFailure, SyncRemoteAsset, SyncResult,
+    SyncedFile,
+};
+use crate::commands::upload;
+use crate::config::UserConfig;
+use crate::storage::asset_index::{AssetIndex, IndexedAsset};
+use anyhow::{Context, Result, bail};
+use futures_util::TryStreamExt;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// 同期の計画（`plan` で作成し、`execute` で実行する）
+#[derive(Debug, Clone)]
+pub struct SyncPlan {
+    /// 同期するディレクトリ
+    pub directory: String,
+    /// アップロード済みのため何もしないファイル
+    pub unchanged: Vec<SyncedFile>,
+    /// アップロードするファイル
+    pub to_upload: Vec<String>,
+    /// 削除するアセット（`--delete` 指定時のみ）
+    pub to_delete: Vec<SyncRemoteAsset>,
+    /// 計画の作成にかかった時間（結果の所要時間に含める）
+    pub elapsed: Duration,
+}
+
+impl SyncPlan {
+    /// 実行せずに計画のみを結果にする（`--dry-run`）
+    pub fn into_dry_run(self) -> CommandResult {
+        let elapsed = self.elapsed;
+        CommandResult::Batch(BatchResult::new(
+            BatchDetails::Sync(self.into_result(true)),
+            elapsed,
+        ))
+    }
+
+    fn into_result(self, dry_run: bool) -> SyncResult {
+        SyncResult {
+            directory: self.directory,
+            dry_run,
+            unchanged: self.unchanged,
+            to_upload: self.to_upload,
+            to_delete: self.to_delete,
+            uploaded: Vec::new(),
+            deleted: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
+
+/// 比較対象のローカルファイル
+#[derive(Debug, Clone)]
+struct LocalFile {
+    path: String,
+    sha256: String,
+}
+
+/// 比較対象のアセット
+#[derive(Debug, Clone)]
+struct RemoteAsset {
+    asset_id: String,
+    title: Option<String>,
+    sha256: Option<String>,
+    /// 元ファイル名（このCLIでアップロードした場合のみ、ローカルインデックスから取得）
+    file_name: Option<String>,
+}
+
+/// ローカルファイルとアセットを比較して同期の計画を作成する
+///
+/// ファイルのチェックサムを計算し、全アセットを取得します（アップロード・削除は行いません）。
+///
+/// # 引数
+/// * `dir` - 同期するディレクトリ（直下の対応フォーマットのファイルが対象）
+/// * `delete` - 対応するローカルファイルがないアセットを削除対象にするか（`--delete`）
+pub async fn plan(dir: &Path, delete: bool) -> Result<SyncPlan> {
+    let started_at = Instant::now();
+    if !dir.is_dir() {
+        bail!("Not a directory: {}", dir.display());
+    }
+
+    // ユーザー設定を読み込み
+    let user_config = UserConfig::load()
+        .context("Failed to load user configuration. Please check your config.toml file.")?;
+
+    let directory = dir.display().to_string();
+    let options = upload::UploadOptions::default().with_user_defaults(&user_config);
+    let file_paths = batch_upload::expand_paths(
+        std::slice::from_ref(&directory),
+        &options.supported_formats(),
+    )?;
+
+    let mut local = Vec::with_capacity(file_paths.len());
+    for path in file_paths {
+        let sha256 = upload::sha256_file(&path)
+            .await
+            .with_context(|| format!("Failed to compute checksum of {}", path))?;
+        local.push(LocalFile { path, sha256 });
+    }
+
+    // 認証マネージャーとAPIクライアントを初期化（最新の状態と比較するためキャッシュは使わない）
+    let (client, auth_manager) = connect_with(&user_config)?;
+
+    let auth_header = auth_manager.get_auth_header();
+    let assets: Vec<AssetData> = client
+        .list_assets(&auth_header)
+        .try_collect()
+        .await
+        .context("Failed to fetch assets list")?;
+
+    // 元ファイル名はAPIから取得できないため、更新前のインデックスから引き継ぐ
+    let index = AssetIndex::load_default().ok();
+    let remote: Vec<RemoteAsset> = assets
+        .iter()
+        .map(|asset| RemoteAsset {
+            asset_id: asset.id.clone(),
+            title: asset.title().map(str::to_string),
+            sha256: asset.sha256(),
+            file_name: index
+                .as_ref()
+                .and_then(|index| index.find(&asset.id))
+                .and_then(|indexed| indexed.file_name.clone()),
+        })
+        .collect();
+    AssetIndex::update_default(|index| {
+        index.replace_all(assets.iter().map(IndexedAsset::from_asset).collect())
+    });
+
+    let (unchanged, to_upload, to_delete) = compare(&local, &remote, delete);
+    Ok(SyncPlan {
+        directory,
+        unchanged,
+        to_upload,
+        to_delete,
+        elapsed: started_at.elapsed(),
+    })
+}
+
+/// 同期の計画を実行する（アップロードしてから削除する）
+///
+/// 個々のアップロード・削除の失敗は結果の`failed`に記録し、残りの操作は続行します。
+///
+/// # 引数
+/// * `plan` - `plan` で作成した計画
+/// * `options` - アップロードの同時実行数・帯域上限
+pub async fn execute(plan: SyncPlan, options: BatchOptions) -> Result<CommandResult> {
+    let started_at = Instant::now();
+    // ユーザー設定を読み込み
+    let user_config = UserConfig::load()
+        .context("Failed to load user configuration. Please check your config.toml file.")?;
+
+    // 認証マネージャーとAPIクライアントを初期化（アップロードと削除で共有）
+    let (client, auth_manager) = connect_with(&user_config)?;
+    let plan_elapsed = plan.elapsed;
+    let mut result = plan.into_result(false);
+
+    if !result.to_upload.is_empty() {
+        // 計画の時点で照合済みのため、アップロード時の重複検出は行わない
+        let mut options = options;
+        options.upload.force = true;
+        let batch = batch_upload::upload_files(
+            &user_config,
+            client.clone(),
+            &auth_manager,
+            &result.to_upload,
+            options,
+            None,
+        )
+        .await?;
+        result.uploaded = batch.uploaded;
+        result
+            .failed
+            .extend(batch.failed.into_iter().map(|failure| SyncFailure {
+                action: SyncAction::Upload,
+                target: failure.file_path,
+                error: failure.error,
+            }));
+    }
+
+    if !result.to_delete.is_empty() {
+        for asset in &result.to_delete {
+            match delete::run(&client, &auth_manager, &asset.asset_id).await {
+                Ok(_) => {
+                    AssetIndex::update_default(|index| index.remove(&asset.asset_id));
+                    result.deleted.push(asset.asset_id.clone());
+                }
+                Err(e) => result.failed.push(SyncFailure {
+                    action: SyncAction::Delete,
+                    target: asset.asset_id.clone(),
+                    error: format!("{:#}", e),
+                }),
+            }
+        }
+    }
+
+    Ok(CommandResult::Batch(BatchResult::new(
+        BatchDetails::Sync(result),
+        plan_elapsed + started_at.elapsed(),
+    )))
+}
+
+/// ローカルファイルとアセットを照合する
+///
+/// チェックサムが一致するアセットを優先し、見つからない場合はチェックサムのないアセットから
+/// 元ファイル名またはタイトル（ファイル名・拡張子を除いた名前）が一致するものを探します。
+///
+/// # 戻り値
+/// （一致したファイル、アップロードするファイル、削除するアセット）
+fn compare(
+    local: &[LocalFile],
+    remote: &[RemoteAsset],
+    delete: bool,
+) -> (Vec<SyncedFile>, Vec<String>, Vec<SyncRemoteAsset>) {
+    let mut matched = vec![false; remote.len()];
+    let mut unchanged = Vec::new();
+    let mut to_upload = Vec::new();
+
+    for file in local {
+        let by_checksum = remote
+            .iter()
+            .position(|asset| asset.sha256.as_deref() == Some(file.sha256.as_str()));
+        let by_name = || {
+            let path = Path::new(&file.path);
+            let name = upload::file_name_of(&file.path);
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or(name);
+            remote.iter().enumerate().position(|(i, asset)| {
+                !matched[i]
+                    && asset.sha256.is_none()
+                    && (asset.file_name.as_deref() == Some(name)
+                        || asset.title.as_deref() == Some(name)
+                        || asset.title.as_deref() == Some(stem))
+            })
+        };
+
+        match by_checksum.or_else(by_name) {
+            Some(i) => {
+                matched[i] = true;
+                unchanged.push(SyncedFile {
+                    file_path: file.path.clone(),
+                    asset_id: remote[i].asset_id.clone(),
+                });
+            }
+            None => to_upload.push(file.path.clone()),
+        }
+    }
+
+    let to_delete = if delete {
+        remote
+            .iter()
+            .zip(&matched)
+            .filter(|(_, matched)| !**matched)
+            .map(|(asset, _)| SyncRemoteAsset {
+                asset_id: asset.asset_id.clone(),
+                title: asset.title.clone(),
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    (unchanged, to_upload, to_delete)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(path: &str, sha256: &str) -> LocalFile {
+        LocalFile {
+            path: path.to_string(),
+            sha256: sha256.to_string(),
+        }
+    }
+
+    fn remote(asset_id: &str, sha256: Option<&str>, title: Option<&str>) -> RemoteAsset {
+        RemoteAsset {
+            asset_id: asset_id.to_string(),
+            title: title.map(str::to_string),
+            sha256: sha256.map(str::to_string),
+            file_name: None,
+        }
+    }
+
+    #[test]
+    fn test_compare_by_checksum() {
+        let local = [local("dir/a.mp4", "aaa"), local("dir/b.mp4", "bbb")];
+        let assets = [
+            remote("asset_a", Some("aaa"), None),
+            remote("asset_x", Some("xxx"), None),
+        ];
+
+        let (unchanged, to_upload, to_delete) = compare(&local, &assets, false);
+        assert_eq!(unchanged[0].asset_id, "asset_a");
+        assert_eq!(to_upload, ["dir/b.mp4"]);
+        assert!(to_delete.is_empty());
+
+        let (_, _, to_delete) = compare(&local, &assets, true);
+        assert_eq!(to_delete.len(), 1);
+        assert_eq!(to_delete[0].asset_id, "asset_x");
+    }
+
+    #[test]
+    fn test_compare_by_name_without_checksum() {
+        let local = [local("dir/intro.mp4", "aaa"), local("dir/outro.mp4", "bbb")];
+        let mut by_file_name = remote("asset_1", None, None);
+        by_file_name.file_name = Some("intro.mp4".to_string());
+        // チェックサムが異なる（内容が変わった）アセットは名前が一致しても別物として扱う
+        let changed = remote("asset_2", Some("old"), Some("outro"));
+        let assets = [by_file_name, changed];
+
+        let (unchanged, to_upload, to_delete) = compare(&local, &assets, true);
+        assert_eq!(unchanged.len(), 1);
+        assert_eq!(unchanged[0].asset_id, "asset_1");
+        assert_eq!(to_upload, ["dir/outro.mp4"]);
+        assert_eq!(to_delete[0].asset_id, "asset_2");
+
+        let by_title = [remote("asset_3", None, Some("outro"))];
+        let (unchanged, _, _) = compare(&local, &by_title, false);
+        assert_eq!(unchanged[0].file_path, "dir/outro.mp4");
+    }
+}
@@ -0,0 +1,137 @@
+use crate::api::cache::ResponseCache;
+use crate::api::types::{AssetData, AssetPatch};
+/// タグコマンド
+///
+/// アセットのpassthroughにJSONで記録したタグを追加・削除・一覧表示します。
+/// 別のデータベースを持たずに、`list --tag` で動画を絞り込めるようにするためのものです。
+/// passthroughにvidyeet以外が書き込んだ値がある場合は、上書きせずエラーにします。
+use crate::commands::connect;
+use crate::commands::result::{CommandResult, TagAction, TagResult};
+use crate::domain::error::DomainError;
+use crate::domain::passthrough::{Passthrough, normalize_tag};
+use crate::storage::asset_index::{AssetIndex, IndexedAsset};
+use anyhow::{Context, Result};
+
+/// タグコマンドを実行する
+///
+/// # 引数
+/// * `action` - 追加・削除・一覧
+/// * `asset_id` - 対象のアセットID
+/// * `tag` - 追加・削除するタグ（一覧の場合はNone）
+///
+/// # 戻り値
+/// 成功・失敗を示すResult<CommandResult>
+pub async fn execute(
+    action: TagAction,
+    asset_id: &str,
+    tag: Option<&str>,
+) -> Result<CommandResult> {
+    // 不正なタグはAPIに問い合わせる前に弾く
+    if action == TagAction::Add
+        && let Some(tag) = tag
+    {
+        normalize_tag(tag)?;
+    }
+
+    // 認証マネージャーとAPIクライアントを初期化
+    let (client, auth_manager) = connect()?;
+
+    let auth_header = auth_manager.get_auth_header();
+
+    let current = client
+        .get_asset(asset_id, &auth_header)
+        .await
+        .context("Failed to fetch asset details")?;
+
+    let (mut passthrough, changed) = apply(action, &current, tag)?;
+    if changed {
+        let patch = AssetPatch {
+            passthrough: Some(passthrough.encode()?),
+            ..Default::default()
+        };
+        let updated = client
+            .patch_asset(asset_id, &patch, &auth_header)
+            .await
+            .context(format!("Failed to update tags of asset {}", asset_id))?;
+
+        // list/showのキャッシュに古いタグが残らないよう破棄し、ローカルインデックスも更新
+        ResponseCache::invalidate_default();
+        AssetIndex::update_default(|index| index.upsert(IndexedAsset::from_asset(&updated)));
+
+        passthrough = Passthrough::parse(updated.passthrough.as_deref()).unwrap_or(passthrough);
+    }
+
+    Ok(CommandResult::Tag(TagResult {
+        asset_id: asset_id.to_string(),
+        action,
+        tag: tag.map(|tag| tag.trim().to_lowercase()),
+        changed,
+        tags: passthrough.tags(),
+    }))
+}
+
+/// 現在のアセットにタグの追加・削除を適用する
+///
+/// # 戻り値
+/// 適用後のpassthroughと、変更があったかどうか
+fn apply(action: TagAction, asset: &AssetData, tag: Option<&str>) -> Result<(Passthrough, bool)> {
+    let parsed = Passthrough::parse(asset.passthrough.as_deref());
+    let mut passthrough = match (action, parsed) {
+        (_, Some(passthrough)) => passthrough,
+        // 一覧表示のみの場合は、読めない値をタグなしとして扱う
+        (TagAction::List, None) => Passthrough::default(),
+        (_, None) => return Err(DomainError::passthrough_in_use(&asset.id).into()),
+    };
+
+    let changed = match (action, tag) {
+        (TagAction::Add, Some(tag)) => passthrough.add_tag(tag)?,
+        (TagAction::Remove, Some(tag)) => passthrough.remove_tag(tag),
+        _ => false,
+    };
+    Ok((passthrough, changed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(passthrough: Option<&str>) -> AssetData {
+        serde_json::from_value(serde_json::json!({
+            "id": "asset123",
+            "status": "ready",
+            "created_at": "1700000000",
+            "playback_ids": [],
+            "tracks": [],
+            "passthrough": passthrough,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_apply_tags() {
+        let (passthrough, changed) = apply(TagAction::Add, &asset(None), Some("Demo")).unwrap();
+        assert!(changed);
+        assert_eq!(passthrough.tags(), vec!["demo"]);
+
+        let tagged = asset(Some(r#"{"tags":["demo"]}"#));
+        let (_, changed) = apply(TagAction::Add, &tagged, Some("demo")).unwrap();
+        assert!(!changed);
+        let (passthrough, changed) = apply(TagAction::Remove, &tagged, Some("demo")).unwrap();
+        assert!(changed);
+        assert!(passthrough.tags().is_empty());
+    }
+
+    #[test]
+    fn test_apply_keeps_foreign_passthrough() {
+        let foreign = asset(Some("customer-42"));
+        let error = apply(TagAction::Add, &foreign, Some("demo")).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<DomainError>(),
+            Some(DomainError::PassthroughInUse { .. })
+        ));
+
+        let (passthrough, changed) = apply(TagAction::List, &foreign, None).unwrap();
+        assert!(!changed);
+        assert!(passthrough.tags().is_empty());
+    }
+}
@@ -1,21 +1,155 @@
 use crate::api::auth::AuthManager;
+use crate::api::cache::ResponseCache;
 use crate::api::client::ApiClient;
 use crate::api::error::InfraError;
+use crate::api::pause;
+use crate::api::retry::RetryPolicy;
+use crate::api::thumbnail::{self, ThumbnailSize};
 use crate::api::types::{
-    AssetResponse, AssetsListResponse, DirectUploadResponse, MuxErrorResponse,
+    AssetData, AssetMeta, AssetPatch, AssetStatus, CHECKSUM_PASSTHROUGH_PREFIX,
+    CreateUploadRequest, DirectUploadResponse, Mp4Rendition, NewAssetSettingsRequest,
+    RenditionStatus, StaticRenditionRequest,
 };
-use crate::commands::result::{CommandResult, Mp4Status, UploadResult};
-use crate::config::{APP_CONFIG, UserConfig};
-use crate::domain::progress::{UploadPhase, UploadProgress};
-use crate::domain::validator;
+use crate::commands::connect_with;
+use crate::commands::result::{
+    CommandResult, DeletedAsset, FinalMedia, Mp4Status, TrackSummary, UploadEstimateResult,
+    UploadResult, UploadTimings, WaitUntil,
+};
+use crate::commands::wait;
+use crate::config::user::{CapacityPolicy, HookCommands, PlaybackPolicy};
+use crate::config::{APP_CONFIG, BITS_PER_MEGABIT, UserConfig};
+use crate::domain::error::DomainError;
+use crate::domain::estimate::UploadEstimate;
+use crate::domain::formatter;
+use crate::domain::progress::{
+    ProgressSender, ThroughputEstimator, UploadPhase, percent_complete, percent_of_duration,
+};
+use crate::domain::validator::{self, FileValidation};
+use crate::domain::warning::{Warning, WarningKind};
+use crate::hooks::{self, HookEvent};
+use crate::media::MediaTools;
+use crate::media::probe::{self, MediaProbe};
+use crate::media::transcode::{self, Transcode, TranscodeProgress};
+use crate::storage::asset_index::{AssetIndex, IndexedAsset};
+use crate::storage::recent_uploads::{RecentUpload, RecentUploads};
+use crate::storage::throughput::ThroughputRecord;
 use anyhow::{Context, Result, bail};
-use std::time::Duration;
-use tokio::time::sleep;
+use bytes::{Bytes, BytesMut};
+use futures_util::TryStreamExt;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+
+/// アップロードのオプション
+///
+/// `retry` で同じ条件のまま再実行できるよう、失敗の記録にも保存します。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct UploadOptions {
+    /// アップロード前にffprobeでメディアを解析する（ffprobeがない場合はスキップ）
+    pub probe: bool,
+    /// アップロードできる最大再生時間（秒）、Noneの場合は制限なし
+    pub max_duration_secs: Option<u64>,
+    /// アップロードせず、所要時間とエンコード量の見積もりのみ行う
+    pub dry_run: bool,
+    /// アップロード済みのファイルでもアップロードする（重複検出を無視）
+    pub force: bool,
+    /// アセット数の上限に達したときの動作（`--on-full`）、Noneの場合はconfig.tomlの設定
+    pub on_full: Option<CapacityPolicy>,
+    /// アセットが `ready` になるまで待ち、最終的なメディア情報を結果に含める
+    pub wait_ready: bool,
+    /// 作成するアセットの再生ポリシー（`--policy`）、Noneの場合はconfig.tomlの `defaults.playback_policy`（未設定時は `public`）
+    pub policy: Option<PlaybackPolicy>,
+    /// 拡張子の代わりに使う形式（`--force-format`）。拡張子とシグネチャの検証を省略する
+    pub force_format: Option<String>,
+    /// 送信前にffmpegで変換する際の出力の引数（`--transcode`、例: `-c:v libx264 -crf 20`）
+    ///
+    /// 変換の出力は一時ファイルを作らずにそのまま送信します。変換ごとに出力が変わるため、重複検出は行いません。
+    pub transcode: Option<String>,
+    /// 結果のサムネイル画像のURL（`thumbnail_url`）の大きさ（`--thumbnail-width` / `--thumbnail-height`）
+    pub thumbnail: ThumbnailSize,
+    /// アセット作成時に生成するStatic Rendition（`--mp4`、既定: `highest`）
+    pub mp4: Mp4Rendition,
+    /// アップロードを許可する形式（config.tomlの`allowed_formats`）、Noneの場合は `APP_CONFIG.upload.supported_formats`
+    ///
+    /// 実行時の設定から決まるため、失敗の記録には保存しません。
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub allowed_formats: Option<Vec<String>>,
+    /// 作成するアセットの作成者ID（config.tomlの`defaults.creator_id`）
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub creator_id: Option<String>,
+    /// 作成するアセットのタイトルのテンプレート（config.tomlの`defaults.title_template`）
+    ///
+    /// `{date}` は設定をマージした時点でアップロード日（config.tomlのタイムゾーン）に置き換え済みです。
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub title_template: Option<String>,
+    /// 同じファイル名・サイズの再アップロードを警告する期間（日、config.tomlの`recent_upload_days`）。0の場合は警告しない
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub recent_upload_days: u64,
+    /// アセット数の上限に達したときに、最も古いアセットの削除を確認する処理（`capacity_policy = "ask"`）
+    ///
+    /// 確認プロンプトはプレゼンテーション層が表示するため、CLIが単一ファイルの対話モードでのみ設定します。
+    /// 未設定の場合（`--machine`・複数ファイル・ライブラリからの呼び出し）、`ask` は `fail` と同じです。
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub confirm_delete: Option<ConfirmDelete>,
+    /// メディア解析・変換に使う外部ツール（既定では `PATH` から探す）
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub media_tools: MediaTools,
+}
+
+/// 削除候補のアセットを受け取り、削除を許可するか返す確認処理
+///
+/// アップロードを中断せずに確認するため、確認の間はDirect Uploadの作成で待機します。
+#[derive(Clone)]
+pub struct ConfirmDelete(Arc<ConfirmDeleteFn>);
+
+/// 削除候補のアセットを受け取り、削除を許可した場合はtrueを返す関数
+type ConfirmDeleteFn = dyn Fn(&DeletedAsset) -> Result<bool> + Send + Sync;
+
+impl ConfirmDelete {
+    pub fn new(confirm: impl Fn(&DeletedAsset) -> Result<bool> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(confirm))
+    }
+
+    /// 確認する（標準入力の読み取りで非同期ランタイムを止めないよう、ブロッキング用のスレッドで実行）
+    async fn ask(&self, candidate: &DeletedAsset) -> Result<bool> {
+        let confirm = Arc::clone(&self.0);
+        let candidate = candidate.clone();
+        tokio::task::spawn_blocking(move || confirm(&candidate))
+            .await
+            .context("Capacity confirmation task panicked")?
+    }
+}
+
+impl std::fmt::Debug for ConfirmDelete {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ConfirmDelete(..)")
+    }
+}
+
+impl PartialEq for ConfirmDelete {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
 
 /// アップロードコマンドを実行する
 ///
 /// # 引数
 /// * `file_path` - アップロード対象の動画ファイルのパス
+/// * `options` - アップロードのオプション（ユーザー設定の既定値とマージされる）
 /// * `progress_tx` - 進捗通知用チャネルの送信側（オプション）
 ///
 /// # 戻り値
@@ -26,76 +160,499 @@ use tokio::time::sleep;
 /// ドメイン層・インフラ層のエラーを集約する。
 pub async fn execute(
     file_path: &str,
-    progress_tx: Option<tokio::sync::mpsc::Sender<UploadProgress>>,
+    options: UploadOptions,
+    progress_tx: Option<ProgressSender>,
 ) -> Result<CommandResult> {
+    // ユーザー設定を読み込み
+    let user_config = UserConfig::load()
+        .context("Failed to load user configuration. Please check your config.toml file.")?;
+
+    let options = options.with_user_defaults(&user_config);
+
+    // --dry-run: 見積もりのみ（認証不要、フックは実行しない）
+    if options.dry_run {
+        let result = estimate(file_path, &options).await?;
+        return Ok(CommandResult::UploadEstimate(Box::new(result)));
+    }
+
+    // アップロード前フック（変換などでアップロードするファイルが差し替わる場合がある）
+    let file_path = &run_before_upload_hook(&user_config.hooks, file_path).await?;
+
+    // 認証マネージャーとAPIクライアントを初期化
+    let (client, auth_manager) = connect_with(&user_config)?;
+
+    let result = run(&client, &auth_manager, file_path, &options, progress_tx).await?;
+
+    record_upload(&result);
+
+    Ok(CommandResult::Upload(Box::new(result)))
+}
+
+impl UploadOptions {
+    /// ユーザー設定（config.toml）の既定値をマージする
+    ///
+    /// コマンドラインで有効にした項目は設定に関わらず有効のままです。
+    pub fn with_user_defaults(mut self, user_config: &UserConfig) -> Self {
+        self.probe |= user_config.probe;
+        self.max_duration_secs = self.max_duration_secs.or(user_config.max_duration_secs);
+        self.on_full = self.on_full.or(Some(user_config.capacity_policy));
+        self.policy = self.policy.or(user_config.defaults.playback_policy);
+        self.allowed_formats = self
+            .allowed_formats
+            .or_else(|| user_config.allowed_formats.clone());
+        self.creator_id = self
+            .creator_id
+            .or_else(|| user_config.defaults.creator_id.clone());
+        self.title_template = self.title_template.or_else(|| {
+            let template = user_config.defaults.title_template.as_deref()?;
+            let today = formatter::format_date(
+                &chrono::Utc::now().timestamp().to_string(),
+                user_config.timezone_offset_seconds,
+                "%Y-%m-%d",
+            )
+            .unwrap_or_default();
+            Some(formatter::fill_template(template, &[("date", &today)]))
+        });
+        self.recent_upload_days = user_config.recent_upload_days;
+        self
+    }
+
+    /// 作成するアセットのメタデータ（作成者ID・タイトル）、どちらも未設定の場合はNone
+    ///
+    /// タイトルはテンプレートの `{filename}`・`{stem}` をファイル名で置き換え、上限の文字数で切り詰めます。
+    fn asset_meta(&self, file_path: &str) -> Option<AssetMeta> {
+        let path = std::path::Path::new(file_path);
+        let title = self.title_template.as_deref().and_then(|template| {
+            let stem = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            let title = formatter::fill_template(
+                template,
+                &[("filename", file_name_of(file_path)), ("stem", stem)],
+            );
+            let title: String = title
+                .trim()
+                .chars()
+                .take(APP_CONFIG.metadata.max_title_chars)
+                .collect();
+            (!title.is_empty()).then_some(title)
+        });
+        if title.is_none() && self.creator_id.is_none() {
+            return None;
+        }
+        Some(AssetMeta {
+            title,
+            creator_id: self.creator_id.clone(),
+            external_id: None,
+        })
+    }
+
+    /// アップロードを許可する形式（`allowed_formats`、未設定の場合は組み込みの対応形式）
+    pub fn supported_formats(&self) -> Vec<&str> {
+        match &self.allowed_formats {
+            Some(formats) => formats.iter().map(String::as_str).collect(),
+            None => APP_CONFIG.upload.supported_formats.to_vec(),
+        }
+    }
+}
+
+/// アップロード前フック（config.tomlの`on_before_upload`）を実行し、アップロードするファイルを決める
+///
+/// フックにはファイルパスを標準入力（JSON）と`VIDYEET_FILE_PATH`で渡します。
+/// 標準出力の最終行にパスが出力された場合はそのファイルを、何も出力されなければ元のファイルをアップロードします。
+///
+/// # エラー
+/// フックが失敗した場合、または出力されたパスが存在しない場合は`InfraError::Hook`を返す
+pub async fn run_before_upload_hook(hooks: &HookCommands, file_path: &str) -> Result<String> {
+    let Some(command) = HookEvent::BeforeUpload.command(hooks) else {
+        return Ok(file_path.to_string());
+    };
+
+    let payload = serde_json::json!({
+        "command": "upload",
+        "file_path": file_path
+    });
+    let output = hooks::run(
+        HookEvent::BeforeUpload,
+        command,
+        &payload,
+        &[("FILE_PATH", file_path.to_string())],
+    )
+    .await
+    .with_context(|| format!("Pre-upload hook failed for '{}'", file_path))?;
+
+    let Some(rewritten) = output
+        .stdout
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+    else {
+        return Ok(file_path.to_string());
+    };
+
+    if !std::path::Path::new(rewritten).is_file() {
+        return Err(InfraError::hook(
+            HookEvent::BeforeUpload.config_key(),
+            format!("printed '{}', which is not an existing file", rewritten),
+        ))
+        .with_context(|| format!("Pre-upload hook failed for '{}'", file_path));
+    }
+
+    Ok(rewritten.to_string())
+}
+
+/// アップロードしたアセットをローカルインデックスと最近のアップロードの記録に記録する
+///
+/// ステータスなど未確定の情報は次回の `list` で更新されます。
+pub(crate) fn record_upload(result: &UploadResult) {
+    RecentUploads::record_default(RecentUpload {
+        file_name: file_name_of(&result.file_path).to_string(),
+        file_size: result.file_size,
+        asset_id: result.asset_id.clone(),
+        uploaded_at: chrono::Utc::now().timestamp().to_string(),
+    });
+    AssetIndex::update_default(|index| {
+        index.upsert(IndexedAsset {
+            asset_id: result.asset_id.clone(),
+            title: None,
+            external_id: None,
+            tags: Vec::new(),
+            status: "preparing".to_string(),
+            playback_id: result.playback_id.clone(),
+            playback_policy: result.playback_policy.clone(),
+            hls_url: result.hls_url.clone(),
+            mp4_url: result.mp4_url.clone(),
+            duration: None,
+            aspect_ratio: None,
+            created_at: chrono::Utc::now().timestamp().to_string(),
+            sha256: Some(result.sha256.clone()),
+            file_name: Some(file_name_of(&result.file_path).to_string()),
+            file_size: Some(result.file_size),
+        })
+    });
+}
+
+/// アップロード前の検査結果
+struct Inspection {
+    validation: FileValidation,
+    media: Option<MediaProbe>,
+    duration_secs: Option<f64>,
+    warnings: Vec<Warning>,
+}
+
+/// アップロード前にファイルを検査する
+///
+/// バリデーション、メディア解析（`--probe`指定時）、再生時間の上限チェックを行い、
+/// 再エンコードや品質低下につながる入力を警告として集めます。
+async fn inspect(file_path: &str, options: &UploadOptions) -> Result<Inspection> {
+    let validation = validate(file_path, options)?;
+
+    // 重複アップロードの検出（--force指定時はスキップ）
+    if !options.force
+        && let Some(asset_id) = find_duplicate(&validation)
+            .await
+            .context("Failed to check for duplicate uploads")?
+    {
+        return Err(DomainError::duplicate_upload(&validation.path, asset_id))
+            .context("File validation failed");
+    }
+
+    // メディア解析（--probe指定時、ffprobeがない環境ではスキップ）
+    let media = if options.probe {
+        probe::probe(&validation.path, &options.media_tools.ffprobe)
+            .await
+            .context("Media probe failed")?
+    } else {
+        None
+    };
+
+    // 再生時間の上限チェック（プランの制限をアップロード前に検出）
+    let duration_secs =
+        validator::media_duration(&validation, media.as_ref().and_then(|m| m.duration_secs));
+    if let Some(max_duration_secs) = options.max_duration_secs {
+        validator::validate_duration(&validation, duration_secs, max_duration_secs)
+            .context("File validation failed")?;
+    }
+
+    let mut warnings = media
+        .as_ref()
+        .map(|media| {
+            validator::media_warnings(
+                media,
+                &validation.extension,
+                APP_CONFIG.upload.video_quality,
+            )
+        })
+        .unwrap_or_default();
+
+    // 同じファイル名・サイズの直近のアップロード（--force指定時はスキップ）
+    if !options.force
+        && options.recent_upload_days > 0
+        && let Some(warning) = recent_upload_warning(
+            &RecentUploads::load_default(),
+            &validation,
+            options.recent_upload_days,
+            chrono::Utc::now(),
+        )
+    {
+        warnings.push(warning);
+    }
+
+    Ok(Inspection {
+        validation,
+        media,
+        duration_secs,
+        warnings,
+    })
+}
+
+/// 変換してアップロードする元ファイルを検査する（`--transcode`）
+///
+/// 形式は検証せず、変換の進捗率の計算に使う再生時間を `--probe` の指定に関わらずffprobeで取得します。
+/// 再エンコードに関する警告は変換後の出力には当てはまらないため集めず、重複検出も行いません。
+async fn inspect_transcode_source(file_path: &str, options: &UploadOptions) -> Result<Inspection> {
+    let validation = validator::validate_transcode_source(file_path, transcode::OUTPUT_FORMAT)
+        .context("File validation failed")?;
+
+    let media = probe::probe(&validation.path, &options.media_tools.ffprobe)
+        .await
+        .context("Media probe failed")?;
+    let duration_secs = media.as_ref().and_then(|m| m.duration_secs);
+    if let Some(max_duration_secs) = options.max_duration_secs {
+        validator::validate_duration(&validation, duration_secs, max_duration_secs)
+            .context("File validation failed")?;
+    }
+
+    Ok(Inspection {
+        validation,
+        media: media.filter(|_| options.probe),
+        duration_secs,
+        warnings: Vec::new(),
+    })
+}
+
+/// ファイルの形式とサイズを検証する（`--force-format` 指定時は拡張子とシグネチャの検証を省略）
+fn validate(file_path: &str, options: &UploadOptions) -> Result<FileValidation> {
+    let supported_formats = options.supported_formats();
+    match &options.force_format {
+        Some(format) => validator::validate_upload_file_as(file_path, format, &supported_formats),
+        None => validator::validate_upload_file(file_path, &supported_formats),
+    }
+    .context("File validation failed")
+}
+
+/// ローカルインデックスから、同じファイルをアップロード済みのアセットを探す
+///
+/// サイズが一致する候補がある場合のみファイルのSHA-256を計算して照合するため、
+/// 通常のアップロードでは追加の読み込みは発生しません。
+/// チェックサムが記録されていない候補はファイル名とサイズで判定します。
+async fn find_duplicate(validation: &FileValidation) -> Result<Option<String>> {
+    // インデックスは補助的なデータのため、読めない場合は検出をスキップ
+    let Ok(index) = AssetIndex::load_default() else {
+        return Ok(None);
+    };
+
+    let file_name = file_name_of(&validation.path);
+    let mut file_sha256: Option<String> = None;
+    for candidate in index.uploaded_with_size(validation.size) {
+        let is_duplicate = match &candidate.sha256 {
+            Some(recorded) => {
+                let actual = match &file_sha256 {
+                    Some(hash) => hash,
+                    None => file_sha256.insert(sha256_file(&validation.path).await?),
+                };
+                actual == recorded
+            }
+            None => candidate.file_name.as_deref() == Some(file_name),
+        };
+        if is_duplicate {
+            return Ok(Some(candidate.asset_id.clone()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// 同じファイル名・サイズのファイルを `days` 日以内にアップロードしていた場合の警告
+///
+/// アップロードスクリプトの誤った再実行を検出するためのもので、内容は照合しません。
+/// ローカルインデックスによる重複検出と異なり、アセットを削除した後も検出します。
+fn recent_upload_warning(
+    recent: &RecentUploads,
+    validation: &FileValidation,
+    days: u64,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<Warning> {
+    let window_secs = i64::try_from(days.saturating_mul(86_400)).unwrap_or(i64::MAX);
+    let file_name = file_name_of(&validation.path);
+    let upload = recent.find(
+        file_name,
+        validation.size,
+        now.timestamp().saturating_sub(window_secs),
+    )?;
+    let uploaded_at = upload
+        .uploaded_at
+        .parse::<i64>()
+        .ok()
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        .map_or_else(
+            || upload.uploaded_at.clone(),
+            |at| at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        );
+    Some(Warning::new(
+        WarningKind::RecentlyUploaded,
+        format!(
+            "{} ({}) was already uploaded at {} as asset {}; if this is an accidental re-run, stop it with Ctrl+C (use --force to skip this check)",
+            file_name,
+            formatter::format_size(validation.size),
+            uploaded_at,
+            upload.asset_id
+        ),
+    ))
+}
+
+/// ファイル全体のSHA-256を計算する（16進小文字）
+pub(crate) async fn sha256_file(file_path: &str) -> Result<String> {
+    let mut file = tokio::fs::File::open(file_path)
+        .await
+        .context("Failed to open file for checksum")?;
+    let mut digest = Sha256::new();
+    let mut buffer = vec![0u8; APP_CONFIG.upload.stream_read_buffer_size];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .await
+            .context("Failed to read file for checksum")?;
+        if read == 0 {
+            break;
+        }
+        digest.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", digest.finalize()))
+}
+
+/// パスからファイル名を取り出す（取り出せない場合はパス全体）
+pub(crate) fn file_name_of(path: &str) -> &str {
+    std::path::Path::new(path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(path)
+}
+
+/// アップロードせずに所要時間とエンコード量を見積もる（`upload --dry-run`）
+///
+/// 転送速度は直近のアップロードで計測した値を使います。
+/// 認証情報は不要です。
+pub async fn estimate(file_path: &str, options: &UploadOptions) -> Result<UploadEstimateResult> {
+    let Inspection {
+        validation,
+        media,
+        duration_secs,
+        warnings,
+    } = inspect(file_path, options).await?;
+
+    let measured = ThroughputRecord::load_default().map(|record| record.bytes_per_sec);
+    let estimate = UploadEstimate::new(
+        validation.size,
+        duration_secs,
+        APP_CONFIG.upload.video_quality,
+        measured,
+    );
+
+    Ok(UploadEstimateResult {
+        file_path: validation.path,
+        file_format: validation.extension,
+        estimate,
+        media,
+        warnings,
+    })
+}
+
+/// 認証済みクライアントで動画をアップロードする
+///
+/// 設定ファイルを読まないため、ライブラリ（`vidyeet::Client`）からも利用できます。
+///
+/// # 引数
+/// * `client` - APIクライアント
+/// * `auth_manager` - 認証マネージャー
+/// * `file_path` - アップロード対象の動画ファイルのパス
+/// * `options` - アップロードのオプション
+/// * `progress_tx` - 進捗通知用チャネルの送信側（オプション）
+pub async fn run(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    file_path: &str,
+    options: &UploadOptions,
+    progress_tx: Option<ProgressSender>,
+) -> Result<UploadResult> {
     // 進捗通知ヘルパー関数
     let notify = |phase: UploadPhase| {
-        let tx = progress_tx.clone();
-        async move {
-            if let Some(tx) = tx {
-                let _ = tx.send(UploadProgress::new(phase)).await;
-            }
+        if let Some(tx) = &progress_tx {
+            tx.send(phase);
         }
     };
 
+    let started_at = Instant::now();
+
     // ファイル検証開始
     notify(UploadPhase::ValidatingFile {
         file_path: file_path.to_string(),
-    })
-    .await;
-
-    // ユーザー設定を読み込み
-    let user_config = UserConfig::load()
-        .context("Failed to load user configuration. Please check your config.toml file.")?;
+    });
 
-    // 認証情報を取得
-    let auth = user_config
-        .get_auth()
-        .context("Authentication credentials not found. Please run 'vidyeet login' first.")?;
+    // バリデーション・メディア解析・再生時間チェック
+    let Inspection {
+        validation,
+        media,
+        duration_secs,
+        mut warnings,
+    } = match &options.transcode {
+        Some(_) => inspect_transcode_source(file_path, options).await?,
+        None => inspect(file_path, options).await?,
+    };
+    let validate_secs = elapsed_secs(started_at);
 
-    // ドメイン層のバリデーションを実行
-    let validation =
-        validator::validate_upload_file(file_path).context("File validation failed")?;
+    // 解析結果から再エンコードや品質低下につながる入力を警告
+    for warning in &warnings {
+        notify(UploadPhase::Warning {
+            warning: warning.clone(),
+        });
+    }
 
     // ファイル検証完了
     notify(UploadPhase::FileValidated {
-        file_name: std::path::Path::new(&validation.path)
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or(&validation.path)
-            .to_string(),
+        file_name: file_name_of(&validation.path).to_string(),
         size_bytes: validation.size,
         format: validation.extension.clone(),
-    })
-    .await;
-
-    // 認証マネージャーとAPIクライアントを初期化
-    let auth_manager = AuthManager::new(auth.token_id.clone(), auth.token_secret.clone());
-    let client = ApiClient::new(APP_CONFIG.api.endpoint.to_string())
-        .context("Failed to create API client")?;
+    });
 
     // Direct Upload URL作成開始
-    let file_name = std::path::Path::new(&validation.path)
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or(&validation.path)
-        .to_string();
+    let file_name = file_name_of(&validation.path).to_string();
 
     notify(UploadPhase::CreatingDirectUpload {
         file_name: file_name.clone(),
-    })
-    .await;
+    });
 
-    // Direct Uploadを開始（制限エラー時に古いものを削除して一度だけ再試行）
-    let (upload, deleted_count) = create_direct_upload_with_capacity(&client, &auth_manager)
-        .await
-        .context("Failed to create Direct Upload (with capacity handling)")?;
+    // Direct Uploadを開始（制限エラー時は、許可されていれば古いものを削除して一度だけ再試行）
+    let on_full = options.on_full.unwrap_or_default();
+    let create_started_at = Instant::now();
+    let (upload, deleted_assets) = create_direct_upload_with_capacity(
+        client,
+        auth_manager,
+        on_full,
+        options.confirm_delete.as_ref(),
+        options.policy.unwrap_or_default(),
+        options.mp4,
+        options.asset_meta(&validation.path).as_ref(),
+    )
+    .await
+    .context("Failed to create Direct Upload (with capacity handling)")?;
+    let create_upload_secs = elapsed_secs(create_started_at);
 
     // Direct Upload作成完了
     notify(UploadPhase::DirectUploadCreated {
         upload_id: upload.data.id.clone(),
-    })
-    .await;
+    });
 
     let upload_url = upload
         .data
@@ -103,112 +660,492 @@ pub async fn execute(
         .as_ref()
         .ok_or_else(|| anyhow::anyhow!("Upload URL not found in response"))?;
 
-    // total_chunksを事前計算
-    let chunk_size = APP_CONFIG.upload.chunk_size;
-    let total_chunks = ((validation.size as f64) / (chunk_size as f64)).ceil() as usize;
+    // ファイルをチャンクアップロード（--transcode 指定時は変換しながらその出力を送信）
+    let upload_started_at = Instant::now();
+    let ChunkedUpload {
+        bytes_sent,
+        bytes_per_sec,
+        sha256,
+    } = match &options.transcode {
+        Some(transcode_args) => {
+            match upload_transcoded(
+                client,
+                upload_url,
+                &validation.path,
+                transcode_args,
+                &options.media_tools.ffmpeg,
+                duration_secs,
+                progress_tx.clone(),
+            )
+            .await
+            {
+                Ok(uploaded) => uploaded,
+                Err(e) => {
+                    // 途中まで送信したDirect Uploadは完了できないため取り消す（取り消しの失敗は無視）
+                    let auth_header = auth_manager.get_auth_header();
+                    let _ = client.cancel_upload(&upload.data.id, &auth_header).await;
+                    return Err(e.context("Failed to transcode and upload file"));
+                }
+            }
+        }
+        None => {
+            // total_chunksを事前計算
+            let chunk_size = APP_CONFIG.upload.chunk_size;
+            let total_chunks = ((validation.size as f64) / (chunk_size as f64)).ceil() as usize;
+
+            // ファイルアップロード開始
+            notify(UploadPhase::UploadingFile {
+                file_name: file_name.clone(),
+                size_bytes: validation.size,
+                total_chunks,
+            });
+
+            upload_file_chunked(
+                client,
+                upload_url,
+                file_path,
+                APP_CONFIG.upload.get_content_type(&validation.extension),
+                validation.size,
+                duration_secs,
+                progress_tx.clone(),
+            )
+            .await
+            .context("Failed to upload file")?
+        }
+    };
+    let upload_secs = elapsed_secs(upload_started_at);
 
-    // ファイルアップロード開始
-    notify(UploadPhase::UploadingFile {
+    // 次回の見積もり（--dry-run）のために実効転送速度を記録
+    if bytes_per_sec > 0.0 {
+        ThroughputRecord {
+            bytes_per_sec,
+            measured_at: chrono::Utc::now().timestamp().to_string(),
+        }
+        .save_default();
+    }
+
+    // ファイルアップロード完了
+    notify(UploadPhase::FileUploaded {
         file_name: file_name.clone(),
-        size_bytes: validation.size,
-        total_chunks,
-    })
-    .await;
+        size_bytes: bytes_sent,
+    });
 
-    // ファイルをチャンクアップロード
-    upload_file_chunked(
-        &client,
-        upload_url,
-        file_path,
-        validation.size,
+    // アップロードとアセット作成の完了を待機
+    // （中断された場合に同じDirect Uploadから待機を再開できるよう、エラーにIDを添える）
+    let wait_started_at = Instant::now();
+    let asset = wait_for_asset(
+        client,
+        auth_manager,
+        &upload.data.id,
+        options,
         progress_tx.clone(),
+        &mut warnings,
     )
     .await
-    .context("Failed to upload file")?;
+    .with_context(|| {
+        format!(
+            "Failed to wait for upload completion; run 'vidyeet upload --resume-wait {}' to keep waiting",
+            upload.data.id
+        )
+    })?;
+    let wait_secs = elapsed_secs(wait_started_at);
 
-    // ファイルアップロード完了
-    notify(UploadPhase::FileUploaded {
-        file_name: file_name.clone(),
-        size_bytes: validation.size,
-    })
-    .await;
+    // 重複検出・整合性確認のためにチェックサムをpassthroughへ記録
+    // （アップロード自体は完了しているため、記録の失敗は警告にとどめる）
+    if let Err(e) = record_checksum(client, auth_manager, &asset.id, &sha256).await {
+        warnings.push(checksum_warning(e));
+    }
 
-    // アップロードとアセット作成の完了を待機
-    // wait_for_upload_completion内で初回のWaitingForAssetメッセージを送信
-    let asset =
-        wait_for_upload_completion(&client, &auth_manager, &upload.data.id, progress_tx.clone())
-            .await
-            .context("Failed to wait for upload completion")?;
+    // list/showのキャッシュに新しいアセットを反映させるため破棄
+    ResponseCache::invalidate_default();
 
     // 完了
     notify(UploadPhase::Completed {
-        asset_id: asset.data.id.clone(),
-    })
-    .await;
+        asset_id: asset.id.clone(),
+    });
 
-    // 結果を構造化して返す
+    // 変換した場合は、送信した変換後の出力のサイズとチェックサムを記録する
+    let file = UploadedFile {
+        path: validation.path,
+        size: bytes_sent,
+        format: validation.extension,
+        sha256,
+    };
+    Ok(build_result(
+        asset,
+        file,
+        media,
+        deleted_assets,
+        warnings,
+        options,
+        UploadTimings {
+            validate_secs,
+            create_upload_secs,
+            upload_secs,
+            wait_secs,
+            total_secs: elapsed_secs(started_at),
+            throughput_mbps: round_millis(bytes_per_sec * 8.0 / BITS_PER_MEGABIT),
+        },
+    ))
+}
+
+/// 既知のDirect Uploadのアセット作成を待ち、通常のアップロードと同じ結果を返す（`upload --resume-wait`）
+///
+/// ファイルの送信が終わった後、アセットが作成される前にCLIが終了した場合に、
+/// アップロードし直さずに結果を得るために使います。
+/// 元のファイルを指定した場合は検証とSHA-256の計算を行い、結果の `file_*`・`sha256` と
+/// アセットのチェックサムの記録に使います（重複検出は行いません）。
+///
+/// # 引数
+/// * `upload_id` - Direct UploadのID
+/// * `file_path` - 元のファイルのパス（オプション）
+/// * `options` - アップロードのオプション（`--wait-ready` などを使用）
+/// * `progress_tx` - 進捗通知用チャネルの送信側（オプション）
+pub async fn resume_wait(
+    upload_id: &str,
+    file_path: Option<&str>,
+    options: UploadOptions,
+    progress_tx: Option<ProgressSender>,
+) -> Result<CommandResult> {
+    let user_config = UserConfig::load()
+        .context("Failed to load user configuration. Please check your config.toml file.")?;
+    let options = options.with_user_defaults(&user_config);
+    let (client, auth_manager) = connect_with(&user_config)?;
+
+    let notify = |phase: UploadPhase| {
+        if let Some(tx) = &progress_tx {
+            tx.send(phase);
+        }
+    };
+
+    let started_at = Instant::now();
+    let file = match file_path {
+        Some(file_path) => {
+            notify(UploadPhase::ValidatingFile {
+                file_path: file_path.to_string(),
+            });
+            let validation = validate(file_path, &options)?;
+            let sha256 = sha256_file(&validation.path).await?;
+            notify(UploadPhase::FileValidated {
+                file_name: file_name_of(&validation.path).to_string(),
+                size_bytes: validation.size,
+                format: validation.extension.clone(),
+            });
+            Some(UploadedFile {
+                path: validation.path,
+                size: validation.size,
+                format: validation.extension,
+                sha256,
+            })
+        }
+        None => None,
+    };
+    let validate_secs = elapsed_secs(started_at);
+
+    let mut warnings = Vec::new();
+    let wait_started_at = Instant::now();
+    let asset = wait_for_asset(
+        &client,
+        &auth_manager,
+        upload_id,
+        &options,
+        progress_tx.clone(),
+        &mut warnings,
+    )
+    .await
+    .context("Failed to wait for upload completion")?;
+    let wait_secs = elapsed_secs(wait_started_at);
+
+    match &file {
+        Some(file) => {
+            if let Err(e) = record_checksum(&client, &auth_manager, &asset.id, &file.sha256).await
+            {
+                warnings.push(checksum_warning(e));
+            }
+        }
+        None => warnings.push(Warning::new(
+            WarningKind::ChecksumNotRecorded,
+            "No file was given; the checksum was not recorded and re-uploading the file will not be detected as a duplicate",
+        )),
+    }
+
+    ResponseCache::invalidate_default();
+    notify(UploadPhase::Completed {
+        asset_id: asset.id.clone(),
+    });
+
+    let has_file = file.is_some();
+    let result = build_result(
+        asset,
+        file.unwrap_or_default(),
+        None,
+        Vec::new(),
+        warnings,
+        &options,
+        UploadTimings {
+            validate_secs,
+            create_upload_secs: 0.0,
+            upload_secs: 0.0,
+            wait_secs,
+            total_secs: elapsed_secs(started_at),
+            throughput_mbps: 0.0,
+        },
+    );
+    // 重複検出はファイルのサイズとチェックサムで行うため、ファイルがない場合は記録しない
+    if has_file {
+        record_upload(&result);
+    }
+
+    Ok(CommandResult::Upload(Box::new(result)))
+}
+
+/// アップロードしたファイル（結果の `file_*`・`sha256`）
+///
+/// `--resume-wait` でファイルを指定しなかった場合は空のままです。
+#[derive(Debug, Default)]
+struct UploadedFile {
+    path: String,
+    size: u64,
+    format: String,
+    sha256: String,
+}
+
+/// Direct Uploadからアセットが作成されるのを待つ（`--wait-ready` 指定時は `ready` まで）
+///
+/// `ready` までの待機はアップロード自体の完了後のため、失敗しても警告にとどめます。
+async fn wait_for_asset(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    upload_id: &str,
+    options: &UploadOptions,
+    progress_tx: Option<ProgressSender>,
+    warnings: &mut Vec<Warning>,
+) -> Result<AssetData> {
+    // wait_for_asset_created内で初回のWaitingForAssetメッセージを送信
+    let asset = wait::wait_for_asset_created(
+        client,
+        auth_manager,
+        upload_id,
+        progress_tx.clone(),
+        APP_CONFIG.upload.max_wait_secs,
+    )
+    .await?
+    .data;
+
+    if !options.wait_ready {
+        return Ok(asset);
+    }
+    match wait::wait_for_state(
+        client,
+        auth_manager,
+        asset.clone(),
+        WaitUntil::Ready,
+        progress_tx,
+        APP_CONFIG.upload.wait_timeout_secs,
+    )
+    .await
+    {
+        Ok(ready) => Ok(ready),
+        Err(e) => {
+            warnings.push(Warning::new(
+                WarningKind::AssetNotReady,
+                format!(
+                    "Asset {} did not become ready; run 'vidyeet wait {}' to keep waiting: {:#}",
+                    asset.id, asset.id, e
+                ),
+            ));
+            Ok(asset)
+        }
+    }
+}
+
+/// チェックサムを記録できなかったことの警告
+fn checksum_warning(error: anyhow::Error) -> Warning {
+    Warning::new(
+        WarningKind::ChecksumNotRecorded,
+        format!(
+            "Failed to record checksum on asset; re-uploading this file will not be detected as a duplicate: {:#}",
+            error
+        ),
+    )
+}
+
+/// 作成されたアセットから結果を組み立てる
+fn build_result(
+    asset: AssetData,
+    file: UploadedFile,
+    media: Option<MediaProbe>,
+    deleted_assets: Vec<DeletedAsset>,
+    mut warnings: Vec<Warning>,
+    options: &UploadOptions,
+    timings: UploadTimings,
+) -> UploadResult {
     let hls_url = asset.get_playback_url();
-    let playback_id = asset.data.playback_ids.first().map(|p| p.id.clone());
+    let playback_id = asset.playback_ids.first().map(|p| p.id.clone());
+    let playback_policy = asset.playback_ids.first().map(|p| p.policy.clone());
+
+    // アセットの再生時間は処理が終わるまで分からないため、送信前の解析結果で補う
+    let duration = asset
+        .duration
+        .or_else(|| media.as_ref().and_then(|m| m.duration_secs));
+    let thumbnail_url = playback_id
+        .as_deref()
+        .map(|pid| thumbnail::thumbnail_url(pid, duration, options.thumbnail));
+
+    // MP4 URL: `--mp4` で選んだレンディションのファイル名から組み立てる（生成前でも同じURLになる）
+    let mp4_file_name = options.mp4.file_name();
+    let mp4_url = playback_id
+        .as_ref()
+        .zip(mp4_file_name)
+        .map(|(pid, name)| format!("https://stream.mux.com/{}/{}", pid, name));
+    let mp4_ready = asset.static_renditions.as_ref().is_some_and(|wrapper| {
+        wrapper
+            .files
+            .iter()
+            .any(|r| r.status == RenditionStatus::Ready && Some(r.name.as_str()) == mp4_file_name)
+    });
+    let mp4_status = match (mp4_file_name, mp4_ready) {
+        (None, _) => Mp4Status::Disabled,
+        (Some(_), true) => Mp4Status::Ready,
+        (Some(_), false) => Mp4Status::Generating,
+    };
+    if matches!(mp4_status, Mp4Status::Generating) && mp4_url.is_some() {
+        warnings.push(Warning::new(
+            WarningKind::Mp4Generating,
+            "MP4 is still being generated in the background (usually 2-5 minutes); the MP4 URL will work once it is ready. The HLS URL can be streamed now.",
+        ));
+    }
+    if !deleted_assets.is_empty() {
+        warnings.push(Warning::new(
+            WarningKind::AssetsAutoDeleted,
+            format!(
+                "Deleted {} old asset(s) because the video limit for your plan was reached",
+                deleted_assets.len()
+            ),
+        ));
+    }
 
-    // MP4 URLを取得: ready状態なら実URLを、それ以外なら予測URLを生成
-    let mp4_url_from_api = asset.get_mp4_playback_url();
-    let mp4_status = if mp4_url_from_api.is_some() {
-        Mp4Status::Ready
-    } else {
-        Mp4Status::Generating
+    let final_media = final_media(&asset);
+    UploadResult {
+        asset_id: asset.id,
+        playback_id,
+        playback_policy,
+        hls_url,
+        mp4_url,
+        mp4_status,
+        thumbnail_url,
+        file_path: file.path,
+        file_size: file.size,
+        file_format: file.format,
+        deleted_old_videos: deleted_assets.len(),
+        deleted_assets,
+        media,
+        warnings,
+        sha256: file.sha256,
+        uploaded_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        final_media,
+        copied: None,
+        timings,
+    }
+}
+
+/// アセットが `ready` の場合、Muxが処理した最終的なメディア情報を要約する
+fn final_media(asset: &AssetData) -> Option<FinalMedia> {
+    if asset.status != AssetStatus::Ready {
+        return None;
+    }
+    let tracks = asset
+        .tracks
+        .iter()
+        .flatten()
+        .map(|track| TrackSummary {
+            track_type: track.track_type.clone(),
+            width: track.max_width,
+            height: track.max_height,
+            frame_rate: track.max_frame_rate,
+            channel_layout: track.max_channel_layout.clone(),
+        })
+        .collect();
+    Some(FinalMedia {
+        duration: asset.duration,
+        resolution_tier: asset.resolution_tier.clone(),
+        aspect_ratio: asset.aspect_ratio.clone(),
+        tracks,
+    })
+}
+
+/// 経過時間（秒、ミリ秒単位に丸める）
+fn elapsed_secs(started_at: Instant) -> f64 {
+    round_millis(started_at.elapsed().as_secs_f64())
+}
+
+/// 小数点以下3桁に丸める
+fn round_millis(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+/// 元ファイルのSHA-256をアセットのpassthroughに記録する
+async fn record_checksum(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    asset_id: &str,
+    sha256: &str,
+) -> Result<()> {
+    let auth_header = auth_manager.get_auth_header();
+    let patch = AssetPatch {
+        passthrough: Some(format!("{}{}", CHECKSUM_PASSTHROUGH_PREFIX, sha256)),
+        ..Default::default()
     };
 
-    // MP4 URLが取得できない場合でも、playback_idがあれば予測URLを生成
-    let mp4_url = mp4_url_from_api.or_else(|| {
-        playback_id
-            .as_ref()
-            .map(|pid| format!("https://stream.mux.com/{}/highest.mp4", pid))
-    });
+    client
+        .patch_asset(asset_id, &patch, &auth_header)
+        .await
+        .context("Failed to update asset passthrough")?;
 
-    Ok(CommandResult::Upload(UploadResult {
-        asset_id: asset.data.id,
-        playback_id,
-        hls_url,
-        mp4_url,
-        mp4_status,
-        file_path: validation.path,
-        file_size: validation.size,
-        file_format: validation.extension,
-        deleted_old_videos: deleted_count,
-    }))
+    Ok(())
 }
 
 /// Direct Uploadを作成
 async fn create_direct_upload(
     client: &ApiClient,
     auth_manager: &AuthManager,
+    policy: PlaybackPolicy,
+    mp4: Mp4Rendition,
+    meta: Option<&AssetMeta>,
 ) -> Result<DirectUploadResponse> {
     let auth_header = auth_manager.get_auth_header();
 
     // Direct Upload作成リクエスト
-    let request_body = serde_json::json!({
-        "new_asset_settings": {
-            "playback_policies": ["public"],
-            "video_quality": "premium",
-            "max_resolution_tier": "2160p",
-            "static_renditions": [
-                { "resolution": "highest" },
-            ]
-        }
-    });
+    let request = CreateUploadRequest {
+        new_asset_settings: NewAssetSettingsRequest {
+            playback_policies: vec![policy.as_str().to_string()],
+            video_quality: APP_CONFIG.upload.video_quality.to_string(),
+            max_resolution_tier: "2160p".to_string(),
+            static_renditions: mp4
+                .resolution()
+                .map(|resolution| StaticRenditionRequest {
+                    resolution: resolution.to_string(),
+                })
+                .into_iter()
+                .collect(),
+            meta: meta.cloned(),
+        },
+    };
 
-    let response = client
-        .post("/video/v1/uploads", &request_body, Some(&auth_header))
+    let data = client
+        .create_upload(&request, &auth_header)
         .await
         .context("Failed to create Direct Upload")?;
 
-    let response = ApiClient::check_response(response, "/video/v1/uploads").await?;
-    let upload: DirectUploadResponse = ApiClient::parse_json(response).await?;
-
-    Ok(upload)
+    Ok(DirectUploadResponse { data })
 }
 
-/// 容量制限エラーに当たった場合、古いアセットを1つ削除して再試行する
+/// 容量制限エラーに当たった場合、`on_full` が `auto_delete` なら古いアセットを1つ削除して再試行する
+///
+/// `ask` では `confirm_delete` で最も古いアセットの削除を確認し、許可された場合はそのアセットのみを削除して
+/// Direct Uploadの作成だけを再試行します（検証やチェックサムの計算はやり直しません）。
+/// 確認できない（`confirm_delete` がない）・許可されなかった場合と `fail` では削除せず、
+/// 削除候補の最も古いアセットを添えて `DomainError::AssetLimitReached` を返します。
 ///
 /// Mux APIの制限系エラーを以下の条件で判定:
 /// - HTTP 429 (レート制限): Too Many Requests
@@ -216,96 +1153,148 @@ async fn create_direct_upload(
 async fn create_direct_upload_with_capacity(
     client: &ApiClient,
     auth_manager: &AuthManager,
-) -> Result<(DirectUploadResponse, usize)> {
-    match create_direct_upload(client, auth_manager).await {
-        Ok(upload) => Ok((upload, 0)),
-        Err(e) => {
-            let is_limit_error = is_capacity_limit_error(&e);
-
-            if is_limit_error {
+    on_full: CapacityPolicy,
+    confirm_delete: Option<&ConfirmDelete>,
+    policy: PlaybackPolicy,
+    mp4: Mp4Rendition,
+    meta: Option<&AssetMeta>,
+) -> Result<(DirectUploadResponse, Vec<DeletedAsset>)> {
+    match create_direct_upload(client, auth_manager, policy, mp4, meta).await {
+        Ok(upload) => Ok((upload, Vec::new())),
+        Err(e) if is_capacity_limit_error(&e) => match on_full {
+            CapacityPolicy::AutoDelete => {
                 // 最古のアセットを1つ削除して再試行
-                let deleted = delete_oldest_assets(client, auth_manager, 1).await?;
-                let upload = create_direct_upload(client, auth_manager).await?;
+                let deleted = delete_oldest_asset(client, auth_manager, None).await?;
+                let upload = create_direct_upload(client, auth_manager, policy, mp4, meta).await?;
                 Ok((upload, deleted))
-            } else {
-                Err(e)
             }
-        }
+            CapacityPolicy::Ask | CapacityPolicy::Fail => {
+                // 確認・案内のために削除候補を示す（一覧を取得できなくてもエラー自体は返す）
+                let oldest = fetch_oldest_first(client, auth_manager)
+                    .await
+                    .ok()
+                    .and_then(|assets| assets.first().map(deleted_asset_of));
+
+                // 確認では表示したアセットのみ削除を許可するため、候補が分からない場合は確認しない
+                if on_full == CapacityPolicy::Ask
+                    && let (Some(confirm_delete), Some(candidate)) = (confirm_delete, &oldest)
+                    && confirm_delete.ask(candidate).await?
+                {
+                    let deleted =
+                        delete_oldest_asset(client, auth_manager, Some(&candidate.asset_id))
+                            .await?;
+                    let upload =
+                        create_direct_upload(client, auth_manager, policy, mp4, meta).await?;
+                    return Ok((upload, deleted));
+                }
+
+                Err(DomainError::AssetLimitReached {
+                    oldest_asset_id: oldest.as_ref().map(|asset| asset.asset_id.clone()),
+                    oldest_title: oldest.as_ref().and_then(|asset| asset.title.clone()),
+                    oldest_created_at: oldest.map(|asset| asset.created_at),
+                }
+                .into())
+            }
+        },
+        Err(e) => Err(e),
     }
 }
 
-/// エラーが容量/クォータ制限に起因するかを判定
+/// 再試行で成功する見込みのある失敗か判定
 ///
-/// 判定条件:
-/// - HTTP 429: レート制限超過（Too Many Requests）
-/// - HTTP 400/422 かつ error.type が "invalid_parameters" かつ
-///   メッセージに "limited to" + "assets" を含む: 容量制限エラー
-fn is_capacity_limit_error(error: &anyhow::Error) -> bool {
-    // InfraError::Apiの場合、ステータスコードとメッセージを確認
-    if let Some(infra_err) = error.downcast_ref::<InfraError>()
-        && let InfraError::Api {
-            status_code,
-            message,
-            ..
-        } = infra_err
-    {
-        // HTTP 429はレート制限
-        if matches!(status_code, Some(429)) {
-            return true;
-        }
+/// エラーチェーン中のInfraErrorが、ネットワークエラー・タイムアウト・5xxの場合にtrue。
+/// キュー実行や複数ファイルアップロードで、項目を再試行するかの判断に使用します。
+pub(crate) fn is_transient_failure(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause
+            .downcast_ref::<InfraError>()
+            .is_some_and(|infra| match infra {
+                InfraError::Api {
+                    status_code: Some(status),
+                    ..
+                } => *status >= 500,
+                other => RetryPolicy::is_retryable_error(other),
+            })
+    })
+}
 
-        // HTTP 400/422の場合、JSONエラーレスポンスをパースして詳細に判定
-        if matches!(status_code, Some(400 | 422))
-            && let Ok(mux_error) = serde_json::from_str::<MuxErrorResponse>(message)
-        {
-            // error.typeが"invalid_parameters"でも、メッセージで容量制限を確認
-            if mux_error.error.error_type == "invalid_parameters" {
-                // メッセージに"limited to"と"assets"の両方が含まれる場合のみ制限エラー
-                let messages_text = mux_error.error.messages.join(" ").to_lowercase();
-                return messages_text.contains("limited to") && messages_text.contains("assets");
-            }
-        }
-    }
-    false
+/// エラーが容量/クォータ制限に起因するかを判定（判定条件は `InfraError::is_capacity_limit`）
+fn is_capacity_limit_error(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<InfraError>()
+        .is_some_and(InfraError::is_capacity_limit)
 }
 
-/// 最も古いアセットからcount件削除
+/// 全アセットを作成日時の古い順に取得
 ///
 /// Mux APIは新しいものから古いものの順（降順）でアセットを返すため、
-/// created_atでソートして最も古いアセットを特定します。
-async fn delete_oldest_assets(
+/// created_atでソートして最も古いアセットを先頭にします。
+async fn fetch_oldest_first(
     client: &ApiClient,
     auth_manager: &AuthManager,
-    count: usize,
-) -> Result<usize> {
+) -> Result<Vec<AssetData>> {
     let auth_header = auth_manager.get_auth_header();
-    let response = client
-        .get("/video/v1/assets?limit=100", Some(&auth_header))
+    let mut assets: Vec<AssetData> = client
+        .list_assets(&auth_header)
+        .try_collect()
         .await
         .context("Failed to fetch assets list for deletion")?;
 
-    let response = ApiClient::check_response(response, "/video/v1/assets").await?;
-    let assets_list: AssetsListResponse = ApiClient::parse_json(response).await?;
-
     // created_atでソートして最も古いものを特定（昇順）
-    let mut assets_sorted = assets_list.data;
-    assets_sorted.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-
-    let delete_targets = assets_sorted.iter().take(count);
-    let mut deleted = 0usize;
-    for asset in delete_targets {
-        let resp = client
-            .delete(
-                &format!("/video/v1/assets/{}", asset.id),
-                Some(&auth_header),
-            )
-            .await
-            .context(format!("Failed to delete asset {}", asset.id))?;
-        ApiClient::check_response(resp, &format!("/video/v1/assets/{}", asset.id)).await?;
-        deleted += 1;
+    sort_oldest_first(&mut assets);
+    Ok(assets)
+}
+
+/// 最も古いアセットを1つ削除し、削除したアセットを返す（アセットがない場合は空）
+///
+/// `confirmed` を指定した場合は、最も古いアセットがそのIDのときのみ削除します。
+/// 確認の間に他のアップロードや `prune` で最も古いアセットが変わった場合は、何も削除せずにエラーを返します。
+async fn delete_oldest_asset(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    confirmed: Option<&str>,
+) -> Result<Vec<DeletedAsset>> {
+    let auth_header = auth_manager.get_auth_header();
+    let assets_sorted = fetch_oldest_first(client, auth_manager).await?;
+    let oldest = assets_sorted.first();
+
+    if let Some(confirmed) = confirmed
+        && oldest.map(|asset| asset.id.as_str()) != Some(confirmed)
+    {
+        bail!(
+            "The oldest asset is no longer {} (it was deleted or changed while waiting for confirmation); \
+             nothing was deleted. Run the upload again to confirm the current oldest asset",
+            confirmed
+        );
+    }
+
+    let Some(asset) = oldest else {
+        return Ok(Vec::new());
+    };
+    client
+        .delete_asset(&asset.id, &auth_header)
+        .await
+        .context(format!("Failed to delete asset {}", asset.id))?;
+    AssetIndex::update_default(|index| index.remove(&asset.id));
+    ResponseCache::invalidate_default();
+
+    Ok(vec![deleted_asset_of(asset)])
+}
+
+/// 削除候補・削除したアセットとして結果やエラーに載せる情報
+fn deleted_asset_of(asset: &AssetData) -> DeletedAsset {
+    DeletedAsset {
+        asset_id: asset.id.clone(),
+        title: asset.title().map(str::to_string),
+        created_at: asset.created_at.clone(),
     }
+}
 
-    Ok(deleted)
+/// アセットを作成日時の古い順（昇順）に並べる
+///
+/// 容量超過時の自動削除と `prune` で、削除するアセットを同じ順序で選ぶために共通化しています。
+pub(crate) fn sort_oldest_first(assets: &mut [AssetData]) {
+    assets.sort_by(|a, b| a.created_at.cmp(&b.created_at));
 }
 
 /// ファイルをDirect Upload URLにアップロード（従来の一括アップロード、未使用）
@@ -342,7 +1331,11 @@ async fn upload_file(client: &ApiClient, upload_url: &str, file_path: &str) -> R
 /// # 設計
 /// - チャンクサイズ: 32MB（APP_CONFIG.upload.chunk_size）
 /// - Content-Rangeヘッダー: `bytes {start}-{end}/{total}`
+/// - 送信方式: 既定ではファイルの該当範囲をストリーミング送信（APP_CONFIG.upload.stream_chunks）
+///   し、チャンク全体をメモリに載せない。`mmap` featureが有効な場合はファイルをメモリマップし、
+///   チャンクをそのスライスとして送信する（readシステムコールとバッファ確保なし）
 /// - 進捗通知: チャンク完了ごとに UploadingChunk イベントを送信
+///   （指数平滑化した転送速度と推定残り時間を含む）
 /// - リトライ: 指数バックオフで最大3回
 /// - レスポンス: 308（継続）、200/201（完了）
 ///
@@ -350,17 +1343,18 @@ async fn upload_file(client: &ApiClient, upload_url: &str, file_path: &str) -> R
 /// * `client` - APIクライアント
 /// * `upload_url` - Direct Upload URL
 /// * `file_path` - アップロード対象ファイルのパス
+/// * `content_type` - 送信するContent-Type（検証済みの形式から決定）
 /// * `total_size` - ファイルの総サイズ（バイト）
 /// * `progress_tx` - 進捗通知チャネル
 async fn upload_file_chunked(
     client: &ApiClient,
     upload_url: &str,
     file_path: &str,
+    content_type: &str,
     total_size: u64,
-    progress_tx: Option<tokio::sync::mpsc::Sender<UploadProgress>>,
-) -> Result<()> {
-    use tokio::io::AsyncReadExt;
-
+    duration_secs: Option<f64>,
+    progress_tx: Option<ProgressSender>,
+) -> Result<ChunkedUpload> {
     let chunk_size = APP_CONFIG.upload.chunk_size;
     let total_chunks = ((total_size as f64) / (chunk_size as f64)).ceil() as usize;
 
@@ -369,17 +1363,41 @@ async fn upload_file_chunked(
         .await
         .context("Failed to open file for chunked upload")?;
 
-    // Content-Typeを推定
-    let content_type = std::path::Path::new(file_path)
-        .extension()
-        .and_then(|e| e.to_str())
-        .map(|ext| APP_CONFIG.upload.get_content_type(ext))
-        .unwrap_or("application/octet-stream");
-
     let mut bytes_sent: u64 = 0;
     let mut current_chunk = 0;
+    let mut throughput = ThroughputEstimator::new(APP_CONFIG.upload.throughput_smoothing_factor);
+    let upload_started = std::time::Instant::now();
+    let mut digest = Sha256::new();
+    let mapped =
+        map_file(file_path, total_size).context("Failed to map file for chunked upload")?;
+    let mut read_buffer = if mapped.is_some() || APP_CONFIG.upload.stream_chunks {
+        BytesMut::new()
+    } else {
+        BytesMut::with_capacity(chunk_size.min(total_size as usize))
+    };
+
+    let mut paused_duration = Duration::ZERO;
 
     loop {
+        // 一時停止中は次のチャンクを送信する前に再開を待つ
+        if pause::is_paused() && bytes_sent < total_size {
+            if let Some(ref tx) = progress_tx {
+                tx.send(UploadPhase::Paused {
+                    bytes_sent,
+                    total_bytes: total_size,
+                });
+            }
+            let paused_at = std::time::Instant::now();
+            pause::wait_until_resumed().await;
+            paused_duration += paused_at.elapsed();
+            if let Some(ref tx) = progress_tx {
+                tx.send(UploadPhase::Resumed {
+                    bytes_sent,
+                    total_bytes: total_size,
+                });
+            }
+        }
+
         current_chunk += 1;
 
         // チャンクサイズ分のバッファを用意（最終チャンクは残りサイズ）
@@ -394,11 +1412,24 @@ async fn upload_file_chunked(
             break; // 全て送信完了
         }
 
-        // チャンクを読み込み
-        let mut chunk_buffer = vec![0u8; this_chunk_size];
-        file.read_exact(&mut chunk_buffer)
-            .await
-            .context("Failed to read chunk from file")?;
+        // メモリマップ時はマップ領域をコピーせずに切り出す
+        // ストリーミング時はファイルの該当範囲を送信時に直接読み出す
+        // バッファ時はチャンクを読み込み（前のチャンクの送信完了後はその領域を再利用）
+        let chunk = if let Some(mapped) = &mapped {
+            let start = bytes_sent as usize;
+            ChunkPayload::Buffered(mapped.slice(start..start + this_chunk_size))
+        } else if APP_CONFIG.upload.stream_chunks {
+            ChunkPayload::Streamed {
+                file_path,
+                offset: bytes_sent,
+                len: this_chunk_size as u64,
+            }
+        } else {
+            let bytes = read_chunk(&mut file, &mut read_buffer, this_chunk_size)
+                .await
+                .context("Failed to read chunk from file")?;
+            ChunkPayload::Buffered(bytes)
+        };
 
         // Content-Rangeヘッダーを構築
         let byte_start = bytes_sent;
@@ -406,31 +1437,358 @@ async fn upload_file_chunked(
         let content_range = format!("bytes {}-{}/{}", byte_start, byte_end, total_size);
 
         // チャンクをアップロード（リトライ付き）
+        let chunk_started = std::time::Instant::now();
         upload_chunk_with_retry(
             client,
             upload_url,
-            chunk_buffer,
+            chunk,
             &content_range,
             content_type,
+            &mut digest,
         )
         .await?;
 
         bytes_sent += this_chunk_size as u64;
 
+        // 転送速度を更新（リトライ待機時間も含めた実効速度）
+        let bytes_per_sec = throughput.record(this_chunk_size as u64, chunk_started.elapsed());
+        let eta_secs = throughput.eta_secs(total_size - bytes_sent);
+
         // 進捗通知
         if let Some(ref tx) = progress_tx {
-            let _ = tx
-                .send(UploadProgress::new(UploadPhase::UploadingChunk {
-                    current_chunk,
-                    total_chunks,
-                    bytes_sent,
-                    total_bytes: total_size,
-                }))
-                .await;
+            tx.send(UploadPhase::UploadingChunk {
+                current_chunk,
+                total_chunks,
+                bytes_sent,
+                total_bytes: total_size,
+                percent: percent_complete(bytes_sent, total_size),
+                bytes_per_sec,
+                eta_secs,
+            });
+
+            // 最初のチャンクで計測した速度から全体の見積もりを通知
+            if current_chunk == 1 && current_chunk < total_chunks {
+                tx.send(UploadPhase::Estimate {
+                    estimate: UploadEstimate::new(
+                        total_size,
+                        duration_secs,
+                        APP_CONFIG.upload.video_quality,
+                        Some(bytes_per_sec),
+                    ),
+                });
+            }
         }
     }
 
-    Ok(())
+    // 実効転送速度（リトライ待機時間を含み、一時停止していた時間を除く全体の平均）
+    let elapsed = upload_started
+        .elapsed()
+        .saturating_sub(paused_duration)
+        .as_secs_f64();
+    Ok(ChunkedUpload {
+        bytes_sent,
+        bytes_per_sec: if elapsed > 0.0 {
+            total_size as f64 / elapsed
+        } else {
+            0.0
+        },
+        sha256: format!("{:x}", digest.finalize()),
+    })
+}
+
+/// チャンクアップロードの結果
+struct ChunkedUpload {
+    /// 送信したバイト数
+    bytes_sent: u64,
+    /// 実効転送速度（バイト/秒）
+    bytes_per_sec: f64,
+    /// ファイル全体のSHA-256（16進小文字）
+    sha256: String,
+}
+
+/// ffmpegで変換しながら、その出力をDirect Upload URLへチャンクアップロード（`--transcode`）
+///
+/// 出力の総サイズは変換が終わるまで分からないため、途中のチャンクは総サイズを `*` とした
+/// Content-Range（`bytes {start}-{end}/*`）で送り、最後のチャンクで総サイズを確定させます。
+/// 最後のチャンクはffmpegの正常終了を確認してから送るため、変換が途中で失敗した場合に
+/// 不完全な出力でアップロードが完了することはありません。
+///
+/// 変換と送信の進捗は `transcoding` として一定間隔で通知します。
+/// 一時停止（`p`）には対応しません（ffmpegの出力を待たせずに送り続けます）。
+async fn upload_transcoded(
+    client: &ApiClient,
+    upload_url: &str,
+    source_path: &str,
+    transcode_args: &str,
+    ffmpeg: &Path,
+    duration_secs: Option<f64>,
+    progress_tx: Option<ProgressSender>,
+) -> Result<ChunkedUpload> {
+    let mut transcode = transcode::spawn(source_path, transcode_args, ffmpeg)?;
+    let bytes_sent = Arc::new(AtomicU64::new(0));
+    let started = Instant::now();
+
+    let reporter = progress_tx.map(|tx| {
+        tokio::spawn(report_transcode_progress(
+            tx,
+            transcode.progress(),
+            Arc::clone(&bytes_sent),
+            duration_secs,
+            started,
+        ))
+    });
+    let result = send_transcoded_output(client, upload_url, &mut transcode, &bytes_sent).await;
+    if let Some(reporter) = reporter {
+        reporter.abort();
+    }
+    let sha256 = result?;
+
+    let total = bytes_sent.load(Ordering::Relaxed);
+    let elapsed = started.elapsed().as_secs_f64();
+    Ok(ChunkedUpload {
+        bytes_sent: total,
+        bytes_per_sec: if elapsed > 0.0 {
+            total as f64 / elapsed
+        } else {
+            0.0
+        },
+        sha256,
+    })
+}
+
+/// 変換の出力をチャンクに分けて送信し、出力全体のSHA-256を返す
+///
+/// 送信済みのバイト数は`bytes_sent`に反映します（進捗の通知に使用）。
+async fn send_transcoded_output(
+    client: &ApiClient,
+    upload_url: &str,
+    transcode: &mut Transcode,
+    bytes_sent: &AtomicU64,
+) -> Result<String> {
+    let chunk_size = APP_CONFIG.upload.chunk_size;
+    let content_type = APP_CONFIG.upload.get_content_type(transcode::OUTPUT_FORMAT);
+    let mut buffer = BytesMut::new();
+    let mut digest = Sha256::new();
+    let mut offset: u64 = 0;
+
+    loop {
+        let chunk = read_up_to(transcode.output(), &mut buffer, chunk_size)
+            .await
+            .context("Failed to read transcoded output")?;
+        // チャンクサイズに満たない場合は出力の終わり
+        let is_last = chunk.len() < chunk_size;
+        if is_last {
+            transcode.finish().await?;
+        }
+
+        let total = offset + chunk.len() as u64;
+        let max_file_size = APP_CONFIG.upload.max_file_size;
+        if total > max_file_size {
+            return Err(DomainError::FileTooLarge {
+                size: total,
+                max: max_file_size,
+            })
+            .context("Transcoded output is too large");
+        }
+        if is_last && total == 0 {
+            return Err(InfraError::transcode("ffmpeg produced no output").into());
+        }
+
+        let content_range = streamed_content_range(offset, chunk.len(), is_last.then_some(total));
+        upload_chunk_with_retry(
+            client,
+            upload_url,
+            ChunkPayload::Buffered(chunk),
+            &content_range,
+            content_type,
+            &mut digest,
+        )
+        .await?;
+
+        offset = total;
+        bytes_sent.store(offset, Ordering::Relaxed);
+        if is_last {
+            return Ok(format!("{:x}", digest.finalize()));
+        }
+    }
+}
+
+/// 総サイズが確定していない送信のContent-Range
+///
+/// 途中のチャンクは総サイズを `*` とし、最後のチャンクで総サイズを指定します。
+/// 出力がチャンクサイズの倍数で終わった場合、最後のチャンクは空になるため範囲を `*` とします。
+fn streamed_content_range(offset: u64, len: usize, total: Option<u64>) -> String {
+    let total = total.map_or_else(|| "*".to_string(), |total| total.to_string());
+    if len == 0 {
+        return format!("bytes */{}", total);
+    }
+    format!("bytes {}-{}/{}", offset, offset + len as u64 - 1, total)
+}
+
+/// 最大`len`バイトを読み込む（終端に達した場合はそれまでに読み込んだ分）
+///
+/// 読み込み先の`BytesMut`は`read_chunk`と同じくチャンク間で使い回します。
+async fn read_up_to<R>(reader: &mut R, buffer: &mut BytesMut, len: usize) -> std::io::Result<Bytes>
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    buffer.clear();
+    buffer.reserve(len);
+    while buffer.len() < len {
+        let remaining = (len - buffer.len()) as u64;
+        if (&mut *reader).take(remaining).read_buf(buffer).await? == 0 {
+            break;
+        }
+    }
+    Ok(buffer.split().freeze())
+}
+
+/// 変換と送信の進捗を一定間隔で通知する（送信が終わるまで続け、呼び出し側が中断する）
+async fn report_transcode_progress(
+    progress_tx: ProgressSender,
+    progress: TranscodeProgress,
+    bytes_sent: Arc<AtomicU64>,
+    duration_secs: Option<f64>,
+    started: Instant,
+) {
+    let mut ticker = tokio::time::interval(Duration::from_millis(
+        APP_CONFIG.upload.transcode_progress_interval_ms,
+    ));
+    loop {
+        ticker.tick().await;
+        let out_time_secs = progress.out_time_secs();
+        let bytes_sent = bytes_sent.load(Ordering::Relaxed);
+        let elapsed = started.elapsed().as_secs_f64();
+        progress_tx.send(UploadPhase::Transcoding {
+            out_time_secs: round_millis(out_time_secs),
+            duration_secs,
+            percent: percent_of_duration(out_time_secs, duration_secs),
+            bytes_sent,
+            bytes_per_sec: if elapsed > 0.0 {
+                bytes_sent as f64 / elapsed
+            } else {
+                0.0
+            },
+        });
+    }
+}
+
+/// 送信するチャンクの中身
+///
+/// リトライ時に同じ内容を再送できるよう、送信のたびに`reqwest::Body`を作り直します。
+enum ChunkPayload<'a> {
+    /// メモリ上に読み込んだチャンク（クローンは参照カウントのみ）
+    Buffered(Bytes),
+    /// ファイルの範囲（送信時にストリーミングで読み出し、チャンク全体をメモリに載せない）
+    Streamed {
+        file_path: &'a str,
+        offset: u64,
+        len: u64,
+    },
+}
+
+impl ChunkPayload<'_> {
+    /// チャンクのバイト数
+    fn len(&self) -> u64 {
+        match self {
+            Self::Buffered(bytes) => bytes.len() as u64,
+            Self::Streamed { len, .. } => *len,
+        }
+    }
+
+    /// 送信用のボディを作成する
+    ///
+    /// 送信する内容を`digest`にも通します。ストリーミング時は送信しながら計算するため、
+    /// ファイルを読み直す必要はありません。
+    async fn to_body(&self, digest: Arc<Mutex<Sha256>>) -> std::io::Result<reqwest::Body> {
+        match self {
+            Self::Buffered(bytes) => {
+                lock_digest(&digest).update(bytes);
+                Ok(reqwest::Body::from(bytes.clone()))
+            }
+            Self::Streamed {
+                file_path,
+                offset,
+                len,
+            } => Ok(reqwest::Body::wrap_stream(
+                hashed_file_range_stream(file_path, *offset, *len, digest).await?,
+            )),
+        }
+    }
+}
+
+/// ファイルの範囲を読み出しながら、読み出した内容を`digest`に通すストリームを作成する
+async fn hashed_file_range_stream(
+    file_path: &str,
+    offset: u64,
+    len: u64,
+    digest: Arc<Mutex<Sha256>>,
+) -> std::io::Result<impl futures_util::Stream<Item = std::io::Result<Bytes>> + use<>> {
+    Ok(file_range_stream(file_path, offset, len)
+        .await?
+        .inspect_ok(move |bytes| lock_digest(&digest).update(bytes)))
+}
+
+/// ハッシュ状態をロックする（計算中のパニックでロックが汚染されても状態はそのまま使う）
+fn lock_digest(digest: &Mutex<Sha256>) -> std::sync::MutexGuard<'_, Sha256> {
+    digest
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// ファイルの`offset`から`len`バイトを読み出すストリームを作成する
+async fn file_range_stream(
+    file_path: &str,
+    offset: u64,
+    len: u64,
+) -> std::io::Result<ReaderStream<tokio::io::Take<tokio::fs::File>>> {
+    let mut file = tokio::fs::File::open(file_path).await?;
+    file.seek(std::io::SeekFrom::Start(offset)).await?;
+    Ok(ReaderStream::with_capacity(
+        file.take(len),
+        APP_CONFIG.upload.stream_read_buffer_size,
+    ))
+}
+
+/// ファイルをメモリマップし、`Bytes`として扱えるようにする（`mmap` feature有効時）
+///
+/// 空ファイルはマップできない環境があるため`None`を返します。
+#[cfg(feature = "mmap")]
+fn map_file(file_path: &str, total_size: u64) -> std::io::Result<Option<Bytes>> {
+    if total_size == 0 {
+        return Ok(None);
+    }
+
+    let file = std::fs::File::open(file_path)?;
+    // SAFETY: マップ中に他のプロセスがファイルを切り詰めると、該当領域へのアクセスで
+    // SIGBUSとなる可能性がある。アップロード中のファイルを書き換えないことを前提とし、
+    // この制約はfeatureの説明（README）に明記している。
+    let mmap = unsafe { memmap2::Mmap::map(&file)? };
+    Ok(Some(Bytes::from_owner(mmap)))
+}
+
+/// `mmap` feature無効時はメモリマップしない
+#[cfg(not(feature = "mmap"))]
+fn map_file(_file_path: &str, _total_size: u64) -> std::io::Result<Option<Bytes>> {
+    Ok(None)
+}
+
+/// ファイルから次のチャンクを読み込む
+///
+/// 読み込み先の`BytesMut`はチャンク間で使い回します。
+/// 返した`Bytes`（前のチャンク）が送信・リトライを終えて破棄されていれば、
+/// `reserve`がその領域を回収するため、チャンクごとの新規確保は発生しません。
+/// `Bytes`のクローンは参照カウントの増加のみで、リトライ時もデータはコピーされません。
+async fn read_chunk<R>(reader: &mut R, buffer: &mut BytesMut, len: usize) -> std::io::Result<Bytes>
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    buffer.clear();
+    buffer.reserve(len);
+    // 未初期化領域への書き込み（unsafe）を避けるためゼロ埋めしてから読み込む
+    buffer.resize(len, 0);
+    reader.read_exact(&mut buffer[..]).await?;
+    Ok(buffer.split().freeze())
 }
 
 /// チャンクを指数バックオフでリトライしながらアップロード
@@ -438,22 +1796,38 @@ async fn upload_file_chunked(
 /// # 引数
 /// * `client` - APIクライアント
 /// * `upload_url` - Direct Upload URL
-/// * `chunk_data` - チャンクのバイトデータ
+/// * `chunk` - チャンクの中身（リトライ時はボディを作り直して再送、データのコピーは発生しない）
 /// * `content_range` - Content-Rangeヘッダー値
 /// * `content_type` - Content-Type
 async fn upload_chunk_with_retry(
     client: &ApiClient,
     upload_url: &str,
-    chunk_data: Vec<u8>,
+    chunk: ChunkPayload<'_>,
     content_range: &str,
     content_type: &str,
+    digest: &mut Sha256,
 ) -> Result<()> {
     let max_retries = APP_CONFIG.upload.max_retries;
     let backoff_base_ms = APP_CONFIG.upload.backoff_base_ms;
 
     for attempt in 0..max_retries {
-        match upload_chunk(client, upload_url, &chunk_data, content_range, content_type).await {
-            Ok(_) => return Ok(()),
+        // 試行ごとにここまでのハッシュ状態を複製し、送信に成功した試行の分だけを確定させる
+        // （リトライで同じチャンクを二重に計算しない）
+        let attempt_digest = Arc::new(Mutex::new(digest.clone()));
+        match upload_chunk(
+            client,
+            upload_url,
+            &chunk,
+            content_range,
+            content_type,
+            attempt_digest.clone(),
+        )
+        .await
+        {
+            Ok(_) => {
+                *digest = lock_digest(&attempt_digest).clone();
+                return Ok(());
+            }
             Err(e) if attempt < max_retries - 1 => {
                 // 指数バックオフ: 1秒、2秒、4秒...
                 let backoff_ms = backoff_base_ms * (2_u64.pow(attempt));
@@ -480,36 +1854,33 @@ async fn upload_chunk_with_retry(
 
 /// 単一チャンクをアップロード
 ///
+/// ApiClientのコネクションプールを再利用して送信します。
+///
 /// # レスポンスコード
 /// - 308: Resume Incomplete（継続中）
 /// - 200/201: Success（完了）
 async fn upload_chunk(
-    _client: &ApiClient,
+    client: &ApiClient,
     upload_url: &str,
-    chunk_data: &[u8],
+    chunk: &ChunkPayload<'_>,
     content_range: &str,
     content_type: &str,
+    digest: Arc<Mutex<Sha256>>,
 ) -> Result<()> {
-    // reqwestクライアントを直接使用してContent-Rangeヘッダーを設定
-    let reqwest_client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(APP_CONFIG.api.timeout_seconds))
-        .build()
-        .context("Failed to build reqwest client")?;
-
-    let response = reqwest_client
-        .put(upload_url)
-        .header("Content-Type", content_type)
-        .header("Content-Length", chunk_data.len().to_string())
-        .header("Content-Range", content_range)
-        .body(chunk_data.to_vec())
-        .send()
+    let body = chunk
+        .to_body(digest)
+        .await
+        .context("Failed to read chunk from file")?;
+
+    let response = client
+        .put_chunk(upload_url, body, chunk.len(), content_type, content_range)
         .await
         .context("Failed to send chunk PUT request")?;
 
     let status = response.status();
 
     // 308 (Resume Incomplete) または 2xx (Success) なら成功
-    if status == reqwest::StatusCode::from_u16(308).unwrap() || status.is_success() {
+    if status == reqwest::StatusCode::PERMANENT_REDIRECT || status.is_success() {
         return Ok(());
     }
 
@@ -521,106 +1892,387 @@ async fn upload_chunk(
     bail!("Chunk upload failed with status {}: {}", status, error_body)
 }
 
-/// アップロードとアセット作成の完了を待機
-///
-/// Direct Uploadのステータスをポーリングし、`asset_created`状態になるまで待機します。
-/// この時点でHLS再生が可能ですが、MP4 static renditionはバックグラウンドで
-/// 生成中の場合があります。
-///
-/// # 設計意図
-/// CLIの役割は「アップロードとアセット作成の完了確認」までとし、
-/// MP4生成（数分かかる可能性）は待たずにMux側に任せます。
-/// これにより、ユーザーはすぐにHLS URLでストリーミングを開始でき、
-/// MP4は後で生成完了時にアクセスできます。
-async fn wait_for_upload_completion(
-    client: &ApiClient,
-    auth_manager: &AuthManager,
-    upload_id: &str,
-    progress_tx: Option<tokio::sync::mpsc::Sender<UploadProgress>>,
-) -> Result<AssetResponse> {
-    let auth_header = auth_manager.get_auth_header();
-    let max_iterations = APP_CONFIG.upload.max_wait_secs / APP_CONFIG.upload.poll_interval_secs;
-    let start_time = std::time::Instant::now();
-
-    // 初回の待機メッセージを送信
-    if let Some(ref tx) = progress_tx {
-        let _ = tx
-            .send(UploadProgress::new(UploadPhase::WaitingForAsset {
-                upload_id: upload_id.to_string(),
-                elapsed_secs: 0,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::user::DefaultsConfig;
+
+    #[test]
+    fn test_network_errors_are_transient() {
+        let error = anyhow::Error::new(InfraError::network("connection reset"))
+            .context("Failed to upload file");
+        assert!(is_transient_failure(&error));
+
+        let error = anyhow::Error::new(InfraError::api("/video/v1/uploads", "oops", Some(503)));
+        assert!(is_transient_failure(&error));
+    }
+
+    #[test]
+    fn test_permanent_errors_are_not_transient() {
+        let error = anyhow::Error::new(InfraError::api("/video/v1/uploads", "bad", Some(400)));
+        assert!(!is_transient_failure(&error));
+
+        let error = anyhow::Error::new(DomainError::file_not_found("/videos/missing.mp4"))
+            .context("File validation failed");
+        assert!(!is_transient_failure(&error));
+    }
+
+    #[test]
+    fn test_supported_formats_from_user_config() {
+        let defaults = UploadOptions::default();
+        assert_eq!(
+            defaults.supported_formats(),
+            APP_CONFIG.upload.supported_formats
+        );
+
+        let user_config = UserConfig {
+            allowed_formats: Some(vec!["mp4".to_string(), "mov".to_string()]),
+            ..Default::default()
+        };
+        let options = defaults.with_user_defaults(&user_config);
+        assert_eq!(options.supported_formats(), ["mp4", "mov"]);
+    }
+
+    #[test]
+    fn test_playback_policy_from_user_config() {
+        let user_config = UserConfig {
+            defaults: DefaultsConfig {
+                playback_policy: Some(PlaybackPolicy::Signed),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let options = UploadOptions::default().with_user_defaults(&user_config);
+        assert_eq!(options.policy, Some(PlaybackPolicy::Signed));
+
+        // --policy の指定は設定より優先
+        let options = UploadOptions {
+            policy: Some(PlaybackPolicy::Public),
+            ..Default::default()
+        }
+        .with_user_defaults(&user_config);
+        assert_eq!(options.policy, Some(PlaybackPolicy::Public));
+
+        let options = UploadOptions::default().with_user_defaults(&UserConfig::default());
+        assert_eq!(options.policy.unwrap_or_default(), PlaybackPolicy::Public);
+    }
+
+    #[test]
+    fn test_asset_meta_from_user_config() {
+        let user_config = UserConfig {
+            defaults: DefaultsConfig {
+                creator_id: Some("team-a".to_string()),
+                title_template: Some("{stem} ({filename}) {date}".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let options = UploadOptions::default().with_user_defaults(&user_config);
+        let meta = options
+            .asset_meta("/videos/clip.mp4")
+            .expect("meta should be set");
+        assert_eq!(meta.creator_id.as_deref(), Some("team-a"));
+        let title = meta.title.unwrap();
+        assert!(title.starts_with("clip (clip.mp4) 20"), "{}", title);
+        assert!(!title.contains("{date}"));
+
+        assert!(
+            UploadOptions::default()
+                .with_user_defaults(&UserConfig::default())
+                .asset_meta("/videos/clip.mp4")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_recent_upload_warning() {
+        let now = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let mut recent = RecentUploads::default();
+        recent.push(RecentUpload {
+            file_name: "clip.mp4".to_string(),
+            file_size: 1024,
+            asset_id: "asset_1".to_string(),
+            uploaded_at: (1_700_000_000 - 2 * 86_400).to_string(),
+        });
+        let validation = |path: &str, size: u64| FileValidation {
+            path: path.to_string(),
+            size,
+            extension: "mp4".to_string(),
+        };
+
+        let warning =
+            recent_upload_warning(&recent, &validation("/videos/clip.mp4", 1024), 7, now).unwrap();
+        assert_eq!(warning.kind, WarningKind::RecentlyUploaded);
+        assert!(warning.message.contains("asset_1"), "{}", warning.message);
+        assert!(
+            warning.message.contains("2023-11-12T22:13:20Z"),
+            "{}",
+            warning.message
+        );
+
+        // 期間外・サイズ違いは警告しない
+        assert!(recent_upload_warning(&recent, &validation("clip.mp4", 1024), 1, now).is_none());
+        assert!(recent_upload_warning(&recent, &validation("clip.mp4", 2048), 7, now).is_none());
+    }
+
+    #[test]
+    fn test_round_millis() {
+        assert_eq!(round_millis(1.23456), 1.235);
+        assert_eq!(round_millis(0.0004), 0.0);
+        // 8 Mbps = 1,000,000 bytes/s
+        assert_eq!(round_millis(1_000_000.0 * 8.0 / BITS_PER_MEGABIT), 8.0);
+    }
+
+    #[test]
+    fn test_final_media_only_when_ready() {
+        let asset = |status: &str| -> AssetData {
+            serde_json::from_value(serde_json::json!({
+                "id": "asset_1",
+                "status": status,
+                "created_at": "1700000000",
+                "duration": 12.5,
+                "resolution_tier": "1080p",
+                "aspect_ratio": "16:9",
+                "tracks": [
+                    { "type": "video", "id": "t1", "max_width": 1920, "max_height": 1080, "max_frame_rate": 30.0 },
+                    { "type": "audio", "id": "t2", "max_channel_layout": "stereo" }
+                ]
             }))
-            .await;
+            .unwrap()
+        };
+
+        assert_eq!(final_media(&asset("preparing")), None);
+
+        let media = final_media(&asset("ready")).unwrap();
+        assert_eq!(media.duration, Some(12.5));
+        assert_eq!(media.resolution_tier.as_deref(), Some("1080p"));
+        assert_eq!(media.aspect_ratio.as_deref(), Some("16:9"));
+        assert_eq!(media.tracks.len(), 2);
+        assert_eq!(media.tracks[0].width, Some(1920));
+        assert_eq!(media.tracks[1].channel_layout.as_deref(), Some("stereo"));
+    }
+
+    #[test]
+    fn test_build_result_thumbnail_url() {
+        let asset: AssetData = serde_json::from_value(serde_json::json!({
+            "id": "asset_1",
+            "status": "ready",
+            "created_at": "1700000000",
+            "duration": 130.0,
+            "playback_ids": [{ "id": "play_1", "policy": "public" }]
+        }))
+        .unwrap();
+        let options = UploadOptions {
+            thumbnail: ThumbnailSize {
+                width: Some(640),
+                height: None,
+            },
+            ..Default::default()
+        };
+
+        let result = build_result(
+            asset,
+            UploadedFile::default(),
+            None,
+            Vec::new(),
+            Vec::new(),
+            &options,
+            UploadTimings::default(),
+        );
+        assert_eq!(
+            result.thumbnail_url.as_deref(),
+            Some("https://image.mux.com/play_1/thumbnail.jpg?time=13&width=640")
+        );
     }
 
-    for _i in 0..max_iterations {
-        // Upload情報を取得
-        let response = client
-            .get(
-                &format!("/video/v1/uploads/{}", upload_id),
-                Some(&auth_header),
+    #[test]
+    fn test_build_result_mp4_url_follows_chosen_rendition() {
+        let asset: AssetData = serde_json::from_value(serde_json::json!({
+            "id": "asset_1",
+            "status": "ready",
+            "created_at": "1700000000",
+            "playback_ids": [{ "id": "play_1", "policy": "public" }],
+            "static_renditions": { "files": [
+                {
+                    "id": "r1", "type": "standard", "status": "ready",
+                    "resolution": "720p", "name": "720p.mp4", "ext": "mp4"
+                },
+                {
+                    "id": "r2", "type": "standard", "status": "preparing",
+                    "resolution": "audio-only", "name": "audio.m4a", "ext": "m4a"
+                }
+            ]}
+        }))
+        .unwrap();
+        let result_for = |mp4| {
+            build_result(
+                asset.clone(),
+                UploadedFile::default(),
+                None,
+                Vec::new(),
+                Vec::new(),
+                &UploadOptions {
+                    mp4,
+                    ..Default::default()
+                },
+                UploadTimings::default(),
             )
+        };
+
+        let result = result_for(Mp4Rendition::P720);
+        assert_eq!(
+            result.mp4_url.as_deref(),
+            Some("https://stream.mux.com/play_1/720p.mp4")
+        );
+        assert!(matches!(result.mp4_status, Mp4Status::Ready));
+
+        let result = result_for(Mp4Rendition::AudioOnly);
+        assert_eq!(
+            result.mp4_url.as_deref(),
+            Some("https://stream.mux.com/play_1/audio.m4a")
+        );
+        assert!(matches!(result.mp4_status, Mp4Status::Generating));
+        assert!(
+            result
+                .warnings
+                .iter()
+                .any(|w| w.kind == WarningKind::Mp4Generating)
+        );
+
+        let result = result_for(Mp4Rendition::None);
+        assert_eq!(result.mp4_url, None);
+        assert!(matches!(result.mp4_status, Mp4Status::Disabled));
+        assert!(result.warnings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_chunk_reuses_buffer_after_previous_chunk_is_dropped() {
+        let data: Vec<u8> = (0..=255).collect();
+        let mut reader = &data[..];
+        let mut buffer = BytesMut::with_capacity(128);
+
+        let first = read_chunk(&mut reader, &mut buffer, 128).await.unwrap();
+        assert_eq!(&first[..], &data[..128]);
+        let first_ptr = first.as_ptr();
+        drop(first);
+
+        let second = read_chunk(&mut reader, &mut buffer, 128).await.unwrap();
+        assert_eq!(&second[..], &data[128..]);
+        assert_eq!(second.as_ptr(), first_ptr);
+    }
+
+    #[tokio::test]
+    async fn test_file_range_stream_reads_only_the_requested_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("video.mp4");
+        let data: Vec<u8> = (0..=255).collect();
+        std::fs::write(&path, &data).unwrap();
+
+        let stream = file_range_stream(path.to_str().unwrap(), 100, 50)
             .await
-            .context("Failed to fetch upload status")?;
-
-        let response =
-            ApiClient::check_response(response, &format!("/video/v1/uploads/{}", upload_id))
-                .await?;
-        let upload: DirectUploadResponse = ApiClient::parse_json(response).await?;
-
-        match upload.data.status.as_str() {
-            "asset_created" => {
-                // Asset IDを取得
-                if let Some(asset_id) = upload.data.asset_id {
-                    // Assetの詳細を取得
-                    let asset_response = client
-                        .get(
-                            &format!("/video/v1/assets/{}", asset_id),
-                            Some(&auth_header),
-                        )
-                        .await
-                        .context("Failed to fetch asset details")?;
-
-                    let asset_response = ApiClient::check_response(
-                        asset_response,
-                        &format!("/video/v1/assets/{}", asset_id),
-                    )
-                    .await?;
-                    let asset: AssetResponse = ApiClient::parse_json(asset_response).await?;
-
-                    return Ok(asset);
-                } else {
-                    bail!("Upload completed but asset_id is missing");
-                }
-            }
-            "errored" => {
-                bail!("Upload failed with error status");
-            }
-            "cancelled" => {
-                bail!("Upload was cancelled");
-            }
-            "timed_out" => {
-                bail!("Upload timed out");
-            }
-            _ => {
-                // まだ処理中 - 待機してから次の進捗通知
-                sleep(Duration::from_secs(APP_CONFIG.upload.poll_interval_secs)).await;
-
-                // sleep後に経過時間を進捗通知
-                if let Some(ref tx) = progress_tx {
-                    let elapsed = start_time.elapsed().as_secs();
-                    let _ = tx
-                        .send(UploadProgress::new(UploadPhase::WaitingForAsset {
-                            upload_id: upload_id.to_string(),
-                            elapsed_secs: elapsed,
-                        }))
-                        .await;
-                }
-            }
-        }
+            .unwrap();
+        let chunks: Vec<Bytes> = stream.try_collect().await.unwrap();
+
+        assert_eq!(chunks.concat(), &data[100..150]);
     }
 
-    bail!(
-        "Upload processing timed out after {} seconds",
-        APP_CONFIG.upload.max_wait_secs
-    )
+    #[tokio::test]
+    async fn test_chunk_digests_match_whole_file_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("video.mp4");
+        let data: Vec<u8> = (0..=255).collect();
+        std::fs::write(&path, &data).unwrap();
+
+        // 前半はバッファ、後半はストリーミングで送信した場合と同じ順にハッシュへ通す
+        let digest = Arc::new(Mutex::new(Sha256::new()));
+        ChunkPayload::Buffered(Bytes::copy_from_slice(&data[..100]))
+            .to_body(digest.clone())
+            .await
+            .unwrap();
+        let stream = hashed_file_range_stream(path.to_str().unwrap(), 100, 156, digest.clone())
+            .await
+            .unwrap();
+        let _: Vec<Bytes> = stream.try_collect().await.unwrap();
+
+        let streamed = format!("{:x}", lock_digest(&digest).clone().finalize());
+        assert_eq!(streamed, format!("{:x}", Sha256::digest(&data)));
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_map_file_slices_match_file_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("video.mp4");
+        let data: Vec<u8> = (0..=255).collect();
+        std::fs::write(&path, &data).unwrap();
+
+        let mapped = map_file(path.to_str().unwrap(), data.len() as u64)
+            .unwrap()
+            .expect("non-empty file should be mapped");
+        assert_eq!(&mapped.slice(10..20)[..], &data[10..20]);
+
+        assert!(map_file(path.to_str().unwrap(), 0).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sha256_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("video.mp4");
+        std::fs::write(&path, b"abc").unwrap();
+
+        assert_eq!(
+            sha256_file(path.to_str().unwrap()).await.unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_before_upload_hook_rewrites_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("video.mkv");
+        let remuxed = dir.path().join("video.mp4");
+        std::fs::write(&source, b"mkv").unwrap();
+        let source = source.to_str().unwrap();
+
+        // 未設定の場合は元のファイルのまま
+        let path = run_before_upload_hook(&HookCommands::default(), source)
+            .await
+            .unwrap();
+        assert_eq!(path, source);
+
+        // 標準出力の最終行のパスに差し替え
+        let hooks = HookCommands {
+            on_before_upload: Some(format!(
+                r#"echo converting; cp "$VIDYEET_FILE_PATH" '{0}'; echo '{0}'"#,
+                remuxed.display()
+            )),
+            ..Default::default()
+        };
+        let path = run_before_upload_hook(&hooks, source).await.unwrap();
+        assert_eq!(path, remuxed.to_str().unwrap());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_before_upload_hook_errors_propagate() {
+        let failing = HookCommands {
+            on_before_upload: Some("exit 1".to_string()),
+            ..Default::default()
+        };
+        let err = run_before_upload_hook(&failing, "video.mkv")
+            .await
+            .unwrap_err();
+        assert!(err.downcast_ref::<InfraError>().is_some());
+
+        // 存在しないパスを出力した場合もエラー
+        let missing = HookCommands {
+            on_before_upload: Some("echo /nonexistent/video.mp4".to_string()),
+            ..Default::default()
+        };
+        let err = run_before_upload_hook(&missing, "video.mkv")
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("/nonexistent/video.mp4"));
+    }
 }
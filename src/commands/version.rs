@@ -0,0 +1,43 @@
+/// バージョンコマンド
+///
+/// バージョン・git コミット・ビルド日・ターゲット・APIエンドポイント・TLSバックエンドを返します。
+/// ビルド時のメタデータは build.rs が埋め込みます。
+use crate::api::client::TLS_BACKEND;
+use crate::commands::result::{CommandResult, VersionResult};
+use crate::config::APP_CONFIG;
+
+/// バージョンコマンドを実行
+///
+/// # Returns
+/// 成功時はOk(CommandResult)、失敗時はエラー
+pub async fn execute() -> anyhow::Result<CommandResult> {
+    Ok(CommandResult::Version(version_info()))
+}
+
+/// ビルド時のメタデータ
+pub fn version_info() -> VersionResult {
+    VersionResult {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_commit: env!("VIDYEET_GIT_COMMIT").to_string(),
+        build_date: env!("VIDYEET_BUILD_DATE").to_string(),
+        target: env!("VIDYEET_TARGET").to_string(),
+        api_endpoint: APP_CONFIG.api.endpoint.to_string(),
+        tls_backend: TLS_BACKEND.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_info() {
+        let info = version_info();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(info.api_endpoint, APP_CONFIG.api.endpoint);
+        assert!(!info.git_commit.is_empty());
+        assert!(!info.target.is_empty());
+        assert!(["rustls", "native-tls"].contains(&info.tls_backend.as_str()));
+        assert!(info.build_date == "unknown" || info.build_date.len() == 10);
+    }
+}
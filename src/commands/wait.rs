@@ -0,0 +1,454 @@
+/// アセット待機（wait）コマンド
+///
+/// アセットIDまたはDirect UploadのIDを受け取り、アセットが指定した状態
+/// （作成済み・再生可能・MP4生成済み）になるまでポーリングします。
+/// アップロード時のアセット作成待ち（`wait_for_asset_created`）もこのモジュールの
+/// ポーリングを使うため、`upload` と同じ進捗イベントを送信します。
+/// ポーリングの間隔は初回を短く、以降を指数的に延ばします（`api::poll`）。
+use crate::api::auth::AuthManager;
+use crate::api::client::ApiClient;
+use crate::api::endpoints;
+use crate::api::poll::{self, PollBackoff};
+use crate::api::types::{
+    AssetData, AssetResponse, AssetStatus, DirectUploadResponse, RenditionStatus, UploadStatus,
+};
+use crate::commands::connect;
+use crate::commands::result::{CommandResult, WaitResult, WaitUntil};
+use crate::config::APP_CONFIG;
+use crate::domain::progress::{ProgressSender, UploadPhase};
+use anyhow::{Context, Result, bail};
+use std::time::Instant;
+use tokio::time::{Duration, sleep};
+
+/// アセットが指定した状態になるまで待機する
+///
+/// `id` はまずアセットIDとして取得し、見つからなければDirect UploadのIDとして
+/// アセットの作成を待ってから、指定した状態まで待機します。
+///
+/// # 引数
+/// * `id` - アセットIDまたはDirect UploadのID
+/// * `until` - 待機する状態（`--until`）
+/// * `timeout_secs` - 待機の上限（秒、`--timeout`）。Noneの場合は `APP_CONFIG.upload.wait_timeout_secs`
+/// * `progress_tx` - 進捗通知用チャネルの送信側（オプション）
+pub async fn execute(
+    id: &str,
+    until: WaitUntil,
+    timeout_secs: Option<u64>,
+    progress_tx: Option<ProgressSender>,
+) -> Result<CommandResult> {
+    let timeout_secs = timeout_secs.unwrap_or(APP_CONFIG.upload.wait_timeout_secs);
+    let (client, auth_manager) = connect()?;
+    let started_at = Instant::now();
+
+    let (upload_id, asset) = match find_asset(&client, &auth_manager, id).await? {
+        Some(asset) => (None, asset),
+        None => {
+            let asset = wait_for_asset_created(
+                &client,
+                &auth_manager,
+                id,
+                progress_tx.clone(),
+                timeout_secs,
+            )
+            .await?;
+            (Some(id.to_string()), asset.data)
+        }
+    };
+
+    let asset = if until == WaitUntil::Created {
+        asset
+    } else {
+        let remaining_secs = timeout_secs.saturating_sub(started_at.elapsed().as_secs());
+        wait_for_state(
+            &client,
+            &auth_manager,
+            asset,
+            until,
+            progress_tx.clone(),
+            remaining_secs,
+        )
+        .await?
+    };
+
+    if let Some(ref tx) = progress_tx {
+        tx.send(UploadPhase::Completed {
+            asset_id: asset.id.clone(),
+        });
+    }
+
+    let playback_id = asset.playback_ids.first();
+    let mp4_url = if until == WaitUntil::Mp4Ready {
+        asset.get_mp4_playback_url()
+    } else {
+        None
+    };
+    Ok(CommandResult::Wait(WaitResult {
+        upload_id,
+        until,
+        status: asset.status.to_string(),
+        elapsed_secs: started_at.elapsed().as_secs(),
+        playback_id: playback_id.map(|p| p.id.clone()),
+        playback_policy: playback_id.map(|p| p.policy.clone()),
+        hls_url: asset.get_playback_url(),
+        mp4_url,
+        asset_id: asset.id,
+        warnings: Vec::new(),
+    }))
+}
+
+/// Direct Uploadからアセットが作成されるまで待機する
+///
+/// Direct Uploadのステータスをポーリングし、`asset_created`状態になったら
+/// アセットの詳細を取得して返します。
+/// この時点でHLS再生が可能ですが、MP4 static renditionはバックグラウンドで
+/// 生成中の場合があります。
+///
+/// # 設計意図
+/// `upload` の役割は「アップロードとアセット作成の完了確認」までとし、
+/// MP4生成（数分かかる可能性）は待たずにMux側に任せます。
+/// これにより、ユーザーはすぐにHLS URLでストリーミングを開始でき、
+/// MP4の完了を待ちたい場合は `wait --until mp4-ready` を使います。
+///
+/// # 引数
+/// * `upload_id` - Direct UploadのID
+/// * `progress_tx` - 進捗通知用チャネルの送信側（オプション）
+/// * `max_wait_secs` - 待機の上限（秒）
+pub(crate) async fn wait_for_asset_created(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    upload_id: &str,
+    progress_tx: Option<ProgressSender>,
+    max_wait_secs: u64,
+) -> Result<AssetResponse> {
+    let auth_header = auth_manager.get_auth_header();
+    let mut poller = Poller::new(max_wait_secs);
+    let endpoint = endpoints::upload(upload_id);
+
+    // 初回の待機メッセージを送信
+    if let Some(ref tx) = progress_tx {
+        tx.send(UploadPhase::WaitingForAsset {
+            upload_id: upload_id.to_string(),
+            elapsed_secs: 0,
+        });
+    }
+
+    loop {
+        // Upload情報を取得（レート制限中はNoneのため次のポーリングを待つ）
+        if let Some(response) = poller
+            .get(client, &auth_header, &endpoint)
+            .await
+            .context("Failed to fetch upload status")?
+        {
+            let upload: DirectUploadResponse = ApiClient::parse_json(response).await?;
+
+            match upload.data.status {
+                UploadStatus::AssetCreated => {
+                    // Asset IDを取得
+                    if let Some(asset_id) = upload.data.asset_id {
+                        // Assetの詳細を取得
+                        let asset = fetch_asset(client, auth_manager, &asset_id).await?;
+                        return Ok(AssetResponse { data: asset });
+                    } else {
+                        bail!("Upload completed but asset_id is missing");
+                    }
+                }
+                UploadStatus::Errored => {
+                    bail!("Upload failed with error status");
+                }
+                UploadStatus::Cancelled => {
+                    bail!("Upload was cancelled");
+                }
+                UploadStatus::TimedOut => {
+                    bail!("Upload timed out");
+                }
+                // まだ処理中（未知のステータスも完了・失敗が確定するまで待つ）
+                UploadStatus::Waiting | UploadStatus::Unknown(_) => {}
+            }
+        }
+
+        // 待機してから経過時間を進捗通知
+        if !poller.sleep().await {
+            break;
+        }
+        if let Some(ref tx) = progress_tx {
+            tx.send(UploadPhase::WaitingForAsset {
+                upload_id: upload_id.to_string(),
+                elapsed_secs: poller.elapsed_secs(),
+            });
+        }
+    }
+
+    bail!(
+        "Upload processing timed out after {} seconds",
+        max_wait_secs
+    )
+}
+
+/// アセットが指定した状態（`ready` または `mp4_ready`）になるまでポーリングする
+pub(crate) async fn wait_for_state(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    mut asset: AssetData,
+    until: WaitUntil,
+    progress_tx: Option<ProgressSender>,
+    max_wait_secs: u64,
+) -> Result<AssetData> {
+    let auth_header = auth_manager.get_auth_header();
+    let mut poller = Poller::new(max_wait_secs);
+    let endpoint = endpoints::asset(&asset.id);
+
+    loop {
+        if reached(&asset, until)? {
+            return Ok(asset);
+        }
+        if let Some(ref tx) = progress_tx {
+            tx.send(UploadPhase::WaitingForReady {
+                asset_id: asset.id.clone(),
+                status: current_status(&asset, until).to_string(),
+                elapsed_secs: poller.elapsed_secs(),
+            });
+        }
+
+        if !poller.sleep().await {
+            break;
+        }
+        if let Some(response) = poller
+            .get(client, &auth_header, &endpoint)
+            .await
+            .context("Failed to fetch asset details")?
+        {
+            let response: AssetResponse = ApiClient::parse_json(response).await?;
+            asset = response.data;
+        }
+    }
+
+    bail!(
+        "Asset {} did not become {} within {} seconds. Run 'vidyeet wait {} --until {}' to keep waiting.",
+        asset.id,
+        until.name(),
+        max_wait_secs,
+        asset.id,
+        until.name()
+    )
+}
+
+/// 状態待ちのポーリング
+///
+/// 間隔は `PollBackoff` で初回から指数的に延ばし、待機の上限の時刻には
+/// 間隔を切り詰めて最後の1回を取得します。
+/// レート制限（HTTP 429）はポーリングの失敗とせず、`Retry-After` だけ待って続けます。
+pub(crate) struct Poller {
+    backoff: PollBackoff,
+    started_at: Instant,
+    max_wait: Duration,
+    /// 次のポーリングの番号（0始まり）
+    attempt: u32,
+    /// サーバーが最後に示した待機時間（`Retry-After`）
+    hint: Option<Duration>,
+}
+
+impl Poller {
+    /// 待機の上限（秒）を指定してポーリングを始める
+    pub(crate) fn new(max_wait_secs: u64) -> Self {
+        Self {
+            backoff: PollBackoff::default(),
+            started_at: Instant::now(),
+            max_wait: Duration::from_secs(max_wait_secs),
+            attempt: 0,
+            hint: None,
+        }
+    }
+
+    /// ポーリング開始からの経過時間（秒）
+    pub(crate) fn elapsed_secs(&self) -> u64 {
+        self.started_at.elapsed().as_secs()
+    }
+
+    /// エンドポイントを取得する
+    ///
+    /// レート制限（HTTP 429）の場合はNoneを返し、次のポーリングを待たせます。
+    /// それ以外のエラーレスポンスは `check_response` と同じくエラーになります。
+    pub(crate) async fn get(
+        &mut self,
+        client: &ApiClient,
+        auth_header: &str,
+        endpoint: &str,
+    ) -> Result<Option<reqwest::Response>> {
+        let response = client.get(endpoint, Some(auth_header)).await?;
+        self.hint = poll::retry_after(response.headers());
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Ok(None);
+        }
+        Ok(Some(ApiClient::check_response(response, endpoint).await?))
+    }
+
+    /// 次のポーリングまで待機する（待機の上限を過ぎている場合はfalse）
+    pub(crate) async fn sleep(&mut self) -> bool {
+        let Some(remaining) = self.max_wait.checked_sub(self.started_at.elapsed()) else {
+            return false;
+        };
+        if remaining.is_zero() {
+            return false;
+        }
+        let delay = self.backoff.next_delay(self.attempt, self.hint.take());
+        self.attempt = self.attempt.saturating_add(1);
+        sleep(delay.min(remaining)).await;
+        true
+    }
+}
+
+/// アセットが待機する状態に達したか
+///
+/// 達しないことが確定した場合（アセットのエラー、MP4の生成失敗・未設定）はエラーを返します。
+fn reached(asset: &AssetData, until: WaitUntil) -> Result<bool> {
+    if asset.status == AssetStatus::Errored {
+        bail!("Asset {} failed to process (status: errored)", asset.id);
+    }
+
+    match until {
+        WaitUntil::Created => Ok(true),
+        WaitUntil::Ready => Ok(asset.status == AssetStatus::Ready),
+        WaitUntil::Mp4Ready => {
+            let mp4s: Vec<_> = asset
+                .static_renditions
+                .iter()
+                .flat_map(|wrapper| &wrapper.files)
+                .filter(|r| r.ext == "mp4" && r.status != RenditionStatus::Deleted)
+                .collect();
+            if mp4s.iter().any(|r| r.status == RenditionStatus::Ready) {
+                return Ok(true);
+            }
+            // アセットの準備中はStatic Renditionがまだ一覧にない場合がある
+            if asset.status == AssetStatus::Ready && mp4s.is_empty() {
+                bail!(
+                    "Asset {} has no MP4 renditions. Run 'vidyeet renditions create {} --resolution highest' to add one.",
+                    asset.id,
+                    asset.id
+                );
+            }
+            if !mp4s.is_empty() && mp4s.iter().all(|r| r.status.is_failed()) {
+                bail!(
+                    "All MP4 renditions of asset {} failed to generate",
+                    asset.id
+                );
+            }
+            Ok(false)
+        }
+    }
+}
+
+/// 進捗通知する現在の状態（MP4待ちの場合は最初のMP4のステータス）
+fn current_status(asset: &AssetData, until: WaitUntil) -> &str {
+    let mp4_status = asset
+        .static_renditions
+        .iter()
+        .flat_map(|wrapper| &wrapper.files)
+        .find(|r| r.ext == "mp4")
+        .map(|r| r.status.as_str());
+    match (until, mp4_status) {
+        (WaitUntil::Mp4Ready, Some(status)) if asset.status == AssetStatus::Ready => status,
+        _ => asset.status.as_str(),
+    }
+}
+
+/// アセットを取得する
+async fn fetch_asset(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    asset_id: &str,
+) -> Result<AssetData> {
+    let auth_header = auth_manager.get_auth_header();
+    client
+        .get_asset(asset_id, &auth_header)
+        .await
+        .context("Failed to fetch asset details")
+}
+
+/// IDをアセットIDとして取得する（アセットが見つからない場合はNone）
+async fn find_asset(
+    client: &ApiClient,
+    auth_manager: &AuthManager,
+    id: &str,
+) -> Result<Option<AssetData>> {
+    let auth_header = auth_manager.get_auth_header();
+    let endpoint = endpoints::asset(id);
+    let response = client
+        .get(&endpoint, Some(&auth_header))
+        .await
+        .context("Failed to fetch asset details")?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let response = ApiClient::check_response(response, &endpoint).await?;
+    let asset: AssetResponse = ApiClient::parse_json(response).await?;
+    Ok(Some(asset.data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::{StaticRendition, StaticRenditionsWrapper};
+
+    fn asset(status: &str, renditions: &[&str]) -> AssetData {
+        let asset: AssetData = serde_json::from_value(serde_json::json!({
+            "id": "asset_1",
+            "status": status,
+            "created_at": "1700000000",
+            "playback_ids": [{ "id": "play_1", "policy": "public" }]
+        }))
+        .unwrap();
+        AssetData {
+            static_renditions: (!renditions.is_empty()).then(|| StaticRenditionsWrapper {
+                files: renditions
+                    .iter()
+                    .map(|status| StaticRendition {
+                        id: format!("r_{}", status),
+                        rendition_type: "standard".to_string(),
+                        status: RenditionStatus::from(*status),
+                        resolution: "highest".to_string(),
+                        name: "highest.mp4".to_string(),
+                        ext: "mp4".to_string(),
+                        filesize: None,
+                    })
+                    .collect(),
+            }),
+            ..asset
+        }
+    }
+
+    #[test]
+    fn test_reached() {
+        assert!(reached(&asset("preparing", &[]), WaitUntil::Created).unwrap());
+        assert!(!reached(&asset("preparing", &[]), WaitUntil::Ready).unwrap());
+        assert!(reached(&asset("ready", &[]), WaitUntil::Ready).unwrap());
+        assert!(reached(&asset("errored", &[]), WaitUntil::Created).is_err());
+
+        assert!(!reached(&asset("preparing", &[]), WaitUntil::Mp4Ready).unwrap());
+        assert!(!reached(&asset("ready", &["preparing"]), WaitUntil::Mp4Ready).unwrap());
+        assert!(reached(&asset("ready", &["errored", "ready"]), WaitUntil::Mp4Ready).unwrap());
+        assert!(reached(&asset("ready", &[]), WaitUntil::Mp4Ready).is_err());
+        assert!(
+            reached(
+                &asset("ready", &["errored", "skipped"]),
+                WaitUntil::Mp4Ready
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_current_status() {
+        assert_eq!(
+            current_status(&asset("ready", &["preparing"]), WaitUntil::Mp4Ready),
+            "preparing"
+        );
+        assert_eq!(
+            current_status(&asset("preparing", &["preparing"]), WaitUntil::Mp4Ready),
+            "preparing"
+        );
+        assert_eq!(
+            current_status(&asset("preparing", &[]), WaitUntil::Ready),
+            "preparing"
+        );
+    }
+}
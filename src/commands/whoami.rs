@@ -0,0 +1,64 @@
+/// whoamiコマンド
+///
+/// 使用中のプロファイル名・マスクしたToken ID・APIエンドポイント・設定ファイルのパスを返します。
+/// 認証情報の検証（API呼び出し）はおこないません。有効性の確認は `status` を使用してください。
+use crate::api::auth::AuthManager;
+use crate::commands::result::{CommandResult, WhoamiResult};
+use crate::config::APP_CONFIG;
+use crate::config::user::UserConfig;
+use anyhow::{Context, Result};
+
+pub use crate::config::user::DEFAULT_PROFILE;
+
+/// whoamiコマンドを実行
+///
+/// # Returns
+/// 成功時はOk(CommandResult)、失敗時はエラー
+pub async fn execute() -> Result<CommandResult> {
+    let config_path = UserConfig::config_path().context("Failed to get config file path")?;
+    let config = UserConfig::load().context("Failed to load configuration file")?;
+
+    Ok(CommandResult::Whoami(whoami(
+        &config,
+        config_path.display().to_string(),
+    )))
+}
+
+/// 設定から結果を組み立てる
+fn whoami(config: &UserConfig, config_path: String) -> WhoamiResult {
+    let token_id = config.get_auth().ok().map(|auth| {
+        AuthManager::new(auth.token_id.clone(), auth.token_secret.clone()).get_masked_token_id()
+    });
+
+    WhoamiResult {
+        profile: DEFAULT_PROFILE.to_string(),
+        is_logged_in: token_id.is_some(),
+        token_id,
+        api_endpoint: APP_CONFIG.api.endpoint.to_string(),
+        config_path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::user::AuthConfig;
+
+    #[test]
+    fn test_whoami_masks_token_id() {
+        let mut config = UserConfig::default();
+        let result = whoami(&config, "/tmp/vidyeet/config.toml".to_string());
+        assert!(!result.is_logged_in);
+        assert_eq!(result.token_id, None);
+        assert_eq!(result.profile, DEFAULT_PROFILE);
+
+        config.auth = Some(AuthConfig {
+            token_id: "abcdef123456789".to_string(),
+            token_secret: "secret".to_string(),
+        });
+        let result = whoami(&config, "/tmp/vidyeet/config.toml".to_string());
+        assert!(result.is_logged_in);
+        assert_eq!(result.token_id.as_deref(), Some("abcd***6789"));
+        assert_eq!(result.api_endpoint, APP_CONFIG.api.endpoint);
+    }
+}
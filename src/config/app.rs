@@ -8,6 +8,172 @@ pub struct AppConfig {
     pub api: ApiConfig,
     pub upload: UploadConfig,
     pub presentation: PresentationConfig,
+    pub hooks: HooksConfig,
+    pub webhook: WebhookConfig,
+    pub doctor: DoctorConfig,
+    pub ping: PingConfig,
+    pub metadata: MetadataConfig,
+    pub backup: BackupConfig,
+    pub history: HistoryConfig,
+    pub signing: SigningConfig,
+    pub stats: StatsConfig,
+    pub thumbnail: ThumbnailConfig,
+    pub storyboard: StoryboardConfig,
+    pub serve: ServeConfig,
+}
+
+/// ローカルのプレビュープレーヤー（`vidyeet serve`）の設定
+#[derive(Debug, Clone, Copy)]
+pub struct ServeConfig {
+    /// 待ち受けるアドレス（ループバックのみ。他のマシンには公開しない）
+    pub bind_address: &'static str,
+
+    /// 既定のポート（0はOSが空いているポートを選ぶ、`--port` で上書き）
+    pub default_port: u16,
+
+    /// ページが読み込むhls.jsのURL（HLSをネイティブに再生できないブラウザ向け）
+    pub hls_js_url: &'static str,
+
+    /// 受け付けるリクエストヘッダーの最大バイト数
+    pub max_request_bytes: usize,
+
+    /// リクエストの受信・レスポンスの送信のタイムアウト(秒)
+    pub request_timeout_secs: u64,
+}
+
+/// ストーリーボード（`vidyeet storyboard`）の設定
+#[derive(Debug, Clone, Copy)]
+pub struct StoryboardConfig {
+    /// `--download` でVTT・スプライト画像を取得する際のタイムアウト(秒)
+    /// スプライト画像の生成を待つ場合があるため、APIの呼び出しより長めにする
+    pub download_timeout_secs: u64,
+
+    /// `--download` で保存するVTTのファイル名
+    pub vtt_file_name: &'static str,
+
+    /// `--download` で保存するスプライト画像のファイル名（VTTの参照もこの名前に書き換える）
+    pub sprite_file_name: &'static str,
+}
+
+/// サムネイル画像のURL（`upload` / `show` の `thumbnail_url`）の設定
+#[derive(Debug, Clone, Copy)]
+pub struct ThumbnailConfig {
+    /// 再生時間に対するサムネイルを切り出す位置の割合（冒頭の黒画面やタイトルを避ける）
+    pub time_ratio: f64,
+
+    /// `--thumbnail-width` / `--thumbnail-height` に指定できる最大値（ピクセル）
+    pub max_dimension: u32,
+}
+
+/// 統計（`vidyeet stats`）の設定
+#[derive(Debug, Clone, Copy)]
+pub struct StatsConfig {
+    /// 人間向け出力で週ごとのアップロード数を表示する週の数（直近から）
+    pub recent_weeks: usize,
+}
+
+/// 署名付き再生URL（config.tomlの`[signing]`）の設定
+#[derive(Debug, Clone, Copy)]
+pub struct SigningConfig {
+    /// 署名付きURLの既定の有効期間（秒、`--expires` で上書き）
+    pub default_expires_secs: u64,
+
+    /// 再生制限（`restrictions create`）で許可できるドメインの最大数（Mux APIの制限）
+    pub max_allowed_domains: usize,
+}
+
+/// 操作履歴（`vidyeet history`）の設定
+#[derive(Debug, Clone, Copy)]
+pub struct HistoryConfig {
+    /// `history` で表示する既定の件数
+    pub default_limit: usize,
+
+    /// 保持する履歴の最大件数（超えた分は古いものから削除）
+    pub max_entries: usize,
+}
+
+/// バックアップコマンド（`vidyeet backup`）の設定
+#[derive(Debug, Clone, Copy)]
+pub struct BackupConfig {
+    /// 既定の同時ダウンロード数
+    pub concurrency: usize,
+
+    /// 同時ダウンロード数の上限
+    pub max_concurrency: usize,
+
+    /// 接続のタイムアウト(秒)
+    /// ダウンロード全体には時間制限を設けない（大きなファイルを想定）
+    pub connect_timeout_secs: u64,
+
+    /// 出力先に保存するダウンロード記録のファイル名
+    pub state_file_name: &'static str,
+}
+
+/// アセットのメタデータ（タイトルなど）の設定
+#[derive(Debug, Clone, Copy)]
+pub struct MetadataConfig {
+    /// タイトル（meta.title）の最大文字数（Mux APIの制限）
+    pub max_title_chars: usize,
+
+    /// passthroughの最大文字数（Mux APIの制限）
+    pub max_passthrough_chars: usize,
+
+    /// 作成者ID（meta.creator_id）の最大文字数（Mux APIの制限）
+    pub max_creator_id_chars: usize,
+
+    /// タグ1つの最大文字数
+    pub max_tag_chars: usize,
+}
+
+/// 疎通確認コマンド（`vidyeet ping`）の設定
+#[derive(Debug, Clone, Copy)]
+pub struct PingConfig {
+    /// 計測に使う軽量な認証付きリクエスト
+    pub endpoint: &'static str,
+
+    /// 既定のリクエスト回数
+    pub default_count: u32,
+
+    /// リクエスト回数の上限
+    pub max_count: u32,
+
+    /// リクエストの間隔(ミリ秒)
+    pub interval_ms: u64,
+}
+
+/// 診断コマンド（`vidyeet doctor`）の設定
+#[derive(Debug, Clone, Copy)]
+pub struct DoctorConfig {
+    /// 再生URLのホスト（DNS・HTTPSの到達性を確認する）
+    pub stream_endpoint: &'static str,
+
+    /// 各到達性チェックのタイムアウト(秒)
+    pub timeout_secs: u64,
+
+    /// サーバー時刻とのずれの警告しきい値(秒)
+    pub clock_skew_warn_secs: i64,
+
+    /// サーバー時刻とのずれの失敗しきい値(秒)
+    /// 署名付きURLの有効期限やTLS証明書の検証に影響する
+    pub clock_skew_fail_secs: i64,
+
+    /// データディレクトリの空き容量の警告しきい値 (バイト)
+    pub min_free_disk_bytes: u64,
+}
+
+/// Webhook通知（config.tomlの`[notifications]`）の設定
+#[derive(Debug, Clone, Copy)]
+pub struct WebhookConfig {
+    /// Webhookへの送信のタイムアウト(秒)
+    pub timeout_secs: u64,
+}
+
+/// フック（config.tomlの`[hooks]`）実行の設定
+#[derive(Debug, Clone, Copy)]
+pub struct HooksConfig {
+    /// フックの最大実行時間(秒)
+    /// 超過した場合はフックを強制終了してエラーとする
+    pub timeout_secs: u64,
 }
 
 /// プレゼンテーション層の設定
@@ -17,8 +183,12 @@ pub struct PresentationConfig {
     pub size_display_precision: usize,
 
     /// 進捗更新の表示間隔(秒)
-    /// WaitingForAsset フェーズでの更新頻度を制御
+    /// WaitingForAsset / WaitingForReady フェーズで、開始からこの時間が経つまで経過時間を表示しない
     pub progress_update_interval_secs: u64,
+
+    /// スピナーのフレーム更新間隔(ミリ秒)
+    /// TTYでの待機中フェーズ表示に使用
+    pub spinner_frame_interval_ms: u64,
 }
 
 /// API関連の設定
@@ -29,6 +199,45 @@ pub struct ApiConfig {
 
     /// APIリクエストのタイムアウト(秒)
     pub timeout_seconds: u64,
+
+    /// APIリクエストの最大試行回数（初回を含む）
+    /// ネットワークエラー・タイムアウト・5xxの場合にリトライする
+    pub max_attempts: u32,
+
+    /// APIリトライ時の指数バックオフ基準時間 (ミリ秒)
+    pub retry_backoff_base_ms: u64,
+
+    /// APIリトライ時のバックオフ上限 (ミリ秒)
+    pub retry_backoff_max_ms: u64,
+
+    /// クライアント側レート制限: 1秒あたりのリクエスト数（0以下で無制限）
+    pub requests_per_second: f64,
+
+    /// クライアント側レート制限: バースト許容量（連続即時送信できる数）
+    pub rate_limit_burst: u32,
+
+    /// TCP keep-aliveの間隔(秒)
+    pub tcp_keepalive_secs: u64,
+
+    /// アイドルコネクションをプールに保持する時間(秒)
+    pub pool_idle_timeout_secs: u64,
+
+    /// ホストごとのアイドルコネクション最大数
+    pub pool_max_idle_per_host: usize,
+
+    /// HTTP/2 prior knowledgeを使用するか
+    /// （有効にするとHTTP/1.1のみのサーバーに接続できないため既定は無効）
+    pub http2_prior_knowledge: bool,
+
+    /// 一覧系APIの1ページあたりの取得件数（Muxの上限は100）
+    pub page_size: usize,
+
+    /// list/showのレスポンスキャッシュを再検証なしで使う期間(秒)
+    /// これを過ぎるとETag/Last-Modifiedによる条件付きリクエストで再検証する
+    pub cache_max_age_secs: u64,
+
+    /// `--debug-http=body` で出力するリクエストボディの最大バイト数
+    pub debug_body_max_bytes: usize,
 }
 
 /// アップロード関連の設定
@@ -40,14 +249,26 @@ pub struct UploadConfig {
     /// 対応する動画フォーマット
     pub supported_formats: &'static [&'static str],
 
-    /// アップロード完了ポーリング間隔(秒)
+    /// アセット待機ポーリングの初回の間隔(秒)
+    /// 以降は指数バックオフで poll_max_interval_secs まで延ばす
     pub poll_interval_secs: u64,
 
+    /// アセット待機ポーリングの間隔の上限(秒)
+    pub poll_max_interval_secs: u64,
+
+    /// ポーリング間隔のジッターの割合(0.0〜1.0)
+    /// 複数のポーリングが同じタイミングに揃わないよう、間隔を最大でこの割合だけ短縮する
+    pub poll_jitter_ratio: f64,
+
     /// アップロード待機の最大時間(秒)
     pub max_wait_secs: u64,
 
-    /// 進捗チャネルの受信タイムアウト(秒)
+    /// waitコマンドの待機の既定の上限(秒)、`--timeout` で変更できる
+    pub wait_timeout_secs: u64,
+
+    /// 進捗の更新が途絶えたと警告するまでの時間(秒)
     /// アップロード処理全体のタイムアウト(max_wait_secs)にバッファを追加
+    /// 警告後も完了イベントを取りこぼさないよう受信は続ける
     pub progress_timeout_secs: u64,
 
     /// チャンクアップロードのチャンクサイズ (バイト)
@@ -59,6 +280,51 @@ pub struct UploadConfig {
 
     /// リトライ時の指数バックオフ基準時間 (ミリ秒)
     pub backoff_base_ms: u64,
+
+    /// 転送速度の指数平滑化係数（0.0〜1.0）
+    /// 大きいほど直近のチャンクの速度を重視する
+    pub throughput_smoothing_factor: f64,
+
+    /// チャンクをファイルから直接ストリーミング送信するか
+    /// trueの場合、チャンク全体をメモリに読み込まないため、メモリの少ない環境でも大きなチャンクを扱える
+    pub stream_chunks: bool,
+
+    /// ストリーミング送信時にファイルから一度に読み込むサイズ (バイト)
+    pub stream_read_buffer_size: usize,
+
+    /// メディア解析（`--probe`）に使うffprobeのコマンド名
+    /// PATH上にない場合は解析をスキップする
+    pub ffprobe_command: &'static str,
+
+    /// 変換（`--transcode`）に使うffmpegのコマンド名
+    pub ffmpeg_command: &'static str,
+
+    /// 変換中の進捗（`transcoding`）を通知する間隔(ミリ秒)
+    pub transcode_progress_interval_ms: u64,
+
+    /// 作成するアセットの画質（Muxの`video_quality`: "basic" / "plus" / "premium"）
+    pub video_quality: &'static str,
+
+    /// 再エンコードなしで取り込まれる映像コーデック（Muxの標準入力仕様）
+    /// これ以外のコーデックは解析時に警告する
+    pub standard_video_codecs: &'static [&'static str],
+
+    /// WebMで使える映像コーデック（WebMの仕様）
+    /// .webmのファイルがこれ以外のコーデックの場合は解析時に警告する
+    pub webm_video_codecs: &'static [&'static str],
+
+    /// 複数ファイルアップロードの既定の同時実行数
+    pub batch_concurrency: usize,
+
+    /// 複数ファイルアップロードの同時実行数の上限
+    /// Direct Uploadの作成やポーリングがレート制限に当たらない範囲に抑える
+    pub max_batch_concurrency: usize,
+
+    /// 複数ファイルアップロードで一時的な失敗を再試行する際の1ファイルあたりの最大試行回数
+    pub batch_max_attempts: u32,
+
+    /// 最近のアップロードの記録（同じファイル名・サイズの再アップロードの警告用）に保持する最大件数
+    pub recent_uploads_max_entries: usize,
 }
 
 impl AppConfig {
@@ -68,25 +334,117 @@ impl AppConfig {
             api: ApiConfig {
                 endpoint: "https://api.mux.com",
                 timeout_seconds: 300, // 5分（大きなファイルアップロード用）
+                max_attempts: 4,      // 初回 + 3回リトライ
+                retry_backoff_base_ms: 500,
+                retry_backoff_max_ms: 8000, // 8秒
+                requests_per_second: 5.0,
+                rate_limit_burst: 10,
+                tcp_keepalive_secs: 60,
+                pool_idle_timeout_secs: 90,
+                pool_max_idle_per_host: 8,
+                http2_prior_knowledge: false,
+                page_size: 100,
+                cache_max_age_secs: 10,
+                debug_body_max_bytes: 4096, // 4KB
             },
             upload: UploadConfig {
                 max_file_size: 10_737_418_240, // 10GB
-                supported_formats: &["mp4", "mov", "avi", "wmv", "flv", "mkv", "webm"],
+                supported_formats: &[
+                    "mp4", "mov", "avi", "wmv", "flv", "mkv", "webm", "ts", "m2ts", "3gp", "mpg",
+                    "mpeg", "mxf", "ogv",
+                ],
                 poll_interval_secs: 2,
+                poll_max_interval_secs: 30,
+                poll_jitter_ratio: 0.2,
                 max_wait_secs: 300,
+                wait_timeout_secs: 600,
                 progress_timeout_secs: 350, // max_wait_secs + 50秒バッファ
                 chunk_size: 16_777_216, // 16MB (256KiB * 64)　[16_777_216=16MB, 33_554_432=32MB]
                 max_retries: 3,
                 backoff_base_ms: 1000, // 1秒
+                throughput_smoothing_factor: 0.3,
+                stream_chunks: true,
+                stream_read_buffer_size: 262_144, // 256KiB
+                ffprobe_command: "ffprobe",
+                ffmpeg_command: "ffmpeg",
+                transcode_progress_interval_ms: 1000,
+                video_quality: "premium",
+                standard_video_codecs: &["h264"],
+                webm_video_codecs: &["vp8", "vp9", "av1"],
+                batch_concurrency: 3,
+                max_batch_concurrency: 8,
+                batch_max_attempts: 3,
+                recent_uploads_max_entries: 500,
             },
             presentation: PresentationConfig {
                 size_display_precision: 2,         // 「10.00 MB」形式
                 progress_update_interval_secs: 10, // 10秒ごとに更新
+                spinner_frame_interval_ms: 100,    // 10fps
+            },
+            hooks: HooksConfig {
+                timeout_secs: 600, // 10分（トランスコードを行うフックを想定）
+            },
+            webhook: WebhookConfig { timeout_secs: 10 },
+            doctor: DoctorConfig {
+                stream_endpoint: "https://stream.mux.com",
+                timeout_secs: 10,
+                clock_skew_warn_secs: 30,
+                clock_skew_fail_secs: 300,        // 5分
+                min_free_disk_bytes: 536_870_912, // 512MB
+            },
+            ping: PingConfig {
+                endpoint: "/video/v1/assets?limit=1",
+                default_count: 5,
+                max_count: 100,
+                interval_ms: 1000,
+            },
+            metadata: MetadataConfig {
+                max_title_chars: 512,
+                max_passthrough_chars: 255,
+                max_creator_id_chars: 128,
+                max_tag_chars: 32,
+            },
+            backup: BackupConfig {
+                concurrency: 3,
+                max_concurrency: 8,
+                connect_timeout_secs: 10,
+                state_file_name: ".vidyeet-backup.json",
+            },
+            history: HistoryConfig {
+                default_limit: 20,
+                max_entries: 1000,
+            },
+            signing: SigningConfig {
+                default_expires_secs: 3600, // 1時間
+                max_allowed_domains: 100,
+            },
+            stats: StatsConfig { recent_weeks: 8 },
+            thumbnail: ThumbnailConfig {
+                time_ratio: 0.1, // 再生時間の10%
+                max_dimension: 4096,
+            },
+            storyboard: StoryboardConfig {
+                download_timeout_secs: 60,
+                vtt_file_name: "storyboard.vtt",
+                sprite_file_name: "storyboard.jpg",
+            },
+            serve: ServeConfig {
+                bind_address: "127.0.0.1",
+                default_port: 0,
+                hls_js_url: "https://cdn.jsdelivr.net/npm/hls.js@1/dist/hls.min.js",
+                max_request_bytes: 8 * 1024,
+                request_timeout_secs: 10,
             },
         }
     }
 }
 
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// アプリケーション設定のグローバル定数
 ///
 /// コンパイル時に評価され、実行時のコストはゼロです。
@@ -99,6 +457,12 @@ pub const APP_CONFIG: AppConfig = AppConfig::new();
 /// 1メガバイトのバイト数
 pub const BYTES_PER_MB: f64 = 1_048_576.0;
 
+/// 1ギガバイトのバイト数
+pub const BYTES_PER_GB: f64 = 1_073_741_824.0;
+
+/// 1メガビットのビット数（ビットレート表示用、SI接頭辞）
+pub const BITS_PER_MEGABIT: f64 = 1_000_000.0;
+
 impl UploadConfig {
     /// 拡張子からContent-Typeを取得
     ///
@@ -116,6 +480,11 @@ impl UploadConfig {
             "flv" => "video/x-flv",
             "mkv" => "video/x-matroska",
             "webm" => "video/webm",
+            "ts" | "m2ts" => "video/mp2t",
+            "3gp" => "video/3gpp",
+            "mpg" | "mpeg" => "video/mpeg",
+            "mxf" => "application/mxf",
+            "ogv" => "video/ogg",
             _ => "application/octet-stream",
         }
     }
@@ -137,7 +506,7 @@ mod tests {
     fn test_app_config_values() {
         // 各設定値が期待通りであることを確認
         assert_eq!(APP_CONFIG.upload.max_file_size, 10_737_418_240); // 10GB
-        assert_eq!(APP_CONFIG.upload.supported_formats.len(), 7);
+        assert_eq!(APP_CONFIG.upload.supported_formats.len(), 14);
     }
 
     #[test]
@@ -161,6 +530,12 @@ mod tests {
         assert_eq!(upload_config.get_content_type("flv"), "video/x-flv");
         assert_eq!(upload_config.get_content_type("mkv"), "video/x-matroska");
         assert_eq!(upload_config.get_content_type("webm"), "video/webm");
+        assert_eq!(upload_config.get_content_type("ts"), "video/mp2t");
+        assert_eq!(upload_config.get_content_type("m2ts"), "video/mp2t");
+        assert_eq!(upload_config.get_content_type("3gp"), "video/3gpp");
+        assert_eq!(upload_config.get_content_type("mpeg"), "video/mpeg");
+        assert_eq!(upload_config.get_content_type("mxf"), "application/mxf");
+        assert_eq!(upload_config.get_content_type("ogv"), "video/ogg");
 
         // サポートされていない拡張子
         assert_eq!(
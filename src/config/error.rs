@@ -105,6 +105,20 @@ impl ConfigError {
         }
     }
 
+    /// 機械可読なエラーコードを返す
+    ///
+    /// `--machine` 出力の `error.code` に使われる安定した識別子（変更しない）
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::DirectoryNotFound { .. } => "CONFIG_DIR_NOT_FOUND",
+            Self::FileSystem { .. } => "CONFIG_IO_ERROR",
+            Self::ParseError { .. } => "CONFIG_PARSE_ERROR",
+            Self::SerializeError { .. } => "CONFIG_SERIALIZE_ERROR",
+            Self::ValidationError { .. } => "CONFIG_INVALID",
+            Self::TokenNotFound { .. } => "AUTH_MISSING",
+        }
+    }
+
     /// ユーザー向けのヒントメッセージを返す
     pub fn hint(&self) -> Option<&str> {
         match self {
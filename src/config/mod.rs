@@ -6,7 +6,7 @@
 ///
 /// # 使用例
 ///
-/// ```rust
+/// ```rust,ignore
 /// use crate::config::{APP_CONFIG, UserConfig};
 ///
 /// // AppConfig: グローバル定数として直接参照
@@ -21,7 +21,7 @@ pub mod app;
 pub mod error;
 pub mod user;
 
-pub use app::{APP_CONFIG, BYTES_PER_MB};
+pub use app::{APP_CONFIG, BITS_PER_MEGABIT, BYTES_PER_GB, BYTES_PER_MB};
 pub use user::UserConfig;
 
 #[cfg(test)]
@@ -65,6 +65,7 @@ mod tests {
         let mut config = UserConfig {
             auth: None,
             timezone_offset_seconds: 0, // UTC
+            ..Default::default()
         };
         config.set_auth("test_id".to_string(), "test_secret".to_string());
 
@@ -98,6 +99,7 @@ mod tests {
         let mut user_config = UserConfig {
             auth: None,
             timezone_offset_seconds: 0, // UTC
+            ..Default::default()
         };
         user_config.set_auth("test_id".to_string(), "test_secret".to_string());
 
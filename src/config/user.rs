@@ -6,10 +6,16 @@
 /// Linux:   /home/<user>/.config/vidyeet/config.toml
 ///
 /// 初回起動時にデフォルト値から自動的にconfig.tomlを作成します。
+use crate::config::APP_CONFIG;
 use crate::config::error::ConfigError;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// `UserConfig::use_data_dir` で指定したデータディレクトリ
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
 
 /// デフォルトのタイムゾーンオフセット（UTC）
 const DEFAULT_TIMEZONE_OFFSET: i32 = 0;
@@ -20,6 +26,79 @@ const MAX_TIMEZONE_OFFSET: i32 = 64800;
 /// タイムゾーンオフセットの最小値（-18時間 = -64800秒）
 const MIN_TIMEZONE_OFFSET: i32 = -64800;
 
+/// デスクトップ通知を表示する処理時間のしきい値のデフォルト（秒）
+const DEFAULT_NOTIFY_AFTER_SECS: u64 = 30;
+
+/// 同じファイル名・サイズの再アップロードを警告する期間のデフォルト（日）
+const DEFAULT_RECENT_UPLOAD_DAYS: u64 = 7;
+
+/// プロファイル名（プロファイルの切り替えに対応するまでは常にこの値）
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// `language` に指定できる表示言語
+const SUPPORTED_LANGUAGES: &[&str] = &["en", "ja"];
+
+/// アセット数の上限に達したときの動作（config.tomlの`capacity_policy`、`upload --on-full`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CapacityPolicy {
+    /// 確認してから最も古いアセットを削除する（確認できない `--machine` 時は失敗）
+    #[default]
+    Ask,
+    /// 確認せずに最も古いアセットを削除する
+    AutoDelete,
+    /// 削除せずにアップロードを失敗させる
+    Fail,
+}
+
+impl CapacityPolicy {
+    /// 指定できる値（`--on-full`・エラーメッセージ用）
+    pub const NAMES: &'static [&'static str] = &["ask", "auto_delete", "fail"];
+
+    /// `ask` / `auto_delete` / `fail` を解析する（`auto-delete` も受け付ける）
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "ask" => Some(Self::Ask),
+            "auto_delete" => Some(Self::AutoDelete),
+            "fail" => Some(Self::Fail),
+            _ => None,
+        }
+    }
+}
+
+/// アップロードしたアセットの再生ポリシー（`upload --policy`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaybackPolicy {
+    /// 誰でも再生できる
+    #[default]
+    Public,
+    /// 署名付きトークンがないと再生できない
+    Signed,
+}
+
+impl PlaybackPolicy {
+    /// 指定できる値（`--policy`・エラーメッセージ用）
+    pub const NAMES: &'static [&'static str] = &["public", "signed"];
+
+    /// `public` / `signed` を解析する
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "public" => Some(Self::Public),
+            "signed" => Some(Self::Signed),
+            _ => None,
+        }
+    }
+
+    /// Mux APIでの名前（`playback_policies` の値）
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Signed => "signed",
+        }
+    }
+}
+
 /// Mux認証設定
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthConfig {
@@ -40,6 +119,144 @@ pub struct UserConfig {
     /// 例: UTC=0, JST(UTC+9)=32400, PST(UTC-8)=-28800
     #[serde(default = "default_timezone_offset")]
     pub timezone_offset_seconds: i32,
+
+    /// アップロード前に常にffprobeでメディアを解析するか（`upload --probe` と同じ）
+    #[serde(default)]
+    pub probe: bool,
+
+    /// アップロードできる動画の最大再生時間（秒）
+    /// プランの再生時間制限に合わせて設定すると、アップロード前に超過を検出する
+    #[serde(default)]
+    pub max_duration_secs: Option<u64>,
+
+    /// プランのアセット数上限
+    /// 設定すると `status` で上限までの残りを表示する
+    #[serde(default)]
+    pub asset_limit: Option<usize>,
+
+    /// アセット数の上限に達したときの動作（既定は確認してから削除）
+    #[serde(default)]
+    pub capacity_policy: CapacityPolicy,
+
+    /// アップロードを許可する形式（拡張子、小文字）
+    /// 設定すると組み込みの対応形式の代わりに使う（制限・追加のどちらにも使える）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_formats: Option<Vec<String>>,
+
+    /// 長時間かかったアップロードの完了・失敗時にデスクトップ通知を表示するか
+    #[serde(default)]
+    pub notify: bool,
+
+    /// デスクトップ通知を表示する処理時間のしきい値（秒）
+    /// これより短く終わったアップロードでは通知しない
+    #[serde(default = "default_notify_after_secs")]
+    pub notify_after_secs: u64,
+
+    /// 同じファイル名・サイズのファイルをこの日数以内にアップロードしていた場合に警告する（0で無効）
+    /// アップロードスクリプトの誤った再実行を検出するため。`upload --force` で確認を省略できる
+    #[serde(default = "default_recent_upload_days")]
+    pub recent_upload_days: u64,
+
+    /// 人間向け出力の表示言語（"en" / "ja"）
+    /// 未設定の場合は環境変数 LC_ALL / LC_MESSAGES / LANG から決定する
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    /// コマンドラインで指定しなかった場合に使う既定値
+    #[serde(default)]
+    pub defaults: DefaultsConfig,
+
+    /// コマンド完了時などに実行するフック
+    #[serde(default)]
+    pub hooks: HookCommands,
+
+    /// アップロード結果の通知先
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+
+    /// API操作の監査ログ
+    #[serde(default)]
+    pub audit: AuditConfig,
+
+    /// 署名付き再生URLの生成に使う署名キー
+    #[serde(default)]
+    pub signing: SigningKeyConfig,
+}
+
+/// コマンドラインで指定しなかった場合に使う既定値（config.tomlの`[defaults]`）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DefaultsConfig {
+    /// アップロードするアセットの再生ポリシー（`upload --policy` を省略した場合）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub playback_policy: Option<PlaybackPolicy>,
+
+    /// アップロードするアセットの作成者ID（meta.creator_id）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creator_id: Option<String>,
+
+    /// アップロードするアセットのタイトル（meta.title）のテンプレート
+    /// `{filename}`（ファイル名）、`{stem}`（拡張子を除いたファイル名）、`{date}`（アップロード日）を置き換える
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_template: Option<String>,
+}
+
+/// 署名付き再生URLの署名キー（config.tomlの`[signing]`）
+///
+/// Muxで作成した署名キー（URL Signing Key）のIDと秘密鍵。
+/// 再生ポリシーが `signed` のアセットの再生URLに付けるトークンの署名に使う。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SigningKeyConfig {
+    /// 署名キーのID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+
+    /// 署名キーの秘密鍵（Mux APIが返すBase64エンコードされたPEM、またはPEMそのもの）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+
+    /// 署名付きURLに適用する再生制限のID（`vidyeet restrictions create` で作成）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub playback_restriction_id: Option<String>,
+}
+
+/// API操作の監査ログ（config.tomlの`[audit]`）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditConfig {
+    /// 状態を変更するAPI呼び出し（POST/PUT/PATCH/DELETE）を記録するか
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// 記録先のファイル（未設定の場合はデータディレクトリの `audit.jsonl`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+/// アップロード結果の通知先（config.tomlの`[notifications]`）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    /// アップロードの完了・失敗を送信するWebhook URL（Slack Incoming Webhook / Discord Webhook）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+}
+
+/// ユーザー定義フック（config.tomlの`[hooks]`）
+///
+/// 各コマンドはシェル（Windowsでは`cmd /C`、それ以外では`sh -c`）で実行され、
+/// 結果のJSONを標準入力で、主な値を`VIDYEET_*`環境変数で受け取ります。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HookCommands {
+    /// アップロード前に実行するコマンド
+    /// 標準出力の最終行にパスを出力すると、そのファイルをアップロードする（例: ffmpegでMP4へ変換）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_before_upload: Option<String>,
+
+    /// アップロード成功時に実行するコマンド
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_upload_success: Option<String>,
+
+    /// アップロード失敗時に実行するコマンド
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_upload_failure: Option<String>,
 }
 
 // プライベート関数（serde用）
@@ -47,11 +264,33 @@ fn default_timezone_offset() -> i32 {
     DEFAULT_TIMEZONE_OFFSET
 }
 
+fn default_notify_after_secs() -> u64 {
+    DEFAULT_NOTIFY_AFTER_SECS
+}
+
+fn default_recent_upload_days() -> u64 {
+    DEFAULT_RECENT_UPLOAD_DAYS
+}
+
 impl Default for UserConfig {
     fn default() -> Self {
         Self {
             auth: None,
             timezone_offset_seconds: DEFAULT_TIMEZONE_OFFSET,
+            probe: false,
+            max_duration_secs: None,
+            asset_limit: None,
+            capacity_policy: CapacityPolicy::Ask,
+            allowed_formats: None,
+            notify: false,
+            notify_after_secs: DEFAULT_NOTIFY_AFTER_SECS,
+            recent_upload_days: DEFAULT_RECENT_UPLOAD_DAYS,
+            language: None,
+            defaults: DefaultsConfig::default(),
+            hooks: HookCommands::default(),
+            notifications: NotificationsConfig::default(),
+            audit: AuditConfig::default(),
+            signing: SigningKeyConfig::default(),
         }
     }
 }
@@ -65,9 +304,30 @@ impl UserConfig {
     /// # Errors
     /// ホームディレクトリが取得できない場合に ConfigError::DirectoryNotFound を返します。
     pub fn config_path() -> Result<PathBuf, ConfigError> {
+        Self::data_dir().map(|dir| dir.join("config.toml"))
+    }
+
+    /// vidyeetのデータディレクトリ（config.tomlやキャッシュを置くディレクトリ）を取得
+    ///
+    /// # Errors
+    /// ホームディレクトリが取得できない場合に ConfigError::DirectoryNotFound を返します。
+    pub fn data_dir() -> Result<PathBuf, ConfigError> {
+        if let Some(dir) = DATA_DIR.get() {
+            return Ok(dir.clone());
+        }
         dirs::config_dir()
             .ok_or_else(|| ConfigError::directory_not_found("Failed to get user config directory"))
-            .map(|config_dir| config_dir.join("vidyeet").join("config.toml"))
+            .map(|config_dir| config_dir.join("vidyeet"))
+    }
+
+    /// このプロセスのデータディレクトリを指定する（ライブラリとして組み込む場合やテスト用）
+    ///
+    /// 環境変数（`XDG_CONFIG_HOME` など）を変えずに、config.tomlやキャッシュ・インデックスの置き場所を切り替えます。
+    /// 最初の1回のみ有効で、既に指定されている場合は指定済みのディレクトリをErrで返します。
+    pub fn use_data_dir(dir: impl AsRef<Path>) -> Result<(), PathBuf> {
+        DATA_DIR
+            .set(dir.as_ref().to_path_buf())
+            .map_err(|_| DATA_DIR.get().cloned().unwrap_or_default())
     }
 
     /// ユーザー設定を読み込む
@@ -167,8 +427,80 @@ impl UserConfig {
 # Timezone offset in seconds
 # Examples: UTC=0, JST(UTC+9)=32400, PST(UTC-8)=-28800
 timezone_offset_seconds = {}
+
+# Analyze videos with ffprobe (if installed) before every upload
+# Same as 'vidyeet upload --probe'
+probe = false
+
+# Reject videos longer than this many seconds before uploading
+# (match your plan's duration limit; checked with ffprobe or the MP4 header)
+# max_duration_secs = 3600
+
+# Number of assets your plan allows ('vidyeet status' shows how many remain)
+# asset_limit = 10
+
+# What to do when the plan's asset limit is reached during an upload
+# "ask": confirm before deleting the oldest asset (fails with --machine)
+# "auto_delete": delete the oldest asset without asking
+# "fail": never delete; the upload fails
+# Overridden by 'vidyeet upload --on-full <policy>'
+capacity_policy = "ask"
+
+# Show a desktop notification when an upload that took longer than
+# notify_after_secs seconds finishes or fails
+notify = false
+notify_after_secs = {}
+
+# Warn when a file with the same name and size was uploaded within this many
+# days (catches accidental re-runs of upload scripts); 0 disables the warning
+# Skipped by 'vidyeet upload --force'
+recent_upload_days = {}
+
+# Language of human-readable output ("en" or "ja")
+# Defaults to LC_ALL / LC_MESSAGES / LANG; --machine output is always English
+# language = "ja"
+
+# Values used when the corresponding command-line flag is omitted
+[defaults]
+# Playback policy of uploaded assets ("public" or "signed")
+# Overridden by 'vidyeet upload --policy <policy>'
+# playback_policy = "signed"
+# Metadata set on every uploaded asset
+# title_template: {{filename}}, {{stem}} (file name without extension), {{date}} (upload date)
+# creator_id = "team-marketing"
+# title_template = "{{filename}} {{date}}"
+
+# Commands run around uploads (via 'sh -c', or 'cmd /C' on Windows)
+# JSON is passed on stdin, and VIDYEET_* environment variables
+# (VIDYEET_EVENT, VIDYEET_FILE_PATH, VIDYEET_ASSET_ID, VIDYEET_HLS_URL, ...) are set
+# on_before_upload may print a path on its last stdout line to upload that file instead
+[hooks]
+# on_before_upload = "./remux-to-mp4.sh"
+# on_upload_success = "./notify-cms.sh"
+# on_upload_failure = "notify-send 'vidyeet upload failed'"
+
+# Post a short message when uploads complete or fail
+# (Slack incoming webhook or Discord webhook URL)
+[notifications]
+# webhook_url = "https://hooks.slack.com/services/..."
+
+# Append every API call that changes something (POST/PUT/PATCH/DELETE) to a
+# JSONL audit log with the endpoint, asset ID, status code, profile and OS user
+[audit]
+enabled = false
+# path = "/var/log/vidyeet/audit.jsonl"
+
+# URL signing key for assets whose playback policy is "signed"
+# (create one in the Mux dashboard or with POST /system/v1/signing-keys)
+# show/list/upload print short-lived signed URLs; set the lifetime with --expires
+[signing]
+# key_id = "your-signing-key-id"
+# private_key = "base64-encoded-private-key"
+# Only allow playback from the domains of this playback restriction
+# (create one with: vidyeet restrictions create --domain example.com)
+# playback_restriction_id = "your-playback-restriction-id"
 "#,
-            DEFAULT_TIMEZONE_OFFSET
+            DEFAULT_TIMEZONE_OFFSET, DEFAULT_NOTIFY_AFTER_SECS, DEFAULT_RECENT_UPLOAD_DAYS
         )
     }
 
@@ -211,6 +543,11 @@ timezone_offset_seconds = {}
     /// # 検証内容
     /// - auth.token_id: 空文字列でないこと
     /// - auth.token_secret: 空文字列でないこと
+    /// - language: 対応している表示言語であること
+    /// - asset_limit: 1以上であること
+    /// - notifications.webhook_url: http(s)のURLであること
+    /// - defaults.creator_id: 空でなく、上限の文字数以下であること
+    /// - defaults.title_template: 空でないこと
     ///
     /// # Errors
     /// 検証に失敗した場合に ConfigError::ValidationError を返します。
@@ -224,6 +561,75 @@ timezone_offset_seconds = {}
         // タイムゾーンオフセットの検証
         Self::validate_timezone_offset(self.timezone_offset_seconds)?;
 
+        // 表示言語の検証
+        if let Some(language) = &self.language {
+            Self::validate_language(language)?;
+        }
+
+        // アセット数上限の検証
+        if self.asset_limit == Some(0) {
+            return Err(ConfigError::validation_error(
+                "Invalid asset_limit: 0. It must be 1 or more (remove the setting for no limit)",
+            ));
+        }
+
+        // 許可する形式の検証
+        if let Some(formats) = &self.allowed_formats {
+            Self::validate_allowed_formats(formats)?;
+        }
+
+        // Webhook URLの検証
+        if let Some(url) = &self.notifications.webhook_url {
+            Self::validate_webhook_url(url)?;
+        }
+
+        // アップロードのメタデータの既定値の検証
+        Self::validate_defaults(&self.defaults)?;
+
+        Ok(())
+    }
+
+    /// アップロードのメタデータの既定値を検証
+    fn validate_defaults(defaults: &DefaultsConfig) -> Result<(), ConfigError> {
+        if let Some(creator_id) = &defaults.creator_id {
+            let max = APP_CONFIG.metadata.max_creator_id_chars;
+            if creator_id.trim().is_empty() || creator_id.chars().count() > max {
+                return Err(ConfigError::validation_error(format!(
+                    "Invalid defaults.creator_id: it must be 1 to {} characters (remove the setting to leave it unset)",
+                    max
+                )));
+            }
+        }
+        if defaults
+            .title_template
+            .as_ref()
+            .is_some_and(|template| template.trim().is_empty())
+        {
+            return Err(ConfigError::validation_error(
+                "Invalid defaults.title_template: the template is empty (remove the setting to leave titles unset)",
+            ));
+        }
+        Ok(())
+    }
+
+    /// 許可する形式を検証（空でなく、各要素がドットなしの小文字の拡張子であること）
+    fn validate_allowed_formats(formats: &[String]) -> Result<(), ConfigError> {
+        if formats.is_empty() {
+            return Err(ConfigError::validation_error(
+                "Invalid allowed_formats: the list is empty (remove the setting to allow the built-in formats)",
+            ));
+        }
+        if let Some(format) = formats.iter().find(|format| {
+            format.is_empty()
+                || !format
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        }) {
+            return Err(ConfigError::validation_error(format!(
+                "Invalid allowed_formats entry '{}'. Use lowercase extensions without a dot (e.g. \"mp4\")",
+                format
+            )));
+        }
         Ok(())
     }
 
@@ -249,6 +655,28 @@ timezone_offset_seconds = {}
         Ok(())
     }
 
+    /// 表示言語を検証
+    fn validate_language(language: &str) -> Result<(), ConfigError> {
+        if !SUPPORTED_LANGUAGES.contains(&language) {
+            return Err(ConfigError::validation_error(format!(
+                "Invalid language '{}'. Must be one of: {}",
+                language,
+                SUPPORTED_LANGUAGES.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// Webhook URLを検証
+    fn validate_webhook_url(url: &str) -> Result<(), ConfigError> {
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            return Err(ConfigError::validation_error(
+                "Invalid notifications.webhook_url. It must start with https:// (or http://)",
+            ));
+        }
+        Ok(())
+    }
+
     /// 認証情報を設定
     pub fn set_auth(&mut self, token_id: String, token_secret: String) {
         self.auth = Some(AuthConfig {
@@ -291,6 +719,7 @@ mod tests {
         let mut config = UserConfig {
             auth: None,
             timezone_offset_seconds: 0,
+            ..Default::default()
         };
 
         assert!(!config.has_auth());
@@ -353,6 +782,7 @@ mod tests {
         let mut test_config = UserConfig {
             auth: None,
             timezone_offset_seconds: 32400, // JST = UTC+9
+            ..Default::default()
         };
         test_config.set_auth("test_id_xyz".to_string(), "test_secret_xyz".to_string());
 
@@ -396,6 +826,7 @@ mod tests {
                     token_secret: "test_token_secret".to_string(),
                 }),
                 timezone_offset_seconds: 0,
+                ..Default::default()
             };
 
             test_config.save().expect("Failed to save config");
@@ -441,6 +872,7 @@ mod tests {
                 token_secret: "test_token_secret".to_string(),
             }),
             timezone_offset_seconds: 0, // UTC
+            ..Default::default()
         };
 
         // TOML形式にシリアライズ
@@ -453,12 +885,96 @@ mod tests {
         assert!(serialized.contains("timezone_offset_seconds"));
     }
 
+    #[test]
+    fn test_hooks_section_parsing() {
+        // [hooks] セクションが読み込まれ、省略時は未設定になることを確認
+        let config: UserConfig = toml::from_str(
+            r#"
+timezone_offset_seconds = 0
+
+[hooks]
+on_upload_success = "./notify.sh"
+"#,
+        )
+        .expect("Failed to parse");
+        assert_eq!(
+            config.hooks.on_upload_success.as_deref(),
+            Some("./notify.sh")
+        );
+        assert!(config.hooks.on_upload_failure.is_none());
+
+        let config: UserConfig =
+            toml::from_str("timezone_offset_seconds = 0").expect("Failed to parse");
+        assert!(config.hooks.on_upload_success.is_none());
+
+        // 既定のテンプレートもそのまま読み込めること
+        let config: UserConfig =
+            toml::from_str(&UserConfig::default_toml_content()).expect("Failed to parse");
+        assert!(config.hooks.on_upload_failure.is_none());
+        assert!(!config.notify);
+        assert_eq!(config.notify_after_secs, DEFAULT_NOTIFY_AFTER_SECS);
+        assert_eq!(config.recent_upload_days, DEFAULT_RECENT_UPLOAD_DAYS);
+        assert!(config.notifications.webhook_url.is_none());
+        assert_eq!(config.capacity_policy, CapacityPolicy::Ask);
+        assert!(!config.audit.enabled);
+        assert!(config.audit.path.is_none());
+        assert!(config.signing.key_id.is_none());
+        assert!(config.signing.private_key.is_none());
+        assert!(config.signing.playback_restriction_id.is_none());
+        assert!(config.defaults.playback_policy.is_none());
+    }
+
+    #[test]
+    fn test_defaults_playback_policy() {
+        let config: UserConfig =
+            toml::from_str("[defaults]\nplayback_policy = \"signed\"").unwrap();
+        assert_eq!(
+            config.defaults.playback_policy,
+            Some(PlaybackPolicy::Signed)
+        );
+        assert!(toml::from_str::<UserConfig>("[defaults]\nplayback_policy = \"private\"").is_err());
+    }
+
+    #[test]
+    fn test_validate_defaults_metadata() {
+        let mut config: UserConfig = toml::from_str(
+            "[defaults]\ncreator_id = \"team-a\"\ntitle_template = \"{filename} {date}\"",
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+
+        config.defaults.creator_id = Some(" ".to_string());
+        assert!(config.validate().is_err());
+        let max = APP_CONFIG.metadata.max_creator_id_chars;
+        config.defaults.creator_id = Some("a".repeat(max + 1));
+        assert!(config.validate().is_err());
+
+        config.defaults.creator_id = None;
+        config.defaults.title_template = Some(String::new());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_capacity_policy() {
+        let config: UserConfig = toml::from_str("capacity_policy = \"auto_delete\"").unwrap();
+        assert_eq!(config.capacity_policy, CapacityPolicy::AutoDelete);
+        assert!(toml::from_str::<UserConfig>("capacity_policy = \"always\"").is_err());
+
+        assert_eq!(CapacityPolicy::parse("fail"), Some(CapacityPolicy::Fail));
+        assert_eq!(
+            CapacityPolicy::parse("Auto-Delete"),
+            Some(CapacityPolicy::AutoDelete)
+        );
+        assert_eq!(CapacityPolicy::parse("delete"), None);
+    }
+
     #[test]
     fn test_validate_accepts_config_without_auth() {
         // 認証情報なしの設定は有効
         let config = UserConfig {
             auth: None,
             timezone_offset_seconds: 0,
+            ..Default::default()
         };
 
         let result = config.validate();
@@ -504,4 +1020,67 @@ mod tests {
         let result = config.validate();
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_validate_language() {
+        let mut config = UserConfig {
+            language: Some("ja".to_string()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.language = Some("fr".to_string());
+        if let Err(ConfigError::ValidationError { message }) = config.validate() {
+            assert!(message.contains("fr"));
+        } else {
+            panic!("Expected ValidationError for unsupported language");
+        }
+    }
+
+    #[test]
+    fn test_validate_asset_limit() {
+        let mut config = UserConfig {
+            asset_limit: Some(10),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.asset_limit = Some(0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_allowed_formats() {
+        let mut config = UserConfig {
+            allowed_formats: Some(vec!["mp4".to_string(), "mp2".to_string()]),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.allowed_formats = Some(Vec::new());
+        assert!(config.validate().is_err());
+
+        config.allowed_formats = Some(vec![".MP4".to_string()]);
+        if let Err(ConfigError::ValidationError { message }) = config.validate() {
+            assert!(message.contains(".MP4"));
+        } else {
+            panic!("Expected ValidationError for invalid format");
+        }
+    }
+
+    #[test]
+    fn test_validate_webhook_url() {
+        let mut config = UserConfig::default();
+        config.notifications.webhook_url =
+            Some("https://hooks.slack.com/services/T/B/X".to_string());
+        assert!(config.validate().is_ok());
+
+        // URLは秘密情報のため、エラーメッセージに含めない
+        config.notifications.webhook_url = Some("hooks.slack.com/services/T/B/X".to_string());
+        if let Err(ConfigError::ValidationError { message }) = config.validate() {
+            assert!(!message.contains("services/T/B/X"));
+        } else {
+            panic!("Expected ValidationError for invalid webhook URL");
+        }
+    }
 }
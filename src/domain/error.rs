@@ -2,6 +2,7 @@
 ///
 /// ビジネスロジックに関連するエラーを構造化して定義。
 /// 外部クレートのエラーは含まず、純粋にドメインの制約違反を表現する。
+use crate::domain::formatter::format_size;
 use crate::error_severity::ErrorSeverity;
 use thiserror::Error;
 
@@ -20,7 +21,11 @@ pub enum DomainError {
     },
 
     /// ファイルサイズが制限を超過
-    #[error("file too large: {size} bytes (maximum allowed: {max} bytes)")]
+    #[error(
+        "file too large: {} (maximum allowed: {})",
+        format_size(*.size),
+        format_size(*.max)
+    )]
     FileTooLarge { size: u64, max: u64 },
 
     /// ファイルが空
@@ -30,6 +35,60 @@ pub enum DomainError {
     /// ディレクトリが指定された（ファイルが期待される場所）
     #[error("'{path}' is a directory, not a file")]
     NotAFile { path: String },
+
+    /// ファイルの内容（コンテナ形式）が拡張子と一致しない
+    #[error(
+        "file content does not match its extension: {path} (extension: {extension}, detected: {detected})"
+    )]
+    ContentMismatch {
+        path: String,
+        extension: String,
+        detected: String,
+    },
+
+    /// メディア解析（ffprobe）でMuxが取り込めない形式と判定された
+    #[error("unsupported media: {path} ({reason})")]
+    UnsupportedMedia { path: String, reason: String },
+
+    /// 再生時間が設定された上限（`max_duration_secs`）を超過
+    #[error(
+        "video too long: {path} ({duration_secs:.1} seconds, maximum allowed: {max_secs} seconds)"
+    )]
+    DurationTooLong {
+        path: String,
+        duration_secs: f64,
+        max_secs: u64,
+    },
+
+    /// 同じファイルがアップロード済み（ローカルインデックスで検出）
+    #[error("this file appears to already exist as asset {asset_id}: {path}")]
+    DuplicateUpload { path: String, asset_id: String },
+
+    /// タグの形式が不正
+    #[error("invalid tag '{tag}': {reason}")]
+    InvalidTag { tag: String, reason: String },
+
+    /// 言語コード（BCP 47）の形式が不正
+    #[error("invalid language code '{code}': {reason}")]
+    InvalidLanguageCode { code: String, reason: String },
+
+    /// passthroughにvidyeet以外が書き込んだ値がある（上書きしない）
+    #[error("passthrough of asset {asset_id} contains data not written by vidyeet")]
+    PassthroughInUse { asset_id: String },
+
+    /// passthroughが上限の文字数を超える
+    #[error("passthrough would be {len} characters (maximum allowed: {max})")]
+    PassthroughTooLong { len: usize, max: usize },
+
+    /// プランのアセット数上限に達しており、古いアセットの削除が許可されていない
+    ///
+    /// `oldest_*` は空きを作るために削除される最も古いアセット（一覧を取得できた場合のみ）
+    #[error("the asset limit of your plan has been reached{}", .oldest_asset_id.as_ref().map(|id| format!(" (oldest asset: {})", id)).unwrap_or_default())]
+    AssetLimitReached {
+        oldest_asset_id: Option<String>,
+        oldest_title: Option<String>,
+        oldest_created_at: Option<String>,
+    },
 }
 
 impl DomainError {
@@ -61,6 +120,72 @@ impl DomainError {
         Self::NotAFile { path: path.into() }
     }
 
+    /// 内容と拡張子の不一致エラーを生成
+    pub fn content_mismatch(
+        path: impl Into<String>,
+        extension: impl Into<String>,
+        detected: impl Into<String>,
+    ) -> Self {
+        Self::ContentMismatch {
+            path: path.into(),
+            extension: extension.into(),
+            detected: detected.into(),
+        }
+    }
+
+    /// 取り込めないメディアのエラーを生成
+    pub fn unsupported_media(path: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::UnsupportedMedia {
+            path: path.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// 再生時間超過エラーを生成
+    pub fn duration_too_long(path: impl Into<String>, duration_secs: f64, max_secs: u64) -> Self {
+        Self::DurationTooLong {
+            path: path.into(),
+            duration_secs,
+            max_secs,
+        }
+    }
+
+    /// 重複アップロードエラーを生成
+    pub fn duplicate_upload(path: impl Into<String>, asset_id: impl Into<String>) -> Self {
+        Self::DuplicateUpload {
+            path: path.into(),
+            asset_id: asset_id.into(),
+        }
+    }
+
+    /// タグの形式エラーを生成
+    pub fn invalid_tag(tag: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::InvalidTag {
+            tag: tag.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// 言語コードの形式エラーを生成
+    pub fn invalid_language_code(code: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::InvalidLanguageCode {
+            code: code.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// passthrough使用中エラーを生成
+    pub fn passthrough_in_use(asset_id: impl Into<String>) -> Self {
+        Self::PassthroughInUse {
+            asset_id: asset_id.into(),
+        }
+    }
+
+    /// passthroughの文字数超過エラーを生成
+    pub fn passthrough_too_long(len: usize, max: usize) -> Self {
+        Self::PassthroughTooLong { len, max }
+    }
+
     /// エラーの深刻度を返す
     ///
     /// 終了コードの決定に使用できる
@@ -71,6 +196,37 @@ impl DomainError {
             Self::FileTooLarge { .. } => ErrorSeverity::UserError,
             Self::EmptyFile { .. } => ErrorSeverity::UserError,
             Self::NotAFile { .. } => ErrorSeverity::UserError,
+            Self::ContentMismatch { .. } => ErrorSeverity::UserError,
+            Self::UnsupportedMedia { .. } => ErrorSeverity::UserError,
+            Self::DurationTooLong { .. } => ErrorSeverity::UserError,
+            Self::DuplicateUpload { .. } => ErrorSeverity::UserError,
+            Self::InvalidTag { .. } => ErrorSeverity::UserError,
+            Self::InvalidLanguageCode { .. } => ErrorSeverity::UserError,
+            Self::PassthroughInUse { .. } => ErrorSeverity::UserError,
+            Self::PassthroughTooLong { .. } => ErrorSeverity::UserError,
+            Self::AssetLimitReached { .. } => ErrorSeverity::UserError,
+        }
+    }
+
+    /// 機械可読なエラーコードを返す
+    ///
+    /// `--machine` 出力の `error.code` に使われる安定した識別子（変更しない）
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::FileNotFound { .. } => "FILE_NOT_FOUND",
+            Self::InvalidFormat { .. } => "INVALID_FORMAT",
+            Self::FileTooLarge { .. } => "FILE_TOO_LARGE",
+            Self::EmptyFile { .. } => "EMPTY_FILE",
+            Self::NotAFile { .. } => "NOT_A_FILE",
+            Self::ContentMismatch { .. } => "CONTENT_MISMATCH",
+            Self::UnsupportedMedia { .. } => "UNSUPPORTED_MEDIA",
+            Self::DurationTooLong { .. } => "DURATION_TOO_LONG",
+            Self::DuplicateUpload { .. } => "DUPLICATE_UPLOAD",
+            Self::InvalidTag { .. } => "INVALID_TAG",
+            Self::InvalidLanguageCode { .. } => "INVALID_LANGUAGE_CODE",
+            Self::PassthroughInUse { .. } => "PASSTHROUGH_IN_USE",
+            Self::PassthroughTooLong { .. } => "PASSTHROUGH_TOO_LONG",
+            Self::AssetLimitReached { .. } => "ASSET_LIMIT_REACHED",
         }
     }
 
@@ -84,6 +240,31 @@ impl DomainError {
             Self::FileTooLarge { .. } => Some("Try compressing the video or use a smaller file."),
             Self::EmptyFile { .. } => Some("The file appears to be empty or corrupted."),
             Self::NotAFile { .. } => Some("Please specify a file, not a directory."),
+            Self::ContentMismatch { .. } => Some(
+                "The file may be mislabeled or not a video. Check the file, or rename it with the correct extension.",
+            ),
+            Self::UnsupportedMedia { .. } => Some(
+                "The file could not be read as a video. Check that it plays locally, or re-export it (e.g. H.264 MP4).",
+            ),
+            Self::DurationTooLong { .. } => Some(
+                "Trim the video to fit your plan's duration limit, or raise 'max_duration_secs' in config.toml.",
+            ),
+            Self::DuplicateUpload { .. } => Some(
+                "Check the existing asset with 'vidyeet show <asset_id>', or re-run with --force to upload it again.",
+            ),
+            Self::InvalidTag { .. } => {
+                Some("Tags may contain letters, digits, '-', '_' and '.' (up to 32 characters).")
+            }
+            Self::InvalidLanguageCode { .. } => {
+                Some("Use a BCP 47 language code such as 'en', 'ja' or 'pt-BR'.")
+            }
+            Self::PassthroughInUse { .. } => Some(
+                "Tags are stored in the asset's passthrough field. Clear it in the Mux dashboard to tag this asset.",
+            ),
+            Self::PassthroughTooLong { .. } => Some("Remove some tags or use shorter tag names."),
+            Self::AssetLimitReached { .. } => Some(
+                "Free up space with 'vidyeet prune --keep <n>' or 'vidyeet delete', or re-run with --on-full auto_delete to delete the oldest asset.",
+            ),
         }
     }
 }
@@ -0,0 +1,70 @@
+use schemars::JsonSchema;
+/// ドメイン層: アップロード前の所要時間・エンコード量の見積もり
+///
+/// ファイルサイズ、作成するアセットの画質、計測済みの転送速度から、
+/// アップロードにかかる時間とエンコードされる分数（Muxの課金単位）を見積もります。
+use serde::Serialize;
+
+/// アップロードの見積もり
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct UploadEstimate {
+    /// アップロードするバイト数
+    pub size_bytes: u64,
+    /// 見積もりに使った転送速度（バイト/秒）、計測値がない場合はNone
+    pub bytes_per_sec: Option<f64>,
+    /// 推定アップロード時間（秒）、転送速度が不明な場合はNone
+    pub upload_secs: Option<u64>,
+    /// 動画の再生時間（秒）、取得できない場合はNone
+    pub duration_secs: Option<f64>,
+    /// エンコードされる分数（再生時間を分に換算、小数点以下2桁）
+    pub encoded_minutes: Option<f64>,
+    /// 作成するアセットの画質（Muxの`video_quality`）
+    pub video_quality: String,
+}
+
+impl UploadEstimate {
+    /// 見積もりを計算する
+    ///
+    /// # 引数
+    /// * `size_bytes` - アップロードするバイト数
+    /// * `duration_secs` - 動画の再生時間（秒）
+    /// * `video_quality` - 作成するアセットの画質
+    /// * `bytes_per_sec` - 計測済みの転送速度（バイト/秒）
+    pub fn new(
+        size_bytes: u64,
+        duration_secs: Option<f64>,
+        video_quality: &str,
+        bytes_per_sec: Option<f64>,
+    ) -> Self {
+        let bytes_per_sec = bytes_per_sec.filter(|rate| *rate > 0.0);
+        Self {
+            size_bytes,
+            bytes_per_sec,
+            upload_secs: bytes_per_sec.map(|rate| (size_bytes as f64 / rate).ceil() as u64),
+            duration_secs,
+            encoded_minutes: duration_secs.map(|secs| (secs / 60.0 * 100.0).ceil() / 100.0),
+            video_quality: video_quality.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_with_measured_throughput() {
+        let estimate = UploadEstimate::new(10_000_000, Some(150.0), "premium", Some(2_000_000.0));
+        assert_eq!(estimate.upload_secs, Some(5));
+        assert_eq!(estimate.encoded_minutes, Some(2.5));
+        assert_eq!(estimate.video_quality, "premium");
+    }
+
+    #[test]
+    fn test_estimate_without_measurements() {
+        let estimate = UploadEstimate::new(10_000_000, None, "basic", Some(0.0));
+        assert_eq!(estimate.bytes_per_sec, None);
+        assert_eq!(estimate.upload_secs, None);
+        assert_eq!(estimate.encoded_minutes, None);
+    }
+}
@@ -1,8 +1,9 @@
-/// ドメインサービス: タイムスタンプフォーマット
+/// ドメインサービス: タイムスタンプ・サイズ・再生時間のフォーマット
 ///
-/// Unixタイムスタンプを人間向けの時刻文字列に変換する。
+/// Unixタイムスタンプを人間向けの時刻文字列に変換し、現在からの経過時間（「3時間前」）を求める。
 /// ドメイン層の責務として、ユーザー設定に基づいたビジネスルール(タイムゾーン変換)を適用する。
-use crate::config::UserConfig;
+/// バイト数（「10.00 MB」）と再生時間（「1:02:03」）の表記、HTMLのエスケープもここに集約する。
+use crate::config::{APP_CONFIG, BYTES_PER_GB, BYTES_PER_MB, UserConfig};
 use chrono::{DateTime, FixedOffset, TimeZone, Utc};
 
 /// Unixタイムスタンプをユーザー設定に応じてフォーマット
@@ -35,6 +36,189 @@ pub fn format_timestamp(timestamp_str: &str, user_config: &UserConfig) -> String
     format_with_offset(datetime_utc, user_config.timezone_offset_seconds)
 }
 
+/// Unixタイムスタンプの日付部分を指定したオフセットでフォーマット
+///
+/// 一覧のグループ化（`list --group-by day|month`）のキーに使います。
+///
+/// # 引数
+/// * `timestamp_str` - Unixタイムスタンプ（文字列、秒単位）
+/// * `offset_seconds` - タイムゾーンオフセット（秒）
+/// * `pattern` - chronoの書式（例: `"%Y-%m-%d"`）
+///
+/// # 戻り値
+/// パースできない場合はNone
+pub fn format_date(timestamp_str: &str, offset_seconds: i32, pattern: &str) -> Option<String> {
+    let timestamp = timestamp_str.parse::<i64>().ok()?;
+    let datetime = Utc.timestamp_opt(timestamp, 0).single()?;
+    let offset = FixedOffset::east_opt(offset_seconds)
+        .unwrap_or_else(|| FixedOffset::east_opt(0).expect("UTC offset should always be valid"));
+    Some(datetime.with_timezone(&offset).format(pattern).to_string())
+}
+
+/// テンプレートの `{名前}` を値で置き換える（未知の名前はそのまま残す）
+///
+/// 置き換えた値の中の `{名前}` は再度置き換えません。
+///
+/// # 引数
+/// * `template` - テンプレート（例: `"{filename} {date}"`）
+/// * `values` - 名前と値の組
+pub fn fill_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        let placeholder = &rest[start..];
+        let value = placeholder.find('}').and_then(|end| {
+            let name = &placeholder[1..end];
+            values
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| (*value, end + 1))
+        });
+        match value {
+            Some((value, consumed)) => {
+                filled.push_str(value);
+                rest = &placeholder[consumed..];
+            }
+            None => {
+                filled.push('{');
+                rest = &placeholder[1..];
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+/// 現在からの経過時間（相対時刻の表示用）
+///
+/// 表示の文言は表示言語に依存するため、プレゼンテーション層で組み立てます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Elapsed {
+    /// 1分未満（未来の日時も含む）
+    JustNow,
+    /// 分（1〜59）
+    Minutes(i64),
+    /// 時間（1〜23）
+    Hours(i64),
+    /// 日（1〜29）
+    Days(i64),
+    /// か月（30日単位、1〜12）
+    Months(i64),
+    /// 年（365日単位）
+    Years(i64),
+}
+
+/// Unixタイムスタンプから現在までの経過時間を求める
+///
+/// # 引数
+/// * `timestamp_str` - Unixタイムスタンプ（文字列、秒単位）
+/// * `now` - 現在日時
+///
+/// # 戻り値
+/// パースできない場合はNone
+pub fn elapsed_since(timestamp_str: &str, now: DateTime<Utc>) -> Option<Elapsed> {
+    let timestamp = timestamp_str.parse::<i64>().ok()?;
+    let seconds = now.timestamp().saturating_sub(timestamp);
+
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+    const MONTH: i64 = 30 * DAY;
+    const YEAR: i64 = 365 * DAY;
+
+    Some(match seconds {
+        s if s < MINUTE => Elapsed::JustNow,
+        s if s < HOUR => Elapsed::Minutes(s / MINUTE),
+        s if s < DAY => Elapsed::Hours(s / HOUR),
+        s if s < MONTH => Elapsed::Days(s / DAY),
+        s if s < YEAR => Elapsed::Months((s / MONTH).min(12)),
+        s => Elapsed::Years(s / YEAR),
+    })
+}
+
+/// バイト数を「10.00 MB」「1.50 GB」の形式にする
+///
+/// 1GB以上はGB、それ未満はMBで表し、小数点以下の桁数は
+/// `size_display_precision` に従います。
+pub fn format_size(bytes: u64) -> String {
+    let bytes = bytes as f64;
+    let precision = APP_CONFIG.presentation.size_display_precision;
+    if bytes >= BYTES_PER_GB {
+        format!("{:.prec$} GB", bytes / BYTES_PER_GB, prec = precision)
+    } else {
+        format!("{:.prec$} MB", bytes / BYTES_PER_MB, prec = precision)
+    }
+}
+
+/// 転送速度を「12.50 MB/s」の形式にする
+pub fn format_rate(bytes_per_sec: f64) -> String {
+    format!(
+        "{:.prec$} MB/s",
+        bytes_per_sec / BYTES_PER_MB,
+        prec = APP_CONFIG.presentation.size_display_precision
+    )
+}
+
+/// 再生時間・所要時間（秒）を「h:mm:ss」（1時間未満は「m:ss」）の形式にする
+///
+/// 1秒未満は切り捨てます。
+pub fn format_duration(duration_secs: f64) -> String {
+    let total = duration_secs.max(0.0) as u64;
+    let (hours, minutes, seconds) = (total / 3600, total % 3600 / 60, total % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// HTMLの特殊文字をエスケープする
+///
+/// `serve` のプレビューページと `--output html` のスニペットに埋め込む値に使います。
+pub fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 再生時間（秒）をISO 8601の期間（例: "PT1H15M30.5S"）にする
+///
+/// 秒の小数部はミリ秒で丸め、末尾の0は省きます。0秒は "PT0S" です。
+pub fn format_iso8601_duration(duration_secs: f64) -> String {
+    let millis = (duration_secs.max(0.0) * 1000.0).round() as u64;
+    let hours = millis / 3_600_000;
+    let minutes = millis % 3_600_000 / 60_000;
+    let seconds = millis % 60_000;
+
+    let mut iso = String::from("PT");
+    if hours > 0 {
+        iso.push_str(&format!("{}H", hours));
+    }
+    if minutes > 0 {
+        iso.push_str(&format!("{}M", minutes));
+    }
+    if seconds > 0 || millis < 60_000 {
+        let fraction = format!("{:03}", seconds % 1000);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            iso.push_str(&format!("{}S", seconds / 1000));
+        } else {
+            iso.push_str(&format!("{}.{}S", seconds / 1000, fraction));
+        }
+    }
+    iso
+}
+
 /// 指定されたオフセット(秒)でフォーマット
 fn format_with_offset(datetime: DateTime<Utc>, offset_seconds: i32) -> String {
     // オフセットを適用（無効な場合はUTCにフォールバック）
@@ -53,9 +237,10 @@ mod tests {
     use crate::config::UserConfig;
 
     fn create_test_config(timezone_offset_seconds: i32) -> UserConfig {
-        let mut config = UserConfig::default();
-        config.timezone_offset_seconds = timezone_offset_seconds;
-        config
+        UserConfig {
+            timezone_offset_seconds,
+            ..Default::default()
+        }
     }
 
     #[test]
@@ -93,6 +278,104 @@ mod tests {
         assert_eq!(result, "invalid");
     }
 
+    #[test]
+    fn test_fill_template() {
+        let values = [("filename", "clip.mp4"), ("date", "2025-11-30")];
+        assert_eq!(
+            fill_template("{filename} {date}", &values),
+            "clip.mp4 2025-11-30"
+        );
+        assert_eq!(fill_template("{unknown} {", &values), "{unknown} {");
+        // 値の中のプレースホルダーは置き換えない
+        assert_eq!(
+            fill_template("{filename}", &[("filename", "{date}.mp4"), ("date", "x")]),
+            "{date}.mp4"
+        );
+    }
+
+    #[test]
+    fn test_format_date() {
+        // 1764434950 = 2025-11-29 16:49:10 UTC = 2025-11-30 01:49:10 JST
+        assert_eq!(
+            format_date("1764434950", 0, "%Y-%m-%d").as_deref(),
+            Some("2025-11-29")
+        );
+        assert_eq!(
+            format_date("1764434950", 32400, "%Y-%m-%d").as_deref(),
+            Some("2025-11-30")
+        );
+        assert_eq!(
+            format_date("1764434950", 32400, "%Y-%m").as_deref(),
+            Some("2025-11")
+        );
+        assert_eq!(format_date("invalid", 0, "%Y-%m-%d"), None);
+    }
+
+    #[test]
+    fn test_elapsed_since() {
+        let now = Utc.timestamp_opt(1764434950, 0).unwrap();
+        let ago = |secs: i64| (1764434950 - secs).to_string();
+
+        assert_eq!(elapsed_since(&ago(30), now), Some(Elapsed::JustNow));
+        assert_eq!(elapsed_since(&ago(-600), now), Some(Elapsed::JustNow)); // 未来
+        assert_eq!(
+            elapsed_since(&ago(59 * 60), now),
+            Some(Elapsed::Minutes(59))
+        );
+        assert_eq!(
+            elapsed_since(&ago(3 * 3600 + 59), now),
+            Some(Elapsed::Hours(3))
+        );
+        assert_eq!(elapsed_since(&ago(86_400), now), Some(Elapsed::Days(1)));
+        assert_eq!(
+            elapsed_since(&ago(45 * 86_400), now),
+            Some(Elapsed::Months(1))
+        );
+        assert_eq!(
+            elapsed_since(&ago(364 * 86_400), now),
+            Some(Elapsed::Months(12))
+        );
+        assert_eq!(
+            elapsed_since(&ago(800 * 86_400), now),
+            Some(Elapsed::Years(2))
+        );
+        assert_eq!(elapsed_since("invalid", now), None);
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0.00 MB");
+        assert_eq!(format_size(10 * 1_048_576), "10.00 MB");
+        assert_eq!(format_size(1_073_741_823), "1024.00 MB");
+        assert_eq!(format_size(1_073_741_824), "1.00 GB");
+        assert_eq!(format_size(1_610_612_736), "1.50 GB");
+    }
+
+    #[test]
+    fn test_format_rate() {
+        assert_eq!(format_rate(8.0 * 1_048_576.0), "8.00 MB/s");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0.0), "0:00");
+        assert_eq!(format_duration(83.4), "1:23");
+        assert_eq!(format_duration(3599.9), "59:59");
+        assert_eq!(format_duration(3600.0), "1:00:00");
+        assert_eq!(format_duration(4530.0), "1:15:30");
+        assert_eq!(format_duration(-5.0), "0:00");
+    }
+
+    #[test]
+    fn test_format_iso8601_duration() {
+        assert_eq!(format_iso8601_duration(0.0), "PT0S");
+        assert_eq!(format_iso8601_duration(12.345678), "PT12.346S");
+        assert_eq!(format_iso8601_duration(90.5), "PT1M30.5S");
+        assert_eq!(format_iso8601_duration(3600.0), "PT1H");
+        assert_eq!(format_iso8601_duration(4530.0), "PT1H15M30S");
+        assert_eq!(format_iso8601_duration(3661.25), "PT1H1M1.25S");
+    }
+
     #[test]
     fn test_format_with_offset_utc() {
         let dt = Utc.timestamp_opt(1764434950, 0).unwrap();
@@ -0,0 +1,186 @@
+/// 言語コード（BCP 47）の検証
+///
+/// 字幕トラックの追加・自動生成（`captions`）で指定する言語コードを、Mux APIへ送る前に検証します。
+/// `language[-extlang][-script][-region][-variant][-extension][-x-privateuse]` の構文のみを確認し、
+/// IANAのレジストリに登録されたサブタグかどうかまでは確認しません。
+use crate::domain::error::DomainError;
+
+/// サブタグの最大文字数
+const MAX_SUBTAG_LEN: usize = 8;
+
+/// 私用サブタグの接頭辞（`x-...`）
+const PRIVATE_USE: &str = "x";
+
+/// 言語コードを検証し、BCP 47の推奨する大文字・小文字に正規化する
+///
+/// 区切りには `-` のほか `_`（例: `en_US`）も使用でき、`-` に置き換えます。
+/// 言語は小文字、文字体系は先頭のみ大文字、地域は大文字にします（例: `zh-hant-tw` → `zh-Hant-TW`）。
+///
+/// # Errors
+/// 空・サブタグの形式や順序が不正な場合に DomainError::InvalidLanguageCode を返します。
+pub fn normalize_language_code(code: &str) -> Result<String, DomainError> {
+    let invalid = |reason: String| DomainError::invalid_language_code(code, reason);
+
+    let trimmed = code.trim();
+    if trimmed.is_empty() {
+        return Err(invalid("language code is empty".to_string()));
+    }
+    let subtags: Vec<&str> = trimmed.split(['-', '_']).collect();
+    if subtags.iter().any(|s| s.is_empty()) {
+        return Err(invalid("contains an empty subtag".to_string()));
+    }
+    if let Some(subtag) = subtags
+        .iter()
+        .find(|s| s.len() > MAX_SUBTAG_LEN || !s.chars().all(|c| c.is_ascii_alphanumeric()))
+    {
+        return Err(invalid(format!(
+            "'{}' is not a subtag of 1 to {} ASCII letters or digits",
+            subtag, MAX_SUBTAG_LEN
+        )));
+    }
+
+    let language = subtags[0];
+    if !(is_alpha(language) && matches!(language.len(), 2..=3 | 5..=8)) {
+        return Err(invalid(format!(
+            "'{}' is not a language subtag (2 or 3 letters, e.g. en, ja)",
+            language
+        )));
+    }
+    let mut normalized = vec![language.to_ascii_lowercase()];
+    let mut i = 1;
+
+    // 拡張言語（3文字、最大3つ。例: zh-yue）
+    if language.len() <= 3 {
+        let start = i;
+        while i - start < 3
+            && let Some(s) = subtags.get(i)
+            && s.len() == 3
+            && is_alpha(s)
+        {
+            normalized.push(s.to_ascii_lowercase());
+            i += 1;
+        }
+    }
+    // 文字体系（4文字。例: Hant）
+    if let Some(s) = subtags.get(i)
+        && s.len() == 4
+        && is_alpha(s)
+    {
+        let (head, tail) = s.split_at(1);
+        normalized.push(head.to_ascii_uppercase() + &tail.to_ascii_lowercase());
+        i += 1;
+    }
+    // 地域（2文字または3桁。例: US, 419）
+    if let Some(s) = subtags.get(i)
+        && ((s.len() == 2 && is_alpha(s)) || (s.len() == 3 && is_digit(s)))
+    {
+        normalized.push(s.to_ascii_uppercase());
+        i += 1;
+    }
+    // バリアント（5〜8文字、または数字で始まる4文字。例: 1996）
+    while let Some(s) = subtags.get(i)
+        && is_variant(s)
+    {
+        let variant = s.to_ascii_lowercase();
+        if normalized.contains(&variant) {
+            return Err(invalid(format!("variant '{}' is repeated", s)));
+        }
+        normalized.push(variant);
+        i += 1;
+    }
+    // 拡張（1文字 + 2〜8文字のサブタグ）と私用（x + 1〜8文字のサブタグ、以降はすべて私用）
+    while let Some(s) = subtags.get(i)
+        && s.len() == 1
+    {
+        let singleton = s.to_ascii_lowercase();
+        let min_len = if singleton == PRIVATE_USE { 1 } else { 2 };
+        normalized.push(singleton.clone());
+        i += 1;
+
+        let start = i;
+        while let Some(s) = subtags.get(i)
+            && s.len() >= min_len
+        {
+            normalized.push(s.to_ascii_lowercase());
+            i += 1;
+        }
+        if i == start {
+            return Err(invalid(format!(
+                "'{}' must be followed by at least one subtag",
+                singleton
+            )));
+        }
+    }
+
+    if let Some(s) = subtags.get(i) {
+        return Err(invalid(format!("unexpected subtag '{}'", s)));
+    }
+    Ok(normalized.join("-"))
+}
+
+fn is_alpha(s: &str) -> bool {
+    s.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+fn is_digit(s: &str) -> bool {
+    s.chars().all(|c| c.is_ascii_digit())
+}
+
+fn is_variant(s: &str) -> bool {
+    match s.len() {
+        5..=8 => true,
+        4 => s.starts_with(|c: char| c.is_ascii_digit()),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_language_code() {
+        for (code, expected) in [
+            ("en", "en"),
+            (" JA ", "ja"),
+            ("en-us", "en-US"),
+            ("pt_BR", "pt-BR"),
+            ("zh-hant-tw", "zh-Hant-TW"),
+            ("es-419", "es-419"),
+            ("zh-yue-HK", "zh-yue-HK"),
+            ("sl-rozaj-biske", "sl-rozaj-biske"),
+            ("de-CH-1996", "de-CH-1996"),
+            ("en-US-u-ca-gregory", "en-US-u-ca-gregory"),
+            ("en-x-Auto", "en-x-auto"),
+        ] {
+            assert_eq!(normalize_language_code(code).unwrap(), expected, "{}", code);
+        }
+    }
+
+    #[test]
+    fn test_normalize_language_code_rejects_invalid() {
+        for code in [
+            "",
+            "e",
+            "engl",
+            "en-english-us",
+            "en-",
+            "en--US",
+            "en US",
+            "日本語",
+            "en-US-US",
+            "de-1996-1996",
+            "en-u",
+            "en-x",
+            "x-private",
+        ] {
+            let err = normalize_language_code(code).unwrap_err();
+            assert!(
+                matches!(err, DomainError::InvalidLanguageCode { .. }),
+                "{}: {:?}",
+                code,
+                err
+            );
+        }
+    }
+}
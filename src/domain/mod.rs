@@ -1,4 +1,8 @@
 pub mod error;
+pub mod estimate;
 pub mod formatter;
+pub mod language;
+pub mod passthrough;
 pub mod progress;
 pub mod validator;
+pub mod warning;
@@ -0,0 +1,210 @@
+/// アセットのpassthroughの読み書き
+///
+/// passthrough（Mux APIが保持する最大255文字の任意の文字列）に、
+/// アップロード時に記録した元ファイルのSHA-256とタグをJSONで格納します。
+/// チェックサムのみの場合は従来どおり `sha256:<hex>` 形式で書き込み、どちらの形式も読み込めます。
+use crate::config::APP_CONFIG;
+use crate::domain::error::DomainError;
+use serde_json::{Map, Value};
+
+/// 従来形式のチェックサムの接頭辞（例: "sha256:9f86d0..."）
+pub const CHECKSUM_PREFIX: &str = "sha256:";
+
+const SHA256_KEY: &str = "sha256";
+const TAGS_KEY: &str = "tags";
+
+/// passthroughの内容
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Passthrough {
+    /// JSONのフィールド（未知のキーも保持して書き戻す）
+    fields: Map<String, Value>,
+}
+
+impl Passthrough {
+    /// passthroughを解析する
+    ///
+    /// 空・従来形式のチェックサム・JSONオブジェクトに対応します。
+    /// それ以外（vidyeet以外が書き込んだ値）の場合は、上書きしないようNoneを返します。
+    pub fn parse(value: Option<&str>) -> Option<Self> {
+        let value = value.map(str::trim).unwrap_or_default();
+        if value.is_empty() {
+            return Some(Self::default());
+        }
+        if let Some(sha256) = value.strip_prefix(CHECKSUM_PREFIX) {
+            let mut fields = Map::new();
+            fields.insert(SHA256_KEY.to_string(), Value::from(sha256));
+            return Some(Self { fields });
+        }
+        match serde_json::from_str::<Value>(value) {
+            Ok(Value::Object(fields)) => Some(Self { fields }),
+            _ => None,
+        }
+    }
+
+    /// 元ファイルのSHA-256
+    pub fn sha256(&self) -> Option<&str> {
+        self.fields.get(SHA256_KEY).and_then(Value::as_str)
+    }
+
+    /// タグ（追加した順）
+    pub fn tags(&self) -> Vec<String> {
+        self.fields
+            .get(TAGS_KEY)
+            .and_then(Value::as_array)
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// タグを追加する（既にある場合はfalse）
+    ///
+    /// # Errors
+    /// タグの形式が不正な場合に DomainError::InvalidTag を返します。
+    pub fn add_tag(&mut self, tag: &str) -> Result<bool, DomainError> {
+        let tag = normalize_tag(tag)?;
+        let mut tags = self.tags();
+        if tags.contains(&tag) {
+            return Ok(false);
+        }
+        tags.push(tag);
+        self.set_tags(tags);
+        Ok(true)
+    }
+
+    /// タグを削除する（なかった場合はfalse）
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let tag = tag.trim().to_lowercase();
+        let mut tags = self.tags();
+        let before = tags.len();
+        tags.retain(|t| *t != tag);
+        if tags.len() == before {
+            return false;
+        }
+        self.set_tags(tags);
+        true
+    }
+
+    fn set_tags(&mut self, tags: Vec<String>) {
+        if tags.is_empty() {
+            self.fields.remove(TAGS_KEY);
+        } else {
+            self.fields.insert(TAGS_KEY.to_string(), Value::from(tags));
+        }
+    }
+
+    /// APIへ書き込む文字列にする
+    ///
+    /// チェックサムのみの場合は従来形式、何もない場合は空文字列になります。
+    ///
+    /// # Errors
+    /// passthroughの上限（255文字）を超える場合に DomainError::PassthroughTooLong を返します。
+    pub fn encode(&self) -> Result<String, DomainError> {
+        let encoded = match (self.fields.len(), self.sha256()) {
+            (0, _) => String::new(),
+            (1, Some(sha256)) => format!("{}{}", CHECKSUM_PREFIX, sha256),
+            _ => Value::Object(self.fields.clone()).to_string(),
+        };
+
+        let len = encoded.chars().count();
+        let max = APP_CONFIG.metadata.max_passthrough_chars;
+        if len > max {
+            return Err(DomainError::passthrough_too_long(len, max));
+        }
+        Ok(encoded)
+    }
+}
+
+/// タグを正規化する（前後の空白を除去し小文字に）
+///
+/// 英数字（日本語などを含む）と `-` `_` `.` のみ使用できます。
+///
+/// # Errors
+/// 空・長すぎる・使用できない文字を含む場合に DomainError::InvalidTag を返します。
+pub fn normalize_tag(tag: &str) -> Result<String, DomainError> {
+    let normalized = tag.trim().to_lowercase();
+    let max = APP_CONFIG.metadata.max_tag_chars;
+    if normalized.is_empty() {
+        return Err(DomainError::invalid_tag(tag, "tag is empty"));
+    }
+    if normalized.chars().count() > max {
+        return Err(DomainError::invalid_tag(
+            tag,
+            format!("longer than {} characters", max),
+        ));
+    }
+    if let Some(c) = normalized
+        .chars()
+        .find(|c| !(c.is_alphanumeric() || matches!(c, '-' | '_' | '.')))
+    {
+        return Err(DomainError::invalid_tag(tag, format!("contains '{}'", c)));
+    }
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA256: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    #[test]
+    fn test_parse_formats() {
+        assert_eq!(Passthrough::parse(None), Some(Passthrough::default()));
+
+        let legacy = Passthrough::parse(Some(&format!("sha256:{}", SHA256))).unwrap();
+        assert_eq!(legacy.sha256(), Some(SHA256));
+        assert!(legacy.tags().is_empty());
+
+        let json = Passthrough::parse(Some(r#"{"sha256":"abc","tags":["demo"],"x":1}"#)).unwrap();
+        assert_eq!(json.sha256(), Some("abc"));
+        assert_eq!(json.tags(), vec!["demo"]);
+
+        assert_eq!(Passthrough::parse(Some("customer-42")), None);
+        assert_eq!(Passthrough::parse(Some("[1,2]")), None);
+    }
+
+    #[test]
+    fn test_add_and_remove_tags() {
+        let mut passthrough = Passthrough::parse(Some(&format!("sha256:{}", SHA256))).unwrap();
+        assert!(passthrough.add_tag(" Demo ").unwrap());
+        assert!(!passthrough.add_tag("demo").unwrap());
+        assert!(passthrough.add_tag("2024.q1").unwrap());
+        assert_eq!(passthrough.tags(), vec!["demo", "2024.q1"]);
+
+        let encoded = passthrough.encode().unwrap();
+        let decoded = Passthrough::parse(Some(&encoded)).unwrap();
+        assert_eq!(decoded, passthrough);
+
+        assert!(passthrough.remove_tag("DEMO"));
+        assert!(!passthrough.remove_tag("missing"));
+        assert!(passthrough.remove_tag("2024.q1"));
+        // タグがなくなれば従来形式に戻る
+        assert_eq!(passthrough.encode().unwrap(), format!("sha256:{}", SHA256));
+    }
+
+    #[test]
+    fn test_normalize_tag() {
+        assert_eq!(normalize_tag("Launch_2024").unwrap(), "launch_2024");
+        assert_eq!(normalize_tag("デモ").unwrap(), "デモ");
+        assert!(normalize_tag("  ").is_err());
+        assert!(normalize_tag("has space").is_err());
+        assert!(normalize_tag("a,b").is_err());
+        assert!(normalize_tag(&"a".repeat(33)).is_err());
+    }
+
+    #[test]
+    fn test_encode_rejects_too_long() {
+        let mut passthrough = Passthrough::parse(Some(&format!("sha256:{}", SHA256))).unwrap();
+        for i in 0..20 {
+            let _ = passthrough.add_tag(&format!("tag-number-{}", i));
+        }
+        assert!(matches!(
+            passthrough.encode(),
+            Err(DomainError::PassthroughTooLong { .. })
+        ));
+    }
+}
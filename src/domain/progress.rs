@@ -1,10 +1,15 @@
+use crate::domain::estimate::UploadEstimate;
+use crate::domain::warning::Warning;
+use schemars::JsonSchema;
 use serde::Serialize;
 /// ドメイン層: アップロード進捗イベント定義
 ///
 /// アップロード処理の各段階をビジネスロジックのイベントとして表現します。
 /// プレゼンテーション層はこれらのイベントを受け取り、
 /// 人間向けの進捗表示や機械向けの制御に使用します。
-use std::time::SystemTime;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc;
 
 /// アップロード処理の各段階を表すイベント
 ///
@@ -13,7 +18,7 @@ use std::time::SystemTime;
 /// - プレゼンテーション層での柔軟な出力制御
 /// - 将来のプログレスバー実装への拡張性
 /// - 機械可読出力のためにSerialize可能
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(tag = "phase", rename_all = "snake_case")]
 pub enum UploadPhase {
     /// ファイル検証開始
@@ -26,6 +31,20 @@ pub enum UploadPhase {
         format: String,
     },
 
+    /// アップロード前の警告（再エンコードが必要な入力など）
+    ///
+    /// `--progress` の有無に関わらず人間向けには表示されます。
+    Warning {
+        #[serde(flatten)]
+        warning: Warning,
+    },
+
+    /// 最初のチャンクで計測した速度に基づく見積もり
+    Estimate {
+        #[serde(flatten)]
+        estimate: UploadEstimate,
+    },
+
     /// Direct Upload URL作成中
     CreatingDirectUpload { file_name: String },
 
@@ -39,14 +58,43 @@ pub enum UploadPhase {
         total_chunks: usize,
     },
 
+    /// ffmpegで変換しながらアップロード中（`--transcode`、一定間隔で通知）
+    ///
+    /// 変換の出力をそのまま送信するため、総バイト数とチャンク数は変換が終わるまで分かりません。
+    /// 進捗率は変換済みの再生位置から計算します（送信は変換に最大1チャンク分遅れて追従します）。
+    Transcoding {
+        /// 変換済みの再生位置（秒）
+        out_time_secs: f64,
+        /// 元ファイルの再生時間（秒）、ffprobeで取得できない場合はNone
+        duration_secs: Option<f64>,
+        /// 進捗率（0.0〜100.0、小数点以下1桁）、再生時間が不明な場合はNone
+        percent: Option<f64>,
+        /// 送信済みのバイト数
+        bytes_sent: u64,
+        /// 送信開始からの平均転送速度（バイト/秒）
+        bytes_per_sec: f64,
+    },
+
     /// チャンクアップロード中
     UploadingChunk {
         current_chunk: usize,
         total_chunks: usize,
         bytes_sent: u64,
         total_bytes: u64,
+        /// 進捗率（0.0〜100.0、小数点以下1桁）
+        percent: f64,
+        /// 指数平滑化した転送速度（バイト/秒）
+        bytes_per_sec: f64,
+        /// 残り時間の推定値（秒）、速度が未確定の場合はNone
+        eta_secs: Option<u64>,
     },
 
+    /// チャンク転送を一時停止（対話モードで `p` を入力）
+    Paused { bytes_sent: u64, total_bytes: u64 },
+
+    /// チャンク転送を再開（対話モードで `r` を入力）
+    Resumed { bytes_sent: u64, total_bytes: u64 },
+
     /// ファイルアップロード完了
     FileUploaded { file_name: String, size_bytes: u64 },
 
@@ -57,6 +105,14 @@ pub enum UploadPhase {
         elapsed_secs: u64,
     },
 
+    /// アセットが指定した状態になるのを待機中（`wait` コマンド）
+    WaitingForReady {
+        asset_id: String,
+        /// 待機対象の現在のステータス（`--until mp4-ready` ではMP4のステータス）
+        status: String,
+        elapsed_secs: u64,
+    },
+
     /// アップロード処理完了
     Completed { asset_id: String },
 }
@@ -68,6 +124,9 @@ pub enum UploadPhase {
 pub struct UploadProgress {
     /// 処理段階
     pub phase: UploadPhase,
+    /// 複数ファイルのアップロードで、どのファイルの進捗か（単一ファイル時はNone）
+    #[serde(skip)]
+    pub job: Option<UploadJob>,
     /// イベント発生時刻（将来の分析や詳細ログ用に保持）
     #[serde(skip)]
     #[allow(dead_code)]
@@ -79,7 +138,312 @@ impl UploadProgress {
     pub fn new(phase: UploadPhase) -> Self {
         Self {
             phase,
+            job: None,
             timestamp: SystemTime::now(),
         }
     }
 }
+
+/// 並行して進むアップロードの1件（ジョブ）の識別情報
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UploadJob {
+    /// ジョブID（ファイル番号、1始まり、指定順）
+    pub job_id: usize,
+    /// 表示用のラベル（ファイル名）
+    pub label: String,
+}
+
+impl UploadJob {
+    /// ファイルパスからジョブを作成する（ラベルはファイル名）
+    pub fn new(job_id: usize, file_path: &str) -> Self {
+        let label = std::path::Path::new(file_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| file_path.to_string());
+        Self { job_id, label }
+    }
+}
+
+/// 複数ファイルアップロードにおける1ファイル分の進捗情報
+///
+/// 並行して進む各ファイルの`UploadProgress`を、どのファイルのものか識別できるよう
+/// ファイル番号とパスを付けて1本のチャネルに多重化するために使用します。
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BatchUploadProgress {
+    /// ファイル番号（1始まり、指定順）
+    pub file_index: usize,
+    /// ファイル総数
+    pub total_files: usize,
+    /// ファイルパス
+    ///
+    /// `validating_file` フェーズの `file_path` と衝突しないよう別名で出力する
+    pub source_path: String,
+    /// 表示用のラベル（ファイル名）
+    pub label: String,
+    /// 処理段階（単一ファイル時と同じ形式で展開して出力）
+    #[serde(flatten)]
+    pub phase: UploadPhase,
+}
+
+impl BatchUploadProgress {
+    /// ジョブ付きの進捗から作成する（ファイル番号とラベルはジョブから取る）
+    pub fn new(progress: UploadProgress, total_files: usize, source_path: String) -> Self {
+        let job = progress.job.unwrap_or_default();
+        Self {
+            file_index: job.job_id,
+            total_files,
+            source_path,
+            label: job.label,
+            phase: progress.phase,
+        }
+    }
+}
+
+/// 進捗通知の送信側
+///
+/// 受信側（表示）が遅れてもアップロード処理を止めず、イベントを取りこぼさないよう、
+/// 容量の上限がないチャネルへ送信順に届けます。送信は待機せず、失敗もしません
+/// （受信側が破棄された後の送信は無視します）。
+/// イベントはチャンクごと・ポーリングごとに1件のため、溜まる量はファイルサイズと待機時間に比例する程度です。
+///
+/// 複数ファイルのアップロードでは [`ProgressSender::for_job`] でジョブを付け、
+/// [`ProgressSender::from_fn`] で多重化チャネルへ転送します。
+#[derive(Clone)]
+pub struct ProgressSender {
+    deliver: Arc<dyn Fn(UploadProgress) + Send + Sync>,
+}
+
+impl ProgressSender {
+    /// 進捗を受け取る関数から作成する（関数は送信側のタスクで同期的に呼ばれる）
+    pub fn from_fn(deliver: impl Fn(UploadProgress) + Send + Sync + 'static) -> Self {
+        Self {
+            deliver: Arc::new(deliver),
+        }
+    }
+
+    /// 送信する進捗にジョブを付ける送信側を作る
+    pub fn for_job(&self, job: UploadJob) -> Self {
+        let deliver = Arc::clone(&self.deliver);
+        Self::from_fn(move |mut progress| {
+            progress.job = Some(job.clone());
+            deliver(progress);
+        })
+    }
+
+    /// 進捗を送信する
+    pub fn send(&self, phase: UploadPhase) {
+        (self.deliver)(UploadProgress::new(phase));
+    }
+}
+
+impl From<mpsc::UnboundedSender<UploadProgress>> for ProgressSender {
+    fn from(tx: mpsc::UnboundedSender<UploadProgress>) -> Self {
+        Self::from_fn(move |progress| {
+            let _ = tx.send(progress);
+        })
+    }
+}
+
+impl std::fmt::Debug for ProgressSender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressSender").finish_non_exhaustive()
+    }
+}
+
+/// 進捗通知用のチャネルを作成する
+pub fn progress_channel() -> (ProgressSender, mpsc::UnboundedReceiver<UploadProgress>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    (ProgressSender::from(tx), rx)
+}
+
+/// 送信済みバイト数から進捗率を計算
+///
+/// 0.0〜100.0の範囲で、小数点以下1桁に丸めた値を返します。
+/// 総バイト数が0の場合は100.0（完了扱い）を返します。
+pub fn percent_complete(bytes_sent: u64, total_bytes: u64) -> f64 {
+    if total_bytes == 0 {
+        return 100.0;
+    }
+    let ratio = (bytes_sent as f64 / total_bytes as f64).clamp(0.0, 1.0);
+    (ratio * 1000.0).round() / 10.0
+}
+
+/// 再生位置から進捗率を計算
+///
+/// 0.0〜100.0の範囲で、小数点以下1桁に丸めた値を返します。
+/// 再生時間が不明または0の場合はNoneを返します。
+pub fn percent_of_duration(position_secs: f64, duration_secs: Option<f64>) -> Option<f64> {
+    let duration_secs = duration_secs.filter(|secs| *secs > 0.0)?;
+    let ratio = (position_secs / duration_secs).clamp(0.0, 1.0);
+    Some((ratio * 1000.0).round() / 10.0)
+}
+
+/// 転送速度の推定器
+///
+/// チャンク単位の転送速度を指数移動平均（EMA）で平滑化し、
+/// 残り時間（ETA）を推定します。チャンクごとの速度のばらつきを吸収することで、
+/// 表示されるETAが大きく揺れないようにします。
+#[derive(Debug, Clone)]
+pub struct ThroughputEstimator {
+    /// 平滑化係数（0.0〜1.0、大きいほど直近の値を重視）
+    smoothing_factor: f64,
+    /// 平滑化済みの転送速度（バイト/秒）
+    smoothed_bytes_per_sec: Option<f64>,
+}
+
+impl ThroughputEstimator {
+    /// 新しい推定器を作成
+    ///
+    /// # 引数
+    /// * `smoothing_factor` - EMAの平滑化係数（0.0〜1.0に丸められる）
+    pub fn new(smoothing_factor: f64) -> Self {
+        Self {
+            smoothing_factor: smoothing_factor.clamp(0.0, 1.0),
+            smoothed_bytes_per_sec: None,
+        }
+    }
+
+    /// 転送サンプルを記録し、平滑化後の速度を返す
+    ///
+    /// 初回サンプルはそのまま採用し、以降は
+    /// `smoothed = α * sample + (1 - α) * smoothed` で更新します。
+    /// 経過時間が0の場合は速度を更新しません。
+    pub fn record(&mut self, bytes: u64, elapsed: Duration) -> f64 {
+        let secs = elapsed.as_secs_f64();
+        if secs > 0.0 {
+            let sample = bytes as f64 / secs;
+            let updated = match self.smoothed_bytes_per_sec {
+                Some(prev) => self.smoothing_factor * sample + (1.0 - self.smoothing_factor) * prev,
+                None => sample,
+            };
+            self.smoothed_bytes_per_sec = Some(updated);
+        }
+        self.bytes_per_sec()
+    }
+
+    /// 現在の平滑化済み転送速度（バイト/秒）
+    pub fn bytes_per_sec(&self) -> f64 {
+        self.smoothed_bytes_per_sec.unwrap_or(0.0)
+    }
+
+    /// 残りバイト数から推定残り時間（秒）を計算
+    ///
+    /// 速度が未確定（サンプルなし、または0）の場合はNoneを返します。
+    pub fn eta_secs(&self, remaining_bytes: u64) -> Option<u64> {
+        match self.smoothed_bytes_per_sec {
+            Some(rate) if rate > 0.0 => Some((remaining_bytes as f64 / rate).ceil() as u64),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_channel_delivers_every_event_in_order() {
+        let (tx, mut rx) = progress_channel();
+        // 受信側が読まなくても送信は止まらない（容量の上限がない）
+        for elapsed_secs in 0..1_000 {
+            tx.send(UploadPhase::WaitingForAsset {
+                upload_id: "up_1".to_string(),
+                elapsed_secs,
+            });
+        }
+        tx.send(UploadPhase::Completed {
+            asset_id: "asset_1".to_string(),
+        });
+        drop(tx);
+
+        let mut received = Vec::new();
+        while let Ok(progress) = rx.try_recv() {
+            received.push(progress.phase);
+        }
+        assert_eq!(received.len(), 1_001);
+        assert!(matches!(
+            received.last(),
+            Some(UploadPhase::Completed { asset_id }) if asset_id == "asset_1"
+        ));
+    }
+
+    #[test]
+    fn test_progress_sender_ignores_closed_receiver() {
+        let (tx, rx) = progress_channel();
+        drop(rx);
+        tx.send(UploadPhase::Completed {
+            asset_id: "asset_1".to_string(),
+        });
+    }
+
+    #[test]
+    fn test_progress_sender_for_job_tags_events() {
+        let (tx, mut rx) = progress_channel();
+        let job = UploadJob::new(2, "/videos/clip.mp4");
+        assert_eq!(job.label, "clip.mp4");
+
+        tx.for_job(job.clone()).send(UploadPhase::Completed {
+            asset_id: "asset_1".to_string(),
+        });
+        tx.send(UploadPhase::Completed {
+            asset_id: "asset_2".to_string(),
+        });
+
+        assert_eq!(rx.try_recv().unwrap().job, Some(job));
+        assert_eq!(rx.try_recv().unwrap().job, None);
+    }
+
+    #[test]
+    fn test_percent_complete_rounds_to_one_decimal() {
+        assert_eq!(percent_complete(1, 3), 33.3);
+        assert_eq!(percent_complete(2, 3), 66.7);
+        assert_eq!(percent_complete(3, 3), 100.0);
+    }
+
+    #[test]
+    fn test_percent_complete_edge_cases() {
+        assert_eq!(percent_complete(0, 100), 0.0);
+        assert_eq!(percent_complete(0, 0), 100.0);
+        assert_eq!(percent_complete(200, 100), 100.0);
+    }
+
+    #[test]
+    fn test_percent_of_duration() {
+        assert_eq!(percent_of_duration(30.0, Some(90.0)), Some(33.3));
+        assert_eq!(percent_of_duration(95.0, Some(90.0)), Some(100.0));
+        assert_eq!(percent_of_duration(30.0, Some(0.0)), None);
+        assert_eq!(percent_of_duration(30.0, None), None);
+    }
+
+    #[test]
+    fn test_estimator_first_sample_is_used_as_is() {
+        let mut estimator = ThroughputEstimator::new(0.3);
+        let rate = estimator.record(1_000_000, Duration::from_secs(2));
+        assert_eq!(rate, 500_000.0);
+    }
+
+    #[test]
+    fn test_estimator_applies_exponential_smoothing() {
+        let mut estimator = ThroughputEstimator::new(0.5);
+        estimator.record(1_000, Duration::from_secs(1)); // 1000 B/s
+        let rate = estimator.record(3_000, Duration::from_secs(1)); // 3000 B/s
+        // 0.5 * 3000 + 0.5 * 1000 = 2000
+        assert_eq!(rate, 2_000.0);
+    }
+
+    #[test]
+    fn test_estimator_ignores_zero_elapsed() {
+        let mut estimator = ThroughputEstimator::new(0.3);
+        let rate = estimator.record(1_000, Duration::ZERO);
+        assert_eq!(rate, 0.0);
+        assert!(estimator.eta_secs(1_000).is_none());
+    }
+
+    #[test]
+    fn test_estimator_eta() {
+        let mut estimator = ThroughputEstimator::new(0.3);
+        estimator.record(1_000, Duration::from_secs(1));
+        assert_eq!(estimator.eta_secs(2_500), Some(3)); // 2.5秒 → 切り上げ
+        assert_eq!(estimator.eta_secs(0), Some(0));
+    }
+}
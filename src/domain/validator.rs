@@ -3,11 +3,28 @@
 /// アップロード対象のファイルを検証する。
 /// ドメイン層の責務として、ビジネスルールを適用する。
 ///
-/// 設定値（最大ファイルサイズ、サポート形式）はAPP_CONFIGから取得します。
+/// 最大ファイルサイズはAPP_CONFIGから取得し、対応形式は呼び出し側から受け取ります
+/// （config.tomlの`allowed_formats`、未設定の場合は`APP_CONFIG.upload.supported_formats`）。
 use crate::config::APP_CONFIG;
 use crate::domain::error::DomainError;
+use crate::domain::warning::{Warning, WarningKind};
+use crate::media::mp4;
+use crate::media::probe::MediaProbe;
+use std::io::Read;
 use std::path::Path;
 
+/// MPEG-TSのパケット長
+const TS_PACKET_LEN: usize = 188;
+
+/// BDAV（m2ts）のパケット長（4バイトのタイムスタンプ + MPEG-TSパケット）
+const BDAV_PACKET_LEN: usize = 192;
+
+/// MPEG-TSの同期バイト
+const TS_SYNC_BYTE: u8 = 0x47;
+
+/// コンテナ形式の判定に読み込む先頭バイト数（m2tsの2パケット目の同期バイトまで）
+const SIGNATURE_LEN: usize = BDAV_PACKET_LEN + 5;
+
 /// バリデーション結果の型エイリアス
 type ValidationResult<T> = Result<T, DomainError>;
 
@@ -23,6 +40,7 @@ pub struct FileValidation {
 ///
 /// # 引数
 /// * `file_path` - 検証対象のファイルパス
+/// * `supported_formats` - アップロードを許可する形式（小文字の拡張子）
 ///
 /// # 戻り値
 /// 検証に成功した場合は`FileValidation`を返す
@@ -33,9 +51,108 @@ pub struct FileValidation {
 /// - ファイルが空
 /// - サポートされていない形式
 /// - ファイルサイズが制限を超過
-pub fn validate_upload_file(file_path: &str) -> ValidationResult<FileValidation> {
+/// - ファイル先頭のシグネチャ（ftyp/EBML/RIFFなど）が拡張子のコンテナ形式と一致しない
+pub fn validate_upload_file(
+    file_path: &str,
+    supported_formats: &[&str],
+) -> ValidationResult<FileValidation> {
     let path = Path::new(file_path);
+    let size = validate_file_size(path, file_path)?;
 
+    // 拡張子チェック
+    let extension = extract_extension(path, file_path, supported_formats)?;
+
+    if !supported_formats.contains(&extension.as_str()) {
+        return Err(DomainError::invalid_format(
+            file_path,
+            supported_formats,
+            &extension,
+        ));
+    }
+
+    // 内容チェック（拡張子を付け替えただけのファイルをアップロード前に弾く）
+    verify_signature(path, file_path, &extension)?;
+
+    Ok(FileValidation {
+        path: file_path.to_string(),
+        size,
+        extension,
+    })
+}
+
+/// 拡張子の代わりに指定した形式としてファイルをバリデーションする（`upload --force-format`）
+///
+/// 拡張子がない・一般的でないファイル（キャプチャツールの一時ファイルなど）向けで、
+/// 存在・空ファイル・サイズの検証のみ行い、拡張子とシグネチャは検証しません。
+/// 結果の`extension`は指定した形式になり、Content-Typeの決定に使われます。
+///
+/// # 引数
+/// * `file_path` - 検証対象のファイルパス
+/// * `format` - アップロードする形式（大文字小文字は区別しない）
+/// * `supported_formats` - アップロードを許可する形式（小文字の拡張子）
+///
+/// # エラー
+/// `validate_upload_file` と同じ（拡張子・シグネチャの検証を除く）。
+/// 指定した形式がサポートされていない場合は`DomainError::InvalidFormat`
+pub fn validate_upload_file_as(
+    file_path: &str,
+    format: &str,
+    supported_formats: &[&str],
+) -> ValidationResult<FileValidation> {
+    let format = format.trim().trim_start_matches('.').to_lowercase();
+    if !supported_formats.contains(&format.as_str()) {
+        return Err(DomainError::invalid_format(
+            file_path,
+            supported_formats,
+            &format,
+        ));
+    }
+
+    let size = validate_file_size(Path::new(file_path), file_path)?;
+
+    Ok(FileValidation {
+        path: file_path.to_string(),
+        size,
+        extension: format,
+    })
+}
+
+/// 変換してアップロードする元ファイルをバリデーションする（`upload --transcode`）
+///
+/// ffmpegが読めるファイルであればよいため、存在・種類・空ファイルのみ検証し、
+/// 拡張子・シグネチャ・サイズの上限は検証しません（サイズの上限は変換後の出力に適用します）。
+/// 結果の`extension`は変換後の形式（`output_format`）になります。
+pub fn validate_transcode_source(
+    file_path: &str,
+    output_format: &str,
+) -> ValidationResult<FileValidation> {
+    let size = validate_existing_file(Path::new(file_path), file_path)?;
+
+    Ok(FileValidation {
+        path: file_path.to_string(),
+        size,
+        extension: output_format.to_string(),
+    })
+}
+
+/// ファイルの存在・種類・サイズを検証し、サイズ（バイト）を返す
+fn validate_file_size(path: &Path, file_path: &str) -> ValidationResult<u64> {
+    let size = validate_existing_file(path, file_path)?;
+
+    // ファイルサイズチェック（APP_CONFIGから設定値を取得）
+    let max_file_size = APP_CONFIG.upload.max_file_size;
+    if size > max_file_size {
+        return Err(DomainError::FileTooLarge {
+            size,
+            max: max_file_size,
+        });
+    }
+
+    Ok(size)
+}
+
+/// ファイルが存在し、空でない通常のファイルか検証し、サイズ（バイト）を返す
+fn validate_existing_file(path: &Path, file_path: &str) -> ValidationResult<u64> {
     // 存在確認
     if !path.exists() {
         return Err(DomainError::file_not_found(file_path));
@@ -55,32 +172,7 @@ pub fn validate_upload_file(file_path: &str) -> ValidationResult<FileValidation>
         return Err(DomainError::empty_file(file_path));
     }
 
-    // ファイルサイズチェック（APP_CONFIGから設定値を取得）
-    let max_file_size = APP_CONFIG.upload.max_file_size;
-    if size > max_file_size {
-        return Err(DomainError::FileTooLarge {
-            size,
-            max: max_file_size,
-        });
-    }
-
-    // 拡張子チェック（APP_CONFIGから設定値を取得）
-    let supported_formats = APP_CONFIG.upload.supported_formats;
-    let extension = extract_extension(path, file_path, supported_formats)?;
-
-    if !supported_formats.contains(&extension.as_str()) {
-        return Err(DomainError::invalid_format(
-            file_path,
-            supported_formats,
-            &extension,
-        ));
-    }
-
-    Ok(FileValidation {
-        path: file_path.to_string(),
-        size,
-        extension,
-    })
+    Ok(size)
 }
 
 /// ファイルパスから拡張子を抽出する
@@ -95,6 +187,256 @@ fn extract_extension(
         .ok_or_else(|| DomainError::invalid_format(file_path, supported_formats, "no extension"))
 }
 
+/// 動画のコンテナ形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Container {
+    /// ISO Base Media File Format（mp4, mov）
+    IsoBmff,
+    /// Matroska / WebM（mkv, webm）
+    Matroska,
+    /// RIFF AVI
+    Avi,
+    /// Advanced Systems Format（wmv）
+    Asf,
+    /// Flash Video
+    Flv,
+    /// MPEG-2 Transport Stream（ts）
+    MpegTs,
+    /// BDAV MPEG-2 Transport Stream（m2ts）
+    Bdav,
+    /// MPEG Program Stream（mpg, mpeg）
+    MpegPs,
+    /// Material Exchange Format
+    Mxf,
+    /// Ogg（ogv）
+    Ogg,
+}
+
+impl Container {
+    /// 拡張子から期待されるコンテナ形式
+    fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "mp4" | "mov" | "3gp" => Some(Self::IsoBmff),
+            "mkv" | "webm" => Some(Self::Matroska),
+            "avi" => Some(Self::Avi),
+            "wmv" => Some(Self::Asf),
+            "flv" => Some(Self::Flv),
+            "ts" => Some(Self::MpegTs),
+            "m2ts" => Some(Self::Bdav),
+            "mpg" | "mpeg" => Some(Self::MpegPs),
+            "mxf" => Some(Self::Mxf),
+            "ogv" => Some(Self::Ogg),
+            _ => None,
+        }
+    }
+
+    /// ファイル先頭のバイト列からコンテナ形式を判定する
+    fn sniff(header: &[u8]) -> Option<Self> {
+        // ISO BMFFは先頭ボックスの型（4〜8バイト目）で判定
+        // 古いQuickTimeファイルはftypを持たず、moov/mdatなどから始まる場合がある
+        const ISO_BOX_TYPES: &[&[u8; 4]] = &[
+            b"ftyp", b"moov", b"mdat", b"free", b"skip", b"wide", b"pnot",
+        ];
+        const EBML_MAGIC: &[u8] = &[0x1A, 0x45, 0xDF, 0xA3];
+        const ASF_MAGIC: &[u8] = &[0x30, 0x26, 0xB2, 0x75, 0x8E, 0x66, 0xCF, 0x11];
+        // パックヘッダー（MPEG-1/2 PS）またはシーケンスヘッダー（エレメンタリーストリーム）
+        const MPEG_PS_MAGICS: &[&[u8]] = &[&[0x00, 0x00, 0x01, 0xBA], &[0x00, 0x00, 0x01, 0xB3]];
+        // SMPTEのユニバーサルラベル（ヘッダーパーティションパックのキー）
+        const MXF_MAGIC: &[u8] = &[0x06, 0x0E, 0x2B, 0x34, 0x02, 0x05, 0x01, 0x01];
+
+        // MPEG-TSは同期バイトが一定間隔で並ぶため、読み込めた範囲で2パケット目も確認する
+        let synced = |offset: usize, packet_len: usize| {
+            header.get(offset) == Some(&TS_SYNC_BYTE)
+                && header
+                    .get(offset + packet_len)
+                    .is_none_or(|byte| *byte == TS_SYNC_BYTE)
+        };
+
+        if header.len() >= 8 && ISO_BOX_TYPES.iter().any(|t| &header[4..8] == *t) {
+            Some(Self::IsoBmff)
+        } else if header.starts_with(EBML_MAGIC) {
+            Some(Self::Matroska)
+        } else if header.len() >= 12 && header.starts_with(b"RIFF") && &header[8..12] == b"AVI " {
+            Some(Self::Avi)
+        } else if header.starts_with(ASF_MAGIC) {
+            Some(Self::Asf)
+        } else if header.starts_with(b"FLV") {
+            Some(Self::Flv)
+        } else if MPEG_PS_MAGICS.iter().any(|magic| header.starts_with(magic)) {
+            Some(Self::MpegPs)
+        } else if header.starts_with(MXF_MAGIC) {
+            Some(Self::Mxf)
+        } else if header.starts_with(b"OggS") {
+            Some(Self::Ogg)
+        } else if synced(0, TS_PACKET_LEN) {
+            Some(Self::MpegTs)
+        } else if synced(4, BDAV_PACKET_LEN) {
+            Some(Self::Bdav)
+        } else {
+            None
+        }
+    }
+
+    /// エラーメッセージ用の名前
+    fn name(self) -> &'static str {
+        match self {
+            Self::IsoBmff => "MP4/QuickTime (ftyp)",
+            Self::Matroska => "Matroska/WebM (EBML)",
+            Self::Avi => "AVI (RIFF)",
+            Self::Asf => "ASF/WMV",
+            Self::Flv => "FLV",
+            Self::MpegTs => "MPEG-TS",
+            Self::Bdav => "MPEG-TS (BDAV/M2TS)",
+            Self::MpegPs => "MPEG-PS",
+            Self::Mxf => "MXF",
+            Self::Ogg => "Ogg",
+        }
+    }
+}
+
+/// ファイル先頭のシグネチャが拡張子のコンテナ形式と一致するか検証する
+///
+/// 判定できない拡張子（設定で追加された形式など）は検証しません。
+fn verify_signature(path: &Path, file_path: &str, extension: &str) -> ValidationResult<()> {
+    let Some(expected) = Container::from_extension(extension) else {
+        return Ok(());
+    };
+
+    let mut header = Vec::with_capacity(SIGNATURE_LEN);
+    std::fs::File::open(path)
+        .and_then(|file| file.take(SIGNATURE_LEN as u64).read_to_end(&mut header))
+        .map_err(|_| DomainError::file_not_found(file_path))?;
+
+    match Container::sniff(&header) {
+        Some(detected) if detected == expected => Ok(()),
+        Some(detected) => Err(DomainError::content_mismatch(
+            file_path,
+            extension,
+            detected.name(),
+        )),
+        None => Err(DomainError::content_mismatch(
+            file_path,
+            extension,
+            "unknown (not a recognized video container)",
+        )),
+    }
+}
+
+/// HDRの伝達特性（PQ / HLG）
+const HDR_TRANSFERS: &[&str] = &["smpte2084", "arib-std-b67"];
+
+/// メディア解析結果から、再エンコードや品質低下につながる入力を警告として列挙する
+///
+/// いずれもアップロードは可能なため、エラーではなく警告として返します。
+///
+/// # 引数
+/// * `media` - ffprobeによる解析結果
+/// * `extension` - ファイルの拡張子（小文字）
+/// * `video_quality` - 作成するアセットの画質（"basic" の場合のみHDRを警告）
+pub fn media_warnings(media: &MediaProbe, extension: &str, video_quality: &str) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+
+    if let Some(field_order) = media
+        .field_order
+        .as_deref()
+        .filter(|order| !matches!(*order, "progressive" | "unknown"))
+    {
+        warnings.push(Warning::new(
+            WarningKind::Interlaced,
+            format!(
+                "Interlaced video (field order: {}) will be deinterlaced and fully re-encoded; processing may be slow",
+                field_order
+            ),
+        ));
+    }
+
+    let is_hdr = media
+        .color_transfer
+        .as_deref()
+        .is_some_and(|transfer| HDR_TRANSFERS.contains(&transfer));
+    let is_high_bit_depth = media
+        .pix_fmt
+        .as_deref()
+        .is_some_and(|pix_fmt| pix_fmt.contains("10") || pix_fmt.contains("12"));
+    if is_hdr && is_high_bit_depth && video_quality == "basic" {
+        warnings.push(Warning::new(
+            WarningKind::HdrOnBasicQuality,
+            format!(
+                "10-bit HDR video ({}) will be converted to 8-bit SDR on basic video quality",
+                media.pix_fmt.as_deref().unwrap_or_default()
+            ),
+        ));
+    }
+
+    if !APP_CONFIG
+        .upload
+        .standard_video_codecs
+        .contains(&media.video_codec.as_str())
+    {
+        warnings.push(Warning::new(
+            WarningKind::NonStandardCodec,
+            format!(
+                "Video codec '{}' is not a standard input and will be fully re-encoded; processing may be slow",
+                media.video_codec
+            ),
+        ));
+    }
+
+    // コンテナ形式はアップロード前に検証済みのため、ここではコーデックの組み合わせのみを見る
+    let webm_codecs = APP_CONFIG.upload.webm_video_codecs;
+    if extension == "webm" && !webm_codecs.contains(&media.video_codec.as_str()) {
+        warnings.push(Warning::new(
+            WarningKind::ExtensionCodecMismatch,
+            format!(
+                "Video codec '{}' is not allowed in WebM (expected {}); the file may not play in other tools",
+                media.video_codec,
+                webm_codecs.join(", ")
+            ),
+        ));
+    }
+
+    warnings
+}
+
+/// 動画の再生時間（秒）を取得する
+///
+/// ffprobeの解析結果を優先し、ない場合はMP4/MOVのヘッダーから読み取ります。
+pub fn media_duration(
+    validation: &FileValidation,
+    probed_duration_secs: Option<f64>,
+) -> Option<f64> {
+    probed_duration_secs.or_else(|| {
+        (Container::from_extension(&validation.extension) == Some(Container::IsoBmff))
+            .then(|| {
+                mp4::read_duration(Path::new(&validation.path))
+                    .ok()
+                    .flatten()
+            })
+            .flatten()
+    })
+}
+
+/// 再生時間が上限以内か検証する
+///
+/// 再生時間（`media_duration`）が取得できない場合は検証しません（上限はMux側で適用されます）。
+///
+/// # 引数
+/// * `validation` - ファイルのバリデーション結果
+/// * `probed_duration_secs` - ffprobeで取得した再生時間（秒）
+/// * `max_duration_secs` - 許可する最大再生時間（秒）
+pub fn validate_duration(
+    validation: &FileValidation,
+    probed_duration_secs: Option<f64>,
+    max_duration_secs: u64,
+) -> ValidationResult<()> {
+    match media_duration(validation, probed_duration_secs) {
+        Some(duration_secs) if duration_secs > max_duration_secs as f64 => Err(
+            DomainError::duration_too_long(&validation.path, duration_secs, max_duration_secs),
+        ),
+        _ => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,4 +449,221 @@ mod tests {
         assert!(formats.contains(&"mov"));
         assert!(formats.contains(&"webm"));
     }
+
+    /// 指定した内容のファイルを一時ディレクトリに作成する
+    fn write_temp(dir: &tempfile::TempDir, name: &str, content: &[u8]) -> String {
+        let path = dir.path().join(name);
+        std::fs::write(&path, content).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_sniff_known_containers() {
+        assert_eq!(
+            Container::sniff(b"\0\0\0\x20ftypisom\0\0"),
+            Some(Container::IsoBmff)
+        );
+        assert_eq!(
+            Container::sniff(b"\0\0\0\x08wide\0\0\0\0"),
+            Some(Container::IsoBmff)
+        );
+        assert_eq!(
+            Container::sniff(&[0x1A, 0x45, 0xDF, 0xA3, 0x9F, 0x42]),
+            Some(Container::Matroska)
+        );
+        assert_eq!(
+            Container::sniff(b"RIFF\x10\0\0\0AVI LIST"),
+            Some(Container::Avi)
+        );
+        assert_eq!(Container::sniff(b"FLV\x01\x05"), Some(Container::Flv));
+        assert_eq!(
+            Container::sniff(&[0x00, 0x00, 0x01, 0xBA, 0x44]),
+            Some(Container::MpegPs)
+        );
+        assert_eq!(
+            Container::sniff(&[0x06, 0x0E, 0x2B, 0x34, 0x02, 0x05, 0x01, 0x01, 0x0D]),
+            Some(Container::Mxf)
+        );
+        assert_eq!(Container::sniff(b"OggS\0\x02"), Some(Container::Ogg));
+
+        let mut ts = vec![0u8; SIGNATURE_LEN];
+        ts[0] = TS_SYNC_BYTE;
+        ts[TS_PACKET_LEN] = TS_SYNC_BYTE;
+        assert_eq!(Container::sniff(&ts), Some(Container::MpegTs));
+
+        let mut m2ts = vec![0u8; SIGNATURE_LEN];
+        m2ts[4] = TS_SYNC_BYTE;
+        m2ts[4 + BDAV_PACKET_LEN] = TS_SYNC_BYTE;
+        assert_eq!(Container::sniff(&m2ts), Some(Container::Bdav));
+
+        // 先頭が 'G' なだけのテキストはMPEG-TSとみなさない
+        let text = b"Good morning. ".repeat(SIGNATURE_LEN / 14 + 1);
+        assert_eq!(Container::sniff(&text), None);
+        assert_eq!(Container::sniff(b"hello world!"), None);
+        assert_eq!(Container::sniff(b""), None);
+    }
+
+    #[test]
+    fn test_validate_accepts_matching_signature() {
+        let dir = tempfile::tempdir().unwrap();
+        let mov = write_temp(&dir, "clip.MOV", b"\0\0\0\x14ftypqt  \0\0\0\0");
+
+        let validation = validate_upload_file(&mov, APP_CONFIG.upload.supported_formats).unwrap();
+        assert_eq!(validation.extension, "mov");
+    }
+
+    #[test]
+    fn test_validate_uses_given_formats() {
+        let dir = tempfile::tempdir().unwrap();
+        let mov = write_temp(&dir, "clip.mov", b"\0\0\0\x14ftypqt  \0\0\0\0");
+        let custom = write_temp(&dir, "clip.y4m", b"YUV4MPEG2 W320 H240");
+
+        assert!(matches!(
+            validate_upload_file(&mov, &["mp4"]),
+            Err(DomainError::InvalidFormat { .. })
+        ));
+        // 判定できない形式は内容を検証しない
+        assert_eq!(
+            validate_upload_file(&custom, &["mp4", "y4m"])
+                .unwrap()
+                .extension,
+            "y4m"
+        );
+    }
+
+    #[test]
+    fn test_validate_as_forced_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let capture = write_temp(&dir, "capture.tmp", b"\0\0\0\x14ftypisom\0\0\0\0");
+        let empty = write_temp(&dir, "empty.tmp", b"");
+        let formats = APP_CONFIG.upload.supported_formats;
+
+        let validation = validate_upload_file_as(&capture, "MP4", formats).unwrap();
+        assert_eq!(validation.extension, "mp4");
+        assert!(matches!(
+            validate_upload_file_as(&capture, "txt", formats),
+            Err(DomainError::InvalidFormat { .. })
+        ));
+        // サイズ・空ファイルの検証は省略しない
+        assert!(matches!(
+            validate_upload_file_as(&empty, "mp4", formats),
+            Err(DomainError::EmptyFile { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_transcode_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = write_temp(&dir, "screen.y4m", b"YUV4MPEG2 W640 H360");
+        let empty = write_temp(&dir, "empty.y4m", b"");
+
+        // 対応形式以外の拡張子でも、変換後の形式として扱う
+        let validation = validate_transcode_source(&source, "mp4").unwrap();
+        assert_eq!(validation.extension, "mp4");
+        assert_eq!(validation.size, 19);
+        assert!(matches!(
+            validate_transcode_source(&empty, "mp4"),
+            Err(DomainError::EmptyFile { .. })
+        ));
+        assert!(matches!(
+            validate_transcode_source(dir.path().to_str().unwrap(), "mp4"),
+            Err(DomainError::NotAFile { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_rejects_mislabeled_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mkv_as_mp4 = write_temp(&dir, "clip.mp4", &[0x1A, 0x45, 0xDF, 0xA3, 0x9F, 0x42]);
+        let text_as_webm = write_temp(&dir, "notes.webm", b"just some text");
+
+        assert!(matches!(
+            validate_upload_file(&mkv_as_mp4, APP_CONFIG.upload.supported_formats),
+            Err(DomainError::ContentMismatch { detected, .. }) if detected.contains("Matroska")
+        ));
+        assert!(matches!(
+            validate_upload_file(&text_as_webm, APP_CONFIG.upload.supported_formats),
+            Err(DomainError::ContentMismatch { .. })
+        ));
+    }
+
+    fn probe(video_codec: &str) -> MediaProbe {
+        MediaProbe {
+            format_name: "mov,mp4,m4a,3gp,3g2,mj2".to_string(),
+            duration_secs: Some(10.0),
+            bit_rate: None,
+            video_codec: video_codec.to_string(),
+            width: 1920,
+            height: 1080,
+            pix_fmt: Some("yuv420p".to_string()),
+            field_order: Some("progressive".to_string()),
+            color_transfer: None,
+            audio_codec: None,
+        }
+    }
+
+    #[test]
+    fn test_media_warnings_standard_input() {
+        assert!(media_warnings(&probe("h264"), "mp4", "basic").is_empty());
+    }
+
+    #[test]
+    fn test_media_warnings_detects_reencode_inputs() {
+        let mut media = probe("prores");
+        media.field_order = Some("tt".to_string());
+        media.pix_fmt = Some("yuv422p10le".to_string());
+        media.color_transfer = Some("smpte2084".to_string());
+
+        let kinds: Vec<_> = media_warnings(&media, "mov", "basic")
+            .into_iter()
+            .map(|w| w.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                WarningKind::Interlaced,
+                WarningKind::HdrOnBasicQuality,
+                WarningKind::NonStandardCodec,
+            ]
+        );
+
+        // premium画質ではHDRが保持されるため警告しない
+        assert!(
+            !media_warnings(&media, "mov", "premium")
+                .iter()
+                .any(|w| w.kind == WarningKind::HdrOnBasicQuality)
+        );
+    }
+
+    #[test]
+    fn test_media_warnings_detects_codec_not_allowed_in_webm() {
+        let kinds: Vec<_> = media_warnings(&probe("h264"), "webm", "basic")
+            .into_iter()
+            .map(|w| w.kind)
+            .collect();
+        assert_eq!(kinds, [WarningKind::ExtensionCodecMismatch]);
+
+        assert!(
+            media_warnings(&probe("vp9"), "webm", "basic")
+                .iter()
+                .all(|w| w.kind != WarningKind::ExtensionCodecMismatch)
+        );
+    }
+
+    #[test]
+    fn test_validate_duration() {
+        let validation = FileValidation {
+            path: "/videos/clip.webm".to_string(),
+            size: 1024,
+            extension: "webm".to_string(),
+        };
+
+        assert!(validate_duration(&validation, Some(59.9), 60).is_ok());
+        assert!(matches!(
+            validate_duration(&validation, Some(60.5), 60),
+            Err(DomainError::DurationTooLong { max_secs: 60, .. })
+        ));
+        // 再生時間が分からない場合は検証しない
+        assert!(validate_duration(&validation, None, 60).is_ok());
+    }
 }
@@ -0,0 +1,68 @@
+use schemars::JsonSchema;
+/// ドメイン層: 警告定義
+///
+/// 処理自体は成功したものの、利用者が知っておくべき注意事項を表す型です。
+/// アップロード前の解析で見つかった入力の問題（Mux側で完全な再エンコードが必要になる、
+/// または出力品質が下がる可能性がある入力）と、コマンド実行後の注意事項
+/// （MP4が生成中、古いアセットを自動削除した、など）を含みます。
+/// エラーと異なり処理は継続し、コマンド結果の `warnings` として返します。
+use serde::Serialize;
+
+/// 警告の種類（機械可読出力の `code` として使用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    /// インターレース映像（デインターレースのため再エンコードが必要）
+    Interlaced,
+    /// 10bit HDR映像をbasic画質でアップロード（SDRに変換される）
+    HdrOnBasicQuality,
+    /// 標準入力仕様外のコーデック（再エンコードのため処理が遅くなる）
+    NonStandardCodec,
+    /// 拡張子の形式では使えない映像コーデック（例: H.264の.webm）
+    ExtensionCodecMismatch,
+    /// MP4がまだ生成中（URLは生成完了後に利用可能）
+    Mp4Generating,
+    /// 容量確保のために古いアセットを自動削除した
+    AssetsAutoDeleted,
+    /// 元ファイルのチェックサムをアセットに記録できなかった（重複検出に使えない）
+    ChecksumNotRecorded,
+    /// 同じファイル名・サイズのファイルを直近（config.tomlの `recent_upload_days` 日以内）にアップロードしている
+    RecentlyUploaded,
+    /// `--wait-ready` で待機したが、アセットが `ready` にならなかった
+    AssetNotReady,
+    /// 再生ポリシーが `signed` だが署名キーがないため、再生URLを出力できない
+    SignedUrlsUnavailable,
+    /// 再生IDの削除（`playback revoke`）により、アセットに再生IDが残っていない
+    NoPlaybackIds,
+    /// `--copy` で指定したURLをクリップボードにコピーできなかった（URLがない、クリップボードが使えない環境など）
+    CopyFailed,
+    /// `status` でアセット数を取得できなかった（残りの容量も分からない）
+    AssetCountUnavailable,
+    /// `status` でトークンが属する環境を取得できなかった
+    EnvironmentUnavailable,
+}
+
+/// 警告
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct Warning {
+    /// 警告の種類
+    #[serde(rename = "code")]
+    pub kind: WarningKind,
+    /// 利用者向けの説明
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new(kind: WarningKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
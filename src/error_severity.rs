@@ -39,6 +39,14 @@ pub enum ErrorSeverity {
     ///
     /// **Exit Code: 3**
     SystemError,
+
+    /// 一部失敗
+    ///
+    /// 複数ファイルのアップロードなどの一括処理で、
+    /// 一部の項目は成功し、残りが失敗した。
+    ///
+    /// **Exit Code: 4**
+    PartialFailure,
 }
 
 impl ErrorSeverity {
@@ -48,6 +56,7 @@ impl ErrorSeverity {
             Self::UserError => 1,
             Self::ConfigError => 2,
             Self::SystemError => 3,
+            Self::PartialFailure => 4,
         }
     }
 }
@@ -58,6 +67,7 @@ impl fmt::Display for ErrorSeverity {
             Self::UserError => write!(f, "user error"),
             Self::ConfigError => write!(f, "configuration error"),
             Self::SystemError => write!(f, "system error"),
+            Self::PartialFailure => write!(f, "partial failure"),
         }
     }
 }
@@ -71,6 +81,7 @@ mod tests {
         assert_eq!(ErrorSeverity::UserError.exit_code(), 1);
         assert_eq!(ErrorSeverity::ConfigError.exit_code(), 2);
         assert_eq!(ErrorSeverity::SystemError.exit_code(), 3);
+        assert_eq!(ErrorSeverity::PartialFailure.exit_code(), 4);
     }
 
     #[test]
@@ -81,6 +92,7 @@ mod tests {
             "configuration error"
         );
         assert_eq!(ErrorSeverity::SystemError.to_string(), "system error");
+        assert_eq!(ErrorSeverity::PartialFailure.to_string(), "partial failure");
     }
 
     #[test]
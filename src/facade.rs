@@ -0,0 +1,145 @@
+/// ライブラリ利用者向けのファサード
+///
+/// 認証情報とAPIクライアントをまとめて保持し、
+/// アップロード・一覧・詳細・削除・リネームを1つの型から呼び出せるようにします。
+/// CLIの各コマンドと同じユースケース（`commands::*::run`）を使用するため、
+/// 挙動（リトライ、レート制限、容量超過時の自動削除など）はCLIと同一です。
+use crate::api::auth::AuthManager;
+use crate::api::client::ApiClient;
+use crate::api::thumbnail::ThumbnailSize;
+use crate::commands::result::{DeleteResult, ListResult, RenameResult, ShowResult, UploadResult};
+use crate::commands::upload::UploadOptions;
+use crate::commands::{delete, list, rename, show, upload};
+use crate::config::{APP_CONFIG, UserConfig};
+use crate::domain::progress::ProgressSender;
+use anyhow::{Context, Result};
+
+/// Mux Video操作用クライアント
+pub struct Client {
+    api: ApiClient,
+    auth: AuthManager,
+}
+
+impl Client {
+    /// 認証情報を指定してクライアントを作成
+    ///
+    /// # Arguments
+    /// * `token_id` - Mux Access Token ID
+    /// * `token_secret` - Mux Access Token Secret
+    pub fn new(token_id: impl Into<String>, token_secret: impl Into<String>) -> Result<Self> {
+        let api = ApiClient::new(APP_CONFIG.api.endpoint.to_string())
+            .context("Failed to create API client")?;
+
+        Ok(Self::with_api_client(api, token_id, token_secret))
+    }
+
+    /// 設定済みのAPIクライアント（コネクション設定、リトライポリシーなど）を使用して作成
+    ///
+    /// # Arguments
+    /// * `api` - APIクライアント
+    /// * `token_id` - Mux Access Token ID
+    /// * `token_secret` - Mux Access Token Secret
+    pub fn with_api_client(
+        api: ApiClient,
+        token_id: impl Into<String>,
+        token_secret: impl Into<String>,
+    ) -> Self {
+        Self {
+            api,
+            auth: AuthManager::new(token_id.into(), token_secret.into()),
+        }
+    }
+
+    /// CLIの設定ファイル（`vidyeet login` で保存した認証情報）からクライアントを作成
+    pub fn from_user_config() -> Result<Self> {
+        let user_config = UserConfig::load()
+            .context("Failed to load user configuration. Please check your config.toml file.")?;
+
+        let auth = user_config
+            .get_auth()
+            .context("Authentication credentials not found. Please run 'vidyeet login' first.")?;
+
+        Self::new(auth.token_id.clone(), auth.token_secret.clone())
+    }
+
+    /// 動画一覧を取得（完全なAPIレスポンス `raw_assets` を含む）
+    pub async fn list(&self) -> Result<ListResult> {
+        list::run(&self.api, &self.auth, true).await
+    }
+
+    /// アセット詳細を取得（サムネイル画像のURLは元の大きさ）
+    ///
+    /// # Arguments
+    /// * `asset_id` - 取得するアセットのID
+    pub async fn show(&self, asset_id: &str) -> Result<ShowResult> {
+        show::run(&self.api, &self.auth, asset_id, ThumbnailSize::default()).await
+    }
+
+    /// アセットを削除
+    ///
+    /// # Arguments
+    /// * `asset_id` - 削除対象のアセットID
+    pub async fn delete(&self, asset_id: &str) -> Result<DeleteResult> {
+        delete::run(&self.api, &self.auth, asset_id).await
+    }
+
+    /// アセットのタイトルを変更
+    ///
+    /// # Arguments
+    /// * `asset_id` - 対象のアセットID
+    /// * `title` - 新しいタイトル
+    pub async fn rename(&self, asset_id: &str, title: &str) -> Result<RenameResult> {
+        rename::run(&self.api, &self.auth, asset_id, title).await
+    }
+
+    /// 動画をアップロードし、アセットの作成完了まで待機
+    ///
+    /// アセット数の上限に達している場合は古いアセットを削除せず、
+    /// `DomainError::AssetLimitReached` を返します（削除させる場合は `upload_with_options` で
+    /// `on_full: Some(CapacityPolicy::AutoDelete)` を指定）。
+    ///
+    /// # Arguments
+    /// * `file_path` - アップロード対象の動画ファイルのパス
+    /// * `progress_tx` - 進捗通知の送信側（`vidyeet::progress_channel()` で作成、不要ならNone）
+    pub async fn upload(
+        &self,
+        file_path: &str,
+        progress_tx: Option<ProgressSender>,
+    ) -> Result<UploadResult> {
+        self.upload_with_options(file_path, UploadOptions::default(), progress_tx)
+            .await
+    }
+
+    /// オプションを指定して動画をアップロード
+    ///
+    /// # Arguments
+    /// * `file_path` - アップロード対象の動画ファイルのパス
+    /// * `options` - アップロードのオプション（ffprobeによる解析など）
+    /// * `progress_tx` - 進捗通知用チャネルの送信側（不要ならNone）
+    pub async fn upload_with_options(
+        &self,
+        file_path: &str,
+        options: UploadOptions,
+        progress_tx: Option<ProgressSender>,
+    ) -> Result<UploadResult> {
+        upload::run(&self.api, &self.auth, file_path, &options, progress_tx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_creation() {
+        let client = Client::new("test_token_id", "test_token_secret");
+        assert!(client.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_missing_file_before_network() {
+        let client = Client::new("test_token_id", "test_token_secret").unwrap();
+        let result = client.upload("/nonexistent/video.mp4", None).await;
+        assert!(result.is_err());
+    }
+}
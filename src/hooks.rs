@@ -0,0 +1,177 @@
+/// ユーザー定義フック実行モジュール
+///
+/// config.tomlの`[hooks]`に設定されたコマンドをシェル経由で実行します。
+/// 結果のJSONを標準入力に渡し、主な値を`VIDYEET_*`環境変数で公開するため、
+/// CLIをラップせずにCMS更新や通知などを連携できます。
+use crate::api::error::InfraError;
+use crate::config::APP_CONFIG;
+use crate::config::user::HookCommands;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// 発生したイベント名を渡す環境変数
+pub const ENV_EVENT: &str = "VIDYEET_EVENT";
+
+/// フックに渡す追加の環境変数の接頭辞
+pub const ENV_PREFIX: &str = "VIDYEET_";
+
+/// フックの起動イベント
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    /// アップロード前（`on_before_upload`）。標準出力でアップロードするファイルを差し替えられる
+    BeforeUpload,
+    /// アップロード成功時（`on_upload_success`）
+    UploadSuccess,
+    /// アップロード失敗時（`on_upload_failure`）
+    UploadFailure,
+}
+
+impl HookEvent {
+    /// config.tomlでのキー名
+    pub fn config_key(&self) -> &'static str {
+        match self {
+            Self::BeforeUpload => "on_before_upload",
+            Self::UploadSuccess => "on_upload_success",
+            Self::UploadFailure => "on_upload_failure",
+        }
+    }
+
+    /// このイベントに設定されたコマンドを取得
+    pub fn command<'a>(&self, hooks: &'a HookCommands) -> Option<&'a str> {
+        match self {
+            Self::BeforeUpload => hooks.on_before_upload.as_deref(),
+            Self::UploadSuccess => hooks.on_upload_success.as_deref(),
+            Self::UploadFailure => hooks.on_upload_failure.as_deref(),
+        }
+    }
+
+    /// `VIDYEET_EVENT`に設定する値
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::BeforeUpload => "before_upload",
+            Self::UploadSuccess => "upload_success",
+            Self::UploadFailure => "upload_failure",
+        }
+    }
+}
+
+/// フックの実行結果
+#[derive(Debug, Clone)]
+pub struct HookOutput {
+    /// 標準出力（前後の空白を除去済み）
+    pub stdout: String,
+}
+
+/// フックコマンドを実行する
+///
+/// # 引数
+/// * `event` - 起動イベント（エラーメッセージと`VIDYEET_EVENT`に使用）
+/// * `command` - シェルで実行するコマンド文字列
+/// * `payload` - 標準入力に渡すJSON
+/// * `env` - 追加の環境変数（キーには`VIDYEET_`が付与される）
+///
+/// # エラー
+/// 起動失敗・非ゼロ終了・タイムアウトの場合は`InfraError::Hook`を返す。
+/// 標準エラー出力は端末にそのまま流す。
+pub async fn run(
+    event: HookEvent,
+    command: &str,
+    payload: &serde_json::Value,
+    env: &[(&str, String)],
+) -> Result<HookOutput, InfraError> {
+    let hook = event.config_key();
+    let mut child = shell_command(command)
+        .env(ENV_EVENT, event.name())
+        .envs(
+            env.iter()
+                .map(|(key, value)| (format!("{}{}", ENV_PREFIX, key), value)),
+        )
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| InfraError::hook(hook, format!("failed to start '{}': {}", command, e)))?;
+
+    // 標準入力を読まないフックもあるため、書き込み失敗（BrokenPipe）は無視する
+    if let Some(mut stdin) = child.stdin.take() {
+        let body = serde_json::to_vec(payload).unwrap_or_default();
+        let _ = stdin.write_all(&body).await;
+    }
+
+    let timeout = Duration::from_secs(APP_CONFIG.hooks.timeout_secs);
+    let output = tokio::time::timeout(timeout, child.wait_with_output())
+        .await
+        .map_err(|_| {
+            InfraError::hook(
+                hook,
+                format!("timed out after {}s", APP_CONFIG.hooks.timeout_secs),
+            )
+        })?
+        .map_err(|e| InfraError::hook(hook, e.to_string()))?;
+
+    if !output.status.success() {
+        let status = output
+            .status
+            .code()
+            .map(|code| format!("exited with status {}", code))
+            .unwrap_or_else(|| "terminated by signal".to_string());
+        return Err(InfraError::hook(hook, status));
+    }
+
+    Ok(HookOutput {
+        stdout: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    })
+}
+
+/// プラットフォームのシェルでコマンドを実行する`Command`を作る
+fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_passes_payload_and_env() {
+        let payload = serde_json::json!({ "asset_id": "abc" });
+        let output = run(
+            HookEvent::UploadSuccess,
+            r#"printf '%s|%s|' "$VIDYEET_EVENT" "$VIDYEET_ASSET_ID"; cat"#,
+            &payload,
+            &[("ASSET_ID", "abc".to_string())],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(output.stdout, r#"upload_success|abc|{"asset_id":"abc"}"#);
+    }
+
+    #[tokio::test]
+    async fn test_run_reports_non_zero_exit() {
+        let err = run(
+            HookEvent::UploadFailure,
+            "exit 7",
+            &serde_json::json!({}),
+            &[],
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(err, InfraError::Hook { .. }));
+        let message = err.to_string();
+        assert!(message.contains("on_upload_failure"));
+        assert!(message.contains("status 7"));
+    }
+}
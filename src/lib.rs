@@ -0,0 +1,49 @@
+//! vidyeet: Mux Videoへの動画アップロード・管理ライブラリ
+//!
+//! CLI（`vidyeet-cli` バイナリ）と同じアップロード・一覧・詳細・削除の機能を、
+//! 他のRustプログラムから組み込んで利用するためのライブラリです。
+//! 通常は [`Client`] ファサードを使用してください。
+//!
+//! # 使用例
+//!
+//! ```no_run
+//! # async fn example() -> anyhow::Result<()> {
+//! let client = vidyeet::Client::new("token_id", "token_secret")?;
+//!
+//! let uploaded = client.upload("video.mp4", None).await?;
+//! println!("{}", uploaded.asset_id);
+//!
+//! let list = client.list().await?;
+//! for video in list.videos {
+//!     println!("{} {}", video.asset_id, video.status);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # モジュール
+//! - `api`: インフラ層（Mux APIクライアント、認証、リトライ、レート制限）
+//! - `commands`: アプリケーション層（各コマンドのユースケース）
+//! - `config`: 設定（AppConfig定数、ユーザー設定ファイル）
+//! - `domain`: ドメイン層（検証、進捗、フォーマット、ドメインエラー）
+//! - `error_severity`: エラーの深刻度と終了コード
+//! - `hooks`: ユーザー定義フック（config.tomlの`[hooks]`）の実行
+//! - `media`: 外部ツール（ffprobe）による動画ファイルの解析
+//! - `storage`: ローカルデータ（アセットインデックスなど）の読み書き
+//! - `webhook`: アップロード結果のWebhook通知（Slack / Discord）
+pub mod api;
+pub mod commands;
+pub mod config;
+pub mod domain;
+pub mod error_severity;
+mod facade;
+pub mod hooks;
+pub mod media;
+pub mod storage;
+pub mod webhook;
+
+pub use commands::result::{
+    DeleteResult, ListResult, RenameResult, ShowResult, UploadResult, VideoInfo,
+};
+pub use domain::progress::{ProgressSender, UploadPhase, UploadProgress, progress_channel};
+pub use facade::Client;
@@ -1,75 +1,135 @@
-mod api;
+//! vidyeet-cli: vidyeetライブラリの上に構築された薄いCLI
+//!
+//! 引数解析（cli）と表示（presentation）のみを担当し、
+//! ビジネスロジックはすべてライブラリ（`vidyeet`）に委譲します。
 mod cli;
-mod commands;
-mod config;
-mod domain;
-mod error_severity;
 mod presentation;
 
 use anyhow::Result;
-use api::error::InfraError;
-use config::error::ConfigError;
-use config::user::UserConfig;
-use domain::error::DomainError;
+use presentation::i18n::{self, Locale, Msg, t, tf};
+use presentation::schema::SCHEMA_VERSION;
+use presentation::style;
+use presentation::units;
 use std::env;
+use vidyeet::api::error::InfraError;
+use vidyeet::config::error::ConfigError;
+use vidyeet::config::user::UserConfig;
+use vidyeet::domain::error::DomainError;
 
 #[tokio::main]
 async fn main() {
-    let args: Vec<String> = env::args().collect();
+    // --debug-http はどの位置でも有効なため、他の引数解析より先に取り除く
+    let args = cli::apply_debug_http_flag(env::args().collect());
 
-    // --machine フラグのチェック（エラーハンドリングにも必要）
-    let machine_output = args.len() > 1 && args[1] == "--machine";
+    // --machine もどの位置でも有効（エラーハンドリングにも必要）
+    let (machine_output, args) = cli::take_machine_flag(args);
 
-    if let Err(e) = run(&args).await {
-        handle_error(e, machine_output);
+    // --query は機械可読出力から値を取り出すため、--machine を含意する
+    let (query, args) = cli::take_query_flag(args);
+    let machine_output = machine_output || query.is_some();
+
+    // --no-color も同様（NO_COLOR・非TTYの判定と合わせて色付けの有無を決定）
+    let (no_color, args) = cli::take_no_color_flag(args);
+    style::init(no_color);
+
+    // --bytes も同様（人間向け出力のサイズをMB/GBに換算せず表示）
+    let (raw_bytes, args) = cli::take_bytes_flag(args);
+    units::init(raw_bytes);
+
+    // --iso-duration も同様（機械可読出力の再生時間にISO 8601の期間を併記）
+    let (iso_duration, args) = cli::take_iso_duration_flag(args);
+    presentation::output::init_iso_duration(iso_duration);
+
+    let outcome = run(&args, machine_output, query.as_deref()).await;
+
+    // 状態を変更するコマンドは結果とともに操作履歴へ記録
+    cli::record_history(&args, &outcome);
+
+    match outcome {
+        Ok(0) => {}
+        // 一括処理の一部・全件失敗（結果は出力済み）
+        Ok(exit_code) => std::process::exit(exit_code),
+        Err(e) => handle_error(e, machine_output),
     }
 }
 
 /// アプリケーションのメイン処理
-async fn run(args: &[String]) -> Result<()> {
+///
+/// 成功時は終了コードを返します。
+async fn run(args: &[String], machine_output: bool, query: Option<&str>) -> Result<i32> {
+    // 不正な --query の式はコマンドを実行する前に拒否する
+    if let Some(expr) = query {
+        presentation::query::init(expr)?;
+    }
+
     // アプリケーション起動時に設定ファイルが存在することを保証
     // 存在しない場合はデフォルト設定から自動生成される
     UserConfig::ensure_config_exists()?;
 
-    cli::parse_args(args).await
+    // 人間向け出力の表示言語（config.toml の language、なければ LANG など）
+    let language = UserConfig::load().ok().and_then(|config| config.language);
+    i18n::init(Locale::detect(language.as_deref()));
+
+    cli::parse_args(args, machine_output).await
 }
 
 /// エラーハンドリングとユーザーへの表示
 ///
 /// エラーチェーンを一度走査して、最初にヒットしたアプリケーション定義エラーから
-/// 終了コードとヒントを取得する。
+/// 終了コード・エラーコード・ヒントを取得する。
 fn handle_error(error: anyhow::Error, machine_output: bool) {
-    // エラーチェーンから終了コードとヒントを同時取得
-    let (exit_code, hint) = extract_error_info(&error);
+    // エラーチェーンから終了コード・エラーコード・ヒントを同時取得
+    let ErrorInfo {
+        exit_code,
+        code,
+        hint,
+        endpoint,
+        status_code,
+    } = extract_error_info(&error);
 
     if machine_output {
         // 機械可読なJSON出力
-        let error_json = serde_json::json!({
+        let mut error_json = serde_json::json!({
             "success": false,
+            "schema_version": SCHEMA_VERSION,
             "error": {
+                "code": code,
                 "message": error.to_string(),
                 "exit_code": exit_code,
                 "hint": hint,
             }
         });
+        // API通信エラーの場合のみエンドポイントとステータスを含める
+        if let Some(endpoint) = &endpoint {
+            error_json["error"]["endpoint"] = serde_json::json!(endpoint);
+            error_json["error"]["status_code"] = serde_json::json!(status_code);
+        }
         println!("{}", error_json);
     } else {
         // 人間可読な出力（従来の動作）
         // エラーメッセージのヘッダー
-        eprintln!("Error: {}", error);
+        eprintln!("{}", style::error(&tf(Msg::ErrorHeader, &[&error])));
 
         // エラーチェーンを辿って詳細を表示
         let chain: Vec<_> = error.chain().skip(1).collect();
         if !chain.is_empty() {
-            eprintln!("\nCaused by:");
+            eprintln!("\n{}", t(Msg::CausedBy));
             for (i, cause) in chain.iter().enumerate() {
                 eprintln!("  {}: {}", i + 1, cause);
             }
         }
 
-        // ユーザー向けのヒントを表示
-        if let Some(hint_text) = hint {
-            eprintln!("\nHint: {}", hint_text);
+        // API通信エラーの場合はエンドポイントとステータスを表示
+        if let Some(endpoint) = &endpoint {
+            match status_code {
+                Some(status) => eprintln!("\nAPI: {} (HTTP {})", endpoint, status),
+                None => eprintln!("\nAPI: {}", endpoint),
+            }
+        }
+
+        // ユーザー向けのヒントを表示（表示言語に翻訳）
+        if let Some(hint_text) = i18n::localize_hint(code, status_code, hint) {
+            eprintln!("\n{}", style::hint(&tf(Msg::Hint, &[&hint_text])));
         }
     }
 
@@ -77,35 +137,122 @@ fn handle_error(error: anyhow::Error, machine_output: bool) {
     std::process::exit(exit_code);
 }
 
-/// エラーチェーンから終了コードとヒントを一度の走査で抽出
+/// アプリケーション定義エラーのコード（どれにも該当しない場合）
+const UNKNOWN_ERROR_CODE: &str = "UNKNOWN";
+
+/// エラーチェーンから抽出したエラー情報
+struct ErrorInfo {
+    exit_code: i32,
+    code: &'static str,
+    hint: Option<String>,
+    /// API通信エラーのエンドポイント
+    endpoint: Option<String>,
+    /// API通信エラーのHTTPステータスコード
+    status_code: Option<u16>,
+}
+
+impl Default for ErrorInfo {
+    /// アプリケーション定義エラーが見つからない場合の情報
+    fn default() -> Self {
+        Self {
+            exit_code: 1,
+            code: UNKNOWN_ERROR_CODE,
+            hint: None,
+            endpoint: None,
+            status_code: None,
+        }
+    }
+}
+
+/// エラーチェーンから終了コード・エラーコード・ヒントを一度の走査で抽出
 ///
 /// 最初にヒットしたアプリケーション定義エラー（DomainError, ConfigError, InfraError）
-/// から責務の委譲によりseverity() と hint() を取得する。
+/// から責務の委譲によりseverity()・code()・hint() を取得する。
 /// 型判定の重複を排除し、エラー型側への分類責務の委譲を実現。
-fn extract_error_info(error: &anyhow::Error) -> (i32, Option<String>) {
+fn extract_error_info(error: &anyhow::Error) -> ErrorInfo {
     // エラーチェーン全体を一度走査
     for cause in error.chain() {
         // DomainError の場合
         if let Some(domain_err) = cause.downcast_ref::<DomainError>() {
-            let severity = domain_err.severity();
-            let hint = domain_err.hint().map(|s| s.to_string());
-            return (severity.exit_code(), hint);
+            return ErrorInfo {
+                exit_code: domain_err.severity().exit_code(),
+                code: domain_err.code(),
+                hint: domain_err.hint().map(|s| s.to_string()),
+                ..ErrorInfo::default()
+            };
         }
 
         // ConfigError の場合
         if let Some(config_err) = cause.downcast_ref::<ConfigError>() {
-            let severity = config_err.severity();
-            let hint = config_err.hint().map(|s| s.to_string());
-            return (severity.exit_code(), hint);
+            return ErrorInfo {
+                exit_code: config_err.severity().exit_code(),
+                code: config_err.code(),
+                hint: config_err.hint().map(|s| s.to_string()),
+                ..ErrorInfo::default()
+            };
         }
 
         // InfraError の場合
         if let Some(infra_err) = cause.downcast_ref::<InfraError>() {
-            let severity = infra_err.severity();
-            return (severity.exit_code(), None);
+            let (endpoint, status_code) = infra_err
+                .api_details()
+                .map(|(endpoint, status_code)| (Some(endpoint.to_string()), status_code))
+                .unwrap_or_default();
+            return ErrorInfo {
+                exit_code: infra_err.severity().exit_code(),
+                code: infra_err.code(),
+                hint: infra_err.hint().map(|s| s.to_string()),
+                endpoint,
+                status_code,
+            };
         }
     }
 
     // 不明なエラーの場合はデフォルトの終了コード
-    (1, None)
+    ErrorInfo::default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_extract_error_info_uses_first_app_error_in_chain() {
+        let error = Err::<(), _>(DomainError::FileNotFound {
+            path: "video.mp4".to_string(),
+        })
+        .context("File validation failed")
+        .context("Upload command failed")
+        .unwrap_err();
+
+        let info = extract_error_info(&error);
+        assert_eq!(info.code, "FILE_NOT_FOUND");
+        assert_eq!(info.exit_code, 1);
+        assert!(info.hint.is_some());
+    }
+
+    #[test]
+    fn test_extract_error_info_codes() {
+        let error = anyhow::Error::new(ConfigError::token_not_found("not logged in"));
+        assert_eq!(extract_error_info(&error).code, "AUTH_MISSING");
+
+        let error = anyhow::Error::new(InfraError::api("/video/v1/assets", "denied", Some(401)));
+        let info = extract_error_info(&error);
+        assert_eq!(info.code, "AUTH_REJECTED");
+        assert_eq!(info.exit_code, 3);
+        assert_eq!(info.endpoint.as_deref(), Some("/video/v1/assets"));
+        assert_eq!(info.status_code, Some(401));
+        assert!(info.hint.is_some());
+
+        let error = anyhow::Error::new(InfraError::network("connection reset"))
+            .context("List command failed");
+        let info = extract_error_info(&error);
+        assert_eq!(info.code, "NETWORK_ERROR");
+        assert!(info.hint.is_some());
+        assert!(info.endpoint.is_none());
+
+        let error = anyhow::anyhow!("Unknown command: 'foo'");
+        assert_eq!(extract_error_info(&error).code, UNKNOWN_ERROR_CODE);
+    }
 }
@@ -0,0 +1,34 @@
+/// メディア解析
+///
+/// 外部ツール（ffprobe）を使って動画ファイルの中身を解析します。
+/// ツールがインストールされていない環境では解析をスキップし、
+/// 従来どおり拡張子とシグネチャによる検証のみで動作します。
+/// MP4/MOVの再生時間はffprobeなしでもヘッダーから読み取れます。
+/// `upload --transcode` ではffmpegで変換した出力をそのままアップロードします。
+pub mod mp4;
+pub mod probe;
+pub mod transcode;
+
+use crate::config::APP_CONFIG;
+use std::path::PathBuf;
+
+/// 外部ツール（ffprobe・ffmpeg）の実行ファイル
+///
+/// 既定では `APP_CONFIG.upload` のコマンド名で `PATH` から探します。
+/// ライブラリとして組み込む場合やテストでは、`PATH` を変えずに実行ファイルのパスを指定できます。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaTools {
+    /// ffprobeのコマンド名またはパス
+    pub ffprobe: PathBuf,
+    /// ffmpegのコマンド名またはパス
+    pub ffmpeg: PathBuf,
+}
+
+impl Default for MediaTools {
+    fn default() -> Self {
+        Self {
+            ffprobe: PathBuf::from(APP_CONFIG.upload.ffprobe_command),
+            ffmpeg: PathBuf::from(APP_CONFIG.upload.ffmpeg_command),
+        }
+    }
+}
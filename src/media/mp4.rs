@@ -0,0 +1,162 @@
+/// MP4/MOV（ISO BMFF）ヘッダーの解析
+///
+/// ffprobeがない環境でも再生時間を確認できるよう、
+/// `moov` ボックス内の `mvhd`（ムービーヘッダー）から再生時間を読み取ります。
+/// `moov` がファイル末尾にある場合も、ボックスのサイズをたどって読み飛ばすため
+/// 動画データ本体は読み込みません。
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// ボックスヘッダーの長さ（size: u32 + type: 4バイト）
+const BOX_HEADER_LEN: u64 = 8;
+
+/// MP4/MOVファイルの再生時間（秒）を読み取る
+///
+/// # 戻り値
+/// - `Ok(Some(秒))`: `mvhd` から再生時間を取得できた
+/// - `Ok(None)`: `moov` / `mvhd` が見つからない、または再生時間が未設定
+pub fn read_duration(path: &Path) -> io::Result<Option<f64>> {
+    let mut file = File::open(path)?;
+    let file_len = file.metadata()?.len();
+
+    let Some((moov_start, moov_end)) = find_box(&mut file, 0, file_len, b"moov")? else {
+        return Ok(None);
+    };
+    let Some((mvhd_start, mvhd_end)) = find_box(&mut file, moov_start, moov_end, b"mvhd")? else {
+        return Ok(None);
+    };
+
+    let mut mvhd = vec![0u8; (mvhd_end - mvhd_start).min(128) as usize];
+    file.seek(SeekFrom::Start(mvhd_start))?;
+    file.read_exact(&mut mvhd)?;
+
+    Ok(parse_mvhd_duration(&mvhd))
+}
+
+/// `[start, end)` の範囲から指定タイプのボックスを探し、中身の範囲を返す
+fn find_box(
+    file: &mut File,
+    start: u64,
+    end: u64,
+    box_type: &[u8; 4],
+) -> io::Result<Option<(u64, u64)>> {
+    let mut offset = start;
+    while offset + BOX_HEADER_LEN <= end {
+        let mut header = [0u8; BOX_HEADER_LEN as usize];
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut header)?;
+
+        let size = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as u64;
+        let (header_len, box_size) = match size {
+            // size=1: 直後の64bitに実サイズ（largesize）
+            1 => {
+                let mut large = [0u8; 8];
+                file.read_exact(&mut large)?;
+                (BOX_HEADER_LEN + 8, u64::from_be_bytes(large))
+            }
+            // size=0: ファイル（親ボックス）末尾まで
+            0 => (BOX_HEADER_LEN, end - offset),
+            size => (BOX_HEADER_LEN, size),
+        };
+
+        if box_size < header_len || offset + box_size > end {
+            // 壊れたボックス（これ以上たどれない）
+            return Ok(None);
+        }
+        if &header[4..8] == box_type {
+            return Ok(Some((offset + header_len, offset + box_size)));
+        }
+        offset += box_size;
+    }
+    Ok(None)
+}
+
+/// `mvhd` の中身から再生時間（秒）を取り出す
+///
+/// version 0 は32bit、version 1 は64bitの時刻・再生時間フィールドを持ちます。
+fn parse_mvhd_duration(mvhd: &[u8]) -> Option<f64> {
+    let read_u32 = |at: usize| {
+        mvhd.get(at..at + 4)
+            .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as u64)
+    };
+    let read_u64 = |at: usize| {
+        mvhd.get(at..at + 8).map(|b| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(b);
+            u64::from_be_bytes(bytes)
+        })
+    };
+
+    // version(1) + flags(3) の後に creation_time / modification_time が続く
+    let (timescale, duration) = match *mvhd.first()? {
+        0 => (read_u32(12)?, read_u32(16)?),
+        1 => (read_u32(20)?, read_u64(24)?),
+        _ => return None,
+    };
+
+    // 0xFFFFFFFF（全ビット1）は再生時間が不明であることを示す
+    let unknown = duration == u32::MAX as u64 || duration == u64::MAX;
+    if timescale == 0 || duration == 0 || unknown {
+        return None;
+    }
+    Some(duration as f64 / timescale as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ボックスを組み立てる
+    fn make_box(box_type: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut data = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(box_type);
+        data.extend_from_slice(body);
+        data
+    }
+
+    /// version 0 の mvhd を組み立てる
+    fn mvhd_v0(timescale: u32, duration: u32) -> Vec<u8> {
+        let mut body = vec![0u8; 12];
+        body.extend_from_slice(&timescale.to_be_bytes());
+        body.extend_from_slice(&duration.to_be_bytes());
+        body.extend_from_slice(&[0u8; 80]);
+        body
+    }
+
+    #[test]
+    fn test_read_duration_with_moov_after_mdat() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clip.mp4");
+
+        let mut data = make_box(b"ftyp", b"isom\0\0\0\0");
+        data.extend(make_box(b"mdat", &[0u8; 1024]));
+        data.extend(make_box(
+            b"moov",
+            &make_box(b"mvhd", &mvhd_v0(1000, 125_500)),
+        ));
+        std::fs::write(&path, data).unwrap();
+
+        assert_eq!(read_duration(&path).unwrap(), Some(125.5));
+    }
+
+    #[test]
+    fn test_read_duration_without_moov() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clip.mp4");
+        std::fs::write(&path, make_box(b"ftyp", b"isom\0\0\0\0")).unwrap();
+
+        assert_eq!(read_duration(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_mvhd_duration_version1() {
+        let mut body = vec![1u8, 0, 0, 0];
+        body.extend_from_slice(&[0u8; 16]);
+        body.extend_from_slice(&600u32.to_be_bytes());
+        body.extend_from_slice(&(600u64 * 7200).to_be_bytes());
+
+        assert_eq!(parse_mvhd_duration(&body), Some(7200.0));
+        assert_eq!(parse_mvhd_duration(&mvhd_v0(0, 100)), None);
+    }
+}
@@ -0,0 +1,203 @@
+/// ffprobeによる動画ファイルの解析
+///
+/// `ffprobe -show_format -show_streams` のJSON出力から、
+/// コーデック・再生時間・解像度・ビットレートを取り出します。
+/// ffprobeが解析できないファイルや映像ストリームを含まないファイルは
+/// Muxでも取り込めないため、アップロード前にエラーとします。
+use crate::domain::error::DomainError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 動画ファイルの解析結果
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct MediaProbe {
+    /// コンテナ形式（ffprobeのformat_name、例: "mov,mp4,m4a,3gp,3g2,mj2"）
+    pub format_name: String,
+    /// 再生時間（秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<f64>,
+    /// 全体のビットレート（bps）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bit_rate: Option<u64>,
+    /// 映像コーデック（例: "h264"）
+    pub video_codec: String,
+    /// 幅（ピクセル）
+    pub width: u32,
+    /// 高さ（ピクセル）
+    pub height: u32,
+    /// ピクセルフォーマット（例: "yuv420p", "yuv420p10le"）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pix_fmt: Option<String>,
+    /// フィールド順序（"progressive" 以外はインターレース、例: "tt", "bb"）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_order: Option<String>,
+    /// 伝達特性（HDRの場合 "smpte2084"（PQ）または "arib-std-b67"（HLG））
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color_transfer: Option<String>,
+    /// 音声コーデック（音声なしの場合はNone）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_codec: Option<String>,
+}
+
+/// ffprobeのJSON出力（必要な項目のみ）
+#[derive(Debug, Deserialize)]
+struct FfprobeOutput {
+    #[serde(default)]
+    streams: Vec<FfprobeStream>,
+    format: Option<FfprobeFormat>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FfprobeStream {
+    codec_type: Option<String>,
+    codec_name: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+    pix_fmt: Option<String>,
+    field_order: Option<String>,
+    color_transfer: Option<String>,
+}
+
+/// ffprobeは数値を文字列で出力する（例: "duration": "12.345000"）
+#[derive(Debug, Deserialize)]
+struct FfprobeFormat {
+    format_name: Option<String>,
+    duration: Option<String>,
+    bit_rate: Option<String>,
+}
+
+/// ffprobeで動画ファイルを解析する
+///
+/// # 引数
+/// * `file_path` - 解析するファイルのパス
+/// * `ffprobe` - ffprobeのコマンド名またはパス（`MediaTools::ffprobe`）
+///
+/// # 戻り値
+/// - `Ok(Some(MediaProbe))`: 解析成功
+/// - `Ok(None)`: ffprobeが見つからない（解析をスキップ）
+///
+/// # エラー
+/// ffprobeがファイルを解析できない、または映像ストリームがない場合に
+/// `DomainError::UnsupportedMedia` を返します。
+pub async fn probe(file_path: &str, ffprobe: &Path) -> Result<Option<MediaProbe>, DomainError> {
+    let output = tokio::process::Command::new(ffprobe)
+        .args([
+            "-v",
+            "error",
+            "-print_format",
+            "json",
+            "-show_format",
+            "-show_streams",
+        ])
+        .arg(file_path)
+        .output()
+        .await;
+
+    let output = match output {
+        Ok(output) => output,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(DomainError::unsupported_media(
+                file_path,
+                format!("failed to run ffprobe: {}", e),
+            ));
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(DomainError::unsupported_media(
+            file_path,
+            stderr
+                .trim()
+                .lines()
+                .last()
+                .unwrap_or("ffprobe could not read the file"),
+        ));
+    }
+
+    parse_ffprobe_output(&String::from_utf8_lossy(&output.stdout))
+        .map(Some)
+        .map_err(|reason| DomainError::unsupported_media(file_path, reason))
+}
+
+/// ffprobeのJSON出力を解析結果に変換する
+fn parse_ffprobe_output(json: &str) -> Result<MediaProbe, String> {
+    let output: FfprobeOutput =
+        serde_json::from_str(json).map_err(|e| format!("unexpected ffprobe output: {}", e))?;
+
+    let video = output
+        .streams
+        .iter()
+        .find(|s| s.codec_type.as_deref() == Some("video"))
+        .ok_or_else(|| "no video stream found".to_string())?;
+
+    let audio_codec = output
+        .streams
+        .iter()
+        .find(|s| s.codec_type.as_deref() == Some("audio"))
+        .and_then(|s| s.codec_name.clone());
+
+    let format = output.format;
+    Ok(MediaProbe {
+        format_name: format
+            .as_ref()
+            .and_then(|f| f.format_name.clone())
+            .unwrap_or_default(),
+        duration_secs: format
+            .as_ref()
+            .and_then(|f| f.duration.as_deref())
+            .and_then(|d| d.parse().ok()),
+        bit_rate: format
+            .as_ref()
+            .and_then(|f| f.bit_rate.as_deref())
+            .and_then(|b| b.parse().ok()),
+        video_codec: video.codec_name.clone().unwrap_or_default(),
+        width: video.width.unwrap_or(0),
+        height: video.height.unwrap_or(0),
+        pix_fmt: video.pix_fmt.clone(),
+        field_order: video.field_order.clone(),
+        color_transfer: video.color_transfer.clone(),
+        audio_codec,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ffprobe_output() {
+        let json = r#"{
+            "streams": [
+                {"index": 0, "codec_type": "video", "codec_name": "h264", "width": 1920, "height": 1080, "pix_fmt": "yuv420p", "field_order": "progressive"},
+                {"index": 1, "codec_type": "audio", "codec_name": "aac"}
+            ],
+            "format": {"format_name": "mov,mp4,m4a,3gp,3g2,mj2", "duration": "12.500000", "bit_rate": "5242880"}
+        }"#;
+
+        let probe = parse_ffprobe_output(json).unwrap();
+        assert_eq!(probe.video_codec, "h264");
+        assert_eq!((probe.width, probe.height), (1920, 1080));
+        assert_eq!(probe.duration_secs, Some(12.5));
+        assert_eq!(probe.bit_rate, Some(5_242_880));
+        assert_eq!(probe.audio_codec.as_deref(), Some("aac"));
+        assert_eq!(probe.pix_fmt.as_deref(), Some("yuv420p"));
+        assert_eq!(probe.field_order.as_deref(), Some("progressive"));
+        assert_eq!(probe.color_transfer, None);
+    }
+
+    #[test]
+    fn test_parse_ffprobe_output_requires_video_stream() {
+        let json = r#"{
+            "streams": [{"codec_type": "audio", "codec_name": "mp3"}],
+            "format": {"format_name": "mp3", "duration": "180.0"}
+        }"#;
+
+        assert_eq!(
+            parse_ffprobe_output(json).unwrap_err(),
+            "no video stream found"
+        );
+    }
+}
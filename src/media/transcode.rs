@@ -0,0 +1,330 @@
+/// ffmpegによるアップロード前の変換（`upload --transcode`）
+///
+/// ffmpegの出力を一時ファイルに書き出さず、標準出力からそのままチャンクアップロードへ流します。
+/// 標準出力へは先頭から順に書き出せるフラグメント化MP4（`-movflags frag_keyframe+empty_moov`）で出力し、
+/// 変換の進捗は `-progress pipe:2` で標準エラーに出力される `out_time_us` から読み取ります。
+/// 標準エラーのそれ以外の行（`-loglevel error` のエラー）は、変換に失敗した場合のメッセージに使います。
+use crate::api::error::InfraError;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, ChildStdout, Command};
+use tokio::task::JoinHandle;
+
+/// 変換後の形式（結果の `file_format` とContent-Typeの決定に使う）
+pub const OUTPUT_FORMAT: &str = "mp4";
+
+/// 変換に失敗した場合のメッセージに含める、ffmpegのエラー出力の行数
+const ERROR_LOG_LINES: usize = 5;
+
+/// 実行中の変換
+///
+/// 破棄するとffmpegのプロセスも終了します（アップロードの失敗・中断時）。
+pub struct Transcode {
+    child: Child,
+    output: ChildStdout,
+    progress: TranscodeProgress,
+    stderr: Option<JoinHandle<Vec<String>>>,
+}
+
+/// 変換の進捗（変換済みの再生位置）
+///
+/// 標準エラーを読むタスクが更新し、進捗の通知側が参照します。
+#[derive(Debug, Clone, Default)]
+pub struct TranscodeProgress {
+    out_time_us: Arc<AtomicU64>,
+}
+
+impl TranscodeProgress {
+    /// 変換済みの再生位置（秒）
+    pub fn out_time_secs(&self) -> f64 {
+        self.out_time_us.load(Ordering::Relaxed) as f64 / 1_000_000.0
+    }
+}
+
+/// ffmpegを起動し、変換を開始する
+///
+/// # 引数
+/// * `input_path` - 変換元のファイルのパス
+/// * `transcode_args` - 出力に付けるffmpegの引数（例: `-c:v libx264 -crf 20`）
+/// * `ffmpeg` - ffmpegのコマンド名またはパス（`MediaTools::ffmpeg`）
+///
+/// # エラー
+/// 引数を解釈できない場合、またはffmpegを起動できない場合は`InfraError::Transcode`を返す
+pub fn spawn(
+    input_path: &str,
+    transcode_args: &str,
+    ffmpeg: &Path,
+) -> Result<Transcode, InfraError> {
+    let user_args = split_args(transcode_args).map_err(InfraError::transcode)?;
+
+    let mut child = Command::new(ffmpeg)
+        .args(ffmpeg_args(input_path, &user_args))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => InfraError::transcode(format!(
+                "'{}' was not found in PATH; install ffmpeg to use --transcode",
+                ffmpeg.display()
+            )),
+            _ => InfraError::transcode(format!("failed to run ffmpeg: {}", e)),
+        })?;
+
+    let output = child
+        .stdout
+        .take()
+        .ok_or_else(|| InfraError::transcode("ffmpeg output is not available"))?;
+    let progress = TranscodeProgress::default();
+    let stderr = child
+        .stderr
+        .take()
+        .map(|stderr| tokio::spawn(read_stderr(stderr, progress.clone())));
+
+    Ok(Transcode {
+        child,
+        output,
+        progress,
+        stderr,
+    })
+}
+
+impl Transcode {
+    /// 変換の出力（ffmpegの標準出力）
+    pub fn output(&mut self) -> &mut ChildStdout {
+        &mut self.output
+    }
+
+    /// 変換の進捗
+    pub fn progress(&self) -> TranscodeProgress {
+        self.progress.clone()
+    }
+
+    /// ffmpegの終了を待ち、変換が成功したか確認する
+    ///
+    /// 出力を最後まで読んだ後に呼び出します。
+    ///
+    /// # エラー
+    /// ffmpegが失敗した場合は、エラー出力の末尾を含む`InfraError::Transcode`を返す
+    pub async fn finish(&mut self) -> Result<(), InfraError> {
+        let status = self
+            .child
+            .wait()
+            .await
+            .map_err(|e| InfraError::transcode(format!("failed to wait for ffmpeg: {}", e)))?;
+        let log = match self.stderr.take() {
+            Some(stderr) => stderr.await.unwrap_or_default(),
+            None => Vec::new(),
+        };
+
+        if status.success() {
+            return Ok(());
+        }
+        let detail = if log.is_empty() {
+            "no error output".to_string()
+        } else {
+            log.join("; ")
+        };
+        Err(InfraError::transcode(format!(
+            "ffmpeg exited with {}: {}",
+            status, detail
+        )))
+    }
+}
+
+/// ffmpegの引数を組み立てる
+///
+/// 形式の指定（`-f`・`-movflags`）はユーザーの引数より前に置くため、ユーザーの引数で上書きできます。
+fn ffmpeg_args(input_path: &str, user_args: &[String]) -> Vec<String> {
+    let leading = [
+        "-hide_banner",
+        "-nostdin",
+        "-nostats",
+        "-loglevel",
+        "error",
+        "-progress",
+        "pipe:2",
+        "-i",
+        input_path,
+        "-f",
+        OUTPUT_FORMAT,
+        "-movflags",
+        "frag_keyframe+empty_moov+default_base_moof",
+    ];
+    leading
+        .into_iter()
+        .map(str::to_string)
+        .chain(user_args.iter().cloned())
+        .chain(std::iter::once("pipe:1".to_string()))
+        .collect()
+}
+
+/// `--transcode` の値を引数に分割する
+///
+/// 空白で区切り、シングルクォート・ダブルクォートで囲んだ部分は空白を含めて1つの引数にします
+/// （シェルを介さないため、変数の展開やエスケープは行いません）。
+///
+/// # エラー
+/// クォートが閉じていない場合、または引数がない場合
+pub fn split_args(value: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+
+    for c in value.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '\'' || c == '"' => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            None if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if let Some(q) = quote {
+        return Err(format!("unterminated {} in --transcode arguments", q));
+    }
+    if in_arg {
+        args.push(current);
+    }
+    if args.is_empty() {
+        return Err("--transcode requires ffmpeg arguments (e.g. \"-c:v libx264 -crf 20\")".into());
+    }
+    Ok(args)
+}
+
+/// ffmpegの標準エラーを読み、進捗を更新しながらエラー出力の末尾を集める
+async fn read_stderr(
+    stderr: tokio::process::ChildStderr,
+    progress: TranscodeProgress,
+) -> Vec<String> {
+    let mut log = VecDeque::with_capacity(ERROR_LOG_LINES);
+    let mut lines = BufReader::new(stderr).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        match parse_progress_line(&line) {
+            Some(ProgressLine::OutTime(out_time_us)) => {
+                progress.out_time_us.store(out_time_us, Ordering::Relaxed);
+            }
+            Some(ProgressLine::Other) => {}
+            None if line.trim().is_empty() => {}
+            None => {
+                if log.len() == ERROR_LOG_LINES {
+                    log.pop_front();
+                }
+                log.push_back(line.trim().to_string());
+            }
+        }
+    }
+    log.into()
+}
+
+/// `-progress` の出力行
+#[derive(Debug, PartialEq)]
+enum ProgressLine {
+    /// 変換済みの再生位置（マイクロ秒）
+    OutTime(u64),
+    /// それ以外の項目（`frame=`・`speed=` など）
+    Other,
+}
+
+/// `-progress` の出力行（`key=value`）を解釈する（それ以外の行はNone）
+///
+/// `out_time_ms` は名前に反してマイクロ秒のため、`out_time_us` と同じに扱います。
+/// 再生位置が未確定の間は `N/A` や負の値が出力されるため、その場合は位置を更新しません。
+fn parse_progress_line(line: &str) -> Option<ProgressLine> {
+    let (key, value) = line.trim().split_once('=')?;
+    if key.is_empty()
+        || !key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return None;
+    }
+    match key {
+        "out_time_us" | "out_time_ms" => Some(
+            value
+                .trim()
+                .parse()
+                .map_or(ProgressLine::Other, ProgressLine::OutTime),
+        ),
+        _ => Some(ProgressLine::Other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(
+            split_args("-c:v libx264  -crf 20").unwrap(),
+            ["-c:v", "libx264", "-crf", "20"]
+        );
+        assert_eq!(
+            split_args(r#"-vf "scale=1280:-2, fps=30" -metadata title='My clip'"#).unwrap(),
+            ["-vf", "scale=1280:-2, fps=30", "-metadata", "title=My clip"]
+        );
+        assert_eq!(
+            split_args(r#"-metadata comment="""#).unwrap(),
+            ["-metadata", "comment=",]
+        );
+        assert!(split_args("-vf 'scale=1280:-2").is_err());
+        assert!(split_args("   ").is_err());
+    }
+
+    #[test]
+    fn test_ffmpeg_args_let_user_override_format() {
+        let args = ffmpeg_args("in.mkv", &["-c".to_string(), "copy".to_string()]);
+        let input = args.iter().position(|a| a == "-i").unwrap();
+        let format = args.iter().position(|a| a == "-f").unwrap();
+        let user = args.iter().position(|a| a == "copy").unwrap();
+        assert_eq!(args[input + 1], "in.mkv");
+        assert!(input < format && format < user);
+        assert_eq!(args.last().map(String::as_str), Some("pipe:1"));
+    }
+
+    #[test]
+    fn test_parse_progress_line() {
+        assert_eq!(
+            parse_progress_line("out_time_us=12500000"),
+            Some(ProgressLine::OutTime(12_500_000))
+        );
+        assert_eq!(
+            parse_progress_line("out_time_ms=12500000"),
+            Some(ProgressLine::OutTime(12_500_000))
+        );
+        assert_eq!(
+            parse_progress_line("out_time_us=N/A"),
+            Some(ProgressLine::Other)
+        );
+        assert_eq!(parse_progress_line("speed=2.5x"), Some(ProgressLine::Other));
+        assert_eq!(
+            parse_progress_line("progress=end"),
+            Some(ProgressLine::Other)
+        );
+        // エラー出力は進捗の行として扱わない
+        assert_eq!(parse_progress_line("Unknown encoder 'libx265'"), None);
+        assert_eq!(
+            parse_progress_line("[libx264 @ 0x55] Error setting option crf=abc."),
+            None
+        );
+    }
+}
@@ -0,0 +1,32 @@
+/// プレゼンテーション層: ブラウザでURLを開く
+///
+/// `serve` のプレビューページを、OSの既定のブラウザで開きます
+/// （Windowsは `start`、macOSは `open`、それ以外は `xdg-open`）。
+/// ブラウザの起動を待たずに戻り、起動コマンドの終了はバックグラウンドで回収します。
+use std::io;
+use std::process::{Command, Stdio};
+
+/// URLを既定のブラウザで開く
+///
+/// # エラー
+/// 起動コマンドが見つからない・実行できない場合
+pub fn open(url: &str) -> io::Result<()> {
+    let mut command = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        // `start` の最初の引用符付き引数はウィンドウのタイトルになるため、空のタイトルを渡す
+        cmd.args(["/C", "start", ""]);
+        cmd
+    } else if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else {
+        Command::new("xdg-open")
+    };
+    let mut child = command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
@@ -0,0 +1,108 @@
+/// プレゼンテーション層: URLのクリップボードへのコピー
+///
+/// `upload` / `show` の `--copy [hls|mp4|thumbnail]` で、結果のURLをOSのクリップボード（arboard）へコピーします。
+/// 署名付きURLは署名後のURLをコピーするため、`sign_result` の後に呼び出します。
+/// ディスプレイのないサーバーやSSH接続先などクリップボードが使えない環境、
+/// `clipboard` フィーチャーなしのビルドでは、コマンドを失敗させずに警告（`copy_failed`）を加えます。
+use vidyeet::commands::result::{CommandResult, CopiedUrl, CopyTarget};
+use vidyeet::domain::warning::{Warning, WarningKind};
+
+/// 結果のURLをクリップボードへコピーし、コピーしたURLを結果に記録する
+///
+/// URLがない・コピーできない場合は結果の警告に加える（`upload` / `show` 以外の結果は何もしない）。
+pub fn copy_result_url(result: &mut CommandResult, target: CopyTarget) {
+    let (url, warnings, copied) = match result {
+        CommandResult::Upload(r) => (
+            target_url(
+                target,
+                r.hls_url.as_deref(),
+                r.mp4_url.as_deref(),
+                r.thumbnail_url.as_deref(),
+            )
+            .map(str::to_string),
+            &mut r.warnings,
+            &mut r.copied,
+        ),
+        CommandResult::Show(r) => (
+            target_url(
+                target,
+                r.hls_url.as_deref(),
+                r.mp4_url.as_deref(),
+                r.thumbnail(),
+            )
+            .map(str::to_string),
+            &mut r.warnings,
+            &mut r.copied,
+        ),
+        _ => return,
+    };
+
+    let Some(url) = url else {
+        warnings.push(Warning::new(
+            WarningKind::CopyFailed,
+            format!("There is no {} URL to copy to the clipboard", target.name()),
+        ));
+        return;
+    };
+    match set_text(&url) {
+        Ok(()) => *copied = Some(CopiedUrl { target, url }),
+        Err(e) => warnings.push(Warning::new(
+            WarningKind::CopyFailed,
+            format!(
+                "Could not copy the {} URL to the clipboard ({}); copy it from the output instead",
+                target.name(),
+                e
+            ),
+        )),
+    }
+}
+
+/// コピーするURLを選ぶ
+fn target_url<'a>(
+    target: CopyTarget,
+    hls_url: Option<&'a str>,
+    mp4_url: Option<&'a str>,
+    thumbnail_url: Option<&'a str>,
+) -> Option<&'a str> {
+    match target {
+        CopyTarget::Hls => hls_url,
+        CopyTarget::Mp4 => mp4_url,
+        CopyTarget::Thumbnail => thumbnail_url,
+    }
+}
+
+/// テキストをクリップボードへ書き込む
+///
+/// Linux（X11・Wayland）ではクリップボードの内容をプロセスが保持するため、
+/// 終了後も貼り付けられるかはクリップボードマネージャーに依存します。
+#[cfg(feature = "clipboard")]
+fn set_text(text: &str) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| e.to_string())
+}
+
+/// テキストをクリップボードへ書き込む（`clipboard` フィーチャーなしのビルド）
+#[cfg(not(feature = "clipboard"))]
+fn set_text(_text: &str) -> Result<(), String> {
+    Err("vidyeet was built without the 'clipboard' feature".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_url() {
+        let (hls, mp4, thumbnail) = (Some("a.m3u8"), None, Some("thumbnail.jpg"));
+        assert_eq!(
+            target_url(CopyTarget::Hls, hls, mp4, thumbnail),
+            Some("a.m3u8")
+        );
+        assert_eq!(target_url(CopyTarget::Mp4, hls, mp4, thumbnail), None);
+        assert_eq!(
+            target_url(CopyTarget::Thumbnail, hls, mp4, thumbnail),
+            Some("thumbnail.jpg")
+        );
+    }
+}
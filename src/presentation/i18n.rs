@@ -0,0 +1,597 @@
+/// 人間向け出力の多言語対応（英語・日本語）
+///
+/// 表示言語は config.toml の `language`、未設定の場合は環境変数
+/// `LC_ALL` / `LC_MESSAGES` / `LANG` から決定します。
+/// 機械可読出力（`--machine`）は言語に関わらず英語のまま変わりません。
+use std::env;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+/// 表示言語
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    Ja,
+}
+
+impl Locale {
+    /// 言語タグ・ロケール名（`ja`, `ja_JP.UTF-8`, `en-US` など）から判定する
+    ///
+    /// `C` / `POSIX` は英語として扱い、未対応の言語はNoneを返します。
+    pub fn parse(tag: &str) -> Option<Self> {
+        let language = tag.split(['_', '-', '.', '@']).next()?.to_ascii_lowercase();
+        match language.as_str() {
+            "ja" => Some(Self::Ja),
+            "en" | "c" | "posix" => Some(Self::En),
+            _ => None,
+        }
+    }
+
+    /// 設定値と環境変数から表示言語を決定する
+    ///
+    /// 優先順位: config.toml の `language` → `LC_ALL` → `LC_MESSAGES` → `LANG` → 英語
+    pub fn detect(configured: Option<&str>) -> Self {
+        configured
+            .and_then(Self::parse)
+            .or_else(|| Self::from_env(|key| env::var(key).ok()))
+            .unwrap_or(Self::En)
+    }
+
+    /// 環境変数から判定する（POSIXと同じく、最初に設定されている変数で決まる）
+    fn from_env(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|key| var(key))
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::parse(&value))
+    }
+}
+
+/// 現在の表示言語
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+/// 表示言語を設定する（最初の呼び出しのみ有効）
+pub fn init(locale: Locale) {
+    let _ = LOCALE.set(locale);
+}
+
+/// 現在の表示言語（未設定の場合は環境変数から決定）
+pub fn locale() -> Locale {
+    *LOCALE.get_or_init(|| Locale::detect(None))
+}
+
+/// 現在の表示言語のメッセージ
+pub fn t(msg: Msg) -> &'static str {
+    msg.text(locale())
+}
+
+/// 現在の表示言語のメッセージに引数を埋め込む
+///
+/// メッセージ中の `{}` を引数で順に置き換えます。
+/// 桁数などの書式は呼び出し側で整形してから渡してください。
+pub fn tf(msg: Msg, args: &[&dyn Display]) -> String {
+    fill(t(msg), args)
+}
+
+fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(pos) = rest.find("{}") {
+        output.push_str(&rest[..pos]);
+        if let Some(arg) = args.next() {
+            output.push_str(&arg.to_string());
+        }
+        rest = &rest[pos + 2..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// エラーのヒントを現在の表示言語にする
+///
+/// ヒントはエラー型（ライブラリ）が英語で返すため、日本語の場合のみ
+/// エラーコード（とHTTPステータス）から翻訳を引きます。翻訳がなければ元のヒントを使います。
+pub fn localize_hint(code: &str, status_code: Option<u16>, hint: Option<String>) -> Option<String> {
+    match locale() {
+        Locale::En => hint,
+        Locale::Ja => hint.map(|hint| {
+            ja_hint(code, status_code)
+                .map(str::to_string)
+                .unwrap_or(hint)
+        }),
+    }
+}
+
+/// エラーコードに対応する日本語のヒント
+fn ja_hint(code: &str, status_code: Option<u16>) -> Option<&'static str> {
+    let hint = match code {
+        "FILE_NOT_FOUND" => "ファイルパスを確認し、ファイルが存在することを確かめてください。",
+        "INVALID_FORMAT" => "対応フォーマット: mp4, mov, avi, mkv, webm",
+        "FILE_TOO_LARGE" => "動画を圧縮するか、より小さいファイルを使用してください。",
+        "EMPTY_FILE" => "ファイルが空か、破損している可能性があります。",
+        "NOT_A_FILE" => "ディレクトリではなくファイルを指定してください。",
+        "CONTENT_MISMATCH" => {
+            "ファイルの拡張子が誤っているか、動画ではない可能性があります。ファイルを確認するか、正しい拡張子に変更してください。"
+        }
+        "UNSUPPORTED_MEDIA" => {
+            "動画として読み込めませんでした。ローカルで再生できるか確認するか、再エクスポートしてください（例: H.264 MP4）。"
+        }
+        "DURATION_TOO_LONG" => {
+            "プランの再生時間制限に収まるよう動画をトリミングするか、config.toml の 'max_duration_secs' を引き上げてください。"
+        }
+        "DUPLICATE_UPLOAD" => {
+            "'vidyeet show <asset_id>' で既存のアセットを確認するか、--force を指定して再度アップロードしてください。"
+        }
+        "INVALID_TAG" => "タグには英数字と '-'・'_'・'.' のみ使用できます（32文字まで）。",
+        "INVALID_LANGUAGE_CODE" => {
+            "'en'・'ja'・'pt-BR' のようなBCP 47の言語コードを指定してください。"
+        }
+        "PASSTHROUGH_IN_USE" => {
+            "タグはアセットのpassthroughに保存されます。タグを付けるにはMuxダッシュボードでpassthroughを空にしてください。"
+        }
+        "PASSTHROUGH_TOO_LONG" => "タグを減らすか、短いタグ名を使用してください。",
+        "ASSET_LIMIT_REACHED" => {
+            "'vidyeet prune --keep <n>' や 'vidyeet delete' で空きを作るか、--on-full auto_delete を指定して最も古いアセットを削除してください。"
+        }
+        "CONFIG_DIR_NOT_FOUND" => {
+            "設定ディレクトリが見つかりません。システム環境を確認してください。"
+        }
+        "CONFIG_IO_ERROR" => {
+            "ファイルの権限を確認し、設定ディレクトリに書き込めることを確かめてください。"
+        }
+        "CONFIG_PARSE_ERROR" => {
+            "設定ファイルが破損している可能性があります。削除するとデフォルト設定で再生成されます。"
+        }
+        "CONFIG_SERIALIZE_ERROR" => {
+            "設定を保存できませんでした。不正な文字や書式がないか確認してください。"
+        }
+        "CONFIG_INVALID" => "設定内容を見直し、すべての項目が正しいことを確認してください。",
+        "AUTH_MISSING" => "'vidyeet login' を実行して認証してください。",
+        "NETWORK_ERROR" => "インターネット接続を確認して、もう一度お試しください。",
+        "AUTH_REJECTED" => {
+            "APIの認証情報と権限を確認するか、'vidyeet login' をやり直してください。"
+        }
+        "NOT_FOUND" => "リソースが見つかりません。'vidyeet list' でアセットIDを確認してください。",
+        "RATE_LIMITED" => "Muxへのリクエストが多すぎます。しばらく待ってから再試行してください。",
+        "API_ERROR" if matches!(status_code, Some(500..=599)) => {
+            "Muxが一時的に利用できません。時間をおいて再試行してください。"
+        }
+        "API_ERROR" => "APIの認証情報と権限を確認してください。",
+        "TIMEOUT" => "処理に時間がかかりすぎました。再試行するか、接続を確認してください。",
+        "STORAGE_ERROR" => {
+            "ローカルデータを読み書きできませんでした。権限を確認するか、ファイルを削除して再構築してください。"
+        }
+        "HOOK_FAILED" => {
+            "config.toml の [hooks] のコマンドを確認し、手動で実行して出力を確認してください。"
+        }
+        "TRANSCODE_FAILED" => {
+            "ffmpegがインストールされているか確認し、--transcode の引数でffmpegを手動で実行して出力を確認してください。"
+        }
+        "IO_ERROR" => {
+            "I/Oエラーが発生しました。ファイルの権限とディスクの空き容量を確認してください。"
+        }
+        _ => return None,
+    };
+    Some(hint)
+}
+
+/// メッセージカタログを定義する
+///
+/// 各メッセージの英語と日本語を並べて書き、`Msg` 列挙型と `Msg::text` を生成します。
+macro_rules! messages {
+    ($($name:ident => $en:literal, $ja:literal;)*) => {
+        /// 人間向け出力のメッセージ
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Msg {
+            $($name,)*
+        }
+
+        impl Msg {
+            /// すべてのメッセージ
+            #[cfg(test)]
+            const ALL: &[Msg] = &[$(Msg::$name,)*];
+
+            /// 指定した言語のメッセージ
+            pub fn text(self, locale: Locale) -> &'static str {
+                match (self, locale) {
+                    $(
+                        (Msg::$name, Locale::En) => $en,
+                        (Msg::$name, Locale::Ja) => $ja,
+                    )*
+                }
+            }
+        }
+    };
+}
+
+messages! {
+    // エラー表示（main.rs）
+    ErrorHeader => "Error: {}", "エラー: {}";
+    CausedBy => "Caused by:", "原因:";
+    Hint => "Hint: {}", "ヒント: {}";
+
+    // 共通のラベル
+    AssetId => "Asset ID: {}", "アセットID: {}";
+    Status => "Status: {}", "ステータス: {}";
+    Duration => "Duration: {}", "再生時間: {}";
+    AspectRatio => "Aspect Ratio: {}", "アスペクト比: {}";
+    Created => "Created: {}", "作成日時: {}";
+    WithRelativeTime => "{} ({})", "{}（{}）";
+    RelativeJustNow => "just now", "たった今";
+    RelativeMinute => "1 minute ago", "1 分前";
+    RelativeMinutes => "{} minutes ago", "{} 分前";
+    RelativeHour => "1 hour ago", "1 時間前";
+    RelativeHours => "{} hours ago", "{} 時間前";
+    RelativeDay => "1 day ago", "1 日前";
+    RelativeDays => "{} days ago", "{} 日前";
+    RelativeMonth => "1 month ago", "1 か月前";
+    RelativeMonths => "{} months ago", "{} か月前";
+    RelativeYear => "1 year ago", "1 年前";
+    RelativeYears => "{} years ago", "{} 年前";
+    File => "File: {}", "ファイル: {}";
+    Warning => "Warning: {}", "警告: {}";
+    WarningsHeading => "Warnings ({}):", "警告（{} 件）:";
+    LastError => "Last error: {}", "最後のエラー: {}";
+    Media => "Media:", "メディア:";
+    FinalMedia => "Processed media:", "処理後のメディア:";
+    NotAvailable => "(not available)", "（利用不可）";
+    NoAudio => "{} (no audio)", "{}（音声なし）";
+    ItemUploaded => "✓ Uploaded: {}", "✓ アップロード完了: {}";
+
+    // login / logout / status
+    LoginUpdated => "✓ Login credentials updated!", "✓ 認証情報を更新しました";
+    LoginUpdatedDetail => "New authentication credentials have been saved.", "新しい認証情報を保存しました。";
+    LoginSucceeded => "Login successful.", "ログインしました。";
+    LoginSavedDetail => "Authentication credentials have been saved.", "認証情報を保存しました。";
+    LogoutSucceeded => "Logged out successfully.", "ログアウトしました。";
+    LogoutRemovedDetail => "Authentication credentials have been removed.", "認証情報を削除しました。";
+    AlreadyLoggedOut => "Already logged out.", "すでにログアウトしています。";
+    StatusAuthenticated => "Authenticated", "認証済み";
+    StatusTokenId => "Token ID: {}", "トークンID: {}";
+    StatusEnvironment => "Environment: {}", "環境: {}";
+    StatusValid => "Your credentials are valid and working.", "認証情報は有効です。";
+    StatusAuthFailed => "✗ Authentication failed", "✗ 認証に失敗しました";
+    StatusInvalid => "Your credentials may be invalid or expired.", "認証情報が無効か、期限切れの可能性があります。";
+    StatusRunLoginUpdate => "Please run 'vidyeet login' to update your credentials.", "'vidyeet login' を実行して認証情報を更新してください。";
+    StatusNotLoggedIn => "Not logged in", "未ログイン";
+    StatusNoCredentials => "No authentication credentials found.", "認証情報が見つかりません。";
+    StatusAssets => "Assets: {}", "アセット数: {}";
+    StatusAssetsWithLimit => "Assets: {} / {} ({} remaining)", "アセット数: {} / {}（残り {} 件）";
+    StatusAtCapacity => "The asset limit has been reached. The next upload will delete the oldest asset.", "アセット数が上限に達しています。次のアップロードで最も古いアセットが削除されます。";
+    StatusAtCapacityAsk => "The asset limit has been reached. The next upload will ask before deleting the oldest asset.", "アセット数が上限に達しています。次のアップロードでは、最も古いアセットを削除する前に確認します。";
+    StatusAtCapacityFail => "The asset limit has been reached. Uploads will fail until assets are deleted.", "アセット数が上限に達しています。アセットを削除するまでアップロードは失敗します。";
+    StatusSetAssetLimit => "Set asset_limit in config.toml to see how many uploads remain.", "config.toml に asset_limit を設定すると、残りのアップロード可能数を表示します。";
+    StatusRunLogin => "Please run 'vidyeet login' to authenticate.", "'vidyeet login' を実行して認証してください。";
+
+    // list
+    ListEmpty => "No videos found.", "動画が見つかりません。";
+    ListUploadFirst => "Upload your first video with 'vidyeet upload <file>'", "'vidyeet upload <file>' で最初の動画をアップロードできます";
+    ListFound => "Found {} video(s):", "{} 件の動画が見つかりました:";
+    ListFoundOffline => "Found {} video(s) (offline, last synced: {}):", "{} 件の動画が見つかりました（オフライン、最終同期: {}）:";
+    ListVideoNumber => "Video #{}", "動画 #{}";
+    ListGroupHeading => "== {} ({} video(s), {} total) ==", "== {}（{} 件、合計 {}）==";
+    ListSummary => "Total: {} video(s) ({}), duration {}", "合計: {} 件（{}）、再生時間 {}";
+    ListSummaryStorage => ", approx. storage {}", "、保存容量 約 {}";
+    ListTitle => "Title: {}", "タイトル: {}";
+    ListExternalId => "External ID: {}", "外部ID: {}";
+    ListTags => "Tags: {}", "タグ: {}";
+
+    // tag
+    TagAdded => "✓ Added tag '{}' to {}", "✓ タグ '{}' を {} に追加しました";
+    TagAlreadySet => "Tag '{}' is already set on {}", "タグ '{}' は {} に既に付いています";
+    TagRemoved => "✓ Removed tag '{}' from {}", "✓ タグ '{}' を {} から削除しました";
+    TagNotSet => "Tag '{}' is not set on {}", "タグ '{}' は {} に付いていません";
+    TagList => "Tags of {}: {}", "{} のタグ: {}";
+    RenditionCreated => "✓ Added {} MP4 to {}", "✓ {} のMP4を {} に追加しました";
+    RenditionExists => "{} already has a {} MP4", "{} には {} のMP4が既にあります";
+    RenditionDeleted => "✓ Deleted {} MP4 from {}", "✓ {} のMP4を {} から削除しました";
+    RenditionWaiting => "Waiting for the {} MP4 to be ready...", "{} のMP4の生成を待っています...";
+    WaitWaiting => "Waiting for {} to be {}...", "{} が {} になるのを待っています...";
+    WaitReached => "✓ Asset {} is {} ({}s)", "✓ アセット {} が {} になりました（{}秒）";
+    RenditionPending => "The MP4 is being generated. Run 'vidyeet show {}' later to check it.", "MP4は生成中です。後で 'vidyeet show {}' で確認してください。";
+    TagNone => "{} has no tags", "{} にはタグがありません";
+    CaptionAdded => "✓ Added {} captions to {}", "✓ {} の字幕を {} に追加しました";
+    CaptionGenerating => "✓ Started generating {} captions for {} from audio track {}", "✓ {} の字幕を {} に生成しています（生成元のオーディオトラック: {}）";
+    CaptionTrack => "Track: {} {}", "トラック: {} {}";
+    CaptionPending => "Captions are being generated. Run 'vidyeet show {}' later to check them.", "字幕は生成中です。後で 'vidyeet show {}' で確認してください。";
+
+    // show
+    ShowHeading => "Asset Details:", "アセット詳細:";
+    ShowAssetId => "Asset ID:       {}", "アセットID:     {}";
+    ShowStatus => "Status:         {}", "ステータス:     {}";
+    ShowDuration => "Duration:       {} ({}s)", "再生時間:       {}（{}秒）";
+    ShowAspectRatio => "Aspect Ratio:   {}", "アスペクト比:   {}";
+    ShowVideoQuality => "Video Quality:  {}", "画質:           {}";
+    ShowCreatedAt => "Created At:     {}", "作成日時:       {}";
+    ShowPlaybackHeading => "Playback Information:", "再生情報:";
+    ShowPlaybackId => "Playback ID #{}: {}", "再生ID #{}: {}";
+    ShowPolicy => "  Policy:       {}", "  ポリシー:     {}";
+    ShowNoPlaybackIds => "No playback IDs available", "再生IDがありません";
+    ShowTracksHeading => "Tracks:", "トラック:";
+    ShowTrack => "Track #{}: {} ", "トラック #{}: {} ";
+    ShowTrackDuration => "(duration: {}s)", "（再生時間: {}秒）";
+    ShowTrackStatus => "({}) ", "（{}）";
+    ShowRenditionsHeading => "Static Renditions:", "静的レンディション:";
+    ShowRendition => "Rendition #{}: {}", "レンディション #{}: {}";
+    ShowRenditionStatus => "  Status:       {}", "  ステータス:   {}";
+    ShowRenditionResolution => "  Resolution:   {}", "  解像度:       {}";
+    ShowRenditionType => "  Type:         {}", "  種類:         {}";
+    ShowRenditionFormat => "  Format:       {}", "  形式:         {}";
+
+    // upload
+    UploadCompleted => "Upload completed successfully!", "アップロードが完了しました！";
+    UploadHlsHeading => "HLS Streaming URL:", "HLSストリーミングURL:";
+    UploadMp4Heading => "MP4 Download URL:", "MP4ダウンロードURL:";
+    UploadThumbnailHeading => "Thumbnail URL:", "サムネイルURL:";
+    UploadTimings => "Time: validate {}s, create upload {}s, transfer {}s ({} Mbps), asset wait {}s, total {}s", "所要時間: 検証 {}秒、アップロード作成 {}秒、転送 {}秒（{} Mbps）、アセット待機 {}秒、合計 {}秒";
+    UploadDeletedOld => "Deleted to free capacity:", "容量確保のために削除したアセット:";
+    UploadDeletedAsset => "{} (title: {}, created: {})", "{}（タイトル: {}、作成日時: {}）";
+
+    // upload --dry-run
+    DryRunHeading => "Dry run: nothing was uploaded.", "ドライラン: アップロードは行っていません。";
+    EstimateFile => "File:             {} ({}, {})", "ファイル:           {}（{}, {}）";
+    EstimateUploadTime => "Upload time:      {}", "アップロード時間:   {}";
+    EstimateEncodedMinutes => "Encoded minutes:  {}", "エンコード時間:     {}";
+    EstimateMedia => "Media:            {}", "メディア:           {}";
+    EstimateNoThroughput => "Note: Upload time is estimated from your last upload; none has been measured yet.", "注意: アップロード時間は前回のアップロードから推定しますが、まだ計測されていません。";
+    EstimateNoDuration => "Note: Encoded minutes need the video duration; use --probe (or an MP4/MOV file).", "注意: エンコード時間の推定には再生時間が必要です。--probe を指定してください（MP4/MOVファイルは不要）。";
+
+    // delete
+    DeleteSucceeded => "✓ Asset deleted successfully!", "✓ アセットを削除しました";
+    RenameSucceeded => "✓ Asset renamed", "✓ アセットのタイトルを変更しました";
+    RenameOldTitle => "Old title: {}", "変更前: {}";
+    RenameNewTitle => "New title: {}", "変更後: {}";
+    RenameNoTitle => "(none)", "（なし）";
+    DeletePermanent => "The video and all its data have been permanently removed.", "動画とそのすべてのデータを完全に削除しました。";
+
+    // queue add / queue run
+    QueueAdded => "✓ Added to upload queue (#{})", "✓ アップロードキューに追加しました（#{}）";
+    QueuePending => "Pending uploads: {}", "未実行のアップロード: {} 件";
+    QueueRunHint => "Run 'vidyeet queue run' to upload queued files.", "'vidyeet queue run' でキューのファイルをアップロードします。";
+    QueueEmpty => "Upload queue is empty.", "アップロードキューは空です。";
+    QueueFailed => "✗ Failed (#{}): {}", "✗ 失敗（#{}）: {}";
+    QueueRemoved => "Removed from the queue.", "キューから除去しました。";
+    RetryEmpty => "No failed uploads to retry.", "再実行する失敗したアップロードはありません。";
+    RetryListHeading => "Failed uploads: {}", "失敗したアップロード: {} 件";
+    RetryEntry => "#{} {} (failed {} time(s))", "#{} {}（失敗 {} 回）";
+    RetryHint => "Run 'vidyeet retry --all' or 'vidyeet retry <id>' to upload them again.", "'vidyeet retry --all' または 'vidyeet retry <id>' で再アップロードします。";
+    RetryFailed => "✗ Failed again (#{}): {}", "✗ 再び失敗しました（#{}）: {}";
+    RetryRemaining => "{} failed upload(s) remain. Run 'vidyeet retry' to list them.", "失敗したアップロードが {} 件残っています。'vidyeet retry' で一覧を表示します。";
+    HistoryEmpty => "No command history yet.", "操作履歴はまだありません。";
+    HistoryHeading => "Recent commands: {} of {}", "最近のコマンド: {} 件（全 {} 件）";
+    HistoryRerunHint => "Run 'vidyeet history rerun <id>' to run a command again.", "'vidyeet history rerun <id>' でコマンドを再実行します。";
+    SignHeading => "Signed {} URL (expires {}):", "署名付き {} URL（有効期限: {}）:";
+    StoryboardHeading => "Storyboard of asset {} (playback ID: {})", "アセット {} のストーリーボード（再生ID: {}）";
+    StoryboardVttHeading => "WebVTT (preview positions):", "WebVTT（プレビューの位置）:";
+    StoryboardSpriteHeading => "Sprite image:", "スプライト画像:";
+    ServeListening => "Previewing asset {} (playback ID: {}). Press Ctrl+C to stop.", "アセット {} をプレビューしています（再生ID: {}）。Ctrl+C で停止します。";
+    ServeUrlHeading => "Preview page:", "プレビューページ:";
+    ServeExpires => "The signed playback URL expires at {}; restart serve after that.", "署名付きの再生URLは {} に期限切れになります。その後は serve を再起動してください。";
+    ServeOpenFailed => "Could not open a browser ({}); open the URL above manually", "ブラウザを開けませんでした（{}）。上のURLを手動で開いてください";
+    ServeStopped => "✓ Preview server stopped (page views: {})", "✓ プレビューサーバーを停止しました（ページの表示回数: {}）";
+    StoryboardSaved => "✓ Saved {} previews: {} and {} ({})", "✓ {} 個のプレビューを保存しました: {}・{}（{}）";
+    HistoryRerunning => "Re-running #{}: vidyeet {}", "#{} を再実行します: vidyeet {}";
+    RestrictionCreated => "✓ Playback restriction created: {}", "✓ 再生制限を作成しました: {}";
+    RestrictionDomains => "Allowed domains: {}", "許可するドメイン: {}";
+    RestrictionNoReferrerAllowed => "Requests without a referrer: allowed", "リファラーのないリクエスト: 許可";
+    RestrictionNoReferrerBlocked => "Requests without a referrer: blocked", "リファラーのないリクエスト: 拒否";
+    RestrictionAttachHint => "Run 'vidyeet restrictions attach <asset_id> {}' to add a playback ID that uses it.", "'vidyeet restrictions attach <asset_id> {}' で、この制限を使う再生IDを追加します。";
+    RestrictionsEmpty => "No playback restrictions.", "再生制限はありません。";
+    RestrictionsHeading => "Playback restrictions: {}", "再生制限: {} 件";
+    RestrictionDeleted => "✓ Playback restriction deleted: {}", "✓ 再生制限を削除しました: {}";
+    RestrictionAttached => "✓ Signed playback ID added: {}", "✓ 署名付き再生IDを追加しました: {}";
+    PlaybackRevoked => "✓ Playback ID {} revoked; its URLs no longer play", "✓ 再生ID {} を削除しました（このIDのURLは再生できなくなります）";
+    PlaybackReplaced => "New playback ID: {} ({})", "新しい再生ID: {}（{}）";
+    PlaybackRemaining => "Remaining playback IDs: {}", "残っている再生ID: {}";
+    LiveKeyReset => "✓ Stream key of live stream {} reset; the old key no longer works", "✓ ライブストリーム {} のストリームキーを再発行しました（古いキーは使えなくなります）";
+    LiveNewKey => "New stream key: {}", "新しいストリームキー: {}";
+    LiveKeyHint => "Run 'vidyeet live reset-key {} --plain' to print only the full key to stdout, then update your encoder.", "'vidyeet live reset-key {} --plain' でキー全体だけをstdoutへ出力できます。エンコーダーの設定を更新してください。";
+    RestrictionSignHint => "Run 'vidyeet sign {} --restriction {}' to print a URL that only plays on the allowed domains.", "'vidyeet sign {} --restriction {}' で、許可したドメインでのみ再生できるURLを発行します。";
+    QueueRemaining => "{} upload(s) remain queued and will be retried on the next 'vidyeet queue run'.", "{} 件のアップロードがキューに残っています。次回の 'vidyeet queue run' で再試行します。";
+
+    // デスクトップ通知
+    NotifyUploadSucceeded => "vidyeet: Upload completed", "vidyeet: アップロード完了";
+    NotifyUploadFailed => "vidyeet: Upload failed", "vidyeet: アップロード失敗";
+    NotifyUploadBody => "{} ({})", "{}（{}）";
+    NotifyBatchBody => "{} uploaded, {} failed ({})", "成功 {} 件、失敗 {} 件（{}）";
+    NotifyFailed => "Failed to show desktop notification: {}", "デスクトップ通知を表示できませんでした: {}";
+
+    // ping
+    PingHeader => "PING {} ({})", "PING {}（{}）";
+    PingReply => "seq={} status={} time={} ms", "seq={} status={} 時間={} ms";
+    PingError => "seq={} error: {}", "seq={} エラー: {}";
+    PingStatisticsHeader => "--- {} ping statistics ---", "--- {} の統計 ---";
+    PingSummary => "{} requests, {} succeeded, {} failed ({}% failed)", "リクエスト {} 件、成功 {} 件、失敗 {} 件（失敗率 {}%）";
+    PingLatency => "latency min/avg/max = {} ms", "レイテンシ 最小/平均/最大 = {} ms";
+
+    // doctor
+    DoctorHeader => "vidyeet doctor", "vidyeet doctor（診断）";
+    DoctorConfig => "Config file", "設定ファイル";
+    DoctorCredentials => "Credentials", "認証情報";
+    DoctorApiDns => "DNS (API)", "DNS（API）";
+    DoctorApiTls => "HTTPS (API)", "HTTPS（API）";
+    DoctorStreamDns => "DNS (stream)", "DNS（再生）";
+    DoctorStreamTls => "HTTPS (stream)", "HTTPS（再生）";
+    DoctorClock => "Clock", "時刻";
+    DoctorDisk => "Disk space", "空き容量";
+    DoctorPassed => "All checks passed.", "すべてのチェックに合格しました。";
+    DoctorFailed => "{} check(s) failed.", "{} 件のチェックが失敗しました。";
+
+    // export
+    ExportSucceeded => "✓ Exported {} asset(s) to {}", "✓ {} 件のアセットを {} に書き出しました";
+    ExportResumed => "Resumed from checkpoint ({} asset(s) already fetched)", "チェックポイントから再開しました（取得済み {} 件）";
+    // import
+    ImportCreated => "✓ Imported {} as {}", "✓ {} を {} として作成しました";
+    ImportFailed => "✗ Failed to import {}", "✗ {} を作成できませんでした";
+    ImportSummary => "Imported {} asset(s), {} failed", "作成: {} 件、失敗: {} 件";
+    ImportProcessingHint => "Mux processes the new assets in the background. Check their status with 'vidyeet list'.", "新しいアセットはMux側で処理されます。状態は 'vidyeet list' で確認できます。";
+    // sync
+    SyncPlanHeading => "Sync plan for {}", "{} の同期の計画";
+    SyncPlanSummary => "{} to upload, {} to delete, {} up to date", "アップロード {} 件、削除 {} 件、変更なし {} 件";
+    SyncDryRunHint => "Dry run: nothing was uploaded or deleted.", "ドライラン: アップロード・削除は行っていません。";
+    SyncDeleted => "✓ Deleted {}", "✓ {} を削除しました";
+    SyncDeleteFailed => "✗ Failed to delete {}", "✗ {} を削除できませんでした";
+    SyncSummary => "{} uploaded, {} deleted, {} up to date, {} failed", "アップロード {} 件、削除 {} 件、変更なし {} 件、失敗 {} 件";
+    // backup
+    BackupDownloaded => "✓ Downloaded: {}", "✓ ダウンロード完了: {}";
+    BackupSignedOnly => "⚠ Skipped {} asset(s) without a public playback ID: {}", "⚠ 公開の再生IDがないため {} 件のアセットを省略しました: {}";
+    BackupSummary => "{} downloaded ({}), {} up to date, {} failed → {}", "ダウンロード {} 件（{}）、変更なし {} 件、失敗 {} 件 → {}";
+    // gc
+    GcNothing => "Nothing to clean up.", "掃除の対象はありません。";
+    GcPlanHeading => "Cleanup targets", "掃除の対象";
+    GcPlanSummary => "{} errored asset(s) to delete, {} direct upload(s) to cancel", "削除するエラー状態のアセット {} 件、取り消すDirect Upload {} 件";
+    GcDryRunHint => "Dry run: nothing was deleted or cancelled.", "ドライラン: 削除・取り消しは行っていません。";
+    GcCancelled => "✓ Cancelled direct upload {}", "✓ Direct Upload {} を取り消しました";
+    GcFailed => "✗ Failed to clean up {}", "✗ {} を掃除できませんでした";
+    GcSummary => "{} asset(s) deleted, {} upload(s) cancelled, {} failed", "削除 {} 件、取り消し {} 件、失敗 {} 件";
+    // prune
+    PruneNothing => "Nothing to prune ({} asset(s), keeping {}).", "削除の対象はありません（アセット {} 件、保持数 {}）。";
+    PrunePlanHeading => "Assets to delete (keeping the newest {})", "削除するアセット（新しいものから {} 件を保持）";
+    PrunePlanSummary => "{} of {} asset(s) to delete", "{} 件を削除（全 {} 件）";
+    PruneDryRunHint => "Dry run: nothing was deleted.", "ドライラン: 削除は行っていません。";
+    PruneSummary => "{} deleted, {} failed (keeping the newest {})", "削除 {} 件、失敗 {} 件（新しいものから {} 件を保持）";
+
+    // whoami
+    StatsHeading => "Library stats", "ライブラリの統計";
+    StatsAssets => "Assets:         {} ({})", "アセット:       {} 件（{}）";
+    StatsQuality => "Quality:        {}", "品質:           {}";
+    StatsResolution => "Resolution:     {}", "解像度:         {}";
+    StatsDuration => "Total duration: {} (average {})", "再生時間:       合計 {}（平均 {}）";
+    StatsOldest => "Oldest:         {}", "最も古い:       {}";
+    StatsNewest => "Newest:         {}", "最も新しい:     {}";
+    StatsUploadsPerWeek => "Uploads per week (last {} weeks with uploads):", "週ごとのアップロード数（アップロードのあった直近 {} 週）:";
+    WhoamiProfile => "Profile:      {}", "プロファイル: {}";
+    WhoamiTokenId => "Token ID:     {}", "トークンID:   {}";
+    WhoamiNotLoggedIn => "Token ID:     (not logged in)", "トークンID:   （未ログイン）";
+    WhoamiApiEndpoint => "API endpoint: {}", "APIエンドポイント: {}";
+    WhoamiConfigPath => "Config file:  {}", "設定ファイル: {}";
+
+    // version
+    VersionCommit => "Commit:       {}", "コミット:     {}";
+    VersionBuildDate => "Build date:   {}", "ビルド日:     {}";
+    VersionTarget => "Target:       {}", "ターゲット:   {}";
+    VersionApiEndpoint => "API endpoint: {}", "APIエンドポイント: {}";
+    VersionTlsBackend => "TLS backend:  {}", "TLSバックエンド: {}";
+
+    // --copy
+    CopiedHlsUrl => "✓ Copied the HLS URL to the clipboard", "✓ HLS URLをクリップボードにコピーしました";
+    CopiedMp4Url => "✓ Copied the MP4 URL to the clipboard", "✓ MP4 URLをクリップボードにコピーしました";
+    CopiedThumbnailUrl => "✓ Copied the thumbnail URL to the clipboard", "✓ サムネイルURLをクリップボードにコピーしました";
+
+    // --output markdown|html
+    SnippetDuration => "Duration", "再生時間";
+
+    // --plain
+    PlainNoHlsUrl => "No HLS URL for {} (no public playback ID)", "{} のHLS URLがありません（公開再生IDなし）";
+
+    // 複数ファイルのアップロード
+    BatchFailed => "✗ Failed: {}", "✗ 失敗: {}";
+    BatchFailedAfter => "✗ Failed: {} (after {} attempt(s))", "✗ 失敗: {}（{} 回試行）";
+    BatchSummary => "{} uploaded, {} failed (concurrency: {})", "成功 {} 件、失敗 {} 件（同時実行数: {}）";
+    BatchDuration => "Finished in {}s", "所要時間: {} 秒";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_locale() {
+        assert_eq!(Locale::parse("ja"), Some(Locale::Ja));
+        assert_eq!(Locale::parse("ja_JP.UTF-8"), Some(Locale::Ja));
+        assert_eq!(Locale::parse("en-US"), Some(Locale::En));
+        assert_eq!(Locale::parse("C.UTF-8"), Some(Locale::En));
+        assert_eq!(Locale::parse("POSIX"), Some(Locale::En));
+        assert_eq!(Locale::parse("fr_FR.UTF-8"), None);
+        assert_eq!(Locale::parse(""), None);
+    }
+
+    #[test]
+    fn test_locale_from_env_precedence() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+
+        // LC_ALL が最優先、空の変数は無視
+        let vars = env(&[("LC_ALL", "ja_JP.UTF-8"), ("LANG", "en_US.UTF-8")]);
+        assert_eq!(Locale::from_env(vars), Some(Locale::Ja));
+        let vars = env(&[("LC_ALL", ""), ("LANG", "ja_JP.UTF-8")]);
+        assert_eq!(Locale::from_env(vars), Some(Locale::Ja));
+        assert_eq!(Locale::from_env(env(&[])), None);
+
+        // 設定値は環境変数より優先
+        assert_eq!(Locale::detect(Some("ja")), Locale::Ja);
+        assert_eq!(Locale::detect(Some("en")), Locale::En);
+    }
+
+    #[test]
+    fn test_catalogs_have_same_placeholders() {
+        for msg in Msg::ALL {
+            let en = msg.text(Locale::En);
+            let ja = msg.text(Locale::Ja);
+            assert_eq!(
+                en.matches("{}").count(),
+                ja.matches("{}").count(),
+                "placeholder mismatch: {:?}",
+                msg
+            );
+            assert_ne!(en, ja, "untranslated message: {:?}", msg);
+        }
+    }
+
+    #[test]
+    fn test_fill() {
+        let args: [&dyn Display; 2] = [&3, &"clips"];
+        assert_eq!(fill("{} file(s) in {}", &args), "3 file(s) in clips");
+        assert_eq!(fill("no args", &[]), "no args");
+        assert_eq!(fill("{} and {}", &args[..1]), "3 and ");
+    }
+
+    #[test]
+    fn test_ja_hints_cover_error_codes() {
+        for code in [
+            "FILE_NOT_FOUND",
+            "INVALID_FORMAT",
+            "FILE_TOO_LARGE",
+            "EMPTY_FILE",
+            "NOT_A_FILE",
+            "CONTENT_MISMATCH",
+            "UNSUPPORTED_MEDIA",
+            "DURATION_TOO_LONG",
+            "DUPLICATE_UPLOAD",
+            "INVALID_TAG",
+            "INVALID_LANGUAGE_CODE",
+            "PASSTHROUGH_IN_USE",
+            "PASSTHROUGH_TOO_LONG",
+            "ASSET_LIMIT_REACHED",
+            "CONFIG_DIR_NOT_FOUND",
+            "CONFIG_IO_ERROR",
+            "CONFIG_PARSE_ERROR",
+            "CONFIG_SERIALIZE_ERROR",
+            "CONFIG_INVALID",
+            "AUTH_MISSING",
+            "NETWORK_ERROR",
+            "AUTH_REJECTED",
+            "NOT_FOUND",
+            "RATE_LIMITED",
+            "API_ERROR",
+            "TIMEOUT",
+            "STORAGE_ERROR",
+            "HOOK_FAILED",
+            "TRANSCODE_FAILED",
+            "IO_ERROR",
+        ] {
+            assert!(ja_hint(code, None).is_some(), "missing hint: {}", code);
+        }
+        assert_ne!(
+            ja_hint("API_ERROR", Some(503)),
+            ja_hint("API_ERROR", Some(400))
+        );
+        assert!(ja_hint("UNKNOWN", None).is_none());
+    }
+}
@@ -2,9 +2,38 @@
 ///
 /// CLI引数やstdinからのユーザー入力を取得し、
 /// アプリケーション層で使用可能な形式に変換します。
-use crate::commands::login::LoginCredentials;
+use crate::presentation::spinner;
 use anyhow::{Context, Result, bail};
-use std::io::{self, Write};
+use serde_json::Value;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, mpsc};
+use vidyeet::api::pause;
+use vidyeet::commands::gc::GcPlan;
+use vidyeet::commands::login::LoginCredentials;
+use vidyeet::commands::prune::PrunePlan;
+use vidyeet::commands::result::{DeletedAsset, SyncRemoteAsset};
+use vidyeet::config::UserConfig;
+use vidyeet::domain::formatter;
+
+/// 認証情報ファイルでToken IDとして受け付けるキー（大文字・小文字は区別しない）
+const TOKEN_ID_KEYS: &[&str] = &[
+    "id",
+    "token_id",
+    "tokenid",
+    "access_token_id",
+    "mux_token_id",
+];
+
+/// 認証情報ファイルでToken Secretとして受け付けるキー（大文字・小文字は区別しない）
+const TOKEN_SECRET_KEYS: &[&str] = &[
+    "secret",
+    "token_secret",
+    "tokensecret",
+    "access_token_secret",
+    "mux_token_secret",
+];
 
 /// 対話的に認証情報を取得
 ///
@@ -84,6 +113,103 @@ pub fn read_credentials_from_stdin() -> Result<LoginCredentials> {
     })
 }
 
+/// `--token-id` / `--token-secret` で指定された認証情報を検証する
+pub fn credentials_from_flags(token_id: &str, token_secret: &str) -> Result<LoginCredentials> {
+    validated_credentials(token_id, token_secret, "--token-id / --token-secret")
+}
+
+/// Muxダッシュボードからダウンロードした認証情報ファイルを読み込む
+///
+/// 以下の形式に対応します。
+/// - JSON: `{"id": "...", "secret": "..."}`（`token_id` / `token_secret` なども可、`data` 内も可）
+/// - .env: `MUX_TOKEN_ID=...` と `MUX_TOKEN_SECRET=...` の行
+pub fn read_credentials_from_file(path: &Path) -> Result<LoginCredentials> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read token file: {}", path.display()))?;
+    parse_credentials_file(&content)
+        .with_context(|| format!("Invalid token file: {}", path.display()))
+}
+
+/// 認証情報ファイルの内容を解析する
+fn parse_credentials_file(content: &str) -> Result<LoginCredentials> {
+    let (token_id, token_secret) = match serde_json::from_str::<Value>(content) {
+        Ok(json) => {
+            let object = json
+                .get("data")
+                .filter(|data| data.is_object())
+                .unwrap_or(&json);
+            (
+                json_field(object, TOKEN_ID_KEYS),
+                json_field(object, TOKEN_SECRET_KEYS),
+            )
+        }
+        Err(_) => (
+            env_field(content, TOKEN_ID_KEYS),
+            env_field(content, TOKEN_SECRET_KEYS),
+        ),
+    };
+
+    let token_id =
+        token_id.context("Token ID not found (expected a key such as \"id\" or MUX_TOKEN_ID)")?;
+    let token_secret = token_secret.context(
+        "Token Secret not found (expected a key such as \"secret\" or MUX_TOKEN_SECRET)",
+    )?;
+    validated_credentials(&token_id, &token_secret, "token file")
+}
+
+/// JSONオブジェクトから、いずれかのキーの文字列値を取得する
+fn json_field(object: &Value, keys: &[&str]) -> Option<String> {
+    object.as_object()?.iter().find_map(|(key, value)| {
+        keys.contains(&key.to_ascii_lowercase().as_str())
+            .then(|| value.as_str().map(str::to_string))
+            .flatten()
+    })
+}
+
+/// .env形式（`KEY=value`）から、いずれかのキーの値を取得する
+fn env_field(content: &str, keys: &[&str]) -> Option<String> {
+    content.lines().find_map(|line| {
+        let line = line.trim();
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line.split_once('=')?;
+        keys.contains(&key.trim().to_ascii_lowercase().as_str())
+            .then(|| {
+                value
+                    .trim()
+                    .trim_matches(|c| c == '"' || c == '\'')
+                    .to_string()
+            })
+    })
+}
+
+/// 認証情報の形式を検証する（空・空白を含む値を拒否）
+fn validated_credentials(
+    token_id: &str,
+    token_secret: &str,
+    source: &str,
+) -> Result<LoginCredentials> {
+    let token_id = token_id.trim();
+    let token_secret = token_secret.trim();
+
+    if token_id.is_empty() {
+        bail!("Token ID cannot be empty ({})", source);
+    }
+    if token_secret.is_empty() {
+        bail!("Token Secret cannot be empty ({})", source);
+    }
+    if token_id.contains(char::is_whitespace) || token_secret.contains(char::is_whitespace) {
+        bail!(
+            "Token ID and Token Secret must not contain whitespace ({})",
+            source
+        );
+    }
+
+    Ok(LoginCredentials {
+        token_id: token_id.to_string(),
+        token_secret: token_secret.to_string(),
+    })
+}
+
 /// 削除確認プロンプトを表示し、ユーザーの確認を得る
 ///
 /// # 引数
@@ -102,9 +228,7 @@ pub fn confirm_delete(asset_id: &str) -> Result<bool> {
     io::stdout().flush()?;
 
     let mut input = String::new();
-    io::stdin()
-        .read_line(&mut input)
-        .context("Failed to read confirmation from input")?;
+    read_line(&mut input).context("Failed to read confirmation from input")?;
 
     let input = input.trim();
 
@@ -116,8 +240,361 @@ pub fn confirm_delete(asset_id: &str) -> Result<bool> {
     }
 }
 
+/// 再生IDの削除（`playback revoke`）についてユーザーの確認を得る
+///
+/// # 引数
+/// * `asset_id` - 対象のアセットID
+/// * `playback_id` - 削除する再生ID
+/// * `replace` - 同じポリシーの再生IDを追加するか（`--replace`）
+///
+/// # 戻り値
+/// ユーザーが "yes" と入力した場合はtrue、それ以外はfalse
+pub fn confirm_revoke(asset_id: &str, playback_id: &str, replace: bool) -> Result<bool> {
+    eprintln!();
+    eprintln!("⚠️  WARNING: You are about to delete the following playback ID:");
+    eprintln!("   Asset ID:    {}", asset_id);
+    eprintln!("   Playback ID: {}", playback_id);
+    eprintln!();
+    eprintln!(
+        "This action cannot be undone. URLs and embeds using this playback ID will stop working."
+    );
+    if replace {
+        eprintln!("A new playback ID with the same policy will be added first.");
+    }
+    eprintln!();
+    eprint!("Type 'yes' to confirm: ");
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    read_line(&mut input).context("Failed to read confirmation from input")?;
+
+    if input.trim().eq_ignore_ascii_case("yes") {
+        Ok(true)
+    } else {
+        eprintln!("Revoke cancelled.");
+        Ok(false)
+    }
+}
+
+/// 同期で削除するアセットの一覧を表示し、ユーザーの確認を得る
+///
+/// # 引数
+/// * `assets` - 削除対象のアセット
+///
+/// # 戻り値
+/// ユーザーが削除を承認した場合はOk(true)、キャンセルした場合はOk(false)
+pub fn confirm_sync_delete(assets: &[SyncRemoteAsset]) -> Result<bool> {
+    eprintln!();
+    eprintln!(
+        "⚠️  WARNING: The following {} asset(s) have no local file and will be deleted:",
+        assets.len()
+    );
+    for asset in assets {
+        match &asset.title {
+            Some(title) => eprintln!("   {} ({})", asset.asset_id, title),
+            None => eprintln!("   {}", asset.asset_id),
+        }
+    }
+    eprintln!();
+    eprintln!("This action cannot be undone. All video data will be permanently deleted.");
+    eprintln!();
+    eprint!("Type 'yes' to confirm deletion: ");
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    read_line(&mut input).context("Failed to read confirmation from input")?;
+
+    if input.trim().eq_ignore_ascii_case("yes") {
+        Ok(true)
+    } else {
+        eprintln!("Sync cancelled.");
+        Ok(false)
+    }
+}
+
+/// アセット数の上限に達したときの確認プロンプトを表示（`capacity_policy = "ask"`）
+///
+/// アップロードの途中（Direct Uploadの作成時）に表示するため、表示の間は一時停止操作の受付を止め、
+/// 入力された行をプロンプトの回答として受け取ります。
+///
+/// # 引数
+/// * `oldest` - 削除される最も古いアセット
+///
+/// # 戻り値
+/// 表示したアセットの削除をユーザーが許可した場合はtrue
+pub fn confirm_capacity_delete(oldest: &DeletedAsset) -> Result<bool> {
+    let _prompting = Prompting::start();
+    let user_config = UserConfig::load().unwrap_or_default();
+    eprintln!();
+    eprintln!("⚠️  WARNING: The asset limit of your plan has been reached.");
+    eprintln!("To continue, the following oldest asset will be permanently deleted:");
+    eprintln!("   Asset ID: {}", oldest.asset_id);
+    eprintln!("   Title:    {}", oldest.title.as_deref().unwrap_or("-"));
+    eprintln!(
+        "   Created:  {}",
+        formatter::format_timestamp(&oldest.created_at, &user_config)
+    );
+    eprintln!();
+    eprint!("Type 'yes' to delete the oldest asset and retry the upload: ");
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    read_line(&mut input).context("Failed to read confirmation from input")?;
+
+    Ok(input.trim().eq_ignore_ascii_case("yes"))
+}
+
+/// 掃除の確認プロンプトを表示（対象を一覧表示）
+///
+/// # 戻り値
+/// ユーザーが確認した場合はtrue、キャンセルした場合はfalse
+pub fn confirm_gc(plan: &GcPlan) -> Result<bool> {
+    eprintln!();
+    if !plan.errored_assets.is_empty() {
+        eprintln!(
+            "⚠️  WARNING: The following {} errored asset(s) will be deleted:",
+            plan.errored_assets.len()
+        );
+        for asset in &plan.errored_assets {
+            match &asset.title {
+                Some(title) => eprintln!("   {} ({})", asset.asset_id, title),
+                None => eprintln!("   {}", asset.asset_id),
+            }
+        }
+    }
+    if !plan.stale_uploads.is_empty() {
+        eprintln!(
+            "The following {} waiting direct upload(s) will be cancelled:",
+            plan.stale_uploads.len()
+        );
+        for upload_id in &plan.stale_uploads {
+            eprintln!("   {}", upload_id);
+        }
+    }
+    eprintln!();
+    eprintln!("This action cannot be undone.");
+    eprintln!();
+    eprint!("Type 'yes' to confirm: ");
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    read_line(&mut input).context("Failed to read confirmation from input")?;
+
+    if input.trim().eq_ignore_ascii_case("yes") {
+        Ok(true)
+    } else {
+        eprintln!("Cleanup cancelled.");
+        Ok(false)
+    }
+}
+
+/// 保持数による削除の確認プロンプトを表示（削除するアセットを一覧表示）
+///
+/// # 戻り値
+/// ユーザーが確認した場合はtrue、キャンセルした場合はfalse
+pub fn confirm_prune(plan: &PrunePlan) -> Result<bool> {
+    eprintln!();
+    eprintln!(
+        "⚠️  WARNING: Keeping the newest {} of {} asset(s). The following {} asset(s) will be deleted:",
+        plan.keep,
+        plan.total_assets,
+        plan.to_delete.len()
+    );
+    for asset in &plan.to_delete {
+        match &asset.title {
+            Some(title) => eprintln!("   {} ({})", asset.asset_id, title),
+            None => eprintln!("   {}", asset.asset_id),
+        }
+    }
+    eprintln!();
+    eprintln!("This action cannot be undone. All video data will be permanently deleted.");
+    eprintln!();
+    eprint!("Type 'yes' to confirm deletion: ");
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    read_line(&mut input).context("Failed to read confirmation from input")?;
+
+    if input.trim().eq_ignore_ascii_case("yes") {
+        Ok(true)
+    } else {
+        eprintln!("Prune cancelled.");
+        Ok(false)
+    }
+}
+
+/// 標準入力を読み取るスレッド（起動後、転送された行を受け取り終えるまで）
+static LINE_READER: Mutex<Option<LineReader>> = Mutex::new(None);
+
+/// 標準入力を読み取るスレッドと、確認プロンプトへ転送する行の受信側
+struct LineReader {
+    lines: mpsc::Receiver<String>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+/// 一時停止操作（`p` / `r`）を受け付けているか
+static PAUSE_CONTROLS_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// アップロード中に確認プロンプトを表示しているか
+static PROMPTING: AtomicBool = AtomicBool::new(false);
+
+/// アップロード中に確認プロンプトを表示しているか
+///
+/// 表示の間は進捗の表示（スピナーの再描画・停滞の警告）を控えます。
+pub fn is_prompting() -> bool {
+    PROMPTING.load(Ordering::Relaxed)
+}
+
+/// アップロード中の確認プロンプトの表示（破棄時に終了）
+struct Prompting;
+
+impl Prompting {
+    fn start() -> Self {
+        PROMPTING.store(true, Ordering::Relaxed);
+        Self
+    }
+}
+
+impl Drop for Prompting {
+    fn drop(&mut self) {
+        PROMPTING.store(false, Ordering::Relaxed);
+    }
+}
+
+/// アップロード中の一時停止操作（`p` + Enterで一時停止、`r` + Enterで再開）の受付
+///
+/// 受付中に入力された行は操作としてのみ扱います（上限到達時の確認プロンプトの表示中を除く）。
+/// 破棄時に受付を終了し、一時停止中であれば再開します。
+pub struct PauseControls {
+    active: bool,
+}
+
+impl PauseControls {
+    /// 受付を開始する（`interactive` がtrueで、stdinとstderrがともに端末の場合のみ）
+    ///
+    /// # 引数
+    /// * `interactive` - 人間向け出力でチャンクを転送する場合はtrue
+    pub fn start(interactive: bool) -> Self {
+        let active = interactive && io::stdin().is_terminal() && spinner::stderr_is_terminal();
+        if active {
+            ensure_line_reader();
+            PAUSE_CONTROLS_ACTIVE.store(true, Ordering::Relaxed);
+            eprintln!("Press 'p' + Enter to pause the upload, 'r' + Enter to resume.");
+        }
+        Self { active }
+    }
+}
+
+impl Drop for PauseControls {
+    fn drop(&mut self) {
+        if self.active {
+            PAUSE_CONTROLS_ACTIVE.store(false, Ordering::Relaxed);
+            pause::resume();
+        }
+    }
+}
+
+/// 標準入力を1行ずつ読み取るスレッドを起動する（起動中であれば何もしない）
+///
+/// 読み取り中の標準入力は他から読めないため、スレッドが読み取った行は
+/// 確認プロンプトへ転送します（[`read_line`]）。
+fn ensure_line_reader() {
+    let mut reader = LINE_READER.lock().unwrap_or_else(|e| e.into_inner());
+    // 前回のスレッドが終了している場合のみ起動し直す（受付の間に入力された未使用の行は破棄する）
+    if reader
+        .as_ref()
+        .is_some_and(|reader| !reader.thread.is_finished())
+    {
+        return;
+    }
+
+    let (sender, lines) = mpsc::channel();
+    let thread = std::thread::spawn(move || {
+        loop {
+            let mut line = String::new();
+            match io::stdin().read_line(&mut line) {
+                // EOFまたは読み取りエラー（送信側の破棄でプロンプトにもEOFを伝える）
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+            if PAUSE_CONTROLS_ACTIVE.load(Ordering::Relaxed) && !is_prompting() {
+                apply_pause_command(&line);
+                continue;
+            }
+            if sender.send(line).is_err() {
+                break;
+            }
+            // 受付の終了後に読み取った行は確認プロンプトへ渡して終了し、以降は標準入力から直接読ませる
+            if !PAUSE_CONTROLS_ACTIVE.load(Ordering::Relaxed) {
+                break;
+            }
+        }
+    });
+    *reader = Some(LineReader { lines, thread });
+}
+
+/// 入力された行を一時停止操作として適用する（`p` / `r` 以外は無視）
+fn apply_pause_command(line: &str) {
+    match line.trim() {
+        command if command.eq_ignore_ascii_case("p") => {
+            pause::pause();
+        }
+        command if command.eq_ignore_ascii_case("r") => {
+            pause::resume();
+        }
+        _ => {}
+    }
+}
+
+/// 確認プロンプトの回答を標準入力から1行読み取る
+///
+/// 一時停止操作の受付で起動した読み取りスレッドがある場合は、そのスレッドから転送された行を受け取ります。
+/// スレッドが終了し、転送された行もなくなった後は標準入力から直接読み取ります。
+fn read_line(input: &mut String) -> io::Result<usize> {
+    let mut reader = LINE_READER.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(LineReader { lines, .. }) = reader.as_ref() {
+        if let Ok(line) = lines.recv() {
+            input.push_str(&line);
+            return Ok(line.len());
+        }
+        *reader = None;
+    }
+    io::stdin().read_line(input)
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_credentials_file_json() {
+        let credentials =
+            parse_credentials_file(r#"{"id": "abc123", "secret": "s3cr3t", "name": "ci"}"#)
+                .unwrap();
+        assert_eq!(credentials.token_id, "abc123");
+        assert_eq!(credentials.token_secret, "s3cr3t");
+
+        let credentials =
+            parse_credentials_file(r#"{"data": {"Token_ID": "abc123", "token_secret": "s3cr3t"}}"#)
+                .unwrap();
+        assert_eq!(credentials.token_id, "abc123");
+
+        assert!(parse_credentials_file(r#"{"id": "abc123"}"#).is_err());
+        assert!(parse_credentials_file(r#"{"id": "abc 123", "secret": "s"}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_credentials_file_env() {
+        let credentials = parse_credentials_file(
+            "# Mux access token\nMUX_TOKEN_ID=abc123\nexport MUX_TOKEN_SECRET=\"s3cr3t\"\n",
+        )
+        .unwrap();
+        assert_eq!(credentials.token_id, "abc123");
+        assert_eq!(credentials.token_secret, "s3cr3t");
+
+        assert!(parse_credentials_file("MUX_TOKEN_ID=abc123\n").is_err());
+    }
+
     #[test]
     fn test_empty_token_validation() {
         // 空のトークンは検証でエラーとなることを確認
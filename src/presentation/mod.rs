@@ -5,9 +5,31 @@
 /// ドメイン層に依存しますが、その逆はありません。
 ///
 /// # モジュール
+/// - `browser`: 既定のブラウザでURLを開く（`serve` のプレビューページ）
+/// - `clipboard`: URLのクリップボードへのコピー（`upload` / `show` の `--copy`）
+/// - `i18n`: 人間向け出力の多言語対応（英語・日本語）
 /// - `input`: ユーザー入力処理
+/// - `multi_progress`: 複数ファイルの並行アップロードの進捗バー表示（TTY時のみ）
+/// - `notify`: 長時間のアップロード完了時のデスクトップ通知
 /// - `output`: コマンド結果の出力（人間向け・機械向け）
 /// - `progress`: アップロード進捗のDTO変換
+/// - `query`: 機械可読出力からの値の抽出（`--query`、jq互換の式）
+/// - `schema`: 機械可読出力のJSON Schema
+/// - `snippet`: 共有用スニペットの出力（`upload` / `show` の `--output markdown|html`）
+/// - `spinner`: 待機中フェーズのスピナー表示（TTY時のみ）
+/// - `style`: 人間向け出力の色付け（TTY時のみ、`--no-color` / `NO_COLOR` で無効）
+/// - `units`: バイト数・転送速度の表示（`--bytes` で換算せずに表示）
+pub mod browser;
+pub mod clipboard;
+pub mod i18n;
 pub mod input;
+pub mod multi_progress;
+pub mod notify;
 pub mod output;
 pub mod progress;
+pub mod query;
+pub mod schema;
+pub mod snippet;
+pub mod spinner;
+pub mod style;
+pub mod units;
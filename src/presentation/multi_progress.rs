@@ -0,0 +1,193 @@
+/// プレゼンテーション層: 複数ファイルの進捗バー表示
+///
+/// 並行して進む複数ファイルのアップロードを、端末上にファイルごとの行（進捗バー）として
+/// まとめて表示し、更新のたびに行のまとまりを描き直します。
+/// 完了や警告などの確定した表示は、進捗バーの上に通常の行として出力します。
+/// stderrが端末でない場合は使用せず、呼び出し側で「[番号/総数]」付きのテキスト行にフォールバックします。
+use std::io::{self, Write};
+
+/// 進捗バーの幅（文字数）
+const BAR_WIDTH: usize = 20;
+
+/// ラベル（ファイル名）の表示幅（文字数、超える場合は末尾を省略）
+const LABEL_WIDTH: usize = 24;
+
+/// 描画済みの行のまとまりを消去する（`n` 行上の行頭へ戻り、以降を消去）
+fn clear_lines(n: usize) -> String {
+    format!("\x1b[{}A\r\x1b[J", n)
+}
+
+/// 1ファイル分の行
+#[derive(Debug, Clone, PartialEq)]
+struct ProgressRow {
+    /// ジョブID（ファイル番号）
+    job_id: usize,
+    /// ラベル（ファイル名）
+    label: String,
+    /// 状態の説明
+    status: String,
+    /// 進捗率（0.0〜100.0、転送中のみ）
+    percent: Option<f64>,
+}
+
+/// 複数ファイルの進捗バー
+///
+/// 行はジョブIDの順に並べます。完了したファイルの行は [`MultiProgress::remove`] で取り除きます。
+#[derive(Debug, Default)]
+pub struct MultiProgress {
+    /// 表示中の行
+    rows: Vec<ProgressRow>,
+    /// 前回描画した行数（描き直す前に消去する行数）
+    drawn: usize,
+}
+
+impl MultiProgress {
+    /// 空の進捗バーを作成する
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ファイルの行を追加・更新して描き直す
+    ///
+    /// # 引数
+    /// * `job_id` - ジョブID（ファイル番号）
+    /// * `label` - ラベル（ファイル名）
+    /// * `status` - 状態の説明
+    /// * `percent` - 進捗率（転送中以外はNone）
+    pub fn update(&mut self, job_id: usize, label: &str, status: String, percent: Option<f64>) {
+        let row = ProgressRow {
+            job_id,
+            label: label.to_string(),
+            status,
+            percent,
+        };
+        match self.rows.binary_search_by_key(&job_id, |row| row.job_id) {
+            Ok(index) => self.rows[index] = row,
+            Err(index) => self.rows.insert(index, row),
+        }
+        self.redraw();
+    }
+
+    /// ファイルの行を取り除く（完了時）
+    pub fn remove(&mut self, job_id: usize) {
+        self.rows.retain(|row| row.job_id != job_id);
+        self.redraw();
+    }
+
+    /// 進捗バーの上に確定した行を出力する
+    pub fn println(&mut self, line: &str) {
+        self.clear();
+        eprintln!("{}", line);
+        self.redraw();
+    }
+
+    /// 進捗バーを消去して終了する
+    pub fn finish(mut self) {
+        self.clear();
+        let _ = io::stderr().flush();
+    }
+
+    /// 描画済みの行を消去する
+    fn clear(&mut self) {
+        if self.drawn > 0 {
+            eprint!("{}", clear_lines(self.drawn));
+            self.drawn = 0;
+        }
+    }
+
+    /// すべての行を描き直す
+    fn redraw(&mut self) {
+        self.clear();
+        for row in &self.rows {
+            eprintln!("{}", format_row(row));
+        }
+        self.drawn = self.rows.len();
+        let _ = io::stderr().flush();
+    }
+}
+
+/// 1行分の表示を生成（制御文字を含まない）
+///
+/// 例: "clip.mp4                 [########------------]  40.0% 64.00 MB / 160.00 MB"
+fn format_row(row: &ProgressRow) -> String {
+    let bar = match row.percent {
+        Some(percent) => {
+            let filled = ((percent / 100.0) * BAR_WIDTH as f64).round() as usize;
+            let filled = filled.min(BAR_WIDTH);
+            format!(
+                "[{}{}] {:>5.1}%",
+                "#".repeat(filled),
+                "-".repeat(BAR_WIDTH - filled),
+                percent
+            )
+        }
+        // 進捗率がないフェーズはバーの幅だけ空けて揃える
+        None => " ".repeat(BAR_WIDTH + 9),
+    };
+    format!("{} {} {}", format_label(&row.label), bar, row.status)
+}
+
+/// ラベルを表示幅に揃える（長い場合は末尾を「…」で省略）
+fn format_label(label: &str) -> String {
+    if label.chars().count() > LABEL_WIDTH {
+        let truncated: String = label.chars().take(LABEL_WIDTH - 1).collect();
+        format!("{}…", truncated)
+    } else {
+        format!("{:<width$}", label, width = LABEL_WIDTH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(label: &str, percent: Option<f64>) -> ProgressRow {
+        ProgressRow {
+            job_id: 1,
+            label: label.to_string(),
+            status: "uploading".to_string(),
+            percent,
+        }
+    }
+
+    #[test]
+    fn test_format_row_with_bar() {
+        let line = format_row(&row("clip.mp4", Some(40.0)));
+        assert!(line.starts_with("clip.mp4 "));
+        assert!(line.contains("[########------------]  40.0% uploading"));
+    }
+
+    #[test]
+    fn test_format_row_aligns_without_bar() {
+        let with_bar = format_row(&row("clip.mp4", Some(0.0)));
+        let without_bar = format_row(&row("clip.mp4", None));
+        assert_eq!(
+            with_bar.find("uploading").unwrap(),
+            without_bar.find("uploading").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_format_label_truncates_long_names() {
+        let label = format_label("a_very_long_file_name_for_upload.mp4");
+        assert_eq!(label.chars().count(), LABEL_WIDTH);
+        assert!(label.ends_with('…'));
+        assert_eq!(format_label("a.mp4").len(), LABEL_WIDTH);
+    }
+
+    #[test]
+    fn test_rows_are_ordered_by_job_id() {
+        let mut progress = MultiProgress::new();
+        progress.update(3, "c.mp4", "validating".to_string(), None);
+        progress.update(1, "a.mp4", "validating".to_string(), None);
+        progress.update(3, "c.mp4", "uploading".to_string(), Some(10.0));
+
+        let ids: Vec<usize> = progress.rows.iter().map(|row| row.job_id).collect();
+        assert_eq!(ids, [1, 3]);
+        assert_eq!(progress.rows[1].status, "uploading");
+
+        progress.remove(1);
+        assert_eq!(progress.rows.len(), 1);
+        progress.finish();
+    }
+}
@@ -0,0 +1,149 @@
+/// プレゼンテーション層: デスクトップ通知
+///
+/// config.toml の `notify = true` のとき、`notify_after_secs` 以上かかった
+/// アップロードの完了・失敗をOSのデスクトップ通知（notify-rust）で知らせます。
+/// アップロードを開始して別のウィンドウで作業している場合向けです。
+/// `desktop-notify` フィーチャーなしでビルドした場合は通知せず、警告のみ表示します。
+use crate::presentation::i18n::{Msg, t, tf};
+use crate::presentation::style;
+use anyhow::Result;
+use std::path::Path;
+use std::time::Duration;
+use vidyeet::commands::result::{BatchDetails, BatchResult, CommandResult};
+use vidyeet::config::user::UserConfig;
+
+/// 通知のアプリケーション名
+const APP_NAME: &str = "vidyeet";
+
+/// アップロードの完了・失敗を通知する（設定で有効かつしきい値以上かかった場合のみ）
+///
+/// 通知の失敗は警告にとどめ、アップロード結果には影響させない。
+pub fn notify_upload_finished(outcome: &Result<CommandResult>, elapsed: Duration) {
+    let Ok(config) = UserConfig::load() else {
+        return;
+    };
+    if !config.notify || elapsed.as_secs() < config.notify_after_secs {
+        return;
+    }
+
+    if let Some((summary, body)) = upload_message(outcome, elapsed)
+        && let Err(e) = show(&summary, &body)
+    {
+        let message = tf(Msg::NotifyFailed, &[&e]);
+        eprintln!("{}", style::warning(&tf(Msg::Warning, &[&message])));
+    }
+}
+
+/// 通知の見出しと本文（アップロードしていない `--dry-run` はNone）
+fn upload_message(outcome: &Result<CommandResult>, elapsed: Duration) -> Option<(String, String)> {
+    let elapsed = format_elapsed(elapsed);
+    let message = match outcome {
+        Ok(CommandResult::Upload(r)) => (
+            t(Msg::NotifyUploadSucceeded).to_string(),
+            tf(Msg::NotifyUploadBody, &[&file_name(&r.file_path), &elapsed]),
+        ),
+        Ok(CommandResult::Batch(BatchResult {
+            details: BatchDetails::Upload(r),
+            ..
+        })) => {
+            let summary = if r.failed.is_empty() {
+                Msg::NotifyUploadSucceeded
+            } else {
+                Msg::NotifyUploadFailed
+            };
+            (
+                t(summary).to_string(),
+                tf(
+                    Msg::NotifyBatchBody,
+                    &[&r.uploaded.len(), &r.failed.len(), &elapsed],
+                ),
+            )
+        }
+        Ok(_) => return None,
+        Err(e) => (
+            t(Msg::NotifyUploadFailed).to_string(),
+            e.root_cause().to_string(),
+        ),
+    };
+    Some(message)
+}
+
+/// パスからファイル名を取り出す（取り出せない場合はパスのまま）
+fn file_name(path: &str) -> &str {
+    Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(path)
+}
+
+/// 経過時間を「1m 05s」「42s」の形式にする
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs >= 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// デスクトップ通知を表示する
+#[cfg(feature = "desktop-notify")]
+fn show(summary: &str, body: &str) -> Result<(), String> {
+    notify_rust::Notification::new()
+        .appname(APP_NAME)
+        .summary(summary)
+        .body(body)
+        .show()
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// デスクトップ通知を表示する（`desktop-notify` フィーチャーなしのビルド）
+#[cfg(not(feature = "desktop-notify"))]
+fn show(_summary: &str, _body: &str) -> Result<(), String> {
+    Err(format!(
+        "{} was built without the 'desktop-notify' feature",
+        APP_NAME
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vidyeet::commands::result::{BatchUploadFailure, BatchUploadResult};
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_secs(42)), "42s");
+        assert_eq!(format_elapsed(Duration::from_secs(65)), "1m 05s");
+        assert_eq!(format_elapsed(Duration::from_secs(3600)), "60m 00s");
+    }
+
+    #[test]
+    fn test_upload_message() {
+        let elapsed = Duration::from_secs(90);
+
+        let outcome = Ok(CommandResult::Batch(BatchResult::new(
+            BatchDetails::Upload(BatchUploadResult {
+                uploaded: Vec::new(),
+                failed: vec![BatchUploadFailure {
+                    file_path: "clips/a.mp4".to_string(),
+                    error: "network error".to_string(),
+                    attempts: 3,
+                }],
+                concurrency: 3,
+            }),
+            Duration::ZERO,
+        )));
+        let (summary, body) = upload_message(&outcome, elapsed).unwrap();
+        assert_eq!(summary, t(Msg::NotifyUploadFailed));
+        assert!(body.contains("1m 30s"));
+
+        let outcome = Err(anyhow::anyhow!("connection reset").context("Upload command failed"));
+        let (_, body) = upload_message(&outcome, elapsed).unwrap();
+        assert_eq!(body, "connection reset");
+
+        assert!(upload_message(&Ok(CommandResult::Help), elapsed).is_none());
+        assert_eq!(file_name("clips/a.mp4"), "a.mp4");
+    }
+}
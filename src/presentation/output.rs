@@ -1,37 +1,286 @@
+use crate::presentation::i18n::{Locale, Msg, locale, t, tf};
+use crate::presentation::progress;
+use crate::presentation::query;
+use crate::presentation::schema;
+use crate::presentation::style;
+use crate::presentation::units;
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
 /// プレゼンテーション層: コマンド結果の出力
 ///
 /// コマンド実行結果をユーザー向け（人間可読）または
 /// 機械向け（JSON）形式で出力する責務を担います。
 /// CLI使用方法の表示もこのモジュールが担当します。
-use crate::commands::result::{CommandResult, Mp4Status};
-use anyhow::Result;
+use vidyeet::api::types::{PlaybackRestriction, RenditionStatus, Track};
+use vidyeet::commands::playback::SIGNED_POLICY;
+use vidyeet::commands::result::{
+    BatchDetails, BatchResult, CaptionAction, CheckStatus, CommandResult, CopiedUrl, CopyTarget,
+    FinalMedia, ListResult, ListSummary, Mp4Status, RenditionAction, ServeResult, StatsAsset,
+    SyncAction, TagAction, UploadResult, VideoInfo,
+};
+use vidyeet::config::user::CapacityPolicy;
+use vidyeet::config::{APP_CONFIG, BITS_PER_MEGABIT, UserConfig};
+use vidyeet::domain::formatter::{self, Elapsed};
+use vidyeet::domain::warning::Warning;
+use vidyeet::media::probe::MediaProbe;
 
-/// ヘルプテキスト（単一の情報源）
+/// 作成日時を相対時刻のみで表示するか（`list` / `show` の `--relative-time`）
+static RELATIVE_TIME_ONLY: OnceLock<bool> = OnceLock::new();
+
+/// 作成日時を相対時刻のみで表示する（最初の呼び出しのみ有効）
+pub fn init_relative_time(only: bool) {
+    let _ = RELATIVE_TIME_ONLY.set(only);
+}
+
+fn relative_time_only() -> bool {
+    RELATIVE_TIME_ONLY.get().copied().unwrap_or(false)
+}
+
+/// 機械可読出力の再生時間にISO 8601の期間を併記するか（`--iso-duration`）
+static ISO_DURATION: OnceLock<bool> = OnceLock::new();
+
+/// 機械可読出力の再生時間にISO 8601の期間を併記する（最初の呼び出しのみ有効）
+pub fn init_iso_duration(enabled: bool) {
+    let _ = ISO_DURATION.set(enabled);
+}
+
+fn iso_duration() -> bool {
+    ISO_DURATION.get().copied().unwrap_or(false)
+}
+
+/// ヘルプテキスト（英語。日本語は `HELP_TEXT_JA`）
 const HELP_TEXT: &str = "vidyeet-CLI
 Upload videos to Mux Video easily from the command line
 
 Usage:
-  vidyeet [--machine] <command> [args...]
+  vidyeet [--machine] [--query <expr>] [--debug-http[=body]] <command> [args...]
 
-Global Flags:
+Global Flags (flags may appear anywhere, before or after the command):
   --machine        - Output machine-readable JSON to stdout (for scripting)
                      Works for both success and error cases
+  --query <expr>   - Print only the values a jq-style expression selects from
+                     the --machine result, one per line (implies --machine)
+                     e.g. --query '.videos[].asset_id'
+  --debug-http     - Trace every HTTP request/response to stderr
+                     (method, URL, status, latency, redacted headers)
+  --debug-http=body
+                   - Same as --debug-http, plus JSON/text request bodies
+  --no-color       - Disable colored output (also disabled by NO_COLOR or when
+                     stderr is not a terminal)
+  --bytes          - Show sizes and transfer rates as raw byte counts instead
+                     of MB/GB
+  --iso-duration   - Add ISO 8601 durations (e.g. \"duration_iso8601\": \"PT1H15M30S\")
+                     next to duration fields in --machine output
 
 Available commands:
-  login [--stdin]  - Login to Mux Video
-                     Without --stdin: Interactive credential input (default)
+  login [--stdin | --token-file <path> | --token-id <id> --token-secret <secret>]
+                   - Login to Mux Video
+                     Without flags: Interactive credential input (default)
                      With --stdin: Read credentials from standard input
                                    Format: line 1 = Token ID, line 2 = Token Secret
+                     --token-file: Read the credential file downloaded from the
+                                   Mux dashboard (JSON or MUX_TOKEN_ID=... .env)
+                     --token-id / --token-secret: Pass credentials as arguments
   logout           - Logout from Mux Video
   status           - Check authentication status
-  list             - List all uploaded videos
-  show <asset_id>  - Show detailed information about a specific video asset
+  list [--no-cache] [--offline] [--title-only] [--tag <tag>] [--group-by <unit>]
+       [--relative-time] [--expires <duration>]
+                   - List all uploaded videos with their titles
+                     --no-cache: Bypass the local response cache
+                     --offline: Show the locally indexed videos without network access
+                     --title-only: Show only videos that have a title
+                     --tag <tag>: Show only videos with the tag
+                     --group-by <unit>: Group by day, month, or status with
+                       per-group counts and total duration
+                     --relative-time: Show creation times only as '3 hours ago'
+                     --expires <duration>: Lifetime of signed URLs (default: 1h, e.g. 30m, 7d)
+  show <asset_id> [--no-cache] [--plain] [--relative-time] [--expires <duration>]
+       [--thumbnail-width <px>] [--thumbnail-height <px>] [--copy [hls|mp4|thumbnail]]
+       [--output markdown|html]
+                   - Show detailed information about a specific video asset
+                     --no-cache: Bypass the local response cache
+                     --relative-time: Show the creation time only as '3 hours ago'
+                     --plain: Print only the HLS URL to stdout (alias: --url-only)
+                     --copy [hls|mp4|thumbnail]: Copy the URL to the clipboard (default: hls).
+                       Without a clipboard (e.g. over SSH) a warning is shown instead
+                     --output markdown|html: Print a block to paste into issues and wikis
+                       (title, duration, thumbnail linked to the video, HLS/MP4 links)
+                     --expires <duration>: Lifetime of signed URLs (default: 1h, e.g. 30m, 7d)
+                     --thumbnail-width/--thumbnail-height <px>: Size of the thumbnail URL
+                       (taken at 10% of the duration)
+                     Assets with a signed playback policy get URLs signed with the
+                     [signing] key in config.toml
   delete <asset_id> [--force]
                    - Delete a video asset from Mux Video
                      --force: Skip confirmation prompt
-  upload <file> [--progress]
+  rename <asset_id> <title>
+                   - Change the title of a video asset (quote titles with spaces)
+  tag add|remove <asset_id> <tag>
+  tag list <asset_id>
+                   - Add, remove or show tags stored in the asset's passthrough
+                     Tags: letters, digits, '-', '_' and '.' (case-insensitive)
+  renditions create|delete <asset_id> --resolution <resolution> [--no-wait] [--plain]
+                   - Add or delete a static MP4 rendition of an existing asset
+                     --resolution: highest, audio-only, 2160p, 1440p, 1080p, 720p,
+                       540p, 480p, 360p or 270p
+                     --no-wait: Do not wait for the new MP4 to be ready (create only)
+                     --plain: Print only the MP4 URL to stdout (create only)
+  captions add <asset_id> <url> --language <code> [--name <name>] [--closed-captions]
+  captions generate <asset_id> --language <code> [--name <name>]
+                   - Add a caption track from a WebVTT/SRT URL, or generate one from
+                     the asset's audio
+                     --language: BCP 47 language code (e.g. en, ja, pt-BR); for
+                       generate, the language spoken in the audio
+                     --name: Track name shown in players (e.g. \"English (auto)\")
+                     --closed-captions: Mark the track as closed captions (add only)
+  wait <asset_id|upload_id> [--until created|ready|mp4-ready] [--timeout <duration>] [--progress]
+                   - Wait until an asset (or the asset of a direct upload) reaches a state
+                     --until: created, ready (default) or mp4-ready
+                     --timeout: Give up after this long (default: 600s, e.g. 300, 10m)
+                     --progress: Show progress while waiting
+  export --output <file.json|file.csv> [--restart]
+                   - Write the full metadata of every asset (playback IDs, renditions)
+                     to a JSON or CSV file. An interrupted export resumes when re-run
+                     --restart: Discard the checkpoint and start from the first page
+  import <file.json>
+                   - Re-create the assets in an export or upload manifest via URL ingest
+                     and print the mapping from old to new asset IDs
+  sync <dir> [--delete] [--dry-run] [--force] [--concurrency <n>] [--limit-rate <rate>]
+                   - Upload the videos in a directory that are not on Mux yet
+                     (compared by checksum, or by file name for assets without one)
+                     --delete: Also delete assets that have no local file
+                     --dry-run: Only print the plan
+                     --force: Skip the deletion confirmation prompt
+                     --on-full <policy>: Same as upload (ask is treated as fail)
+  backup --output <dir> [--concurrency <n>]
+                   - Download the ready MP4 renditions of every asset to <dir>/<asset_id>/
+                     Files already downloaded (same size and checksum) are skipped
+  gc [--errored] [--timed-out-uploads] [--older-than <age>] [--dry-run] [--force]
+                   - Delete errored assets and cancel direct uploads that never received a file
+                     Without --errored / --timed-out-uploads, both are cleaned up
+                     --older-than: Only errored assets created before <age> ago (e.g. 90d, 12h)
+                     --dry-run: Only print what would be cleaned up
+                     --force: Skip the confirmation prompt
+  prune --keep <n> [--dry-run] [--force]
+                   - Keep the newest <n> assets and delete the older ones
+                     --dry-run: Only print the assets that would be deleted
+                     --force: Skip the deletion confirmation prompt
+  upload <file>... [--progress] [--probe] [--dry-run] [--wait-ready] [--force]
+         [--manifest <path>] [--concurrency <n>] [--limit-rate <rate>] [--on-full <policy>]
+         [--policy <policy>] [--force-format <format>] [--transcode <args>] [--plain]
+         [--expires <duration>] [--thumbnail-width <px>] [--thumbnail-height <px>] [--mp4 <rendition>]
+         [--copy [hls|mp4|thumbnail]] [--output markdown|html]
+  upload [<file>] --resume-wait <upload_id> [--progress] [--wait-ready]
                    - Upload a video to Mux Video
                      --progress: Show upload progress (required for progress output)
+                     --probe: Analyze the video with ffprobe (if installed) before uploading
+                     --dry-run: Only estimate upload time and encoded minutes (single file)
+                     --wait-ready: Wait until the asset is ready and include its duration,
+                       resolution and tracks in the result
+                     --force: Upload even if the file appears to be already uploaded
+                              and skip the recent-upload warning
+                     --manifest <path>: Write uploaded files, checksums and URLs to a JSON file
+                     --plain: Print only the HLS URL to stdout (alias: --url-only)
+                     --copy [hls|mp4|thumbnail]: Copy the URL to the clipboard (default: hls).
+                       Without a clipboard (e.g. over SSH) a warning is shown instead
+                     --output markdown|html: Print a block to paste into issues and wikis
+                       (title, duration, thumbnail linked to the video, HLS/MP4 links)
+                     --policy <policy>: Playback policy of the new asset: public (default) or
+                       signed (URLs are signed with the [signing] key in config.toml).
+                       Defaults to [defaults] playback_policy in config.toml
+                     --force-format <format>: Upload as this format regardless of the file
+                       extension (skips the extension and content checks, not the size checks)
+                     --transcode <args>: Convert with ffmpeg while uploading (no temp file),
+                       e.g. --transcode \"-c:v libx264 -crf 20\". The output is fragmented MP4
+                     --expires <duration>: Lifetime of signed URLs (default: 1h)
+                     --thumbnail-width/--thumbnail-height <px>: Size of the thumbnail URL in the
+                       result (taken at 10% of the duration once it is known)
+                     --mp4 <rendition>: MP4 to generate: highest (default), 1080p, 720p,
+                       audio-only (audio.m4a) or none. The MP4 URL uses the chosen name
+                     --resume-wait <upload_id>: Skip the upload and wait for the asset of an
+                       upload whose file was already sent (e.g. after the CLI was interrupted).
+                       Pass the original file to record its checksum
+                     --on-full <policy>: When the asset limit is reached: ask (default),
+                       auto_delete (delete the oldest asset) or fail. Multiple files never ask
+                     Multiple files or a directory are uploaded concurrently:
+                     --concurrency <n>: Number of parallel uploads (default: 3, max: 8)
+                     --limit-rate <rate>: Total upload bandwidth cap (e.g. 500K, 10M)
+                     In a terminal, type 'p' + Enter to pause the transfer after the
+                     current chunk and 'r' + Enter to resume it
+  queue add <file> - Add a video to the local upload queue (validated now, uploaded later)
+  queue run        - Upload all queued videos
+                     Uploads that fail due to network errors stay queued for the next run
+  retry [--all | <id>]
+                   - Upload failed uploads again with the options they were run with
+                     Without arguments, lists the recorded failures
+                     --all: Retry every recorded failure
+  history [--limit <n>]
+                   - Show recent commands that changed state (upload, delete, login, ...)
+                     --limit <n>: Number of entries to show (default: 20)
+  history rerun <id>
+                   - Run a recorded command again with the same arguments
+  sign <playback_id> [--type <type>] [--expires <duration>] [--restriction <id>] [--plain]
+                   - Print a signed URL for a playback ID with a signed policy
+                     (uses the [signing] key in config.toml; no network access)
+                     --type <type>: video (default), thumbnail, storyboard or gif
+                     --expires <duration>: Lifetime of the URL (default: 1h, e.g. 30m, 7d)
+                     --restriction <id>: Only allow playback from the domains of this
+                       playback restriction (default: [signing] playback_restriction_id)
+                     --plain: Print only the signed URL to stdout
+  storyboard <asset_id> [--download <dir>] [--expires <duration>] [--plain]
+                   - Print the storyboard (WebVTT and sprite image) URLs of a ready
+                     asset for seek-bar previews (signed when the policy is signed)
+                     --download <dir>: Save storyboard.vtt and storyboard.jpg to the
+                       directory, pointing the VTT at the local sprite image
+                     --expires <duration>: Lifetime of signed URLs (default: 1h)
+                     --plain: Print only the WebVTT URL to stdout
+  serve <asset_id> [--port <port>] [--no-open] [--expires <duration>] [--plain]
+                   - Preview a ready asset in the browser: serve a local page that plays
+                     its HLS with hls.js (signed when the policy is signed) until Ctrl+C
+                     --port <port>: Port on 127.0.0.1 (default: 0, any free port)
+                     --no-open: Only print the page URL; do not open a browser
+                     --expires <duration>: Lifetime of signed URLs (default: 1h)
+                     --plain: Print only the page URL to stdout when the server starts
+  restrictions create --domain <domain>... [--allow-no-referrer]
+                   - Create a playback restriction that only allows the given referrer
+                     domains (repeat --domain; '*.example.com' matches subdomains)
+                     --allow-no-referrer: Also allow requests without a referrer (apps)
+  restrictions list
+                   - List playback restrictions
+  restrictions delete <id>
+                   - Delete a playback restriction
+  restrictions attach <asset_id> <id>
+                   - Add a signed playback ID to the asset for use with the restriction
+                     (sign its URLs with 'sign <playback_id> --restriction <id>')
+  playback revoke <asset_id> <playback_id> [--replace] [--force] [--plain]
+                   - Delete one playback ID and keep the asset (e.g. when a URL leaked)
+                     --replace: First add a new playback ID with the same policy and
+                       print its HLS URL
+                     --force: Skip the confirmation prompt
+                     --plain: Print only the new HLS URL to stdout (with --replace)
+  live reset-key <stream_id> [--plain]
+                   - Reset the stream key of a live stream (the old key stops working).
+                     The new key is masked; --plain prints only the full key to stdout
+  schema [name]    - Print the JSON Schema of the machine-readable output
+                     name: a command (e.g. upload, list), progress, batch_progress or error
+                     Without a name, all schemas are printed as one object
+  ping [--count <n>]
+                   - Send a lightweight authenticated request n times (default: 5,
+                     max: 100) and report min/avg/max latency and failed requests
+  doctor           - Check the config file, credentials, DNS/HTTPS reachability of
+                     api.mux.com and stream.mux.com, clock skew and disk space,
+                     with hints for each problem (exit code 1 if any check fails)
+  stats [--no-cache]
+                   - Show library totals: assets by status, quality and resolution,
+                     total and average duration, uploads per week, and the oldest
+                     and newest assets
+                     --no-cache: Bypass the local response cache
+  whoami           - Show the active profile, masked token ID, API endpoint and
+                     config file path (no network access)
+  version          - Show version, git commit, build date, target and API endpoint
+                     (alias: --version). Please include this in bug reports
   help             - Display this help message
 
 Machine-Readable Output:
@@ -48,11 +297,262 @@ Progress Output:
   upload --progress              - Show human-readable progress to stderr
   --machine upload --progress    - Output machine-readable JSON progress to stdout";
 
+/// ヘルプテキスト（日本語）
+///
+/// `HELP_TEXT` を変更した場合は、こちらも合わせて更新してください。
+const HELP_TEXT_JA: &str = "vidyeet-CLI
+コマンドラインから Mux Video へ簡単に動画をアップロード
+
+使い方:
+  vidyeet [--machine] [--query <expr>] [--debug-http[=body]] <command> [args...]
+
+グローバルフラグ（コマンドの前後どこにでも指定可能）:
+  --machine        - 機械可読なJSONをstdoutへ出力（スクリプト向け）
+                     成功時・エラー時の両方で有効
+  --query <expr>   - --machine の結果からjq形式の式で選んだ値だけを1行ずつ出力
+                     （--machine を含意）例: --query '.videos[].asset_id'
+  --debug-http     - すべてのHTTPリクエスト/レスポンスをstderrへ出力
+                     （メソッド、URL、ステータス、所要時間、秘匿化したヘッダー）
+  --debug-http=body
+                   - --debug-http に加えて JSON/テキストのリクエストボディも出力
+  --no-color       - 色付けを無効化（NO_COLOR 設定時・stderrが端末でない場合も無効）
+  --bytes          - サイズ・転送速度をMB/GBに換算せずバイト数で表示
+  --iso-duration   - --machine の出力の再生時間にISO 8601の期間を併記
+                     （例: \"duration_iso8601\": \"PT1H15M30S\"）
+
+コマンド:
+  login [--stdin | --token-file <path> | --token-id <id> --token-secret <secret>]
+                   - Mux Video にログイン
+                     フラグなし: 対話的に認証情報を入力（既定）
+                     --stdin あり: 標準入力から認証情報を読み込む
+                                   形式: 1行目 = Token ID、2行目 = Token Secret
+                     --token-file: Muxダッシュボードからダウンロードした認証情報
+                                   ファイルを読み込む（JSON または MUX_TOKEN_ID=... の .env）
+                     --token-id / --token-secret: 認証情報を引数で指定
+  logout           - Mux Video からログアウト
+  status           - 認証状態を確認
+  list [--no-cache] [--offline] [--title-only] [--tag <tag>] [--group-by <unit>]
+       [--relative-time] [--expires <duration>]
+                   - アップロード済みの動画をタイトルとともに一覧表示
+                     --no-cache: ローカルのレスポンスキャッシュを使わない
+                     --offline: ネットワークに接続せず、ローカルに記録した動画を表示
+                     --title-only: タイトルのある動画のみを表示
+                     --tag <tag>: 指定したタグの付いた動画のみを表示
+                     --group-by <unit>: day（日）・month（月）・status（ステータス）で
+                       グループに分け、件数と合計時間を表示
+                     --relative-time: 作成日時を「3 時間前」の形式のみで表示
+                     --expires <duration>: 署名付きURLの有効期間（既定: 1h、例: 30m, 7d）
+  show <asset_id> [--no-cache] [--plain] [--relative-time] [--expires <duration>]
+       [--thumbnail-width <px>] [--thumbnail-height <px>] [--copy [hls|mp4|thumbnail]]
+       [--output markdown|html]
+                   - 動画アセットの詳細を表示
+                     --no-cache: ローカルのレスポンスキャッシュを使わない
+                     --relative-time: 作成日時を「3 時間前」の形式のみで表示
+                     --plain: HLS URLだけをstdoutへ出力（別名: --url-only）
+                     --copy [hls|mp4|thumbnail]: URLをクリップボードへコピー（既定: hls）。
+                       クリップボードが使えない環境（SSH接続先など）では警告のみ表示
+                     --output markdown|html: Issueやwikiに貼り付けるブロックを出力
+                       （タイトル・再生時間・動画へリンクしたサムネイル・HLS/MP4のリンク）
+                     --expires <duration>: 署名付きURLの有効期間（既定: 1h、例: 30m, 7d）
+                     --thumbnail-width/--thumbnail-height <px>: サムネイルURLの大きさ
+                       （再生時間の10%の位置）
+                     再生ポリシーが signed のアセットは、config.toml の [signing] の
+                     署名キーで署名したURLを表示
+  delete <asset_id> [--force]
+                   - Mux Video から動画アセットを削除
+                     --force: 確認プロンプトを省略
+  rename <asset_id> <title>
+                   - 動画アセットのタイトルを変更（空白を含む場合は引用符で囲む）
+  tag add|remove <asset_id> <tag>
+  tag list <asset_id>
+                   - アセットのpassthroughに保存したタグを追加・削除・表示
+                     タグ: 英数字と '-'・'_'・'.'（大文字・小文字は区別しない）
+  renditions create|delete <asset_id> --resolution <resolution> [--no-wait] [--plain]
+                   - 既存のアセットにStatic Rendition（MP4）を追加・削除
+                     --resolution: highest, audio-only, 2160p, 1440p, 1080p, 720p,
+                       540p, 480p, 360p, 270p
+                     --no-wait: 追加したMP4の生成完了を待たない（create のみ）
+                     --plain: MP4のURLだけをstdoutへ出力（create のみ）
+  captions add <asset_id> <url> --language <code> [--name <name>] [--closed-captions]
+  captions generate <asset_id> --language <code> [--name <name>]
+                   - WebVTT・SRTのURLから字幕トラックを追加、またはアセットの音声から生成
+                     --language: BCP 47の言語コード（例: en, ja, pt-BR）。generate では
+                       音声の言語
+                     --name: プレイヤーに表示するトラック名（例: \"English (auto)\"）
+                     --closed-captions: 聴覚障害者向けの字幕（CC）として追加（add のみ）
+  wait <asset_id|upload_id> [--until created|ready|mp4-ready] [--timeout <duration>] [--progress]
+                   - アセット（またはDirect Uploadのアセット）が指定した状態になるまで待機
+                     --until: created, ready（既定）, mp4-ready
+                     --timeout: 待機の上限（既定: 600秒、例: 300, 10m）
+                     --progress: 待機中の進捗を表示
+  export --output <file.json|file.csv> [--restart]
+                   - 全アセットの完全なメタデータ（再生ID・レンディション）を
+                     JSONまたはCSVに書き出す。中断した場合は再実行で続きから取得
+                     --restart: チェックポイントを破棄して最初のページから取得
+  import <file.json>
+                   - エクスポートまたはアップロードマニフェストのアセットをURL取り込みで
+                     作り直し、元のアセットIDと新しいアセットIDの対応を表示
+  sync <dir> [--delete] [--dry-run] [--force] [--concurrency <n>] [--limit-rate <rate>]
+                   - ディレクトリ内の動画のうち、Muxにないものをアップロード
+                     （チェックサム、チェックサムのないアセットはファイル名で照合）
+                     --delete: ローカルファイルのないアセットも削除
+                     --dry-run: 計画のみ表示
+                     --force: 削除の確認プロンプトを省略
+                     --on-full <policy>: upload と同じ（ask は fail として扱う）
+  backup --output <dir> [--concurrency <n>]
+                   - 全アセットのready状態のMP4を <dir>/<asset_id>/ にダウンロード
+                     ダウンロード済み（サイズ・チェックサムが一致）のファイルは省略
+  gc [--errored] [--timed-out-uploads] [--older-than <age>] [--dry-run] [--force]
+                   - エラー状態のアセットを削除し、ファイルを受け取っていないDirect Uploadを取り消す
+                     --errored / --timed-out-uploads の指定がない場合は両方が対象
+                     --older-than: <age> より前に作成されたエラー状態のアセットのみ（例: 90d, 12h）
+                     --dry-run: 対象の一覧のみ表示
+                     --force: 確認プロンプトを省略
+  prune --keep <n> [--dry-run] [--force]
+                   - 新しいものから <n> 件のアセットを残し、それより古いものを削除
+                     --dry-run: 削除するアセットの一覧のみ表示
+                     --force: 削除の確認プロンプトを省略
+  upload <file>... [--progress] [--probe] [--dry-run] [--wait-ready] [--force]
+         [--manifest <path>] [--concurrency <n>] [--limit-rate <rate>] [--on-full <policy>]
+         [--policy <policy>] [--force-format <format>] [--transcode <args>] [--plain]
+         [--expires <duration>] [--thumbnail-width <px>] [--thumbnail-height <px>] [--mp4 <rendition>]
+         [--copy [hls|mp4|thumbnail]] [--output markdown|html]
+  upload [<file>] --resume-wait <upload_id> [--progress] [--wait-ready]
+                   - Mux Video へ動画をアップロード
+                     --progress: アップロードの進捗を表示（進捗出力に必須）
+                     --probe: アップロード前に ffprobe（インストール時）で動画を解析
+                     --dry-run: アップロード時間とエンコード時間の見積もりのみ（単一ファイル）
+                     --wait-ready: アセットが ready になるまで待ち、再生時間・解像度・トラックを
+                       結果に含める
+                     --force: アップロード済みと思われるファイルでもアップロードし、
+                              直近のアップロードの警告も省略
+                     --manifest <path>: アップロードしたファイル・チェックサム・URLをJSONファイルへ書き出す
+                     --plain: HLS URLだけをstdoutへ出力（別名: --url-only）
+                     --copy [hls|mp4|thumbnail]: URLをクリップボードへコピー（既定: hls）。
+                       クリップボードが使えない環境（SSH接続先など）では警告のみ表示
+                     --output markdown|html: Issueやwikiに貼り付けるブロックを出力
+                       （タイトル・再生時間・動画へリンクしたサムネイル・HLS/MP4のリンク）
+                     --policy <policy>: 作成するアセットの再生ポリシー: public（既定）または
+                       signed（URLは config.toml の [signing] の鍵で署名）。
+                       省略時は config.toml の [defaults] の playback_policy
+                     --force-format <format>: 拡張子に関わらず指定した形式としてアップロード
+                       （拡張子と内容の検証を省略、サイズの検証は行う）
+                     --transcode <args>: ffmpegで変換しながらアップロード（一時ファイルなし）
+                       例: --transcode \"-c:v libx264 -crf 20\"。出力はフラグメント化MP4
+                     --expires <duration>: 署名付きURLの有効期間（既定: 1h）
+                     --thumbnail-width/--thumbnail-height <px>: 結果のサムネイルURLの大きさ
+                       （再生時間が分かれば、その10%の位置）
+                     --mp4 <rendition>: 生成するMP4: highest（既定）、1080p、720p、
+                       audio-only（audio.m4a）、none。MP4のURLは選んだファイル名になる
+                     --resume-wait <upload_id>: ファイルの送信を終えたアップロード（CLIが中断された
+                       場合など）のアセット作成を、アップロードし直さずに待つ。
+                       元のファイルを指定するとチェックサムを記録
+                     --on-full <policy>: アセット数が上限のとき: ask（既定、確認して削除）、
+                       auto_delete（最も古いアセットを削除）、fail。複数ファイルでは確認しない
+                     複数ファイルまたはディレクトリは並行してアップロード:
+                     --concurrency <n>: 同時アップロード数（既定: 3、上限: 8）
+                     --limit-rate <rate>: 全体の送信帯域の上限（例: 500K, 10M）
+                     端末では 'p' + Enter で送信中のチャンクの後に転送を一時停止し、
+                     'r' + Enter で再開
+  queue add <file> - 動画をローカルのアップロードキューに追加（検証は今、アップロードは後で）
+  queue run        - キューのすべての動画をアップロード
+                     ネットワークエラーで失敗した項目は次回の実行までキューに残る
+  retry [--all | <id>]
+                   - 失敗したアップロードを、失敗時と同じオプションで再実行
+                     引数なしの場合は記録されている失敗を一覧表示
+                     --all: 記録されているすべての失敗を再実行
+  history [--limit <n>]
+                   - 状態を変更したコマンド（upload, delete, login など）の履歴を表示
+                     --limit <n>: 表示する件数（既定: 20）
+  history rerun <id>
+                   - 記録されたコマンドを同じ引数で再実行
+  sign <playback_id> [--type <type>] [--expires <duration>] [--restriction <id>] [--plain]
+                   - 再生ポリシーが signed の再生IDの署名付きURLを表示
+                     （config.toml の [signing] の署名キーを使用。ネットワーク接続なし）
+                     --type <type>: video（既定）、thumbnail、storyboard、gif
+                     --expires <duration>: URLの有効期間（既定: 1h、例: 30m, 7d）
+                     --restriction <id>: 再生制限で許可したドメインからのみ再生を許可
+                       （既定: [signing] の playback_restriction_id）
+                     --plain: 署名付きURLだけをstdoutへ出力
+  storyboard <asset_id> [--download <dir>] [--expires <duration>] [--plain]
+                   - シークバーのプレビューに使う、ready のアセットのストーリーボード
+                     （WebVTTとスプライト画像）のURLを表示（signed の場合は署名付き）
+                     --download <dir>: storyboard.vtt と storyboard.jpg をディレクトリに保存し、
+                       VTTが参照するスプライト画像を保存したファイルに書き換える
+                     --expires <duration>: 署名付きURLの有効期間（既定: 1h）
+                     --plain: WebVTTのURLだけをstdoutへ出力
+  serve <asset_id> [--port <port>] [--no-open] [--expires <duration>] [--plain]
+                   - ready のアセットをブラウザでプレビュー: HLSをhls.jsで再生するページを
+                     Ctrl+C まで手元で配信（signed の場合は署名付き）
+                     --port <port>: 127.0.0.1 で待ち受けるポート（既定: 0、空いているポート）
+                     --no-open: ページのURLを表示するだけでブラウザを開かない
+                     --expires <duration>: 署名付きURLの有効期間（既定: 1h）
+                     --plain: 起動時にページのURLだけをstdoutへ出力
+  restrictions create --domain <domain>... [--allow-no-referrer]
+                   - 指定したリファラーのドメインからのみ再生を許可する再生制限を作成
+                     （--domain は複数指定可。'*.example.com' はサブドメインに一致）
+                     --allow-no-referrer: リファラーのないリクエスト（アプリなど）も許可
+  restrictions list
+                   - 再生制限を一覧表示
+  restrictions delete <id>
+                   - 再生制限を削除
+  restrictions attach <asset_id> <id>
+                   - 再生制限に使う署名付き再生IDをアセットに追加
+                     （URLは 'sign <playback_id> --restriction <id>' で署名）
+  playback revoke <asset_id> <playback_id> [--replace] [--force] [--plain]
+                   - アセットを残したまま再生IDを1つ削除（URLが漏れた場合など）
+                     --replace: 先に同じポリシーの再生IDを追加し、そのHLS URLを表示
+                     --force: 確認プロンプトを省略
+                     --plain: 新しいHLS URLだけをstdoutへ出力（--replace と併用）
+  live reset-key <stream_id> [--plain]
+                   - ライブストリームのストリームキーを再発行（古いキーは使えなくなる）。
+                     新しいキーは伏せ字で表示し、--plain でキー全体だけをstdoutへ出力
+  schema [name]    - 機械可読出力のJSON Schemaを表示
+                     name: コマンド名（upload, list など）、progress、batch_progress、error
+                     省略時はすべてのスキーマを1つのオブジェクトで出力
+  ping [--count <n>]
+                   - 認証付きの軽量なリクエストを n 回（既定: 5、上限: 100）送り、
+                     レイテンシの最小・平均・最大と失敗したリクエストを表示
+  doctor           - 設定ファイル・認証情報・api.mux.com / stream.mux.com への接続（DNS・HTTPS）・
+                     時刻のずれ・空き容量を確認し、問題があれば対処方法を表示
+                     失敗したチェックがあれば終了コード1
+  stats [--no-cache]
+                   - ライブラリ全体の集計を表示（ステータス・品質・解像度ごとの件数、
+                     再生時間の合計と平均、週ごとのアップロード数、最も古い・新しいアセット）
+                     --no-cache: ローカルのレスポンスキャッシュを使わない
+  whoami           - 使用中のプロファイル・マスクしたToken ID・APIエンドポイント・
+                     設定ファイルのパスを表示（通信なし）
+  version          - バージョン・git コミット・ビルド日・ターゲット・APIエンドポイントを表示
+                     （別名: --version）。不具合報告の際は添えてください
+  help             - このヘルプを表示
+
+機械可読出力:
+  --machine status               - 成功時のJSON出力
+  --machine list                 - エラー処理を含むJSON出力
+  echo \"id\nkey\" | --machine login --stdin
+                                 - JSONレスポンス付きの自動ログイン
+
+エラー出力:
+  通常モード:    人間向けのエラーメッセージをstderrへ
+  --machine:     exit_code と hint を含むJSONエラーオブジェクト
+
+進捗出力:
+  upload --progress              - 人間向けの進捗をstderrへ表示
+  --machine upload --progress    - 機械可読なJSONの進捗をstdoutへ出力";
+
 /// コマンド使用方法を表示する
 ///
 /// CLI引数が不正な場合や、ヘルプが必要な場合に呼び出されます。
 pub fn print_usage() {
-    eprintln!("{}", HELP_TEXT);
+    eprintln!("{}", help_text());
+}
+
+/// 表示言語のヘルプテキスト
+fn help_text() -> &'static str {
+    match locale() {
+        Locale::En => HELP_TEXT,
+        Locale::Ja => HELP_TEXT_JA,
+    }
 }
 
 /// コマンド結果を適切な形式で出力する
@@ -69,11 +569,135 @@ pub fn output_result(result: &CommandResult, machine_output: bool) -> Result<()>
         output_machine_readable(result)?;
     } else {
         output_human_readable(result)?;
+        print_warnings(result.warnings());
     }
 
     Ok(())
 }
 
+/// 結果の値だけをstdoutへ出力する（`--plain`）
+///
+/// HLS URL（`sign` では署名付きURL、`renditions create` ではMP4のURL、`storyboard` ではWebVTTのURL、
+/// `playback revoke --replace` では新しい再生IDのHLS URL）を1行で出力し、それ以外は何も出力しません。
+/// `serve` のプレビューページのURLは起動時に `print_serve_started` が出力します。
+/// 複数ファイルのアップロードでは成功したファイルごとに1行ずつ出力し、
+/// 失敗したファイルはstderrに表示します。
+///
+/// # Errors
+/// 公開再生IDがなくHLS URLがない場合（署名キーがなく署名付きURLを作れない場合を含む）、MP4がまだ生成されていない場合
+pub fn output_plain(result: &CommandResult) -> Result<()> {
+    let hls_url = match result {
+        CommandResult::Upload(r) => r.hls_url.as_deref(),
+        CommandResult::Show(r) => r.hls_url.as_deref(),
+        CommandResult::Batch(BatchResult {
+            details: BatchDetails::Upload(r),
+            ..
+        }) => {
+            for upload in &r.uploaded {
+                match &upload.hls_url {
+                    Some(hls_url) => println!("{}", hls_url),
+                    None => eprintln!(
+                        "{}",
+                        style::warning(&tf(Msg::PlainNoHlsUrl, &[&upload.file_path]))
+                    ),
+                }
+            }
+            for failure in &r.failed {
+                eprintln!(
+                    "{}",
+                    style::error(&tf(Msg::BatchFailed, &[&failure.file_path]))
+                );
+                eprintln!("  {}", failure.error);
+            }
+            return Ok(());
+        }
+        // ストリームキーは秘密の値のため、--plain でのみ人間向けにそのまま出力する
+        CommandResult::LiveResetKey(r) => {
+            println!("{}", r.stream_key);
+            return Ok(());
+        }
+        CommandResult::Sign(r) => {
+            println!("{}", r.url);
+            return Ok(());
+        }
+        CommandResult::Renditions(r) => {
+            let mp4_url = r
+                .mp4_url
+                .as_deref()
+                .context("No MP4 URL is available (the MP4 is not ready yet)")?;
+            println!("{}", mp4_url);
+            return Ok(());
+        }
+        // WebVTTがスプライト画像を参照するため、プレーヤーに渡すWebVTTのURLだけを出力する
+        CommandResult::Storyboard(r) => {
+            println!("{}", r.vtt_url);
+            return Ok(());
+        }
+        CommandResult::PlaybackRevoke(r) => {
+            let hls_url = r.hls_url.as_deref().context(
+                "No HLS URL is available for the new playback ID (signed URLs need key_id and private_key under [signing] in config.toml)",
+            )?;
+            println!("{}", hls_url);
+            return Ok(());
+        }
+        // プレビューページのURLは起動時に出力済み
+        CommandResult::Serve(_) => return Ok(()),
+        _ => bail!(
+            "--plain is only supported by upload, show, sign, renditions create, storyboard, serve, playback revoke --replace and live reset-key"
+        ),
+    };
+
+    let hls_url =
+        hls_url.context("No HLS URL is available (the asset has no public playback ID)")?;
+    println!("{}", hls_url);
+    Ok(())
+}
+
+/// `serve` の起動を知らせる（stderr。`--plain` ではプレビューページのURLだけをstdoutへ出力）
+pub fn print_serve_started(info: &ServeResult, plain: bool) {
+    if plain {
+        println!("{}", info.url);
+        return;
+    }
+    eprintln!(
+        "{}",
+        tf(Msg::ServeListening, &[&info.asset_id, &info.playback_id])
+    );
+    eprintln!("\n{}", t(Msg::ServeUrlHeading));
+    eprintln!("{}", info.url);
+    if let Some(expires_at) = &info.expires_at {
+        let expires_at = match UserConfig::load() {
+            Ok(config) => formatter::format_timestamp(expires_at, &config),
+            Err(_) => expires_at.clone(),
+        };
+        eprintln!("{}", style::hint(&tf(Msg::ServeExpires, &[&expires_at])));
+    }
+}
+
+/// 秘密の値を伏せ字にする（先頭と末尾の4文字のみ表示、8文字以下はすべて伏せる）
+fn mask_secret(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}***{}", head, tail)
+}
+
+/// トラックの言語とトラック名（例: `[en] English (auto)`、どちらもなければ空）
+fn track_language(track: &Track) -> String {
+    let language = track
+        .language_code
+        .as_ref()
+        .map(|code| format!("[{}]", code));
+    [language.as_deref(), track.name.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// 人間向けの詳細メッセージを出力（stderr）
 ///
 /// ユーザーが理解しやすい形式でコマンド結果を表示します。
@@ -83,134 +707,179 @@ fn output_human_readable(result: &CommandResult) -> Result<()> {
         CommandResult::Login(r) => {
             eprintln!();
             if r.was_logged_in {
-                eprintln!("✓ Login credentials updated!");
-                eprintln!("New authentication credentials have been saved.");
+                eprintln!("{}", style::success(t(Msg::LoginUpdated)));
+                eprintln!("{}", t(Msg::LoginUpdatedDetail));
             } else {
-                eprintln!("Login successful.");
-                eprintln!("Authentication credentials have been saved.");
+                eprintln!("{}", style::success(t(Msg::LoginSucceeded)));
+                eprintln!("{}", t(Msg::LoginSavedDetail));
             }
         }
         CommandResult::Logout(r) => {
             if r.was_logged_in {
-                eprintln!("Logged out successfully.");
-                eprintln!("Authentication credentials have been removed.");
+                eprintln!("{}", style::success(t(Msg::LogoutSucceeded)));
+                eprintln!("{}", t(Msg::LogoutRemovedDetail));
             } else {
-                eprintln!("Already logged out.");
+                eprintln!("{}", t(Msg::AlreadyLoggedOut));
             }
         }
         CommandResult::Status(r) => {
             eprintln!();
             if r.is_authenticated {
-                eprintln!("Authenticated");
+                eprintln!("{}", style::success(t(Msg::StatusAuthenticated)));
                 if let Some(token_id) = &r.token_id {
-                    eprintln!("Token ID: {}", token_id);
+                    eprintln!("{}", tf(Msg::StatusTokenId, &[token_id]));
+                }
+                if let Some(environment) = &r.environment {
+                    eprintln!("{}", tf(Msg::StatusEnvironment, &[environment]));
+                }
+                match (r.asset_count, r.asset_limit, r.remaining_capacity) {
+                    (Some(count), Some(limit), Some(remaining)) => eprintln!(
+                        "{}",
+                        tf(Msg::StatusAssetsWithLimit, &[&count, &limit, &remaining])
+                    ),
+                    (Some(count), _, _) => eprintln!("{}", tf(Msg::StatusAssets, &[&count])),
+                    (None, _, _) => {}
                 }
                 eprintln!();
-                eprintln!("Your credentials are valid and working.");
+                eprintln!("{}", t(Msg::StatusValid));
+                if r.is_at_capacity() {
+                    let message = match r.capacity_policy {
+                        CapacityPolicy::Ask => Msg::StatusAtCapacityAsk,
+                        CapacityPolicy::AutoDelete => Msg::StatusAtCapacity,
+                        CapacityPolicy::Fail => Msg::StatusAtCapacityFail,
+                    };
+                    eprintln!("{}", style::warning(t(message)));
+                } else if r.asset_count.is_some() && r.asset_limit.is_none() {
+                    eprintln!("{}", style::hint(t(Msg::StatusSetAssetLimit)));
+                }
             } else if let Some(token_id) = &r.token_id {
                 // 認証情報はあるが検証失敗
-                eprintln!("✗ Authentication failed");
-                eprintln!("  Token ID: {}", token_id);
+                eprintln!("{}", style::error(t(Msg::StatusAuthFailed)));
+                eprintln!("  {}", tf(Msg::StatusTokenId, &[token_id]));
                 eprintln!();
-                eprintln!("Your credentials may be invalid or expired.");
-                eprintln!("Please run 'vidyeet login' to update your credentials.");
+                eprintln!("{}", t(Msg::StatusInvalid));
+                eprintln!("{}", style::hint(t(Msg::StatusRunLoginUpdate)));
             } else {
                 // 認証情報が存在しない
-                eprintln!("Not logged in");
-                eprintln!("No authentication credentials found.");
-                eprintln!("Please run 'vidyeet login' to authenticate.");
+                eprintln!("{}", style::warning(t(Msg::StatusNotLoggedIn)));
+                eprintln!("{}", t(Msg::StatusNoCredentials));
+                eprintln!("{}", style::hint(t(Msg::StatusRunLogin)));
             }
         }
         CommandResult::List(r) => {
             eprintln!();
             if r.total_count == 0 {
-                eprintln!("No videos found.");
-                eprintln!("Upload your first video with 'vidyeet upload <file>'");
+                eprintln!("{}", t(Msg::ListEmpty));
+                eprintln!("{}", style::hint(t(Msg::ListUploadFirst)));
             } else {
                 // ユーザー設定を読み込んでタイムゾーン設定を取得
-                let user_config = crate::config::user::UserConfig::load().ok();
+                let user_config = vidyeet::config::user::UserConfig::load().ok();
 
-                eprintln!("Found {} video(s):", r.total_count);
+                match (&r.synced_at, &user_config) {
+                    (Some(synced_at), Some(config)) => eprintln!(
+                        "{}",
+                        tf(
+                            Msg::ListFoundOffline,
+                            &[
+                                &r.total_count,
+                                &vidyeet::domain::formatter::format_timestamp(synced_at, config)
+                            ]
+                        )
+                    ),
+                    (Some(synced_at), None) => eprintln!(
+                        "{}",
+                        tf(Msg::ListFoundOffline, &[&r.total_count, synced_at])
+                    ),
+                    (None, _) => eprintln!("{}", tf(Msg::ListFound, &[&r.total_count])),
+                }
                 eprintln!();
-                for (idx, video) in r.videos.iter().enumerate() {
-                    eprintln!("---");
-                    eprintln!("Video #{}", idx + 1);
-                    eprintln!("Asset ID: {}", video.asset_id);
-                    eprintln!("Status: {}", video.status);
-
-                    if let Some(duration) = video.duration {
-                        let minutes = (duration / 60.0) as u64;
-                        let seconds = (duration % 60.0) as u64;
-                        eprintln!("Duration: {}:{:02}", minutes, seconds);
+                if r.groups.is_empty() {
+                    for (idx, video) in r.videos.iter().enumerate() {
+                        print_list_video(idx + 1, video, user_config.as_ref());
                     }
-
-                    if let Some(aspect_ratio) = &video.aspect_ratio {
-                        eprintln!("Aspect Ratio: {}", aspect_ratio);
+                } else {
+                    // --group-by: グループごとに見出し（件数・合計時間）を付けて表示
+                    let videos: HashMap<&str, &VideoInfo> = r
+                        .videos
+                        .iter()
+                        .map(|video| (video.asset_id.as_str(), video))
+                        .collect();
+                    let mut number = 0;
+                    for group in &r.groups {
+                        eprintln!(
+                            "{}",
+                            style::heading(&tf(
+                                Msg::ListGroupHeading,
+                                &[
+                                    &group.key,
+                                    &group.count,
+                                    &formatter::format_duration(group.total_duration)
+                                ]
+                            ))
+                        );
+                        eprintln!();
+                        for asset_id in &group.asset_ids {
+                            if let Some(video) = videos.get(asset_id.as_str()) {
+                                number += 1;
+                                print_list_video(number, video, user_config.as_ref());
+                            }
+                        }
                     }
-
-                    if let Some(hls_url) = &video.hls_url {
-                        eprintln!("HLS URL: {}", hls_url);
-                    }
-                    if let Some(mp4_url) = &video.mp4_url {
-                        eprintln!("MP4 URL: {}", mp4_url);
-                    }
-
-                    // 作成日時をフォーマット（ユーザー設定のタイムゾーンを使用）
-                    let formatted_time = if let Some(config) = &user_config {
-                        crate::domain::formatter::format_timestamp(&video.created_at, config)
-                    } else {
-                        video.created_at.clone()
-                    };
-                    eprintln!("Created: {}", formatted_time);
-                    eprintln!();
                 }
                 eprintln!("---");
+                print_list_summary(&r.summary());
             }
         }
         CommandResult::Show(r) => {
             eprintln!();
-            eprintln!("Asset Details:");
+            eprintln!("{}", style::heading(t(Msg::ShowHeading)));
             eprintln!("==============");
-            eprintln!("Asset ID:       {}", r.asset_id);
-            eprintln!("Status:         {}", r.status);
+            eprintln!("{}", tf(Msg::ShowAssetId, &[&r.asset_id]));
+            eprintln!(
+                "{}",
+                style::asset_status(&r.status, &tf(Msg::ShowStatus, &[&r.status]))
+            );
 
             if let Some(duration) = r.duration {
-                let minutes = (duration / 60.0) as u64;
-                let seconds = (duration % 60.0) as u64;
                 eprintln!(
-                    "Duration:       {}:{:02} ({:.2}s)",
-                    minutes, seconds, duration
+                    "{}",
+                    tf(
+                        Msg::ShowDuration,
+                        &[
+                            &formatter::format_duration(duration),
+                            &format!("{:.2}", duration)
+                        ]
+                    )
                 );
             }
 
             if let Some(aspect_ratio) = &r.aspect_ratio {
-                eprintln!("Aspect Ratio:   {}", aspect_ratio);
+                eprintln!("{}", tf(Msg::ShowAspectRatio, &[aspect_ratio]));
             }
 
             if let Some(video_quality) = &r.video_quality {
-                eprintln!("Video Quality:  {}", video_quality);
+                eprintln!("{}", tf(Msg::ShowVideoQuality, &[video_quality]));
             }
 
             // 作成日時をフォーマット（ユーザー設定のタイムゾーンを使用）
-            let user_config = crate::config::user::UserConfig::load().ok();
-            let formatted_time = if let Some(config) = &user_config {
-                crate::domain::formatter::format_timestamp(&r.created_at, config)
-            } else {
-                r.created_at.clone()
-            };
-            eprintln!("Created At:     {}", formatted_time);
+            let user_config = vidyeet::config::user::UserConfig::load().ok();
+            let formatted_time = format_created_at(&r.created_at, user_config.as_ref());
+            eprintln!("{}", tf(Msg::ShowCreatedAt, &[&formatted_time]));
 
             eprintln!();
-            eprintln!("Playback Information:");
+            eprintln!("{}", style::heading(t(Msg::ShowPlaybackHeading)));
             eprintln!("--------------------");
 
             if !r.playback_ids.is_empty() {
                 for (idx, playback_id) in r.playback_ids.iter().enumerate() {
-                    eprintln!("Playback ID #{}: {}", idx + 1, playback_id.id);
-                    eprintln!("  Policy:       {}", playback_id.policy);
+                    eprintln!(
+                        "{}",
+                        tf(Msg::ShowPlaybackId, &[&(idx + 1), &playback_id.id])
+                    );
+                    eprintln!("{}", tf(Msg::ShowPolicy, &[&playback_id.policy]));
                 }
             } else {
-                eprintln!("No playback IDs available");
+                eprintln!("{}", t(Msg::ShowNoPlaybackIds));
             }
 
             if let Some(hls_url) = &r.hls_url {
@@ -221,16 +890,40 @@ fn output_human_readable(result: &CommandResult) -> Result<()> {
                 eprintln!("MP4 URL:        {}", mp4_url);
             }
 
+            if let Some(thumbnail_url) = &r.thumbnail_url {
+                eprintln!("Thumbnail URL:  {}", thumbnail_url);
+            }
+
+            if let Some(images) = &r.signed_image_urls {
+                if r.thumbnail_url.is_none() {
+                    eprintln!("Thumbnail URL:  {}", images.thumbnail_url);
+                }
+                eprintln!("Storyboard URL: {}", images.storyboard_url);
+                eprintln!("GIF URL:        {}", images.gif_url);
+            }
+
             if let Some(tracks) = &r.tracks
                 && !tracks.is_empty()
             {
                 eprintln!();
-                eprintln!("Tracks:");
+                eprintln!("{}", style::heading(t(Msg::ShowTracksHeading)));
                 eprintln!("-------");
                 for (idx, track) in tracks.iter().enumerate() {
-                    eprint!("Track #{}: {} ", idx + 1, track.track_type);
+                    eprint!("{}", tf(Msg::ShowTrack, &[&(idx + 1), &track.track_type]));
+                    let language = track_language(track);
+                    if !language.is_empty() {
+                        eprint!("{} ", language);
+                    }
+                    if let Some(status) = &track.status
+                        && status != "ready"
+                    {
+                        eprint!("{}", tf(Msg::ShowTrackStatus, &[status]));
+                    }
                     if let Some(duration) = track.duration {
-                        eprint!("(duration: {:.2}s)", duration);
+                        eprint!(
+                            "{}",
+                            tf(Msg::ShowTrackDuration, &[&format!("{:.2}", duration)])
+                        );
                     }
                     eprintln!();
                 }
@@ -240,69 +933,1239 @@ fn output_human_readable(result: &CommandResult) -> Result<()> {
                 && !renditions.files.is_empty()
             {
                 eprintln!();
-                eprintln!("Static Renditions:");
+                eprintln!("{}", style::heading(t(Msg::ShowRenditionsHeading)));
                 eprintln!("------------------");
                 for (idx, rendition) in renditions.files.iter().enumerate() {
-                    eprintln!("Rendition #{}: {}", idx + 1, rendition.name);
-                    eprintln!("  Status:       {}", rendition.status);
-                    eprintln!("  Resolution:   {}", rendition.resolution);
-                    eprintln!("  Type:         {}", rendition.rendition_type);
-                    eprintln!("  Format:       {}", rendition.ext);
+                    eprintln!("{}", tf(Msg::ShowRendition, &[&(idx + 1), &rendition.name]));
+                    eprintln!(
+                        "{}",
+                        style::asset_status(
+                            rendition.status.as_str(),
+                            &tf(Msg::ShowRenditionStatus, &[&rendition.status])
+                        )
+                    );
+                    eprintln!(
+                        "{}",
+                        tf(Msg::ShowRenditionResolution, &[&rendition.resolution])
+                    );
+                    eprintln!(
+                        "{}",
+                        tf(Msg::ShowRenditionType, &[&rendition.rendition_type])
+                    );
+                    eprintln!("{}", tf(Msg::ShowRenditionFormat, &[&rendition.ext]));
                 }
             }
+            print_copied(r.copied.as_ref());
             eprintln!();
         }
         CommandResult::Upload(r) => {
-            eprintln!("\nUpload completed successfully!");
+            eprintln!("\n{}", style::success(t(Msg::UploadCompleted)));
             eprintln!("---");
-            eprintln!("Asset ID: {}", r.asset_id);
+            eprintln!("{}", tf(Msg::AssetId, &[&r.asset_id]));
 
             // HLS再生URL（すぐに利用可能）
             if let Some(hls_url) = &r.hls_url {
-                eprintln!("\nHLS Streaming URL:");
+                eprintln!("\n{}", t(Msg::UploadHlsHeading));
                 eprintln!("{}", hls_url);
             }
 
-            // MP4再生URL（アプリケーション層で既に生成済み）
-            eprintln!("\nMP4 Download URL:");
+            // MP4再生URL（アプリケーション層で既に生成済み、`--mp4 none` の場合は表示しない）
+            if !matches!(r.mp4_status, Mp4Status::Disabled) {
+                eprintln!("\n{}", t(Msg::UploadMp4Heading));
+                if let Some(mp4_url) = &r.mp4_url {
+                    eprintln!("{}", mp4_url);
+                } else {
+                    eprintln!("{}", t(Msg::NotAvailable));
+                }
+            }
+
+            // サムネイル画像のURL（再生時間の10%の位置）
+            if let Some(thumbnail_url) = &r.thumbnail_url {
+                eprintln!("\n{}", t(Msg::UploadThumbnailHeading));
+                eprintln!("{}", thumbnail_url);
+            }
+
+            // メディア解析結果（--probe指定時）
+            if let Some(media) = &r.media {
+                eprintln!("\n{}", t(Msg::Media));
+                eprintln!("{}", format_media_summary(media));
+            }
+
+            // Muxが処理した最終的なメディア情報（readyの場合のみ）
+            if let Some(final_media) = &r.final_media {
+                eprintln!("\n{}", t(Msg::FinalMedia));
+                eprintln!("{}", format_final_media(final_media));
+            }
+
+            // 段階ごとの所要時間
+            let timings = &r.timings;
+            eprintln!(
+                "\n{}",
+                tf(
+                    Msg::UploadTimings,
+                    &[
+                        &format!("{:.1}", timings.validate_secs),
+                        &format!("{:.1}", timings.create_upload_secs),
+                        &format!("{:.1}", timings.upload_secs),
+                        &format!("{:.1}", timings.throughput_mbps),
+                        &format!("{:.1}", timings.wait_secs),
+                        &format!("{:.1}", timings.total_secs),
+                    ]
+                )
+            );
+
+            eprintln!("---");
+
+            // 容量確保のために削除したアセットがある場合
+            if !r.deleted_assets.is_empty() {
+                let user_config = vidyeet::config::user::UserConfig::load().ok();
+                eprintln!("\n{}", t(Msg::UploadDeletedOld));
+                for asset in &r.deleted_assets {
+                    let created_at = match &user_config {
+                        Some(config) => {
+                            vidyeet::domain::formatter::format_timestamp(&asset.created_at, config)
+                        }
+                        None => asset.created_at.clone(),
+                    };
+                    eprintln!(
+                        "  {}",
+                        tf(
+                            Msg::UploadDeletedAsset,
+                            &[
+                                &asset.asset_id,
+                                &asset.title.as_deref().unwrap_or("-"),
+                                &created_at
+                            ]
+                        )
+                    );
+                }
+            }
+
+            print_copied(r.copied.as_ref());
+        }
+        CommandResult::UploadEstimate(r) => {
+            eprintln!("\n{}", style::heading(t(Msg::DryRunHeading)));
+            eprintln!("---");
+            eprintln!(
+                "{}",
+                tf(
+                    Msg::EstimateFile,
+                    &[
+                        &r.file_path,
+                        &units::size(r.estimate.size_bytes),
+                        &r.file_format
+                    ]
+                )
+            );
+            eprintln!(
+                "{}",
+                tf(
+                    Msg::EstimateUploadTime,
+                    &[&progress::format_estimated_upload_time(&r.estimate)]
+                )
+            );
+            eprintln!(
+                "{}",
+                tf(
+                    Msg::EstimateEncodedMinutes,
+                    &[&progress::format_encoded_minutes(&r.estimate)]
+                )
+            );
+
+            // メディア解析結果（--probe指定時）
+            if let Some(media) = &r.media {
+                eprintln!(
+                    "{}",
+                    tf(Msg::EstimateMedia, &[&format_media_summary(media)])
+                );
+            }
+            eprintln!("---");
+
+            if r.estimate.bytes_per_sec.is_none() {
+                eprintln!("\n{}", style::hint(t(Msg::EstimateNoThroughput)));
+            }
+            if r.estimate.duration_secs.is_none() {
+                eprintln!("{}", style::hint(t(Msg::EstimateNoDuration)));
+            }
+        }
+        CommandResult::Delete(r) => {
+            eprintln!();
+            eprintln!("{}", style::success(t(Msg::DeleteSucceeded)));
+            eprintln!("{}", tf(Msg::AssetId, &[&r.asset_id]));
+            eprintln!();
+            eprintln!("{}", t(Msg::DeletePermanent));
+        }
+        CommandResult::Rename(r) => {
+            eprintln!();
+            eprintln!("{}", style::success(t(Msg::RenameSucceeded)));
+            eprintln!("{}", tf(Msg::AssetId, &[&r.asset_id]));
+            let old_title = r.old_title.as_deref().unwrap_or(t(Msg::RenameNoTitle));
+            eprintln!("{}", tf(Msg::RenameOldTitle, &[&old_title]));
+            eprintln!("{}", tf(Msg::RenameNewTitle, &[&r.new_title]));
+        }
+        CommandResult::Tag(r) => {
+            let tag = r.tag.as_deref().unwrap_or_default();
+            match (r.action, r.changed) {
+                (TagAction::Add, true) => {
+                    eprintln!(
+                        "{}",
+                        style::success(&tf(Msg::TagAdded, &[&tag, &r.asset_id]))
+                    )
+                }
+                (TagAction::Add, false) => {
+                    eprintln!("{}", tf(Msg::TagAlreadySet, &[&tag, &r.asset_id]))
+                }
+                (TagAction::Remove, true) => {
+                    eprintln!(
+                        "{}",
+                        style::success(&tf(Msg::TagRemoved, &[&tag, &r.asset_id]))
+                    )
+                }
+                (TagAction::Remove, false) => {
+                    eprintln!(
+                        "{}",
+                        style::warning(&tf(Msg::TagNotSet, &[&tag, &r.asset_id]))
+                    )
+                }
+                (TagAction::List, _) => {}
+            }
+            if r.tags.is_empty() {
+                eprintln!("{}", tf(Msg::TagNone, &[&r.asset_id]));
+            } else {
+                eprintln!("{}", tf(Msg::TagList, &[&r.asset_id, &r.tags.join(", ")]));
+            }
+        }
+        CommandResult::Renditions(r) => {
+            let resolution = &r.rendition.resolution;
+            eprintln!();
+            match (r.action, r.changed) {
+                (RenditionAction::Create, true) => eprintln!(
+                    "{}",
+                    style::success(&tf(Msg::RenditionCreated, &[resolution, &r.asset_id]))
+                ),
+                (RenditionAction::Create, false) => {
+                    eprintln!("{}", tf(Msg::RenditionExists, &[&r.asset_id, resolution]))
+                }
+                (RenditionAction::Delete, _) => eprintln!(
+                    "{}",
+                    style::success(&tf(Msg::RenditionDeleted, &[resolution, &r.asset_id]))
+                ),
+            }
+            if r.action == RenditionAction::Create {
+                eprintln!("{}", tf(Msg::Status, &[&r.rendition.status]));
+                match &r.mp4_url {
+                    Some(url) => {
+                        eprintln!("{}", t(Msg::UploadMp4Heading));
+                        eprintln!("{}", url);
+                    }
+                    None if r.rendition.status != RenditionStatus::Ready => {
+                        eprintln!(
+                            "{}",
+                            style::hint(&tf(Msg::RenditionPending, &[&r.asset_id]))
+                        );
+                    }
+                    None => {}
+                }
+            }
+        }
+        CommandResult::Captions(r) => {
+            let languages = r
+                .tracks
+                .iter()
+                .filter_map(|track| track.language_code.as_deref())
+                .collect::<Vec<_>>()
+                .join(", ");
+            eprintln!();
+            match (r.action, &r.source_track_id) {
+                (CaptionAction::Generate, Some(source)) => eprintln!(
+                    "{}",
+                    style::success(&tf(
+                        Msg::CaptionGenerating,
+                        &[&languages, &r.asset_id, source]
+                    ))
+                ),
+                _ => eprintln!(
+                    "{}",
+                    style::success(&tf(Msg::CaptionAdded, &[&languages, &r.asset_id]))
+                ),
+            }
+            for track in &r.tracks {
+                eprintln!(
+                    "{}",
+                    tf(
+                        Msg::CaptionTrack,
+                        &[&track.id.as_deref().unwrap_or("-"), &track_language(track)]
+                    )
+                );
+            }
+            if r.action == CaptionAction::Generate {
+                eprintln!("{}", style::hint(&tf(Msg::CaptionPending, &[&r.asset_id])));
+            }
+        }
+        CommandResult::Wait(r) => {
+            eprintln!(
+                "\n{}",
+                style::success(&tf(
+                    Msg::WaitReached,
+                    &[&r.asset_id, &r.until.name(), &r.elapsed_secs]
+                ))
+            );
+            eprintln!("---");
+            eprintln!("{}", tf(Msg::AssetId, &[&r.asset_id]));
+            eprintln!("{}", tf(Msg::Status, &[&r.status]));
+            if let Some(hls_url) = &r.hls_url {
+                eprintln!("\n{}", t(Msg::UploadHlsHeading));
+                eprintln!("{}", hls_url);
+            }
             if let Some(mp4_url) = &r.mp4_url {
+                eprintln!("\n{}", t(Msg::UploadMp4Heading));
                 eprintln!("{}", mp4_url);
+            }
+        }
+        CommandResult::Export(r) => {
+            if r.resumed_assets > 0 {
+                eprintln!("{}", tf(Msg::ExportResumed, &[&r.resumed_assets]));
+            }
+            eprintln!(
+                "{}",
+                style::success(&tf(Msg::ExportSucceeded, &[&r.asset_count, &r.output_path]))
+            );
+        }
+        CommandResult::Import(r) => {
+            eprintln!();
+            for asset in &r.imported {
+                eprintln!(
+                    "{}",
+                    style::success(&tf(
+                        Msg::ImportCreated,
+                        &[&asset.old_asset_id, &asset.new_asset_id]
+                    ))
+                );
+                if let Some(title) = &asset.title {
+                    eprintln!("  {}", tf(Msg::ListTitle, &[title]));
+                }
+            }
+            for failure in &r.failed {
+                eprintln!(
+                    "{}",
+                    style::error(&tf(Msg::ImportFailed, &[&failure.old_asset_id]))
+                );
+                eprintln!("  {}", failure.error);
+            }
+            eprintln!();
+            eprintln!(
+                "{}",
+                tf(Msg::ImportSummary, &[&r.imported.len(), &r.failed.len()])
+            );
+            if !r.imported.is_empty() {
+                eprintln!("{}", style::hint(t(Msg::ImportProcessingHint)));
+            }
+        }
+        CommandResult::Gc(r) => {
+            eprintln!();
+            if r.dry_run {
+                if r.errored_assets.is_empty() && r.stale_uploads.is_empty() {
+                    eprintln!("{}", t(Msg::GcNothing));
+                    return Ok(());
+                }
+                eprintln!("{}", style::heading(t(Msg::GcPlanHeading)));
+                for asset in &r.errored_assets {
+                    match &asset.title {
+                        Some(title) => eprintln!("  - {} ({})", asset.asset_id, title),
+                        None => eprintln!("  - {}", asset.asset_id),
+                    }
+                }
+                for upload_id in &r.stale_uploads {
+                    eprintln!("  - {} (upload)", upload_id);
+                }
+                eprintln!();
+                eprintln!(
+                    "{}",
+                    tf(
+                        Msg::GcPlanSummary,
+                        &[&r.errored_assets.len(), &r.stale_uploads.len()]
+                    )
+                );
+                eprintln!("{}", style::hint(t(Msg::GcDryRunHint)));
+                return Ok(());
+            }
+
+            for asset_id in &r.deleted_assets {
+                eprintln!("{}", style::success(&tf(Msg::SyncDeleted, &[asset_id])));
+            }
+            for upload_id in &r.cancelled_uploads {
+                eprintln!("{}", style::success(&tf(Msg::GcCancelled, &[upload_id])));
+            }
+            for failure in &r.failed {
+                eprintln!("{}", style::error(&tf(Msg::GcFailed, &[&failure.target])));
+                eprintln!("  {}", failure.error);
+            }
+            eprintln!();
+            eprintln!(
+                "{}",
+                tf(
+                    Msg::GcSummary,
+                    &[
+                        &r.deleted_assets.len(),
+                        &r.cancelled_uploads.len(),
+                        &r.failed.len()
+                    ]
+                )
+            );
+        }
+        CommandResult::QueueAdd(r) => {
+            eprintln!();
+            eprintln!("{}", style::success(&tf(Msg::QueueAdded, &[&r.id])));
+            eprintln!("{}", tf(Msg::File, &[&r.file_path]));
+            eprintln!("{}", tf(Msg::QueuePending, &[&r.pending_count]));
+            eprintln!();
+            eprintln!("{}", style::hint(t(Msg::QueueRunHint)));
+        }
+        CommandResult::QueueRun(r) => {
+            eprintln!();
+            if r.uploaded.is_empty() && r.failed.is_empty() && r.remaining.is_empty() {
+                eprintln!("{}", t(Msg::QueueEmpty));
+                return Ok(());
+            }
 
-                // MP4生成中の場合のみ注記を表示
-                if matches!(r.mp4_status, Mp4Status::Generating) {
+            for upload in &r.uploaded {
+                print_uploaded_item(upload);
+            }
+
+            for failure in &r.failed {
+                eprintln!(
+                    "{}",
+                    style::error(&tf(Msg::QueueFailed, &[&failure.id, &failure.file_path]))
+                );
+                eprintln!("  {}", failure.error);
+                eprintln!("  {}", t(Msg::QueueRemoved));
+            }
+
+            if !r.remaining.is_empty() {
+                eprintln!();
+                eprintln!(
+                    "{}",
+                    style::warning(&tf(Msg::QueueRemaining, &[&r.remaining.len()]))
+                );
+                if let Some(last_error) = r.remaining.first().and_then(|i| i.last_error.as_ref()) {
+                    eprintln!("{}", tf(Msg::LastError, &[last_error]));
+                }
+            }
+        }
+        CommandResult::RetryList(r) => {
+            eprintln!();
+            if r.entries.is_empty() {
+                eprintln!("{}", t(Msg::RetryEmpty));
+                return Ok(());
+            }
+
+            eprintln!("{}", tf(Msg::RetryListHeading, &[&r.entries.len()]));
+            for entry in &r.entries {
+                eprintln!(
+                    "{}",
+                    tf(
+                        Msg::RetryEntry,
+                        &[&entry.id, &entry.file_path, &entry.attempts]
+                    )
+                );
+                eprintln!("  {}", tf(Msg::LastError, &[&entry.error]));
+            }
+            eprintln!();
+            eprintln!("{}", style::hint(t(Msg::RetryHint)));
+        }
+        CommandResult::Retry(r) => {
+            eprintln!();
+            if r.uploaded.is_empty() && r.failed.is_empty() {
+                eprintln!("{}", t(Msg::RetryEmpty));
+                return Ok(());
+            }
+
+            for upload in &r.uploaded {
+                print_uploaded_item(upload);
+            }
+
+            for failure in &r.failed {
+                eprintln!(
+                    "{}",
+                    style::error(&tf(Msg::RetryFailed, &[&failure.id, &failure.file_path]))
+                );
+                eprintln!("  {}", failure.error);
+            }
+
+            if r.remaining_count > 0 {
+                eprintln!();
+                eprintln!(
+                    "{}",
+                    style::warning(&tf(Msg::RetryRemaining, &[&r.remaining_count]))
+                );
+            }
+        }
+        CommandResult::History(r) => {
+            eprintln!();
+            if r.entries.is_empty() {
+                eprintln!("{}", t(Msg::HistoryEmpty));
+                return Ok(());
+            }
+
+            let user_config = vidyeet::config::user::UserConfig::load().ok();
+            eprintln!(
+                "{}",
+                tf(Msg::HistoryHeading, &[&r.entries.len(), &r.total_count])
+            );
+            for entry in &r.entries {
+                let recorded_at = match &user_config {
+                    Some(config) => {
+                        vidyeet::domain::formatter::format_timestamp(&entry.recorded_at, config)
+                    }
+                    None => entry.recorded_at.clone(),
+                };
+                let command_line = std::iter::once(entry.command.as_str())
+                    .chain(entry.args.iter().map(String::as_str))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let line = format!("#{:<4} {}  vidyeet {}", entry.id, recorded_at, command_line);
+                if entry.success {
+                    eprintln!("{} {}", style::success("✓"), line);
+                } else {
+                    eprintln!("{} {}", style::error("✗"), line);
+                    if let Some(error) = &entry.error {
+                        eprintln!("       {}", tf(Msg::LastError, &[error]));
+                    }
+                }
+            }
+            eprintln!();
+            eprintln!("{}", style::hint(t(Msg::HistoryRerunHint)));
+        }
+        CommandResult::Sign(r) => {
+            let user_config = vidyeet::config::user::UserConfig::load().ok();
+            let expires_at = match &user_config {
+                Some(config) => vidyeet::domain::formatter::format_timestamp(&r.expires_at, config),
+                None => r.expires_at.clone(),
+            };
+            eprintln!(
+                "{}",
+                tf(Msg::SignHeading, &[&r.url_type.name(), &expires_at])
+            );
+            eprintln!("{}", r.url);
+        }
+        CommandResult::Serve(r) => {
+            eprintln!();
+            eprintln!(
+                "{}",
+                style::success(&tf(Msg::ServeStopped, &[&r.page_views]))
+            );
+        }
+        CommandResult::Storyboard(r) => {
+            eprintln!();
+            eprintln!(
+                "{}",
+                tf(Msg::StoryboardHeading, &[&r.asset_id, &r.playback_id])
+            );
+            eprintln!("\n{}", t(Msg::StoryboardVttHeading));
+            eprintln!("{}", r.vtt_url);
+            eprintln!("\n{}", t(Msg::StoryboardSpriteHeading));
+            eprintln!("{}", r.sprite_url);
+            if let Some(download) = &r.download {
+                eprintln!();
+                eprintln!(
+                    "{}",
+                    style::success(&tf(
+                        Msg::StoryboardSaved,
+                        &[
+                            &download.tiles,
+                            &download.vtt_path,
+                            &download.sprite_path,
+                            &units::size(download.sprite_bytes)
+                        ]
+                    ))
+                );
+            }
+        }
+        CommandResult::RestrictionsCreate(r) => {
+            eprintln!();
+            eprintln!(
+                "{}",
+                style::success(&tf(Msg::RestrictionCreated, &[&r.restriction.id]))
+            );
+            print_restriction_details(&r.restriction);
+            eprintln!();
+            eprintln!(
+                "{}",
+                style::hint(&tf(Msg::RestrictionAttachHint, &[&r.restriction.id]))
+            );
+        }
+        CommandResult::RestrictionsList(r) => {
+            eprintln!();
+            if r.restrictions.is_empty() {
+                eprintln!("{}", t(Msg::RestrictionsEmpty));
+                return Ok(());
+            }
+
+            eprintln!("{}", tf(Msg::RestrictionsHeading, &[&r.total_count]));
+            for restriction in &r.restrictions {
+                eprintln!();
+                eprintln!("{}", style::heading(&restriction.id));
+                print_restriction_details(restriction);
+            }
+        }
+        CommandResult::RestrictionsDelete(r) => {
+            eprintln!();
+            eprintln!(
+                "{}",
+                style::success(&tf(Msg::RestrictionDeleted, &[&r.restriction_id]))
+            );
+        }
+        CommandResult::RestrictionsAttach(r) => {
+            eprintln!();
+            eprintln!(
+                "{}",
+                style::success(&tf(Msg::RestrictionAttached, &[&r.playback_id]))
+            );
+            eprintln!("{}", tf(Msg::AssetId, &[&r.asset_id]));
+            eprintln!(
+                "{}",
+                tf(Msg::RestrictionDomains, &[&r.allowed_domains.join(", ")])
+            );
+            eprintln!();
+            eprintln!(
+                "{}",
+                style::hint(&tf(
+                    Msg::RestrictionSignHint,
+                    &[&r.playback_id, &r.restriction_id]
+                ))
+            );
+        }
+        CommandResult::PlaybackRevoke(r) => {
+            eprintln!();
+            eprintln!(
+                "{}",
+                style::success(&tf(Msg::PlaybackRevoked, &[&r.revoked.id]))
+            );
+            eprintln!("{}", tf(Msg::AssetId, &[&r.asset_id]));
+            if let Some(replacement) = &r.replacement {
+                eprintln!(
+                    "{}",
+                    tf(
+                        Msg::PlaybackReplaced,
+                        &[&replacement.id, &replacement.policy]
+                    )
+                );
+            }
+            if !r.remaining_playback_ids.is_empty() {
+                let ids: Vec<&str> = r
+                    .remaining_playback_ids
+                    .iter()
+                    .map(|p| p.id.as_str())
+                    .collect();
+                eprintln!("{}", tf(Msg::PlaybackRemaining, &[&ids.join(", ")]));
+            }
+            if let Some(hls_url) = &r.hls_url {
+                eprintln!("\n{}", t(Msg::UploadHlsHeading));
+                eprintln!("{}", hls_url);
+            }
+        }
+        CommandResult::LiveResetKey(r) => {
+            eprintln!();
+            eprintln!(
+                "{}",
+                style::success(&tf(Msg::LiveKeyReset, &[&r.live_stream_id]))
+            );
+            eprintln!("{}", tf(Msg::Status, &[&r.status]));
+            eprintln!("{}", tf(Msg::LiveNewKey, &[&mask_secret(&r.stream_key)]));
+            eprintln!();
+            eprintln!(
+                "{}",
+                style::hint(&tf(Msg::LiveKeyHint, &[&r.live_stream_id]))
+            );
+        }
+        CommandResult::Batch(r) => print_batch(r),
+        CommandResult::Version(r) => {
+            eprintln!("{}", style::heading(&format!("vidyeet-cli {}", r.version)));
+            eprintln!("{}", tf(Msg::VersionCommit, &[&r.git_commit]));
+            eprintln!("{}", tf(Msg::VersionBuildDate, &[&r.build_date]));
+            eprintln!("{}", tf(Msg::VersionTarget, &[&r.target]));
+            eprintln!("{}", tf(Msg::VersionApiEndpoint, &[&r.api_endpoint]));
+            eprintln!("{}", tf(Msg::VersionTlsBackend, &[&r.tls_backend]));
+        }
+        CommandResult::Stats(r) => {
+            eprintln!();
+            eprintln!("{}", style::heading(t(Msg::StatsHeading)));
+            eprintln!("=============");
+            if r.total_count == 0 {
+                eprintln!("{}", t(Msg::ListEmpty));
+                return Ok(());
+            }
+            let user_config = UserConfig::load().ok();
+            eprintln!(
+                "{}",
+                tf(
+                    Msg::StatsAssets,
+                    &[&r.total_count, &format_counts(&r.by_status)]
+                )
+            );
+            eprintln!(
+                "{}",
+                tf(Msg::StatsQuality, &[&format_counts(&r.by_quality)])
+            );
+            eprintln!(
+                "{}",
+                tf(Msg::StatsResolution, &[&format_counts(&r.by_resolution)])
+            );
+            let average = r
+                .average_duration
+                .map(formatter::format_duration)
+                .unwrap_or_else(|| "-".to_string());
+            eprintln!(
+                "{}",
+                tf(
+                    Msg::StatsDuration,
+                    &[&formatter::format_duration(r.total_duration), &average]
+                )
+            );
+            if let Some(oldest) = &r.oldest {
+                eprintln!(
+                    "{}",
+                    tf(
+                        Msg::StatsOldest,
+                        &[&format_stats_asset(oldest, user_config.as_ref())]
+                    )
+                );
+            }
+            if let Some(newest) = &r.newest {
+                eprintln!(
+                    "{}",
+                    tf(
+                        Msg::StatsNewest,
+                        &[&format_stats_asset(newest, user_config.as_ref())]
+                    )
+                );
+            }
+            // 週ごとのアップロード数は直近の週のみ（機械可読出力はすべての週）
+            let recent_weeks = APP_CONFIG.stats.recent_weeks;
+            let skip = r.uploads_per_week.len().saturating_sub(recent_weeks);
+            eprintln!();
+            eprintln!("{}", tf(Msg::StatsUploadsPerWeek, &[&recent_weeks]));
+            for week in &r.uploads_per_week[skip..] {
+                eprintln!("  {}  {:>4}", week.week, week.count);
+            }
+        }
+        CommandResult::Whoami(r) => {
+            eprintln!("{}", tf(Msg::WhoamiProfile, &[&r.profile]));
+            match &r.token_id {
+                Some(token_id) => eprintln!("{}", tf(Msg::WhoamiTokenId, &[token_id])),
+                None => eprintln!("{}", style::warning(t(Msg::WhoamiNotLoggedIn))),
+            }
+            eprintln!("{}", tf(Msg::WhoamiApiEndpoint, &[&r.api_endpoint]));
+            eprintln!("{}", tf(Msg::WhoamiConfigPath, &[&r.config_path]));
+        }
+        CommandResult::Ping(r) => {
+            eprintln!(
+                "{}",
+                style::heading(&tf(Msg::PingHeader, &[&r.host, &r.request]))
+            );
+            for attempt in &r.attempts {
+                match &attempt.error {
+                    None => eprintln!(
+                        "{}",
+                        tf(
+                            Msg::PingReply,
+                            &[
+                                &attempt.seq,
+                                &attempt.status_code.unwrap_or_default(),
+                                &format!("{:.1}", attempt.latency_ms)
+                            ]
+                        )
+                    ),
+                    Some(error) => eprintln!(
+                        "{}",
+                        style::error(&tf(Msg::PingError, &[&attempt.seq, error]))
+                    ),
+                }
+            }
+
+            eprintln!();
+            eprintln!("{}", tf(Msg::PingStatisticsHeader, &[&r.host]));
+            let total = r.succeeded + r.failed;
+            let loss = if total == 0 {
+                0.0
+            } else {
+                r.failed as f64 * 100.0 / total as f64
+            };
+            let summary = tf(
+                Msg::PingSummary,
+                &[&total, &r.succeeded, &r.failed, &format!("{:.0}", loss)],
+            );
+            if r.failed == 0 {
+                eprintln!("{}", style::success(&summary));
+            } else {
+                eprintln!("{}", style::warning(&summary));
+            }
+            if let (Some(min), Some(avg), Some(max)) = (r.min_ms, r.avg_ms, r.max_ms) {
+                eprintln!(
+                    "{}",
+                    tf(
+                        Msg::PingLatency,
+                        &[&format!("{:.1}/{:.1}/{:.1}", min, avg, max)]
+                    )
+                );
+            }
+        }
+        CommandResult::Doctor(r) => {
+            eprintln!("{}", style::heading(t(Msg::DoctorHeader)));
+            for check in &r.checks {
+                let line = format!("{} {}", doctor_check_label(&check.name), check.message);
+                let line = match check.status {
+                    CheckStatus::Pass => style::success(&format!("✓ {}", line)),
+                    CheckStatus::Warn => style::warning(&format!("! {}", line)),
+                    CheckStatus::Fail => style::error(&format!("✗ {}", line)),
+                    CheckStatus::Skip => style::hint(&format!("- {}", line)),
+                };
+                eprintln!("{}", line);
+                if let Some(hint) = &check.hint {
+                    eprintln!("  {}", style::hint(&tf(Msg::Hint, &[hint])));
+                }
+            }
+
+            eprintln!();
+            let failed = r
+                .checks
+                .iter()
+                .filter(|check| check.status == CheckStatus::Fail)
+                .count();
+            if failed == 0 {
+                eprintln!("{}", style::success(t(Msg::DoctorPassed)));
+            } else {
+                eprintln!("{}", style::error(&tf(Msg::DoctorFailed, &[&failed])));
+            }
+        }
+        CommandResult::Help => {
+            eprintln!("{}", help_text());
+        }
+    }
+
+    Ok(())
+}
+
+/// 診断チェックの表示名
+fn doctor_check_label(name: &str) -> String {
+    let msg = match name {
+        "config" => Msg::DoctorConfig,
+        "credentials" => Msg::DoctorCredentials,
+        "api_dns" => Msg::DoctorApiDns,
+        "api_tls" => Msg::DoctorApiTls,
+        "stream_dns" => Msg::DoctorStreamDns,
+        "stream_tls" => Msg::DoctorStreamTls,
+        "clock" => Msg::DoctorClock,
+        "disk" => Msg::DoctorDisk,
+        _ => return format!("[{}]", name),
+    };
+    format!("[{}]", t(msg))
+}
+
+/// 再生制限の許可ドメインとリファラーなしの扱いを表示する
+fn print_restriction_details(restriction: &PlaybackRestriction) {
+    eprintln!(
+        "{}",
+        tf(
+            Msg::RestrictionDomains,
+            &[&restriction.referrer.allowed_domains.join(", ")]
+        )
+    );
+    if restriction.referrer.allow_no_referrer {
+        eprintln!("{}", t(Msg::RestrictionNoReferrerAllowed));
+    } else {
+        eprintln!("{}", t(Msg::RestrictionNoReferrerBlocked));
+    }
+}
+
+/// `--copy` でクリップボードにコピーしたURLの種類を表示する（コピーしていない場合は何も表示しない）
+fn print_copied(copied: Option<&CopiedUrl>) {
+    let Some(copied) = copied else {
+        return;
+    };
+    let message = match copied.target {
+        CopyTarget::Hls => Msg::CopiedHlsUrl,
+        CopyTarget::Mp4 => Msg::CopiedMp4Url,
+        CopyTarget::Thumbnail => Msg::CopiedThumbnailUrl,
+    };
+    eprintln!("\n{}", style::success(t(message)));
+}
+
+/// 警告を独立したセクションとして表示する（警告がない場合は何も表示しない）
+fn print_warnings(warnings: &[Warning]) {
+    if warnings.is_empty() {
+        return;
+    }
+    eprintln!(
+        "\n{}",
+        style::warning(&tf(Msg::WarningsHeading, &[&warnings.len()]))
+    );
+    for warning in warnings {
+        eprintln!("  - {}", warning);
+    }
+}
+
+/// 一括処理で成功した項目を表示する
+fn print_uploaded_item(upload: &UploadResult) {
+    eprintln!(
+        "{}",
+        style::success(&tf(Msg::ItemUploaded, &[&upload.file_path]))
+    );
+    eprintln!("  {}", tf(Msg::AssetId, &[&upload.asset_id]));
+    if let Some(hls_url) = &upload.hls_url {
+        eprintln!("  HLS URL: {}", hls_url);
+    }
+    for warning in &upload.warnings {
+        eprintln!("  {}", style::warning(&tf(Msg::Warning, &[warning])));
+    }
+}
+
+/// 一括処理の結果を表示する
+///
+/// 処理ごとの結果に続けて、所要時間を表示します（`--dry-run` の見積もりは予定のみ）。
+fn print_batch(batch: &BatchResult) {
+    match &batch.details {
+        BatchDetails::Upload(r) => {
+            eprintln!();
+            for upload in &r.uploaded {
+                print_uploaded_item(upload);
+            }
+
+            for failure in &r.failed {
+                // 試行回数0はアップロード前（フックなど）で失敗したファイル
+                if failure.attempts > 0 {
                     eprintln!(
-                        "\nNote: MP4 file is being generated in the background (usually 2-5 minutes)."
+                        "{}",
+                        style::error(&tf(
+                            Msg::BatchFailedAfter,
+                            &[&failure.file_path, &failure.attempts]
+                        ))
+                    );
+                } else {
+                    eprintln!(
+                        "{}",
+                        style::error(&tf(Msg::BatchFailed, &[&failure.file_path]))
                     );
-                    eprintln!("The URL above will be available once generation completes.");
-                    eprintln!("You can start streaming with HLS URL immediately!");
                 }
-            } else {
-                eprintln!("(not available)");
+                eprintln!("  {}", failure.error);
             }
 
-            eprintln!("---");
+            eprintln!();
+            eprintln!(
+                "{}",
+                tf(
+                    Msg::BatchSummary,
+                    &[&r.uploaded.len(), &r.failed.len(), &r.concurrency]
+                )
+            );
+        }
+        BatchDetails::Prune(r) => {
+            eprintln!();
+            if r.dry_run {
+                if r.to_delete.is_empty() {
+                    eprintln!("{}", tf(Msg::PruneNothing, &[&r.total_assets, &r.keep]));
+                    return;
+                }
+                eprintln!("{}", style::heading(&tf(Msg::PrunePlanHeading, &[&r.keep])));
+                for asset in &r.to_delete {
+                    match &asset.title {
+                        Some(title) => eprintln!("  - {} ({})", asset.asset_id, title),
+                        None => eprintln!("  - {}", asset.asset_id),
+                    }
+                }
+                eprintln!();
+                eprintln!(
+                    "{}",
+                    tf(
+                        Msg::PrunePlanSummary,
+                        &[&r.to_delete.len(), &r.total_assets]
+                    )
+                );
+                eprintln!("{}", style::hint(t(Msg::PruneDryRunHint)));
+                return;
+            }
+
+            for asset_id in &r.deleted {
+                eprintln!("{}", style::success(&tf(Msg::SyncDeleted, &[asset_id])));
+            }
+            for failure in &r.failed {
+                eprintln!(
+                    "{}",
+                    style::error(&tf(Msg::SyncDeleteFailed, &[&failure.asset_id]))
+                );
+                eprintln!("  {}", failure.error);
+            }
+            eprintln!();
+            eprintln!(
+                "{}",
+                tf(
+                    Msg::PruneSummary,
+                    &[&r.deleted.len(), &r.failed.len(), &r.keep]
+                )
+            );
+        }
+        BatchDetails::Sync(r) => {
+            eprintln!();
+            if r.dry_run {
+                eprintln!(
+                    "{}",
+                    style::heading(&tf(Msg::SyncPlanHeading, &[&r.directory]))
+                );
+                for file_path in &r.to_upload {
+                    eprintln!("  + {}", file_path);
+                }
+                for asset in &r.to_delete {
+                    match &asset.title {
+                        Some(title) => eprintln!("  - {} ({})", asset.asset_id, title),
+                        None => eprintln!("  - {}", asset.asset_id),
+                    }
+                }
+                eprintln!();
+                eprintln!(
+                    "{}",
+                    tf(
+                        Msg::SyncPlanSummary,
+                        &[&r.to_upload.len(), &r.to_delete.len(), &r.unchanged.len()]
+                    )
+                );
+                eprintln!("{}", style::hint(t(Msg::SyncDryRunHint)));
+                return;
+            }
+
+            for upload in &r.uploaded {
+                print_uploaded_item(upload);
+            }
+            for asset_id in &r.deleted {
+                eprintln!("{}", style::success(&tf(Msg::SyncDeleted, &[asset_id])));
+            }
+            for failure in &r.failed {
+                let message = match failure.action {
+                    SyncAction::Upload => Msg::BatchFailed,
+                    SyncAction::Delete => Msg::SyncDeleteFailed,
+                };
+                eprintln!("{}", style::error(&tf(message, &[&failure.target])));
+                eprintln!("  {}", failure.error);
+            }
+            eprintln!();
+            eprintln!(
+                "{}",
+                tf(
+                    Msg::SyncSummary,
+                    &[
+                        &r.uploaded.len(),
+                        &r.deleted.len(),
+                        &r.unchanged.len(),
+                        &r.failed.len()
+                    ]
+                )
+            );
+        }
+        BatchDetails::Backup(r) => {
+            eprintln!();
+            for file in &r.downloaded {
+                eprintln!(
+                    "{}",
+                    style::success(&tf(Msg::BackupDownloaded, &[&file.path]))
+                );
+            }
+            for failure in &r.failed {
+                eprintln!("{}", style::error(&tf(Msg::BatchFailed, &[&failure.path])));
+                eprintln!("  {}", failure.error);
+            }
+            if !r.signed_only.is_empty() {
+                eprintln!(
+                    "{}",
+                    style::warning(&tf(
+                        Msg::BackupSignedOnly,
+                        &[&r.signed_only.len(), &r.signed_only.join(", ")]
+                    ))
+                );
+            }
+            eprintln!();
+            eprintln!(
+                "{}",
+                tf(
+                    Msg::BackupSummary,
+                    &[
+                        &r.downloaded.len(),
+                        &units::size(r.downloaded_bytes),
+                        &r.up_to_date.len(),
+                        &r.failed.len(),
+                        &r.output_dir
+                    ]
+                )
+            );
+        }
+    }
+    eprintln!(
+        "{}",
+        tf(Msg::BatchDuration, &[&format!("{:.1}", batch.duration)])
+    );
+}
+
+/// 一覧の動画1件を表示する（stderr）
+///
+/// # Arguments
+/// * `number` - 表示する通し番号（1始まり）
+/// * `video` - 動画
+/// * `user_config` - 作成日時のタイムゾーンに使うユーザー設定（読み込めない場合はNone）
+fn print_list_video(number: usize, video: &VideoInfo, user_config: Option<&UserConfig>) {
+    eprintln!("---");
+    eprintln!("{}", tf(Msg::ListVideoNumber, &[&number]));
+    if let Some(title) = &video.title {
+        eprintln!("{}", style::heading(&tf(Msg::ListTitle, &[title])));
+    }
+    eprintln!("{}", tf(Msg::AssetId, &[&video.asset_id]));
+    if let Some(external_id) = &video.external_id {
+        eprintln!("{}", tf(Msg::ListExternalId, &[external_id]));
+    }
+    if !video.tags.is_empty() {
+        eprintln!("{}", tf(Msg::ListTags, &[&video.tags.join(", ")]));
+    }
+    eprintln!(
+        "{}",
+        style::asset_status(&video.status, &tf(Msg::Status, &[&video.status]))
+    );
+
+    if let Some(duration) = video.duration {
+        eprintln!(
+            "{}",
+            tf(Msg::Duration, &[&formatter::format_duration(duration)])
+        );
+    }
+
+    if let Some(aspect_ratio) = &video.aspect_ratio {
+        eprintln!("{}", tf(Msg::AspectRatio, &[aspect_ratio]));
+    }
+
+    if let Some(hls_url) = &video.hls_url {
+        eprintln!("HLS URL: {}", hls_url);
+    }
+    if let Some(mp4_url) = &video.mp4_url {
+        eprintln!("MP4 URL: {}", mp4_url);
+    }
+
+    // 作成日時をフォーマット（ユーザー設定のタイムゾーンを使用）
+    let formatted_time = format_created_at(&video.created_at, user_config);
+    eprintln!("{}", tf(Msg::Created, &[&formatted_time]));
+    eprintln!();
+}
+
+/// 一覧の集計をフッターとして表示する（stderr）
+///
+/// 例: "Total: 3 video(s) (errored 1, ready 2), duration 1:02:03, approx. storage 1.50 GB"
+fn print_list_summary(summary: &ListSummary) {
+    let count: usize = summary.status_counts.values().sum();
+    let mut footer = tf(
+        Msg::ListSummary,
+        &[
+            &count,
+            &format_counts(&summary.status_counts),
+            &formatter::format_duration(summary.total_duration),
+        ],
+    );
+    if let Some(bytes) = summary.approx_storage_bytes {
+        footer.push_str(&tf(Msg::ListSummaryStorage, &[&units::size(bytes)]));
+    }
+    eprintln!("{}", footer);
+}
+
+/// 値ごとの件数を「errored 1, ready 2」の形式にする
+fn format_counts(counts: &BTreeMap<String, usize>) -> String {
+    counts
+        .iter()
+        .map(|(key, count)| format!("{} {}", key, count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 統計で示すアセットを1行にする（例: "abc123 \"Intro\" (2025-11-30 01:49:10 +09:00)"）
+fn format_stats_asset(asset: &StatsAsset, user_config: Option<&UserConfig>) -> String {
+    let created_at = format_created_at(&asset.created_at, user_config);
+    match &asset.title {
+        Some(title) => format!("{} \"{}\" ({})", asset.asset_id, title, created_at),
+        None => format!("{} ({})", asset.asset_id, created_at),
+    }
+}
+
+/// 作成日時を表示する文字列（例: "2025-11-30 01:49:10 +09:00 (3 hours ago)"）
+///
+/// `--relative-time` 指定時は相対時刻のみを表示します。
+/// タイムスタンプを解析できない場合は元の文字列をそのまま表示します。
+fn format_created_at(created_at: &str, user_config: Option<&UserConfig>) -> String {
+    let relative = formatter::elapsed_since(created_at, Utc::now()).map(format_elapsed);
+    let absolute = match user_config {
+        Some(config) => formatter::format_timestamp(created_at, config),
+        None => created_at.to_string(),
+    };
+    match relative {
+        Some(relative) if relative_time_only() => relative,
+        Some(relative) => tf(Msg::WithRelativeTime, &[&absolute, &relative]),
+        None => absolute,
+    }
+}
+
+/// 経過時間を表示言語の文言にする（例: "3 hours ago"）
+fn format_elapsed(elapsed: Elapsed) -> String {
+    let (one, many, n) = match elapsed {
+        Elapsed::JustNow => return t(Msg::RelativeJustNow).to_string(),
+        Elapsed::Minutes(n) => (Msg::RelativeMinute, Msg::RelativeMinutes, n),
+        Elapsed::Hours(n) => (Msg::RelativeHour, Msg::RelativeHours, n),
+        Elapsed::Days(n) => (Msg::RelativeDay, Msg::RelativeDays, n),
+        Elapsed::Months(n) => (Msg::RelativeMonth, Msg::RelativeMonths, n),
+        Elapsed::Years(n) => (Msg::RelativeYear, Msg::RelativeYears, n),
+    };
+    if n == 1 {
+        t(one).to_string()
+    } else {
+        tf(many, &[&n])
+    }
+}
 
-            // 削除した動画がある場合
-            if r.deleted_old_videos > 0 {
-                eprintln!(
-                    "\nNote: Deleted {} old video(s) because the video limit for your plan was reached.",
-                    r.deleted_old_videos
-                );
-            }
+/// メディア解析結果を1行に要約する
+///
+/// 例: "h264 / aac, 1920x1080, 12.5s, 5.24 Mbps"
+fn format_media_summary(media: &MediaProbe) -> String {
+    let mut parts = vec![
+        match &media.audio_codec {
+            Some(audio) => format!("{} / {}", media.video_codec, audio),
+            None => tf(Msg::NoAudio, &[&media.video_codec]),
+        },
+        format!("{}x{}", media.width, media.height),
+    ];
+    if let Some(duration) = media.duration_secs {
+        parts.push(format!("{:.1}s", duration));
+    }
+    if let Some(bit_rate) = media.bit_rate {
+        parts.push(format!("{:.2} Mbps", bit_rate as f64 / BITS_PER_MEGABIT));
+    }
+    parts.join(", ")
+}
+
+/// Muxが処理した最終的なメディア情報を1行に要約する
+///
+/// 例: "1:23, 1080p, 16:9, video 1920x1080 29.97fps, audio stereo"
+fn format_final_media(media: &FinalMedia) -> String {
+    let mut parts = Vec::new();
+    if let Some(duration) = media.duration {
+        parts.push(formatter::format_duration(duration));
+    }
+    parts.extend(media.resolution_tier.clone());
+    parts.extend(media.aspect_ratio.clone());
+    for track in &media.tracks {
+        let mut summary = track.track_type.clone();
+        if let (Some(width), Some(height)) = (track.width, track.height) {
+            summary.push_str(&format!(" {}x{}", width, height));
         }
-        CommandResult::Delete(r) => {
-            eprintln!();
-            eprintln!("✓ Asset deleted successfully!");
-            eprintln!("Asset ID: {}", r.asset_id);
-            eprintln!();
-            eprintln!("The video and all its data have been permanently removed.");
+        if let Some(frame_rate) = track.frame_rate {
+            summary.push_str(&format!(" {}fps", frame_rate));
         }
-        CommandResult::Help => {
-            eprintln!("{}", HELP_TEXT);
+        if let Some(layout) = &track.channel_layout {
+            summary.push_str(&format!(" {}", layout));
         }
+        parts.push(summary);
     }
-
-    Ok(())
+    parts.join(", ")
 }
 
 /// 機械可読JSONを出力（stdout）
@@ -310,7 +2173,176 @@ fn output_human_readable(result: &CommandResult) -> Result<()> {
 /// スクリプトやパイプライン処理のために、
 /// コマンド結果を構造化されたJSON形式で出力します。
 fn output_machine_readable(result: &CommandResult) -> Result<()> {
-    let json = match result {
+    print_machine_json(machine_json(result))
+}
+
+/// 機械可読JSONを1行でstdoutへ出力する
+///
+/// `--query` が指定された場合は、式の結果の値だけを出力します。
+pub fn print_machine_json(json: serde_json::Value) -> Result<()> {
+    match query::active() {
+        // --query: 式の結果の値だけを1行ずつ出力
+        Some(expr) => {
+            for line in query::run(expr, json)? {
+                println!("{}", line);
+            }
+        }
+        None => println!("{}", serde_json::to_string(&json)?),
+    }
+    Ok(())
+}
+
+/// コマンド結果の機械可読JSONを構築
+///
+/// `--machine`出力と、フック（config.tomlの`[hooks]`）の標準入力で共通に使用します。
+pub fn machine_json(result: &CommandResult) -> serde_json::Value {
+    let mut json = command_json(result);
+    json["warnings"] = serde_json::json!(result.warnings());
+    if iso_duration() {
+        add_iso_durations(&mut json);
+    }
+    schema::with_schema_version(json)
+}
+
+/// 再生時間のフィールドにISO 8601の期間を併記する（`--iso-duration`）
+///
+/// 名前が `duration` で終わる数値のフィールド（`duration`、`total_duration`）ごとに、
+/// `<名前>_iso8601` を加えます（例: `"duration": 4530.0` → `"duration_iso8601": "PT1H15M30S"`）。
+/// Muxの完全データ（`data`）やトラックを含め、入れ子のオブジェクトもすべて対象です。
+fn add_iso_durations(json: &mut serde_json::Value) {
+    match json {
+        serde_json::Value::Object(object) => {
+            let iso: Vec<(String, serde_json::Value)> = object
+                .iter()
+                .filter(|(key, _)| key.ends_with("duration"))
+                .filter_map(|(key, value)| {
+                    let secs = value.as_f64()?;
+                    Some((
+                        format!("{}_iso8601", key),
+                        serde_json::json!(formatter::format_iso8601_duration(secs)),
+                    ))
+                })
+                .collect();
+            for value in object.values_mut() {
+                add_iso_durations(value);
+            }
+            object.extend(iso);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(add_iso_durations),
+        _ => {}
+    }
+}
+
+/// `list --group-by` の機械可読JSONを入れ子にする
+///
+/// 一覧の配列（`data` または `videos`）をグループごとに分けて `groups` の各要素へ移し、
+/// `group_by` を加えます。グループ化していない場合は何もしません。
+fn group_list_json(json: &mut serde_json::Value, result: &ListResult) {
+    let Some(group_by) = result.group_by else {
+        return;
+    };
+    // 完全データのアセットIDは `id`、簡略データは `asset_id`
+    let (items_key, id_key) = if result.raw_assets.is_some() {
+        ("data", "id")
+    } else {
+        ("videos", "asset_id")
+    };
+    let Some(serde_json::Value::Array(items)) = json
+        .as_object_mut()
+        .and_then(|object| object.remove(items_key))
+    else {
+        return;
+    };
+    let mut items: HashMap<String, serde_json::Value> = items
+        .into_iter()
+        .filter_map(|item| Some((item.get(id_key)?.as_str()?.to_string(), item)))
+        .collect();
+
+    let groups: Vec<serde_json::Value> = result
+        .groups
+        .iter()
+        .map(|group| {
+            let group_items: Vec<serde_json::Value> = group
+                .asset_ids
+                .iter()
+                .filter_map(|asset_id| items.remove(asset_id))
+                .collect();
+            serde_json::json!({
+                "key": group.key,
+                "count": group.count,
+                "total_duration": group.total_duration,
+                items_key: group_items
+            })
+        })
+        .collect();
+    json["group_by"] = serde_json::json!(group_by.name());
+    json["groups"] = serde_json::json!(groups);
+}
+
+/// 一括処理の機械可読JSON
+///
+/// 処理ごとのフィールドに、共通の `status`・`items`・件数・所要時間を加えます。
+fn batch_json(batch: &BatchResult) -> serde_json::Value {
+    let mut json = match &batch.details {
+        BatchDetails::Upload(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "batch_upload",
+                "concurrency": r.concurrency,
+                "uploaded": r.uploaded,
+                "failed": r.failed
+            })
+        }
+        BatchDetails::Prune(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "prune",
+                "keep": r.keep,
+                "dry_run": r.dry_run,
+                "total_assets": r.total_assets,
+                "to_delete": r.to_delete,
+                "deleted": r.deleted,
+                "failed": r.failed
+            })
+        }
+        BatchDetails::Sync(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "sync",
+                "directory": r.directory,
+                "dry_run": r.dry_run,
+                "unchanged": r.unchanged,
+                "to_upload": r.to_upload,
+                "to_delete": r.to_delete,
+                "uploaded": r.uploaded,
+                "deleted": r.deleted,
+                "failed": r.failed
+            })
+        }
+        BatchDetails::Backup(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "backup",
+                "output_dir": r.output_dir,
+                "downloaded": r.downloaded,
+                "up_to_date": r.up_to_date,
+                "signed_only": r.signed_only,
+                "failed": r.failed,
+                "downloaded_bytes": r.downloaded_bytes
+            })
+        }
+    };
+    json["status"] = serde_json::json!(batch.status());
+    json["items"] = serde_json::json!(batch.items);
+    json["succeeded_count"] = serde_json::json!(batch.succeeded);
+    json["failed_count"] = serde_json::json!(batch.failed);
+    json["duration_secs"] = serde_json::json!(batch.duration);
+    json
+}
+
+/// コマンドごとの機械可読JSON（`schema_version` と `warnings` を除く）
+fn command_json(result: &CommandResult) -> serde_json::Value {
+    match result {
         CommandResult::Login(r) => {
             serde_json::json!({
                 "success": true,
@@ -331,35 +2363,68 @@ fn output_machine_readable(result: &CommandResult) -> Result<()> {
                 "success": true,
                 "command": "status",
                 "is_authenticated": r.is_authenticated,
-                "token_id": r.token_id
+                "token_id": r.token_id,
+                "environment": r.environment,
+                "asset_count": r.asset_count,
+                "asset_limit": r.asset_limit,
+                "remaining_capacity": r.remaining_capacity,
+                "capacity_policy": r.capacity_policy
             })
         }
         CommandResult::List(r) => {
             // raw_assetsがある場合（--machine フラグ時）は完全データを出力
-            if let Some(raw_assets) = &r.raw_assets {
-                serde_json::json!({
+            let mut json = if let Some(raw_assets) = &r.raw_assets {
+                let mut json = serde_json::json!({
                     "success": true,
                     "command": "list",
                     "data": raw_assets,
                     "total_count": r.total_count
-                })
+                });
+                // 再生ポリシーがsignedのアセットは署名付きURLを別途出力（完全データには含まれないため）
+                let signed_urls: serde_json::Map<String, serde_json::Value> = r
+                    .videos
+                    .iter()
+                    .filter(|v| v.playback_policy.as_deref() == Some(SIGNED_POLICY))
+                    .filter(|v| v.hls_url.is_some())
+                    .map(|v| {
+                        (
+                            v.asset_id.clone(),
+                            serde_json::json!({ "hls_url": v.hls_url, "mp4_url": v.mp4_url }),
+                        )
+                    })
+                    .collect();
+                if !signed_urls.is_empty() {
+                    json["signed_urls"] = serde_json::Value::Object(signed_urls);
+                }
+                json
             } else {
-                // 簡略版を出力（人間向けの互換性維持）
-                serde_json::json!({
+                // 簡略版を出力（人間向けの互換性維持、--offline時）
+                let mut json = serde_json::json!({
                     "success": true,
                     "command": "list",
                     "videos": r.videos,
                     "total_count": r.total_count
-                })
-            }
+                });
+                if let Some(synced_at) = &r.synced_at {
+                    json["offline"] = serde_json::json!(true);
+                    json["synced_at"] = serde_json::json!(synced_at);
+                }
+                json
+            };
+            json["summary"] = serde_json::json!(r.summary());
+            group_list_json(&mut json, r);
+            json
         }
         CommandResult::Show(r) => {
             // raw_assetがある場合は完全データを出力
-            if let Some(raw_asset) = &r.raw_asset {
+            let mut json = if let Some(raw_asset) = &r.raw_asset {
                 serde_json::json!({
                     "success": true,
                     "command": "show",
-                    "data": raw_asset
+                    "data": raw_asset,
+                    "hls_url": r.hls_url,
+                    "mp4_url": r.mp4_url,
+                    "thumbnail_url": r.thumbnail_url
                 })
             } else {
                 // 簡略版を出力（互換性維持）
@@ -375,10 +2440,18 @@ fn output_machine_readable(result: &CommandResult) -> Result<()> {
                     "playback_ids": r.playback_ids,
                     "hls_url": r.hls_url,
                     "mp4_url": r.mp4_url,
+                    "thumbnail_url": r.thumbnail_url,
                     "tracks": r.tracks,
                     "static_renditions": r.static_renditions
                 })
+            };
+            if let Some(images) = &r.signed_image_urls {
+                json["signed_image_urls"] = serde_json::json!(images);
             }
+            if let Some(copied) = &r.copied {
+                json["copied"] = serde_json::json!(copied);
+            }
+            json
         }
         CommandResult::Upload(r) => {
             serde_json::json!({
@@ -386,13 +2459,33 @@ fn output_machine_readable(result: &CommandResult) -> Result<()> {
                 "command": "upload",
                 "asset_id": r.asset_id,
                 "playback_id": r.playback_id,
+                "playback_policy": r.playback_policy,
                 "hls_url": r.hls_url,
                 "mp4_url": r.mp4_url,
                 "mp4_status": r.mp4_status,
+                "thumbnail_url": r.thumbnail_url,
                 "file_path": r.file_path,
                 "file_size": r.file_size,
                 "file_format": r.file_format,
-                "deleted_old_videos": r.deleted_old_videos
+                "deleted_old_videos": r.deleted_old_videos,
+                "deleted_assets": r.deleted_assets,
+                "media": r.media,
+                "sha256": r.sha256,
+                "uploaded_at": r.uploaded_at,
+                "timings": r.timings,
+                "final_media": r.final_media,
+                "copied": r.copied
+            })
+        }
+        CommandResult::UploadEstimate(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "upload",
+                "dry_run": true,
+                "file_path": r.file_path,
+                "file_format": r.file_format,
+                "estimate": r.estimate,
+                "media": r.media
             })
         }
         CommandResult::Delete(r) => {
@@ -402,24 +2495,312 @@ fn output_machine_readable(result: &CommandResult) -> Result<()> {
                 "asset_id": r.asset_id
             })
         }
+        CommandResult::Rename(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "rename",
+                "asset_id": r.asset_id,
+                "old_title": r.old_title,
+                "new_title": r.new_title
+            })
+        }
+        CommandResult::Tag(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "tag",
+                "asset_id": r.asset_id,
+                "action": r.action,
+                "tag": r.tag,
+                "changed": r.changed,
+                "tags": r.tags
+            })
+        }
+        CommandResult::Renditions(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "renditions",
+                "asset_id": r.asset_id,
+                "action": r.action,
+                "changed": r.changed,
+                "rendition": r.rendition,
+                "mp4_url": r.mp4_url
+            })
+        }
+        CommandResult::Captions(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "captions",
+                "asset_id": r.asset_id,
+                "action": r.action,
+                "source_track_id": r.source_track_id,
+                "tracks": r.tracks
+            })
+        }
+        CommandResult::Wait(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "wait",
+                "asset_id": r.asset_id,
+                "upload_id": r.upload_id,
+                "until": r.until,
+                "status": r.status,
+                "elapsed_secs": r.elapsed_secs,
+                "playback_id": r.playback_id,
+                "playback_policy": r.playback_policy,
+                "hls_url": r.hls_url,
+                "mp4_url": r.mp4_url
+            })
+        }
+        CommandResult::Export(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "export",
+                "output_path": r.output_path,
+                "format": r.format,
+                "asset_count": r.asset_count,
+                "resumed_assets": r.resumed_assets
+            })
+        }
+        CommandResult::Import(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "import",
+                "status": r.status(),
+                "source_path": r.source_path,
+                "id_map": r.id_map(),
+                "imported": r.imported,
+                "failed": r.failed
+            })
+        }
+        CommandResult::Gc(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "gc",
+                "status": r.status(),
+                "dry_run": r.dry_run,
+                "errored_assets": r.errored_assets,
+                "stale_uploads": r.stale_uploads,
+                "deleted_assets": r.deleted_assets,
+                "cancelled_uploads": r.cancelled_uploads,
+                "failed": r.failed
+            })
+        }
+        CommandResult::QueueAdd(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "queue_add",
+                "id": r.id,
+                "file_path": r.file_path,
+                "pending_count": r.pending_count
+            })
+        }
+        CommandResult::QueueRun(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "queue_run",
+                "status": r.status(),
+                "items": r.items(),
+                "uploaded": r.uploaded,
+                "failed": r.failed,
+                "remaining": r.remaining
+            })
+        }
+        CommandResult::Retry(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "retry",
+                "status": r.status(),
+                "items": r.items(),
+                "uploaded": r.uploaded,
+                "failed": r.failed,
+                "remaining_count": r.remaining_count
+            })
+        }
+        CommandResult::RetryList(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "retry_list",
+                "entries": r.entries
+            })
+        }
+        CommandResult::History(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "history",
+                "total_count": r.total_count,
+                "entries": r.entries
+            })
+        }
+        CommandResult::Sign(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "sign",
+                "playback_id": r.playback_id,
+                "url_type": r.url_type,
+                "url": r.url,
+                "expires_at": r.expires_at,
+                "playback_restriction_id": r.playback_restriction_id
+            })
+        }
+        CommandResult::Serve(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "serve",
+                "asset_id": r.asset_id,
+                "url": r.url,
+                "playback_id": r.playback_id,
+                "playback_policy": r.playback_policy,
+                "hls_url": r.hls_url,
+                "expires_at": r.expires_at,
+                "page_views": r.page_views
+            })
+        }
+        CommandResult::Storyboard(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "storyboard",
+                "asset_id": r.asset_id,
+                "playback_id": r.playback_id,
+                "playback_policy": r.playback_policy,
+                "vtt_url": r.vtt_url,
+                "sprite_url": r.sprite_url,
+                "expires_at": r.expires_at,
+                "download": r.download
+            })
+        }
+        CommandResult::RestrictionsCreate(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "restrictions_create",
+                "restriction": r.restriction
+            })
+        }
+        CommandResult::RestrictionsList(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "restrictions_list",
+                "total_count": r.total_count,
+                "restrictions": r.restrictions
+            })
+        }
+        CommandResult::RestrictionsDelete(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "restrictions_delete",
+                "restriction_id": r.restriction_id
+            })
+        }
+        CommandResult::RestrictionsAttach(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "restrictions_attach",
+                "asset_id": r.asset_id,
+                "playback_id": r.playback_id,
+                "policy": r.policy,
+                "restriction_id": r.restriction_id,
+                "allowed_domains": r.allowed_domains
+            })
+        }
+        CommandResult::PlaybackRevoke(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "playback_revoke",
+                "asset_id": r.asset_id,
+                "revoked": r.revoked,
+                "replacement": r.replacement,
+                "hls_url": r.hls_url,
+                "remaining_playback_ids": r.remaining_playback_ids
+            })
+        }
+        CommandResult::LiveResetKey(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "live_reset_key",
+                "live_stream_id": r.live_stream_id,
+                "status": r.status,
+                "stream_key": r.stream_key
+            })
+        }
+        CommandResult::Batch(r) => batch_json(r),
+        CommandResult::Version(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "version",
+                "version": r.version,
+                "git_commit": r.git_commit,
+                "build_date": r.build_date,
+                "target": r.target,
+                "api_endpoint": r.api_endpoint,
+                "tls_backend": r.tls_backend
+            })
+        }
+        CommandResult::Stats(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "stats",
+                "total_count": r.total_count,
+                "by_status": r.by_status,
+                "by_quality": r.by_quality,
+                "by_resolution": r.by_resolution,
+                "total_duration": r.total_duration,
+                "average_duration": r.average_duration,
+                "uploads_per_week": r.uploads_per_week,
+                "oldest": r.oldest,
+                "newest": r.newest
+            })
+        }
+        CommandResult::Whoami(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "whoami",
+                "profile": r.profile,
+                "is_logged_in": r.is_logged_in,
+                "token_id": r.token_id,
+                "api_endpoint": r.api_endpoint,
+                "config_path": r.config_path
+            })
+        }
+        CommandResult::Ping(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "ping",
+                "status": r.status(),
+                "host": r.host,
+                "request": r.request,
+                "succeeded": r.succeeded,
+                "failed": r.failed,
+                "min_ms": r.min_ms,
+                "avg_ms": r.avg_ms,
+                "max_ms": r.max_ms,
+                "attempts": r.attempts
+            })
+        }
+        CommandResult::Doctor(r) => {
+            serde_json::json!({
+                "success": true,
+                "command": "doctor",
+                "passed": r.passed(),
+                "checks": r.checks
+            })
+        }
         CommandResult::Help => {
             serde_json::json!({
                 "success": true,
                 "command": "help"
             })
         }
-    };
-
-    println!("{}", serde_json::to_string(&json)?);
-    Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::commands::result::{
-        ListResult, LoginResult, LogoutResult, Mp4Status, StatusResult, UploadResult,
+    use serde_json::json;
+    use vidyeet::commands::result::{
+        GroupBy, ListResult, LoginResult, LogoutResult, Mp4Status, StatusResult, TrackSummary,
+        UploadResult,
     };
+    use vidyeet::domain::warning::WarningKind;
 
     #[test]
     fn test_output_machine_readable_login() {
@@ -444,10 +2825,14 @@ mod tests {
 
     #[test]
     fn test_output_machine_readable_status_authenticated() {
-        let result = CommandResult::Status(StatusResult {
-            is_authenticated: true,
-            token_id: Some("test_token_masked".to_string()),
-        });
+        let result = CommandResult::Status(StatusResult::authenticated(
+            "test_token_masked".to_string(),
+            Some("Production".to_string()),
+            Some(7),
+            Some(10),
+            CapacityPolicy::Ask,
+            Vec::new(),
+        ));
 
         let output = output_machine_readable(&result);
         assert!(output.is_ok());
@@ -459,25 +2844,135 @@ mod tests {
             videos: vec![],
             total_count: 0,
             raw_assets: None,
+            synced_at: None,
+            group_by: None,
+            groups: Vec::new(),
+            warnings: Vec::new(),
         });
 
         let output = output_machine_readable(&result);
         assert!(output.is_ok());
     }
 
+    #[test]
+    fn test_add_iso_durations() {
+        let mut json = serde_json::json!({
+            "data": [{
+                "id": "a",
+                "duration": 4530.0,
+                "tracks": [{ "type": "video", "duration": 90.5 }]
+            }],
+            "groups": [{ "key": "ready", "total_duration": 12.25 }],
+            "final_media": { "duration": null }
+        });
+        add_iso_durations(&mut json);
+
+        assert_eq!(json["data"][0]["duration"], 4530.0);
+        assert_eq!(json["data"][0]["duration_iso8601"], "PT1H15M30S");
+        assert_eq!(
+            json["data"][0]["tracks"][0]["duration_iso8601"],
+            "PT1M30.5S"
+        );
+        assert_eq!(json["groups"][0]["total_duration_iso8601"], "PT12.25S");
+        assert!(json["final_media"].get("duration_iso8601").is_none());
+    }
+
+    #[test]
+    fn test_machine_json_list_grouped() {
+        let video = |id: &str, status: &str, duration: Option<f64>| VideoInfo {
+            asset_id: id.to_string(),
+            title: None,
+            external_id: None,
+            tags: Vec::new(),
+            status: status.to_string(),
+            playback_id: None,
+            playback_policy: None,
+            hls_url: None,
+            mp4_url: None,
+            duration,
+            created_at: "1764434950".to_string(),
+            aspect_ratio: None,
+            storage_bytes: None,
+        };
+        let mut list = ListResult {
+            videos: vec![
+                video("a", "ready", Some(10.0)),
+                video("b", "errored", None),
+                video("c", "ready", Some(5.5)),
+            ],
+            total_count: 3,
+            raw_assets: None,
+            synced_at: None,
+            group_by: None,
+            groups: Vec::new(),
+            warnings: Vec::new(),
+        };
+        list.group(GroupBy::Status, 0);
+
+        let json = machine_json(&CommandResult::List(list));
+        assert_eq!(json["group_by"], "status");
+        assert_eq!(json["total_count"], 3);
+        assert!(json.get("videos").is_none());
+        assert_eq!(json["groups"][0]["key"], "ready");
+        assert_eq!(json["groups"][0]["count"], 2);
+        assert_eq!(json["groups"][0]["total_duration"], 15.5);
+        assert_eq!(json["groups"][0]["videos"][1]["asset_id"], "c");
+        assert_eq!(json["groups"][1]["videos"][0]["asset_id"], "b");
+        assert_eq!(json["summary"]["status_counts"]["ready"], 2);
+        assert_eq!(json["summary"]["total_duration"], 15.5);
+        assert!(json["summary"]["approx_storage_bytes"].is_null());
+    }
+
+    #[test]
+    fn test_machine_json_includes_warnings() {
+        let result = CommandResult::List(ListResult {
+            videos: vec![],
+            total_count: 0,
+            raw_assets: None,
+            synced_at: None,
+            group_by: None,
+            groups: Vec::new(),
+            warnings: vec![Warning::new(
+                WarningKind::SignedUrlsUnavailable,
+                "1 asset(s) have a signed playback policy",
+            )],
+        });
+        let json = machine_json(&result);
+        assert_eq!(json["warnings"][0]["code"], "signed_urls_unavailable");
+        assert_eq!(
+            json["warnings"][0]["message"],
+            "1 asset(s) have a signed playback policy"
+        );
+
+        // 警告のないコマンドも空の配列を出力
+        assert_eq!(machine_json(&CommandResult::Help)["warnings"], json!([]));
+    }
+
     #[test]
     fn test_output_machine_readable_upload() {
-        let result = CommandResult::Upload(UploadResult {
+        let result = CommandResult::Upload(Box::new(UploadResult {
             asset_id: "test_asset_123".to_string(),
             playback_id: Some("test_playback_123".to_string()),
+            playback_policy: None,
             hls_url: Some("https://stream.mux.com/test.m3u8".to_string()),
             mp4_url: Some("https://stream.mux.com/test/highest.mp4".to_string()),
             mp4_status: Mp4Status::Ready,
+            thumbnail_url: Some(
+                "https://image.mux.com/test_playback_123/thumbnail.jpg?time=3".to_string(),
+            ),
             file_path: "/path/to/video.mp4".to_string(),
             file_size: 10485760,
             file_format: "mp4".to_string(),
             deleted_old_videos: 0,
-        });
+            deleted_assets: Vec::new(),
+            media: None,
+            warnings: Vec::new(),
+            sha256: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855".to_string(),
+            uploaded_at: "2024-01-01T00:00:00Z".to_string(),
+            timings: Default::default(),
+            final_media: None,
+            copied: None,
+        }));
 
         let output = output_machine_readable(&result);
         assert!(output.is_ok());
@@ -519,4 +3014,89 @@ mod tests {
         let output = output_result(&result, false);
         assert!(output.is_ok());
     }
+
+    #[test]
+    fn test_format_media_summary() {
+        let mut media = MediaProbe {
+            format_name: "mov,mp4,m4a,3gp,3g2,mj2".to_string(),
+            duration_secs: Some(12.5),
+            bit_rate: Some(5_240_000),
+            video_codec: "h264".to_string(),
+            width: 1920,
+            height: 1080,
+            pix_fmt: None,
+            field_order: None,
+            color_transfer: None,
+            audio_codec: Some("aac".to_string()),
+        };
+        assert_eq!(
+            format_media_summary(&media),
+            "h264 / aac, 1920x1080, 12.5s, 5.24 Mbps"
+        );
+
+        media.audio_codec = None;
+        media.duration_secs = None;
+        media.bit_rate = None;
+        // 音声なしの表記は表示言語による
+        assert_eq!(
+            format_media_summary(&media),
+            format!("{}, 1920x1080", tf(Msg::NoAudio, &[&"h264"]))
+        );
+    }
+
+    #[test]
+    fn test_format_final_media() {
+        let media = FinalMedia {
+            duration: Some(83.4),
+            resolution_tier: Some("1080p".to_string()),
+            aspect_ratio: Some("16:9".to_string()),
+            tracks: vec![
+                TrackSummary {
+                    track_type: "video".to_string(),
+                    width: Some(1920),
+                    height: Some(1080),
+                    frame_rate: Some(29.97),
+                    channel_layout: None,
+                },
+                TrackSummary {
+                    track_type: "audio".to_string(),
+                    width: None,
+                    height: None,
+                    frame_rate: None,
+                    channel_layout: Some("stereo".to_string()),
+                },
+            ],
+        };
+        assert_eq!(
+            format_final_media(&media),
+            "1:23, 1080p, 16:9, video 1920x1080 29.97fps, audio stereo"
+        );
+    }
+
+    #[test]
+    fn test_format_elapsed_singular_and_plural() {
+        assert_eq!(format_elapsed(Elapsed::JustNow), t(Msg::RelativeJustNow));
+        assert_eq!(format_elapsed(Elapsed::Hours(1)), t(Msg::RelativeHour));
+        assert_eq!(
+            format_elapsed(Elapsed::Hours(3)),
+            tf(Msg::RelativeHours, &[&3])
+        );
+        assert_eq!(
+            format_elapsed(Elapsed::Years(2)),
+            tf(Msg::RelativeYears, &[&2])
+        );
+        assert_eq!(
+            Msg::RelativeHours.text(Locale::En).replace("{}", "3"),
+            "3 hours ago"
+        );
+    }
+
+    #[test]
+    fn test_mask_secret() {
+        assert_eq!(
+            mask_secret("a1b2c3d4-e5f6-7890-abcd-ef1234567890"),
+            "a1b2***7890"
+        );
+        assert_eq!(mask_secret("short"), "*****");
+    }
 }
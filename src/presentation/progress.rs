@@ -1,3 +1,11 @@
+use crate::presentation::input;
+use crate::presentation::multi_progress::MultiProgress;
+use crate::presentation::schema;
+use crate::presentation::spinner::{self, Spinner};
+use crate::presentation::units;
+use anyhow::Result;
+use tokio::sync::mpsc;
+use vidyeet::api::pause;
 /// プレゼンテーション層: アップロード進捗表示DTO
 ///
 /// ドメイン層の`UploadProgress`をUI表示に適した形式に変換します。
@@ -9,9 +17,12 @@
 /// - `Option<DisplayProgress>`で表示抑制を明示的に表現
 /// - ヘルパー関数で各フェーズの変換ロジックを分離（密結合緩和）
 /// - 進捗受信ループの処理もこのモジュールで管理（プレゼンテーション層の責務）
-use crate::config::{APP_CONFIG, BYTES_PER_MB};
-use crate::domain::progress::{UploadPhase, UploadProgress};
-use anyhow::Result;
+use vidyeet::config::APP_CONFIG;
+use vidyeet::domain::estimate::UploadEstimate;
+use vidyeet::domain::formatter;
+use vidyeet::domain::progress::{
+    BatchUploadProgress, UploadPhase, UploadProgress, percent_complete,
+};
 
 /// ドメイン型からプレゼンテーション表示型への変換トレイト
 ///
@@ -38,6 +49,8 @@ pub trait ToDisplay {
 pub enum ProgressCategory {
     /// ファイル検証中
     Validation,
+    /// アップロード前の警告
+    Warning,
     /// アップロード準備中
     Preparation,
     /// ファイルアップロード中
@@ -97,36 +110,65 @@ pub fn display_upload_progress(progress: &DisplayProgress) {
 /// プレゼンテーション層の責務として、進捗チャネルから受信した
 /// ドメイン層の進捗情報を表示用に変換し、ユーザーに表示します。
 ///
+/// 人間向け表示でstderrが端末の場合、所要時間が不定なフェーズ
+/// （Direct Upload作成、アセット作成待機）はスピナーで表示します。
+/// 端末でない場合は従来どおり定期的なテキスト行で表示します。
+///
+/// 送信側（アップロード処理）が破棄されてチャネルが閉じるまで受信を続けるため、
+/// 完了（`completed`）を含むすべてのイベントを送信順に1回ずつ出力します。
+/// 一定時間（`APP_CONFIG.upload.progress_timeout_secs`）更新がない場合は一度だけ警告し、受信を続けます。
+///
 /// # 引数
 /// * `progress_rx` - 進捗受信チャネル
 /// * `machine_output` - 機械可読出力フラグ（true時は機械向けJSON出力）
 /// * `show_progress` - 進捗表示フラグ（false時は進捗を完全に抑制）
-///
-/// # 戻り値
-/// 処理が正常に完了した場合は`Ok(())`、タイムアウトした場合は警告を出力
 pub async fn handle_upload_progress(
-    mut progress_rx: tokio::sync::mpsc::Receiver<UploadProgress>,
+    mut progress_rx: mpsc::UnboundedReceiver<UploadProgress>,
     machine_output: bool,
     show_progress: bool,
 ) -> Result<()> {
     // タイムアウトを設定して無限待機を防ぐ
-    use tokio::time::{Duration, timeout};
+    use tokio::time::{Duration, Instant, interval, sleep_until};
     let progress_timeout = Duration::from_secs(APP_CONFIG.upload.progress_timeout_secs);
+    let mut deadline = Instant::now() + progress_timeout;
+    let mut stall_warned = false;
+
+    // スピナーは人間向け表示かつ端末出力の場合のみ使用
+    let use_spinner = show_progress && !machine_output && spinner::stderr_is_terminal();
+    let mut active_spinner: Option<Spinner> = None;
+    let mut ticker = interval(Duration::from_millis(
+        APP_CONFIG.presentation.spinner_frame_interval_ms,
+    ));
 
     loop {
-        match timeout(progress_timeout, progress_rx.recv()).await {
-            Ok(Some(progress)) => {
+        tokio::select! {
+            received = progress_rx.recv() => {
+                let Some(progress) = received else {
+                    // チャネルがクローズされた（正常終了）
+                    break;
+                };
+                deadline = Instant::now() + progress_timeout;
+
                 if !show_progress {
                     // --progress フラグが指定されていない場合は進捗を表示しない
+                    // （警告はアップロード前に知らせるため人間向けには常に表示）
+                    if !machine_output
+                        && is_always_shown(&progress.phase)
+                        && let Some(display_progress) = progress.to_display()
+                    {
+                        display_upload_progress(&display_progress);
+                    }
                     continue;
                 }
 
                 if machine_output {
                     // 機械可読JSON出力（stdout）
                     // JSONL形式（1行1JSON）で出力
-                    if let Ok(json) = serde_json::to_string(&progress.phase) {
-                        println!("{}", json);
+                    if let Ok(json) = serde_json::to_value(&progress.phase) {
+                        println!("{}", schema::with_schema_version(json));
                     }
+                } else if use_spinner {
+                    render_with_spinner(&progress, &mut active_spinner);
                 } else {
                     // 人間向け進捗表示（stderr）
                     // ドメイン層の型をプレゼンテーション層の型に変換（借用）
@@ -137,21 +179,289 @@ pub async fn handle_upload_progress(
                     // Noneの場合は表示を抑制（10秒未満の経過時間更新など）
                 }
             }
-            Ok(std::option::Option::None) => {
-                // チャネルがクローズされた（正常終了）
-                break;
+            _ = ticker.tick(), if active_spinner.is_some() && !input::is_prompting() => {
+                if let Some(spinner) = active_spinner.as_mut() {
+                    spinner.tick();
+                }
             }
-            Err(_) => {
-                // タイムアウト発生
-                eprintln!("Warning: Progress update timed out");
-                break;
+            _ = sleep_until(deadline), if !stall_warned => {
+                // 一時停止中・確認プロンプトの表示中は更新がないのが正常なため警告しない
+                if pause::is_paused() || input::is_prompting() {
+                    deadline = Instant::now() + progress_timeout;
+                    continue;
+                }
+                // 更新が途絶えても終了イベントを取りこぼさないよう、警告のみで受信を続ける
+                if let Some(spinner) = active_spinner.take() {
+                    spinner.finish();
+                }
+                warn_stalled(progress_timeout);
+                stall_warned = true;
             }
         }
     }
 
+    if let Some(spinner) = active_spinner.take() {
+        spinner.finish();
+    }
+
     Ok(())
 }
 
+/// 複数ファイルアップロードの進捗を受信して表示するループ処理
+///
+/// 並行して進む複数ファイルの進捗が混在するため、スピナーは使いません。
+/// 人間向け表示でstderrが端末の場合はファイルごとの進捗バーをまとめて描き直し、
+/// 端末でない場合は「[番号/総数]」付きのテキスト行を出力します。
+/// 機械向けにはファイル情報付きのJSONLを受信順（ファイルをまたいで混在）に出力します。
+///
+/// 単一ファイル時と同様に、チャネルが閉じるまで受信を続けます。
+///
+/// # 引数
+/// * `progress_rx` - 多重化された進捗受信チャネル
+/// * `machine_output` - 機械可読出力フラグ（true時は機械向けJSON出力）
+/// * `show_progress` - 進捗表示フラグ（false時は進捗を完全に抑制）
+pub async fn handle_batch_upload_progress(
+    mut progress_rx: mpsc::UnboundedReceiver<BatchUploadProgress>,
+    machine_output: bool,
+    show_progress: bool,
+) -> Result<()> {
+    use tokio::time::{Duration, timeout};
+    let progress_timeout = Duration::from_secs(APP_CONFIG.upload.progress_timeout_secs);
+    let mut stall_warned = false;
+
+    // 進捗バーは人間向け表示かつ端末出力の場合のみ使用
+    let mut bars = (show_progress && !machine_output && spinner::stderr_is_terminal())
+        .then(MultiProgress::new);
+
+    loop {
+        let received = if stall_warned {
+            progress_rx.recv().await
+        } else {
+            match timeout(progress_timeout, progress_rx.recv()).await {
+                Ok(received) => received,
+                // 一時停止中は更新がないのが正常なため警告しない
+                Err(_) if pause::is_paused() => continue,
+                Err(_) => {
+                    match bars.as_mut() {
+                        Some(bars) => bars.println(&stalled_message(progress_timeout)),
+                        None => warn_stalled(progress_timeout),
+                    }
+                    stall_warned = true;
+                    continue;
+                }
+            }
+        };
+        // チャネルがクローズされた（正常終了）
+        let Some(progress) = received else {
+            break;
+        };
+
+        if !show_progress {
+            // 警告はアップロード前に知らせるため人間向けには常に表示
+            if !machine_output
+                && is_always_shown(&progress.phase)
+                && let Some(display_progress) = progress.to_display()
+            {
+                display_upload_progress(&display_progress);
+            }
+            continue;
+        }
+
+        if machine_output {
+            // JSONL形式（1行1JSON）で出力
+            if let Ok(json) = serde_json::to_value(&progress) {
+                println!("{}", schema::with_schema_version(json));
+            }
+        } else if let Some(bars) = bars.as_mut() {
+            render_with_bars(&progress, bars);
+        } else if let Some(display_progress) = progress.to_display() {
+            display_upload_progress(&display_progress);
+        }
+    }
+
+    if let Some(bars) = bars {
+        bars.finish();
+    }
+
+    Ok(())
+}
+
+/// 進捗の更新が途絶えたことを警告する（アップロード自体は継続中）
+fn warn_stalled(progress_timeout: std::time::Duration) {
+    eprintln!("{}", stalled_message(progress_timeout));
+}
+
+/// 進捗の更新が途絶えたときの警告文
+fn stalled_message(progress_timeout: std::time::Duration) -> String {
+    format!(
+        "Warning: No progress update for {} seconds; still waiting for the upload to finish",
+        progress_timeout.as_secs()
+    )
+}
+
+/// 進捗バーを使って複数ファイルの人間向け進捗を表示する（TTY時）
+///
+/// 警告・見積もり・完了は「[番号/総数]」付きの行として進捗バーの上に出力し、
+/// 完了したファイルの行は取り除きます。それ以外のフェーズはファイルの行を更新します。
+fn render_with_bars(progress: &BatchUploadProgress, bars: &mut MultiProgress) {
+    match &progress.phase {
+        UploadPhase::Warning { .. } | UploadPhase::Estimate { .. } => {
+            if let Some(display_progress) = progress.to_display() {
+                bars.println(&display_progress.message);
+            }
+        }
+        UploadPhase::Completed { .. } => {
+            bars.remove(progress.file_index);
+            if let Some(display_progress) = progress.to_display() {
+                bars.println(&display_progress.message);
+            }
+        }
+        phase => {
+            if let Some((status, percent)) = row_status(phase) {
+                bars.update(progress.file_index, &progress.label, status, percent);
+            }
+        }
+    }
+}
+
+/// 進捗バーの行に表示する状態と進捗率
+///
+/// 確定した行として出力するフェーズ（警告・見積もり・完了）はNoneを返します。
+fn row_status(phase: &UploadPhase) -> Option<(String, Option<f64>)> {
+    match phase {
+        UploadPhase::ValidatingFile { .. } => Some(("validating".to_string(), None)),
+        UploadPhase::FileValidated {
+            size_bytes, format, ..
+        } => Some((
+            format!("validated ({}, {})", units::size(*size_bytes), format),
+            None,
+        )),
+        UploadPhase::CreatingDirectUpload { .. } => {
+            Some(("creating upload session".to_string(), None))
+        }
+        UploadPhase::DirectUploadCreated { .. } => {
+            Some(("upload session created".to_string(), None))
+        }
+        UploadPhase::UploadingFile { .. } => Some(("uploading".to_string(), Some(0.0))),
+        UploadPhase::Transcoding {
+            out_time_secs,
+            duration_secs,
+            percent,
+            bytes_sent,
+            ..
+        } => Some((
+            format!(
+                "transcoding {}, {} sent",
+                format_transcode_position(*out_time_secs, *duration_secs),
+                units::size(*bytes_sent)
+            ),
+            *percent,
+        )),
+        UploadPhase::UploadingChunk {
+            bytes_sent,
+            total_bytes,
+            percent,
+            bytes_per_sec,
+            eta_secs,
+            ..
+        } => {
+            let eta = eta_secs
+                .map(|secs| format!(", ETA {}", formatter::format_duration(secs as f64)))
+                .unwrap_or_default();
+            Some((
+                format!(
+                    "{} / {}, {}{}",
+                    units::size(*bytes_sent),
+                    units::size(*total_bytes),
+                    units::rate(*bytes_per_sec),
+                    eta
+                ),
+                Some(*percent),
+            ))
+        }
+        UploadPhase::Paused {
+            bytes_sent,
+            total_bytes,
+        } => Some((
+            "paused (press 'r' + Enter to resume)".to_string(),
+            Some(percent_complete(*bytes_sent, *total_bytes)),
+        )),
+        UploadPhase::Resumed {
+            bytes_sent,
+            total_bytes,
+        } => Some((
+            "resuming".to_string(),
+            Some(percent_complete(*bytes_sent, *total_bytes)),
+        )),
+        UploadPhase::FileUploaded { .. } => Some(("uploaded".to_string(), Some(100.0))),
+        UploadPhase::WaitingForAsset { elapsed_secs, .. } => {
+            Some((format!("waiting for asset ({}s)", elapsed_secs), None))
+        }
+        UploadPhase::WaitingForReady {
+            status,
+            elapsed_secs,
+            ..
+        } => Some((
+            format!("waiting ({}s, status: {})", elapsed_secs, status),
+            None,
+        )),
+        UploadPhase::Warning { .. }
+        | UploadPhase::Estimate { .. }
+        | UploadPhase::Completed { .. } => None,
+    }
+}
+
+/// スピナーを使って人間向け進捗を表示する（TTY時）
+///
+/// 所要時間が不定なフェーズの開始時にスピナーを起動し、
+/// それ以外のフェーズを受信したらスピナーを確定させてから通常表示します。
+/// スピナー表示中の経過時間更新（WaitingForAsset / WaitingForReady）はスピナー自身が表示するため抑制します。
+fn render_with_spinner(progress: &UploadProgress, active_spinner: &mut Option<Spinner>) {
+    match &progress.phase {
+        UploadPhase::CreatingDirectUpload { file_name } => {
+            if let Some(spinner) = active_spinner.take() {
+                spinner.finish();
+            }
+            *active_spinner = Some(Spinner::start(format_creating_upload(file_name).message));
+        }
+        UploadPhase::WaitingForAsset { elapsed_secs, .. } => {
+            // 初回イベント（またはスピナー未起動時）のみ待機スピナーを開始
+            if *elapsed_secs == 0 || active_spinner.is_none() {
+                if let Some(spinner) = active_spinner.take() {
+                    spinner.finish();
+                }
+                let message = format_waiting_for_asset(0)
+                    .map(|d| d.message)
+                    .unwrap_or_default();
+                *active_spinner = Some(Spinner::start(message));
+            }
+        }
+        UploadPhase::WaitingForReady {
+            asset_id,
+            status,
+            elapsed_secs,
+        } => {
+            if *elapsed_secs == 0 || active_spinner.is_none() {
+                if let Some(spinner) = active_spinner.take() {
+                    spinner.finish();
+                }
+                let message = format_waiting_for_ready(asset_id, status, 0)
+                    .map(|d| d.message)
+                    .unwrap_or_default();
+                *active_spinner = Some(Spinner::start(message));
+            }
+        }
+        _ => {
+            if let Some(spinner) = active_spinner.take() {
+                spinner.finish();
+            }
+            if let Some(display_progress) = progress.to_display() {
+                display_upload_progress(&display_progress);
+            }
+        }
+    }
+}
+
 /// ドメイン層の`UploadProgress`からプレゼンテーション層の`DisplayProgress`への変換
 ///
 /// # 設計改善
@@ -165,44 +475,101 @@ pub async fn handle_upload_progress(
 /// Rustのトレイト孤児規則に違反します。そのため自前トレイトを使用します。
 impl ToDisplay for UploadProgress {
     fn to_display(&self) -> Option<DisplayProgress> {
-        match &self.phase {
-            UploadPhase::ValidatingFile { file_path } => Some(format_validating_file(file_path)),
-            UploadPhase::FileValidated {
-                file_name,
-                size_bytes,
-                format,
-            } => Some(format_file_validated(file_name, *size_bytes, format)),
-            UploadPhase::CreatingDirectUpload { file_name } => {
-                Some(format_creating_upload(file_name))
-            }
-            UploadPhase::DirectUploadCreated { upload_id } => {
-                Some(format_upload_created(upload_id))
-            }
-            UploadPhase::UploadingFile {
-                file_name,
-                size_bytes,
-                total_chunks,
-            } => Some(format_uploading_file(file_name, *size_bytes, *total_chunks)),
-            UploadPhase::UploadingChunk {
-                current_chunk,
-                total_chunks,
-                bytes_sent,
-                total_bytes,
-            } => Some(format_uploading_chunk(
-                *current_chunk,
-                *total_chunks,
-                *bytes_sent,
-                *total_bytes,
-            )),
-            UploadPhase::FileUploaded {
-                file_name,
-                size_bytes,
-            } => Some(format_file_uploaded(file_name, *size_bytes)),
-            UploadPhase::WaitingForAsset { elapsed_secs, .. } => {
-                format_waiting_for_asset(*elapsed_secs)
-            }
-            UploadPhase::Completed { asset_id } => Some(format_completed(asset_id)),
+        phase_to_display(&self.phase)
+    }
+}
+
+/// 複数ファイルアップロードの進捗は、どのファイルのものか分かるよう
+/// 単一ファイル時の表示に「[番号/総数]」を前置します。
+impl ToDisplay for BatchUploadProgress {
+    fn to_display(&self) -> Option<DisplayProgress> {
+        phase_to_display(&self.phase).map(|mut display| {
+            display.message = format!(
+                "[{}/{}] {}",
+                self.file_index, self.total_files, display.message
+            );
+            display
+        })
+    }
+}
+
+/// `--progress` なしでも表示する処理段階（アップロード前の警告、一時停止・再開）か
+fn is_always_shown(phase: &UploadPhase) -> bool {
+    matches!(
+        phase,
+        UploadPhase::Warning { .. } | UploadPhase::Paused { .. } | UploadPhase::Resumed { .. }
+    )
+}
+
+/// 処理段階を表示用進捗情報に変換する
+fn phase_to_display(phase: &UploadPhase) -> Option<DisplayProgress> {
+    match phase {
+        UploadPhase::ValidatingFile { file_path } => Some(format_validating_file(file_path)),
+        UploadPhase::FileValidated {
+            file_name,
+            size_bytes,
+            format,
+        } => Some(format_file_validated(file_name, *size_bytes, format)),
+        UploadPhase::Warning { warning } => Some(format_warning(&warning.message)),
+        UploadPhase::Estimate { estimate } => Some(format_estimate(estimate)),
+        UploadPhase::CreatingDirectUpload { file_name } => Some(format_creating_upload(file_name)),
+        UploadPhase::DirectUploadCreated { upload_id } => Some(format_upload_created(upload_id)),
+        UploadPhase::UploadingFile {
+            file_name,
+            size_bytes,
+            total_chunks,
+        } => Some(format_uploading_file(file_name, *size_bytes, *total_chunks)),
+        UploadPhase::Transcoding {
+            out_time_secs,
+            duration_secs,
+            percent,
+            bytes_sent,
+            bytes_per_sec,
+        } => Some(format_transcoding(
+            *out_time_secs,
+            *duration_secs,
+            *percent,
+            *bytes_sent,
+            *bytes_per_sec,
+        )),
+        UploadPhase::UploadingChunk {
+            current_chunk,
+            total_chunks,
+            bytes_sent,
+            total_bytes,
+            percent,
+            bytes_per_sec,
+            eta_secs,
+        } => Some(format_uploading_chunk(
+            *current_chunk,
+            *total_chunks,
+            *bytes_sent,
+            *total_bytes,
+            *percent,
+            *bytes_per_sec,
+            *eta_secs,
+        )),
+        UploadPhase::Paused {
+            bytes_sent,
+            total_bytes,
+        } => Some(format_paused(*bytes_sent, *total_bytes)),
+        UploadPhase::Resumed {
+            bytes_sent,
+            total_bytes,
+        } => Some(format_resumed(*bytes_sent, *total_bytes)),
+        UploadPhase::FileUploaded {
+            file_name,
+            size_bytes,
+        } => Some(format_file_uploaded(file_name, *size_bytes)),
+        UploadPhase::WaitingForAsset { elapsed_secs, .. } => {
+            format_waiting_for_asset(*elapsed_secs)
         }
+        UploadPhase::WaitingForReady {
+            asset_id,
+            status,
+            elapsed_secs,
+        } => format_waiting_for_ready(asset_id, status, *elapsed_secs),
+        UploadPhase::Completed { asset_id } => Some(format_completed(asset_id)),
     }
 }
 
@@ -223,20 +590,59 @@ fn format_validating_file(file_path: &str) -> DisplayProgress {
 
 /// ファイル検証完了時の進捗表示を生成
 fn format_file_validated(file_name: &str, size_bytes: u64, format: &str) -> DisplayProgress {
-    let size_mb = size_bytes as f64 / BYTES_PER_MB;
-    let precision = APP_CONFIG.presentation.size_display_precision;
     DisplayProgress::new(
         format!(
-            "File validated: {} ({:.prec$} MB, {})",
+            "File validated: {} ({}, {})",
             file_name,
-            size_mb,
-            format,
-            prec = precision
+            units::size(size_bytes),
+            format
         ),
         ProgressCategory::Validation,
     )
 }
 
+fn format_warning(message: &str) -> DisplayProgress {
+    DisplayProgress::new(format!("Warning: {}", message), ProgressCategory::Warning)
+}
+
+/// 見積もりの進捗表示を生成
+///
+/// 例: "Estimated upload time: 1:20 at 12.50 MB/s, encoding 2.50 min (premium)"
+fn format_estimate(estimate: &UploadEstimate) -> DisplayProgress {
+    DisplayProgress::new(
+        format!(
+            "Estimated upload time: {}, encoding {}",
+            format_estimated_upload_time(estimate),
+            format_encoded_minutes(estimate)
+        ),
+        ProgressCategory::Upload,
+    )
+}
+
+/// 見積もりのアップロード時間を表示用に整形する
+///
+/// 例: "1:20 at 12.50 MB/s"、転送速度が不明な場合は "unknown"
+pub(crate) fn format_estimated_upload_time(estimate: &UploadEstimate) -> String {
+    match (estimate.upload_secs, estimate.bytes_per_sec) {
+        (Some(secs), Some(rate)) => format!(
+            "{} at {}",
+            formatter::format_duration(secs as f64),
+            units::rate(rate)
+        ),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// 見積もりのエンコード分数を表示用に整形する
+///
+/// 例: "2.50 min (premium)"、再生時間が不明な場合は "unknown (premium)"
+pub(crate) fn format_encoded_minutes(estimate: &UploadEstimate) -> String {
+    match estimate.encoded_minutes {
+        Some(minutes) => format!("{:.2} min ({})", minutes, estimate.video_quality),
+        None => format!("unknown ({})", estimate.video_quality),
+    }
+}
+
 fn format_creating_upload(file_name: &str) -> DisplayProgress {
     DisplayProgress::new(
         format!("Creating upload session for: {}", file_name),
@@ -255,67 +661,126 @@ fn format_upload_created(upload_id: &str) -> DisplayProgress {
 ///
 /// 例: "Uploading file: video.mp4 (100.00 MB, 5 chunks)..."
 fn format_uploading_file(file_name: &str, size_bytes: u64, total_chunks: usize) -> DisplayProgress {
-    let size_mb = size_bytes as f64 / BYTES_PER_MB;
-    let precision = APP_CONFIG.presentation.size_display_precision;
     DisplayProgress::new(
         format!(
-            "Uploading file: {} ({:.prec$} MB, {} chunks)...",
+            "Uploading file: {} ({}, {} chunks)...",
             file_name,
-            size_mb,
-            total_chunks,
-            prec = precision
+            units::size(size_bytes),
+            total_chunks
         ),
         ProgressCategory::Upload,
     )
 }
 
+/// 変換しながらアップロード中の進捗表示を生成
+///
+/// 例: "Transcoding: 0:42 / 2:10 (32.3%), 120.00 MB sent at 5.00 MB/s"
+fn format_transcoding(
+    out_time_secs: f64,
+    duration_secs: Option<f64>,
+    percent: Option<f64>,
+    bytes_sent: u64,
+    bytes_per_sec: f64,
+) -> DisplayProgress {
+    let percent = percent
+        .map(|percent| format!(" ({:.1}%)", percent))
+        .unwrap_or_default();
+
+    DisplayProgress::new(
+        format!(
+            "Transcoding: {}{}, {} sent at {}",
+            format_transcode_position(out_time_secs, duration_secs),
+            percent,
+            units::size(bytes_sent),
+            units::rate(bytes_per_sec)
+        ),
+        ProgressCategory::Upload,
+    )
+}
+
+/// 変換済みの再生位置を表示用に整形する
+///
+/// 例: "0:42 / 2:10"、再生時間が不明な場合は "0:42"
+fn format_transcode_position(out_time_secs: f64, duration_secs: Option<f64>) -> String {
+    let position = formatter::format_duration(out_time_secs);
+    match duration_secs {
+        Some(duration) => format!("{} / {}", position, formatter::format_duration(duration)),
+        None => position,
+    }
+}
+
 /// チャンクアップロード中の進捗表示を生成
 ///
-/// 例: "Uploading chunk 2/5 (64.00 MB / 160.00 MB, 40%)"
+/// 例: "Uploading chunk 2/5 (64.00 MB / 160.00 MB, 40.0%, 12.50 MB/s, ETA 0:08)"
 fn format_uploading_chunk(
     current_chunk: usize,
     total_chunks: usize,
     bytes_sent: u64,
     total_bytes: u64,
+    percent: f64,
+    bytes_per_sec: f64,
+    eta_secs: Option<u64>,
 ) -> DisplayProgress {
-    let sent_mb = bytes_sent as f64 / BYTES_PER_MB;
-    let total_mb = total_bytes as f64 / BYTES_PER_MB;
-    let percentage = (bytes_sent as f64 / total_bytes as f64 * 100.0) as u8;
-    let precision = APP_CONFIG.presentation.size_display_precision;
+    // 速度が確定していない場合はETAを表示しない
+    let eta = eta_secs
+        .map(|secs| format!(", ETA {}", formatter::format_duration(secs as f64)))
+        .unwrap_or_default();
 
     DisplayProgress::new(
         format!(
-            "Uploading chunk {}/{} ({:.prec$} MB / {:.prec$} MB, {}%)",
+            "Uploading chunk {}/{} ({} / {}, {:.1}%, {}{})",
             current_chunk,
             total_chunks,
-            sent_mb,
-            total_mb,
-            percentage,
-            prec = precision
+            units::size(bytes_sent),
+            units::size(total_bytes),
+            percent,
+            units::rate(bytes_per_sec),
+            eta
         ),
         ProgressCategory::Upload,
     )
 }
 
-/// アップロード完了時の進捗表示を生成
-fn format_file_uploaded(file_name: &str, size_bytes: u64) -> DisplayProgress {
-    let size_mb = size_bytes as f64 / BYTES_PER_MB;
-    let precision = APP_CONFIG.presentation.size_display_precision;
+/// 一時停止時の進捗表示を生成
+///
+/// 例: "Upload paused at 64.00 MB / 160.00 MB (40.0%); press 'r' + Enter to resume"
+fn format_paused(bytes_sent: u64, total_bytes: u64) -> DisplayProgress {
     DisplayProgress::new(
         format!(
-            "File uploaded: {} ({:.prec$} MB)",
-            file_name,
-            size_mb,
-            prec = precision
+            "Upload paused at {} / {} ({:.1}%); press 'r' + Enter to resume",
+            units::size(bytes_sent),
+            units::size(total_bytes),
+            percent_complete(bytes_sent, total_bytes)
+        ),
+        ProgressCategory::Upload,
+    )
+}
+
+/// 再開時の進捗表示を生成
+fn format_resumed(bytes_sent: u64, total_bytes: u64) -> DisplayProgress {
+    DisplayProgress::new(
+        format!(
+            "Upload resumed at {} / {}",
+            units::size(bytes_sent),
+            units::size(total_bytes)
         ),
         ProgressCategory::Upload,
     )
 }
 
+/// アップロード完了時の進捗表示を生成
+fn format_file_uploaded(file_name: &str, size_bytes: u64) -> DisplayProgress {
+    DisplayProgress::new(
+        format!("File uploaded: {} ({})", file_name, units::size(size_bytes)),
+        ProgressCategory::Upload,
+    )
+}
+
 /// アセット待機中の進捗表示
 ///
-/// 設定された間隔（progress_update_interval_secs）ごとにのみ更新を表示し、
-/// それ以外は`None`を返すことで過度な更新を抑制します。
+/// 開始直後の短い間隔のポーリング（progress_update_interval_secs 未満）は表示せず、
+/// `None`を返すことで過度な更新を抑制します。それ以降のポーリングは
+/// 指数バックオフで間隔が延びているため、毎回経過時間を表示します。
 fn format_waiting_for_asset(elapsed_secs: u64) -> Option<DisplayProgress> {
     let update_interval = APP_CONFIG.presentation.progress_update_interval_secs;
 
@@ -324,13 +789,41 @@ fn format_waiting_for_asset(elapsed_secs: u64) -> Option<DisplayProgress> {
             "Waiting for asset creation...".to_string(),
             ProgressCategory::Processing,
         ))
-    } else if elapsed_secs.is_multiple_of(update_interval) {
+    } else if elapsed_secs >= update_interval {
         Some(DisplayProgress::new(
             format!("Still waiting... ({}s elapsed)", elapsed_secs),
             ProgressCategory::Processing,
         ))
     } else {
-        // 設定間隔未満の更新は表示しない（明示的にNoneを返す）
+        // 開始直後の更新は表示しない（明示的にNoneを返す）
+        None
+    }
+}
+
+/// アセットの状態待ち（`wait`）の進捗表示
+///
+/// アセット作成待ちと同じく、開始直後の短い間隔のポーリングは表示しません。
+fn format_waiting_for_ready(
+    asset_id: &str,
+    status: &str,
+    elapsed_secs: u64,
+) -> Option<DisplayProgress> {
+    let update_interval = APP_CONFIG.presentation.progress_update_interval_secs;
+
+    if elapsed_secs == 0 {
+        Some(DisplayProgress::new(
+            format!("Waiting for asset {} (status: {})...", asset_id, status),
+            ProgressCategory::Processing,
+        ))
+    } else if elapsed_secs >= update_interval {
+        Some(DisplayProgress::new(
+            format!(
+                "Still waiting... ({}s elapsed, status: {})",
+                elapsed_secs, status
+            ),
+            ProgressCategory::Processing,
+        ))
+    } else {
         None
     }
 }
@@ -345,6 +838,7 @@ fn format_completed(asset_id: &str) -> DisplayProgress {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use vidyeet::domain::warning::{Warning, WarningKind};
 
     #[test]
     fn test_display_progress_creation() {
@@ -398,6 +892,57 @@ mod tests {
         assert_eq!(display_progress.category, ProgressCategory::Validation);
     }
 
+    #[test]
+    fn test_from_upload_progress_uploading_chunk_with_eta() {
+        let domain_progress = UploadProgress::new(UploadPhase::UploadingChunk {
+            current_chunk: 2,
+            total_chunks: 5,
+            bytes_sent: 67_108_864,   // 64 MB
+            total_bytes: 167_772_160, // 160 MB
+            percent: 40.0,
+            bytes_per_sec: 13_107_200.0, // 12.5 MB/s
+            eta_secs: Some(68),
+        });
+
+        let display_progress = domain_progress
+            .to_display()
+            .expect("update should be displayed");
+
+        assert_eq!(
+            display_progress.message,
+            "Uploading chunk 2/5 (64.00 MB / 160.00 MB, 40.0%, 12.50 MB/s, ETA 1:08)"
+        );
+        assert_eq!(display_progress.category, ProgressCategory::Upload);
+    }
+
+    #[test]
+    fn test_from_upload_progress_transcoding() {
+        let phase = |duration_secs, percent| UploadPhase::Transcoding {
+            out_time_secs: 42.0,
+            duration_secs,
+            percent,
+            bytes_sent: 125_829_120,    // 120 MB
+            bytes_per_sec: 5_242_880.0, // 5 MB/s
+        };
+
+        let display_progress = UploadProgress::new(phase(Some(130.0), Some(32.3)))
+            .to_display()
+            .expect("transcoding should be displayed");
+        assert_eq!(
+            display_progress.message,
+            "Transcoding: 0:42 / 2:10 (32.3%), 120.00 MB sent at 5.00 MB/s"
+        );
+
+        // 再生時間が不明な場合は位置のみ
+        let display_progress = UploadProgress::new(phase(None, None))
+            .to_display()
+            .expect("transcoding should be displayed");
+        assert_eq!(
+            display_progress.message,
+            "Transcoding: 0:42, 120.00 MB sent at 5.00 MB/s"
+        );
+    }
+
     #[test]
     fn test_from_upload_progress_waiting_initial() {
         let domain_progress = UploadProgress::new(UploadPhase::WaitingForAsset {
@@ -445,6 +990,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_upload_progress_waiting_for_ready() {
+        let waiting = |elapsed_secs| {
+            UploadProgress::new(UploadPhase::WaitingForReady {
+                asset_id: "asset_1".to_string(),
+                status: "preparing".to_string(),
+                elapsed_secs,
+            })
+            .to_display()
+            .map(|d| d.message)
+        };
+
+        assert_eq!(
+            waiting(0).as_deref(),
+            Some("Waiting for asset asset_1 (status: preparing)...")
+        );
+        assert_eq!(
+            waiting(20).as_deref(),
+            Some("Still waiting... (20s elapsed, status: preparing)")
+        );
+        // バックオフで間隔が延びた後は、区切りのよい秒数でなくても表示する
+        assert_eq!(
+            waiting(14).as_deref(),
+            Some("Still waiting... (14s elapsed, status: preparing)")
+        );
+        assert!(waiting(5).is_none());
+    }
+
     #[test]
     fn test_from_upload_progress_completed() {
         let domain_progress = UploadProgress::new(UploadPhase::Completed {
@@ -458,4 +1031,130 @@ mod tests {
         assert_eq!(display_progress.message, "Asset created: asset_123");
         assert_eq!(display_progress.category, ProgressCategory::Completed);
     }
+
+    #[test]
+    fn test_batch_progress_is_prefixed_with_file_number() {
+        let progress = BatchUploadProgress {
+            file_index: 2,
+            total_files: 5,
+            source_path: "/videos/clip.mp4".to_string(),
+            label: "clip.mp4".to_string(),
+            phase: UploadPhase::Completed {
+                asset_id: "asset_123".to_string(),
+            },
+        };
+
+        let display = progress.to_display().expect("update should be displayed");
+        assert_eq!(display.message, "[2/5] Asset created: asset_123");
+    }
+
+    #[test]
+    fn test_batch_progress_serializes_phase_inline() {
+        let progress = BatchUploadProgress {
+            file_index: 1,
+            total_files: 2,
+            source_path: "/videos/clip.mp4".to_string(),
+            label: "clip.mp4".to_string(),
+            phase: UploadPhase::Completed {
+                asset_id: "asset_123".to_string(),
+            },
+        };
+
+        let json = serde_json::to_value(&progress).unwrap();
+        assert_eq!(json["file_index"], 1);
+        assert_eq!(json["source_path"], "/videos/clip.mp4");
+        assert_eq!(json["label"], "clip.mp4");
+        assert_eq!(json["phase"], "completed");
+        assert_eq!(json["asset_id"], "asset_123");
+    }
+
+    #[test]
+    fn test_row_status() {
+        let (status, percent) = row_status(&UploadPhase::UploadingChunk {
+            current_chunk: 2,
+            total_chunks: 5,
+            bytes_sent: 1_000_000,
+            total_bytes: 2_500_000,
+            percent: 40.0,
+            bytes_per_sec: 500_000.0,
+            eta_secs: Some(3),
+        })
+        .expect("chunk progress should update the row");
+        assert_eq!(percent, Some(40.0));
+        assert!(status.contains("ETA"));
+
+        assert!(
+            row_status(&UploadPhase::Completed {
+                asset_id: "asset_123".to_string(),
+            })
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_from_upload_progress_warning() {
+        let domain_progress = UploadProgress::new(UploadPhase::Warning {
+            warning: Warning::new(WarningKind::Interlaced, "Interlaced video"),
+        });
+
+        assert!(is_always_shown(&domain_progress.phase));
+        let display_progress = domain_progress
+            .to_display()
+            .expect("warning should be displayed");
+        assert_eq!(display_progress.message, "Warning: Interlaced video");
+        assert_eq!(display_progress.category, ProgressCategory::Warning);
+
+        let json = serde_json::to_value(&domain_progress.phase).unwrap();
+        assert_eq!(json["phase"], "warning");
+        assert_eq!(json["code"], "interlaced");
+        assert_eq!(json["message"], "Interlaced video");
+    }
+
+    #[test]
+    fn test_from_upload_progress_paused_and_resumed() {
+        let paused = UploadProgress::new(UploadPhase::Paused {
+            bytes_sent: 64 * 1024 * 1024,
+            total_bytes: 160 * 1024 * 1024,
+        });
+        assert!(is_always_shown(&paused.phase));
+        let display_progress = paused.to_display().expect("pause should be displayed");
+        assert_eq!(
+            display_progress.message,
+            "Upload paused at 64.00 MB / 160.00 MB (40.0%); press 'r' + Enter to resume"
+        );
+        let (_, percent) = row_status(&paused.phase).expect("pause should update the row");
+        assert_eq!(percent, Some(40.0));
+
+        let resumed = UploadProgress::new(UploadPhase::Resumed {
+            bytes_sent: 64 * 1024 * 1024,
+            total_bytes: 160 * 1024 * 1024,
+        });
+        let json = serde_json::to_value(&resumed.phase).unwrap();
+        assert_eq!(json["phase"], "resumed");
+        assert_eq!(json["bytes_sent"], 64 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_from_upload_progress_estimate() {
+        let domain_progress = UploadProgress::new(UploadPhase::Estimate {
+            estimate: UploadEstimate::new(
+                64 * 1024 * 1024 * 10,
+                Some(150.0),
+                "premium",
+                Some(8.0 * 1024.0 * 1024.0),
+            ),
+        });
+
+        let display_progress = domain_progress
+            .to_display()
+            .expect("estimate should be displayed");
+        assert_eq!(
+            display_progress.message,
+            "Estimated upload time: 1:20 at 8.00 MB/s, encoding 2.50 min (premium)"
+        );
+
+        let unknown = UploadEstimate::new(1024, None, "basic", None);
+        assert_eq!(format_estimated_upload_time(&unknown), "unknown");
+        assert_eq!(format_encoded_minutes(&unknown), "unknown (basic)");
+    }
 }
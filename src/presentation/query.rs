@@ -0,0 +1,162 @@
+/// プレゼンテーション層: 機械可読出力の抽出（`--query`）
+///
+/// `--machine` の結果JSONにjq互換の式（jaqで評価）を適用し、得られた値だけをstdoutへ出力します。
+/// jqがインストールされていない環境（WindowsのCIランナーなど）でも、
+/// `vidyeet list --query '.videos[].asset_id'` のように値を取り出せます。
+///
+/// 出力は値ごとに1行で、文字列は引用符なし（`jq -r` と同様）、それ以外はJSONです。
+/// エラー時のJSONと進捗JSONL（`--progress`）には適用しません。
+use anyhow::{Result, anyhow, bail};
+use jaq_core::load::{Arena, File, Loader};
+use jaq_core::{Compiler, Ctx, Filter, Native, RcIter};
+use jaq_json::Val;
+use std::sync::OnceLock;
+
+/// `--query` で指定された式
+static QUERY: OnceLock<String> = OnceLock::new();
+
+/// `--query` の式を検証して設定する（最初の呼び出しのみ有効）
+///
+/// コマンドの実行前に呼び出し、不正な式ではアップロードなどを行わずに失敗させます。
+pub fn init(expr: &str) -> Result<()> {
+    compile(expr)?;
+    let _ = QUERY.set(expr.to_string());
+    Ok(())
+}
+
+/// 設定された `--query` の式（指定されていない場合はNone）
+pub fn active() -> Option<&'static str> {
+    QUERY.get().map(String::as_str)
+}
+
+/// 式をJSONに適用し、出力する行を返す
+///
+/// # Errors
+/// 式が不正な場合、または評価中にエラーになった場合（例: 文字列に `.[]` を適用）
+pub fn run(expr: &str, input: serde_json::Value) -> Result<Vec<String>> {
+    let filter = compile(expr)?;
+    let inputs = RcIter::new(core::iter::empty());
+    filter
+        .run((Ctx::new([], &inputs), Val::from(input)))
+        .map(|output| {
+            output
+                .map(|value| format_value(serde_json::Value::from(value)))
+                .map_err(|e| anyhow!("--query failed: {}", e))
+        })
+        .collect()
+}
+
+/// 出力する値を1行の文字列にする（文字列は引用符なし）
+fn format_value(value: serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+/// 式を解析・コンパイルする（jqの標準ライブラリの関数を含む）
+fn compile(expr: &str) -> Result<Filter<Native<Val>>> {
+    if expr.trim().is_empty() {
+        bail!("--query requires an expression (e.g. --query '.asset_id')");
+    }
+
+    let loader = Loader::new(jaq_std::defs().chain(jaq_json::defs()));
+    let arena = Arena::default();
+    let modules = loader
+        .load(
+            &arena,
+            File {
+                code: expr,
+                path: (),
+            },
+        )
+        .map_err(|errors| {
+            let reasons = errors.into_iter().flat_map(|(_, error)| {
+                use jaq_core::load::Error;
+                match error {
+                    Error::Io(errors) => errors.into_iter().map(|(_, e)| e).collect(),
+                    Error::Lex(errors) => errors
+                        .into_iter()
+                        .map(|(expected, found)| expected_at(expr, expected.as_str(), found))
+                        .collect(),
+                    Error::Parse(errors) => errors
+                        .into_iter()
+                        .map(|(expected, found)| expected_at(expr, expected.as_str(), found))
+                        .collect::<Vec<_>>(),
+                }
+            });
+            invalid_query(expr, reasons)
+        })?;
+
+    Compiler::default()
+        .with_funs(jaq_std::funs().chain(jaq_json::funs()))
+        .compile(modules)
+        .map_err(|errors| {
+            let reasons = errors.into_iter().flat_map(|(_, errors)| {
+                errors
+                    .into_iter()
+                    .map(|(name, undefined)| format!("undefined {} '{}'", undefined.as_str(), name))
+            });
+            invalid_query(expr, reasons)
+        })
+}
+
+/// 「〜が必要」のエラー理由（位置は式の先頭からの1始まりの列）
+fn expected_at(expr: &str, expected: &str, found: &str) -> String {
+    let column = (found.as_ptr() as usize)
+        .saturating_sub(expr.as_ptr() as usize)
+        .min(expr.len())
+        + 1;
+    format!("expected {} at column {}", expected, column)
+}
+
+fn invalid_query(expr: &str, reasons: impl Iterator<Item = String>) -> anyhow::Error {
+    anyhow!(
+        "Invalid --query expression '{}': {}",
+        expr,
+        reasons.collect::<Vec<_>>().join("; ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_run_extracts_values() {
+        let input = json!({
+            "success": true,
+            "videos": [
+                { "asset_id": "a1", "playback_ids": [{ "id": "p1" }], "duration": 12.5 },
+                { "asset_id": "a2", "playback_ids": [{ "id": "p2" }], "duration": null }
+            ]
+        });
+
+        assert_eq!(
+            run(".videos[].playback_ids[0].id", input.clone()).unwrap(),
+            vec!["p1", "p2"]
+        );
+        assert_eq!(run(".success", input.clone()).unwrap(), vec!["true"]);
+        assert_eq!(
+            run("[.videos[].duration]", input.clone()).unwrap(),
+            vec!["[12.5,null]"]
+        );
+        assert_eq!(
+            run(".videos | map(select(.duration != null)) | length", input).unwrap(),
+            vec!["1"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_queries() {
+        assert!(compile("").is_err());
+        let error = compile(".videos[").err().unwrap().to_string();
+        assert!(error.contains("Invalid --query expression"), "{}", error);
+        let error = compile("nosuchfn").err().unwrap().to_string();
+        assert!(error.contains("undefined filter 'nosuchfn'"), "{}", error);
+
+        // 評価中のエラー（文字列の要素は列挙できない）
+        assert!(run(".[]", json!("text")).is_err());
+    }
+}
@@ -0,0 +1,672 @@
+/// 機械可読出力のJSON Schema
+///
+/// `--machine` 出力（`output::machine_json`）と進捗JSONLの構造を、
+/// ライブラリの結果型・進捗型から schemars で生成したスキーマに
+/// 共通フィールド（`success` / `command` / `warnings`）を加えて組み立てます。
+/// 出力の構造を変更した場合は、このモジュールも合わせて更新してください。
+use schemars::{JsonSchema, schema_for};
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+use vidyeet::api::types::AssetData;
+use vidyeet::commands::result::{
+    BackupResult, BatchUploadResult, BulkStatus, CaptionsResult, DeleteResult, DoctorResult,
+    ExportResult, GcResult, GroupBy, HistoryResult, ImportResult, ItemOutcome, ListSummary,
+    LiveResetKeyResult, LoginResult, LogoutResult, PingResult, PlaybackRevokeResult, PruneResult,
+    QueueAddResult, QueueRunResult, RenameResult, RenditionsResult, RestrictionsAttachResult,
+    RestrictionsCreateResult, RestrictionsDeleteResult, RestrictionsListResult, RetryListResult,
+    RetryResult, ServeResult, ShowResult, SignResult, SignedImageUrls, StatsResult, StatusResult,
+    StoryboardResult, SyncResult, TagResult, UploadEstimateResult, UploadResult, VersionResult,
+    VideoInfo, WaitResult, WhoamiResult,
+};
+use vidyeet::domain::progress::{BatchUploadProgress, UploadPhase};
+use vidyeet::domain::warning::Warning;
+
+/// スキーマを出力できる名前（コマンド名と進捗・エラー）
+pub const SCHEMA_NAMES: &[&str] = &[
+    "login",
+    "logout",
+    "status",
+    "list",
+    "show",
+    "delete",
+    "rename",
+    "tag",
+    "renditions",
+    "captions",
+    "wait",
+    "export",
+    "import",
+    "sync",
+    "backup",
+    "gc",
+    "prune",
+    "upload",
+    "batch_upload",
+    "queue_add",
+    "queue_run",
+    "retry",
+    "retry_list",
+    "history",
+    "sign",
+    "storyboard",
+    "serve",
+    "restrictions_create",
+    "restrictions_list",
+    "restrictions_delete",
+    "restrictions_attach",
+    "playback_revoke",
+    "live_reset_key",
+    "version",
+    "stats",
+    "whoami",
+    "ping",
+    "doctor",
+    "help",
+    "progress",
+    "batch_progress",
+    "error",
+];
+
+/// JSON Schemaのメタスキーマ
+const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// 機械可読出力の形式のバージョン（すべての出力の `schema_version`）
+///
+/// 互換性のポリシー:
+/// - フィールド・コマンド・進捗フェーズの追加は互換性のある変更とし、バージョンを上げない
+/// - フィールドの削除・名前の変更・型の変更は互換性のない変更とし、バージョンを上げる
+///
+/// 出力に含まれるフィールドは `schema_baseline.json` に記録し、テストで削除されていないことを確認します。
+/// バージョンを上げた場合は `vidyeet schema` の出力からベースラインを作り直してください。
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// 機械可読出力のJSONオブジェクトに `schema_version` を付ける
+pub fn with_schema_version(mut value: Value) -> Value {
+    if let Some(object) = value.as_object_mut() {
+        object.insert("schema_version".to_string(), json!(SCHEMA_VERSION));
+    }
+    value
+}
+
+/// 名前に対応するスキーマを返す（未知の名前はNone）
+pub fn schema_for_name(name: &str) -> Option<Value> {
+    let mut schema = match name {
+        "login" => Envelope::success("login")
+            .flatten::<LoginResult>()
+            .property(
+                "action",
+                json!({ "type": "string", "enum": ["created", "updated"] }),
+                true,
+            )
+            .build(),
+        "logout" => Envelope::success("logout")
+            .flatten::<LogoutResult>()
+            .build(),
+        "status" => Envelope::success("status")
+            .flatten::<StatusResult>()
+            .build(),
+        "list" => one_of(vec![
+            // --machine 時はAPIレスポンスの完全なデータ
+            Envelope::success("list")
+                .field::<Vec<AssetData>>("data", true)
+                .field::<usize>("total_count", true)
+                .field::<ListSummary>("summary", true)
+                .property("signed_urls", signed_urls_schema(), false)
+                .build(),
+            // --offline 時はローカルインデックスの簡略データ
+            Envelope::success("list")
+                .field::<Vec<VideoInfo>>("videos", true)
+                .field::<usize>("total_count", true)
+                .field::<ListSummary>("summary", true)
+                .property("offline", json!({ "const": true }), false)
+                .field::<String>("synced_at", false)
+                .build(),
+            // --group-by 時は一覧の配列をグループごとに入れ子にする
+            Envelope::success("list")
+                .property("group_by", json!({ "enum": GroupBy::NAMES }), true)
+                .field_with::<Vec<AssetData>>("groups", true, |items| {
+                    list_groups_schema("data", items)
+                })
+                .field::<usize>("total_count", true)
+                .field::<ListSummary>("summary", true)
+                .property("signed_urls", signed_urls_schema(), false)
+                .build(),
+            Envelope::success("list")
+                .property("group_by", json!({ "enum": GroupBy::NAMES }), true)
+                .field_with::<Vec<VideoInfo>>("groups", true, |items| {
+                    list_groups_schema("videos", items)
+                })
+                .field::<usize>("total_count", true)
+                .field::<ListSummary>("summary", true)
+                .property("offline", json!({ "const": true }), false)
+                .field::<String>("synced_at", false)
+                .build(),
+        ]),
+        "show" => one_of(vec![
+            Envelope::success("show")
+                .field::<AssetData>("data", true)
+                .field::<Option<String>>("hls_url", true)
+                .field::<Option<String>>("mp4_url", true)
+                .field::<Option<String>>("thumbnail_url", true)
+                .field::<SignedImageUrls>("signed_image_urls", false)
+                .build(),
+            Envelope::success("show")
+                .flatten::<ShowResult>()
+                .without("raw_asset")
+                .build(),
+        ]),
+        "delete" => Envelope::success("delete")
+            .flatten::<DeleteResult>()
+            .build(),
+        "rename" => Envelope::success("rename")
+            .flatten::<RenameResult>()
+            .build(),
+        "renditions" => Envelope::success("renditions")
+            .flatten::<RenditionsResult>()
+            .build(),
+        "captions" => Envelope::success("captions")
+            .flatten::<CaptionsResult>()
+            .build(),
+        "wait" => Envelope::success("wait").flatten::<WaitResult>().build(),
+        "tag" => Envelope::success("tag").flatten::<TagResult>().build(),
+        "export" => Envelope::success("export")
+            .flatten::<ExportResult>()
+            .build(),
+        "import" => Envelope::success("import")
+            .field::<BulkStatus>("status", true)
+            .field::<BTreeMap<String, String>>("id_map", true)
+            .flatten::<ImportResult>()
+            .build(),
+        "sync" => Envelope::success("sync")
+            .batch()
+            .flatten::<SyncResult>()
+            .build(),
+        "backup" => Envelope::success("backup")
+            .batch()
+            .flatten::<BackupResult>()
+            .build(),
+        "gc" => Envelope::success("gc")
+            .field::<BulkStatus>("status", true)
+            .flatten::<GcResult>()
+            .build(),
+        "prune" => Envelope::success("prune")
+            .batch()
+            .flatten::<PruneResult>()
+            .build(),
+        "upload" => one_of(vec![
+            Envelope::success("upload")
+                .flatten::<UploadResult>()
+                .build(),
+            // --dry-run 時の見積もり
+            Envelope::success("upload")
+                .property("dry_run", json!({ "const": true }), true)
+                .flatten::<UploadEstimateResult>()
+                .build(),
+        ]),
+        "batch_upload" => Envelope::success("batch_upload")
+            .batch()
+            .flatten::<BatchUploadResult>()
+            .build(),
+        "queue_add" => Envelope::success("queue_add")
+            .flatten::<QueueAddResult>()
+            .build(),
+        "queue_run" => Envelope::success("queue_run")
+            .field::<BulkStatus>("status", true)
+            .field::<Vec<ItemOutcome>>("items", true)
+            .flatten::<QueueRunResult>()
+            .build(),
+        "retry" => Envelope::success("retry")
+            .field::<BulkStatus>("status", true)
+            .field::<Vec<ItemOutcome>>("items", true)
+            .flatten::<RetryResult>()
+            .build(),
+        "retry_list" => Envelope::success("retry_list")
+            .flatten::<RetryListResult>()
+            .build(),
+        "history" => Envelope::success("history")
+            .flatten::<HistoryResult>()
+            .build(),
+        "sign" => Envelope::success("sign").flatten::<SignResult>().build(),
+        "storyboard" => Envelope::success("storyboard")
+            .flatten::<StoryboardResult>()
+            .build(),
+        "serve" => Envelope::success("serve").flatten::<ServeResult>().build(),
+        "restrictions_create" => Envelope::success("restrictions_create")
+            .flatten::<RestrictionsCreateResult>()
+            .build(),
+        "restrictions_list" => Envelope::success("restrictions_list")
+            .flatten::<RestrictionsListResult>()
+            .build(),
+        "restrictions_delete" => Envelope::success("restrictions_delete")
+            .flatten::<RestrictionsDeleteResult>()
+            .build(),
+        "restrictions_attach" => Envelope::success("restrictions_attach")
+            .flatten::<RestrictionsAttachResult>()
+            .build(),
+        "playback_revoke" => Envelope::success("playback_revoke")
+            .flatten::<PlaybackRevokeResult>()
+            .build(),
+        "live_reset_key" => Envelope::success("live_reset_key")
+            .flatten::<LiveResetKeyResult>()
+            .build(),
+        "version" => Envelope::success("version")
+            .flatten::<VersionResult>()
+            .build(),
+        "stats" => Envelope::success("stats").flatten::<StatsResult>().build(),
+        "whoami" => Envelope::success("whoami")
+            .flatten::<WhoamiResult>()
+            .build(),
+        "ping" => Envelope::success("ping")
+            .field::<BulkStatus>("status", true)
+            .flatten::<PingResult>()
+            .build(),
+        "doctor" => Envelope::success("doctor")
+            .field::<bool>("passed", true)
+            .flatten::<DoctorResult>()
+            .build(),
+        "help" => Envelope::success("help").build(),
+        "progress" => versioned(root::<UploadPhase>()),
+        "batch_progress" => versioned(root::<BatchUploadProgress>()),
+        "error" => error_schema(),
+        _ => return None,
+    };
+
+    if let Some(object) = schema.as_object_mut() {
+        object.insert("$schema".to_string(), json!(SCHEMA_DIALECT));
+        object.insert("title".to_string(), json!(name));
+    }
+    Some(schema)
+}
+
+/// すべてのスキーマを名前をキーにしたオブジェクトで返す
+pub fn all_schemas() -> Value {
+    let schemas: Map<String, Value> = SCHEMA_NAMES
+        .iter()
+        .filter_map(|name| schema_for_name(name).map(|schema| (name.to_string(), schema)))
+        .collect();
+    Value::Object(schemas)
+}
+
+/// 型から生成したルートスキーマ（`$defs`を含む）
+fn root<T: JsonSchema>() -> Value {
+    let mut schema: Value = schema_for!(T).into();
+    if let Some(object) = schema.as_object_mut() {
+        object.remove("$schema");
+        object.remove("title");
+    }
+    schema
+}
+
+/// 複数の出力形式のいずれかであることを表すスキーマ
+///
+/// 各形式の`$defs`はルートにまとめる（`$ref`はルートからの参照のため）。
+fn one_of(variants: Vec<Value>) -> Value {
+    let mut defs = Map::new();
+    let variants: Vec<Value> = variants
+        .into_iter()
+        .map(|mut variant| {
+            take_defs(&mut variant, &mut defs);
+            variant
+        })
+        .collect();
+
+    let mut schema = json!({ "oneOf": variants });
+    if !defs.is_empty() {
+        schema["$defs"] = Value::Object(defs);
+    }
+    schema
+}
+
+/// スキーマから`$defs`を取り出して`defs`へ移す
+fn take_defs(schema: &mut Value, defs: &mut Map<String, Value>) {
+    if let Some(Value::Object(taken)) = schema.as_object_mut().and_then(|o| o.remove("$defs")) {
+        defs.extend(taken);
+    }
+}
+
+/// `schema_version` のスキーマ
+fn schema_version_schema() -> Value {
+    json!({ "const": SCHEMA_VERSION })
+}
+
+/// 型から生成したスキーマに `schema_version` を加える（進捗JSONLの各行）
+///
+/// 列挙型のスキーマ（`oneOf`）にも加えられるよう `allOf` で組み合わせます。
+fn versioned(mut schema: Value) -> Value {
+    let mut defs = Map::new();
+    take_defs(&mut schema, &mut defs);
+
+    let mut versioned = json!({
+        "allOf": [
+            schema,
+            {
+                "type": "object",
+                "properties": { "schema_version": schema_version_schema() },
+                "required": ["schema_version"]
+            }
+        ]
+    });
+    if !defs.is_empty() {
+        versioned["$defs"] = Value::Object(defs);
+    }
+    versioned
+}
+
+/// `list --group-by` のグループの配列のスキーマ
+///
+/// # Arguments
+/// * `items_key` - 各グループの一覧の配列のキー（`data` または `videos`）
+/// * `items` - 一覧の配列のスキーマ
+fn list_groups_schema(items_key: &str, items: Value) -> Value {
+    json!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": {
+                "key": { "type": "string" },
+                "count": { "type": "integer", "minimum": 0 },
+                "total_duration": { "type": "number" },
+                items_key: items
+            },
+            "required": ["key", "count", "total_duration", items_key]
+        }
+    })
+}
+
+/// `list` の署名付きURL（アセットIDをキーにしたオブジェクト）のスキーマ
+fn signed_urls_schema() -> Value {
+    json!({
+        "type": "object",
+        "additionalProperties": {
+            "type": "object",
+            "properties": {
+                "hls_url": { "type": ["string", "null"] },
+                "mp4_url": { "type": ["string", "null"] }
+            },
+            "required": ["hls_url", "mp4_url"]
+        }
+    })
+}
+
+/// エラーレスポンス（main.rsの`handle_error`）のスキーマ
+fn error_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "success": { "const": false },
+            "schema_version": schema_version_schema(),
+            "error": {
+                "type": "object",
+                "properties": {
+                    "code": { "type": "string" },
+                    "message": { "type": "string" },
+                    "exit_code": { "type": "integer", "enum": [1, 2, 3] },
+                    "hint": { "type": ["string", "null"] },
+                    "endpoint": { "type": "string" },
+                    "status_code": { "type": ["integer", "null"] }
+                },
+                "required": ["code", "message", "exit_code", "hint"]
+            }
+        },
+        "required": ["success", "schema_version", "error"]
+    })
+}
+
+/// 成功レスポンスのスキーマを組み立てるビルダー
+struct Envelope {
+    properties: Map<String, Value>,
+    required: Vec<String>,
+    defs: Map<String, Value>,
+}
+
+impl Envelope {
+    /// `success: true` と `command` を持つレスポンス
+    fn success(command: &str) -> Self {
+        Self {
+            properties: Map::new(),
+            required: Vec::new(),
+            defs: Map::new(),
+        }
+        .property("success", json!({ "const": true }), true)
+        .property("schema_version", schema_version_schema(), true)
+        .property("command", json!({ "const": command }), true)
+        .field::<Vec<Warning>>("warnings", true)
+    }
+
+    /// プロパティを追加する
+    fn property(mut self, name: &str, schema: Value, required: bool) -> Self {
+        self.properties.insert(name.to_string(), schema);
+        if required {
+            self.required.push(name.to_string());
+        }
+        self
+    }
+
+    /// 型から生成したスキーマのプロパティを追加する
+    fn field<T: JsonSchema>(self, name: &str, required: bool) -> Self {
+        self.field_with::<T>(name, required, |schema| schema)
+    }
+
+    /// 型から生成したスキーマを `wrap` で組み込んだプロパティを追加する（`$defs` はルートへ移す）
+    fn field_with<T: JsonSchema>(
+        mut self,
+        name: &str,
+        required: bool,
+        wrap: impl FnOnce(Value) -> Value,
+    ) -> Self {
+        let mut schema = root::<T>();
+        take_defs(&mut schema, &mut self.defs);
+        self.property(name, wrap(schema), required)
+    }
+
+    /// 一括処理に共通のプロパティ（`status`・`items`・件数・所要時間）を追加する
+    fn batch(self) -> Self {
+        self.field::<BulkStatus>("status", true)
+            .field::<Vec<ItemOutcome>>("items", true)
+            .field::<usize>("succeeded_count", true)
+            .field::<usize>("failed_count", true)
+            .field::<f64>("duration_secs", true)
+    }
+
+    /// 構造体のフィールドをそのまま展開して追加する
+    fn flatten<T: JsonSchema>(mut self) -> Self {
+        let mut schema = root::<T>();
+        take_defs(&mut schema, &mut self.defs);
+        if let Some(Value::Object(properties)) = schema.get_mut("properties").map(Value::take) {
+            self.properties.extend(properties);
+        }
+        if let Some(Value::Array(required)) = schema.get("required") {
+            self.required.extend(
+                required
+                    .iter()
+                    .filter_map(|r| r.as_str().map(str::to_string)),
+            );
+        }
+        self
+    }
+
+    /// 出力に含まれないフィールドを取り除く
+    fn without(mut self, name: &str) -> Self {
+        self.properties.remove(name);
+        self.required.retain(|r| r != name);
+        self
+    }
+
+    fn build(self) -> Value {
+        let mut schema = json!({
+            "type": "object",
+            "properties": self.properties,
+            "required": self.required,
+        });
+        if !self.defs.is_empty() {
+            schema["$defs"] = Value::Object(self.defs);
+        }
+        schema
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presentation::output::machine_json;
+    use std::collections::BTreeSet;
+    use std::time::Duration;
+    use vidyeet::commands::CommandResult;
+    use vidyeet::commands::result::{BatchDetails, BatchResult, Mp4Status};
+
+    /// スキーマに定義されたプロパティ名（oneOfの場合は指定した形式）
+    fn property_names(schema: &Value) -> Vec<String> {
+        schema["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_all_names_have_schemas() {
+        for name in SCHEMA_NAMES {
+            let schema = schema_for_name(name).unwrap();
+            assert_eq!(schema["title"], *name);
+            assert_eq!(schema["$schema"], SCHEMA_DIALECT);
+        }
+        assert!(schema_for_name("unknown").is_none());
+    }
+
+    #[test]
+    fn test_upload_schema_covers_machine_output() {
+        let result = CommandResult::Upload(Box::new(UploadResult {
+            asset_id: "asset".to_string(),
+            playback_id: None,
+            playback_policy: None,
+            hls_url: None,
+            mp4_url: None,
+            mp4_status: Mp4Status::Generating,
+            thumbnail_url: None,
+            file_path: "video.mp4".to_string(),
+            file_size: 1,
+            file_format: "mp4".to_string(),
+            deleted_old_videos: 0,
+            deleted_assets: Vec::new(),
+            media: None,
+            warnings: Vec::new(),
+            sha256: "00".to_string(),
+            uploaded_at: "2024-01-01T00:00:00Z".to_string(),
+            timings: Default::default(),
+            final_media: None,
+            copied: None,
+        }));
+        let output = machine_json(&result);
+        let schema = schema_for_name("upload").unwrap();
+        let properties = property_names(&schema["oneOf"][0]);
+
+        for key in output.as_object().unwrap().keys() {
+            assert!(properties.contains(key), "missing property: {}", key);
+        }
+        // $refはルートの$defsで解決できる
+        assert!(schema["$defs"].get("Mp4Status").is_some());
+    }
+
+    #[test]
+    fn test_batch_upload_schema_covers_machine_output() {
+        let result = CommandResult::Batch(BatchResult::new(
+            BatchDetails::Upload(BatchUploadResult {
+                uploaded: Vec::new(),
+                failed: Vec::new(),
+                concurrency: 2,
+            }),
+            Duration::from_millis(1500),
+        ));
+        let output = machine_json(&result);
+        let schema = schema_for_name("batch_upload").unwrap();
+        let properties = property_names(&schema);
+
+        assert_eq!(output["status"], "succeeded");
+        assert_eq!(output["duration_secs"], 1.5);
+        for key in output.as_object().unwrap().keys() {
+            assert!(properties.contains(key), "missing property: {}", key);
+        }
+    }
+
+    #[test]
+    fn test_login_schema_covers_machine_output() {
+        let result = CommandResult::Login(LoginResult {
+            was_logged_in: true,
+        });
+        let output = machine_json(&result);
+        let schema = schema_for_name("login").unwrap();
+        let properties = property_names(&schema);
+
+        for key in output.as_object().unwrap().keys() {
+            assert!(properties.contains(key), "missing property: {}", key);
+        }
+        assert_eq!(output["schema_version"], SCHEMA_VERSION);
+    }
+
+    /// 出力に含まれうるフィールド名（`oneOf` / `allOf` / `anyOf` の各形式を合わせる）
+    fn output_fields(schema: &Value) -> BTreeSet<String> {
+        let mut fields: BTreeSet<String> = schema["properties"]
+            .as_object()
+            .map(|properties| properties.keys().cloned().collect())
+            .unwrap_or_default();
+        for keyword in ["oneOf", "allOf", "anyOf"] {
+            for variant in schema[keyword].as_array().into_iter().flatten() {
+                fields.extend(output_fields(variant));
+            }
+        }
+        fields
+    }
+
+    #[test]
+    fn test_every_output_requires_schema_version() {
+        for name in SCHEMA_NAMES {
+            let schema = schema_for_name(name).unwrap();
+            let variants = match schema.get("oneOf") {
+                Some(Value::Array(variants)) => variants.clone(),
+                _ => vec![schema.clone()],
+            };
+            for variant in &variants {
+                let versioned = std::iter::once(variant)
+                    .chain(variant["allOf"].as_array().into_iter().flatten())
+                    .any(|s| {
+                        s["properties"]["schema_version"]["const"] == SCHEMA_VERSION
+                            && s["required"]
+                                .as_array()
+                                .is_some_and(|r| r.contains(&json!("schema_version")))
+                    });
+                assert!(versioned, "{} does not require schema_version", name);
+            }
+        }
+
+        let value = with_schema_version(json!({ "phase": "completed" }));
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+    }
+
+    /// 互換性のポリシー: 同じバージョンのうちは、出力からコマンドやフィールドを削除しない
+    ///
+    /// 互換性のない変更を行う場合は `SCHEMA_VERSION` を上げ、ベースラインを作り直す。
+    #[test]
+    fn test_outputs_keep_baseline_fields() {
+        let baseline: Value = serde_json::from_str(include_str!("schema_baseline.json")).unwrap();
+        assert_eq!(
+            baseline["schema_version"], SCHEMA_VERSION,
+            "schema_baseline.json must be regenerated when SCHEMA_VERSION changes"
+        );
+
+        for (name, fields) in baseline["schemas"].as_object().unwrap() {
+            let schema = schema_for_name(name)
+                .unwrap_or_else(|| panic!("output '{}' was removed without a version bump", name));
+            let current = output_fields(&schema);
+            for field in fields.as_array().unwrap() {
+                let field = field.as_str().unwrap();
+                assert!(
+                    current.contains(field),
+                    "field '{}' of '{}' was removed without a version bump",
+                    field,
+                    name
+                );
+            }
+        }
+    }
+}
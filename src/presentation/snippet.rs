@@ -0,0 +1,267 @@
+/// プレゼンテーション層: 共有用スニペットの出力
+///
+/// `upload` / `show` の `--output markdown|html` で、Issueトラッカーやwikiにそのまま貼り付けられる
+/// ブロック（タイトル・再生時間・サムネイル画像のリンク・HLS/MP4のリンク）をstdoutへ出力します。
+/// 内容は人間向け・機械向けの出力と同じ `CommandResult` から組み立てます。
+use crate::presentation::i18n::{Msg, t, tf};
+use crate::presentation::style;
+use anyhow::{Result, bail};
+use std::path::Path;
+use vidyeet::commands::result::{
+    BatchDetails, BatchResult, CommandResult, Mp4Status, UploadResult,
+};
+use vidyeet::domain::formatter::{self, escape_html};
+use vidyeet::domain::warning::Warning;
+
+/// スニペットの形式（`--output`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnippetFormat {
+    /// Markdown（GitHub・GitLabのIssue、wikiなど）
+    Markdown,
+    /// HTML
+    Html,
+}
+
+impl SnippetFormat {
+    /// 指定できる値（`--output`・エラーメッセージ用）
+    pub const NAMES: &'static [&'static str] = &["markdown", "html"];
+
+    /// `markdown`（`md`）/ `html` を解析する
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Some(Self::Markdown),
+            "html" => Some(Self::Html),
+            _ => None,
+        }
+    }
+}
+
+/// スニペットに載せる値
+#[derive(Debug, Clone, PartialEq)]
+struct Snippet<'a> {
+    /// タイトル（アセットのタイトル、なければファイル名・アセットID）
+    title: String,
+    /// 再生時間（秒）
+    duration: Option<f64>,
+    thumbnail_url: Option<&'a str>,
+    hls_url: Option<&'a str>,
+    mp4_url: Option<&'a str>,
+}
+
+/// 結果を共有用スニペットとしてstdoutへ出力する
+///
+/// 警告（署名キーがなくURLを出力できない場合など）はstderrに表示します。
+/// 複数ファイルのアップロードでは成功したファイルごとに1ブロックずつ出力し、失敗したファイルはstderrに表示します。
+///
+/// # Errors
+/// `upload` / `show` 以外の結果の場合
+pub fn output_snippet(result: &CommandResult, format: SnippetFormat) -> Result<()> {
+    let snippets = match result {
+        CommandResult::Upload(r) => vec![upload_snippet(r)],
+        CommandResult::Show(r) => vec![Snippet {
+            title: r.title().unwrap_or(&r.asset_id).to_string(),
+            duration: r.duration,
+            thumbnail_url: r.thumbnail(),
+            hls_url: r.hls_url.as_deref(),
+            mp4_url: r.mp4_url.as_deref(),
+        }],
+        CommandResult::Batch(BatchResult {
+            details: BatchDetails::Upload(r),
+            ..
+        }) => {
+            for failure in &r.failed {
+                eprintln!(
+                    "{}",
+                    style::error(&tf(Msg::BatchFailed, &[&failure.file_path]))
+                );
+                eprintln!("  {}", failure.error);
+            }
+            r.uploaded.iter().map(upload_snippet).collect()
+        }
+        _ => bail!("--output markdown|html is only supported by upload and show"),
+    };
+
+    let blocks: Vec<String> = snippets
+        .iter()
+        .map(|snippet| render(snippet, format))
+        .collect();
+    println!("{}", blocks.join("\n"));
+    print_warnings(result.warnings());
+    Ok(())
+}
+
+/// アップロード結果のスニペット（タイトルはファイル名）
+fn upload_snippet(r: &UploadResult) -> Snippet<'_> {
+    let title = Path::new(&r.file_path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(&r.file_path);
+    Snippet {
+        title: title.to_string(),
+        duration: r
+            .final_media
+            .as_ref()
+            .and_then(|media| media.duration)
+            .or_else(|| r.media.as_ref().and_then(|media| media.duration_secs)),
+        thumbnail_url: r.thumbnail_url.as_deref(),
+        hls_url: r.hls_url.as_deref(),
+        // `--mp4 none` の場合はMP4のリンクを載せない
+        mp4_url: r
+            .mp4_url
+            .as_deref()
+            .filter(|_| !matches!(r.mp4_status, Mp4Status::Disabled)),
+    }
+}
+
+/// スニペットを指定した形式の文字列にする
+///
+/// サムネイル画像は、ブラウザでそのまま再生できるMP4（なければHLS）へのリンクにします。
+fn render(snippet: &Snippet, format: SnippetFormat) -> String {
+    let link = snippet.mp4_url.or(snippet.hls_url);
+    let duration = snippet.duration.map(formatter::format_duration);
+    let mut lines = Vec::new();
+
+    match format {
+        SnippetFormat::Markdown => {
+            let title = escape_markdown(&snippet.title);
+            lines.push(format!("**{}**", title));
+            lines.push(String::new());
+            if let Some(thumbnail_url) = snippet.thumbnail_url {
+                let image = format!("![{}]({})", title, thumbnail_url);
+                match link {
+                    Some(link) => lines.push(format!("[{}]({})", image, link)),
+                    None => lines.push(image),
+                }
+                lines.push(String::new());
+            }
+            if let Some(duration) = duration {
+                lines.push(format!("- {}: {}", t(Msg::SnippetDuration), duration));
+            }
+            for (label, url) in [("HLS", snippet.hls_url), ("MP4", snippet.mp4_url)] {
+                if let Some(url) = url {
+                    lines.push(format!("- {}: <{}>", label, url));
+                }
+            }
+        }
+        SnippetFormat::Html => {
+            let title = escape_html(&snippet.title);
+            lines.push(format!("<p><strong>{}</strong></p>", title));
+            if let Some(thumbnail_url) = snippet.thumbnail_url {
+                let image = format!(
+                    "<img src=\"{}\" alt=\"{}\">",
+                    escape_html(thumbnail_url),
+                    title
+                );
+                match link {
+                    Some(link) => lines.push(format!(
+                        "<p><a href=\"{}\">{}</a></p>",
+                        escape_html(link),
+                        image
+                    )),
+                    None => lines.push(format!("<p>{}</p>", image)),
+                }
+            }
+            lines.push("<ul>".to_string());
+            if let Some(duration) = duration {
+                lines.push(format!(
+                    "  <li>{}: {}</li>",
+                    t(Msg::SnippetDuration),
+                    duration
+                ));
+            }
+            for (label, url) in [("HLS", snippet.hls_url), ("MP4", snippet.mp4_url)] {
+                if let Some(url) = url {
+                    let url = escape_html(url);
+                    lines.push(format!(
+                        "  <li>{}: <a href=\"{}\">{}</a></li>",
+                        label, url, url
+                    ));
+                }
+            }
+            lines.push("</ul>".to_string());
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Markdownで書式として解釈される文字をエスケープする
+fn escape_markdown(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '(' | ')' | '<' | '>' | '#' | '|' | '!'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 警告をstderrに表示する
+fn print_warnings(warnings: &[Warning]) {
+    for warning in warnings {
+        eprintln!("{}", style::warning(&tf(Msg::Warning, &[warning])));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippet() -> Snippet<'static> {
+        Snippet {
+            title: "Demo [final] <v2>".to_string(),
+            duration: Some(323.5),
+            thumbnail_url: Some("https://image.mux.com/play_1/thumbnail.jpg?time=32"),
+            hls_url: Some("https://stream.mux.com/play_1.m3u8"),
+            mp4_url: None,
+        }
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let markdown = render(&snippet(), SnippetFormat::Markdown);
+        assert_eq!(
+            markdown,
+            format!(
+                "**Demo \\[final\\] \\<v2\\>**\n\n\
+                 [![Demo \\[final\\] \\<v2\\>](https://image.mux.com/play_1/thumbnail.jpg?time=32)](https://stream.mux.com/play_1.m3u8)\n\n\
+                 - {}: 5:23\n\
+                 - HLS: <https://stream.mux.com/play_1.m3u8>\n",
+                t(Msg::SnippetDuration)
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_html_escapes_values() {
+        let html = render(&snippet(), SnippetFormat::Html);
+        assert!(html.starts_with("<p><strong>Demo [final] &lt;v2&gt;</strong></p>\n"));
+        assert!(html.contains(
+            "<a href=\"https://stream.mux.com/play_1.m3u8\"><img src=\"https://image.mux.com/play_1/thumbnail.jpg?time=32\" alt=\"Demo [final] &lt;v2&gt;\"></a>"
+        ));
+        assert!(!html.contains("MP4"));
+
+        let without_media = Snippet {
+            thumbnail_url: None,
+            duration: None,
+            ..snippet()
+        };
+        let html = render(&without_media, SnippetFormat::Html);
+        assert!(!html.contains("<img"));
+        assert!(html.ends_with("</ul>\n"));
+    }
+
+    #[test]
+    fn test_snippet_format_parse() {
+        assert_eq!(
+            SnippetFormat::parse("Markdown"),
+            Some(SnippetFormat::Markdown)
+        );
+        assert_eq!(SnippetFormat::parse("md"), Some(SnippetFormat::Markdown));
+        assert_eq!(SnippetFormat::parse("html"), Some(SnippetFormat::Html));
+        assert_eq!(SnippetFormat::parse("json"), None);
+    }
+}
@@ -0,0 +1,108 @@
+/// プレゼンテーション層: 待機中スピナー表示
+///
+/// 所要時間が不定なフェーズ（Direct Upload作成、アセット作成待機）で、
+/// 端末上にアニメーションするスピナーと経過時間を1行で表示します。
+/// stderrが端末でない場合（リダイレクト・CI環境など）は使用せず、
+/// 呼び出し側で従来の定期的なテキスト行表示にフォールバックします。
+use std::io::{self, IsTerminal, Write};
+use std::time::Instant;
+
+/// スピナーのアニメーションフレーム
+const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// 行クリア用のANSIエスケープシーケンス（カーソルを行頭に戻して行全体を消去）
+const CLEAR_LINE: &str = "\r\x1b[2K";
+
+/// stderrが端末（TTY）かどうかを判定
+///
+/// スピナーのような上書き表示は端末でのみ意味を持つため、
+/// 表示方法の切り替えに使用します。
+pub fn stderr_is_terminal() -> bool {
+    io::stderr().is_terminal()
+}
+
+/// 経過時間付きの待機スピナー
+///
+/// `tick()`ごとにフレームを進めて同じ行を上書きし、
+/// `finish()`で経過時間付きの確定行に置き換えます。
+#[derive(Debug)]
+pub struct Spinner {
+    /// 表示メッセージ
+    message: String,
+    /// 開始時刻
+    started_at: Instant,
+    /// 現在のフレーム番号
+    frame: usize,
+}
+
+impl Spinner {
+    /// スピナーを開始し、初回フレームを描画する
+    pub fn start(message: impl Into<String>) -> Self {
+        let spinner = Self {
+            message: message.into(),
+            started_at: Instant::now(),
+            frame: 0,
+        };
+        spinner.render();
+        spinner
+    }
+
+    /// フレームを1つ進めて再描画する
+    pub fn tick(&mut self) {
+        self.frame = (self.frame + 1) % SPINNER_FRAMES.len();
+        self.render();
+    }
+
+    /// スピナーを停止し、経過時間付きの確定行を出力する
+    pub fn finish(self) {
+        eprintln!(
+            "{}{} ({}s)",
+            CLEAR_LINE,
+            self.message,
+            self.started_at.elapsed().as_secs()
+        );
+    }
+
+    /// 現在のフレームの表示行を生成（制御文字を含まない）
+    fn format_line(&self) -> String {
+        format!(
+            "{} {} ({}s)",
+            SPINNER_FRAMES[self.frame],
+            self.message,
+            self.started_at.elapsed().as_secs()
+        )
+    }
+
+    /// 現在の行を上書き描画する
+    fn render(&self) {
+        eprint!("{}{}", CLEAR_LINE, self.format_line());
+        let _ = io::stderr().flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spinner_line_contains_message_and_elapsed() {
+        let spinner = Spinner::start("Waiting for asset creation...");
+        let line = spinner.format_line();
+
+        assert!(line.starts_with(SPINNER_FRAMES[0]));
+        assert!(line.contains("Waiting for asset creation..."));
+        assert!(line.ends_with("(0s)"));
+    }
+
+    #[test]
+    fn test_spinner_tick_wraps_around_frames() {
+        let mut spinner = Spinner::start("test");
+        for _ in 0..SPINNER_FRAMES.len() {
+            spinner.tick();
+        }
+        assert_eq!(spinner.frame, 0);
+
+        spinner.tick();
+        assert!(spinner.format_line().starts_with(SPINNER_FRAMES[1]));
+    }
+}
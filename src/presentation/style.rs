@@ -0,0 +1,110 @@
+/// プレゼンテーション層: 端末出力の色付け
+///
+/// 人間向け出力（stderr）の成功・エラー・ヒントなどをANSIエスケープで装飾します。
+/// `--no-color`、環境変数 `NO_COLOR`（https://no-color.org/）、`TERM=dumb`、
+/// stderrが端末でない場合は装飾せず、そのままの文字列を返します。
+/// 機械可読出力（stdout）には使用しません。
+use crate::presentation::spinner;
+use std::env;
+use std::sync::OnceLock;
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+
+/// 色付けが有効かどうか
+static COLOR_ENABLED: OnceLock<bool> = OnceLock::new();
+
+/// 色付けの有無を決定する（最初の呼び出しのみ有効）
+///
+/// # Arguments
+/// * `no_color_flag` - `--no-color` が指定されたか
+pub fn init(no_color_flag: bool) {
+    let _ = COLOR_ENABLED.set(detect(no_color_flag));
+}
+
+/// 色付けが有効かどうか（未初期化の場合は `--no-color` なしとして判定）
+fn enabled() -> bool {
+    *COLOR_ENABLED.get_or_init(|| detect(false))
+}
+
+/// 環境から色付けの有無を判定する
+fn detect(no_color_flag: bool) -> bool {
+    should_color(
+        no_color_flag,
+        env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()),
+        env::var("TERM").is_ok_and(|term| term == "dumb"),
+        spinner::stderr_is_terminal(),
+    )
+}
+
+/// 色付けするかを判定する
+fn should_color(no_color_flag: bool, no_color_env: bool, dumb_term: bool, is_tty: bool) -> bool {
+    !no_color_flag && !no_color_env && !dumb_term && is_tty
+}
+
+fn paint(text: &str, codes: &str, enabled: bool) -> String {
+    if enabled {
+        format!("{}{}{}", codes, text, RESET)
+    } else {
+        text.to_string()
+    }
+}
+
+/// 成功（緑）
+pub fn success(text: &str) -> String {
+    paint(text, GREEN, enabled())
+}
+
+/// エラー・失敗（赤の太字）
+pub fn error(text: &str) -> String {
+    paint(text, &format!("{}{}", BOLD, RED), enabled())
+}
+
+/// 警告・注意（黄）
+pub fn warning(text: &str) -> String {
+    paint(text, YELLOW, enabled())
+}
+
+/// ヒント・補足（暗め）
+pub fn hint(text: &str) -> String {
+    paint(text, DIM, enabled())
+}
+
+/// 見出し（太字）
+pub fn heading(text: &str) -> String {
+    paint(text, BOLD, enabled())
+}
+
+/// アセットのステータスに応じた色（ready: 緑、preparing: 黄、errored: 赤）
+pub fn asset_status(status: &str, text: &str) -> String {
+    match status {
+        "ready" => success(text),
+        "preparing" => warning(text),
+        "errored" => error(text),
+        _ => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_color() {
+        assert!(should_color(false, false, false, true));
+        assert!(!should_color(true, false, false, true));
+        assert!(!should_color(false, true, false, true));
+        assert!(!should_color(false, false, true, true));
+        assert!(!should_color(false, false, false, false));
+    }
+
+    #[test]
+    fn test_paint() {
+        assert_eq!(paint("ok", GREEN, true), "\x1b[32mok\x1b[0m");
+        assert_eq!(paint("ok", GREEN, false), "ok");
+    }
+}
@@ -0,0 +1,62 @@
+/// プレゼンテーション層: バイト数・転送速度の表示
+///
+/// 通常は `domain::formatter` で「10.00 MB」「1.50 GB」の形式にし、
+/// グローバルフラグ `--bytes` 指定時は換算せずにバイト数をそのまま表示します。
+/// 機械可読出力（stdout）は常にバイト数のため使用しません。
+use std::sync::OnceLock;
+use vidyeet::domain::formatter;
+
+/// バイト数をそのまま表示するか（`--bytes`）
+static RAW_BYTES: OnceLock<bool> = OnceLock::new();
+
+/// バイト数をそのまま表示するかを決定する（最初の呼び出しのみ有効）
+pub fn init(raw_bytes: bool) {
+    let _ = RAW_BYTES.set(raw_bytes);
+}
+
+fn raw_bytes() -> bool {
+    RAW_BYTES.get().copied().unwrap_or(false)
+}
+
+/// バイト数を表示する（例: "10.00 MB"、`--bytes` 指定時は "10485760 bytes"）
+pub fn size(bytes: u64) -> String {
+    format_size(bytes, raw_bytes())
+}
+
+/// 転送速度を表示する（例: "12.50 MB/s"、`--bytes` 指定時は "13107200 bytes/s"）
+pub fn rate(bytes_per_sec: f64) -> String {
+    format_rate(bytes_per_sec, raw_bytes())
+}
+
+fn format_size(bytes: u64, raw: bool) -> String {
+    if raw {
+        format!("{} bytes", bytes)
+    } else {
+        formatter::format_size(bytes)
+    }
+}
+
+fn format_rate(bytes_per_sec: f64, raw: bool) -> String {
+    if raw {
+        format!("{:.0} bytes/s", bytes_per_sec)
+    } else {
+        formatter::format_rate(bytes_per_sec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size_raw_and_humanized() {
+        assert_eq!(format_size(10 * 1_048_576, false), "10.00 MB");
+        assert_eq!(format_size(10 * 1_048_576, true), "10485760 bytes");
+    }
+
+    #[test]
+    fn test_format_rate_raw_and_humanized() {
+        assert_eq!(format_rate(13_107_200.0, false), "12.50 MB/s");
+        assert_eq!(format_rate(13_107_200.0, true), "13107200 bytes/s");
+    }
+}
@@ -0,0 +1,331 @@
+/// ローカルアセットインデックス
+///
+/// `list` や `upload` のたびに既知のアセット（ID、タイトル、ステータス、URL、作成日時）を
+/// データディレクトリの `assets.json` に記録し、
+/// ネットワークが使えない場合でも `list --offline` で一覧を参照できるようにします。
+use crate::api::error::InfraError;
+use crate::api::types::AssetData;
+use crate::config::UserConfig;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// インデックスファイル名（データディレクトリ配下）
+const INDEX_FILE_NAME: &str = "assets.json";
+
+/// インデックスに記録するアセット情報
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedAsset {
+    /// アセットID
+    pub asset_id: String,
+    /// タイトル（meta.title）
+    pub title: Option<String>,
+    /// 外部ID（meta.external_id）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// タグ（passthroughに記録したもの）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 最後に確認したステータス
+    pub status: String,
+    /// 再生ID
+    pub playback_id: Option<String>,
+    /// 再生IDのポリシー（public, signed）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub playback_policy: Option<String>,
+    /// HLS再生URL
+    pub hls_url: Option<String>,
+    /// MP4再生URL
+    pub mp4_url: Option<String>,
+    /// 動画時間（秒）
+    pub duration: Option<f64>,
+    /// アスペクト比
+    pub aspect_ratio: Option<String>,
+    /// 作成日時（Unix timestamp）
+    pub created_at: String,
+    /// 元ファイルのSHA-256（アップロード時にpassthroughへ記録したもの）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// 元ファイル名（このCLIでアップロードした場合のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    /// 元ファイルのサイズ（このCLIでアップロードした場合のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_size: Option<u64>,
+}
+
+impl IndexedAsset {
+    /// APIのアセットデータからインデックス項目を作成
+    pub fn from_asset(asset: &AssetData) -> Self {
+        Self {
+            asset_id: asset.id.clone(),
+            title: asset.title().map(str::to_string),
+            external_id: asset.external_id().map(str::to_string),
+            status: asset.status.to_string(),
+            playback_id: asset.playback_ids.first().map(|p| p.id.clone()),
+            playback_policy: asset.playback_ids.first().map(|p| p.policy.clone()),
+            hls_url: asset.get_playback_url(),
+            mp4_url: asset.get_mp4_playback_url(),
+            duration: asset.duration,
+            aspect_ratio: asset.aspect_ratio.clone(),
+            created_at: asset.created_at.clone(),
+            sha256: asset.sha256(),
+            tags: asset.tags(),
+            file_name: None,
+            file_size: None,
+        }
+    }
+
+    /// APIからは取得できないローカルの情報（元ファイル名・サイズ・チェックサム）を引き継ぐ
+    fn inherit_local_fields(&mut self, previous: &IndexedAsset) {
+        if self.sha256.is_none() {
+            self.sha256 = previous.sha256.clone();
+        }
+        if self.file_name.is_none() {
+            self.file_name = previous.file_name.clone();
+        }
+        if self.file_size.is_none() {
+            self.file_size = previous.file_size;
+        }
+    }
+}
+
+/// インデックスファイルの形式
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IndexFile {
+    /// 最後にAPIと同期した時刻（Unix timestamp）
+    synced_at: Option<String>,
+    /// アセット一覧（作成日時の昇順）
+    assets: Vec<IndexedAsset>,
+}
+
+/// ローカルアセットインデックス
+#[derive(Debug)]
+pub struct AssetIndex {
+    path: PathBuf,
+    file: IndexFile,
+}
+
+impl AssetIndex {
+    /// 空のインデックスを作成（保存先のみ指定）
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            file: IndexFile::default(),
+        }
+    }
+
+    /// 既定の場所（データディレクトリ配下）のインデックスパス
+    pub fn default_path() -> Result<PathBuf, InfraError> {
+        UserConfig::data_dir()
+            .map(|dir| dir.join(INDEX_FILE_NAME))
+            .map_err(|e| InfraError::storage(INDEX_FILE_NAME, e.to_string()))
+    }
+
+    /// 既定の場所からインデックスを読み込む
+    pub fn load_default() -> Result<Self, InfraError> {
+        Self::load(&Self::default_path()?)
+    }
+
+    /// 既定の場所のインデックスを更新して保存する
+    ///
+    /// インデックスは補助的なデータのため、読み書きの失敗は無視します。
+    /// 既存のファイルが壊れている場合は空のインデックスから作り直します。
+    pub fn update_default(update: impl FnOnce(&mut AssetIndex)) {
+        let Ok(path) = Self::default_path() else {
+            return;
+        };
+        let mut index = Self::load(&path).unwrap_or_else(|_| Self::new(path));
+        update(&mut index);
+        let _ = index.save();
+    }
+
+    /// インデックスを読み込む（ファイルが存在しない場合は空）
+    pub fn load(path: &Path) -> Result<Self, InfraError> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: storage::read_json(path)?.unwrap_or_default(),
+        })
+    }
+
+    /// インデックスを保存する
+    pub fn save(&self) -> Result<(), InfraError> {
+        storage::write_json_atomic(&self.path, &self.file)
+    }
+
+    /// 記録されているアセット一覧
+    pub fn assets(&self) -> &[IndexedAsset] {
+        &self.file.assets
+    }
+
+    /// 最後にAPIと同期した時刻（Unix timestamp、未同期ならNone）
+    pub fn synced_at(&self) -> Option<&str> {
+        self.file.synced_at.as_deref()
+    }
+
+    /// APIから取得した全アセットでインデックスを置き換える（同期時刻も更新）
+    ///
+    /// このCLIでアップロードした際に記録した元ファイルの情報は引き継ぎます。
+    pub fn replace_all(&mut self, mut assets: Vec<IndexedAsset>) {
+        for asset in &mut assets {
+            if let Some(previous) = self.find(&asset.asset_id) {
+                asset.inherit_local_fields(previous);
+            }
+        }
+        self.file.assets = assets;
+        self.sort();
+        self.file.synced_at = Some(unix_now().to_string());
+    }
+
+    /// アセットを追加または更新する
+    pub fn upsert(&mut self, asset: IndexedAsset) {
+        match self
+            .file
+            .assets
+            .iter_mut()
+            .find(|a| a.asset_id == asset.asset_id)
+        {
+            Some(existing) => {
+                let mut asset = asset;
+                asset.inherit_local_fields(existing);
+                *existing = asset;
+            }
+            None => self.file.assets.push(asset),
+        }
+        self.sort();
+    }
+
+    /// IDでアセットを探す
+    pub fn find(&self, asset_id: &str) -> Option<&IndexedAsset> {
+        self.file.assets.iter().find(|a| a.asset_id == asset_id)
+    }
+
+    /// 同じサイズの元ファイルからアップロードしたアセットを探す（重複アップロードの候補）
+    pub fn uploaded_with_size(&self, file_size: u64) -> impl Iterator<Item = &IndexedAsset> {
+        self.file
+            .assets
+            .iter()
+            .filter(move |a| a.file_size == Some(file_size))
+    }
+
+    /// アセットを削除する
+    pub fn remove(&mut self, asset_id: &str) {
+        self.file.assets.retain(|a| a.asset_id != asset_id);
+    }
+
+    /// 作成日時の昇順に並べる
+    fn sort(&mut self) {
+        self.file
+            .assets
+            .sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    }
+}
+
+/// 現在時刻（Unix秒）
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn indexed(id: &str, created_at: &str) -> IndexedAsset {
+        IndexedAsset {
+            asset_id: id.to_string(),
+            title: None,
+            external_id: None,
+            tags: Vec::new(),
+            status: "ready".to_string(),
+            playback_id: None,
+            playback_policy: None,
+            hls_url: None,
+            mp4_url: None,
+            duration: None,
+            aspect_ratio: None,
+            created_at: created_at.to_string(),
+            sha256: None,
+            file_name: None,
+            file_size: None,
+        }
+    }
+
+    #[test]
+    fn test_missing_index_loads_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = AssetIndex::load(&dir.path().join("assets.json")).unwrap();
+
+        assert!(index.assets().is_empty());
+        assert!(index.synced_at().is_none());
+    }
+
+    #[test]
+    fn test_replace_save_and_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("assets.json");
+
+        let mut index = AssetIndex::load(&path).unwrap();
+        index.replace_all(vec![indexed("b", "200"), indexed("a", "100")]);
+        index.save().unwrap();
+
+        let reloaded = AssetIndex::load(&path).unwrap();
+        let ids: Vec<_> = reloaded
+            .assets()
+            .iter()
+            .map(|a| a.asset_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["a", "b"]);
+        assert!(reloaded.synced_at().is_some());
+    }
+
+    #[test]
+    fn test_upsert_and_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut index = AssetIndex::load(&dir.path().join("assets.json")).unwrap();
+
+        index.upsert(indexed("a", "100"));
+        let mut updated = indexed("a", "100");
+        updated.status = "errored".to_string();
+        index.upsert(updated);
+        assert_eq!(index.assets().len(), 1);
+        assert_eq!(index.assets()[0].status, "errored");
+
+        index.remove("a");
+        assert!(index.assets().is_empty());
+    }
+
+    #[test]
+    fn test_corrupted_index_is_storage_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("assets.json");
+        fs::write(&path, "not json").unwrap();
+
+        let err = AssetIndex::load(&path).unwrap_err();
+        assert!(matches!(err, InfraError::Storage { .. }));
+    }
+
+    #[test]
+    fn test_sync_keeps_local_upload_details() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut index = AssetIndex::load(&dir.path().join("assets.json")).unwrap();
+
+        let mut uploaded = indexed("a", "100");
+        uploaded.file_name = Some("clip.mp4".to_string());
+        uploaded.file_size = Some(1024);
+        uploaded.sha256 = Some("abc".to_string());
+        index.upsert(uploaded);
+
+        // APIからの同期ではファイル名・サイズが分からない
+        index.replace_all(vec![indexed("a", "100"), indexed("b", "200")]);
+
+        let candidates: Vec<_> = index.uploaded_with_size(1024).collect();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].file_name.as_deref(), Some("clip.mp4"));
+        assert_eq!(candidates[0].sha256.as_deref(), Some("abc"));
+    }
+}
@@ -0,0 +1,98 @@
+/// バックアップのダウンロード記録
+///
+/// `backup --output <dir>` でダウンロードしたファイルのサイズとSHA-256を、
+/// 出力先ディレクトリの `.vidyeet-backup.json` に記録します。
+/// 再実行時は、既にあるファイルをこの記録（とMux APIが返すファイルサイズ）と照合して
+/// 一致するものをダウンロードしません。
+use crate::api::error::InfraError;
+use crate::config::APP_CONFIG;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// 記録ファイルの形式バージョン（項目の削除・意味の変更時に上げる）
+const BACKUP_STATE_VERSION: u32 = 1;
+
+/// ダウンロードしたファイルの記録
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupRecord {
+    /// アセットID
+    pub asset_id: String,
+    /// ファイルサイズ（bytes）
+    pub size: u64,
+    /// ファイルのSHA-256（16進小文字）
+    pub sha256: String,
+    /// ダウンロード日時（RFC 3339、UTC）
+    pub downloaded_at: String,
+}
+
+/// 出力先ディレクトリのダウンロード記録
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupState {
+    /// 形式バージョン
+    pub version: u32,
+    /// 出力先からの相対パス（`/` 区切り）ごとの記録
+    pub files: BTreeMap<String, BackupRecord>,
+}
+
+impl Default for BackupState {
+    fn default() -> Self {
+        Self {
+            version: BACKUP_STATE_VERSION,
+            files: BTreeMap::new(),
+        }
+    }
+}
+
+impl BackupState {
+    /// 出力先ディレクトリの記録ファイルのパス
+    pub fn path_for(output_dir: &Path) -> PathBuf {
+        output_dir.join(APP_CONFIG.backup.state_file_name)
+    }
+
+    /// 記録を読み込む（ない場合は空の記録）
+    pub fn load(output_dir: &Path) -> Result<Self, InfraError> {
+        Ok(storage::read_json(&Self::path_for(output_dir))?.unwrap_or_default())
+    }
+
+    /// 記録を保存する
+    pub fn save(&self, output_dir: &Path) -> Result<(), InfraError> {
+        storage::write_json_atomic(&Self::path_for(output_dir), self)
+    }
+
+    /// 相対パスの記録
+    pub fn get(&self, relative_path: &str) -> Option<&BackupRecord> {
+        self.files.get(relative_path)
+    }
+
+    /// ダウンロードしたファイルを記録する（既存の記録は置き換える）
+    pub fn record(&mut self, relative_path: &str, record: BackupRecord) {
+        self.files.insert(relative_path.to_string(), record);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_state_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(BackupState::load(dir.path()).unwrap().files.is_empty());
+
+        let record = BackupRecord {
+            asset_id: "asset1".to_string(),
+            size: 42,
+            sha256: "abc".to_string(),
+            downloaded_at: "2024-01-01T00:00:00Z".to_string(),
+        };
+        let mut state = BackupState::default();
+        state.record("asset1/highest.mp4", record.clone());
+        state.save(dir.path()).unwrap();
+
+        let loaded = BackupState::load(dir.path()).unwrap();
+        assert_eq!(loaded.get("asset1/highest.mp4"), Some(&record));
+        assert!(BackupState::path_for(dir.path()).exists());
+    }
+}
@@ -0,0 +1,274 @@
+/// アセットのエクスポート
+///
+/// `export --output <path>` で、全アセットの完全なデータ（再生ID・レンディションを含む `AssetData`）を
+/// JSONまたはCSVで書き出します。形式は出力ファイルの拡張子で決まります。
+/// 大量のアセットを取得する途中で中断しても再開できるよう、取得済みのページを
+/// 出力先の隣のチェックポイント（`<output>.partial`）に保存します。
+use crate::api::error::InfraError;
+use crate::api::pagination::PageCursor;
+use crate::api::types::AssetData;
+use crate::storage;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// エクスポートファイルの形式バージョン（項目の削除・意味の変更時に上げる）
+pub const EXPORT_VERSION: u32 = 1;
+
+/// 出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// 完全な `AssetData` の配列を含むJSON
+    Json,
+    /// 主要な項目を1行1アセットにまとめたCSV
+    Csv,
+}
+
+impl ExportFormat {
+    /// 出力ファイルの拡張子から形式を判定する（未対応の拡張子はNone）
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// JSON形式のエクスポートファイル
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetExport {
+    /// 形式バージョン
+    pub version: u32,
+    /// 作成日時（RFC 3339、UTC）
+    pub exported_at: String,
+    /// アセット数
+    pub total_count: usize,
+    /// 全アセットの完全なデータ（作成日時の昇順）
+    pub assets: Vec<AssetData>,
+}
+
+impl AssetExport {
+    /// アセットからエクスポートを作成する
+    pub fn new(mut assets: Vec<AssetData>) -> Self {
+        assets.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Self {
+            version: EXPORT_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            total_count: assets.len(),
+            assets,
+        }
+    }
+
+    /// 指定した形式で書き出す（既存のファイルは置き換える）
+    pub fn write(&self, path: &Path, format: ExportFormat) -> Result<(), InfraError> {
+        match format {
+            ExportFormat::Json => storage::write_json_atomic(path, self),
+            ExportFormat::Csv => {
+                let to_storage_error = |e: std::io::Error| {
+                    InfraError::storage(path.display().to_string(), e.to_string())
+                };
+                let tmp_path = path.with_extension("csv.tmp");
+                fs::write(&tmp_path, to_csv(&self.assets)).map_err(to_storage_error)?;
+                fs::rename(&tmp_path, path).map_err(to_storage_error)
+            }
+        }
+    }
+}
+
+/// CSVの列
+const CSV_HEADER: &[&str] = &[
+    "asset_id",
+    "title",
+    "external_id",
+    "status",
+    "duration",
+    "aspect_ratio",
+    "resolution_tier",
+    "video_quality",
+    "created_at",
+    "playback_ids",
+    "hls_url",
+    "mp4_url",
+    "static_renditions",
+    "tags",
+    "sha256",
+    "passthrough",
+];
+
+/// アセットをCSVにする（複数の値を持つ列は `;` 区切り）
+fn to_csv(assets: &[AssetData]) -> String {
+    let mut lines = vec![CSV_HEADER.join(",")];
+    lines.extend(assets.iter().map(|asset| {
+        let playback_ids: Vec<String> = asset
+            .playback_ids
+            .iter()
+            .map(|p| format!("{}:{}", p.policy, p.id))
+            .collect();
+        let renditions: Vec<&str> = asset
+            .static_renditions
+            .iter()
+            .flat_map(|wrapper| wrapper.files.iter())
+            .map(|file| file.name.as_str())
+            .collect();
+
+        [
+            asset.id.clone(),
+            asset.title().unwrap_or_default().to_string(),
+            asset.external_id().unwrap_or_default().to_string(),
+            asset.status.to_string(),
+            asset.duration.map(|d| d.to_string()).unwrap_or_default(),
+            asset.aspect_ratio.clone().unwrap_or_default(),
+            asset.resolution_tier.clone().unwrap_or_default(),
+            asset.video_quality.clone().unwrap_or_default(),
+            asset.created_at.clone(),
+            playback_ids.join(";"),
+            asset.get_playback_url().unwrap_or_default(),
+            asset.get_mp4_playback_url().unwrap_or_default(),
+            renditions.join(";"),
+            asset.tags().join(";"),
+            asset.sha256().unwrap_or_default(),
+            asset.passthrough.clone().unwrap_or_default(),
+        ]
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",")
+    }));
+    lines.push(String::new());
+    lines.join("\r\n")
+}
+
+/// CSVの値をエスケープする（RFC 4180）
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 中断したエクスポートのチェックポイント
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportCheckpoint {
+    /// 形式バージョン
+    pub version: u32,
+    /// 次に取得するページの位置
+    pub next: PageCursor,
+    /// 取得済みのアセット
+    pub assets: Vec<AssetData>,
+}
+
+impl ExportCheckpoint {
+    /// 最初のページから始めるチェックポイント
+    pub fn start() -> Self {
+        Self {
+            version: EXPORT_VERSION,
+            next: PageCursor::first(),
+            assets: Vec::new(),
+        }
+    }
+
+    /// 出力先に対応するチェックポイントのパス（`<output>.partial`）
+    pub fn path_for(output: &Path) -> PathBuf {
+        let mut path = output.as_os_str().to_owned();
+        path.push(".partial");
+        PathBuf::from(path)
+    }
+
+    /// チェックポイントを読み込む（ない場合・形式バージョンが異なる場合はNone）
+    pub fn load(path: &Path) -> Result<Option<Self>, InfraError> {
+        Ok(storage::read_json::<Self>(path)?.filter(|c| c.version == EXPORT_VERSION))
+    }
+
+    /// チェックポイントを保存する
+    pub fn save(&self, path: &Path) -> Result<(), InfraError> {
+        storage::write_json_atomic(path, self)
+    }
+
+    /// チェックポイントを削除する（存在しなければ何もしない）
+    pub fn remove(path: &Path) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(id: &str, created_at: &str, title: Option<&str>) -> AssetData {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "status": "ready",
+            "created_at": created_at,
+            "playback_ids": [{ "id": "play_1", "policy": "public" }],
+            "duration": 12.5,
+            "meta": { "title": title },
+            "static_renditions": { "files": [{
+                "id": "r1", "type": "standard", "status": "ready",
+                "resolution": "highest", "name": "highest.mp4", "ext": "mp4"
+            }] }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            ExportFormat::from_path(Path::new("assets.json")),
+            Some(ExportFormat::Json)
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("out/Assets.CSV")),
+            Some(ExportFormat::Csv)
+        );
+        assert_eq!(ExportFormat::from_path(Path::new("assets.txt")), None);
+        assert_eq!(ExportFormat::from_path(Path::new("assets")), None);
+    }
+
+    #[test]
+    fn test_csv_escapes_fields() {
+        let csv = to_csv(&[asset("a1", "1", Some("Intro, \"final\""))]);
+        let mut lines = csv.split("\r\n");
+        assert_eq!(lines.next().unwrap(), CSV_HEADER.join(","));
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("a1,\"Intro, \"\"final\"\"\",,ready,12.5,"));
+        assert!(row.contains(",public:play_1,https://stream.mux.com/play_1.m3u8,"));
+        assert!(row.contains(",highest.mp4,"));
+        assert_eq!(csv_field("plain"), "plain");
+    }
+
+    #[test]
+    fn test_write_json_and_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("assets.json");
+
+        AssetExport::new(vec![asset("b", "2", None), asset("a", "1", None)])
+            .write(&output, ExportFormat::Json)
+            .unwrap();
+        let export: AssetExport =
+            serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(export.total_count, 2);
+        assert_eq!(export.assets[0].id, "a");
+        assert_eq!(export.assets[0].playback_ids[0].id, "play_1");
+
+        let checkpoint = ExportCheckpoint::path_for(&output);
+        assert_eq!(checkpoint, dir.path().join("assets.json.partial"));
+        ExportCheckpoint {
+            next: PageCursor::Page(3),
+            assets: export.assets,
+            ..ExportCheckpoint::start()
+        }
+        .save(&checkpoint)
+        .unwrap();
+        let loaded = ExportCheckpoint::load(&checkpoint).unwrap().unwrap();
+        assert_eq!(loaded.next, PageCursor::Page(3));
+        assert_eq!(loaded.assets.len(), 2);
+
+        ExportCheckpoint::remove(&checkpoint);
+        assert!(ExportCheckpoint::load(&checkpoint).unwrap().is_none());
+    }
+}
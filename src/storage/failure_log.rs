@@ -0,0 +1,195 @@
+/// 失敗したアップロードの記録
+///
+/// `upload` で失敗したファイルを、指定したオプションとエラーとともに
+/// データディレクトリの `failures.json` に保持し、`retry` で同じ条件のまま再実行できるようにします。
+/// 同じファイルの失敗は1件にまとめ、アップロードに成功した時点で記録から取り除きます。
+use crate::api::error::InfraError;
+use crate::commands::upload::UploadOptions;
+use crate::config::UserConfig;
+use crate::storage;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// 記録ファイル名（データディレクトリ配下）
+const FAILURE_LOG_FILE_NAME: &str = "failures.json";
+
+/// 失敗したアップロード
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FailedUpload {
+    /// 記録内で一意なID（`retry <id>` で指定する）
+    pub id: u64,
+    /// アップロードしたファイルの絶対パス
+    pub file_path: String,
+    /// 失敗したときのアップロードオプション（再実行時にそのまま使う）
+    pub options: UploadOptions,
+    /// 直近の失敗理由
+    pub error: String,
+    /// 直近の失敗日時（Unix timestamp）
+    pub failed_at: String,
+    /// これまでに失敗した回数
+    pub attempts: u32,
+}
+
+/// 記録ファイルの形式
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FailureLogFile {
+    /// 次に払い出すID
+    next_id: u64,
+    /// 失敗したアップロード（最初に失敗した順）
+    entries: Vec<FailedUpload>,
+}
+
+/// 失敗したアップロードの記録
+#[derive(Debug)]
+pub struct FailureLog {
+    path: PathBuf,
+    file: FailureLogFile,
+}
+
+impl FailureLog {
+    /// 既定の場所（データディレクトリ配下）から記録を読み込む
+    pub fn load_default() -> Result<Self, InfraError> {
+        let path = UserConfig::data_dir()
+            .map(|dir| dir.join(FAILURE_LOG_FILE_NAME))
+            .map_err(|e| InfraError::storage(FAILURE_LOG_FILE_NAME, e.to_string()))?;
+        Self::load(&path)
+    }
+
+    /// 記録を読み込む（ファイルが存在しない場合は空）
+    pub fn load(path: &Path) -> Result<Self, InfraError> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: storage::read_json(path)?.unwrap_or_default(),
+        })
+    }
+
+    /// 記録を保存する
+    pub fn save(&self) -> Result<(), InfraError> {
+        storage::write_json_atomic(&self.path, &self.file)
+    }
+
+    /// 記録されている失敗（最初に失敗した順）
+    pub fn entries(&self) -> &[FailedUpload] {
+        &self.file.entries
+    }
+
+    /// IDで記録を探す
+    pub fn find(&self, id: u64) -> Option<&FailedUpload> {
+        self.file.entries.iter().find(|entry| entry.id == id)
+    }
+
+    /// 失敗を記録し、記録した項目を返す
+    ///
+    /// 同じファイルの記録がある場合は、IDを変えずにオプション・エラーを更新して回数を増やします。
+    ///
+    /// # Arguments
+    /// * `file_path` - アップロードしたファイルの絶対パス
+    /// * `options` - 失敗したときのアップロードオプション
+    /// * `error` - 失敗理由
+    /// * `failed_at` - 失敗日時（Unix timestamp）
+    pub fn record(
+        &mut self,
+        file_path: &str,
+        options: UploadOptions,
+        error: impl Into<String>,
+        failed_at: String,
+    ) -> FailedUpload {
+        let error = error.into();
+        if let Some(entry) = self
+            .file
+            .entries
+            .iter_mut()
+            .find(|entry| entry.file_path == file_path)
+        {
+            entry.options = options;
+            entry.error = error;
+            entry.failed_at = failed_at;
+            entry.attempts += 1;
+            return entry.clone();
+        }
+
+        self.file.next_id += 1;
+        let entry = FailedUpload {
+            id: self.file.next_id,
+            file_path: file_path.to_string(),
+            options,
+            error,
+            failed_at,
+            attempts: 1,
+        };
+        self.file.entries.push(entry.clone());
+        entry
+    }
+
+    /// 記録を取り除く（再実行に成功した場合）
+    pub fn remove(&mut self, id: u64) {
+        self.file.entries.retain(|entry| entry.id != id);
+    }
+
+    /// ファイルの記録を取り除く（通常のアップロードで成功した場合）
+    ///
+    /// # 戻り値
+    /// 記録を取り除いた場合はtrue
+    pub fn remove_path(&mut self, file_path: &str) -> bool {
+        let before = self.file.entries.len();
+        self.file
+            .entries
+            .retain(|entry| entry.file_path != file_path);
+        self.file.entries.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_merges_same_file_and_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("failures.json");
+
+        let mut log = FailureLog::load(&path).unwrap();
+        let first = log.record(
+            "/videos/a.mp4",
+            UploadOptions::default(),
+            "network error",
+            "100".to_string(),
+        );
+        let probed = UploadOptions {
+            probe: true,
+            ..UploadOptions::default()
+        };
+        let again = log.record("/videos/a.mp4", probed, "timed out", "200".to_string());
+        let other = log.record(
+            "/videos/b.mp4",
+            UploadOptions::default(),
+            "network error",
+            "300".to_string(),
+        );
+        log.save().unwrap();
+
+        assert_eq!(again.id, first.id);
+        assert_eq!(again.attempts, 2);
+        assert!(again.options.probe);
+        assert_eq!(other.id, 2);
+
+        let reloaded = FailureLog::load(&path).unwrap();
+        assert_eq!(reloaded.entries(), &[again, other]);
+    }
+
+    #[test]
+    fn test_remove_by_id_and_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = FailureLog::load(&dir.path().join("failures.json")).unwrap();
+
+        let a = log.record("/a.mp4", UploadOptions::default(), "e", "1".to_string());
+        log.record("/b.mp4", UploadOptions::default(), "e", "2".to_string());
+
+        log.remove(a.id);
+        assert!(log.find(a.id).is_none());
+        assert!(log.remove_path("/b.mp4"));
+        assert!(!log.remove_path("/b.mp4"));
+        assert!(log.entries().is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+/// 操作履歴
+///
+/// 状態を変更するコマンド（`upload`・`delete`・`login` など）の実行を、
+/// 日時・引数（秘密情報は伏せ字）・結果とともにデータディレクトリの `history.jsonl` に
+/// 1行1件で追記します。`history` で確認し、`history rerun <id>` で同じ引数のまま再実行できます。
+/// 件数が `APP_CONFIG.history.max_entries` を超えた場合は古いものから削除します。
+use crate::api::error::InfraError;
+use crate::config::{APP_CONFIG, UserConfig};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// 履歴ファイル名（データディレクトリ配下）
+const HISTORY_FILE_NAME: &str = "history.jsonl";
+
+/// 伏せ字にした引数の値
+pub const REDACTED: &str = "<redacted>";
+
+/// 履歴の1件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct HistoryEntry {
+    /// 履歴内で一意なID（`history rerun <id>` で指定する）
+    pub id: u64,
+    /// 実行日時（Unix timestamp）
+    pub recorded_at: String,
+    /// コマンド名
+    pub command: String,
+    /// コマンド以降の引数（秘密情報は伏せ字、`--machine` などのグローバルフラグは含まない）
+    pub args: Vec<String>,
+    /// 成功したか（一括処理の一部失敗は失敗として扱う）
+    pub success: bool,
+    /// 終了コード
+    pub exit_code: i32,
+    /// 失敗時のエラーメッセージ
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HistoryEntry {
+    /// 伏せ字の引数を含むか（含む場合は同じ引数で再実行できない）
+    pub fn is_redacted(&self) -> bool {
+        self.args.iter().any(|arg| arg == REDACTED)
+    }
+}
+
+/// 追記する履歴（IDは追記時に払い出す）
+#[derive(Debug, Clone)]
+pub struct NewHistoryEntry {
+    /// コマンド名
+    pub command: String,
+    /// コマンド以降の引数（秘密情報は伏せ字にしておくこと）
+    pub args: Vec<String>,
+    /// 終了コード
+    pub exit_code: i32,
+    /// 失敗時のエラーメッセージ
+    pub error: Option<String>,
+}
+
+/// 操作履歴
+#[derive(Debug)]
+pub struct History {
+    path: PathBuf,
+}
+
+impl History {
+    /// 既定の場所（データディレクトリ配下）の履歴
+    pub fn open_default() -> Result<Self, InfraError> {
+        UserConfig::data_dir()
+            .map(|dir| Self::open(&dir.join(HISTORY_FILE_NAME)))
+            .map_err(|e| InfraError::storage(HISTORY_FILE_NAME, e.to_string()))
+    }
+
+    /// 指定したファイルの履歴
+    pub fn open(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    /// すべての履歴を古い順に読み込む（ファイルがない場合は空）
+    ///
+    /// 書き込み中の中断などで壊れた行は読み飛ばします。
+    pub fn entries(&self) -> Result<Vec<HistoryEntry>, InfraError> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path)
+            .map_err(|e| InfraError::storage(self.path.display().to_string(), e.to_string()))?;
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// IDで履歴を探す
+    pub fn find(&self, id: u64) -> Result<Option<HistoryEntry>, InfraError> {
+        Ok(self.entries()?.into_iter().find(|entry| entry.id == id))
+    }
+
+    /// 履歴を追記し、追記した項目を返す
+    ///
+    /// # Arguments
+    /// * `entry` - 追記する内容
+    /// * `recorded_at` - 実行日時（Unix timestamp）
+    pub fn append(
+        &self,
+        entry: NewHistoryEntry,
+        recorded_at: String,
+    ) -> Result<HistoryEntry, InfraError> {
+        let to_storage_error = |e: String| InfraError::storage(self.path.display().to_string(), e);
+
+        let mut entries = self.entries()?;
+        let entry = HistoryEntry {
+            id: entries.last().map_or(1, |last| last.id + 1),
+            recorded_at,
+            command: entry.command,
+            args: entry.args,
+            success: entry.exit_code == 0,
+            exit_code: entry.exit_code,
+            error: entry.error,
+        };
+        let line = serde_json::to_string(&entry).map_err(|e| to_storage_error(e.to_string()))?;
+
+        if entries.len() < APP_CONFIG.history.max_entries {
+            if let Some(parent) = self.path.parent() {
+                fs::create_dir_all(parent).map_err(|e| to_storage_error(e.to_string()))?;
+            }
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .map_err(|e| to_storage_error(e.to_string()))?;
+            writeln!(file, "{}", line).map_err(|e| to_storage_error(e.to_string()))?;
+        } else {
+            // 上限を超える場合は古いものを削除して書き直す（一時ファイル経由で置き換え）
+            entries.push(entry.clone());
+            let excess = entries.len() - APP_CONFIG.history.max_entries;
+            let mut content = String::new();
+            for kept in &entries[excess..] {
+                let kept =
+                    serde_json::to_string(kept).map_err(|e| to_storage_error(e.to_string()))?;
+                content.push_str(&kept);
+                content.push('\n');
+            }
+            let tmp_path = self.path.with_extension("jsonl.tmp");
+            fs::write(&tmp_path, content).map_err(|e| to_storage_error(e.to_string()))?;
+            fs::rename(&tmp_path, &self.path).map_err(|e| to_storage_error(e.to_string()))?;
+        }
+
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_entry(command: &str, exit_code: i32) -> NewHistoryEntry {
+        NewHistoryEntry {
+            command: command.to_string(),
+            args: vec!["a.mp4".to_string()],
+            exit_code,
+            error: (exit_code != 0).then(|| "failed".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_append_assigns_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = History::open(&dir.path().join("history.jsonl"));
+        assert!(history.entries().unwrap().is_empty());
+
+        let first = history
+            .append(new_entry("upload", 0), "100".to_string())
+            .unwrap();
+        let second = history
+            .append(new_entry("delete", 1), "200".to_string())
+            .unwrap();
+
+        assert_eq!(first.id, 1);
+        assert!(first.success);
+        assert_eq!(second.id, 2);
+        assert!(!second.success);
+
+        assert_eq!(history.find(1).unwrap(), Some(first.clone()));
+        assert_eq!(history.entries().unwrap(), [first, second]);
+    }
+
+    #[test]
+    fn test_skips_broken_lines_and_detects_redaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        fs::write(&path, "{broken\n").unwrap();
+
+        let history = History::open(&path);
+        let entry = history
+            .append(
+                NewHistoryEntry {
+                    command: "login".to_string(),
+                    args: vec!["--token-secret".to_string(), REDACTED.to_string()],
+                    exit_code: 0,
+                    error: None,
+                },
+                "100".to_string(),
+            )
+            .unwrap();
+
+        assert_eq!(entry.id, 1);
+        assert!(entry.is_redacted());
+        assert_eq!(history.entries().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,146 @@
+/// アップロードマニフェスト
+///
+/// `upload --manifest <path>` で、アップロードしたファイルとアセットの対応
+/// （元ファイルのパス・チェックサム、アセットID、再生URL、日時）をJSONで書き出します。
+/// コンテンツと一緒にリポジトリへコミットしたり、CMSへの取り込みに使うことを想定しています。
+use crate::api::error::InfraError;
+use crate::commands::result::{BatchUploadFailure, UploadResult};
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// マニフェストの形式バージョン（項目の削除・意味の変更時に上げる）
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// アップロードマニフェスト
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadManifest {
+    /// マニフェストの形式バージョン
+    pub version: u32,
+    /// 作成日時（RFC 3339、UTC）
+    pub generated_at: String,
+    /// アップロードしたファイル（指定順）
+    pub uploads: Vec<ManifestEntry>,
+    /// アップロードできなかったファイル（複数ファイル時のみ）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed: Vec<ManifestFailure>,
+}
+
+/// アップロードしたファイルの記録
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// 元ファイルのパス
+    pub source_path: String,
+    /// 元ファイルのサイズ（bytes）
+    pub file_size: u64,
+    /// 元ファイルのSHA-256
+    pub sha256: String,
+    /// アセットID
+    pub asset_id: String,
+    /// 再生ID
+    pub playback_id: Option<String>,
+    /// HLS再生URL
+    pub hls_url: Option<String>,
+    /// MP4再生URL
+    pub mp4_url: Option<String>,
+    /// アップロード完了日時（RFC 3339、UTC）
+    pub uploaded_at: String,
+}
+
+/// アップロードできなかったファイルの記録
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestFailure {
+    /// 元ファイルのパス
+    pub source_path: String,
+    /// エラーメッセージ
+    pub error: String,
+}
+
+impl UploadManifest {
+    /// アップロード結果からマニフェストを作成する
+    pub fn new(uploaded: &[UploadResult], failed: &[BatchUploadFailure]) -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            generated_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            uploads: uploaded.iter().map(ManifestEntry::from).collect(),
+            failed: failed
+                .iter()
+                .map(|f| ManifestFailure {
+                    source_path: f.file_path.clone(),
+                    error: f.error.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// マニフェストを書き出す（既存のファイルは置き換える）
+    pub fn write(&self, path: &Path) -> Result<(), InfraError> {
+        storage::write_json_atomic(path, self)
+    }
+}
+
+impl From<&UploadResult> for ManifestEntry {
+    fn from(result: &UploadResult) -> Self {
+        Self {
+            source_path: result.file_path.clone(),
+            file_size: result.file_size,
+            sha256: result.sha256.clone(),
+            asset_id: result.asset_id.clone(),
+            playback_id: result.playback_id.clone(),
+            hls_url: result.hls_url.clone(),
+            mp4_url: result.mp4_url.clone(),
+            uploaded_at: result.uploaded_at.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::result::Mp4Status;
+
+    #[test]
+    fn test_write_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("uploads.json");
+
+        let uploaded = UploadResult {
+            asset_id: "asset_1".to_string(),
+            playback_id: Some("play_1".to_string()),
+            playback_policy: None,
+            hls_url: Some("https://stream.mux.com/play_1.m3u8".to_string()),
+            mp4_url: None,
+            mp4_status: Mp4Status::Generating,
+            thumbnail_url: None,
+            file_path: "/videos/a.mp4".to_string(),
+            file_size: 1024,
+            file_format: "mp4".to_string(),
+            deleted_old_videos: 0,
+            deleted_assets: Vec::new(),
+            media: None,
+            warnings: Vec::new(),
+            sha256: "abc".to_string(),
+            uploaded_at: "2024-01-01T00:00:00Z".to_string(),
+            timings: Default::default(),
+            final_media: None,
+            copied: None,
+        };
+        let failed = BatchUploadFailure {
+            file_path: "/videos/b.mp4".to_string(),
+            error: "file is empty".to_string(),
+            attempts: 1,
+        };
+
+        UploadManifest::new(&[uploaded], &[failed])
+            .write(&path)
+            .unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["uploads"][0]["source_path"], "/videos/a.mp4");
+        assert_eq!(json["uploads"][0]["sha256"], "abc");
+        assert_eq!(json["uploads"][0]["asset_id"], "asset_1");
+        assert_eq!(json["failed"][0]["source_path"], "/videos/b.mp4");
+    }
+}
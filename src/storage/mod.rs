@@ -0,0 +1,64 @@
+/// ローカルストレージモジュール（インフラ層）
+///
+/// APIとは別に、データディレクトリ（config.tomlと同じ場所）に
+/// CLIが保持するローカルデータを読み書きします。
+///
+/// # モジュール
+/// - `asset_index`: 既知のアセットのインデックス（`list --offline`用）
+/// - `upload_queue`: 後で実行するアップロードのキュー（`queue add` / `queue run`）
+/// - `recent_uploads`: 最近アップロードしたファイルのファイル名・サイズ（重複アップロードの警告用）
+/// - `throughput`: 直近のアップロードの転送速度（`upload --dry-run`の見積もり用）
+/// - `manifest`: アップロード結果のマニフェスト（`upload --manifest`）
+/// - `export`: 全アセットのエクスポートと、中断時のチェックポイント（`export`）
+/// - `backup`: バックアップでダウンロードしたファイルの記録（`backup`）
+/// - `failure_log`: 失敗したアップロードの記録（`retry`）
+/// - `history`: 状態を変更したコマンドの操作履歴（`history`）
+pub mod asset_index;
+pub mod backup;
+pub mod export;
+pub mod failure_log;
+pub mod history;
+pub mod manifest;
+pub mod recent_uploads;
+pub mod throughput;
+pub mod upload_queue;
+
+use crate::api::error::InfraError;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::Path;
+
+/// JSONファイルを読み込む（ファイルが存在しない場合はNone）
+pub(crate) fn read_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, InfraError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| InfraError::storage(path.display().to_string(), e.to_string()))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| InfraError::storage(path.display().to_string(), e.to_string()))
+}
+
+/// JSONファイルを書き込む
+///
+/// 一時ファイルに書き込んでから置き換えるため、
+/// 書き込み中に中断されても既存のファイルは壊れません。
+pub(crate) fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> Result<(), InfraError> {
+    let to_storage_error =
+        |e: std::io::Error| InfraError::storage(path.display().to_string(), e.to_string());
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(to_storage_error)?;
+    }
+
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|e| InfraError::storage(path.display().to_string(), e.to_string()))?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, content).map_err(to_storage_error)?;
+    fs::rename(&tmp_path, path).map_err(to_storage_error)?;
+
+    Ok(())
+}
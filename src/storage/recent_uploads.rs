@@ -0,0 +1,148 @@
+/// 最近のアップロードの記録
+///
+/// アップロードしたファイルのファイル名・サイズをデータディレクトリの `recent_uploads.json` に記録し、
+/// 同じファイル名・サイズのファイルを直近にアップロードしていた場合の警告（アップロードスクリプトの誤った再実行の検出）に使います。
+/// アセットのローカルインデックスと異なり、アセットを削除した後も記録は残ります。
+/// 件数が `APP_CONFIG.upload.recent_uploads_max_entries` を超えた場合は古いものから削除します。
+use crate::api::error::InfraError;
+use crate::config::{APP_CONFIG, UserConfig};
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 記録ファイル名（データディレクトリ配下）
+const RECENT_UPLOADS_FILE_NAME: &str = "recent_uploads.json";
+
+/// アップロードしたファイルの記録
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentUpload {
+    /// ファイル名（パスを除く）
+    pub file_name: String,
+    /// ファイルサイズ（bytes）
+    pub file_size: u64,
+    /// 作成したアセットのID
+    pub asset_id: String,
+    /// アップロード日時（Unix timestamp）
+    pub uploaded_at: String,
+}
+
+/// 最近のアップロードの記録（古い順）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecentUploads {
+    pub uploads: Vec<RecentUpload>,
+}
+
+impl RecentUploads {
+    /// 既定の場所から読み込む（未記録・読み込み失敗時は空）
+    pub fn load_default() -> Self {
+        UserConfig::data_dir()
+            .ok()
+            .and_then(|dir| Self::load(&dir.join(RECENT_UPLOADS_FILE_NAME)).ok())
+            .unwrap_or_default()
+    }
+
+    /// 記録を読み込む（ファイルが存在しない場合は空）
+    pub fn load(path: &Path) -> Result<Self, InfraError> {
+        Ok(storage::read_json(path)?.unwrap_or_default())
+    }
+
+    /// 記録を保存する
+    pub fn save(&self, path: &Path) -> Result<(), InfraError> {
+        storage::write_json_atomic(path, self)
+    }
+
+    /// 既定の場所の記録に追加する
+    ///
+    /// 重複の警告用の補助情報のため、保存に失敗してもアップロードは失敗させません。
+    pub fn record_default(upload: RecentUpload) {
+        let Ok(dir) = UserConfig::data_dir() else {
+            return;
+        };
+        let path = dir.join(RECENT_UPLOADS_FILE_NAME);
+        let mut recent = Self::load(&path).unwrap_or_default();
+        recent.push(upload);
+        let _ = recent.save(&path);
+    }
+
+    /// 記録を追加する（上限を超えた分は古いものから削除）
+    pub fn push(&mut self, upload: RecentUpload) {
+        self.uploads.push(upload);
+        let excess = self
+            .uploads
+            .len()
+            .saturating_sub(APP_CONFIG.upload.recent_uploads_max_entries);
+        self.uploads.drain(..excess);
+    }
+
+    /// 指定した日時以降に同じファイル名・サイズでアップロードした記録のうち、最新のものを探す
+    ///
+    /// # Arguments
+    /// * `file_name` - ファイル名（パスを除く）
+    /// * `file_size` - ファイルサイズ（bytes）
+    /// * `since` - この日時（Unix timestamp）より前の記録は対象外
+    pub fn find(&self, file_name: &str, file_size: u64, since: i64) -> Option<&RecentUpload> {
+        self.uploads.iter().rev().find(|upload| {
+            upload.file_name == file_name
+                && upload.file_size == file_size
+                && upload
+                    .uploaded_at
+                    .parse::<i64>()
+                    .is_ok_and(|uploaded_at| uploaded_at >= since)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upload(file_name: &str, file_size: u64, asset_id: &str, uploaded_at: i64) -> RecentUpload {
+        RecentUpload {
+            file_name: file_name.to_string(),
+            file_size,
+            asset_id: asset_id.to_string(),
+            uploaded_at: uploaded_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_find_matches_name_size_and_window() {
+        let mut recent = RecentUploads::default();
+        recent.push(upload("clip.mp4", 100, "asset_old", 1_000));
+        recent.push(upload("clip.mp4", 100, "asset_new", 2_000));
+        recent.push(upload("other.mp4", 100, "asset_other", 3_000));
+
+        let found = recent.find("clip.mp4", 100, 1_500).unwrap();
+        assert_eq!(found.asset_id, "asset_new");
+        assert!(recent.find("clip.mp4", 100, 2_500).is_none());
+        assert!(recent.find("clip.mp4", 101, 0).is_none());
+        assert!(recent.find("Clip.mp4", 100, 0).is_none());
+    }
+
+    #[test]
+    fn test_push_drops_oldest_over_limit() {
+        let max = APP_CONFIG.upload.recent_uploads_max_entries;
+        let mut recent = RecentUploads::default();
+        for i in 0..=max {
+            recent.push(upload("clip.mp4", i as u64, "asset", i as i64));
+        }
+        assert_eq!(recent.uploads.len(), max);
+        assert_eq!(recent.uploads[0].file_size, 1);
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(RECENT_UPLOADS_FILE_NAME);
+        assert_eq!(
+            RecentUploads::load(&path).unwrap(),
+            RecentUploads::default()
+        );
+
+        let mut recent = RecentUploads::default();
+        recent.push(upload("clip.mp4", 100, "asset_1", 1_700_000_000));
+        recent.save(&path).unwrap();
+
+        assert_eq!(RecentUploads::load(&path).unwrap(), recent);
+    }
+}
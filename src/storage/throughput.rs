@@ -0,0 +1,68 @@
+/// 転送速度の計測履歴
+///
+/// 直近のアップロードで計測した平均転送速度をデータディレクトリの
+/// `throughput.json` に保持し、`upload --dry-run` の所要時間の見積もりに使います。
+use crate::api::error::InfraError;
+use crate::config::UserConfig;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 計測履歴ファイル名（データディレクトリ配下）
+const THROUGHPUT_FILE_NAME: &str = "throughput.json";
+
+/// 直近のアップロードで計測した転送速度
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThroughputRecord {
+    /// 平均転送速度（バイト/秒）
+    pub bytes_per_sec: f64,
+    /// 計測日時（Unix timestamp）
+    pub measured_at: String,
+}
+
+impl ThroughputRecord {
+    /// 既定の場所から直近の計測値を読み込む（未計測・読み込み失敗時はNone）
+    pub fn load_default() -> Option<Self> {
+        let path = UserConfig::data_dir().ok()?.join(THROUGHPUT_FILE_NAME);
+        Self::load(&path).ok().flatten()
+    }
+
+    /// 計測値を読み込む（ファイルが存在しない場合はNone）
+    pub fn load(path: &Path) -> Result<Option<Self>, InfraError> {
+        storage::read_json(path)
+    }
+
+    /// 既定の場所に計測値を保存する
+    ///
+    /// 見積もり用の補助情報のため、保存に失敗してもアップロードは失敗させません。
+    pub fn save_default(&self) {
+        if let Ok(dir) = UserConfig::data_dir() {
+            let _ = self.save(&dir.join(THROUGHPUT_FILE_NAME));
+        }
+    }
+
+    /// 計測値を保存する
+    pub fn save(&self, path: &Path) -> Result<(), InfraError> {
+        storage::write_json_atomic(path, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(THROUGHPUT_FILE_NAME);
+        assert_eq!(ThroughputRecord::load(&path).unwrap(), None);
+
+        let record = ThroughputRecord {
+            bytes_per_sec: 1_500_000.0,
+            measured_at: "1700000000".to_string(),
+        };
+        record.save(&path).unwrap();
+
+        assert_eq!(ThroughputRecord::load(&path).unwrap(), Some(record));
+    }
+}
@@ -0,0 +1,156 @@
+/// アップロードキュー
+///
+/// `queue add` で登録したアップロードをデータディレクトリの `queue.json` に保持し、
+/// `queue run` で後からまとめて実行できるようにします。
+/// ネットワーク断などの一時的な失敗で実行できなかった項目はキューに残り、
+/// 次回の `queue run` で自動的に再試行されます。
+use crate::api::error::InfraError;
+use crate::config::UserConfig;
+use crate::storage;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// キューファイル名（データディレクトリ配下）
+const QUEUE_FILE_NAME: &str = "queue.json";
+
+/// キューに登録されたアップロード
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct QueuedUpload {
+    /// キュー内で一意なID
+    pub id: u64,
+    /// アップロードするファイルの絶対パス
+    pub file_path: String,
+    /// 登録日時（Unix timestamp）
+    pub added_at: String,
+    /// これまでの実行試行回数
+    #[serde(default)]
+    pub attempts: u32,
+    /// 直近の失敗理由
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// キューファイルの形式
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct QueueFile {
+    /// 次に払い出すID
+    next_id: u64,
+    /// 未実行のアップロード（登録順）
+    pending: Vec<QueuedUpload>,
+}
+
+/// アップロードキュー
+#[derive(Debug)]
+pub struct UploadQueue {
+    path: PathBuf,
+    file: QueueFile,
+}
+
+impl UploadQueue {
+    /// 既定の場所（データディレクトリ配下）からキューを読み込む
+    pub fn load_default() -> Result<Self, InfraError> {
+        let path = UserConfig::data_dir()
+            .map(|dir| dir.join(QUEUE_FILE_NAME))
+            .map_err(|e| InfraError::storage(QUEUE_FILE_NAME, e.to_string()))?;
+        Self::load(&path)
+    }
+
+    /// キューを読み込む（ファイルが存在しない場合は空）
+    pub fn load(path: &Path) -> Result<Self, InfraError> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: storage::read_json(path)?.unwrap_or_default(),
+        })
+    }
+
+    /// キューを保存する
+    pub fn save(&self) -> Result<(), InfraError> {
+        storage::write_json_atomic(&self.path, &self.file)
+    }
+
+    /// 未実行のアップロード一覧（登録順）
+    pub fn pending(&self) -> &[QueuedUpload] {
+        &self.file.pending
+    }
+
+    /// アップロードを登録し、登録した項目を返す
+    ///
+    /// # Arguments
+    /// * `file_path` - アップロードするファイルの絶対パス
+    /// * `added_at` - 登録日時（Unix timestamp）
+    pub fn add(&mut self, file_path: String, added_at: String) -> QueuedUpload {
+        self.file.next_id += 1;
+        let item = QueuedUpload {
+            id: self.file.next_id,
+            file_path,
+            added_at,
+            attempts: 0,
+            last_error: None,
+        };
+        self.file.pending.push(item.clone());
+        item
+    }
+
+    /// 実行に成功した（または再試行しない）項目をキューから取り除く
+    pub fn remove(&mut self, id: u64) {
+        self.file.pending.retain(|item| item.id != id);
+    }
+
+    /// 一時的な失敗を記録する（項目はキューに残り、次回再試行される）
+    pub fn record_failure(&mut self, id: u64, error: impl Into<String>) {
+        if let Some(item) = self.file.pending.iter_mut().find(|item| item.id == id) {
+            item.attempts += 1;
+            item.last_error = Some(error.into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_assigns_increasing_ids_and_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("queue.json");
+
+        let mut queue = UploadQueue::load(&path).unwrap();
+        let first = queue.add("/videos/a.mp4".to_string(), "100".to_string());
+        let second = queue.add("/videos/b.mp4".to_string(), "200".to_string());
+        queue.save().unwrap();
+
+        assert_eq!(first.id, 1);
+        assert_eq!(second.id, 2);
+
+        let reloaded = UploadQueue::load(&path).unwrap();
+        assert_eq!(reloaded.pending(), &[first, second]);
+    }
+
+    #[test]
+    fn test_ids_are_not_reused_after_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut queue = UploadQueue::load(&dir.path().join("queue.json")).unwrap();
+
+        let first = queue.add("/videos/a.mp4".to_string(), "100".to_string());
+        queue.remove(first.id);
+        let second = queue.add("/videos/b.mp4".to_string(), "200".to_string());
+
+        assert_eq!(second.id, 2);
+        assert_eq!(queue.pending().len(), 1);
+    }
+
+    #[test]
+    fn test_record_failure_keeps_item() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut queue = UploadQueue::load(&dir.path().join("queue.json")).unwrap();
+
+        let item = queue.add("/videos/a.mp4".to_string(), "100".to_string());
+        queue.record_failure(item.id, "network error");
+        queue.record_failure(item.id, "timed out");
+
+        let pending = &queue.pending()[0];
+        assert_eq!(pending.attempts, 2);
+        assert_eq!(pending.last_error.as_deref(), Some("timed out"));
+    }
+}
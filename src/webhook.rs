@@ -0,0 +1,231 @@
+/// Webhook通知モジュール
+///
+/// config.tomlの`[notifications]`の`webhook_url`へ、アップロードの完了・失敗を
+/// 短いMarkdownのメッセージ（アセットID・タイトル・再生URL・再生時間）としてPOSTします。
+/// Slack（Incoming Webhook）は`text`、Discordは`content`にメッセージを入れて送信するため、
+/// ラッパーを書かずにチームへ共有できます。
+use crate::api::error::InfraError;
+use crate::commands::result::{BatchUploadResult, UploadResult};
+use crate::config::APP_CONFIG;
+use crate::domain::formatter::format_duration;
+use reqwest::Url;
+use serde_json::{Value, json};
+use std::path::Path;
+use std::time::Duration;
+
+/// 送信先のサービス（URLのホストから判定）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookService {
+    /// Slack互換（`{"text": ...}`）。Discord以外はすべてこの形式
+    Slack,
+    /// Discord（`{"content": ...}`）
+    Discord,
+}
+
+impl WebhookService {
+    /// Webhook URLから送信先のサービスを判定する
+    pub fn from_url(url: &str) -> Self {
+        let host = Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_ascii_lowercase));
+        match host.as_deref() {
+            Some(host)
+                if ["discord.com", "discordapp.com"]
+                    .iter()
+                    .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain))) =>
+            {
+                Self::Discord
+            }
+            _ => Self::Slack,
+        }
+    }
+
+    /// 送信するJSON
+    pub fn payload(&self, message: &str) -> Value {
+        match self {
+            Self::Slack => json!({ "text": message }),
+            Self::Discord => json!({ "content": message }),
+        }
+    }
+}
+
+/// 単一ファイルのアップロード完了メッセージ
+pub fn upload_succeeded_message(result: &UploadResult) -> String {
+    let mut lines = vec![
+        format!("✅ Upload completed: {}", title(&result.file_path)),
+        format!("Asset ID: `{}`", result.asset_id),
+    ];
+    if let Some(duration) = result.media.as_ref().and_then(|m| m.duration_secs) {
+        lines.push(format!("Duration: {}", format_duration(duration)));
+    }
+    if let Some(hls_url) = &result.hls_url {
+        lines.push(format!("Playback: {}", hls_url));
+    }
+    lines.join("\n")
+}
+
+/// 複数ファイルのアップロード結果メッセージ（ファイルごとに1行）
+pub fn batch_upload_message(result: &BatchUploadResult) -> String {
+    let header = if result.failed.is_empty() {
+        format!("✅ Uploaded {} file(s)", result.uploaded.len())
+    } else {
+        format!(
+            "⚠️ Uploaded {} file(s), {} failed",
+            result.uploaded.len(),
+            result.failed.len()
+        )
+    };
+
+    let mut lines = vec![header];
+    lines.extend(result.uploaded.iter().map(|upload| match &upload.hls_url {
+        Some(hls_url) => format!(
+            "• {} (`{}`): {}",
+            title(&upload.file_path),
+            upload.asset_id,
+            hls_url
+        ),
+        None => format!("• {} (`{}`)", title(&upload.file_path), upload.asset_id),
+    }));
+    lines.extend(
+        result
+            .failed
+            .iter()
+            .map(|failure| format!("• {} failed: {}", title(&failure.file_path), failure.error)),
+    );
+    lines.join("\n")
+}
+
+/// アップロード失敗メッセージ
+pub fn upload_failed_message(file_paths: &[String], error: &str) -> String {
+    let titles: Vec<&str> = file_paths.iter().map(|path| title(path)).collect();
+    format!("❌ Upload failed: {}\nError: {}", titles.join(", "), error)
+}
+
+/// メッセージをWebhookへ送信する
+///
+/// # Errors
+/// 接続できない場合や、2xx以外のステータスが返った場合に InfraError を返します。
+/// Webhook URLはトークンを含むため、エラーにはホスト名のみを含めます。
+pub async fn send(url: &str, message: &str) -> Result<(), InfraError> {
+    let endpoint = format!(
+        "webhook {}",
+        Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default()
+    );
+    let payload = WebhookService::from_url(url).payload(message);
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(APP_CONFIG.webhook.timeout_secs))
+        .build()
+        .map_err(|e| InfraError::network(format!("Failed to create HTTP client: {}", e)))?;
+
+    let response = client.post(url).json(&payload).send().await.map_err(|e| {
+        if e.is_timeout() {
+            InfraError::timeout(format!("POST {}", endpoint))
+        } else {
+            // URLを含めないよう、reqwestのエラー本体のみ使う
+            InfraError::network(format!("Failed to send {}: {}", endpoint, e.without_url()))
+        }
+    })?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(InfraError::api(
+            endpoint,
+            format!("Webhook rejected the message: {}", body.trim()),
+            Some(status.as_u16()),
+        ));
+    }
+    Ok(())
+}
+
+/// メッセージに表示するタイトル（ファイル名）
+fn title(path: &str) -> &str {
+    Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::result::{BatchUploadFailure, Mp4Status};
+
+    fn upload_result() -> UploadResult {
+        UploadResult {
+            asset_id: "asset123".to_string(),
+            playback_id: Some("play123".to_string()),
+            playback_policy: None,
+            hls_url: Some("https://stream.mux.com/play123.m3u8".to_string()),
+            mp4_url: None,
+            mp4_status: Mp4Status::Generating,
+            thumbnail_url: None,
+            file_path: "clips/intro.mp4".to_string(),
+            file_size: 1,
+            file_format: "mp4".to_string(),
+            deleted_old_videos: 0,
+            deleted_assets: Vec::new(),
+            media: None,
+            warnings: Vec::new(),
+            sha256: "00".to_string(),
+            uploaded_at: "2024-01-01T00:00:00Z".to_string(),
+            timings: Default::default(),
+            final_media: None,
+            copied: None,
+        }
+    }
+
+    #[test]
+    fn test_service_from_url() {
+        assert_eq!(
+            WebhookService::from_url("https://discord.com/api/webhooks/1/abc"),
+            WebhookService::Discord
+        );
+        assert_eq!(
+            WebhookService::from_url("https://ptb.discordapp.com/api/webhooks/1/abc"),
+            WebhookService::Discord
+        );
+        assert_eq!(
+            WebhookService::from_url("https://hooks.slack.com/services/T/B/X"),
+            WebhookService::Slack
+        );
+        assert_eq!(
+            WebhookService::from_url("https://notdiscord.com/hook"),
+            WebhookService::Slack
+        );
+        assert_eq!(
+            WebhookService::Discord.payload("hi"),
+            json!({ "content": "hi" })
+        );
+        assert_eq!(WebhookService::Slack.payload("hi"), json!({ "text": "hi" }));
+    }
+
+    #[test]
+    fn test_upload_messages() {
+        let message = upload_succeeded_message(&upload_result());
+        assert_eq!(
+            message,
+            "✅ Upload completed: intro.mp4\nAsset ID: `asset123`\nPlayback: https://stream.mux.com/play123.m3u8"
+        );
+
+        let batch = BatchUploadResult {
+            uploaded: vec![upload_result()],
+            failed: vec![BatchUploadFailure {
+                file_path: "clips/outro.mp4".to_string(),
+                error: "network error".to_string(),
+                attempts: 3,
+            }],
+            concurrency: 2,
+        };
+        let message = batch_upload_message(&batch);
+        assert!(message.starts_with("⚠️ Uploaded 1 file(s), 1 failed"));
+        assert!(message.contains("• outro.mp4 failed: network error"));
+
+        let message = upload_failed_message(&["clips/a.mp4".to_string()], "file not found");
+        assert_eq!(message, "❌ Upload failed: a.mp4\nError: file not found");
+    }
+}
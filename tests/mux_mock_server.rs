@@ -0,0 +1,541 @@
+//! モックサーバーに対する結合テスト
+//!
+//! wiremockで立てたMux APIのモックに `ApiClient` の接続先を向け、
+//! アップロード（308によるチャンク継続、容量制限時の自動削除・確認後の削除）・一覧のページネーション・アセット数の取得・削除を
+//! ライブラリのファサード（`vidyeet::Client`）から通しで実行します。
+//! レスポンスは `tests/fixtures/mux/` に記録したMux APIの形式のJSONを使います。
+//!
+//! アセットインデックスなどのローカルデータはデータディレクトリに書き込まれるため、
+//! `UserConfig::use_data_dir` でデータディレクトリを一時ディレクトリに向けて実行します。
+//! 変換（`--transcode`）のテストでは、入力をそのまま出力するffmpeg・ffprobeの代わりのスクリプトを
+//! `UploadOptions::media_tools` で渡して実行します。テストは並列に実行されるため、環境変数は変更しません。
+#![cfg(unix)]
+
+use std::io::Write;
+use std::os::unix::fs::PermissionsExt;
+use std::sync::{Arc, Mutex, OnceLock};
+use vidyeet::api::client::ApiClient;
+use vidyeet::api::error::InfraError;
+use vidyeet::api::retry::RetryPolicy;
+use vidyeet::commands::upload::{ConfirmDelete, UploadOptions};
+use vidyeet::config::APP_CONFIG;
+use vidyeet::config::user::{CapacityPolicy, UserConfig};
+use vidyeet::domain::error::DomainError;
+use vidyeet::media::MediaTools;
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// 記録したMux APIのレスポンス
+macro_rules! fixture {
+    ($name:literal) => {
+        include_str!(concat!("fixtures/mux/", $name))
+    };
+}
+
+/// データディレクトリを一時ディレクトリに向ける（テストバイナリ全体で1回だけ）
+///
+/// 環境変数は変えず、`UserConfig::use_data_dir` でライブラリに直接渡します。
+fn isolate_data_dir() {
+    static DATA_DIR: OnceLock<tempfile::TempDir> = OnceLock::new();
+    DATA_DIR.get_or_init(|| {
+        let dir = tempfile::tempdir().unwrap();
+        UserConfig::use_data_dir(dir.path()).expect("the data dir is set only here");
+        dir
+    });
+}
+
+/// ffmpeg（入力をそのまま出力し、入力名に `broken` を含む場合は出力後に失敗）と
+/// ffprobe（再生時間2秒の動画として解析）の代わりのスクリプトを作り、そのパスを返す
+///
+/// `PATH` は変えず、アップロードのオプション（`UploadOptions::media_tools`）で渡します。
+fn fake_media_tools() -> MediaTools {
+    static TOOLS_DIR: OnceLock<tempfile::TempDir> = OnceLock::new();
+    let dir = TOOLS_DIR.get_or_init(|| {
+        let dir = tempfile::tempdir().unwrap();
+        let ffmpeg = r#"#!/bin/sh
+while [ $# -gt 1 ]; do
+  if [ "$1" = "-i" ]; then input="$2"; fi
+  shift
+done
+echo "out_time_us=1000000" >&2
+cat "$input" || exit 1
+case "$input" in
+  *broken*) echo "Invalid data found when processing input" >&2; exit 1 ;;
+esac
+echo "progress=end" >&2
+"#;
+        let ffprobe = r#"#!/bin/sh
+echo '{"streams":[{"codec_type":"video","codec_name":"h264","width":640,"height":360}],"format":{"format_name":"matroska,webm","duration":"2.000000"}}'
+"#;
+        for (name, script) in [("ffmpeg", ffmpeg), ("ffprobe", ffprobe)] {
+            let path = dir.path().join(name);
+            std::fs::write(&path, script).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        dir
+    });
+    MediaTools {
+        ffprobe: dir.path().join("ffprobe"),
+        ffmpeg: dir.path().join("ffmpeg"),
+    }
+}
+
+/// モックサーバーに接続するクライアントを作成
+///
+/// 5xxの再送で待たされないよう、リトライは無効にします。
+fn client_for(server: &MockServer) -> vidyeet::Client {
+    isolate_data_dir();
+    let api = ApiClient::new(server.uri())
+        .unwrap()
+        .with_retry_policy(RetryPolicy::no_retry());
+    vidyeet::Client::with_api_client(api, "token_id", "token_secret")
+}
+
+/// JSONのレスポンス
+fn json(status: u16, body: &str) -> ResponseTemplate {
+    ResponseTemplate::new(status).set_body_raw(body.to_string(), "application/json")
+}
+
+/// ftypで始まるMP4として検証を通る、指定サイズのファイルを作成
+fn mp4_file(size: usize) -> tempfile::NamedTempFile {
+    let mut file = tempfile::Builder::new().suffix(".mp4").tempfile().unwrap();
+    let header = b"\0\0\0\x20ftypisom\0\0\x02\0isomiso2avc1mp41";
+    file.write_all(header).unwrap();
+    file.write_all(&vec![0u8; size - header.len()]).unwrap();
+    file.flush().unwrap();
+    file
+}
+
+/// Direct Uploadの作成からアセットの取得までのモックを登録
+async fn mount_upload_flow(server: &MockServer) {
+    let upload_url = format!("{}/upload/mock", server.uri());
+    Mock::given(method("POST"))
+        .and(path("/video/v1/uploads"))
+        .respond_with(json(
+            201,
+            &fixture!("upload_waiting.json").replace("{{UPLOAD_URL}}", &upload_url),
+        ))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/video/v1/uploads/upload_mock_1"))
+        .respond_with(json(200, fixture!("upload_asset_created.json")))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/video/v1/assets/asset_new"))
+        .respond_with(json(200, fixture!("asset_new.json")))
+        .mount(server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/video/v1/assets/asset_new"))
+        .respond_with(json(200, fixture!("asset_new.json")))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_upload_sends_chunks_until_final_response() {
+    let server = MockServer::start().await;
+    mount_upload_flow(&server).await;
+
+    // 2チャンクに分かれるサイズ（1チャンク目は308で継続、2チャンク目で完了）
+    let chunk_size = APP_CONFIG.upload.chunk_size;
+    let total = chunk_size + 1024;
+    Mock::given(method("PUT"))
+        .and(path("/upload/mock"))
+        .and(header(
+            "Content-Range",
+            format!("bytes 0-{}/{}", chunk_size - 1, total).as_str(),
+        ))
+        .respond_with(
+            ResponseTemplate::new(308)
+                .insert_header("Range", format!("bytes=0-{}", chunk_size - 1)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/mock"))
+        .and(header(
+            "Content-Range",
+            format!("bytes {}-{}/{}", chunk_size, total - 1, total).as_str(),
+        ))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let file = mp4_file(total);
+    let options = UploadOptions {
+        force: true,
+        ..Default::default()
+    };
+    let result = client_for(&server)
+        .upload_with_options(file.path().to_str().unwrap(), options, None)
+        .await
+        .unwrap();
+
+    assert_eq!(result.asset_id, "asset_new");
+    assert_eq!(result.playback_id.as_deref(), Some("play_new"));
+    // 作成直後のアセットは再生時間が分からないため、切り出す位置を指定しない
+    assert_eq!(
+        result.thumbnail_url.as_deref(),
+        Some("https://image.mux.com/play_new/thumbnail.jpg")
+    );
+    assert_eq!(result.file_size, total as u64);
+    assert!(result.deleted_assets.is_empty());
+}
+
+/// 指定した名前・サイズの変換元ファイル（内容は問わない）を作成
+fn source_file(name: &str, size: usize) -> (tempfile::TempDir, String) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(name);
+    std::fs::write(&path, vec![7u8; size]).unwrap();
+    let path = path.to_str().unwrap().to_string();
+    (dir, path)
+}
+
+#[tokio::test]
+async fn test_transcode_streams_output_with_open_ended_ranges() {
+    let server = MockServer::start().await;
+    mount_upload_flow(&server).await;
+
+    // 総サイズが確定するまでは `*`、最後のチャンクで総サイズを送る
+    let chunk_size = APP_CONFIG.upload.chunk_size;
+    let total = chunk_size + 2048;
+    Mock::given(method("PUT"))
+        .and(path("/upload/mock"))
+        .and(header(
+            "Content-Range",
+            format!("bytes 0-{}/*", chunk_size - 1).as_str(),
+        ))
+        .respond_with(
+            ResponseTemplate::new(308)
+                .insert_header("Range", format!("bytes=0-{}", chunk_size - 1)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/mock"))
+        .and(header(
+            "Content-Range",
+            format!("bytes {}-{}/{}", chunk_size, total - 1, total).as_str(),
+        ))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let (_dir, source) = source_file("screen.mkv", total);
+    let options = UploadOptions {
+        transcode: Some("-c:v libx264 -crf 20".to_string()),
+        media_tools: fake_media_tools(),
+        ..Default::default()
+    };
+    let result = client_for(&server)
+        .upload_with_options(&source, options, None)
+        .await
+        .unwrap();
+
+    assert_eq!(result.asset_id, "asset_new");
+    assert_eq!(result.file_path, source);
+    assert_eq!(result.file_format, "mp4");
+    assert_eq!(result.file_size, total as u64);
+    assert!(result.media.is_none());
+}
+
+#[tokio::test]
+async fn test_transcode_failure_cancels_upload_before_final_chunk() {
+    let server = MockServer::start().await;
+    let upload_url = format!("{}/upload/mock", server.uri());
+    Mock::given(method("POST"))
+        .and(path("/video/v1/uploads"))
+        .respond_with(json(
+            201,
+            &fixture!("upload_waiting.json").replace("{{UPLOAD_URL}}", &upload_url),
+        ))
+        .mount(&server)
+        .await;
+    // 出力は1チャンクに満たないため、変換の終了を確認するまで何も送信しない
+    Mock::given(method("PUT"))
+        .and(path("/upload/mock"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/video/v1/uploads/upload_mock_1/cancel"))
+        .respond_with(json(200, fixture!("upload_waiting.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let (_dir, source) = source_file("broken.mkv", 4096);
+    let options = UploadOptions {
+        transcode: Some("-c copy".to_string()),
+        media_tools: fake_media_tools(),
+        ..Default::default()
+    };
+    let error = client_for(&server)
+        .upload_with_options(&source, options, None)
+        .await
+        .unwrap_err();
+
+    let infra = error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<InfraError>())
+        .expect("transcode errors should surface as InfraError");
+    assert_eq!(infra.code(), "TRANSCODE_FAILED");
+    assert!(
+        infra
+            .to_string()
+            .contains("Invalid data found when processing input"),
+        "{infra}"
+    );
+}
+
+#[tokio::test]
+async fn test_upload_deletes_oldest_asset_when_capacity_is_reached() {
+    let server = MockServer::start().await;
+
+    // 最初の作成は容量制限で失敗し、最古のアセットを削除した後の再試行で成功する
+    Mock::given(method("POST"))
+        .and(path("/video/v1/uploads"))
+        .respond_with(json(400, fixture!("error_capacity.json")))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    mount_upload_flow(&server).await;
+    mount_asset_pages(&server).await;
+    Mock::given(method("DELETE"))
+        .and(path("/video/v1/assets/asset_oldest"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/mock"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let file = mp4_file(4096);
+    let options = UploadOptions {
+        force: true,
+        on_full: Some(CapacityPolicy::AutoDelete),
+        ..Default::default()
+    };
+    let result = client_for(&server)
+        .upload_with_options(file.path().to_str().unwrap(), options, None)
+        .await
+        .unwrap();
+
+    assert_eq!(result.asset_id, "asset_new");
+    assert_eq!(result.deleted_assets.len(), 1);
+    assert_eq!(result.deleted_assets[0].asset_id, "asset_oldest");
+    assert_eq!(
+        result.deleted_assets[0].title.as_deref(),
+        Some("Oldest clip")
+    );
+}
+
+#[tokio::test]
+async fn test_upload_deletes_confirmed_asset_without_restarting() {
+    let server = MockServer::start().await;
+
+    // 確認した最古のアセットのみを削除し、Direct Uploadの作成だけを再試行する
+    Mock::given(method("POST"))
+        .and(path("/video/v1/uploads"))
+        .respond_with(json(400, fixture!("error_capacity.json")))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    mount_upload_flow(&server).await;
+    mount_asset_pages(&server).await;
+    Mock::given(method("DELETE"))
+        .and(path("/video/v1/assets/asset_oldest"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/upload/mock"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let confirmed = Arc::new(Mutex::new(Vec::new()));
+    let confirm_delete = {
+        let confirmed = Arc::clone(&confirmed);
+        ConfirmDelete::new(move |oldest| {
+            confirmed.lock().unwrap().push(oldest.asset_id.clone());
+            Ok(true)
+        })
+    };
+    let file = mp4_file(4096);
+    let options = UploadOptions {
+        force: true,
+        on_full: Some(CapacityPolicy::Ask),
+        confirm_delete: Some(confirm_delete),
+        ..Default::default()
+    };
+    let result = client_for(&server)
+        .upload_with_options(file.path().to_str().unwrap(), options, None)
+        .await
+        .unwrap();
+
+    assert_eq!(*confirmed.lock().unwrap(), vec!["asset_oldest".to_string()]);
+    assert_eq!(result.asset_id, "asset_new");
+    assert_eq!(result.deleted_assets.len(), 1);
+    assert_eq!(result.deleted_assets[0].asset_id, "asset_oldest");
+}
+
+#[tokio::test]
+async fn test_upload_reports_capacity_limit_without_deleting() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/video/v1/uploads"))
+        .respond_with(json(
+            429,
+            r#"{"error":{"type":"rate_limited","messages":[]}}"#,
+        ))
+        .mount(&server)
+        .await;
+    mount_asset_pages(&server).await;
+    Mock::given(method("DELETE"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let file = mp4_file(4096);
+    let options = UploadOptions {
+        force: true,
+        on_full: Some(CapacityPolicy::Fail),
+        ..Default::default()
+    };
+    let error = client_for(&server)
+        .upload_with_options(file.path().to_str().unwrap(), options, None)
+        .await
+        .unwrap_err();
+
+    let limit = error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<DomainError>())
+        .expect("capacity errors should surface as DomainError");
+    match limit {
+        DomainError::AssetLimitReached {
+            oldest_asset_id, ..
+        } => assert_eq!(oldest_asset_id.as_deref(), Some("asset_oldest")),
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+/// アセット一覧（2ページ、カーソルで次のページへ進む）のモックを登録
+async fn mount_asset_pages(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/video/v1/assets"))
+        .and(query_param("page", "1"))
+        .respond_with(json(200, fixture!("assets_page1.json")))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/video/v1/assets"))
+        .and(query_param("cursor", "cursor_page2"))
+        .respond_with(json(200, fixture!("assets_page2.json")))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_list_follows_cursor_pagination() {
+    let server = MockServer::start().await;
+    mount_asset_pages(&server).await;
+
+    let list = client_for(&server).list().await.unwrap();
+
+    let ids: Vec<&str> = list.videos.iter().map(|v| v.asset_id.as_str()).collect();
+    assert_eq!(ids, ["asset_newest", "asset_middle", "asset_oldest"]);
+    assert_eq!(list.total_count, 3);
+    assert_eq!(list.videos[0].title.as_deref(), Some("Newest clip"));
+    assert_eq!(
+        list.videos[0].hls_url.as_deref(),
+        Some("https://stream.mux.com/play_newest.m3u8")
+    );
+    assert_eq!(list.raw_assets.map(|assets| assets.len()), Some(3));
+}
+
+#[tokio::test]
+async fn test_count_assets_reads_total_from_single_page() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/video/v1/assets"))
+        .and(query_param("limit", "1"))
+        .respond_with(json(200, fixture!("assets_count.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let api = ApiClient::new(server.uri())
+        .unwrap()
+        .with_retry_policy(RetryPolicy::no_retry());
+    let count = api.count_assets("Basic dG9rZW4=").await.unwrap();
+
+    // 次のページ（cursor_page2）は取得しない
+    assert_eq!(count, Some(42));
+}
+
+#[tokio::test]
+async fn test_delete_asset() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/video/v1/assets/asset_middle"))
+        .and(header(
+            "Authorization",
+            "Basic dG9rZW5faWQ6dG9rZW5fc2VjcmV0",
+        ))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = client_for(&server).delete("asset_middle").await.unwrap();
+    assert_eq!(result.asset_id, "asset_middle");
+}
+
+#[tokio::test]
+async fn test_delete_missing_asset_is_not_found() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/video/v1/assets/asset_missing"))
+        .respond_with(json(404, fixture!("error_not_found.json")))
+        .mount(&server)
+        .await;
+
+    let error = client_for(&server)
+        .delete("asset_missing")
+        .await
+        .unwrap_err();
+
+    let infra = error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<InfraError>())
+        .expect("API errors should surface as InfraError");
+    assert_eq!(infra.code(), "NOT_FOUND");
+    match infra {
+        InfraError::Api {
+            error_type,
+            status_code,
+            ..
+        } => {
+            assert_eq!(error_type.as_deref(), Some("not_found"));
+            assert_eq!(*status_code, Some(404));
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+}